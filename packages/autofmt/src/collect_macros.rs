@@ -2,21 +2,41 @@
 //!
 //! Returns all macros that match a pattern. You can use this information to autoformat them later
 
-use proc_macro2::LineColumn;
-use syn::{visit::Visit, File, Macro};
+use proc_macro2::{LineColumn, TokenStream};
+use syn::{parse::Parser, punctuated::Punctuated, visit::Visit, Block, Expr, File, Macro, Token};
 
-type CollectedMacro<'a> = &'a Macro;
+type CollectedMacro = Macro;
 
-pub fn collect_from_file<'a>(file: &'a File, macros: &mut Vec<CollectedMacro<'a>>) {
+pub fn collect_from_file(file: &File, macros: &mut Vec<CollectedMacro>) {
     MacroCollector::visit_file(&mut MacroCollector { macros }, file);
 }
 
-struct MacroCollector<'a, 'b> {
-    macros: &'a mut Vec<CollectedMacro<'b>>,
+struct MacroCollector<'a> {
+    macros: &'a mut Vec<CollectedMacro>,
 }
 
-impl<'a, 'b> Visit<'b> for MacroCollector<'a, 'b> {
-    fn visit_macro(&mut self, i: &'b Macro) {
+impl MacroCollector<'_> {
+    /// Look for rsx in the body of another macro, like `assert_eq!(rsx! { .. }, ..)` or `cfg_if! { .. }`.
+    ///
+    /// Macro bodies are parsed as a list of expressions, then statements, then items. Bodies with custom syntax are
+    /// skipped.
+    fn visit_macro_body(&mut self, tokens: TokenStream) {
+        if let Ok(exprs) = Punctuated::<Expr, Token![,]>::parse_terminated.parse2(tokens.clone()) {
+            for expr in &exprs {
+                self.visit_expr(expr);
+            }
+        } else if let Ok(stmts) = Block::parse_within.parse2(tokens.clone()) {
+            for stmt in &stmts {
+                self.visit_stmt(stmt);
+            }
+        } else if let Ok(file) = syn::parse2::<File>(tokens) {
+            self.visit_file(&file);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for MacroCollector<'_> {
+    fn visit_macro(&mut self, i: &'ast Macro) {
         if let Some("rsx" | "render") = i
             .path
             .segments
@@ -24,7 +44,9 @@ impl<'a, 'b> Visit<'b> for MacroCollector<'a, 'b> {
             .map(|i| i.ident.to_string())
             .as_deref()
         {
-            self.macros.push(i)
+            self.macros.push(i.clone())
+        } else {
+            self.visit_macro_body(i.tokens.clone());
         }
     }
 }
@@ -50,3 +72,21 @@ fn parses_file_and_collects_rsx_macros() {
     collect_from_file(&parsed, &mut macros);
     assert_eq!(macros.len(), 3);
 }
+
+#[test]
+fn collects_rsx_nested_in_other_macros() {
+    let contents = r#"
+        fn app() {
+            assert_eq!(rsx! { div {} }, rsx! { span {} });
+            components! {
+                fn web() -> Element { rsx! { "web" } }
+            }
+            vec![rsx! { p {} }];
+            matches!(x, Some(_) | None);
+        }
+    "#;
+    let parsed = syn::parse_file(contents).unwrap();
+    let mut macros = vec![];
+    collect_from_file(&parsed, &mut macros);
+    assert_eq!(macros.len(), 4);
+}
@@ -2,7 +2,7 @@ use crate::{ifmt_to_string, prettier_please::unparse_expr, writer::Location, Wri
 use dioxus_rsx::*;
 use quote::ToTokens;
 use std::fmt::{Result, Write};
-use syn::{spanned::Spanned, AngleBracketedGenericArguments};
+use syn::{spanned::Spanned, token::Brace, AngleBracketedGenericArguments};
 
 #[derive(Debug)]
 enum ShortOptimization {
@@ -29,6 +29,7 @@ impl Writer<'_> {
             manual_props,
             prop_gen_args,
             key,
+            brace,
             ..
         }: &Component,
     ) -> Result {
@@ -40,7 +41,7 @@ impl Writer<'_> {
 
         // check if we have a lot of attributes
         let attr_len = self.field_len(fields, manual_props) + self.key_len(key.as_ref());
-        let is_short_attr_list = attr_len < 80;
+        let is_short_attr_list = attr_len < self.out.indent.attribute_width();
         let is_small_children = self.is_short_children(children).is_some();
 
         // if we have few attributes and a lot of children, place the attrs on top
@@ -63,7 +64,8 @@ impl Writer<'_> {
         }
 
         // If there's nothing at all, empty optimization
-        if fields.is_empty() && children.is_empty() && manual_props.is_none() && key.is_none() {
+        let has_props = !fields.is_empty() || manual_props.is_some() || key.is_some();
+        if !has_props && children.is_empty() {
             opt_level = ShortOptimization::Empty;
         }
 
@@ -86,9 +88,9 @@ impl Writer<'_> {
             ShortOptimization::Oneliner => {
                 write!(self.out, " ")?;
 
-                self.write_component_fields(fields, key.as_ref(), manual_props, true)?;
+                self.write_component_fields(brace, fields, key.as_ref(), manual_props, true)?;
 
-                if !children.is_empty() && !fields.is_empty() {
+                if !children.is_empty() && has_props {
                     write!(self.out, ", ")?;
                 }
 
@@ -103,10 +105,12 @@ impl Writer<'_> {
             }
 
             ShortOptimization::PropsOnTop => {
-                write!(self.out, " ")?;
-                self.write_component_fields(fields, key.as_ref(), manual_props, true)?;
+                if has_props {
+                    write!(self.out, " ")?;
+                }
+                self.write_component_fields(brace, fields, key.as_ref(), manual_props, true)?;
 
-                if !children.is_empty() && !fields.is_empty() {
+                if !children.is_empty() && has_props {
                     write!(self.out, ",")?;
                 }
 
@@ -115,9 +119,9 @@ impl Writer<'_> {
             }
 
             ShortOptimization::NoOpt => {
-                self.write_component_fields(fields, key.as_ref(), manual_props, false)?;
+                self.write_component_fields(brace, fields, key.as_ref(), manual_props, false)?;
 
-                if !children.is_empty() && !fields.is_empty() {
+                if !children.is_empty() && has_props {
                     write!(self.out, ",")?;
                 }
 
@@ -154,6 +158,7 @@ impl Writer<'_> {
 
     fn write_component_fields(
         &mut self,
+        brace: &Option<Brace>,
         fields: &[ComponentField],
         key: Option<&IfmtInput>,
         manual_props: &Option<syn::Expr>,
@@ -174,12 +179,22 @@ impl Writer<'_> {
 
         while let Some(field) = field_iter.next() {
             if !sameline {
+                if let Some(brace) = brace {
+                    self.out.indent_level += 1;
+                    self.write_attr_comments(brace, field.name.span())?;
+                    self.out.indent_level -= 1;
+                }
                 self.out.indented_tabbed_line().unwrap();
             }
 
-            let name = &field.name;
+            let name = match field.optional {
+                true => format!("{}?", field.name),
+                false => field.name.to_string(),
+            };
             match &field.content {
-                ContentField::ManExpr(_exp) if field.can_be_shorthand() => {
+                ContentField::ManExpr(_exp)
+                    if self.out.indent.collapse_shorthands() && field.can_be_shorthand() =>
+                {
                     write!(self.out, "{name}")?;
                 }
 
@@ -203,8 +218,11 @@ impl Writer<'_> {
                         s.source.as_ref().unwrap().to_token_stream()
                     )?;
                 }
+                ContentField::Shorthand(_) if field.can_be_shorthand() => {
+                    write!(self.out, "{name}")?;
+                }
                 ContentField::Shorthand(e) => {
-                    write!(self.out, "{}", e.to_token_stream())?;
+                    write!(self.out, "{} as {name}", e.to_token_stream())?;
                 }
             }
 
@@ -232,11 +250,20 @@ impl Writer<'_> {
         fields: &[ComponentField],
         manual_props: &Option<syn::Expr>,
     ) -> usize {
+        // Fields with comments above them need their own line
+        if fields
+            .iter()
+            .any(|field| self.has_leading_comments(field.name.span()))
+        {
+            return 100000;
+        }
+
         let attr_len = fields
             .iter()
             .map(|field| match &field.content {
                 ContentField::Formatted(s) => ifmt_to_string(s).len() ,
-                ContentField::Shorthand(e) => e.to_token_stream().to_string().len(),
+                ContentField::Shorthand(e) if field.can_be_shorthand() => e.to_token_stream().to_string().len(),
+                ContentField::Shorthand(e) => e.to_token_stream().to_string().len() + field.name.to_string().len() + 4,
                 ContentField::ManExpr(exp) => {
                     let formatted = unparse_expr(exp);
                     let len = if formatted.contains('\n') {
@@ -253,7 +280,7 @@ impl Writer<'_> {
         match manual_props {
             Some(p) => {
                 let content = unparse_expr(p);
-                if content.len() + attr_len > 80 {
+                if content.len() + attr_len > self.out.indent.attribute_width() {
                     return 100000;
                 }
                 let mut lines = content.lines();
@@ -0,0 +1,191 @@
+//! Format rsx inside the rust code blocks of doc comments
+//!
+//! Doc comments aren't tokens rsx can be collected from, so the code blocks are pulled out of the comment as text,
+//! formatted like a normal file, and the edits are mapped back into the comment.
+
+use crate::{collect_macros, fmt_file, FormattedBlock, IndentOptions};
+use dioxus_rsx::CallBody;
+
+/// One line of a code block in a doc comment
+struct CodeLine {
+    /// The offset of the line in the extracted code
+    code_offset: usize,
+    /// The offset of the same text in the original file, after the `/// ` prefix
+    file_offset: usize,
+    /// The `/// ` prefix of the line, including its indentation
+    prefix: String,
+    /// Lines starting with `# ` are hidden in the rendered docs. We leave them alone
+    hidden: bool,
+}
+
+/// Format the rsx in every rust code block of the `///` and `//!` comments in a file
+pub(crate) fn fmt_doc_comments(contents: &str, indent: &IndentOptions) -> Vec<FormattedBlock> {
+    let mut blocks = Vec::new();
+
+    let mut code = String::new();
+    let mut lines = Vec::new();
+    let mut in_rust_block = false;
+    let mut in_other_block = false;
+
+    let mut offset = 0;
+    for line in contents.split_inclusive('\n') {
+        let line_offset = offset;
+        offset += line.len();
+
+        let Some((prefix, text)) = split_doc_prefix(line) else {
+            // The comment ended, so any open code block ended with it
+            if in_rust_block {
+                blocks.extend(fmt_code_block(&code, &lines, indent));
+            }
+            in_rust_block = false;
+            in_other_block = false;
+            continue;
+        };
+
+        let fence = text.trim().strip_prefix("```");
+        match fence {
+            Some(lang) if !in_rust_block && !in_other_block => {
+                in_rust_block = is_rust_block(lang);
+                in_other_block = !in_rust_block;
+                code.clear();
+                lines.clear();
+            }
+            Some(_) => {
+                if in_rust_block {
+                    blocks.extend(fmt_code_block(&code, &lines, indent));
+                }
+                in_rust_block = false;
+                in_other_block = false;
+            }
+            None if in_rust_block => {
+                let trimmed = text.trim_start();
+                let hidden = trimmed.starts_with("# ") || trimmed.trim_end() == "#";
+                lines.push(CodeLine {
+                    code_offset: code.len(),
+                    file_offset: line_offset + prefix.len(),
+                    prefix: prefix.to_string(),
+                    hidden,
+                });
+                // Hidden lines are still part of the code, so parse them without the `#`
+                match hidden {
+                    true => code.push_str(&text.replacen('#', " ", 1)),
+                    false => code.push_str(text),
+                }
+            }
+            None => {}
+        }
+    }
+
+    if in_rust_block {
+        blocks.extend(fmt_code_block(&code, &lines, indent));
+    }
+
+    blocks
+}
+
+/// Split a doc comment line into its `/// ` prefix and the text after it
+fn split_doc_prefix(line: &str) -> Option<(&str, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let marker = if rest.starts_with("///") && !rest.starts_with("////") {
+        "///"
+    } else if rest.starts_with("//!") {
+        "//!"
+    } else {
+        return None;
+    };
+
+    let mut prefix_len = indent + marker.len();
+    if line[prefix_len..].starts_with(' ') {
+        prefix_len += 1;
+    }
+
+    Some(line.split_at(prefix_len))
+}
+
+/// Check if the language of a code fence is rust. Rustdoc treats blocks without a language as rust
+fn is_rust_block(lang: &str) -> bool {
+    lang.split(',').map(str::trim).all(|attr| {
+        matches!(
+            attr,
+            "" | "rust"
+                | "ignore"
+                | "no_run"
+                | "should_panic"
+                | "compile_fail"
+                | "test_harness"
+                | "standalone_crate"
+        ) || attr.starts_with("edition")
+    })
+}
+
+fn fmt_code_block(code: &str, lines: &[CodeLine], indent: &IndentOptions) -> Vec<FormattedBlock> {
+    // Doc examples are often statements without a main function around them
+    const MAIN: &str = "fn main() {\n";
+    let (wrapped, wrapper_len) = match syn::parse_file(code) {
+        Ok(_) => (code.to_string(), 0),
+        Err(_) => (format!("{MAIN}{code}\n}}"), MAIN.len()),
+    };
+
+    // Examples with placeholders like `...` aren't valid rsx, so leave them alone instead of failing the whole file
+    let Ok(file) = syn::parse_file(&wrapped) else {
+        return Vec::new();
+    };
+    let mut macros = vec![];
+    collect_macros::collect_from_file(&file, &mut macros);
+    if macros
+        .iter()
+        .any(|item| item.parse_body_with(CallBody::parse_strict).is_err())
+    {
+        return Vec::new();
+    }
+
+    let line_at = |offset: usize| {
+        lines
+            .iter()
+            .rposition(|line| line.code_offset <= offset)
+            .unwrap_or_default()
+    };
+
+    let mut blocks = Vec::new();
+    for block in fmt_file(&wrapped, indent.clone()) {
+        let (Some(start), Some(end)) = (
+            block.start.checked_sub(wrapper_len),
+            block.end.checked_sub(wrapper_len),
+        ) else {
+            continue;
+        };
+        if end > code.len() || start >= end || lines.is_empty() {
+            continue;
+        }
+
+        // Map the last character of the block instead of the end so the end never lands on the next line's prefix
+        let (first, last) = (line_at(start), line_at(end - 1));
+        if lines[first..=last].iter().any(|line| line.hidden) {
+            continue;
+        }
+
+        // The last line is followed by the rest of the original line, so it always keeps the whole prefix
+        let prefix = &lines[first].prefix;
+        let line_count = block.formatted.split('\n').count();
+        let formatted = block
+            .formatted
+            .split('\n')
+            .enumerate()
+            .map(|(idx, line)| match idx {
+                0 => line.to_string(),
+                _ if line.is_empty() && idx + 1 < line_count => prefix.trim_end().to_string(),
+                _ => format!("{prefix}{line}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        blocks.push(FormattedBlock {
+            formatted,
+            start: lines[first].file_offset + start - lines[first].code_offset,
+            end: lines[last].file_offset + end - lines[last].code_offset,
+        });
+    }
+
+    blocks
+}
@@ -1,4 +1,4 @@
-use crate::{ifmt_to_string, prettier_please::unparse_expr, Writer};
+use crate::{ifmt_to_string, prettier_please::unparse_expr, SingleChildLayout, Writer};
 use dioxus_rsx::*;
 use proc_macro2::Span;
 use quote::ToTokens;
@@ -56,6 +56,14 @@ impl Writer<'_> {
             .as_ref()
             .expect("braces should always be present in strict mode");
 
+        let sorted;
+        let attributes = if self.out.indent.sort_attributes() {
+            sorted = sorted_attributes(attributes);
+            &sorted
+        } else {
+            attributes
+        };
+
         /*
             1. Write the tag
             2. Write the key
@@ -71,7 +79,8 @@ impl Writer<'_> {
 
         // check if we have a lot of attributes
         let attr_len = self.is_short_attrs(attributes);
-        let is_short_attr_list = (attr_len + self.out.indent_level * 4) < 80;
+        let indent_len = self.out.indent_level * self.out.indent.indent_width();
+        let is_short_attr_list = (attr_len + indent_len) < self.out.indent.attribute_width();
         let children_len = self.is_short_children(children);
         let is_small_children = children_len.is_some();
 
@@ -92,7 +101,7 @@ impl Writer<'_> {
 
         // if we have few children and few attributes, make it a one-liner
         if is_short_attr_list && is_small_children {
-            if children_len.unwrap() + attr_len + self.out.indent_level * 4 < 100 {
+            if children_len.unwrap() + attr_len + indent_len < self.out.indent.max_width() {
                 opt_level = ShortOptimization::Oneliner;
             } else {
                 opt_level = ShortOptimization::PropsOnTop;
@@ -226,6 +235,9 @@ impl Writer<'_> {
             ElementAttrName::Custom(name) => {
                 write!(self.out, "{}", name.to_token_stream())?;
             }
+            ElementAttrName::Hyphenated(name) => {
+                write!(self.out, "{}", name.value())?;
+            }
         }
 
         Ok(())
@@ -303,6 +315,7 @@ impl Writer<'_> {
         match attr {
             AttributeType::Named(attr) => self.write_named_attribute(attr),
             AttributeType::Spread(attr) => self.write_spread_attribute(attr),
+            AttributeType::Conditional(group) => self.write_conditional_attributes(group),
         }
     }
 
@@ -310,7 +323,9 @@ impl Writer<'_> {
         self.write_attribute_name(&attr.attr.name)?;
 
         // if the attribute is a shorthand, we don't need to write the colon, just the name
-        if !attr.attr.can_be_shorthand() {
+        let shorthand = attr.attr.value.is_shorthand()
+            || (self.out.indent.collapse_shorthands() && attr.attr.can_be_shorthand());
+        if !shorthand {
             write!(self.out, ": ")?;
             self.write_attribute_value(&attr.attr.value)?;
         }
@@ -318,6 +333,21 @@ impl Writer<'_> {
         Ok(())
     }
 
+    fn write_conditional_attributes(&mut self, group: &ConditionalAttributes) -> Result {
+        write!(self.out, "if {} {{ ", unparse_expr(&group.condition))?;
+
+        for (idx, attr) in group.attributes.iter().enumerate() {
+            if idx != 0 {
+                write!(self.out, ", ")?;
+            }
+            self.write_named_attribute(attr)?;
+        }
+
+        write!(self.out, " }}")?;
+
+        Ok(())
+    }
+
     fn write_spread_attribute(&mut self, attr: &Expr) -> Result {
         write!(self.out, "..")?;
         write!(self.out, "{}", unparse_expr(attr))?;
@@ -342,7 +372,7 @@ impl Writer<'_> {
     }
 
     // check if the children are short enough to be on the same line
-    // We don't have the notion of current line depth - each line tries to be < 80 total
+    // We don't have the notion of current line depth - each line tries to be shorter than the attribute width
     // returns the total line length if it's short
     // returns none if the length exceeds the limit
     // I think this eventually becomes quadratic :(
@@ -362,6 +392,11 @@ impl Writer<'_> {
             return None;
         }
 
+        let single_child = self.out.indent.single_child();
+        if single_child == SingleChildLayout::Never {
+            return None;
+        }
+
         match children {
             [BodyNode::Text(ref text)] => Some(ifmt_to_string(text).len()),
 
@@ -377,6 +412,21 @@ impl Writer<'_> {
                     .sum::<usize>(),
             ),
 
+            // A nested element only stays inline if it would be written on one line itself
+            [BodyNode::Element(ref el)] if single_child == SingleChildLayout::Elements => {
+                if el.key.is_some()
+                    || (!el.attributes.is_empty() && self.out.indent.split_line_attributes())
+                {
+                    return None;
+                }
+                let attr_len = self.is_short_attrs(&el.attributes);
+                if attr_len > self.out.indent.attribute_width() {
+                    return None;
+                }
+                let children_len = self.is_short_children(&el.children)?;
+                Some(el.name.to_string().len() + attr_len + children_len + 6)
+            }
+
             // Feedback on discord indicates folks don't like combining multiple children on the same line
             // We used to do a lot of math to figure out if we should expand out the line, but folks just
             // don't like it.
@@ -385,19 +435,10 @@ impl Writer<'_> {
     }
 
     fn children_have_comments(&self, children: &[BodyNode]) -> bool {
-        for child in children {
-            if self.current_span_is_primary(child.span()) {
-                'line: for line in self.src[..child.span().start().line - 1].iter().rev() {
-                    match (line.trim().starts_with("//"), line.is_empty()) {
-                        (true, _) => return true,
-                        (_, true) => continue 'line,
-                        _ => break 'line,
-                    }
-                }
-            }
-        }
-
-        false
+        children
+            .iter()
+            .any(|child| self.has_leading_comments(child.span()))
+            || !self.trailing_comments(children).is_empty()
     }
 
     /// empty everything except for some comments
@@ -430,6 +471,35 @@ impl Writer<'_> {
     }
 }
 
+/// Order attributes by their kind and name. The sort is stable so event handlers, spreads and repeated
+/// attributes keep their relative order.
+pub(crate) fn sorted_attributes(attributes: &[AttributeType]) -> Vec<AttributeType> {
+    let mut sorted = attributes.to_vec();
+    sorted.sort_by_cached_key(|attr| match attr {
+        AttributeType::Named(named) => named_attribute_order(&named.attr),
+        AttributeType::Conditional(group) => group
+            .attributes
+            .first()
+            .map(|named| named_attribute_order(&named.attr))
+            .unwrap_or_default(),
+        AttributeType::Spread(_) => (4, String::new()),
+    });
+    sorted
+}
+
+fn named_attribute_order(attr: &ElementAttr) -> (u8, String) {
+    if let ElementAttrValue::EventTokens(_) = attr.value {
+        return (3, String::new());
+    }
+
+    let name = attr.name.to_string();
+    match name.trim_start_matches("r#") {
+        "id" => (0, String::new()),
+        "class" => (1, String::new()),
+        name => (2, name.to_string()),
+    }
+}
+
 fn get_expr_length(expr: &impl Spanned) -> Option<usize> {
     let span = expr.span();
     let (start, end) = (span.start(), span.end());
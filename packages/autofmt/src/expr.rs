@@ -1,11 +1,96 @@
 //! pretty printer for rsx!
 use std::fmt::{Result, Write};
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{spanned::Spanned, Expr};
 
 use crate::{collect_macros::byte_offset, Writer};
 
 impl Writer<'_> {
+    /// Write a `{ expr }` node in the body of an rsx block
+    ///
+    /// Expressions written on one line that don't fit in the max width are reformatted with prettyplease. Anything
+    /// else is kept the way it was written.
+    pub fn write_raw_expr_node(&mut self, expr: &TokenStream2) -> Result {
+        match self.overflowing_raw_expr(expr) {
+            Some(parsed) => self.write_unparsed_expr(&parsed),
+            None => self.write_raw_expr(expr.span()),
+        }
+    }
+
+    /// Parse a raw expression if it sits on one line that is too long to fit
+    fn overflowing_raw_expr(&self, expr: &TokenStream2) -> Option<Expr> {
+        let span = expr.span();
+        let (start, end) = (span.start(), span.end());
+        if start.line != end.line {
+            return None;
+        }
+
+        let row =
+            self.raw_src[byte_offset(self.raw_src, start)..byte_offset(self.raw_src, end)].trim();
+        let current_line = self.out.buf.rsplit('\n').next().unwrap_or_default();
+        let indent = &self.out.indent;
+        if indent.line_length(current_line) + indent.line_length(row) <= indent.max_width() {
+            return None;
+        }
+
+        // prettyplease drops comments, so leave expressions with comments alone
+        if row.contains("//") || row.contains("/*") {
+            return None;
+        }
+
+        syn::parse2(expr.clone()).ok()
+    }
+
+    /// Write an expression with prettyplease, shifting its lines to the current indent level
+    fn write_unparsed_expr(&mut self, expr: &Expr) -> Result {
+        // `{ expr }` blocks are split into their braces and the expression indented inside them
+        let inner = match expr {
+            Expr::Block(block) if block.attrs.is_empty() && block.label.is_none() => {
+                match block.block.stmts.as_slice() {
+                    [syn::Stmt::Expr(inner, None)] => Some(inner),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some(inner) = inner else {
+            let depth = self.out.indent_level * self.out.indent.indent_width() / 4;
+            let unparsed = self.unparse_expr_at(expr, depth.saturating_sub(1));
+            return self.write_unparsed_lines(&unparsed, self.out.indent_level);
+        };
+
+        let depth = (self.out.indent_level + 1) * self.out.indent.indent_width() / 4;
+        let unparsed = self.unparse_expr_at(inner, depth.saturating_sub(1));
+        write!(self.out, "{{")?;
+        self.out.indented_tabbed_line()?;
+        self.write_unparsed_lines(&unparsed, self.out.indent_level + 1)?;
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")
+    }
+
+    /// Write the lines of an expression from prettyplease, continuing the current line
+    fn write_unparsed_lines(&mut self, unparsed: &str, indent_level: usize) -> Result {
+        let mut lines = unparsed.lines();
+
+        if let Some(first_line) = lines.next() {
+            write!(self.out, "{first_line}")?;
+        }
+
+        for line in lines {
+            writeln!(self.out)?;
+
+            // prettyplease always indents with four spaces
+            let trimmed = line.trim_start_matches(' ');
+            let spaces = line.len() - trimmed.len();
+            self.out.write_tabs(indent_level + spaces / 4)?;
+            write!(self.out, "{}{trimmed}", " ".repeat(spaces % 4))?;
+        }
+
+        Ok(())
+    }
+
     pub fn write_raw_expr(&mut self, placement: Span) -> Result {
         /*
         We want to normalize the expr to the appropriate indent level.
@@ -4,11 +4,54 @@ pub enum IndentType {
     Tabs,
 }
 
+/// Which single children an element or component is collapsed onto one line with, like rustfmt's `fn_single_line`.
+///
+/// Blocks are only collapsed if the whole line fits in the max width.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SingleChildLayout {
+    /// Always put children on their own lines
+    Never,
+    /// Collapse a single text node, expression or component without props, like `div { "hi" }`
+    #[default]
+    Text,
+    /// Also collapse a single element that fits on one line itself, like `li { a { href: "/", "Home" } }`
+    Elements,
+}
+
+impl std::str::FromStr for SingleChildLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "text" => Ok(Self::Text),
+            "elements" => Ok(Self::Elements),
+            _ => Err(format!(
+                "unknown single child layout `{s}`, expected one of `never`, `text` or `elements`"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SingleChildLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Never => write!(f, "never"),
+            Self::Text => write!(f, "text"),
+            Self::Elements => write!(f, "elements"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IndentOptions {
     width: usize,
     indent_string: String,
     split_line_attributes: bool,
+    max_width: usize,
+    sort_attributes: bool,
+    single_child: SingleChildLayout,
+    collapse_shorthands: bool,
 }
 
 impl IndentOptions {
@@ -21,9 +64,69 @@ impl IndentOptions {
                 IndentType::Spaces => " ".repeat(width),
             },
             split_line_attributes,
+            max_width: 100,
+            sort_attributes: false,
+            single_child: SingleChildLayout::default(),
+            collapse_shorthands: false,
         }
     }
 
+    /// Set the maximum width of a line, like the `max_width` option of rustfmt. Defaults to 100.
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Write the attributes of elements in a consistent order: id and class first, then the other attributes
+    /// alphabetically, then event handlers and finally spreads. Keys always come first. Off by default.
+    pub fn with_sorted_attributes(mut self, sort_attributes: bool) -> Self {
+        self.sort_attributes = sort_attributes;
+        self
+    }
+
+    /// Set which single children blocks are collapsed onto one line with. Defaults to [`SingleChildLayout::Text`].
+    pub fn with_single_child(mut self, single_child: SingleChildLayout) -> Self {
+        self.single_child = single_child;
+        self
+    }
+
+    /// Write attributes and props that are set to a variable with the same name, like `class: class`, with the
+    /// shorthand `class`. Off by default.
+    pub fn with_collapsed_shorthands(mut self, collapse_shorthands: bool) -> Self {
+        self.collapse_shorthands = collapse_shorthands;
+        self
+    }
+
+    /// Whether attributes and props set to a variable with the same name are written with the shorthand
+    pub fn collapse_shorthands(&self) -> bool {
+        self.collapse_shorthands
+    }
+
+    /// Which single children blocks are collapsed onto one line with
+    pub fn single_child(&self) -> SingleChildLayout {
+        self.single_child
+    }
+
+    /// Whether the attributes of elements are sorted
+    pub fn sort_attributes(&self) -> bool {
+        self.sort_attributes
+    }
+
+    /// The maximum width of a line. Elements are only collapsed onto one line if they fit in this width
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    /// The width of attributes and props before they are split onto their own lines
+    pub(crate) fn attribute_width(&self) -> usize {
+        self.max_width * 4 / 5
+    }
+
+    /// The width of one indent, counting tabs as the indent width
+    pub fn indent_width(&self) -> usize {
+        self.width
+    }
+
     /// Gets a string containing one indent worth of whitespace
     pub fn indent_str(&self) -> &str {
         &self.indent_string
@@ -9,18 +9,21 @@ use collect_macros::byte_offset;
 use dioxus_rsx::{BodyNode, CallBody, IfmtInput};
 use proc_macro2::LineColumn;
 use quote::ToTokens;
-use syn::{parse::Parser, ExprMacro, MacroDelimiter};
+use syn::{parse::Parser, punctuated::Punctuated, ExprMacro, Ident, Macro, MacroDelimiter, Token};
 
 mod buffer;
 mod collect_macros;
 mod component;
+mod doc_comments;
 mod element;
 mod expr;
 mod indent;
 mod prettier_please;
+mod roundtrip;
 mod writer;
 
-pub use indent::{IndentOptions, IndentType};
+pub use indent::{IndentOptions, IndentType, SingleChildLayout};
+pub use roundtrip::{verify_roundtrip, verify_roundtrip_with, RoundtripError};
 
 /// A modification to the original file to be applied by an IDE
 ///
@@ -50,12 +53,13 @@ pub struct FormattedBlock {
 /// The point here is to provide precise modifications of a source file so an accompanying IDE tool can map these changes
 /// back to the file precisely.
 ///
-/// Nested blocks of RSX will be handled automatically
+/// Nested blocks of RSX will be handled automatically, including rsx inside other macros and in the rust code blocks
+/// of doc comments.
 pub fn fmt_file(contents: &str, indent: IndentOptions) -> Vec<FormattedBlock> {
-    let mut formatted_blocks = Vec::new();
-
     let parsed = syn::parse_file(contents).unwrap();
 
+    let mut formatted_blocks = doc_comments::fmt_doc_comments(contents, &indent);
+
     let mut macros = vec![];
     collect_macros::collect_from_file(&parsed, &mut macros);
 
@@ -69,7 +73,7 @@ pub fn fmt_file(contents: &str, indent: IndentOptions) -> Vec<FormattedBlock> {
 
     // Don't parse nested macros
     let mut end_span = LineColumn { column: 0, line: 0 };
-    for item in macros {
+    for item in &macros {
         let macro_path = &item.path.segments[0].ident;
 
         // this macro is inside the last macro we parsed, skip it
@@ -77,59 +81,259 @@ pub fn fmt_file(contents: &str, indent: IndentOptions) -> Vec<FormattedBlock> {
             continue;
         }
 
-        let body = item.parse_body_with(CallBody::parse_strict).unwrap();
+        end_span = macro_body_span(item).end();
+
+        formatted_blocks.extend(fmt_macro(&mut writer, contents, item));
+    }
+
+    // Blocks from doc comments and code are collected separately, but edits are applied in order
+    formatted_blocks.sort_by_key(|block| block.start);
 
-        let rsx_start = macro_path.span().start();
+    formatted_blocks
+}
 
-        writer.out.indent_level = writer
-            .out
-            .indent
-            .count_indents(writer.src[rsx_start.line - 1]);
+/// A single change that formats part of a file, as reported by [`fmt_file_checked`]
+///
+/// Unlike [`FormattedBlock`], edits only cover the lines of an rsx! block that are not formatted, so they can be
+/// applied as minimal diffs or reported as the precise location of unformatted rsx.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormatEdit {
+    /// The byte range of the original file to replace
+    pub span: std::ops::Range<usize>,
 
-        write_body(&mut writer, &body);
+    /// The line the edit starts on, starting at 1
+    pub line: usize,
 
-        // writing idents leaves the final line ended at the end of the last ident
-        if writer.out.buf.contains('\n') {
-            writer.out.new_line().unwrap();
-            writer.out.tab().unwrap();
-        }
+    /// The column the edit starts at in characters, starting at 0
+    pub column: usize,
 
-        let span = match item.delimiter {
-            MacroDelimiter::Paren(b) => b.span,
-            MacroDelimiter::Brace(b) => b.span,
-            MacroDelimiter::Bracket(b) => b.span,
+    /// The text to replace the span with
+    pub replacement: String,
+}
+
+/// Check if the rsx in a file is formatted, returning the edits that would format it.
+///
+/// This is meant for editor integrations and pre-commit hooks. An empty list means the file is already formatted.
+/// Returns an error instead of panicking if the file doesn't parse.
+pub fn fmt_file_checked(contents: &str, indent: IndentOptions) -> syn::Result<Vec<FormatEdit>> {
+    syn::parse_file(contents)?;
+
+    let mut edits = Vec::new();
+    for block in fmt_file(contents, indent) {
+        let original = &contents[block.start..block.end];
+        for (range, replacement) in diff_lines(original, &block.formatted) {
+            let start = block.start + range.start;
+            let (line, column) = line_column(contents, start);
+            edits.push(FormatEdit {
+                span: start..block.start + range.end,
+                line,
+                column,
+                replacement: replacement.to_string(),
+            });
         }
-        .join();
+    }
 
-        let mut formatted = String::new();
+    Ok(edits)
+}
 
-        std::mem::swap(&mut formatted, &mut writer.out.buf);
+/// Apply the edits from [`fmt_file_checked`] to the file they were created from
+pub fn apply_edits(input: &str, edits: &[FormatEdit]) -> String {
+    let mut out = String::new();
 
-        let start = byte_offset(contents, span.start()) + 1;
-        let end = byte_offset(contents, span.end()) - 1;
+    let mut last = 0;
 
-        // Rustfmt will remove the space between the macro and the opening paren if the macro is a single expression
-        let body_is_solo_expr = body.roots.len() == 1
-            && matches!(body.roots[0], BodyNode::RawExpr(_) | BodyNode::Text(_));
+    for edit in edits {
+        out.push_str(&input[last..edit.span.start]);
+        out.push_str(&edit.replacement);
+        last = edit.span.end;
+    }
 
-        if formatted.len() <= 80 && !formatted.contains('\n') && !body_is_solo_expr {
-            formatted = format!(" {formatted} ");
-        }
+    out.push_str(&input[last..]);
 
-        end_span = span.end();
+    out
+}
+
+/// Find the changed runs of lines between two strings. Returns the byte range in `old` and the text from `new` that
+/// replaces it, trimmed down to the characters that actually changed.
+fn diff_lines<'a>(old: &str, new: &'a str) -> Vec<(std::ops::Range<usize>, &'a str)> {
+    let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+
+    // The length of the longest common subsequence of the lines after each pair of lines
+    let mut lcs = vec![vec![0; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lcs[i][j] = match old_lines[i] == new_lines[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
 
-        if contents[start..end] == formatted {
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let (mut old_offset, mut new_offset) = (0, 0);
+    while i < old_lines.len() || j < new_lines.len() {
+        if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+            old_offset += old_lines[i].len();
+            new_offset += new_lines[j].len();
+            i += 1;
+            j += 1;
             continue;
         }
 
-        formatted_blocks.push(FormattedBlock {
-            formatted,
-            start,
-            end,
-        });
+        // Collect the run of lines that differ
+        let (old_start, new_start) = (old_offset, new_offset);
+        while i < old_lines.len() || j < new_lines.len() {
+            if i < old_lines.len() && j < new_lines.len() && old_lines[i] == new_lines[j] {
+                break;
+            }
+            if j < new_lines.len() && (i == old_lines.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+                new_offset += new_lines[j].len();
+                j += 1;
+            } else {
+                old_offset += old_lines[i].len();
+                i += 1;
+            }
+        }
+
+        let original = &old[old_start..old_offset];
+        let replacement = &new[new_start..new_offset];
+        let prefix = common_prefix_len(original.chars(), replacement.chars());
+        let suffix = common_prefix_len(
+            original[prefix..].chars().rev(),
+            replacement[prefix..].chars().rev(),
+        );
+        hunks.push((
+            old_start + prefix..old_offset - suffix,
+            &replacement[prefix..replacement.len() - suffix],
+        ));
     }
 
-    formatted_blocks
+    hunks
+}
+
+/// The line (starting at 1) and column (starting at 0) of a byte offset in a file
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count())
+}
+
+/// Format only the rsx! block that contains the byte `offset` of a file, leaving the rest of the file untouched.
+///
+/// This is meant for editor range formatting requests. The returned `FormattedBlock` only covers the part of the block
+/// that changed, so applying it disturbs as little of the file as possible. Returns `None` if the file or the rsx! block
+/// doesn't parse, there is no rsx! block at the offset, or the block is already formatted.
+pub fn fmt_block_at(
+    contents: &str,
+    offset: usize,
+    indent: IndentOptions,
+) -> Option<FormattedBlock> {
+    let parsed = syn::parse_file(contents).ok()?;
+
+    let mut macros = vec![];
+    collect_macros::collect_from_file(&parsed, &mut macros);
+
+    // Nested macros are formatted with the macro they are in, so find the outermost macro around the offset
+    let item = macros.iter().find(|item| {
+        let span = macro_body_span(item);
+        let start = byte_offset(contents, item.path.segments[0].ident.span().start());
+        let end = byte_offset(contents, span.end());
+        (start..end).contains(&offset)
+    })?;
+
+    let mut writer = Writer::new(contents);
+    writer.out.indent = indent;
+
+    let FormattedBlock {
+        formatted,
+        start,
+        end,
+    } = fmt_macro(&mut writer, contents, item)?;
+
+    // Trim the text that didn't change from both ends of the edit
+    let original = &contents[start..end];
+    let prefix = common_prefix_len(original.chars(), formatted.chars());
+    let suffix = common_prefix_len(
+        original[prefix..].chars().rev(),
+        formatted[prefix..].chars().rev(),
+    );
+
+    Some(FormattedBlock {
+        formatted: formatted[prefix..formatted.len() - suffix].to_string(),
+        start: start + prefix,
+        end: end - suffix,
+    })
+}
+
+/// The number of bytes at the start of two strings that are the same
+fn common_prefix_len(a: impl Iterator<Item = char>, b: impl Iterator<Item = char>) -> usize {
+    a.zip(b)
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum()
+}
+
+fn macro_body_span(item: &Macro) -> proc_macro2::Span {
+    match &item.delimiter {
+        MacroDelimiter::Paren(b) => b.span,
+        MacroDelimiter::Brace(b) => b.span,
+        MacroDelimiter::Bracket(b) => b.span,
+    }
+    .join()
+}
+
+/// Format the body of a single rsx! macro in a file. Returns `None` if it is already formatted or doesn't parse
+fn fmt_macro(writer: &mut Writer, contents: &str, item: &Macro) -> Option<FormattedBlock> {
+    // Files are often formatted while the rsx is half written, so a body that doesn't parse is left alone
+    let body = item.parse_body_with(CallBody::parse_strict).ok()?;
+
+    let rsx_start = item.path.segments[0].ident.span().start();
+
+    writer.out.indent_level = writer
+        .out
+        .indent
+        .count_indents(writer.src[rsx_start.line - 1]);
+
+    write_body(writer, &body);
+
+    // writing idents leaves the final line ended at the end of the last ident
+    if writer.out.buf.contains('\n') {
+        writer.out.new_line().unwrap();
+        writer.out.tab().unwrap();
+    }
+
+    let span = macro_body_span(item);
+
+    let mut formatted = String::new();
+
+    std::mem::swap(&mut formatted, &mut writer.out.buf);
+
+    let start = byte_offset(contents, span.start()) + 1;
+    let end = byte_offset(contents, span.end()) - 1;
+
+    // Rustfmt will remove the space between the macro and the opening paren if the macro is a single expression
+    let body_is_solo_expr =
+        body.roots.len() == 1 && matches!(body.roots[0], BodyNode::RawExpr(_) | BodyNode::Text(_));
+
+    if formatted.len() <= writer.out.indent.attribute_width()
+        && !formatted.contains('\n')
+        && !body_is_solo_expr
+    {
+        formatted = format!(" {formatted} ");
+    }
+
+    if contents[start..end] == formatted {
+        return None;
+    }
+
+    Some(FormattedBlock {
+        formatted,
+        start,
+        end,
+    })
 }
 
 pub fn write_block_out(body: CallBody) -> Option<String> {
@@ -141,6 +345,18 @@ pub fn write_block_out(body: CallBody) -> Option<String> {
 }
 
 fn write_body(buf: &mut Writer, body: &CallBody) {
+    // Lint attributes go on their own lines before the nodes
+    if !body.attrs.is_empty() {
+        buf.out.indent_level += 1;
+        for attr in &body.attrs {
+            buf.out.tabbed_line().unwrap();
+            write_lint_attribute(buf, attr);
+        }
+        buf.write_body_no_indent(&body.roots).unwrap();
+        buf.out.indent_level -= 1;
+        return;
+    }
+
     match body.roots.len() {
         0 => {}
         1 if matches!(body.roots[0], BodyNode::Text(_)) => {
@@ -152,6 +368,22 @@ fn write_body(buf: &mut Writer, body: &CallBody) {
     }
 }
 
+/// Write an attribute like `#![allow(key_outside_loop, RSX0003)]`. The rsx parser only accepts lint attributes
+fn write_lint_attribute(buf: &mut Writer, attr: &syn::Attribute) {
+    let lints = attr
+        .parse_args_with(Punctuated::<Ident, Token![,]>::parse_separated_nonempty)
+        .map(|lints| {
+            lints
+                .iter()
+                .map(|lint| lint.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+    let level = attr.path().to_token_stream();
+    write!(buf.out, "#![{level}({lints})]").unwrap();
+}
+
 pub fn fmt_block_from_expr(raw: &str, expr: ExprMacro) -> Option<String> {
     let body = CallBody::parse_strict.parse2(expr.mac.tokens).unwrap();
 
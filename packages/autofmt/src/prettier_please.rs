@@ -5,6 +5,11 @@ use crate::Writer;
 
 impl Writer<'_> {
     pub fn unparse_expr(&mut self, expr: &Expr) -> String {
+        self.unparse_expr_at(expr, 0)
+    }
+
+    /// Unparse an expression that will be written `depth` levels of four spaces deep, formatting any rsx! inside it
+    pub fn unparse_expr_at(&mut self, expr: &Expr, depth: usize) -> String {
         struct ReplaceMacros<'a, 'b> {
             writer: &'a mut Writer<'b>,
             formatted_stack: Vec<String>,
@@ -94,7 +99,7 @@ impl Writer<'_> {
         replacer.visit_expr_mut(&mut modified_expr);
 
         // now unparsed with the modified expression
-        let mut unparsed = unparse_expr(&modified_expr);
+        let mut unparsed = unparse_expr_at(&modified_expr, depth);
 
         // walk each line looking for the dioxus_autofmt_block__________ token
         // if we find it, replace it with the formatted block
@@ -146,20 +151,27 @@ impl Writer<'_> {
 /// This creates a new temporary file, parses the expression into it, and then formats the file.
 /// This is a bit of a hack, but dtonlay doesn't want to support this very simple usecase, forcing us to clone the expr
 pub fn unparse_expr(expr: &Expr) -> String {
-    let file = wrapped(expr);
+    unparse_expr_at(expr, 0)
+}
+
+/// Unparse an expression that will be written `depth` levels of four spaces deep
+///
+/// prettyplease doesn't let us set the starting indent, so we nest the expression in that many blocks to make it break
+/// lines at the same width it will end up at.
+pub fn unparse_expr_at(expr: &Expr, depth: usize) -> String {
+    let file = wrapped(expr, depth);
     let wrapped = unparse(&file);
-    unwrapped(wrapped)
+    unwrapped(wrapped, depth)
 }
 
-// Split off the fn main and then cut the tabs off the front
-fn unwrapped(raw: String) -> String {
-    let mut o = raw
-        .strip_prefix("fn main() {\n")
-        .unwrap()
-        .strip_suffix("}\n")
-        .unwrap()
-        .lines()
-        .map(|line| line.strip_prefix("    ").unwrap()) // todo: set this to tab level
+// Split off the fn main and the nested blocks and then cut the tabs off the front
+fn unwrapped(raw: String, depth: usize) -> String {
+    let indent = "    ".repeat(depth + 1);
+    let lines = raw.lines().collect::<Vec<_>>();
+
+    let mut o = lines[depth + 1..lines.len() - depth - 1]
+        .iter()
+        .map(|line| line.strip_prefix(indent.as_str()).unwrap_or(line))
         .collect::<Vec<_>>()
         .join("\n");
 
@@ -169,7 +181,12 @@ fn unwrapped(raw: String) -> String {
     o
 }
 
-fn wrapped(expr: &Expr) -> File {
+fn wrapped(expr: &Expr, depth: usize) -> File {
+    let mut body = quote::quote! { #expr; };
+    for _ in 0..depth {
+        body = quote::quote! { { #body } };
+    }
+
     File {
         shebang: None,
         attrs: vec![],
@@ -177,7 +194,7 @@ fn wrapped(expr: &Expr) -> File {
             //
             Item::Verbatim(quote::quote! {
                 fn main() {
-                    #expr;
+                    #body
                 }
             }),
         ],
@@ -187,10 +204,26 @@ fn wrapped(expr: &Expr) -> File {
 #[test]
 fn unparses_raw() {
     let expr = syn::parse_str("1 + 1").unwrap();
-    let unparsed = unparse(&wrapped(&expr));
+    let unparsed = unparse(&wrapped(&expr, 0));
     assert_eq!(unparsed, "fn main() {\n    1 + 1;\n}\n");
 }
 
+#[test]
+fn unparses_at_depth() {
+    let expr = syn::parse_str(
+        "items.iter().filter(|item| item.visible).map(|item| item.name.len()).sum::<usize>()",
+    )
+    .unwrap();
+    assert_eq!(
+        unparse_expr(&expr),
+        "items.iter().filter(|item| item.visible).map(|item| item.name.len()).sum::<usize>()"
+    );
+    assert_eq!(
+        unparse_expr_at(&expr, 5),
+        "items\n    .iter()\n    .filter(|item| item.visible)\n    .map(|item| item.name.len())\n    .sum::<usize>()"
+    );
+}
+
 #[test]
 fn unparses_completely() {
     let expr = syn::parse_str("1 + 1").unwrap();
@@ -0,0 +1,294 @@
+//! Check that formatting a file is safe to apply automatically
+
+use crate::{apply_formats, collect_macros, element::sorted_attributes, fmt_file, IndentOptions};
+use dioxus_rsx::{
+    AttributeType, BodyNode, CallBody, ContentField, ElementAttr, ElementAttrValue, IfChain,
+};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::fmt::Display;
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    Expr, ExprClosure, Macro, Stmt,
+};
+
+/// Why a file failed [`verify_roundtrip`]
+#[derive(Debug)]
+pub enum RoundtripError {
+    /// The original file or one of its rsx! blocks doesn't parse
+    Parse(syn::Error),
+
+    /// The formatted file or one of its rsx! blocks doesn't parse
+    Reparse {
+        formatted: String,
+        error: syn::Error,
+    },
+
+    /// The formatted file has a different number of rsx! blocks than the original
+    BlockCount { expected: usize, found: usize },
+
+    /// An rsx! block parses to a different structure after formatting
+    Changed {
+        /// The index of the block in the file
+        block: usize,
+        before: Vec<BodyNode>,
+        after: Vec<BodyNode>,
+    },
+
+    /// Formatting the formatted file changes it again
+    NotIdempotent { first: String, second: String },
+}
+
+impl Display for RoundtripError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "failed to parse the original file: {error}"),
+            Self::Reparse { error, .. } => write!(f, "failed to parse the formatted file: {error}"),
+            Self::BlockCount { expected, found } => write!(
+                f,
+                "expected {expected} rsx! blocks after formatting but found {found}"
+            ),
+            Self::Changed { block, .. } => {
+                write!(f, "rsx! block {block} changed structure after formatting")
+            }
+            Self::NotIdempotent { .. } => write!(f, "formatting the output again changed it"),
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {}
+
+/// Format a file, re-parse it, and check that every rsx! block has the same structure and that formatting the output
+/// again doesn't change it.
+///
+/// Returns the formatted file. Tools that apply formatting automatically can use this to make sure the formatter
+/// didn't change the meaning of the rsx, and the formatter's own tests use it to check that its output is stable.
+pub fn verify_roundtrip(src: &str) -> Result<String, RoundtripError> {
+    verify_roundtrip_with(src, IndentOptions::default())
+}
+
+/// [`verify_roundtrip`] with custom formatting options
+pub fn verify_roundtrip_with(src: &str, indent: IndentOptions) -> Result<String, RoundtripError> {
+    let before = parse_blocks(src, &indent).map_err(RoundtripError::Parse)?;
+
+    let formatted = apply_formats(src, fmt_file(src, indent.clone()));
+
+    let after = match parse_blocks(&formatted, &indent) {
+        Ok(after) => after,
+        Err(error) => return Err(RoundtripError::Reparse { formatted, error }),
+    };
+
+    if before.len() != after.len() {
+        return Err(RoundtripError::BlockCount {
+            expected: before.len(),
+            found: after.len(),
+        });
+    }
+
+    for (block, (before, after)) in before.into_iter().zip(after).enumerate() {
+        if before != after {
+            return Err(RoundtripError::Changed {
+                block,
+                before,
+                after,
+            });
+        }
+    }
+
+    let second = apply_formats(&formatted, fmt_file(&formatted, indent));
+    if second != formatted {
+        return Err(RoundtripError::NotIdempotent {
+            first: formatted,
+            second,
+        });
+    }
+
+    Ok(formatted)
+}
+
+/// Parse every rsx! block in a file, including blocks nested in expressions, in a normalized form
+fn parse_blocks(src: &str, indent: &IndentOptions) -> syn::Result<Vec<Vec<BodyNode>>> {
+    let file = syn::parse_file(src)?;
+
+    let mut macros = vec![];
+    collect_macros::collect_from_file(&file, &mut macros);
+
+    let mut normalizer = Normalizer {
+        sort_attributes: indent.sort_attributes(),
+        blocks: Vec::new(),
+    };
+    for item in &macros {
+        let body = item.parse_body_with(CallBody::parse_strict)?;
+        normalizer.push_block(body.roots);
+    }
+
+    Ok(normalizer.blocks)
+}
+
+/// Rewrites rsx into a canonical form so syntax the formatter changes on purpose compares as equal:
+/// - `name: name` is written as the shorthand `name`, which the formatter can collapse it into
+/// - closures with a single expression body are written without braces
+/// - attributes are sorted if the formatter sorts them
+///
+/// Nested rsx! blocks are moved into their own block so they are compared after they are normalized too.
+struct Normalizer {
+    sort_attributes: bool,
+    blocks: Vec<Vec<BodyNode>>,
+}
+
+impl Normalizer {
+    fn push_block(&mut self, roots: Vec<BodyNode>) {
+        // Reserve the slot first so blocks stay in the order they appear in
+        let idx = self.blocks.len();
+        self.blocks.push(Vec::new());
+        self.blocks[idx] = self.nodes(roots);
+    }
+
+    fn nodes(&mut self, nodes: Vec<BodyNode>) -> Vec<BodyNode> {
+        nodes.into_iter().map(|node| self.node(node)).collect()
+    }
+
+    fn node(&mut self, node: BodyNode) -> BodyNode {
+        match node {
+            BodyNode::Element(mut el) => {
+                el.attributes = self.attributes(el.attributes);
+                el.merged_attributes = self.attributes(el.merged_attributes);
+                el.children = self.nodes(el.children);
+                BodyNode::Element(el)
+            }
+            BodyNode::Component(mut comp) => {
+                for field in &mut comp.fields {
+                    if let ContentField::Shorthand(name) = &field.content {
+                        field.content = ContentField::ManExpr(parse_quote!(#name));
+                    }
+                    if let ContentField::ManExpr(expr) = &mut field.content {
+                        self.visit_expr_mut(expr);
+                    }
+                }
+                if let Some(props) = &mut comp.manual_props {
+                    self.visit_expr_mut(props);
+                }
+                comp.children = self.nodes(comp.children);
+                BodyNode::Component(comp)
+            }
+            BodyNode::ForLoop(mut for_loop) => {
+                self.visit_expr_mut(&mut for_loop.expr);
+                for_loop.body = self.nodes(for_loop.body);
+                BodyNode::ForLoop(for_loop)
+            }
+            BodyNode::WhileLoop(mut while_loop) => {
+                self.visit_expr_mut(&mut while_loop.cond);
+                while_loop.body = self.nodes(while_loop.body);
+                BodyNode::WhileLoop(while_loop)
+            }
+            BodyNode::Fragment(mut fragment) => {
+                fragment.children = self.nodes(fragment.children);
+                BodyNode::Fragment(fragment)
+            }
+            BodyNode::IfChain(chain) => BodyNode::IfChain(self.if_chain(chain)),
+            BodyNode::RawExpr(tokens) => match syn::parse2::<Expr>(tokens.clone()) {
+                Ok(mut expr) => {
+                    self.visit_expr_mut(&mut expr);
+                    BodyNode::RawExpr(expr.into_token_stream())
+                }
+                Err(_) => BodyNode::RawExpr(tokens),
+            },
+            node => node,
+        }
+    }
+
+    fn if_chain(&mut self, mut chain: IfChain) -> IfChain {
+        self.visit_expr_mut(&mut chain.cond);
+        chain.then_branch = self.nodes(chain.then_branch);
+        chain.else_if_branch = chain
+            .else_if_branch
+            .map(|chain| Box::new(self.if_chain(*chain)));
+        chain.else_branch = chain.else_branch.map(|nodes| self.nodes(nodes));
+        chain
+    }
+
+    fn attributes(&mut self, attributes: Vec<AttributeType>) -> Vec<AttributeType> {
+        let mut attributes = attributes
+            .into_iter()
+            .map(|attr| match attr {
+                AttributeType::Named(mut named) => {
+                    self.attribute(&mut named.attr);
+                    AttributeType::Named(named)
+                }
+                AttributeType::Conditional(mut group) => {
+                    self.visit_expr_mut(&mut group.condition);
+                    for named in &mut group.attributes {
+                        self.attribute(&mut named.attr);
+                    }
+                    AttributeType::Conditional(group)
+                }
+                AttributeType::Spread(mut expr) => {
+                    self.visit_expr_mut(&mut expr);
+                    AttributeType::Spread(expr)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if self.sort_attributes {
+            attributes = sorted_attributes(&attributes);
+        }
+
+        attributes
+    }
+
+    fn attribute(&mut self, attr: &mut ElementAttr) {
+        if let ElementAttrValue::Shorthand(name) = &attr.value {
+            attr.value = ElementAttrValue::AttrExpr(parse_quote!(#name));
+        }
+        self.attribute_value(&mut attr.value);
+    }
+
+    fn attribute_value(&mut self, value: &mut ElementAttrValue) {
+        match value {
+            ElementAttrValue::AttrExpr(expr) | ElementAttrValue::EventTokens(expr) => {
+                self.visit_expr_mut(expr)
+            }
+            ElementAttrValue::AttrOptionalExpr { condition, value } => {
+                self.visit_expr_mut(condition);
+                self.attribute_value(value);
+            }
+            ElementAttrValue::Shorthand(_) | ElementAttrValue::AttrLiteral(_) => {}
+        }
+    }
+}
+
+impl VisitMut for Normalizer {
+    fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+        if let Expr::Block(block) = &mut *closure.body {
+            if block.attrs.is_empty() && block.label.is_none() {
+                // Assignments are `()` with or without a trailing semicolon
+                match block.block.stmts.as_slice() {
+                    [Stmt::Expr(inner, None)] | [Stmt::Expr(inner @ Expr::Assign(_), Some(_))] => {
+                        *closure.body = inner.clone();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        visit_mut::visit_expr_closure_mut(self, closure);
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        let is_rsx = matches!(
+            mac.path
+                .segments
+                .last()
+                .map(|seg| seg.ident.to_string())
+                .as_deref(),
+            Some("rsx" | "render")
+        );
+        if !is_rsx {
+            return;
+        }
+        if let Ok(body) = mac.parse_body_with(CallBody::parse_strict) {
+            mac.tokens = TokenStream::new();
+            self.push_block(body.roots);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use dioxus_rsx::{AttributeType, BodyNode, ElementAttrValue, ForLoop, IfChain, IfmtInput};
+use dioxus_rsx::{
+    AttributeType, BodyNode, ElementAttrValue, ForLoop, Fragment, IfChain, IfmtInput, WhileLoop,
+};
 use proc_macro2::{LineColumn, Span};
 use quote::ToTokens;
 use std::{
@@ -51,9 +53,11 @@ impl<'a> Writer<'a> {
             BodyNode::Element(el) => self.write_element(el),
             BodyNode::Component(component) => self.write_component(component),
             BodyNode::Text(text) => self.out.write_text(text),
-            BodyNode::RawExpr(exp) => self.write_raw_expr(exp.span()),
+            BodyNode::RawExpr(exp) => self.write_raw_expr_node(exp),
             BodyNode::ForLoop(forloop) => self.write_for_loop(forloop),
+            BodyNode::WhileLoop(whileloop) => self.write_while_loop(whileloop),
             BodyNode::IfChain(ifchain) => self.write_if_chain(ifchain),
+            BodyNode::Fragment(fragment) => self.write_fragment(fragment),
         }
     }
 
@@ -77,6 +81,23 @@ impl<'a> Writer<'a> {
         Ok(())
     }
 
+    /// Check if the node or attribute starting at this span has `//` comments on the lines above it
+    pub fn has_leading_comments(&self, span: Span) -> bool {
+        if !self.current_span_is_primary(span) {
+            return false;
+        }
+
+        for line in self.src[..span.start().line - 1].iter().rev() {
+            match (line.trim().starts_with("//"), line.is_empty()) {
+                (true, _) => return true,
+                (_, true) => continue,
+                _ => break,
+            }
+        }
+
+        false
+    }
+
     pub fn write_comments(&mut self, child: Span) -> Result {
         // collect all comments upwards
         // make sure we don't collect the comments of the node that we're currently under.
@@ -94,10 +115,64 @@ impl<'a> Writer<'a> {
             }
         }
 
+        self.flush_comments()
+    }
+
+    /// Find the comments between the last child of a body and its closing brace
+    ///
+    /// These have no node after them to attach to, so the body writes them out after its children.
+    pub fn trailing_comments(&self, children: &[BodyNode]) -> Vec<usize> {
+        let Some(last) = children.last() else {
+            return Vec::new();
+        };
+
+        // If anything else follows the last child on its line, the comments below belong to the parent
+        let end = node_end(last);
+        let rest = self
+            .src
+            .get(end.line - 1)
+            .and_then(|line| line.get(end.column..))
+            .unwrap_or_default();
+        if !rest.trim().trim_start_matches(',').trim().is_empty() {
+            return Vec::new();
+        }
+
+        let mut comments = Vec::new();
+        for (id, line) in self.src.iter().enumerate().skip(end.line) {
+            let line = line.trim();
+            if line.starts_with("//") {
+                comments.push(id);
+            } else if !line.is_empty() {
+                if line.starts_with('}') {
+                    return comments;
+                }
+                break;
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn write_trailing_comments(&mut self, children: &[BodyNode]) -> Result {
+        let comments = self.trailing_comments(children);
+        let Some(first) = comments.first() else {
+            return Ok(());
+        };
+
+        // Keep a blank line between the last child and the comments if there was one
+        if self.src[*first - 1].trim().is_empty() {
+            self.comments.push_back(*first - 1);
+        }
+        self.comments.extend(comments);
+
+        self.flush_comments()
+    }
+
+    fn flush_comments(&mut self) -> Result {
         let mut last_was_empty = false;
         while let Some(comment_line) = self.comments.pop_front() {
             let line = &self.src[comment_line];
-            if line.is_empty() {
+            if line.trim().is_empty() {
                 if !last_was_empty {
                     self.out.new_line()?;
                 }
@@ -147,7 +222,7 @@ impl<'a> Writer<'a> {
             }
         }
 
-        Ok(())
+        self.write_trailing_comments(children)
     }
 
     pub(crate) fn attr_value_len(&mut self, value: &ElementAttrValue) -> usize {
@@ -188,14 +263,8 @@ impl<'a> Writer<'a> {
         }
 
         for attr in attributes {
-            if self.current_span_is_primary(attr.start()) {
-                'line: for line in self.src[..attr.start().start().line - 1].iter().rev() {
-                    match (line.trim().starts_with("//"), line.is_empty()) {
-                        (true, _) => return 100000,
-                        (_, true) => continue 'line,
-                        _ => break 'line,
-                    }
-                }
+            if self.has_leading_comments(attr.start()) {
+                return 100000;
             }
 
             match attr {
@@ -206,6 +275,7 @@ impl<'a> Writer<'a> {
                             name.len()
                         }
                         dioxus_rsx::ElementAttrName::Custom(name) => name.value().len() + 2,
+                        dioxus_rsx::ElementAttrName::Hyphenated(name) => name.value().len(),
                     };
                     total += name_len;
 
@@ -220,6 +290,13 @@ impl<'a> Writer<'a> {
                     let expr_len = self.retrieve_formatted_expr(expr).len();
                     total += expr_len + 3;
                 }
+                AttributeType::Conditional(group) => {
+                    total += self.retrieve_formatted_expr(&group.condition).len() + 8;
+                    for attr in &group.attributes {
+                        total += attr.attr.name.to_string().len() + 2;
+                        total += self.attr_value_len(&attr.attr.value);
+                    }
+                }
             };
 
             total += 6;
@@ -249,6 +326,16 @@ impl<'a> Writer<'a> {
 
         self.write_inline_expr(&forloop.expr)?;
 
+        // The key of each iteration stays on the first line like the key of a fragment
+        if let Some(key) = &forloop.key {
+            write!(self.out, " key: {}", ifmt_to_string(key))?;
+            if forloop.body.is_empty() {
+                write!(self.out, " }}")?;
+                return Ok(());
+            }
+            write!(self.out, ",")?;
+        }
+
         if forloop.body.is_empty() {
             write!(self.out, "}}")?;
             return Ok(());
@@ -262,6 +349,50 @@ impl<'a> Writer<'a> {
         Ok(())
     }
 
+    fn write_while_loop(&mut self, whileloop: &WhileLoop) -> std::fmt::Result {
+        write!(self.out, "while ")?;
+
+        self.write_inline_expr(&whileloop.cond)?;
+
+        if whileloop.body.is_empty() {
+            write!(self.out, "}}")?;
+            return Ok(());
+        }
+
+        self.write_body_indented(&whileloop.body)?;
+
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")?;
+
+        Ok(())
+    }
+
+    fn write_fragment(&mut self, fragment: &Fragment) -> std::fmt::Result {
+        write!(self.out, "fragment {{")?;
+
+        // The key stays on the first line like the key of an element
+        if let Some(key) = &fragment.key {
+            write!(self.out, " key: {}", ifmt_to_string(key))?;
+            if fragment.children.is_empty() {
+                write!(self.out, " }}")?;
+                return Ok(());
+            }
+            write!(self.out, ",")?;
+        }
+
+        if fragment.children.is_empty() {
+            write!(self.out, "}}")?;
+            return Ok(());
+        }
+
+        self.write_body_indented(&fragment.children)?;
+
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")?;
+
+        Ok(())
+    }
+
     fn write_if_chain(&mut self, ifchain: &IfChain) -> std::fmt::Result {
         // Recurse in place by setting the next chain
         let mut branch = Some(ifchain);
@@ -338,6 +469,34 @@ impl<'a> Writer<'a> {
     }
 }
 
+/// The position right after the last token of a node
+fn node_end(node: &BodyNode) -> LineColumn {
+    let span = match node {
+        BodyNode::Element(el) => match &el.brace {
+            Some(brace) => brace.span.close(),
+            None => el.name.span(),
+        },
+        BodyNode::Component(component) => match &component.brace {
+            Some(brace) => brace.span.close(),
+            None => component.name.span(),
+        },
+        BodyNode::Text(text) => text.source.span(),
+        BodyNode::RawExpr(exp) => exp.span(),
+        BodyNode::ForLoop(forloop) => forloop.brace_token.span.close(),
+        BodyNode::WhileLoop(whileloop) => whileloop.brace_token.span.close(),
+        BodyNode::Fragment(fragment) => fragment.brace_token.span.close(),
+        BodyNode::IfChain(ifchain) => {
+            let mut chain = ifchain;
+            while let Some(next) = &chain.else_if_branch {
+                chain = next;
+            }
+            chain.else_brace.unwrap_or(chain.then_brace).span.close()
+        }
+    };
+
+    span.end()
+}
+
 pub(crate) trait SpanLength {
     fn line_length(&self) -> usize;
 }
@@ -18,22 +18,30 @@ macro_rules! twoway {
                 let out = out.replace("\r", "");
                 let src = src.replace("\r", "");
                 pretty_assertions::assert_eq!(&src, &out);
+
+                // Formatting keeps the structure of the rsx and is stable
+                dioxus_autofmt::verify_roundtrip(&src).unwrap();
             }
         )*
     };
 }
 
 twoway![
+    attribute_groups,
     attributes,
     collapse_expr,
     comments,
+    comments_nested,
     commentshard,
     complex,
     emoji,
     ifchain_forloop,
+    whileloop,
+    fragment,
     immediate_expr,
     key,
     long_exprs,
+    reformatted_exprs,
     long,
     manual_props,
     messy_indent,
@@ -50,5 +58,15 @@ twoway![
     docsite,
     letsome,
     fat_exprs,
+    lint_attributes,
     nested,
 ];
+
+#[test]
+fn roundtrip_reports_invalid_rsx() {
+    let src = "fn app() -> Element { rsx! { div { class: } } }";
+    assert!(matches!(
+        dioxus_autofmt::verify_roundtrip(src),
+        Err(dioxus_autofmt::RoundtripError::Parse(_))
+    ));
+}
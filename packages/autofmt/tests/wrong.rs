@@ -1,4 +1,4 @@
-use dioxus_autofmt::{IndentOptions, IndentType};
+use dioxus_autofmt::{IndentOptions, IndentType, SingleChildLayout};
 
 macro_rules! twoway {
     ($val:literal => $name:ident ($indent:expr)) => {
@@ -14,12 +14,22 @@ macro_rules! twoway {
             let src_right = src_right.replace("\r", "");
 
             pretty_assertions::assert_eq!(&src_right, &out);
+
+            // The minimal edits format the file the same way
+            let edits = dioxus_autofmt::fmt_file_checked(src_wrong, $indent).unwrap();
+            let out = dioxus_autofmt::apply_edits(src_wrong, &edits).replace("\r", "");
+            pretty_assertions::assert_eq!(&src_right, &out);
+
+            // Formatting keeps the structure of the rsx and is stable
+            let out = dioxus_autofmt::verify_roundtrip_with(src_wrong, $indent).unwrap();
+            pretty_assertions::assert_eq!(&src_right, &out.replace("\r", ""));
         }
     };
 }
 
 twoway!("comments-4sp" => comments_4sp (IndentOptions::new(IndentType::Spaces, 4, false)));
 twoway!("comments-tab" => comments_tab (IndentOptions::new(IndentType::Tabs, 4, false)));
+twoway!("comments-nested" => comments_nested (IndentOptions::new(IndentType::Spaces, 4, false)));
 
 twoway!("multi-4sp" => multi_4sp (IndentOptions::new(IndentType::Spaces, 4, false)));
 twoway!("multi-tab" => multi_tab (IndentOptions::new(IndentType::Tabs, 4, false)));
@@ -29,4 +39,108 @@ twoway!("multiexpr-tab" => multiexpr_tab (IndentOptions::new(IndentType::Tabs, 4
 twoway!("multiexpr-many" => multiexpr_many (IndentOptions::new(IndentType::Spaces, 4, false)));
 twoway!("simple-combo-expr" => simple_combo_expr (IndentOptions::new(IndentType::Spaces, 4, false)));
 twoway!("oneline-expand" => online_expand (IndentOptions::new(IndentType::Spaces, 4, false)));
-twoway!("shortened" => shortened (IndentOptions::new(IndentType::Spaces, 4, false)));
+twoway!("shortened" => shortened (IndentOptions::new(IndentType::Spaces, 4, false).with_collapsed_shorthands(true)));
+twoway!("shorthands-kept" => shorthands_kept (IndentOptions::new(IndentType::Spaces, 4, false)));
+twoway!("long-exprs" => long_exprs (IndentOptions::new(IndentType::Spaces, 4, false)));
+twoway!("sorted-attributes" => sorted_attributes (IndentOptions::new(IndentType::Spaces, 4, false).with_sorted_attributes(true)));
+
+#[test]
+fn formats_only_the_block_at_an_offset() {
+    let src = "fn app() -> Element {\n    rsx! { div {\"hello world\" } }\n}\n\nfn other() -> Element {\n    let x   =   1;\n    rsx! { span {\"{x}\" } }\n}\n";
+    let offset = src.find("span").unwrap();
+
+    let block = dioxus_autofmt::fmt_block_at(
+        src,
+        offset,
+        IndentOptions::new(IndentType::Spaces, 4, false),
+    )
+    .unwrap();
+    let out = dioxus_autofmt::apply_format(src, block);
+
+    // Only the block at the offset changes
+    pretty_assertions::assert_eq!(
+        out,
+        "fn app() -> Element {\n    rsx! { div {\"hello world\" } }\n}\n\nfn other() -> Element {\n    let x   =   1;\n    rsx! {\n        span { \"{x}\" }\n    }\n}\n"
+    );
+
+    // Offsets outside of any rsx block are ignored
+    let offset = src.find("let x").unwrap();
+    assert_eq!(
+        dioxus_autofmt::fmt_block_at(
+            src,
+            offset,
+            IndentOptions::new(IndentType::Spaces, 4, false)
+        ),
+        None
+    );
+
+    // The edit only covers the text that changed
+    let src = "fn app() -> Element {\n    rsx! {\n        div { \"a\" }\n        span {\"b\" }\n    }\n}\n";
+    let offset = src.find("div").unwrap();
+    let block = dioxus_autofmt::fmt_block_at(
+        src,
+        offset,
+        IndentOptions::new(IndentType::Spaces, 4, false),
+    )
+    .unwrap();
+    assert_eq!(block.formatted, " ");
+    assert_eq!(block.start, src.find("\"b\"").unwrap());
+    assert_eq!(block.start, block.end);
+}
+
+#[test]
+fn blocks_that_do_not_parse_are_left_alone() {
+    // The file parses, but the rsx inside of the macro is half written
+    let src = "fn app() -> Element {\n    rsx! { div { class: } }\n}\n";
+    let offset = src.find("div").unwrap();
+    let indent = IndentOptions::new(IndentType::Spaces, 4, false);
+
+    assert_eq!(dioxus_autofmt::fmt_block_at(src, offset, indent.clone()), None);
+    assert_eq!(dioxus_autofmt::fmt_file_checked(src, indent).unwrap(), []);
+}
+
+#[test]
+fn checked_edits_report_unformatted_lines() {
+    let src = "fn app() -> Element {\n    rsx! {\n        div { \"a\" }\n        span {\"b\" }\n        p { \"c\" }\n        a {  \"d\" }\n    }\n}\n";
+    let indent = IndentOptions::new(IndentType::Spaces, 4, false);
+
+    let edits = dioxus_autofmt::fmt_file_checked(src, indent.clone()).unwrap();
+
+    // Each unformatted line gets its own edit
+    assert_eq!(edits.len(), 2);
+    assert_eq!((edits[0].line, edits[0].column), (4, 14));
+    assert_eq!(edits[0].replacement, " ");
+    assert_eq!(
+        edits[0].span,
+        src.find("\"b\"").unwrap()..src.find("\"b\"").unwrap()
+    );
+    assert_eq!((edits[1].line, edits[1].column), (6, 12));
+    assert_eq!(&src[edits[1].span.clone()], " ");
+    assert_eq!(edits[1].replacement, "");
+
+    let formatted = dioxus_autofmt::apply_edits(src, &edits);
+    assert!(dioxus_autofmt::fmt_file_checked(&formatted, indent.clone())
+        .unwrap()
+        .is_empty());
+
+    // Files that don't parse are reported instead of panicking
+    assert!(dioxus_autofmt::fmt_file_checked("fn app( {", indent).is_err());
+}
+
+#[test]
+fn max_width_splits_long_lines() {
+    let src = "fn app() -> Element {\n    rsx! {\n        div { class: \"card\", id: \"main\", \"Some text\" }\n    }\n}\n";
+
+    // The element fits in the default width
+    assert!(dioxus_autofmt::fmt_file(src, IndentOptions::default()).is_empty());
+
+    let narrow = IndentOptions::new(IndentType::Spaces, 4, false).with_max_width(40);
+    let out = dioxus_autofmt::apply_formats(src, dioxus_autofmt::fmt_file(src, narrow));
+    pretty_assertions::assert_eq!(
+        out,
+        "fn app() -> Element {\n    rsx! {\n        div {\n            class: \"card\",\n            id: \"main\",\n            \"Some text\"\n        }\n    }\n}\n"
+    );
+}
+twoway!("single-child-never" => single_child_never (IndentOptions::new(IndentType::Spaces, 4, false).with_single_child(SingleChildLayout::Never)));
+twoway!("single-child-elements" => single_child_elements (IndentOptions::new(IndentType::Spaces, 4, false).with_single_child(SingleChildLayout::Elements)));
+twoway!("doc-comments" => doc_comments (IndentOptions::new(IndentType::Spaces, 4, false)));
@@ -196,6 +196,8 @@ impl Default for DioxusConfig {
                 app: WebAppConfig {
                     title: default_title(),
                     base_path: None,
+                    strict_csp: false,
+                    threads: false,
                 },
                 proxy: vec![],
                 watcher: Default::default(),
@@ -343,6 +345,14 @@ pub struct WebAppConfig {
     #[serde(default = "default_title")]
     pub title: String,
     pub base_path: Option<String>,
+    /// Load the script that starts the app from a file instead of writing it inline, so the page works under
+    /// `script-src 'self'`
+    #[serde(default)]
+    pub strict_csp: bool,
+    /// Build the wasm module with shared memory so work can run on a thread pool of web workers. This needs a nightly
+    /// toolchain with the `rust-src` component, and the page must be served with cross origin isolation headers
+    #[serde(default)]
+    pub threads: bool,
 }
 
 impl Default for WebAppConfig {
@@ -350,6 +360,8 @@ impl Default for WebAppConfig {
         Self {
             title: default_title(),
             base_path: None,
+            strict_csp: false,
+            threads: false,
         }
     }
 }
@@ -1,5 +1,6 @@
 use brotli::enc::BrotliEncoderParams;
 use std::path::Path;
+use std::sync::Mutex;
 use std::{ffi::OsString, path::PathBuf};
 use walkdir::WalkDir;
 
@@ -25,8 +26,25 @@ pub fn create_assets_head(config: &CrateConfig, manifest: &AssetManifest) -> Res
     Ok(())
 }
 
+/// The last asset manifest that was processed and the output directory it was processed into
+static LAST_PROCESSED_ASSETS: Mutex<Option<(PathBuf, AssetManifest)>> = Mutex::new(None);
+
 /// Process any assets collected from the binary
+///
+/// Processing assets hashes and optimizes every file, so it is skipped if the manifest is the same as the last one
+/// that was processed into the same output directory.
 pub(crate) fn process_assets(config: &CrateConfig, manifest: &AssetManifest) -> anyhow::Result<()> {
+    let out_dir = config.out_dir();
+    let mut last_processed = LAST_PROCESSED_ASSETS.lock().unwrap();
+    if out_dir.is_dir()
+        && last_processed
+            .as_ref()
+            .is_some_and(|(dir, last)| *dir == out_dir && last == manifest)
+    {
+        tracing::info!("Assets are unchanged since the last build, skipping asset processing");
+        return Ok(());
+    }
+
     let static_asset_output_dir = PathBuf::from(
         config
             .dioxus_config
@@ -40,6 +58,8 @@ pub(crate) fn process_assets(config: &CrateConfig, manifest: &AssetManifest) ->
 
     manifest.copy_static_assets_to(static_asset_output_dir)?;
 
+    *last_processed = Some((out_dir, manifest.clone()));
+
     Ok(())
 }
 
@@ -77,7 +97,11 @@ pub fn copy_assets_dir(config: &CrateConfig, platform: Platform) -> anyhow::Resu
     Ok(())
 }
 
-fn copy_dir_to(src_dir: PathBuf, dest_dir: PathBuf, pre_compress: bool) -> std::io::Result<()> {
+pub(crate) fn copy_dir_to(
+    src_dir: PathBuf,
+    dest_dir: PathBuf,
+    pre_compress: bool,
+) -> std::io::Result<()> {
     let entries = std::fs::read_dir(&src_dir)?;
     let mut children: Vec<std::thread::JoinHandle<std::io::Result<()>>> = Vec::new();
 
@@ -1,7 +1,7 @@
 use crate::{
     assets::{
-        asset_manifest, copy_assets_dir, create_assets_head, pre_compress_folder, process_assets,
-        AssetConfigDropGuard,
+        asset_manifest, copy_assets_dir, copy_dir_to, create_assets_head, pre_compress_folder,
+        process_assets, AssetConfigDropGuard,
     },
     error::{Error, Result},
     tools::Tool,
@@ -13,11 +13,13 @@ use indicatif::{ProgressBar, ProgressStyle};
 use lazy_static::lazy_static;
 use manganis_cli_support::{AssetManifest, ManganisSupportGuard};
 use std::{
+    collections::hash_map::DefaultHasher,
     env,
     fs::{copy, create_dir_all, File},
+    hash::{Hash, Hasher},
     io::{self, IsTerminal, Read},
     panic,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
     time::Duration,
 };
@@ -66,6 +68,9 @@ impl ExecWithRustFlagsSetter for subprocess::Exec {
     }
 }
 
+/// The flags wasm modules that share their memory with web workers are built with
+const WASM_THREADS_RUST_FLAGS: &str = "-C target-feature=+atomics,+bulk-memory,+mutable-globals";
+
 /// Build client (WASM).
 /// Note: `rust_flags` argument is only used for the fullstack platform.
 pub fn build_web(
@@ -114,6 +119,14 @@ pub fn build_web(
         }
     }
 
+    // Shared memory needs the atomics target features, and a standard library that is rebuilt with them
+    let threads = dioxus_config.web.app.threads;
+    let rust_flags = match rust_flags {
+        Some(rust_flags) if threads => Some(format!("{rust_flags} {WASM_THREADS_RUST_FLAGS}")),
+        None if threads => Some(WASM_THREADS_RUST_FLAGS.to_string()),
+        rust_flags => rust_flags,
+    };
+
     let cmd = subprocess::Exec::cmd("cargo")
         .set_rust_flags(rust_flags)
         .env("CARGO_TARGET_DIR", target_dir)
@@ -123,6 +136,12 @@ pub fn build_web(
         .arg("wasm32-unknown-unknown")
         .arg("--message-format=json-render-diagnostics");
 
+    let cmd = if threads {
+        cmd.arg("-Zbuild-std=panic_abort,std")
+    } else {
+        cmd
+    };
+
     // TODO: make the initial variable mutable to simplify all the expressions
     // below. Look inside the `build_desktop()` as an example.
     let cmd = if config.release {
@@ -169,64 +188,19 @@ pub fn build_web(
 
     let input_path = output_location.with_extension("wasm");
 
-    tracing::info!("Running wasm-bindgen");
-    let run_wasm_bindgen = || {
-        // [3] Bindgen the final binary for use easy linking
-        let mut bindgen_builder = Bindgen::new();
-
-        let keep_debug = dioxus_config.web.wasm_opt.debug || (!config.release);
-
-        bindgen_builder
-            .input_path(&input_path)
-            .web(true)
-            .unwrap()
-            .debug(keep_debug)
-            .demangle(keep_debug)
-            .keep_debug(keep_debug)
-            .reference_types(true)
-            .remove_name_section(!keep_debug)
-            .remove_producers_section(!keep_debug)
-            .out_name(&dioxus_config.application.name)
-            .generate(&bindgen_outdir)
-            .unwrap();
-    };
-    let bindgen_result = panic::catch_unwind(run_wasm_bindgen);
-
-    // WASM bindgen requires the exact version of the bindgen schema to match the version the CLI was built with
-    // If we get an error, we can try to recover by pinning the user's wasm-bindgen version to the version we used
-    if let Err(err) = bindgen_result {
-        tracing::error!("Bindgen build failed: {:?}", err);
-        update_wasm_bindgen_version()?;
-        run_wasm_bindgen();
-    }
-
-    // Run wasm-opt if this is a release build
-    if config.release {
-        tracing::info!("Running optimization with wasm-opt...");
-        let mut options = match dioxus_config.web.wasm_opt.level {
-            WasmOptLevel::Z => wasm_opt::OptimizationOptions::new_optimize_for_size_aggressively(),
-            WasmOptLevel::S => wasm_opt::OptimizationOptions::new_optimize_for_size(),
-            WasmOptLevel::Zero => wasm_opt::OptimizationOptions::new_opt_level_0(),
-            WasmOptLevel::One => wasm_opt::OptimizationOptions::new_opt_level_1(),
-            WasmOptLevel::Two => wasm_opt::OptimizationOptions::new_opt_level_2(),
-            WasmOptLevel::Three => wasm_opt::OptimizationOptions::new_opt_level_3(),
-            WasmOptLevel::Four => wasm_opt::OptimizationOptions::new_opt_level_4(),
-        };
-        let wasm_file = bindgen_outdir.join(format!("{}_bg.wasm", dioxus_config.application.name));
-        let old_size = wasm_file.metadata()?.len();
-        options
-            // WASM bindgen relies on reference types
-            .enable_feature(wasm_opt::Feature::ReferenceTypes)
-            .debug_info(dioxus_config.web.wasm_opt.debug)
-            .run(&wasm_file, &wasm_file)
-            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
-        let new_size = wasm_file.metadata()?.len();
+    // Reuse the output of wasm-bindgen and wasm-opt from an earlier build if cargo produced the same module
+    let cache = WasmModuleCache::new(config, &input_path)?;
+    if cache.restore(&bindgen_outdir) {
         tracing::info!(
-            "wasm-opt reduced WASM size from {} to {} ({:2}%)",
-            old_size,
-            new_size,
-            (new_size as f64 - old_size as f64) / old_size as f64 * 100.0
+            "The wasm module is unchanged, reusing the wasm-bindgen output of an earlier build"
         );
+    } else {
+        generate_wasm_module(config, &input_path, &bindgen_outdir)?;
+        cache.store(&bindgen_outdir);
+    }
+
+    if threads {
+        gen_worker_file(config, &bindgen_outdir)?;
     }
 
     // If pre-compressing is enabled, we can pre_compress the wasm-bindgen output
@@ -294,6 +268,146 @@ pub fn build_web(
     })
 }
 
+/// Run wasm-bindgen on the module cargo built and optimize the output with wasm-opt in release builds
+fn generate_wasm_module(
+    config: &CrateConfig,
+    input_path: &Path,
+    bindgen_outdir: &Path,
+) -> Result<()> {
+    let dioxus_config = &config.dioxus_config;
+
+    tracing::info!("Running wasm-bindgen");
+    let run_wasm_bindgen = || {
+        // [3] Bindgen the final binary for use easy linking
+        let mut bindgen_builder = Bindgen::new();
+
+        let keep_debug = dioxus_config.web.wasm_opt.debug || (!config.release);
+
+        bindgen_builder
+            .input_path(input_path)
+            .web(true)
+            .unwrap()
+            .debug(keep_debug)
+            .demangle(keep_debug)
+            .keep_debug(keep_debug)
+            .reference_types(true)
+            .remove_name_section(!keep_debug)
+            .remove_producers_section(!keep_debug)
+            .out_name(&dioxus_config.application.name)
+            .generate(bindgen_outdir)
+            .unwrap();
+    };
+    let bindgen_result = panic::catch_unwind(run_wasm_bindgen);
+
+    // WASM bindgen requires the exact version of the bindgen schema to match the version the CLI was built with
+    // If we get an error, we can try to recover by pinning the user's wasm-bindgen version to the version we used
+    if let Err(err) = bindgen_result {
+        tracing::error!("Bindgen build failed: {:?}", err);
+        update_wasm_bindgen_version()?;
+        run_wasm_bindgen();
+    }
+
+    // Run wasm-opt if this is a release build
+    if config.release {
+        tracing::info!("Running optimization with wasm-opt...");
+        let mut options = match dioxus_config.web.wasm_opt.level {
+            WasmOptLevel::Z => wasm_opt::OptimizationOptions::new_optimize_for_size_aggressively(),
+            WasmOptLevel::S => wasm_opt::OptimizationOptions::new_optimize_for_size(),
+            WasmOptLevel::Zero => wasm_opt::OptimizationOptions::new_opt_level_0(),
+            WasmOptLevel::One => wasm_opt::OptimizationOptions::new_opt_level_1(),
+            WasmOptLevel::Two => wasm_opt::OptimizationOptions::new_opt_level_2(),
+            WasmOptLevel::Three => wasm_opt::OptimizationOptions::new_opt_level_3(),
+            WasmOptLevel::Four => wasm_opt::OptimizationOptions::new_opt_level_4(),
+        };
+        let wasm_file = bindgen_outdir.join(format!("{}_bg.wasm", dioxus_config.application.name));
+        let old_size = wasm_file.metadata()?.len();
+        if dioxus_config.web.app.threads {
+            options
+                .enable_feature(wasm_opt::Feature::Atomics)
+                .enable_feature(wasm_opt::Feature::BulkMemory)
+                .enable_feature(wasm_opt::Feature::MutableGlobals);
+        }
+        options
+            // WASM bindgen relies on reference types
+            .enable_feature(wasm_opt::Feature::ReferenceTypes)
+            .debug_info(dioxus_config.web.wasm_opt.debug)
+            .run(&wasm_file, &wasm_file)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+        let new_size = wasm_file.metadata()?.len();
+        tracing::info!(
+            "wasm-opt reduced WASM size from {} to {} ({:2}%)",
+            old_size,
+            new_size,
+            (new_size as f64 - old_size as f64) / old_size as f64 * 100.0
+        );
+    }
+
+    Ok(())
+}
+
+/// A cache of the wasm-bindgen and wasm-opt output for each wasm module cargo builds
+///
+/// Cargo writes the same module if nothing the crate depends on changed, for example when only assets or templates
+/// were edited. The output is cached in the target directory under a fingerprint of the module and everything else
+/// that changes the output, so those builds can skip straight to copying the files from the last build.
+struct WasmModuleCache {
+    dir: PathBuf,
+}
+
+impl WasmModuleCache {
+    fn new(config: &CrateConfig, input_path: &Path) -> Result<Self> {
+        let mut hasher = DefaultHasher::new();
+        std::fs::read(input_path)?.hash(&mut hasher);
+        wasm_bindgen_shared::version().hash(&mut hasher);
+        config.release.hash(&mut hasher);
+        config.dioxus_config.application.name.hash(&mut hasher);
+        config.dioxus_config.web.wasm_opt.debug.hash(&mut hasher);
+        format!("{:?}", config.dioxus_config.web.wasm_opt.level).hash(&mut hasher);
+
+        let dir = config
+            .target_dir
+            .join("dx")
+            .join("wasm-bindgen")
+            .join(format!("{:016x}", hasher.finish()));
+
+        Ok(Self { dir })
+    }
+
+    /// Copy the cached output into the output directory. Returns false if the module isn't cached
+    fn restore(&self, bindgen_outdir: &Path) -> bool {
+        if !self.dir.is_dir() {
+            return false;
+        }
+
+        match copy_dir_to(self.dir.clone(), bindgen_outdir.to_path_buf(), false) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!("Failed to restore the cached wasm-bindgen output: {err}");
+                false
+            }
+        }
+    }
+
+    /// Cache the output for this module. Only the latest module is kept
+    fn store(&self, bindgen_outdir: &Path) {
+        let result = (|| {
+            if let Some(parent) = self.dir.parent() {
+                if parent.is_dir() {
+                    std::fs::remove_dir_all(parent)?;
+                }
+            }
+            create_dir_all(&self.dir)?;
+            copy_dir_to(bindgen_outdir.to_path_buf(), self.dir.clone(), false)
+        })();
+
+        if let Err(err) = result {
+            tracing::warn!("Failed to cache the wasm-bindgen output: {err}");
+            // Don't leave a partial copy that would be restored later
+            _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
 // Attempt to automatically recover from a bindgen failure by updating the wasm-bindgen version
 fn update_wasm_bindgen_version() -> Result<()> {
     let cli_bindgen_version = wasm_bindgen_shared::version();
@@ -594,14 +708,13 @@ pub fn gen_page(config: &CrateConfig, manifest: Option<&AssetManifest>, serve: b
 
     replace_or_insert_before("{script_include}", &script_str, "</body", &mut html);
 
-    if serve {
+    let strict_csp = config.dioxus_config.web.app.strict_csp;
+    // Pages with a strict Content Security Policy load the reconnect script with the bootstrap script
+    if serve && !strict_csp {
         html += &format!("<script>{}</script>", dioxus_hot_reload::RECONNECT_SCRIPT);
     }
 
-    let base_path = match &config.dioxus_config.web.app.base_path {
-        Some(path) => path.trim_matches('/'),
-        None => ".",
-    };
+    let base_path = base_path(config);
     let app_name = &config.dioxus_config.application.name;
     // Check if a script already exists
     if html.contains("{app_name}") && html.contains("{base_path}") {
@@ -610,20 +723,19 @@ pub fn gen_page(config: &CrateConfig, manifest: Option<&AssetManifest>, serve: b
         html = html.replace("{base_path}", base_path);
     } else {
         // If not, insert the script
-        html = html.replace(
-            "</body",
-            &format!(
+        let script = if strict_csp {
+            format!(
+                r#"<script type="module" src="/{base_path}/assets/dioxus/{app_name}_bootstrap.js"></script>"#
+            )
+        } else {
+            format!(
                 r#"<script type="module">
-    import init from "/{base_path}/assets/dioxus/{app_name}.js";
-    init("/{base_path}/assets/dioxus/{app_name}_bg.wasm").then(wasm => {{
-      if (wasm.__wbindgen_start == undefined) {{
-        wasm.main();
-      }}
-    }});
-    </script>
-    </body"#
-            ),
-        );
+    {}
+    </script>"#,
+                bootstrap_script(config)
+            )
+        };
+        html = html.replace("</body", &format!("{script}\n    </body"));
     }
 
     let title = config.dioxus_config.web.app.title.clone();
@@ -633,6 +745,72 @@ pub fn gen_page(config: &CrateConfig, manifest: Option<&AssetManifest>, serve: b
     html
 }
 
+fn base_path(config: &CrateConfig) -> &str {
+    match &config.dioxus_config.web.app.base_path {
+        Some(path) => path.trim_matches('/'),
+        None => ".",
+    }
+}
+
+/// The module script that loads the wasm and starts the app
+fn bootstrap_script(config: &CrateConfig) -> String {
+    let base_path = base_path(config);
+    let app_name = &config.dioxus_config.application.name;
+    format!(
+        r#"import init from "/{base_path}/assets/dioxus/{app_name}.js";
+    init("/{base_path}/assets/dioxus/{app_name}_bg.wasm").then(wasm => {{
+      if (wasm.__wbindgen_start == undefined) {{
+        wasm.main();
+      }}
+    }});"#
+    )
+}
+
+/// Write the script the web workers of the thread pool start with. `dioxus-web` spawns the workers from the script next
+/// to the wasm-bindgen output, and sends them the module and memory of the app to instantiate.
+fn gen_worker_file(config: &CrateConfig, bindgen_outdir: &Path) -> Result<()> {
+    let app_name = &config.dioxus_config.application.name;
+    let script = format!(
+        r#"import init, {{ __dioxus_run_worker }} from "./{app_name}.js";
+self.onmessage = async (event) => {{
+  const [module, memory, work] = event.data;
+  await init(module, memory);
+  __dioxus_run_worker(work);
+  close();
+}};
+"#
+    );
+    std::fs::write(bindgen_outdir.join("dioxus_worker.js"), script)?;
+
+    Ok(())
+}
+
+/// Write the script that starts the app next to the wasm for pages with a strict Content Security Policy, which can't
+/// run the inline script [`gen_page`] writes otherwise
+pub fn gen_bootstrap_file(config: &CrateConfig, serve: bool) -> Result<()> {
+    if !config.dioxus_config.web.app.strict_csp {
+        return Ok(());
+    }
+
+    let mut script = bootstrap_script(config);
+    if serve {
+        script.push('\n');
+        script.push_str(dioxus_hot_reload::RECONNECT_SCRIPT);
+    }
+
+    let bindgen_outdir = config.out_dir().join("assets").join("dioxus");
+    create_dir_all(&bindgen_outdir)?;
+    std::fs::write(
+        bindgen_outdir.join(format!(
+            "{}_bootstrap.js",
+            config.dioxus_config.application.name
+        )),
+        script,
+    )?;
+
+    Ok(())
+}
+
 fn replace_or_insert_before(
     replace: &str,
     with: &str,
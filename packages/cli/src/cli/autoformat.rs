@@ -1,4 +1,4 @@
-use dioxus_autofmt::{IndentOptions, IndentType};
+use dioxus_autofmt::{IndentOptions, IndentType, SingleChildLayout};
 use rayon::prelude::*;
 use std::{fs, path::Path, process::exit};
 
@@ -30,6 +30,18 @@ pub struct Autoformat {
     /// Split attributes in lines or not
     #[clap(short, long, default_value = "false")]
     pub split_line_attributes: bool,
+
+    /// Sort the attributes of elements: id and class first, then other attributes alphabetically, then event handlers and spreads
+    #[clap(long, default_value = "false")]
+    pub sort_attributes: bool,
+
+    /// Which single children keep a block on one line: `never`, `text` (text, expressions and components without props) or `elements` (also elements that fit on one line)
+    #[clap(long, default_value = "text")]
+    pub single_child: SingleChildLayout,
+
+    /// Write attributes and props that are set to a variable with the same name, like `class: class`, with the shorthand `class`
+    #[clap(long, default_value = "false")]
+    pub collapse_shorthands: bool,
 }
 
 impl Autoformat {
@@ -39,20 +51,35 @@ impl Autoformat {
             raw,
             file,
             split_line_attributes,
+            sort_attributes,
+            single_child,
+            collapse_shorthands,
             all_code: format_rust_code,
-            ..
         } = self;
 
         // Default to formatting the project
         if raw.is_none() && file.is_none() {
-            if let Err(e) = autoformat_project(check, split_line_attributes, format_rust_code) {
+            if let Err(e) = autoformat_project(
+                check,
+                split_line_attributes,
+                sort_attributes,
+                single_child,
+                collapse_shorthands,
+                format_rust_code,
+            ) {
                 eprintln!("error formatting project: {}", e);
                 exit(1);
             }
         }
 
         if let Some(raw) = raw {
-            let indent = indentation_for(".", self.split_line_attributes)?;
+            let indent = indentation_for(
+                ".",
+                split_line_attributes,
+                sort_attributes,
+                single_child,
+                collapse_shorthands,
+            )?;
             if let Some(inner) = dioxus_autofmt::fmt_block(&raw, 0, indent) {
                 println!("{}", inner);
             } else {
@@ -64,7 +91,14 @@ impl Autoformat {
 
         // Format single file
         if let Some(file) = file {
-            refactor_file(file, split_line_attributes, format_rust_code)?;
+            refactor_file(
+                file,
+                split_line_attributes,
+                sort_attributes,
+                single_child,
+                collapse_shorthands,
+                format_rust_code,
+            )?;
         }
 
         Ok(())
@@ -74,9 +108,18 @@ impl Autoformat {
 fn refactor_file(
     file: String,
     split_line_attributes: bool,
+    sort_attributes: bool,
+    single_child: SingleChildLayout,
+    collapse_shorthands: bool,
     format_rust_code: bool,
 ) -> Result<(), Error> {
-    let indent = indentation_for(".", split_line_attributes)?;
+    let indent = indentation_for(
+        ".",
+        split_line_attributes,
+        sort_attributes,
+        single_child,
+        collapse_shorthands,
+    )?;
     let file_content = if file == "-" {
         let mut contents = String::new();
         std::io::stdin().read_to_string(&mut contents)?;
@@ -160,6 +203,9 @@ fn format_file(
 fn autoformat_project(
     check: bool,
     split_line_attributes: bool,
+    sort_attributes: bool,
+    single_child: SingleChildLayout,
+    collapse_shorthands: bool,
     format_rust_code: bool,
 ) -> Result<()> {
     let files_to_format = get_project_files();
@@ -172,7 +218,13 @@ fn autoformat_project(
         return Ok(());
     }
 
-    let indent = indentation_for(&files_to_format[0], split_line_attributes)?;
+    let indent = indentation_for(
+        &files_to_format[0],
+        split_line_attributes,
+        sort_attributes,
+        single_child,
+        collapse_shorthands,
+    )?;
 
     let counts = files_to_format
         .into_par_iter()
@@ -201,6 +253,9 @@ fn autoformat_project(
 fn indentation_for(
     file_or_dir: impl AsRef<Path>,
     split_line_attributes: bool,
+    sort_attributes: bool,
+    single_child: SingleChildLayout,
+    collapse_shorthands: bool,
 ) -> Result<IndentOptions> {
     let out = std::process::Command::new("cargo")
         .args(["fmt", "--", "--print-config", "current"])
@@ -234,6 +289,18 @@ fn indentation_for(
             Error::RuntimeError("Could not parse tab_spaces option in rustfmt config".into())
         })?;
 
+    let max_width = config
+        .lines()
+        .find(|line| line.starts_with("max_width "))
+        .and_then(|line| line.split_once('='))
+        .map(|(_, value)| value.trim().parse::<usize>())
+        .ok_or_else(|| {
+            Error::RuntimeError("Could not find max_width option in rustfmt config".into())
+        })?
+        .map_err(|_| {
+            Error::RuntimeError("Could not parse max_width option in rustfmt config".into())
+        })?;
+
     Ok(IndentOptions::new(
         if hard_tabs {
             IndentType::Tabs
@@ -242,7 +309,11 @@ fn indentation_for(
         },
         tab_spaces,
         split_line_attributes,
-    ))
+    )
+    .with_max_width(max_width)
+    .with_sorted_attributes(sort_attributes)
+    .with_single_child(single_child)
+    .with_collapsed_shorthands(collapse_shorthands))
 }
 
 /// Format rust code using prettyplease
@@ -285,6 +356,9 @@ async fn test_auto_fmt() {
         raw: Some(test_rsx),
         file: None,
         split_line_attributes: false,
+        sort_attributes: false,
+        single_child: SingleChildLayout::default(),
+        collapse_shorthands: false,
     };
 
     fmt.autoformat().unwrap();
@@ -112,6 +112,7 @@ impl Build {
 
         let mut file = std::fs::File::create(crate_config.out_dir().join("index.html"))?;
         file.write_all(temp.as_bytes())?;
+        gen_bootstrap_file(&crate_config, false)?;
 
         // #[cfg(feature = "plugin")]
         // let _ = crate::plugin::PluginManager::on_build_finish(&crate_config, &platform);
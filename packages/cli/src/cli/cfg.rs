@@ -129,6 +129,22 @@ pub struct ConfigOptsServe {
     #[serde(default)]
     pub hot_reload: bool,
 
+    /// Render the dev overlay next to the app. Toggle it with ctrl+shift+d. Only works when the app depends on dioxus directly. Will not work with release builds. [default: true]
+    #[clap(long)]
+    #[clap(default_missing_value("true"),
+        default_value("true"),
+        num_args(0..=1),
+        require_equals(true),
+        action = clap::ArgAction::Set,
+    )]
+    #[clap(group = "release-incompatible")]
+    #[serde(default)]
+    pub dev_overlay: bool,
+
+    /// Also serve hot reloading over a WebSocket on this port, so desktop apps running on phones and other devices can connect. The address of this machine and a token the server checks are embedded in the app when it is built, and apps that can't reach the address find the server on the local network [default: None]
+    #[clap(long)]
+    pub device_port: Option<u16>,
+
     /// Set cross-origin-policy to same-origin [default: false]
     #[clap(name = "cross-origin-policy")]
     #[clap(long)]
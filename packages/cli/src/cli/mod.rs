@@ -15,7 +15,7 @@ use crate::{
     cfg::{ConfigOptsBuild, ConfigOptsServe},
     custom_error,
     error::Result,
-    gen_page, server, Error,
+    gen_bootstrap_file, gen_page, server, Error,
 };
 use clap::{Parser, Subcommand};
 use dioxus_cli_config::CrateConfig;
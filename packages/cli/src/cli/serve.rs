@@ -21,7 +21,10 @@ impl Serve {
         // change the release state.
         let hot_reload = self.serve.hot_reload || crate_config.dioxus_config.application.hot_reload;
         crate_config.with_hot_reload(hot_reload);
-        crate_config.with_cross_origin_policy(self.serve.cross_origin_policy);
+        // Shared wasm memory is only available on cross origin isolated pages
+        let cross_origin_policy =
+            self.serve.cross_origin_policy || crate_config.dioxus_config.web.app.threads;
+        crate_config.with_cross_origin_policy(cross_origin_policy);
         crate_config.with_release(self.serve.release);
         crate_config.with_verbose(self.serve.verbose);
 
@@ -37,6 +40,16 @@ impl Serve {
             crate_config.set_features(features);
         }
 
+        // Only crates that depend on dioxus directly can enable its features
+        if self.serve.dev_overlay
+            && !self.serve.release
+            && crate_config.manifest.dependencies.contains_key("dioxus")
+        {
+            let mut features = crate_config.features.clone().unwrap_or_default();
+            features.push("dioxus/dev-overlay".to_string());
+            crate_config.set_features(features);
+        }
+
         if let Some(target) = self.serve.target {
             crate_config.set_target(target);
         }
@@ -89,6 +102,7 @@ impl Serve {
         let index_path = dist_path.join("index.html");
         let mut file = std::fs::File::create(index_path)?;
         file.write_all(serve_html.as_bytes())?;
+        gen_bootstrap_file(crate_config, true)?;
 
         Ok(())
     }
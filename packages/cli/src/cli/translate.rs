@@ -81,7 +81,7 @@ fn write_svg_section(out: &mut String, svgs: Vec<BodyNode>) {
     out.push_str("\n\nmod icons {");
     out.push_str("\n    use super::*;");
     for (idx, icon) in svgs.into_iter().enumerate() {
-        let raw = dioxus_autofmt::write_block_out(CallBody { roots: vec![icon] }).unwrap();
+        let raw = dioxus_autofmt::write_block_out(CallBody::new(vec![icon])).unwrap();
         out.push_str("\n\n    pub fn icon_");
         out.push_str(&idx.to_string());
         out.push_str("() -> Element {\n        rsx! {");
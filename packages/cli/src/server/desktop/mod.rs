@@ -1,21 +1,33 @@
-use crate::server::SharedFileMap;
 use crate::{
     cfg::ConfigOptsServe,
     server::{
+        hot_reload_namespace,
         output::{print_console_info, PrettierOptions},
         setup_file_watcher, Platform,
     },
     BuildResult, Result,
 };
 use dioxus_cli_config::CrateConfig;
-use dioxus_hot_reload::HotReloadMsg;
-use dioxus_html::HtmlCtx;
-use dioxus_rsx::hot_reload::*;
+use dioxus_hot_reload::{
+    encode_message, exchange_handshake, has_token, socket_name, write_frame, DiscoveryResponse,
+    Handshake, HotReloadMsg, ScopedHotReloadMsg, ADDRESS_ENV, DISCOVERY_PORT, DISCOVERY_REQUEST,
+    NAMESPACE_ENV, TOKEN_ENV,
+};
 use interprocess::local_socket::LocalSocketListener;
+use rand::RngCore;
 use std::{
     fs::create_dir_all,
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket},
+    path::Path,
     process::{Child, Command},
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+use tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::StatusCode,
+    Message, WebSocket,
 };
 
 #[cfg(feature = "plugin")]
@@ -33,26 +45,7 @@ pub(crate) async fn startup_with_platform<P: Platform + Send + 'static>(
 ) -> Result<()> {
     set_ctrl_c(&config);
 
-    let file_map = match config.hot_reload {
-        true => {
-            let FileMapBuildResult { map, errors } =
-                FileMap::<HtmlCtx>::create(config.crate_dir.clone()).unwrap();
-
-            for err in errors {
-                tracing::error!("{}", err);
-            }
-
-            let file_map = Arc::new(Mutex::new(map));
-
-            Some(file_map.clone())
-        }
-        false => None,
-    };
-
-    let hot_reload_state = HotReloadState {
-        receiver: Default::default(),
-        file_map,
-    };
+    let hot_reload_state = HotReloadState::new(&config);
 
     serve::<P>(config, serve_cfg, hot_reload_state).await?;
 
@@ -69,21 +62,30 @@ fn set_ctrl_c(config: &CrateConfig) {
     });
 }
 
+/// How long an app has to answer the handshake before it is treated as incompatible
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Start the server without hot reload
 async fn serve<P: Platform + Send + 'static>(
     config: CrateConfig,
     serve: &ConfigOptsServe,
     hot_reload_state: HotReloadState,
 ) -> Result<()> {
+    let device_server = serve
+        .device_port
+        .filter(|_| hot_reload_state.enabled())
+        // The address and token need to be set before the first build so they are embedded in the app
+        .map(|port| DeviceServer::advertise(&config, port));
+
     let hot_reload: tokio::task::JoinHandle<Result<()>> = tokio::spawn({
         let hot_reload_state = hot_reload_state.clone();
         async move {
-            match hot_reload_state.file_map.clone() {
-                Some(file_map) => {
+            match hot_reload_state.enabled() {
+                true => {
                     // The open interprocess sockets
-                    start_desktop_hot_reload(hot_reload_state, file_map).await?;
+                    start_desktop_hot_reload(hot_reload_state, device_server).await?;
                 }
-                None => {
+                false => {
                     std::future::pending::<()>().await;
                 }
             }
@@ -115,7 +117,7 @@ async fn serve<P: Platform + Send + 'static>(
 
 async fn start_desktop_hot_reload(
     hot_reload_state: HotReloadState,
-    file_map: SharedFileMap,
+    device_server: Option<DeviceServer>,
 ) -> Result<()> {
     let metadata = cargo_metadata::MetadataCommand::new()
         .no_deps()
@@ -124,10 +126,12 @@ async fn start_desktop_hot_reload(
     let target_dir = metadata.target_directory.as_std_path();
 
     let _ = create_dir_all(target_dir); // `_all` is for good measure and future-proofness.
-    let path = target_dir.join("dioxusin");
+    let namespace = Some(hot_reload_state.namespace.clone());
+    let socket_name = socket_name(namespace.as_deref());
+    let path = target_dir.join(&socket_name);
     clear_paths(&path);
     let listener = if cfg!(windows) {
-        LocalSocketListener::bind("@dioxusin")
+        LocalSocketListener::bind(format!("@{socket_name}"))
     } else {
         LocalSocketListener::bind(path)
     };
@@ -135,39 +139,31 @@ async fn start_desktop_hot_reload(
         Ok(local_socket_stream) => {
             let aborted = Arc::new(Mutex::new(false));
             // States
-            // The open interprocess sockets
-            let channels = Arc::new(Mutex::new(Vec::new()));
+            // The open interprocess sockets and network connections
+            let channels: Arc<Mutex<Vec<Box<dyn Channel>>>> = Default::default();
+
+            // listen for apps running on other devices
+            if let Some(device_server) = device_server {
+                device_server.start(&hot_reload_state, &namespace, &channels);
+            }
 
             // listen for connections
             std::thread::spawn({
                 let channels = channels.clone();
                 let aborted = aborted.clone();
+                let hot_reload_state = hot_reload_state.clone();
+                let namespace = namespace.clone();
                 move || {
                     loop {
                         //accept() will block the thread when local_socket_stream is in blocking mode (default)
                         match local_socket_stream.accept() {
-                            Ok(mut connection) => {
-                                // send any templates than have changed before the socket connected
-                                let templates: Vec<_> = {
-                                    file_map
-                                        .lock()
-                                        .unwrap()
-                                        .map
-                                        .values()
-                                        .flat_map(|v| v.templates.values().copied())
-                                        .collect()
-                                };
-
-                                for template in templates {
-                                    if !send_msg(
-                                        HotReloadMsg::UpdateTemplate(template),
-                                        &mut connection,
-                                    ) {
-                                        continue;
-                                    }
-                                }
-                                channels.lock().unwrap().push(connection);
-                                println!("Connected to hot reloading 🚀");
+                            Ok(connection) => {
+                                add_connection(
+                                    &hot_reload_state,
+                                    &namespace,
+                                    &channels,
+                                    LocalChannel(connection),
+                                );
                             }
                             Err(err) => {
                                 let error_string = err.to_string();
@@ -193,8 +189,7 @@ async fn start_desktop_hot_reload(
                 let mut i = 0;
 
                 while i < channels.len() {
-                    let channel = &mut channels[i];
-                    if send_msg(msg.clone(), channel) {
+                    if send_msg(&namespace, msg.clone(), &mut *channels[i]) {
                         i += 1;
                     } else {
                         channels.remove(i);
@@ -220,17 +215,259 @@ fn clear_paths(file_socket_path: &std::path::Path) {
     }
 }
 
-fn send_msg(msg: HotReloadMsg, channel: &mut impl std::io::Write) -> bool {
-    if let Ok(msg) = serde_json::to_string(&msg) {
-        if channel.write_all(msg.as_bytes()).is_err() {
-            return false;
+/// Exchange handshakes with a new connection, send it the templates and literals that changed before it connected,
+/// then broadcast changes to it
+///
+/// Apps that speak another version of the protocol are marked incompatible so changes rebuild them instead.
+fn add_connection(
+    hot_reload_state: &HotReloadState,
+    namespace: &Option<String>,
+    channels: &Arc<Mutex<Vec<Box<dyn Channel>>>>,
+    mut connection: impl Channel + 'static,
+) {
+    let hot_reload_state = hot_reload_state.clone();
+    let namespace = namespace.clone();
+    let channels = channels.clone();
+
+    std::thread::spawn(move || {
+        // Apps built before the handshake existed never answer it
+        let (handshake_tx, handshake_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let app = connection.handshake();
+            let _ = handshake_tx.send((app, connection));
+        });
+        let mut connection = match handshake_rx.recv_timeout(HANDSHAKE_TIMEOUT) {
+            Ok((Ok(app), connection)) if app.is_compatible() => connection,
+            Ok((Ok(app), _)) => return incompatible_app(&hot_reload_state, &app.to_string()),
+            Ok((Err(err), _)) if err.kind() == std::io::ErrorKind::InvalidData => {
+                return incompatible_app(&hot_reload_state, "an unknown protocol")
+            }
+            // The app disconnected
+            Ok((Err(_), _)) => return,
+            Err(_) => return incompatible_app(&hot_reload_state, "an older protocol"),
+        };
+
+        let templates = hot_reload_state
+            .modified_templates()
+            .into_iter()
+            .map(HotReloadMsg::UpdateTemplate);
+        let literals = hot_reload_state
+            .modified_literals()
+            .into_iter()
+            .map(HotReloadMsg::UpdateLiterals);
+
+        for msg in templates.chain(literals) {
+            if !send_msg(&namespace, msg, &mut connection) {
+                return;
+            }
         }
-        if channel.write_all(&[b'\n']).is_err() {
-            return false;
+        channels.lock().unwrap().push(Box::new(connection));
+        println!("Connected to hot reloading 🚀");
+    });
+}
+
+/// Fall back to full rebuilds for an app that can't read the messages of this version of the CLI
+fn incompatible_app(hot_reload_state: &HotReloadState, app: &str) {
+    hot_reload_state.mark_incompatible();
+    println!(
+        "The app speaks {app} and the CLI speaks {}, changes will rebuild the app instead of hot reloading it. Use a version of the dioxus-cli that matches the version of dioxus the app uses to hot reload it",
+        Handshake::CURRENT
+    );
+}
+
+/// The network server apps running on phones and other devices connect to
+struct DeviceServer {
+    port: u16,
+    token: String,
+}
+
+impl DeviceServer {
+    /// Embed the address other devices can reach the hot reloading server at and the token it accepts in the app,
+    /// unless they were set already.
+    ///
+    /// Apps connected over USB can set the address to `127.0.0.1:<port>` and forward the port with `adb reverse`.
+    fn advertise(config: &CrateConfig, port: u16) -> Self {
+        let address = match std::env::var(ADDRESS_ENV) {
+            Ok(address) => address,
+            Err(_) => {
+                let address = SocketAddr::new(local_ip(), port).to_string();
+                std::env::set_var(ADDRESS_ENV, &address);
+                address
+            }
+        };
+        let token = match std::env::var(TOKEN_ENV) {
+            Ok(token) => token,
+            Err(_) => {
+                let token = device_token(&config.target_dir);
+                std::env::set_var(TOKEN_ENV, &token);
+                token
+            }
+        };
+        println!("📱 Devices can connect to hot reloading at {address}");
+
+        Self { port, token }
+    }
+
+    /// Accept WebSocket connections with the token and answer discovery requests on the local network
+    fn start(
+        self,
+        hot_reload_state: &HotReloadState,
+        namespace: &Option<String>,
+        channels: &Arc<Mutex<Vec<Box<dyn Channel>>>>,
+    ) {
+        let port = self.port;
+        let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)) {
+            Ok(listener) => listener,
+            Err(error) => {
+                println!("failed to serve hot reloading to devices on port {port}\n{error}");
+                return;
+            }
+        };
+        std::thread::spawn({
+            let channels = channels.clone();
+            let hot_reload_state = hot_reload_state.clone();
+            let namespace = namespace.clone();
+            move || {
+                for connection in listener.incoming().flatten() {
+                    if let Some(socket) = accept_device(connection, &self.token) {
+                        add_connection(&hot_reload_state, &namespace, &channels, socket);
+                    }
+                }
+            }
+        });
+
+        // Only one server on this machine can answer discovery requests. Apps can still use the embedded address
+        match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT)) {
+            Ok(socket) => {
+                let response = DiscoveryResponse {
+                    namespace: namespace.clone(),
+                    port,
+                };
+                let response = serde_json::to_vec(&response).unwrap();
+                std::thread::spawn(move || {
+                    let mut buf = [0; 64];
+                    while let Ok((len, from)) = socket.recv_from(&mut buf) {
+                        if &buf[..len] == DISCOVERY_REQUEST {
+                            let _ = socket.send_to(&response, from);
+                        }
+                    }
+                });
+            }
+            Err(error) => {
+                println!("failed to answer hot reloading discovery on port {DISCOVERY_PORT}, devices will connect to the embedded address\n{error}")
+            }
+        }
+    }
+}
+
+/// Finish the WebSocket handshake with an app on another device, rejecting it if it doesn't have the token
+// tungstenite decides the type of the error response
+#[allow(clippy::result_large_err)]
+fn accept_device(connection: TcpStream, token: &str) -> Option<WebSocket<TcpStream>> {
+    let check_token =
+        |request: &Request, response: Response| match has_token(request.uri().query(), token) {
+            true => Ok(response),
+            false => {
+                let mut error = ErrorResponse::new(Some("invalid hot reloading token".to_string()));
+                *error.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(error)
+            }
+        };
+
+    match tungstenite::accept_hdr(connection, check_token) {
+        Ok(socket) => Some(socket),
+        Err(error) => {
+            println!("rejected a hot reloading connection from another device: {error}");
+            None
+        }
+    }
+}
+
+/// The token apps on other devices connect with. It is kept in the target directory so rebuilding the app in a new
+/// session doesn't recompile the crates that embed it
+fn device_token(target_dir: &Path) -> String {
+    let path = target_dir.join("dioxus-hot-reload-token");
+    if let Ok(token) = std::fs::read_to_string(&path) {
+        if !token.trim().is_empty() {
+            return token.trim().to_string();
+        }
+    }
+
+    // Anyone on the network who guesses the token can push code to the app, so it comes from the OS's secure RNG
+    let mut bytes = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    let token = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let _ = create_dir_all(target_dir);
+    let _ = std::fs::write(&path, &token);
+    token
+}
+
+/// The address of this machine on the local network
+fn local_ip() -> IpAddr {
+    // Connecting a UDP socket doesn't send anything, but it picks the interface that routes out of this machine
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80))?;
+            socket.local_addr()
+        })
+        .map(|address| address.ip())
+        .unwrap_or(Ipv4Addr::LOCALHOST.into())
+}
+
+/// A connected app that receives hot reloading messages
+trait Channel: Send {
+    /// Send the handshake of the CLI and read the one of the app
+    fn handshake(&mut self) -> std::io::Result<Handshake>;
+
+    /// Send one encoded message, returning false if the app disconnected
+    fn send(&mut self, frame: Vec<u8>) -> bool;
+}
+
+/// An app connected to the local socket. Messages are prefixed with their length
+struct LocalChannel<S>(S);
+
+impl<S: Read + Write + Send> Channel for LocalChannel<S> {
+    fn handshake(&mut self) -> std::io::Result<Handshake> {
+        exchange_handshake(&mut self.0)
+    }
+
+    fn send(&mut self, frame: Vec<u8>) -> bool {
+        write_frame(&mut self.0, &frame).is_ok()
+    }
+}
+
+/// An app on another device connected over a WebSocket. Every message is its own binary frame
+impl Channel for WebSocket<TcpStream> {
+    fn handshake(&mut self) -> std::io::Result<Handshake> {
+        WebSocket::send(self, Message::Binary(Handshake::CURRENT.encode()))
+            .map_err(std::io::Error::other)?;
+        loop {
+            match self.read().map_err(std::io::Error::other)? {
+                Message::Binary(frame) => return Handshake::decode(&frame),
+                Message::Text(_) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "expected a binary handshake",
+                    ))
+                }
+                _ => {}
+            }
         }
-        true
-    } else {
-        false
+    }
+
+    fn send(&mut self, frame: Vec<u8>) -> bool {
+        WebSocket::send(self, Message::Binary(frame)).is_ok()
+    }
+}
+
+fn send_msg(namespace: &Option<String>, msg: HotReloadMsg, channel: &mut dyn Channel) -> bool {
+    let msg = ScopedHotReloadMsg::new(namespace.clone(), msg);
+    match encode_message(&msg) {
+        Some(frame) => channel.send(frame),
+        None => false,
     }
 }
 
@@ -252,6 +489,7 @@ fn start_desktop(
                 .ok_or(anyhow::anyhow!("No executable found after desktop build"))?,
         )
         .env(active, "true")
+        .env(NAMESPACE_ENV, hot_reload_namespace(config))
         .spawn()?,
     );
 
@@ -299,6 +537,11 @@ impl DesktopPlatform {
         config: &CrateConfig,
         rust_flags: Option<String>,
     ) -> Result<BuildResult> {
+        // With hot reloading, the app saves its state and shuts down on its own when it is told the app is rebuilt
+        if config.hot_reload {
+            wait_for_exit(&mut self.currently_running_child.0, Duration::from_secs(1));
+        }
+
         // Gracefully shtudown the desktop app
         // It might have a receiver to do some cleanup stuff
         let pid = self.currently_running_child.0.id();
@@ -346,6 +589,17 @@ impl Platform for DesktopPlatform {
     }
 }
 
+/// Wait for a process to exit for up to `timeout`
+fn wait_for_exit(child: &mut Child, timeout: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
 struct RAIIChild(Child);
 
 impl Drop for RAIIChild {
@@ -353,3 +607,27 @@ impl Drop for RAIIChild {
         let _ = self.0.kill();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_hot_reload::websocket_url;
+
+    #[test]
+    fn devices_need_the_token() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let mut accepted = Vec::new();
+            for connection in listener.incoming().take(2).flatten() {
+                accepted.push(accept_device(connection, "secret").is_some());
+            }
+            accepted
+        });
+
+        assert!(tungstenite::connect(websocket_url(address, Some("guess"))).is_err());
+        let (mut socket, _) = tungstenite::connect(websocket_url(address, Some("secret"))).unwrap();
+        assert_eq!(server.join().unwrap(), [false, true]);
+        let _ = socket.close(None);
+    }
+}
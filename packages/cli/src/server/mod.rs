@@ -2,12 +2,19 @@ use crate::{cfg::ConfigOptsServe, BuildResult, Result};
 use dioxus_cli_config::CrateConfig;
 
 use cargo_metadata::diagnostic::Diagnostic;
-use dioxus_hot_reload::{HotReloadMsg, HotReloadReceiver};
+use dioxus_core::{HotLiterals, Template};
+use dioxus_hot_reload::{is_hot_reloadable_asset, HotReloadMsg, HotReloadReceiver};
 use dioxus_html::HtmlCtx;
 use dioxus_rsx::hot_reload::*;
 use fs_extra::dir::CopyOptions;
 use notify::{RecommendedWatcher, Watcher};
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 mod output;
 use output::*;
@@ -20,13 +27,186 @@ pub struct HotReloadState {
     /// The receiver for hot reload messages
     pub receiver: HotReloadReceiver,
 
-    /// The file map that tracks the state of the projecta
+    /// The namespace the served app subscribes to on the hot reloading socket
+    pub namespace: String,
+
+    /// The served crate followed by the workspace members it depends on
+    pub crates: Vec<WatchedCrate>,
+
+    /// Set when an app that speaks another version of the hot reloading protocol connects. Changes rebuild the app
+    /// instead of hot reloading it until the next rebuild
+    incompatible: Arc<AtomicBool>,
+}
+
+impl HotReloadState {
+    /// Create the hot reloading state for the served crate and the workspace members it depends on
+    pub fn new(config: &CrateConfig) -> Self {
+        let crates = std::iter::once(config.crate_dir.clone())
+            .chain(workspace_dependencies(config))
+            .map(|crate_dir| WatchedCrate::new(crate_dir, config.hot_reload))
+            .collect();
+
+        Self {
+            receiver: Default::default(),
+            namespace: hot_reload_namespace(config),
+            crates,
+            incompatible: Default::default(),
+        }
+    }
+
+    /// Check if rsx hot reloading is enabled
+    pub fn enabled(&self) -> bool {
+        self.crates.iter().any(|watched| watched.file_map.is_some())
+    }
+
+    /// Rebuild the app on every change because it can't read the hot reloading messages of this CLI
+    pub fn mark_incompatible(&self) {
+        self.incompatible.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if an app that can't read the hot reloading messages of this CLI connected since the last rebuild
+    pub fn is_incompatible(&self) -> bool {
+        self.incompatible.load(Ordering::SeqCst)
+    }
+
+    /// Find all templates that have changed since the last full rebuild in any of the watched crates
+    pub fn modified_templates(&self) -> Vec<Template> {
+        self.crates
+            .iter()
+            .filter_map(|watched| watched.file_map.as_ref())
+            .flat_map(|file_map| {
+                file_map
+                    .lock()
+                    .unwrap()
+                    .map
+                    .values()
+                    .flat_map(|v| v.templates.values().copied())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Find the latest literal component props of every rsx call they changed in since the last full rebuild
+    pub fn modified_literals(&self) -> Vec<HotLiterals> {
+        self.crates
+            .iter()
+            .filter_map(|watched| watched.file_map.as_ref())
+            .flat_map(|file_map| {
+                file_map
+                    .lock()
+                    .unwrap()
+                    .map
+                    .values()
+                    .flat_map(|v| v.literals.values().cloned())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// A crate the file watcher tracks
+///
+/// Every crate gets its own file map and watcher so a change in one workspace member only hot reloads or rebuilds
+/// from what changed in that member.
+#[derive(Clone)]
+pub struct WatchedCrate {
+    /// The directory of the crate
+    pub crate_dir: PathBuf,
+
+    /// The file map that tracks the rsx in the crate if hot reloading is enabled
     pub file_map: Option<SharedFileMap>,
 }
 
+impl WatchedCrate {
+    fn new(crate_dir: PathBuf, hot_reload: bool) -> Self {
+        let file_map = hot_reload.then(|| {
+            let FileMapBuildResult { map, errors } =
+                FileMap::<HtmlCtx>::create(crate_dir.clone()).unwrap();
+
+            for err in errors {
+                tracing::error!("{}", err);
+            }
+
+            Arc::new(Mutex::new(map))
+        });
+
+        Self {
+            crate_dir,
+            file_map,
+        }
+    }
+}
+
 type SharedFileMap = Arc<Mutex<FileMap<HtmlCtx>>>;
 
-/// Sets up a file watcher.
+/// The hot reloading namespace of the served app
+///
+/// Apps in the same workspace share a target directory, so each app gets its own socket named after its package and
+/// only receives the messages tagged with its namespace.
+pub(crate) fn hot_reload_namespace(config: &CrateConfig) -> String {
+    match &config.manifest.package {
+        Some(package) => package.name.clone(),
+        None => config
+            .executable
+            .executable()
+            .unwrap_or("dioxus_app")
+            .to_string(),
+    }
+}
+
+/// Find the workspace members the served crate depends on by path, directly or through other members
+fn workspace_dependencies(config: &CrateConfig) -> Vec<PathBuf> {
+    let Ok(metadata) = cargo_metadata::MetadataCommand::new()
+        .manifest_path(config.crate_dir.join("Cargo.toml"))
+        .no_deps()
+        .exec()
+    else {
+        return Vec::new();
+    };
+
+    let member_dir = |package: &cargo_metadata::Package| {
+        package
+            .manifest_path
+            .parent()
+            .map(|dir| dir.as_std_path().to_path_buf())
+    };
+
+    let mut found = vec![config.crate_dir.clone()];
+    let mut queue = vec![config.crate_dir.clone()];
+    while let Some(crate_dir) = queue.pop() {
+        let Some(package) = metadata
+            .packages
+            .iter()
+            .find(|package| member_dir(package).as_ref() == Some(&crate_dir))
+        else {
+            continue;
+        };
+
+        // Dev and build dependencies don't end up in the app, so changes to them can't be hot reloaded
+        for dependency in &package.dependencies {
+            let Some(path) = &dependency.path else {
+                continue;
+            };
+            if dependency.kind != cargo_metadata::DependencyKind::Normal {
+                continue;
+            }
+
+            let path = path.as_std_path().to_path_buf();
+            let is_member = metadata
+                .packages
+                .iter()
+                .any(|package| member_dir(package).as_ref() == Some(&path));
+            if is_member && !found.contains(&path) {
+                found.push(path.clone());
+                queue.push(path);
+            }
+        }
+    }
+
+    found.split_off(1)
+}
+
+/// Sets up a file watcher for every watched crate.
 ///
 /// Will attempt to hotreload HTML, RSX (.rs), and CSS
 async fn setup_file_watcher<F: Fn() -> Result<BuildResult> + Send + 'static>(
@@ -34,58 +214,90 @@ async fn setup_file_watcher<F: Fn() -> Result<BuildResult> + Send + 'static>(
     config: &CrateConfig,
     web_info: Option<WebServerInfo>,
     hot_reload: HotReloadState,
-) -> Result<RecommendedWatcher> {
-    let mut last_update_time = chrono::Local::now().timestamp();
+) -> Result<Vec<RecommendedWatcher>> {
+    // All the crates end up in the same app, so they share the build. The lock makes sure two crates changing at the
+    // same time don't start two builds at once
+    let build_with = Arc::new(Mutex::new(build_with));
+
+    let mut watchers = Vec::new();
+
+    for watched in &hot_reload.crates {
+        let mut last_update_time = chrono::Local::now().timestamp();
+
+        // Create the file watcher
+        let mut watcher = notify::recommended_watcher({
+            let watcher_config = config.clone();
+            let watched = watched.clone();
+            let hot_reload = hot_reload.clone();
+            let build_with = build_with.clone();
+            let web_info = web_info.clone();
+            move |info: notify::Result<notify::Event>| {
+                let Ok(e) = info else {
+                    return;
+                };
+                watch_event(
+                    e,
+                    &mut last_update_time,
+                    &hot_reload,
+                    &watched,
+                    &watcher_config,
+                    &build_with,
+                    &web_info,
+                );
+            }
+        })
+        .expect("Failed to create file watcher - please ensure you have the required permissions to watch the specified directories.");
+
+        // Watch the specified paths
+        for path in watch_paths(config, &watched.crate_dir) {
+            let mode = notify::RecursiveMode::Recursive;
+
+            if let Err(err) = watcher.watch(&path, mode) {
+                tracing::warn!("Failed to watch path: {}", err);
+            }
+        }
+
+        watchers.push(watcher);
+    }
+
+    Ok(watchers)
+}
+
+/// The paths to watch in a crate
+///
+/// Workspace members only contribute the paths that exist in them. The assets and Dioxus.toml belong to the served crate.
+fn watch_paths(config: &CrateConfig, crate_dir: &Path) -> Vec<PathBuf> {
+    let is_served_crate = crate_dir == config.crate_dir;
 
     // file watcher: check file change
     let mut allow_watch_path = config.dioxus_config.web.watcher.watch_path.clone();
 
     // Extend the watch path to include the assets directory - this is so we can hotreload CSS and other assets by default
-    allow_watch_path.push(config.dioxus_config.application.asset_dir.clone());
+    if is_served_crate {
+        allow_watch_path.push(config.dioxus_config.application.asset_dir.clone());
+    }
 
     // Extend the watch path to include Cargo.toml and Dioxus.toml
     allow_watch_path.push("Cargo.toml".to_string().into());
-    allow_watch_path.push("Dioxus.toml".to_string().into());
-    allow_watch_path.dedup();
-
-    // Create the file watcher
-    let mut watcher = notify::recommended_watcher({
-        let watcher_config = config.clone();
-        move |info: notify::Result<notify::Event>| {
-            let Ok(e) = info else {
-                return;
-            };
-            watch_event(
-                e,
-                &mut last_update_time,
-                &hot_reload,
-                &watcher_config,
-                &build_with,
-                &web_info,
-            );
-        }
-    })
-    .expect("Failed to create file watcher - please ensure you have the required permissions to watch the specified directories.");
-
-    // Watch the specified paths
-    for sub_path in allow_watch_path {
-        let path = &config.crate_dir.join(sub_path);
-        let mode = notify::RecursiveMode::Recursive;
-
-        if let Err(err) = watcher.watch(path, mode) {
-            tracing::warn!("Failed to watch path: {}", err);
-        }
+    if is_served_crate {
+        allow_watch_path.push("Dioxus.toml".to_string().into());
     }
+    allow_watch_path.dedup();
 
-    Ok(watcher)
+    allow_watch_path
+        .into_iter()
+        .map(|sub_path| crate_dir.join(sub_path))
+        .filter(|path| is_served_crate || path.exists())
+        .collect()
 }
 
 fn watch_event<F>(
     event: notify::Event,
     last_update_time: &mut i64,
     hot_reload: &HotReloadState,
+    watched: &WatchedCrate,
     config: &CrateConfig,
-    build_with: &F,
+    build_with: &Mutex<F>,
     web_info: &Option<WebServerInfo>,
 ) where
     F: Fn() -> Result<BuildResult> + Send + 'static,
@@ -106,10 +318,11 @@ fn watch_event<F>(
     // By default we want to not do a full rebuild, and instead let the hot reload system invalidate it
     let mut needs_full_rebuild = false;
 
-    if let Some(file_map) = &hot_reload.file_map {
+    if let Some(file_map) = &watched.file_map {
         hotreload_files(
             hot_reload,
             file_map,
+            &watched.crate_dir,
             &mut needs_full_rebuild,
             &event,
             config,
@@ -117,19 +330,37 @@ fn watch_event<F>(
     }
 
     if needs_full_rebuild {
-        full_rebuild(build_with, last_update_time, config, event, web_info);
+        full_rebuild(
+            build_with,
+            last_update_time,
+            hot_reload,
+            config,
+            event,
+            web_info,
+        );
     }
 }
 
 fn full_rebuild<F>(
-    build_with: &F,
+    build_with: &Mutex<F>,
     last_update_time: &mut i64,
+    hot_reload: &HotReloadState,
     config: &CrateConfig,
     event: notify::Event,
     web_info: &Option<WebServerInfo>,
 ) where
     F: Fn() -> Result<BuildResult> + Send + 'static,
 {
+    let build_with = build_with.lock().unwrap();
+
+    // Let the running app save the state of its restorable hooks so the new build picks up where it left off
+    if hot_reload.enabled() {
+        hot_reload.receiver.send_message(HotReloadMsg::SaveState);
+    }
+
+    // The new build checks its version again when it connects
+    hot_reload.incompatible.store(false, Ordering::SeqCst);
+
     match build_with() {
         Ok(res) => {
             *last_update_time = chrono::Local::now().timestamp();
@@ -155,6 +386,7 @@ fn full_rebuild<F>(
 fn hotreload_files(
     hot_reload: &HotReloadState,
     file_map: &SharedFileMap,
+    crate_dir: &Path,
     needs_full_rebuild: &mut bool,
     event: &notify::Event,
     config: &CrateConfig,
@@ -179,13 +411,25 @@ fn hotreload_files(
         }
 
         // If the file was hotreloaded, update the file map in place
-        match rsx_file_map.update_rsx(path, &config.crate_dir) {
-            Ok(UpdateResult::UpdatedRsx(msgs)) => {
-                messages.extend(msgs.into_iter().map(HotReloadMsg::UpdateTemplate));
+        let result = rsx_file_map.update_rsx(path, crate_dir);
+
+        // Show the app why the change didn't apply, or clear the last error if it did
+        let relative = path.strip_prefix(crate_dir).unwrap_or(path);
+        if let Some(msg) = HotReloadMsg::report_error(relative, &result) {
+            hot_reload.receiver.send_message(msg);
+        }
+
+        match result {
+            Ok(UpdateResult::UpdatedRsx {
+                templates,
+                literals,
+            }) => {
+                messages.extend(templates.into_iter().map(HotReloadMsg::UpdateTemplate));
+                messages.extend(literals.into_iter().map(HotReloadMsg::UpdateLiterals));
             }
 
             // If the file was not updated, we need to do a full rebuild
-            Ok(UpdateResult::NeedsRebuild) => {
+            Ok(UpdateResult::NeedsRebuild { .. }) => {
                 tracing::trace!("Needs full rebuild because file changed: {:?}", path);
                 *needs_full_rebuild = true;
             }
@@ -195,6 +439,11 @@ fn hotreload_files(
         }
     }
 
+    // The app can't read the messages, rebuild it instead
+    if !messages.is_empty() && hot_reload.is_incompatible() {
+        *needs_full_rebuild = true;
+    }
+
     // If full rebuild, extend the file map with the new file map
     // This will wipe away any previous cached changed templates
     if *needs_full_rebuild {
@@ -202,7 +451,7 @@ fn hotreload_files(
         let FileMapBuildResult {
             map: new_file_map,
             errors,
-        } = FileMap::<HtmlCtx>::create(config.crate_dir.clone()).unwrap();
+        } = FileMap::<HtmlCtx>::create(crate_dir.to_path_buf()).unwrap();
 
         for err in errors {
             tracing::error!("{}", err);
@@ -245,27 +494,23 @@ fn hotreload_file(
         return None;
     }
 
-    // Attempt to hotreload css in the asset directory
-    // Currently no other assets are hotreloaded, but in theory we could hotreload pngs/jpegs, etc
-    //
-    // All potential hotreloadable mime types:
-    // "bin" |"css" | "csv" | "html" | "ico" | "js" | "json" | "jsonld" | "mjs" | "rtf" | "svg" | "mp4"
-    if ext == "css" {
+    // Attempt to hotreload stylesheets, scripts and images in the asset directory
+    if is_hot_reloadable_asset(path) {
         let asset_dir = config
             .crate_dir
             .join(&config.dioxus_config.application.asset_dir);
 
-        // Only if the CSS is in the asset directory, and we're tracking it, do we hotreload it
+        // Only if the asset is in the asset directory, and we're tracking it, do we hotreload it
         // Otherwise, we need to do a full rebuild since the user might be doing an include_str! on it
-        if attempt_css_reload(path, asset_dir, rsx_file_map, config, messages).is_none() {
+        if attempt_asset_reload(path, asset_dir, rsx_file_map, config, messages).is_none() {
             *needs_full_rebuild = true;
         }
 
         return None;
     }
 
-    // If the file is not rsx or css and we've already not needed a full rebuild, return
-    if ext != "rs" && ext != "css" {
+    // If the file is not rust or an included rsx template and we've already not needed a full rebuild, return
+    if ext != "rs" && ext != "rsx" {
         *needs_full_rebuild = true;
         return None;
     }
@@ -273,41 +518,30 @@ fn hotreload_file(
     Some(())
 }
 
-fn attempt_css_reload(
+fn attempt_asset_reload(
     path: &Path,
     asset_dir: PathBuf,
     rsx_file_map: &std::sync::MutexGuard<'_, FileMap<HtmlCtx>>,
     config: &CrateConfig,
     messages: &mut Vec<HotReloadMsg>,
 ) -> Option<()> {
-    // If the path is not in the asset directory, return
-    if !path.starts_with(asset_dir) {
-        return None;
-    }
-
     // Get the local path of the asset (ie var.css or some_dir/var.css as long as the dir is under the asset dir)
-    let local_path = local_path_of_asset(path)?;
+    let local_path = path.strip_prefix(asset_dir).ok()?;
 
     // Make sure we're actually tracking this asset...
-    _ = rsx_file_map.is_tracking_asset(&local_path)?;
+    if !rsx_file_map.tracks_asset_file(path) {
+        return None;
+    }
 
     // copy the asset over to the output directory
-    // todo this whole css hotreloading should be less hacky and more robust
-    _ = fs_extra::copy_items(
-        &[path],
-        config.out_dir(),
-        &CopyOptions::new().overwrite(true),
-    );
+    let out_dir = config.out_dir().join(local_path.parent()?);
+    _ = fs_extra::copy_items(&[path], out_dir, &CopyOptions::new().overwrite(true));
 
-    messages.push(HotReloadMsg::UpdateAsset(local_path));
+    messages.push(HotReloadMsg::update_asset(path, local_path.to_path_buf())?);
 
     Some(())
 }
 
-fn local_path_of_asset(path: &Path) -> Option<PathBuf> {
-    path.file_name()?.to_str()?.to_string().parse().ok()
-}
-
 pub(crate) trait Platform {
     fn start(config: &CrateConfig, serve: &ConfigOptsServe) -> Result<Self>
     where
@@ -9,11 +9,7 @@ use crate::{
     BuildResult, Result,
 };
 use dioxus_cli_config::CrateConfig;
-use dioxus_rsx::hot_reload::*;
-use std::{
-    net::{SocketAddr, UdpSocket},
-    sync::Arc,
-};
+use std::net::{SocketAddr, UdpSocket};
 
 mod proxy;
 mod server;
@@ -27,7 +23,7 @@ pub async fn startup(config: CrateConfig, serve_cfg: &ConfigOptsServe) -> Result
 
     let ip = get_ip().unwrap_or(String::from("0.0.0.0"));
 
-    let hot_reload_state = build_hotreload_filemap(&config);
+    let hot_reload_state = HotReloadState::new(&config);
 
     serve(ip, config, hot_reload_state, serve_cfg).await
 }
@@ -190,19 +186,3 @@ fn set_ctrlc_handler(config: &CrateConfig) {
         std::process::exit(0);
     });
 }
-
-fn build_hotreload_filemap(config: &CrateConfig) -> HotReloadState {
-    HotReloadState {
-        file_map: config.hot_reload.then(|| {
-            let FileMapBuildResult { map, errors } =
-                FileMap::create(config.crate_dir.clone()).unwrap();
-
-            for err in errors {
-                tracing::error!("{}", err);
-            }
-
-            Arc::new(Mutex::new(map))
-        }),
-        receiver: Default::default(),
-    }
-}
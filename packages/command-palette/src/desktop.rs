@@ -0,0 +1,13 @@
+use dioxus_desktop::{use_global_shortcut, ShortcutHandle, ShortcutRegistryError};
+
+use crate::use_command_registry;
+
+/// Toggle the command palette with a global shortcut like `"CmdOrCtrl+Shift+P"`.
+///
+/// The shortcut is removed when the component that calls this hook is dropped.
+pub fn use_command_palette_shortcut(
+    accelerator: &'static str,
+) -> Result<ShortcutHandle, ShortcutRegistryError> {
+    let mut registry = use_command_registry();
+    use_global_shortcut(accelerator, move || registry.toggle())
+}
@@ -0,0 +1,74 @@
+/// How well a search query matches the name of a command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match. Better matches have higher scores.
+    pub score: i32,
+    /// The indices of the characters in the name that matched the query
+    pub indices: Vec<usize>,
+}
+
+/// Match a query against some text.
+///
+/// Every character of the query must appear in the text in the same order, ignoring case and whitespace in the query.
+/// Characters at the start of words and runs of consecutive characters score higher, so `"os"` ranks "Open settings"
+/// above "Close tabs".
+///
+/// ```rust
+/// # use dioxus_command_palette::fuzzy_match;
+/// assert!(fuzzy_match("os", "Open settings").is_some());
+/// assert!(fuzzy_match("so", "Open settings").is_none());
+/// ```
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    let mut remaining = query.iter().peekable();
+    let mut score = 0;
+    let mut indices = Vec::with_capacity(query.len());
+    let mut previous: Option<char> = None;
+
+    for (index, c) in text.chars().enumerate() {
+        let Some(&&expected) = remaining.peek() else {
+            break;
+        };
+
+        if c.to_lowercase().eq(std::iter::once(expected)) {
+            score += 1;
+
+            // Reward runs of matching characters
+            if indices.last().is_some_and(|last| *last + 1 == index) {
+                score += 4;
+            }
+
+            // Reward matching the start of a word
+            let word_start = match previous {
+                None => true,
+                Some(previous) => {
+                    !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+                }
+            };
+            if word_start {
+                score += 6;
+            }
+
+            indices.push(index);
+            remaining.next();
+        }
+
+        previous = Some(c);
+    }
+
+    if remaining.peek().is_some() {
+        return None;
+    }
+
+    // Prefer matches that start early in the text
+    if let Some(first) = indices.first() {
+        score -= (*first).min(5) as i32;
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
@@ -0,0 +1,30 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+// cannot use forbid, because props derive macro generates #[allow(missing_docs)]
+#![deny(missing_docs)]
+#![allow(non_snake_case)]
+
+mod fuzzy;
+pub use fuzzy::*;
+
+mod registry;
+pub use registry::*;
+
+mod palette;
+pub use palette::*;
+
+#[cfg(feature = "desktop")]
+mod desktop;
+#[cfg(feature = "desktop")]
+pub use desktop::*;
+
+/// A collection of useful items most applications might need.
+pub mod prelude {
+    pub use crate::fuzzy::*;
+    pub use crate::palette::*;
+    pub use crate::registry::*;
+
+    #[cfg(feature = "desktop")]
+    pub use crate::desktop::*;
+}
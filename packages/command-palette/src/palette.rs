@@ -0,0 +1,109 @@
+use dioxus_lib::prelude::*;
+
+use crate::use_command_registry;
+
+/// A searchable list of every command registered with [`use_command`](crate::use_command).
+///
+/// The palette is only rendered while it is open. Open it with [`CommandRegistry::open`](crate::CommandRegistry::open).
+/// Typing filters the commands with fuzzy matching, the arrow keys move the selection, enter runs the selected command
+/// and escape closes the palette.
+#[component]
+pub fn CommandPalette(
+    /// The text shown in the search box before anything is typed
+    #[props(default = "Type a command...".to_string(), into)]
+    placeholder: String,
+
+    /// The text shown when no command matches the search
+    #[props(default = "No matching commands".to_string(), into)]
+    empty: String,
+) -> Element {
+    let mut registry = use_command_registry();
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+
+    if !registry.is_open() {
+        return None;
+    }
+
+    let matches = registry.search(&query.read());
+    let count = matches.len();
+    let selected_index = selected().min(count.saturating_sub(1));
+    let selected_command = matches.get(selected_index).map(|(command, _)| command.id());
+
+    let mut close = move || {
+        query.set(String::new());
+        selected.set(0);
+        registry.close();
+    };
+
+    rsx! {
+        div { class: "command-palette", role: "dialog", aria_modal: "true",
+            input {
+                class: "command-palette-input",
+                r#type: "text",
+                role: "combobox",
+                aria_expanded: "true",
+                autofocus: true,
+                placeholder: "{placeholder}",
+                value: "{query}",
+                oninput: move |event| {
+                    query.set(event.value());
+                    selected.set(0);
+                },
+                onkeydown: move |event| match event.key() {
+                    Key::ArrowDown if count > 0 => selected.set((selected_index + 1) % count),
+                    Key::ArrowUp if count > 0 => selected.set((selected_index + count - 1) % count),
+                    Key::Enter => {
+                        if let Some(id) = selected_command {
+                            close();
+                            registry.run(id);
+                        }
+                    }
+                    Key::Escape => close(),
+                    _ => {}
+                }
+            }
+            ul { class: "command-palette-results", role: "listbox",
+                for (index, id, command, matched) in matches.into_iter().enumerate().map(|(index, (command, matched))| (index, command.id(), command, matched)) {
+                    li {
+                        key: "{id}",
+                        class: "command-palette-item",
+                        role: "option",
+                        aria_selected: index == selected_index,
+                        onmouseenter: move |_| selected.set(index),
+                        onclick: move |_| {
+                            close();
+                            registry.run(id);
+                        },
+                        {command.icon.clone()}
+                        span { class: "command-palette-name",
+                            for (text, highlighted) in highlight(&command.name, &matched.indices) {
+                                if highlighted {
+                                    mark { "{text}" }
+                                } else {
+                                    "{text}"
+                                }
+                            }
+                        }
+                    }
+                }
+                if count == 0 {
+                    li { class: "command-palette-empty", "{empty}" }
+                }
+            }
+        }
+    }
+}
+
+/// Split the text into runs of characters that did or didn't match the query
+fn highlight(text: &str, indices: &[usize]) -> Vec<(String, bool)> {
+    let mut runs: Vec<(String, bool)> = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        let highlighted = indices.contains(&index);
+        match runs.last_mut() {
+            Some((run, run_highlighted)) if *run_highlighted == highlighted => run.push(c),
+            _ => runs.push((c.to_string(), highlighted)),
+        }
+    }
+    runs
+}
@@ -0,0 +1,178 @@
+use dioxus_lib::prelude::*;
+
+use crate::{fuzzy_match, FuzzyMatch};
+
+/// A unique id for a registered command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CommandId(usize);
+
+impl std::fmt::Display for CommandId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A command that can be run from the command palette
+#[derive(Clone)]
+pub struct Command {
+    id: CommandId,
+    /// The name of the command. This is what the palette shows and searches.
+    pub name: String,
+    /// An icon shown next to the name of the command
+    pub icon: Element,
+    callback: UseCallback<()>,
+}
+
+impl Command {
+    /// Get the id of the command
+    pub fn id(&self) -> CommandId {
+        self.id
+    }
+}
+
+/// The commands registered anywhere in the app and whether the command palette is open.
+///
+/// There is one registry per app. It lives in the root scope, so every component shares it no matter where it is
+/// mounted.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CommandRegistry {
+    commands: Signal<Vec<Command>>,
+    open: Signal<bool>,
+    next_id: CopyValue<usize>,
+}
+
+impl CommandRegistry {
+    fn new_in_root() -> Self {
+        Self {
+            commands: Signal::new_in_scope(Vec::new(), ScopeId::ROOT),
+            open: Signal::new_in_scope(false, ScopeId::ROOT),
+            next_id: CopyValue::new_in_scope(0, ScopeId::ROOT),
+        }
+    }
+
+    /// Get the registered commands in the order they were registered
+    pub fn commands(&self) -> Vec<Command> {
+        self.commands.read().clone()
+    }
+
+    /// Search the registered commands. The best matches come first and commands that match equally well keep the order
+    /// they were registered in.
+    pub fn search(&self, query: &str) -> Vec<(Command, FuzzyMatch)> {
+        let mut matches: Vec<_> = self
+            .commands
+            .read()
+            .iter()
+            .filter_map(|command| {
+                fuzzy_match(query, &command.name).map(|matched| (command.clone(), matched))
+            })
+            .collect();
+        matches.sort_by_key(|(_, matched)| std::cmp::Reverse(matched.score));
+        matches
+    }
+
+    /// Close the command palette and run a command
+    pub fn run(&mut self, id: CommandId) {
+        let command = self
+            .commands
+            .peek()
+            .iter()
+            .find(|command| command.id == id)
+            .map(|command| command.callback);
+
+        self.close();
+        if let Some(callback) = command {
+            callback.call();
+        }
+    }
+
+    /// Check if the command palette is open
+    pub fn is_open(&self) -> bool {
+        (self.open)()
+    }
+
+    /// Open the command palette
+    pub fn open(&mut self) {
+        self.open.set(true);
+    }
+
+    /// Close the command palette
+    pub fn close(&mut self) {
+        self.open.set(false);
+    }
+
+    /// Open the command palette if it is closed and close it if it is open
+    pub fn toggle(&mut self) {
+        let open = !*self.open.peek();
+        self.open.set(open);
+    }
+
+    fn register(&mut self, name: String, icon: Element, callback: UseCallback<()>) -> CommandId {
+        let id = CommandId(self.next_id.cloned());
+        *self.next_id.write() += 1;
+        self.commands.write().push(Command {
+            id,
+            name,
+            icon,
+            callback,
+        });
+        id
+    }
+
+    fn rename(&mut self, id: CommandId, name: String) {
+        let changed = self
+            .commands
+            .peek()
+            .iter()
+            .any(|command| command.id == id && command.name != name);
+        if changed {
+            if let Some(command) = self.commands.write().iter_mut().find(|c| c.id == id) {
+                command.name = name;
+            }
+        }
+    }
+
+    fn unregister(&mut self, id: CommandId) {
+        // The registry may already be gone if the whole app is being dropped
+        if let Ok(mut commands) = self.commands.try_write() {
+            commands.retain(|command| command.id != id);
+        }
+    }
+}
+
+/// Get the command registry of the app.
+pub fn use_command_registry() -> CommandRegistry {
+    use_root_context(CommandRegistry::new_in_root)
+}
+
+/// Register a command in the command palette while the component that calls this hook is mounted.
+///
+/// The callback is always the one passed in the latest render, so it can use any state of the component. The command
+/// is removed when the component is dropped.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_command_palette::prelude::*;
+/// fn Editor() -> Element {
+///     let mut text = use_signal(String::new);
+///     use_command("Clear text", None, move || text.set(String::new()));
+///
+///     rsx! {
+///         textarea { value: "{text}", oninput: move |e| text.set(e.value()) }
+///     }
+/// }
+/// ```
+pub fn use_command(
+    name: impl ToString,
+    icon: Element,
+    callback: impl FnMut() + 'static,
+) -> CommandId {
+    let mut registry = use_command_registry();
+    let callback = use_callback(callback);
+    let name = name.to_string();
+
+    let id = use_hook(|| registry.register(name.clone(), icon, callback));
+    registry.rename(id, name);
+    use_drop(move || registry.unregister(id));
+
+    id
+}
@@ -0,0 +1,30 @@
+use dioxus_command_palette::fuzzy_match;
+
+#[test]
+fn matches_characters_in_order() {
+    let matched = fuzzy_match("opst", "Open settings").unwrap();
+    assert_eq!(matched.indices, vec![0, 1, 5, 7]);
+
+    assert!(fuzzy_match("tso", "Open settings").is_none());
+    assert!(fuzzy_match("open settings!", "Open settings").is_none());
+}
+
+#[test]
+fn ignores_case_and_whitespace() {
+    assert!(fuzzy_match("OPEN", "open settings").is_some());
+    assert!(fuzzy_match("open set", "OpenSettings").is_some());
+}
+
+#[test]
+fn empty_query_matches_everything() {
+    assert!(fuzzy_match("", "Open settings").unwrap().indices.is_empty());
+}
+
+#[test]
+fn prefers_word_starts_and_runs() {
+    let score = |query, text| fuzzy_match(query, text).unwrap().score;
+
+    assert!(score("os", "Open settings") > score("os", "Close tabs"));
+    assert!(score("set", "Open settings") > score("set", "Reset layout"));
+    assert!(score("sf", "Save file") > score("sf", "Transform"));
+}
@@ -0,0 +1,64 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_command_palette::prelude::*;
+
+#[test]
+fn lists_commands_from_the_whole_tree() {
+    fn app() -> Element {
+        let mut registry = use_command_registry();
+        use_hook(move || registry.open());
+        use_command("Save file", None, || {});
+
+        rsx! {
+            Settings {}
+            CommandPalette {}
+        }
+    }
+
+    #[component]
+    fn Settings() -> Element {
+        use_command("Open settings", rsx! { span { class: "icon" } }, || {});
+        None
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains(
+        r#"<li class="command-palette-item" role="option" aria-selected=true><span class="command-palette-name">Save file</span></li>"#
+    ));
+    assert!(html.find("Save file").unwrap() < html.find("Open settings").unwrap());
+    assert!(html.contains(
+        r#"<span class="icon"></span><span class="command-palette-name">Open settings</span>"#
+    ));
+}
+
+#[test]
+fn runs_commands_and_closes() {
+    use std::{cell::Cell, rc::Rc};
+
+    fn app(ran: Rc<Cell<bool>>) -> Element {
+        use_command("Run me", None, move || ran.set(true));
+        rsx! { CommandPalette {} }
+    }
+
+    let ran = Rc::new(Cell::new(false));
+    let mut dom = VirtualDom::new_with_props(app, ran.clone());
+    dom.rebuild_in_place();
+
+    dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            let mut registry = ScopeId::ROOT.consume_context::<CommandRegistry>().unwrap();
+            registry.open();
+            assert!(registry.is_open());
+
+            let id = registry.commands()[0].id();
+            registry.run(id);
+            assert!(ran.get());
+            assert!(!registry.is_open());
+        })
+    });
+}
@@ -0,0 +1,39 @@
+use dioxus_rsx::CallBody;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::path::Path;
+use syn::parse::Parser;
+use syn::{Error, LitStr, Result};
+
+/// Expand `rsx_include!("path/to/template.rsx")` into the template stored in that file
+pub(crate) fn expand(path: &LitStr) -> Result<TokenStream> {
+    let value = path.value();
+    let relative = value.trim_start_matches("./");
+
+    // Proc macros can't see the file they are called from, so paths are resolved from the crate root
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| Error::new(path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+    let full_path = Path::new(&manifest_dir).join(relative);
+
+    let contents = std::fs::read_to_string(&full_path).map_err(|err| {
+        Error::new(
+            path.span(),
+            format!("Failed to read `{}`: {err}", full_path.display()),
+        )
+    })?;
+
+    let body = CallBody::parse_strict
+        .parse_str(&contents)
+        .map_err(|err| Error::new(path.span(), format!("Failed to parse `{relative}`: {err}")))?;
+
+    let full_path = full_path.to_string_lossy();
+    let template = body.render_included(relative);
+
+    Ok(quote! {
+        {
+            // Rebuild the crate when the template changes
+            const _: &str = include_str!(#full_path);
+            #template
+        }
+    })
+}
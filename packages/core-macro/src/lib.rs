@@ -8,7 +8,9 @@ use quote::ToTokens;
 use syn::parse_macro_input;
 
 mod component;
+mod include;
 mod props;
+mod styled;
 mod utils;
 
 use dioxus_rsx as rsx;
@@ -21,6 +23,33 @@ pub fn format_args_f(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derive the builder that components use to receive their props.
+///
+/// Fields can be configured with `#[props(...)]`:
+/// - `default` or `default = expr` makes the prop optional. The expression runs every time the props are built
+/// - `optional` makes an `Option<T>` prop optional and accepts `T` directly
+/// - `into` accepts anything that converts into the field type
+/// - `lazy` lets a [`Lazy<T>`](https://docs.rs/dioxus-core/latest/dioxus_core/prelude/struct.Lazy.html) prop accept a closure that only runs if the component reads the prop
+/// - `extends = element` collects the attributes and event handlers of an element
+/// - `validate = path` checks the final value with a `fn(&T) -> Result<(), impl Display>` when the props are built,
+///   and panics with the error if the check fails
+///
+/// ```rust, ignore
+/// fn not_empty(value: &str) -> Result<(), &'static str> {
+///     if value.is_empty() {
+///         return Err("the label must not be empty");
+///     }
+///     Ok(())
+/// }
+///
+/// #[derive(Props, Clone, PartialEq)]
+/// struct BadgeProps {
+///     #[props(into, validate = not_empty)]
+///     label: String,
+///     #[props(default = 1)]
+///     count: u32,
+/// }
+/// ```
 #[proc_macro_derive(Props, attributes(props))]
 pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
@@ -60,7 +89,10 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
-/// You can wrap your web component in a custom component to add type checking:
+/// Web components accept any attribute unless you declare them with the `custom_elements!` macro from `dioxus::html`,
+/// which gives them the same compile-time checks as built-in elements.
+///
+/// You can also wrap your web component in a custom component to add type checking:
 /// ```rust, no_run
 /// # use dioxus::prelude::*;
 /// #[component]
@@ -93,6 +125,20 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
+/// An attribute without a value is shorthand for the variable with the same name, even for boolean attributes like
+/// `disabled`. Write `disabled: true` to turn a boolean attribute on:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// let disabled = false;
+/// rsx! {
+///     input { r#type: "checkbox", checked: true, disabled }
+/// };
+/// ```
+///
+/// Attributes that only accept a fixed set of keywords, like the `type` of an `input` or `button` or the `dir` of any element,
+/// are checked at compile time when their value is a static string. `input { r#type: "chekbox" }` is a warning.
+///
 /// ### Optional Attributes
 ///
 /// You can include optional attributes with an unterminated if statement as the value of the attribute:
@@ -115,6 +161,22 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
+/// ### Conditional Attribute Groups
+///
+/// If several attributes depend on the same condition, you can group them in an unterminated if block inside the element:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # let selected = true;
+/// rsx! {
+///     div {
+///         class: "item",
+///         // Adds the "active" class and the aria-selected attribute only if selected is true
+///         if selected { class: "active", aria_selected: "true" },
+///     }
+/// };
+/// ```
+///
 /// ### Raw Attributes
 ///
 /// Dioxus defaults to attributes that are type checked as html. If you want to include an attribute that is not included in the html spec, you can use the `raw` attribute surrounded by quotes:
@@ -129,6 +191,59 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
+/// Raw attributes made of hyphen-joined identifiers can also be written without quotes:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// rsx! {
+///     button {
+///         data-testid: "submit",
+///         hx-post: "/items",
+///     }
+/// };
+/// ```
+///
+/// ### Spreading Attributes
+///
+/// You can spread any iterator of [`Attribute`](https://docs.rs/dioxus-core/latest/dioxus_core/struct.Attribute.html)s into an element with the `..` syntax. This is useful for attributes that are only known at runtime:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// let data_attributes = vec![("data-id", "1"), ("data-kind", "primary")];
+/// rsx! {
+///     div {
+///         class: "card",
+///         // Anything that converts into an attribute can be spread, including (name, value) pairs
+///         ..data_attributes
+///     }
+/// };
+/// ```
+///
+/// Attribute names are `&'static str`, so the names of the pairs you spread can't be built at runtime.
+///
+/// Wrapper components can collect every attribute and event handler of an element with `#[props(extends = element)]`
+/// and spread them onto the element they render:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[derive(Props, Clone, PartialEq)]
+/// struct ButtonProps {
+///     #[props(extends = button)]
+///     attributes: Vec<Attribute>,
+///     children: Element,
+/// }
+///
+/// fn Button(props: ButtonProps) -> Element {
+///     rsx! {
+///         button { class: "button", ..props.attributes, {props.children} }
+///     }
+/// }
+///
+/// rsx! {
+///     Button { r#type: "submit", onclick: move |_| println!("submitted"), "Submit" }
+/// };
+/// ```
+///
 /// ## Text
 ///
 /// You can include text in your markup as a string literal:
@@ -145,6 +260,17 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
+/// HTML character references like `&nbsp;`, `&amp;` or `&#169;` in text are decoded when the macro is expanded, so they
+/// render the same way on every platform. Every named reference in the HTML spec is supported. Unknown names are left
+/// as written like a browser would, with an `unknown_html_entity` warning (`RSX0005`) since they are usually typos.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// rsx! {
+///     "Tom&nbsp;&amp;&nbsp;Jerry"
+/// };
+/// ```
+///
 /// ## Components
 ///
 /// You can render any [`macro@crate::component`]s you created inside your markup just like elements. Components must either start with a capital letter or contain a `_` character.
@@ -163,6 +289,24 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 /// };
 /// ```
 ///
+/// ### Optional Props
+///
+/// Props with a default value can be set with `prop?: value`, where `value` is an [`Option`]. The prop is only set if
+/// the value is `Some`, otherwise the component uses the default:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Counter(#[props(default = 1)] step: i32) -> Element {
+///     rsx! { "+{step}" }
+/// }
+///
+/// let step: Option<i32> = None;
+/// rsx! {
+///     Counter { step?: step }
+/// };
+/// ```
+///
 /// ## If statements
 ///
 /// You can use if statements to conditionally render children. The body of the for if statement is parsed as rsx markup:
@@ -216,6 +360,48 @@ pub fn derive_typed_builder(input: TokenStream) -> TokenStream {
 ///     {(0..10).map(|n| n * n).map(|number| rsx! { div { "{number}" } })}
 /// };
 /// ```
+///
+/// ## Lints
+///
+/// With the `lints` feature, rsx that compiles but probably doesn't do what you meant, like a key outside of a loop,
+/// is reported while the crate compiles. Every lint has a name and a code like `RSX0001`. Set the level of a lint for
+/// a whole crate in its `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.dioxus.lints]
+/// for_loop_without_key = "warn"
+/// RSX0003 = "deny"
+/// ```
+///
+/// Or for one rsx call with `allow`, `help`, `warn` or `deny` attributes at the start of the call:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # let id = 0;
+/// rsx! {
+///     #![allow(key_outside_loop)]
+///     div {
+///         span { key: "{id}" }
+///     }
+/// };
+/// ```
+///
+/// Set the `DIOXUS_RSX_DIAGNOSTICS` environment variable to `json` to also print every lint as a line of JSON for tools.
+///
+/// ## Shared templates
+///
+/// Templates are named after the place they are written, so identical rsx in two places creates two templates. Start
+/// the call with `#![hashed_templates]` to name its templates after their structure instead, so every call with the
+/// same structure shares one template. Hot reloading can't find templates named this way.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # let title = "Hello";
+/// rsx! {
+///     #![hashed_templates]
+///     div { class: "card", h1 { "{title}" } }
+/// };
+/// ```
 #[proc_macro]
 pub fn rsx(tokens: TokenStream) -> TokenStream {
     match syn::parse::<rsx::CallBody>(tokens) {
@@ -224,6 +410,62 @@ pub fn rsx(tokens: TokenStream) -> TokenStream {
     }
 }
 
+/// Load an rsx template from an external `.rsx` file.
+///
+/// The file contains the body of an `rsx!` call. The path is relative to the root of the crate (the directory with its
+/// `Cargo.toml`). Expressions in the template can use any variable in scope where the macro is called.
+///
+/// ```rust, ignore
+/// // templates/card.rsx:
+/// // div { class: "card",
+/// //     h1 { "{title}" }
+/// // }
+/// let title = "Hello";
+/// rsx_include!("templates/card.rsx")
+/// ```
+///
+/// Templates in included files are hot reloaded just like `rsx!` calls.
+#[proc_macro]
+pub fn rsx_include(tokens: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(tokens as syn::LitStr);
+    match include::expand(&path) {
+        Err(err) => err.to_compile_error().into(),
+        Ok(tokens) => tokens.into(),
+    }
+}
+
+/// Generate a scoped class from css declarations written in Rust.
+///
+/// The macro returns the class name as a `String`. Identical rule sets share a class, and each stylesheet is injected
+/// once per virtual dom. Values that start with the theme argument are Rust expressions, and `{expr}` interpolates an
+/// expression into a css value. Nested selectors refer to the styled element with `&`.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Button() -> Element {
+///     let class = styled!(|t| {
+///         background: t.colors.primary;
+///         padding: 8px 16px;
+///         border-radius: {t.radius};
+///         "&:hover" {
+///             opacity: 0.8;
+///         }
+///     });
+///     rsx! { button { class, "Click me" } }
+/// }
+/// ```
+///
+/// The theme defaults to [`dioxus_elements::styled::Theme`](https://docs.rs/dioxus-html/latest/dioxus_html/styled/struct.Theme.html).
+/// Use `|t: &MyTheme|` to read a custom theme from context instead. Values rust can't tokenize, like `1.5em`, can be
+/// written as strings.
+#[proc_macro]
+pub fn styled(tokens: TokenStream) -> TokenStream {
+    match syn::parse::<styled::StyledBody>(tokens) {
+        Err(err) => err.to_compile_error().into(),
+        Ok(body) => body.into_token_stream().into(),
+    }
+}
+
 /// The rsx! macro makes it easy for developers to write jsx-style markup in their components.
 #[deprecated(note = "Use `rsx!` instead.")]
 #[proc_macro]
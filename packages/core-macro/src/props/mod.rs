@@ -97,6 +97,14 @@ mod util {
         }
     }
 
+    /// Elements in `dioxus_elements` are lowercase like `div`, while attribute groups are camel case like `GlobalAttributes`
+    pub fn is_element_path(path: &syn::Path) -> bool {
+        path_to_single_string(path).is_some_and(|name| {
+            name.trim_start_matches("r#")
+                .starts_with(|c: char| c.is_ascii_lowercase())
+        })
+    }
+
     pub fn ident_to_type(ident: syn::Ident) -> syn::Type {
         let mut path = syn::Path {
             leading_colon: None,
@@ -170,7 +178,7 @@ mod util {
 }
 
 mod field_info {
-    use crate::props::type_from_inside_option;
+    use crate::props::{is_children_type, looks_like_lazy_type, type_from_inside_option};
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::spanned::Spanned;
@@ -204,6 +212,12 @@ mod field_info {
                     builder_attr.default = Some(
                         syn::parse(quote!(::core::default::Default::default()).into()).unwrap(),
                     );
+
+                    // Children written in rsx are converted into the type of the field, so components can take them as
+                    // an `Element` or as a `ChildList`
+                    if is_children_type(&field.ty) {
+                        builder_attr.auto_into = true;
+                    }
                 }
 
                 // String fields automatically use impl Display
@@ -224,6 +238,14 @@ mod field_info {
                     );
                 }
 
+                // lazy fields need a type that can hold either the value or the closure that creates it
+                if builder_attr.lazy && !looks_like_lazy_type(&field.ty) {
+                    return Err(Error::new_spanned(
+                        &field.ty,
+                        "#[props(lazy)] can only be used on fields with the type `Lazy<T>`",
+                    ));
+                }
+
                 // auto detect optional
                 let strip_option_auto = builder_attr.strip_option
                     || !builder_attr.ignore_option
@@ -280,6 +302,9 @@ mod field_info {
         pub strip_option: bool,
         pub ignore_option: bool,
         pub extends: Vec<Path>,
+        /// A function that checks the final value of the field when the props are built
+        pub validate: Option<syn::Expr>,
+        pub lazy: bool,
     }
 
     impl FieldBuilderAttr {
@@ -320,6 +345,13 @@ mod field_info {
                 }
             }
 
+            if let (Some(validate), Some(_)) = (&self.validate, self.extends.first()) {
+                return Err(Error::new_spanned(
+                    validate,
+                    "#[props(validate)] cannot be used on a field that extends an element",
+                ));
+            }
+
             if self.skip && self.default.is_none() {
                 return Err(Error::new_spanned(
                     skip_tokens.unwrap(),
@@ -369,6 +401,18 @@ mod field_info {
                             }
                             Ok(())
                         }
+                        "validate" => {
+                            // The validator can be written as a path or as a string containing the path
+                            let validate = match *assign.right {
+                                syn::Expr::Lit(syn::ExprLit {
+                                    lit: syn::Lit::Str(code),
+                                    ..
+                                }) => code.parse()?,
+                                expr => expr,
+                            };
+                            self.validate = Some(validate);
+                            Ok(())
+                        }
                         _ => Err(Error::new_spanned(
                             &assign,
                             format!("Unknown parameter {name:?}"),
@@ -429,6 +473,7 @@ mod field_info {
                                 "into", auto_into, "calling into() on the argument";
                                 "displayable", from_displayable, "calling to_string() on the argument";
                                 "strip_option", strip_option, "putting the argument in Some(...)";
+                                "lazy", lazy, "lazy";
                             )
                         }
                     }
@@ -509,7 +554,8 @@ fn type_from_inside_option(ty: &syn::Type, check_option_name: bool) -> Option<&s
 mod struct_info {
     use convert_case::{Case, Casing};
     use proc_macro2::TokenStream;
-    use quote::quote;
+    use quote::{format_ident, quote};
+    use syn::ext::IdentExt;
     use syn::parse::Error;
     use syn::punctuated::Punctuated;
     use syn::spanned::Spanned;
@@ -519,10 +565,13 @@ mod struct_info {
 
     use super::field_info::{FieldBuilderAttr, FieldInfo};
     use super::util::{
-        empty_type, empty_type_tuple, expr_to_single_string, make_punctuated_single,
-        modify_types_generics_hack, path_to_single_string, strip_raw_ident_prefix, type_tuple,
+        empty_type, empty_type_tuple, expr_to_single_string, is_element_path,
+        make_punctuated_single, modify_types_generics_hack, path_to_single_string,
+        strip_raw_ident_prefix, type_tuple,
+    };
+    use super::{
+        child_owned_type, looks_like_callback_type, looks_like_lazy_type, looks_like_signal_type,
     };
-    use super::{child_owned_type, looks_like_callback_type, looks_like_signal_type};
 
     #[derive(Debug)]
     pub struct StructInfo<'a> {
@@ -648,6 +697,21 @@ mod struct_info {
                 })
                 .collect();
 
+            // A lazy prop the component never read can't have changed the last render, so take the new value
+            // without comparing it. The component reads the new value if it needs it later
+            let take_unread_lazy_fields: TokenStream = self
+                .included_fields()
+                .filter(|f| looks_like_lazy_type(f.ty))
+                .map(|field| {
+                    let name = field.name;
+                    quote! {
+                        if !self.#name.is_ready() {
+                            self.#name = new.#name.clone();
+                        }
+                    }
+                })
+                .collect();
+
             let move_event_handlers: TokenStream = event_handlers_fields.iter().map(|field| {
                 // If this is an optional event handler, we need to check if it's None before we try to update it
                 let optional = strip_option(field.ty).is_some();
@@ -670,6 +734,8 @@ mod struct_info {
             // If there are signals, we automatically try to memoize the signals
             if !signal_fields.is_empty() {
                 Ok(quote! {
+                    #take_unread_lazy_fields
+
                     // First check if the fields are equal. This will compare the signal fields by pointer
                     let exactly_equal = self == new;
                     if exactly_equal {
@@ -703,6 +769,7 @@ mod struct_info {
                 })
             } else {
                 Ok(quote! {
+                    #take_unread_lazy_fields
                     let equal = self == new;
                     // Move any signal and event fields into their old container.
                     #move_event_handlers
@@ -807,6 +874,13 @@ Finally, call `.build()` to create the instance of `{name}`.
 
             let memoize = self.memoize_impl()?;
 
+            let debug_fields = self.fields.iter().map(|f| {
+                let name = f.name;
+                let label = name.unraw().to_string();
+                quote!((#label, (&&dioxus_core::internal::DebugProps(&self.#name)).debug_props()))
+            });
+            let debug_name = name.to_string();
+
             let global_fields = self
                 .extend_fields()
                 .map(|f| {
@@ -858,6 +932,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                     fn memoize(&mut self, new: &Self) -> bool {
                         #memoize
                     }
+                    fn debug_props(&self) -> Option<String> {
+                        use dioxus_core::internal::{DebugPropsWithDebug as _, DebugPropsWithoutDebug as _};
+                        Some(dioxus_core::internal::debug_props(#debug_name, &[#(#debug_fields),*]))
+                    }
                 }
             })
         }
@@ -884,6 +962,12 @@ Finally, call `.build()` to create the instance of `{name}`.
                         self.0
                     }
                 }
+
+                impl<T> #trait_name<T> for ::core::option::Option<T> {
+                    fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
+                        self.unwrap_or_else(default)
+                    }
+                }
             })
         }
 
@@ -974,7 +1058,10 @@ Finally, call `.build()` to create the instance of `{name}`.
 
             let extends_impl = field.builder_attr.extends.iter().map(|path| {
                 let name_str = path_to_single_string(path).unwrap();
-                let camel_name = name_str.to_case(Case::UpperCamel);
+                let camel_name = name_str
+                    .strip_prefix("r#")
+                    .unwrap_or(&name_str)
+                    .to_case(Case::UpperCamel);
                 let marker_name = Ident::new(
                     format!("{}Extension", &camel_name).as_str(),
                     path.span(),
@@ -985,6 +1072,23 @@ Finally, call `.build()` to create the instance of `{name}`.
                 }
             });
 
+            // Extending an element (`extends = div`) also forwards the attribute group the element accepts and every
+            // event handler. Elements are lowercase while attribute groups like `GlobalAttributes` are camel case.
+            let element_impl = field
+                .builder_attr
+                .extends
+                .iter()
+                .find(|path| is_element_path(path))
+                .map(|element| {
+                    quote! {
+                        #[allow(dead_code, non_camel_case_types, missing_docs)]
+                        impl #impl_generics dioxus_elements::elements::#element::AttributeGroupExtension for #builder_name < #( #ty_generics ),* > #where_clause {}
+
+                        #[allow(dead_code, non_camel_case_types, missing_docs)]
+                        impl #impl_generics dioxus_elements::events::EventHandlersExtension for #builder_name < #( #ty_generics ),* > #where_clause {}
+                    }
+                });
+
             Ok(quote! {
                 #[allow(dead_code, non_camel_case_types, missing_docs)]
                 impl #impl_generics dioxus_core::prelude::HasAttributes for #builder_name < #( #ty_generics ),* > #where_clause {
@@ -1017,6 +1121,7 @@ Finally, call `.build()` to create the instance of `{name}`.
                 }
 
                 #(#extends_impl)*
+                #element_impl
             })
         }
 
@@ -1031,15 +1136,18 @@ Finally, call `.build()` to create the instance of `{name}`.
                 ref builder_name, ..
             } = *self;
 
-            let descructuring = self.included_fields().map(|f| {
-                if f.ordinal == field.ordinal {
-                    quote!(_)
-                } else {
-                    let name = f.name;
-                    quote!(#name)
-                }
-            });
-            let reconstructing = self.included_fields().map(|f| f.name);
+            let descructuring = self
+                .included_fields()
+                .map(|f| {
+                    if f.ordinal == field.ordinal {
+                        quote!(_)
+                    } else {
+                        let name = f.name;
+                        quote!(#name)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let reconstructing = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
 
             let FieldInfo {
                 name: field_name,
@@ -1090,7 +1198,17 @@ Finally, call `.build()` to create the instance of `{name}`.
                     target_generics_tuple.elems.push_punct(Default::default());
                 }
             });
+            // Setting an optional field to `None` leaves it in a state that falls back to the default when the props are built
+            let mut maybe_target_generics_tuple = target_generics_tuple.clone();
+            if let Some(position) = self
+                .included_fields()
+                .position(|f| f.ordinal == field.ordinal)
+            {
+                maybe_target_generics_tuple.elems[position] =
+                    syn::parse_quote!(::core::option::Option<#field_type>);
+            }
             let mut target_generics = ty_generics.clone();
+            let mut maybe_target_generics = ty_generics.clone();
             let index_after_lifetime_in_generics = target_generics
                 .iter()
                 .filter(|arg| matches!(arg, syn::GenericArgument::Lifetime(_)))
@@ -1099,6 +1217,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                 index_after_lifetime_in_generics,
                 syn::GenericArgument::Type(target_generics_tuple.into()),
             );
+            maybe_target_generics.insert(
+                index_after_lifetime_in_generics,
+                syn::GenericArgument::Type(maybe_target_generics_tuple.into()),
+            );
             ty_generics.insert(
                 index_after_lifetime_in_generics,
                 syn::GenericArgument::Type(ty_generics_tuple.into()),
@@ -1118,7 +1240,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                     // If this looks like a signal type, we automatically convert it with SuperInto and use the props struct as the owner
                     quote!(with_owner(self.owner.clone(), move || dioxus_core::prelude::SuperInto::super_into(#field_name))),
                 )
-            } else if field.builder_attr.auto_into || field.builder_attr.strip_option {
+            } else if field.builder_attr.auto_into
+                || field.builder_attr.strip_option
+                || field.builder_attr.lazy
+            {
                 let marker_ident = syn::Ident::new("__Marker", proc_macro2::Span::call_site());
                 marker = Some(marker_ident.clone());
                 (
@@ -1143,6 +1268,17 @@ Finally, call `.build()` to create the instance of `{name}`.
                 builder_name.span(),
             );
             let repeated_fields_error_message = format!("Repeated field {field_name}");
+            let required_maybe_error_type_name = syn::Ident::new(
+                &format!(
+                    "{}_Error_Required_field_{}_can_not_be_optional",
+                    builder_name,
+                    strip_raw_ident_prefix(field_name.to_string())
+                ),
+                builder_name.span(),
+            );
+            let required_maybe_error_message = format!(
+                "`{field_name}` is required, so it can't be set with `{field_name}?: value`. Give it a default with `#[props(default)]` to make it optional"
+            );
 
             let forward_fields = self
                 .extend_fields()
@@ -1153,7 +1289,45 @@ Finally, call `.build()` to create the instance of `{name}`.
                 .chain(
                     self.has_child_owned_fields()
                         .then(|| quote!(owner: self.owner)),
-                );
+                )
+                .collect::<Vec<_>>();
+
+            // `prop?: value` in rsx only sets fields with a default when the value is `Some`
+            let maybe_name =
+                format_ident!("__maybe_{}", strip_raw_ident_prefix(field_name.to_string()));
+            let (maybe_setter, maybe_error_type) = if field.builder_attr.default.is_some() {
+                let setter = quote! {
+                    #[doc(hidden)]
+                    #[allow(clippy::type_complexity)]
+                    pub fn #maybe_name < #marker > (self, #field_name: ::core::option::Option<#arg_type>) -> #builder_name < #( #maybe_target_generics ),* > {
+                        let #field_name = #field_name.map(|#field_name| #arg_expr);
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            #(#forward_fields,)*
+                            fields: ( #(#reconstructing,)* ),
+                            _phantom: self._phantom,
+                        }
+                    }
+                };
+                (setter, None)
+            } else {
+                // Required fields can't be left unset, so the setter only exists to explain why `prop?: value` fails
+                let setter = quote! {
+                    #[doc(hidden)]
+                    #[deprecated(
+                        note = #required_maybe_error_message
+                    )]
+                    pub fn #maybe_name(self, _: #required_maybe_error_type_name) -> Self {
+                        self
+                    }
+                };
+                let error_type = quote! {
+                    #[doc(hidden)]
+                    #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                    pub enum #required_maybe_error_type_name {}
+                };
+                (setter, Some(error_type))
+            };
 
             Ok(quote! {
                 #[allow(dead_code, non_camel_case_types, missing_docs)]
@@ -1169,10 +1343,12 @@ Finally, call `.build()` to create the instance of `{name}`.
                             _phantom: self._phantom,
                         }
                     }
+                    #maybe_setter
                 }
                 #[doc(hidden)]
                 #[allow(dead_code, non_camel_case_types, non_snake_case)]
                 pub enum #repeated_fields_error_type_name {}
+                #maybe_error_type
                 #[doc(hidden)]
                 #[allow(dead_code, non_camel_case_types, missing_docs)]
                 impl #impl_generics #builder_name < #( #target_generics ),* > #where_clause {
@@ -1367,7 +1543,7 @@ Finally, call `.build()` to create the instance of `{name}`.
             // reordering based on that, but for now this much simpler thing is a reasonable approach.
             let assignments = self.fields.iter().map(|field| {
                 let name = &field.name;
-                if !field.builder_attr.extends.is_empty() {
+                let assignment = if !field.builder_attr.extends.is_empty() {
                     quote!(let #name = self.#name;)
                 } else if let Some(ref default) = field.builder_attr.default {
                     if field.builder_attr.skip {
@@ -1377,6 +1553,21 @@ Finally, call `.build()` to create the instance of `{name}`.
                     }
                 } else {
                     quote!(let #name = #name.0;)
+                };
+                match &field.builder_attr.validate {
+                    Some(validate) => {
+                        let message = format!(
+                            "Invalid value for the `{name}` prop of `{}`: {{}}",
+                            self.name
+                        );
+                        quote! {
+                            #assignment
+                            if let Err(err) = (#validate)(&#name) {
+                                panic!(#message, err);
+                            }
+                        }
+                    }
+                    None => assignment,
                 }
             });
             let field_names = self.fields.iter().map(|field| field.name);
@@ -1436,6 +1627,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                         fn memoize(&mut self, new: &Self) -> bool {
                             self.inner.memoize(&new.inner)
                         }
+                        fn debug_props(&self) -> Option<String> {
+                            self.inner.debug_props()
+                        }
                     }
 
                     #[allow(dead_code, non_camel_case_types, missing_docs)]
@@ -1686,6 +1880,31 @@ fn looks_like_signal_type(ty: &Type) -> bool {
     }
 }
 
+/// Check if a type is one of the types children written in rsx can be converted into
+fn is_children_type(ty: &Type) -> bool {
+    match extract_base_type_without_generics(ty) {
+        Some(path_without_generics) => ["Element", "ChildList"].iter().any(|name| {
+            let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+            path_without_generics == parse_quote!(dioxus_core::prelude::#name)
+                || path_without_generics == parse_quote!(dioxus_core::#name)
+                || path_without_generics == parse_quote!(prelude::#name)
+                || path_without_generics == parse_quote!(#name)
+        }),
+        None => false,
+    }
+}
+
+fn looks_like_lazy_type(ty: &Type) -> bool {
+    match extract_base_type_without_generics(ty) {
+        Some(path_without_generics) => {
+            path_without_generics == parse_quote!(dioxus_core::prelude::Lazy)
+                || path_without_generics == parse_quote!(prelude::Lazy)
+                || path_without_generics == parse_quote!(Lazy)
+        }
+        None => false,
+    }
+}
+
 fn looks_like_callback_type(ty: &Type) -> bool {
     let type_without_option = remove_option_wrapper(ty.clone());
     match extract_base_type_without_generics(&type_without_option) {
@@ -1729,6 +1948,12 @@ fn test_looks_like_type() {
 
     assert!(looks_like_callback_type(&parse_quote!(Callback<i32>)));
     assert!(looks_like_callback_type(&parse_quote!(Callback<i32, u32>)));
+
+    assert!(is_children_type(&parse_quote!(Element)));
+    assert!(is_children_type(&parse_quote!(
+        dioxus_core::prelude::ChildList
+    )));
+    assert!(!is_children_type(&parse_quote!(usize)));
 }
 
 #[test]
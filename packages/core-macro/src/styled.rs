@@ -0,0 +1,287 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    Expr, Ident, LitStr, Pat, Result, Token, Type,
+};
+
+/// The body of a `styled!` call: an optional theme closure and the css declarations
+pub(crate) struct StyledBody {
+    theme: Option<(Pat, Option<Type>)>,
+    rules: Vec<StyleRule>,
+}
+
+/// The declarations for one selector. `&` in the selector is replaced with the generated class
+struct StyleRule {
+    selector: String,
+    declarations: Vec<Declaration>,
+}
+
+struct Declaration {
+    property: String,
+    value: Vec<ValuePart>,
+}
+
+enum ValuePart {
+    Css(String),
+    Expr(Box<Expr>),
+}
+
+impl Parse for StyledBody {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let theme = if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            let pat = Pat::parse_single(input)?;
+            let ty = if input.peek(Token![:]) {
+                input.parse::<Token![:]>()?;
+                Some(input.parse::<Type>()?)
+            } else {
+                None
+            };
+            input.parse::<Token![|]>()?;
+            Some((pat, ty))
+        } else {
+            None
+        };
+
+        let theme_ident = match &theme {
+            Some((Pat::Ident(pat), _)) => Some(pat.ident.clone()),
+            _ => None,
+        };
+
+        let rules = if theme.is_some() {
+            let content;
+            braced!(content in input);
+            parse_rules(&content, theme_ident.as_ref())?
+        } else {
+            parse_rules(input, theme_ident.as_ref())?
+        };
+
+        Ok(Self { theme, rules })
+    }
+}
+
+fn parse_rules(input: ParseStream, theme: Option<&Ident>) -> Result<Vec<StyleRule>> {
+    let mut rules = vec![StyleRule {
+        selector: "&".to_string(),
+        declarations: Vec::new(),
+    }];
+
+    while !input.is_empty() {
+        // Nested rules like `"&:hover" { opacity: 0.8; }`
+        if input.peek(LitStr) {
+            let selector = input.parse::<LitStr>()?;
+            if !selector.value().contains('&') {
+                return Err(syn::Error::new(
+                    selector.span(),
+                    "Nested selectors must refer to the styled element with `&`, like \"&:hover\"",
+                ));
+            }
+            let content;
+            braced!(content in input);
+            let mut declarations = Vec::new();
+            while !content.is_empty() {
+                declarations.push(parse_declaration(&content, theme)?);
+            }
+            rules.push(StyleRule {
+                selector: selector.value(),
+                declarations,
+            });
+            continue;
+        }
+
+        let declaration = parse_declaration(input, theme)?;
+        rules[0].declarations.push(declaration);
+    }
+
+    Ok(rules)
+}
+
+fn parse_declaration(input: ParseStream, theme: Option<&Ident>) -> Result<Declaration> {
+    // Properties are hyphenated words like `background-color` or `-webkit-appearance`
+    let mut property = String::new();
+    while !input.peek(Token![:]) {
+        if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            property.push('-');
+        } else {
+            property.push_str(&Ident::parse_any(input)?.to_string());
+        }
+    }
+    input.parse::<Token![:]>()?;
+
+    let mut tokens = Vec::new();
+    while !input.is_empty() && !input.peek(Token![;]) {
+        tokens.push(input.parse::<TokenTree>()?);
+    }
+    if tokens.is_empty() {
+        return Err(input.error(format!("Expected a value for `{property}`")));
+    }
+    if !input.is_empty() {
+        input.parse::<Token![;]>()?;
+    }
+
+    Ok(Declaration {
+        property,
+        value: parse_value(tokens, theme)?,
+    })
+}
+
+fn parse_value(tokens: Vec<TokenTree>, theme: Option<&Ident>) -> Result<Vec<ValuePart>> {
+    // Values that start with the theme are rust expressions
+    if let (Some(TokenTree::Ident(first)), Some(theme)) = (tokens.first(), theme) {
+        if first == theme {
+            let expr = syn::parse2(tokens.into_iter().collect())?;
+            return Ok(vec![ValuePart::Expr(Box::new(expr))]);
+        }
+    }
+
+    // A lone string is written as is, for values rust can't tokenize like `1.5em`
+    if let [TokenTree::Literal(lit)] = tokens.as_slice() {
+        if let Ok(lit) = syn::parse2::<LitStr>(lit.to_token_stream()) {
+            return Ok(vec![ValuePart::Css(lit.value())]);
+        }
+    }
+
+    let mut parts = Vec::new();
+    write_css_tokens(&tokens, &mut parts)?;
+    Ok(parts)
+}
+
+/// Write css tokens back out, restoring the whitespace that tokenizing them removed
+fn write_css_tokens(tokens: &[TokenTree], parts: &mut Vec<ValuePart>) -> Result<()> {
+    for (idx, token) in tokens.iter().enumerate() {
+        let prev = idx.checked_sub(1).map(|idx| &tokens[idx]);
+        let next = tokens.get(idx + 1);
+
+        if let Some(prev) = prev {
+            if needs_space(prev, token, next, tokens.get(idx.wrapping_sub(2))) {
+                push_css(parts, " ");
+            }
+        }
+
+        match token {
+            // `{expr}` interpolates a rust expression into the value
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                parts.push(ValuePart::Expr(Box::new(syn::parse2(group.stream())?)));
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    _ => ("", ""),
+                };
+                push_css(parts, open);
+                write_css_tokens(&group.stream().into_iter().collect::<Vec<_>>(), parts)?;
+                push_css(parts, close);
+            }
+            token => push_css(parts, &token.to_string()),
+        }
+    }
+
+    Ok(())
+}
+
+const CSS_UNITS: &[&str] = &[
+    "px", "em", "rem", "vh", "vw", "vmin", "vmax", "ch", "ex", "pt", "fr", "deg", "rad", "turn",
+    "s", "ms",
+];
+
+fn needs_space(
+    prev: &TokenTree,
+    token: &TokenTree,
+    next: Option<&TokenTree>,
+    before_prev: Option<&TokenTree>,
+) -> bool {
+    let is_punct =
+        |token: &TokenTree, ch: char| matches!(token, TokenTree::Punct(p) if p.as_char() == ch);
+    let is_ident = |token: Option<&TokenTree>| matches!(token, Some(TokenTree::Ident(_)));
+
+    // Functions like `rgba(...)` and suffixes like `50%` or `a, b` stick to the token before them
+    if matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+        && is_ident(Some(prev))
+    {
+        return false;
+    }
+    if is_punct(token, '%') || is_punct(token, ',') {
+        return false;
+    }
+    // Units after an interpolated value like `{size}px`
+    if matches!(prev, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+        && matches!(token, TokenTree::Ident(ident) if CSS_UNITS.contains(&ident.to_string().as_str()))
+    {
+        return false;
+    }
+
+    // Prefixes like `#fff` or `!important` stick to the token after them
+    if is_punct(prev, '#') || is_punct(prev, '!') || is_punct(prev, '.') {
+        return false;
+    }
+
+    // Hyphens join words like `sans-serif` and prefix names like `--var`, but a minus between values is an operator
+    if is_punct(token, '-') {
+        return !(is_ident(Some(prev)) && is_ident(next) || is_punct(prev, '-'));
+    }
+    if is_punct(prev, '-') {
+        let before_prev_is_value = matches!(
+            before_prev,
+            Some(TokenTree::Literal(_)) | Some(TokenTree::Group(_))
+        ) || before_prev.is_some_and(|token| is_punct(token, '%'));
+        return before_prev_is_value;
+    }
+
+    true
+}
+
+fn push_css(parts: &mut Vec<ValuePart>, css: &str) {
+    match parts.last_mut() {
+        Some(ValuePart::Css(last)) => last.push_str(css),
+        _ => parts.push(ValuePart::Css(css.to_string())),
+    }
+}
+
+impl ToTokens for StyledBody {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (pat, ty) = match &self.theme {
+            Some((pat, ty)) => (
+                pat.to_token_stream(),
+                ty.as_ref().map(|ty| ty.to_token_stream()),
+            ),
+            None => (quote! { _ }, None),
+        };
+        let ty = ty.unwrap_or_else(|| quote! { &dioxus_elements::styled::Theme });
+
+        let declarations = self.rules.iter().flat_map(|rule| {
+            let selector = &rule.selector;
+            rule.declarations.iter().map(move |declaration| {
+                let property = &declaration.property;
+                let mut format = String::new();
+                let mut args = Vec::new();
+                for part in &declaration.value {
+                    match part {
+                        ValuePart::Css(css) => {
+                            format.push_str(&css.replace('{', "{{").replace('}', "}}"))
+                        }
+                        ValuePart::Expr(expr) => {
+                            format.push_str("{}");
+                            args.push(expr);
+                        }
+                    }
+                }
+                quote! {
+                    __styles.declare(#selector, #property, format_args!(#format, #(#args),*));
+                }
+            })
+        });
+
+        tokens.extend(quote! {
+            dioxus_elements::styled::styled(|#pat: #ty| {
+                let mut __styles = dioxus_elements::styled::StyleRules::default();
+                #(#declarations)*
+                __styles
+            })
+        });
+    }
+}
@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::rc::Rc;
+
+#[test]
+fn ref_stores_the_mounted_element() {
+    thread_local! {
+        static MOUNTED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    fn app() -> Element {
+        let header = use_signal(|| None);
+
+        MOUNTED.with(|m| m.set(header.read().is_some()));
+
+        rsx! {
+            h1 { ref: header, "hello" }
+        }
+    }
+
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    assert!(!MOUNTED.with(|m| m.get()));
+
+    dom.handle_event(
+        "mounted",
+        Rc::new(PlatformEventData::new(Box::new(()))),
+        ElementId(1),
+        false,
+    );
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert!(MOUNTED.with(|m| m.get()));
+}
@@ -51,6 +51,37 @@ fn event_handlers_compile() {
         }
     }
 
+    #[component]
+    fn TakesHandlerVariables() -> Element {
+        let mut count = use_signal(|| 0);
+        let increment = move |_| count += 1;
+        let onclick = move |_: MouseEvent| count -= 1;
+        fn log_input(event: FormEvent) {
+            println!("{}", event.value());
+        }
+        async fn log_focus(event: FocusEvent) {
+            println!("{event:?}");
+        }
+
+        rsx! {
+            button {
+                // Closures stored in variables can be passed without wrapping them in another closure
+                onclick: increment,
+                "{count}"
+            }
+            button {
+                // A handler with the same name as the event can use the shorthand syntax
+                onclick,
+                "{count}"
+            }
+            input {
+                // Functions work too
+                oninput: log_input,
+                onfocus: log_focus,
+            }
+        }
+    }
+
     #[component]
     fn TakesEventHandlerWithArg(double: Callback<u32, i32>) -> Element {
         let mut count = use_signal(|| 2);
@@ -0,0 +1,57 @@
+#![allow(non_snake_case)]
+
+use dioxus::html::SerializedMouseData;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::rc::Rc;
+
+#[test]
+fn extending_an_element_forwards_attributes_and_events() {
+    thread_local! {
+        static CLICKED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    #[derive(Props, Clone, PartialEq)]
+    struct ButtonProps {
+        #[props(extends = button)]
+        attributes: Vec<Attribute>,
+        children: Element,
+    }
+
+    fn Button(props: ButtonProps) -> Element {
+        let names: Vec<_> = props.attributes.iter().map(|attr| attr.name).collect();
+        assert_eq!(names, ["class", "type", "disabled", "onclick"]);
+
+        rsx! {
+            button { ..props.attributes, {props.children} }
+        }
+    }
+
+    fn app() -> Element {
+        rsx! {
+            Button {
+                class: "primary",
+                r#type: "submit",
+                disabled: false,
+                onclick: move |_| CLICKED.with(|c| c.set(true)),
+                "Go"
+            }
+        }
+    }
+
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::new(
+            SerializedMouseData::default(),
+        ))),
+        ElementId(1),
+        true,
+    );
+
+    assert!(CLICKED.with(|c| c.get()));
+}
@@ -0,0 +1,101 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static FALLBACKS_CREATED: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct LoaderProps {
+    loaded: bool,
+    #[props(lazy)]
+    fallback: Lazy<Element>,
+    #[props(lazy, default)]
+    label: Lazy<String>,
+}
+
+fn Loader(props: LoaderProps) -> Element {
+    if props.loaded {
+        return rsx! { "{props.label.get()}" };
+    }
+    props.fallback.get().clone()
+}
+
+fn fallback() -> Element {
+    FALLBACKS_CREATED.with(|c| c.set(c.get() + 1));
+    rsx! { "Loading..." }
+}
+
+#[test]
+fn lazy_props_are_only_evaluated_when_read() {
+    fn app() -> Element {
+        rsx! {
+            Loader { loaded: true, fallback: fallback, label: || "Done".to_string() }
+            Loader { loaded: false, fallback: fallback }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(FALLBACKS_CREATED.with(|c| c.get()), 1);
+}
+
+#[test]
+fn lazy_props_accept_values() {
+    let props = LoaderProps::builder()
+        .loaded(true)
+        .fallback(None)
+        .label("Done".to_string())
+        .build();
+
+    assert!(props.label.is_ready());
+    assert_eq!(*props.label.get(), "Done");
+}
+
+thread_local! {
+    static SUMMARY_RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct SummaryProps {
+    #[props(lazy)]
+    title: Lazy<String>,
+    #[props(lazy)]
+    details: Lazy<Element>,
+}
+
+fn Summary(props: SummaryProps) -> Element {
+    SUMMARY_RENDERS.with(|c| c.set(c.get() + 1));
+    rsx! { "{props.title.get()}" }
+}
+
+#[test]
+fn unchanged_lazy_props_are_memoized() {
+    fn app() -> Element {
+        // The title changes on the third render
+        let title = if generation() < 2 {
+            "Title"
+        } else {
+            "New title"
+        };
+        rsx! {
+            Summary { title: title.to_string(), details: || rsx! { "Details" } }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    assert_eq!(SUMMARY_RENDERS.with(|c| c.get()), 1);
+
+    // The title that was read is equal and the new details closure was never read, so the child is skipped
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(SUMMARY_RENDERS.with(|c| c.get()), 1);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(SUMMARY_RENDERS.with(|c| c.get()), 2);
+}
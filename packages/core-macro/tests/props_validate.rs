@@ -0,0 +1,37 @@
+use dioxus::prelude::*;
+
+fn not_empty(value: &str) -> Result<(), &'static str> {
+    if value.is_empty() {
+        return Err("the label must not be empty");
+    }
+    Ok(())
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct BadgeProps {
+    #[props(into, validate = "not_empty")]
+    label: String,
+    #[props(default = 1, validate = |count: &u32| if *count <= 99 { Ok(()) } else { Err(count.to_string()) })]
+    count: u32,
+}
+
+#[test]
+fn valid_props_build() {
+    let props = BadgeProps::builder().label("new").build();
+    assert_eq!(props.label, "new");
+    assert_eq!(props.count, 1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid value for the `label` prop of `BadgeProps`: the label must not be empty"
+)]
+fn invalid_props_panic_when_built() {
+    BadgeProps::builder().label("").build();
+}
+
+#[test]
+#[should_panic(expected = "Invalid value for the `count` prop of `BadgeProps`: 100")]
+fn defaulted_props_are_validated() {
+    BadgeProps::builder().label("new").count(100).build();
+}
@@ -2,4 +2,9 @@
 fn rsx() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/rsx/trailing-comma-0.rs");
+    t.compile_fail("tests/rsx/invalid-attribute-value.rs");
+    t.compile_fail("tests/rsx/invalid-aria-value.rs");
+    t.compile_fail("tests/rsx/invalid-custom-element-attribute.rs");
+    t.compile_fail("tests/rsx/invalid-manual-prop.rs");
+    t.compile_fail("tests/rsx/invalid-optional-required-prop.rs");
 }
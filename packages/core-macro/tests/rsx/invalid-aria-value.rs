@@ -0,0 +1,13 @@
+// Given an `rsx!` invocation with a static value that an enumerated ARIA attribute doesn't accept,
+// ensure the warning points at the value.
+
+#![deny(deprecated)]
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        div { aria_live: AriaLive::Polite, aria_pressed: "mixed" }
+        div { aria_live: "politely" }
+    };
+}
@@ -0,0 +1,15 @@
+// Given an `rsx!` invocation with a static value that an enumerated attribute doesn't accept,
+// ensure the warning points at the value.
+
+#![deny(deprecated)]
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        input { r#type: "chekbox" }
+        button { r#type: "submit", "Submit" }
+        textarea { autocomplete: "email" }
+        div { dir: "RTL" }
+    };
+}
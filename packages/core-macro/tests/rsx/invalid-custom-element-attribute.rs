@@ -0,0 +1,23 @@
+// Given an `rsx!` invocation with an attribute or static value that a declared custom element doesn't accept,
+// ensure the error or warning points at the attribute or value.
+
+#![deny(deprecated)]
+
+use dioxus::prelude::*;
+
+mod custom_elements {
+    dioxus::html::custom_elements! {
+        color_picker "color-picker" {
+            mode: OnOff,
+        };
+    }
+}
+
+fn main() {
+    rsx! {
+        color-picker { class: "picker", mode: "on" }
+        color-picker { colour: "red" }
+        color-picker { mode: "maybe" }
+        my-widget { colour: "red" }
+    };
+}
@@ -0,0 +1,24 @@
+// Given an `rsx!` invocation that sets a field the props struct doesn't have next to `..props`,
+// ensure the error points at the field.
+
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+struct ButtonProps {
+    label: String,
+    disabled: bool,
+}
+
+fn Button(props: ButtonProps) -> Element {
+    rsx! { button { disabled: props.disabled, "{props.label}" } }
+}
+
+fn main() {
+    let props = ButtonProps {
+        label: "Save".to_string(),
+        disabled: false,
+    };
+    _ = rsx! {
+        Button { lable: "Cancel".to_string(), ..props }
+    };
+}
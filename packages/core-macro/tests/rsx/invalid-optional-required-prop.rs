@@ -0,0 +1,22 @@
+// Given an `rsx!` invocation that sets a required prop with `prop?: value`,
+// ensure the error explains that only props with a default can be optional.
+
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+struct ButtonProps {
+    label: String,
+    #[props(default)]
+    disabled: bool,
+}
+
+fn Button(props: ButtonProps) -> Element {
+    rsx! { button { disabled: props.disabled, "{props.label}" } }
+}
+
+fn main() {
+    let label = Some("Save".to_string());
+    _ = rsx! {
+        Button { label?: label }
+    };
+}
@@ -17,12 +17,15 @@ pub(crate) trait AnyProps: 'static {
     fn props(&self) -> &dyn Any;
     /// Duplicate this component into a new boxed component.
     fn duplicate(&self) -> BoxedAnyProps;
+    /// Format the props for development tools, if they can be formatted.
+    fn debug_props(&self) -> Option<String>;
 }
 
 /// A component along with the props the component uses to render.
 pub(crate) struct VProps<F: ComponentFunction<P, M>, P, M> {
     render_fn: F,
     memo: fn(&mut P, &P) -> bool,
+    debug: fn(&P) -> Option<String>,
     props: P,
     name: &'static str,
     phantom: std::marker::PhantomData<M>,
@@ -33,6 +36,7 @@ impl<F: ComponentFunction<P, M>, P: Clone, M> Clone for VProps<F, P, M> {
         Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
@@ -45,12 +49,14 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> VProps<
     pub fn new(
         render_fn: F,
         memo: fn(&mut P, &P) -> bool,
+        debug: fn(&P) -> Option<String>,
         props: P,
         name: &'static str,
     ) -> VProps<F, P, M> {
         VProps {
             render_fn,
             memo,
+            debug,
             props,
             name,
             phantom: std::marker::PhantomData,
@@ -72,6 +78,10 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         &self.props
     }
 
+    fn debug_props(&self) -> Option<String> {
+        (self.debug)(&self.props)
+    }
+
     fn render(&self) -> RenderReturn {
         let res = std::panic::catch_unwind(AssertUnwindSafe(move || {
             self.render_fn.rebuild(self.props.clone())
@@ -93,6 +103,7 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         Box::new(Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
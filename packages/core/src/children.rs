@@ -0,0 +1,194 @@
+use crate::innerlude::{DynamicNode, Element, IntoDynNode, SuperFrom, TemplateNode, VNode};
+
+/// The children of a component as a list with one node per child
+///
+/// Components that need to count or wrap each of their children can take a `ChildList` instead of an [`Element`]. Every
+/// root of the children is one item, and loops or iterators in the children add one item for each node they render:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Stack(children: ChildList) -> Element {
+///     let count = children.len();
+///     rsx! {
+///         ul { "data-count": "{count}",
+///             for child in children {
+///                 li { {child} }
+///             }
+///         }
+///     }
+/// }
+///
+/// fn app() -> Element {
+///     let items = vec![rsx! { "two" }, rsx! { "three" }];
+///     rsx! {
+///         Stack {
+///             "one"
+///             for item in items {
+///                 {item}
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// A `ChildList` can also be built from any iterator of [`Element`]s, like a `Vec` of nodes or a mapped iterator.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChildList {
+    children: Vec<VNode>,
+}
+
+impl ChildList {
+    /// The number of children
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Whether there are no children
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Iterate over the children
+    pub fn iter(&self) -> std::slice::Iter<'_, VNode> {
+        self.children.iter()
+    }
+
+    /// Get the child at a position
+    pub fn get(&self, index: usize) -> Option<&VNode> {
+        self.children.get(index)
+    }
+
+    /// The keys of the children in order. Children without a key are `None`.
+    pub fn keys(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.children.iter().map(|child| child.key.as_deref())
+    }
+
+    /// Wrap each child in other nodes, like the cell of a grid or the slide of a carousel
+    ///
+    /// The wrapper of a keyed child takes the key of the child unless it has its own. Reordered children are moved
+    /// instead of rebuilt, so the components in them keep their state.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// #[component]
+    /// fn Grid(children: ChildList) -> Element {
+    ///     let cells = children.wrap(|child| rsx! {
+    ///         div { class: "cell", {child} }
+    ///     });
+    ///     rsx! {
+    ///         div { class: "grid", {cells} }
+    ///     }
+    /// }
+    /// ```
+    pub fn wrap(self, mut wrap: impl FnMut(VNode) -> Element) -> Self {
+        let children = self
+            .children
+            .into_iter()
+            .filter_map(|child| {
+                let key = child.key.clone();
+                let wrapper = wrap(child)?;
+                Some(match key {
+                    Some(key) if wrapper.key.is_none() => wrapper.with_key(key),
+                    _ => wrapper,
+                })
+            })
+            .collect();
+        Self { children }
+    }
+
+    /// Add an element to the list. A node that only renders a list of nodes, like the body of a loop, adds each node of
+    /// the list instead.
+    fn push(&mut self, element: Element) {
+        let Some(node) = element else {
+            return;
+        };
+
+        if let [TemplateNode::Dynamic { id }] = node.template.get().roots {
+            if let DynamicNode::Fragment(nodes) = &node.dynamic_nodes[*id] {
+                for node in nodes {
+                    self.push(Some(node.clone()));
+                }
+                return;
+            }
+        }
+        self.children.push(node);
+    }
+}
+
+impl FromIterator<Element> for ChildList {
+    fn from_iter<T: IntoIterator<Item = Element>>(iter: T) -> Self {
+        let mut list = Self::default();
+        for element in iter {
+            list.push(element);
+        }
+        list
+    }
+}
+
+impl From<Element> for ChildList {
+    fn from(element: Element) -> Self {
+        std::iter::once(element).collect()
+    }
+}
+
+impl From<Vec<Element>> for ChildList {
+    fn from(elements: Vec<Element>) -> Self {
+        elements.into_iter().collect()
+    }
+}
+
+#[doc(hidden)]
+pub struct ChildListIteratorMarker;
+
+impl<I: Iterator<Item = Element>> SuperFrom<I, ChildListIteratorMarker> for ChildList {
+    fn super_from(iter: I) -> Self {
+        iter.collect()
+    }
+}
+
+impl IntoIterator for ChildList {
+    type Item = VNode;
+    type IntoIter = std::vec::IntoIter<VNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ChildList {
+    type Item = &'a VNode;
+    type IntoIter = std::slice::Iter<'a, VNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.iter()
+    }
+}
+
+impl IntoDynNode for ChildList {
+    fn into_dyn_node(self) -> DynamicNode {
+        match self.children.is_empty() {
+            true => DynamicNode::default(),
+            false => DynamicNode::Fragment(self.children),
+        }
+    }
+}
+
+/// The children of a component written in rsx with more than one root
+///
+/// They render as one node when the component takes an [`Element`], or as one node per root when it takes a
+/// [`ChildList`]. The closure renders one element per root when it is passed `true`.
+#[doc(hidden)]
+pub struct RsxChildren<F>(pub F);
+
+impl<F: FnOnce(bool) -> Vec<Element>> From<RsxChildren<F>> for Element {
+    fn from(children: RsxChildren<F>) -> Self {
+        (children.0)(false).into_iter().next().flatten()
+    }
+}
+
+impl<F: FnOnce(bool) -> Vec<Element>> From<RsxChildren<F>> for ChildList {
+    fn from(children: RsxChildren<F>) -> Self {
+        (children.0)(true).into_iter().collect()
+    }
+}
@@ -0,0 +1,225 @@
+//! Information about a running app for development tools like the dev overlay
+
+use crate::{innerlude::ScopeId, Runtime};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// The number of times a template or literal was hot reloaded in this process
+static HOT_RELOADS: AtomicUsize = AtomicUsize::new(0);
+
+/// How many times a mounted scope has rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeRenderInfo {
+    /// The id of the scope
+    pub id: ScopeId,
+    /// The name of the component the scope renders
+    pub name: &'static str,
+    /// The scope the component is mounted in
+    pub parent: Option<ScopeId>,
+    /// The number of times the scope has rendered, including the first render
+    pub renders: usize,
+}
+
+/// Get the render count of every scope mounted in the current runtime, ordered by scope id.
+///
+/// Returns an empty list when called outside of a virtual dom.
+pub fn scope_renders() -> Vec<ScopeRenderInfo> {
+    let Some(runtime) = Runtime::current() else {
+        return Vec::new();
+    };
+    let scopes = runtime.scope_states.borrow();
+    scopes
+        .iter()
+        .flatten()
+        .map(|scope| ScopeRenderInfo {
+            id: scope.id,
+            name: scope.name,
+            parent: scope.parent_id,
+            renders: scope.render_count.get(),
+        })
+        .collect()
+}
+
+/// Get the number of hot reloads any [`VirtualDom`](crate::VirtualDom) in this process has applied
+pub fn hot_reload_count() -> usize {
+    HOT_RELOADS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn record_hot_reload() {
+    HOT_RELOADS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Something the hot reloader did to a [`VirtualDom`](crate::VirtualDom). Listen for them with
+/// [`subscribe_hot_reload`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum HotReloadEvent {
+    /// A new version of a template was patched in and the components that render it rerendered
+    TemplatePatched {
+        /// The name of the template
+        name: &'static str,
+        /// How long patching the template and rerendering took. `None` on platforms without a clock, like the web.
+        duration: Option<Duration>,
+    },
+    /// A template was the same as the one the app already had, so nothing rerendered
+    TemplateUnchanged {
+        /// The name of the template
+        name: &'static str,
+    },
+    /// New values for the hot reloadable literals of an rsx call were patched in
+    LiteralsPatched {
+        /// The `file:line:column` of the rsx call
+        location: String,
+        /// How long patching the literals and rerendering took. `None` on platforms without a clock, like the web.
+        duration: Option<Duration>,
+    },
+    /// A change couldn't be hot reloaded. Changes of the kind [`HotReloadErrorKind::RebuildRequired`] rebuild the app.
+    Failed(HotReloadError),
+}
+
+type HotReloadListener = Arc<dyn Fn(&HotReloadEvent) + Send + Sync>;
+
+static HOT_RELOAD_LISTENERS: RwLock<Vec<HotReloadListener>> = RwLock::new(Vec::new());
+
+/// Call `listener` with every [`HotReloadEvent`] of every [`VirtualDom`](crate::VirtualDom) in this process
+///
+/// This is meant for development tools like editor integrations that show the state of hot reloading.
+///
+/// ```rust
+/// # use dioxus_core::HotReloadEvent;
+/// dioxus_core::subscribe_hot_reload(|event| {
+///     if let HotReloadEvent::Failed(error) = event {
+///         eprintln!("{error}");
+///     }
+/// });
+/// ```
+pub fn subscribe_hot_reload(listener: impl Fn(&HotReloadEvent) + Send + Sync + 'static) {
+    HOT_RELOAD_LISTENERS
+        .write()
+        .unwrap()
+        .push(Arc::new(listener));
+}
+
+/// Whether anything listens for hot reload events. Timing hot reloads is skipped otherwise.
+pub(crate) fn has_hot_reload_listeners() -> bool {
+    !HOT_RELOAD_LISTENERS.read().unwrap().is_empty()
+}
+
+pub(crate) fn emit_hot_reload_event(event: HotReloadEvent) {
+    // Listeners are called without the lock held so they can subscribe more listeners
+    let listeners = HOT_RELOAD_LISTENERS.read().unwrap().clone();
+    for listener in listeners {
+        listener(&event);
+    }
+}
+
+/// A hot reload that was applied to a virtual dom. It is reported once the components it touched rerender.
+pub(crate) struct PendingHotReload {
+    change: PendingChange,
+    started: Option<Instant>,
+}
+
+enum PendingChange {
+    Template(&'static str),
+    Literals(String),
+}
+
+impl PendingHotReload {
+    pub(crate) fn template(name: &'static str) -> Self {
+        Self {
+            change: PendingChange::Template(name),
+            started: now(),
+        }
+    }
+
+    pub(crate) fn literals(location: String) -> Self {
+        Self {
+            change: PendingChange::Literals(location),
+            started: now(),
+        }
+    }
+
+    pub(crate) fn finish(self) -> HotReloadEvent {
+        let duration = self.started.map(|started| started.elapsed());
+        match self.change {
+            PendingChange::Template(name) => HotReloadEvent::TemplatePatched { name, duration },
+            PendingChange::Literals(location) => {
+                HotReloadEvent::LiteralsPatched { location, duration }
+            }
+        }
+    }
+}
+
+/// The current time, if the platform has a clock. [`Instant::now`] panics on the web.
+pub(crate) fn now() -> Option<Instant> {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+/// A change to the source of the app that couldn't be hot reloaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotReloadError {
+    /// Why the change couldn't be hot reloaded
+    pub kind: HotReloadErrorKind,
+    /// The file that changed, relative to the crate root
+    pub file: String,
+    /// The line of the code that caused the error, starting at 1, if it is known
+    pub line: Option<usize>,
+    /// The column of the code that caused the error, starting at 0, if it is known
+    pub column: Option<usize>,
+    /// A description of the error
+    pub message: String,
+}
+
+/// The kind of a [`HotReloadError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum HotReloadErrorKind {
+    /// The file doesn't parse. The app keeps running the last version that did
+    Parse,
+    /// The change touched code outside of rsx or the structure of an rsx call, so the app needs to be rebuilt
+    RebuildRequired,
+}
+
+impl std::fmt::Display for HotReloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+            if let Some(column) = self.column {
+                write!(f, ":{column}")?;
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The hot reload error of a virtual dom and the scopes that rerender when it changes
+#[derive(Default)]
+pub(crate) struct HotReloadErrorState {
+    pub(crate) error: Option<HotReloadError>,
+    pub(crate) readers: HashSet<ScopeId>,
+}
+
+/// Get the change the hot reloader couldn't apply to the current virtual dom, if the last change failed. The current
+/// scope rerenders when the error changes.
+///
+/// Returns `None` when called outside of a virtual dom.
+pub fn hot_reload_error() -> Option<HotReloadError> {
+    let runtime = Runtime::current()?;
+    let mut state = runtime.hot_reload_error.borrow_mut();
+    if let Some(scope) = runtime.current_scope_id() {
+        state.readers.insert(scope);
+    }
+    state.error.clone()
+}
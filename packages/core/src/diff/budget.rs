@@ -0,0 +1,162 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    arena::ElementId,
+    innerlude::{ScopeOrder, WriteMutations},
+    scopes::ScopeId,
+    virtual_dom::VirtualDom,
+    AttributeValue, Template,
+};
+
+/// Mutations that are counted against the [mutation budget](VirtualDom::with_mutation_budget) of the current render
+pub(crate) struct BudgetedMutations<'a, M> {
+    to: &'a mut M,
+    written: Rc<Cell<usize>>,
+}
+
+impl<'a, M: WriteMutations> BudgetedMutations<'a, M> {
+    pub(crate) fn new(dom: &VirtualDom, to: &'a mut M) -> Self {
+        Self {
+            to,
+            written: dom.mutations_written.clone(),
+        }
+    }
+
+    fn count(&mut self) -> &mut M {
+        self.written.set(self.written.get() + 1);
+        self.to
+    }
+}
+
+impl<M: WriteMutations> WriteMutations for BudgetedMutations<'_, M> {
+    fn register_template(&mut self, template: Template) {
+        self.to.register_template(template);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.count().append_children(id, m);
+    }
+
+    fn append_to_portal(&mut self, target: &str, m: usize) {
+        self.count().append_to_portal(target, m);
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.count().assign_node_id(path, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.count().create_placeholder(id);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.count().create_text_node(value, id);
+    }
+
+    fn hydrate_text_node(&mut self, path: &'static [u8], value: &str, id: ElementId) {
+        self.count().hydrate_text_node(path, value, id);
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        self.count().load_template(name, index, id);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.count().replace_node_with(id, m);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.count().replace_placeholder_with_nodes(path, m);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.count().insert_nodes_after(id, m);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.count().insert_nodes_before(id, m);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.count().set_attribute(name, ns, value, id);
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.count().set_node_text(value, id);
+    }
+
+    fn replace_node_text(
+        &mut self,
+        start: usize,
+        delete: usize,
+        value: &str,
+        text: &str,
+        id: ElementId,
+    ) {
+        self.count()
+            .replace_node_text(start, delete, value, text, id);
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count().create_event_listener(name, id);
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count().remove_event_listener(name, id);
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.count().remove_node(id);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.count().push_root(id);
+    }
+
+    fn swap_subtree(&mut self, subtree_index: usize) {
+        self.to.swap_subtree(subtree_index);
+    }
+
+    fn mark_scope_dirty(&mut self, scope_id: ScopeId) {
+        self.to.mark_scope_dirty(scope_id);
+    }
+
+    fn highlight_elements(&mut self, ids: &[ElementId]) {
+        self.to.highlight_elements(ids);
+    }
+}
+
+impl VirtualDom {
+    /// Check if the current render wrote all of the mutations it is allowed to write
+    pub(crate) fn is_over_mutation_budget(&self) -> bool {
+        self.mutation_budget
+            .is_some_and(|budget| self.mutations_written.get() >= budget)
+    }
+
+    /// Start counting the mutations of a new render from zero
+    pub(crate) fn reset_mutation_budget(&self) {
+        self.mutations_written.set(0);
+    }
+
+    /// Leave a component whose props changed for the next render if the current render is over its budget. Returns
+    /// true if the component was deferred.
+    ///
+    /// The props of the component were already updated, so it renders with them once it is popped off the dirty
+    /// scopes. Until then the renderer keeps showing the old nodes of the component.
+    pub(crate) fn defer_over_budget(&mut self, scope: ScopeId) -> bool {
+        if !self.is_over_mutation_budget() {
+            return false;
+        }
+        let Some(height) = self.runtime.get_state(scope).map(|state| state.height()) else {
+            return false;
+        };
+        self.queue_scope(ScopeOrder::new(height, scope));
+        true
+    }
+}
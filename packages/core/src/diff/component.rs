@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use crate::{
     any_props::AnyProps,
-    innerlude::{ElementRef, MountId, ScopeOrder, VComponent, WriteMutations},
+    innerlude::{ElementRef, MountId, RenderReason, ScopeOrder, VComponent, WriteMutations},
     nodes::RenderReturn,
     nodes::VNode,
     scopes::ScopeId,
@@ -16,6 +16,13 @@ impl VirtualDom {
         scope: ScopeId,
         new_nodes: RenderReturn,
     ) {
+        if let Some(boundary) = self.suspense_boundary(scope) {
+            return self.diff_suspense_boundary(to, scope, boundary, new_nodes);
+        }
+        if let Some(portal) = self.portal(scope) {
+            return self.diff_portal(to, scope, portal, new_nodes);
+        }
+
         self.runtime.scope_stack.borrow_mut().push(scope);
         let scope_state = &mut self.scopes[scope.0];
         // Load the old and new bump arenas
@@ -40,6 +47,13 @@ impl VirtualDom {
         new_node: RenderReturn,
         parent: Option<ElementRef>,
     ) -> usize {
+        if let Some(boundary) = self.suspense_boundary(scope) {
+            return self.create_suspense_boundary(to, scope, boundary, new_node, parent);
+        }
+        if let Some(portal) = self.portal(scope) {
+            return self.create_portal(to, scope, portal, new_node, parent);
+        }
+
         self.runtime.scope_stack.borrow_mut().push(scope);
 
         // Create the node
@@ -83,7 +97,13 @@ impl VNode {
             return;
         }
 
+        // Leave the component for the next render if this render already wrote its share of mutations
+        if dom.defer_over_budget(scope_id) {
+            return;
+        }
+
         // Now run the component and diff it
+        dom.set_render_reason(scope_id, RenderReason::PropsChanged);
         let new = dom.run_scope(scope_id);
         dom.diff_scope(to, scope_id, new);
 
@@ -126,6 +146,13 @@ impl VNode {
         // Store the scope id for the next render
         dom.mounts[mount.0].mounted_dynamic_nodes[idx] = scope.0;
 
+        // If the virtual dom was restored from a snapshot, give the component its state before it runs
+        #[cfg(feature = "serialize")]
+        if !dom.restored_scopes.is_empty() {
+            let path = dom.scope_path(mount, idx);
+            dom.restore_scope(path, scope);
+        }
+
         let new = dom.run_scope(scope);
 
         dom.create_scope(to, scope, new, parent)
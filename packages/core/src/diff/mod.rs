@@ -9,9 +9,14 @@ use crate::{
     Template, TemplateNode,
 };
 
+mod budget;
 mod component;
 mod iterator;
 mod node;
+mod portal;
+mod suspense;
+
+pub(crate) use budget::BudgetedMutations;
 
 impl VirtualDom {
     pub(crate) fn create_children<'a>(
@@ -95,6 +100,10 @@ impl VirtualDom {
             node.remove_node(self, to, replace_with, gen_muts)
         };
 
+        // A boundary that shows its fallback also has children mounted outside of the dom
+        self.remove_suspended_nodes(scope);
+        self.remove_portal_nodes(to, scope);
+
         // Now drop all the resources
         self.drop_scope(scope);
     }
@@ -1,7 +1,9 @@
 use crate::innerlude::MountId;
-use crate::{Attribute, AttributeValue, DynamicNode::*};
+use crate::runtime::RuntimeGuard;
+use crate::{Attribute, AttributeValue, DynamicNode::*, Event};
 use crate::{VNode, VirtualDom, WriteMutations};
 use core::iter::Peekable;
+use std::{any::Any, rc::Rc};
 
 use crate::{
     arena::ElementId,
@@ -12,6 +14,9 @@ use crate::{
     TemplateNode::*,
 };
 
+/// The listener the virtual dom calls when an element is removed
+pub(crate) const UNMOUNTED_LISTENER: &str = "onunmounted";
+
 impl VNode {
     pub(crate) fn diff_node(
         &self,
@@ -96,8 +101,9 @@ impl VNode {
         };
         match (old_node, new_node) {
             (Text(old), Text(new)) => {
+                let threshold = dom.text_diff_threshold;
                 let mount = &dom.mounts[mount.0];
-                self.diff_vtext( to, mount, idx, old, new)
+                self.diff_vtext( to, mount, idx, old, new, threshold)
             },
             (Placeholder(_), Placeholder(_)) => {},
             (Fragment(old), Fragment(new)) => dom.diff_non_empty_fragment(to, old, new, Some(parent())),
@@ -162,7 +168,8 @@ impl VNode {
 
     /// Diff the two text nodes
     ///
-    /// This just sets the text of the node if it's different.
+    /// This sets the text of the node if it's different. Ranged and long text only replaces the range that changed if
+    /// that range is less than half of the new text.
     fn diff_vtext(
         &self,
         to: &mut impl WriteMutations,
@@ -170,11 +177,26 @@ impl VNode {
         idx: usize,
         left: &VText,
         right: &VText,
+        threshold: Option<usize>,
     ) {
-        if left.value != right.value {
-            let id = ElementId(mount.mounted_dynamic_nodes[idx]);
-            to.set_node_text(&right.value, id);
+        if left.value == right.value {
+            return;
+        }
+
+        let id = ElementId(mount.mounted_dynamic_nodes[idx]);
+        let ranged =
+            right.ranged || threshold.is_some_and(|threshold| right.value.len() >= threshold);
+        if ranged {
+            let (start, deleted, inserted) = changed_text_range(&left.value, &right.value);
+            if inserted.len() * 2 < right.value.len() {
+                let start = left.value[..start].encode_utf16().count();
+                let delete = deleted.encode_utf16().count();
+                to.replace_node_text(start, delete, inserted, &right.value, id);
+                return;
+            }
         }
+
+        to.set_node_text(&right.value, id);
     }
 
     pub(crate) fn replace<'a>(
@@ -199,6 +221,9 @@ impl VNode {
     ) {
         let mount = self.mount.get();
 
+        // Let the elements know they are being removed while the components that rendered them are still alive
+        self.call_unmounted_listeners(dom);
+
         // Clean up any attributes that have claimed a static node as dynamic for mount/unmounts
         // Will not generate mutations!
         self.reclaim_attributes(mount, dom);
@@ -218,6 +243,25 @@ impl VNode {
         tracing::trace!(?self, "removed node");
     }
 
+    /// Call the `onunmounted` listeners of the elements in this node. The virtual dom calls them instead of the
+    /// renderer, so they run the same way everywhere. The event has no data.
+    fn call_unmounted_listeners(&self, dom: &VirtualDom) {
+        let listeners = self
+            .dynamic_attrs
+            .iter()
+            .flat_map(|attrs| attrs.iter())
+            .filter(|attr| attr.name == UNMOUNTED_LISTENER)
+            .filter_map(|attr| match &attr.value {
+                AttributeValue::Listener(listener) => Some(listener),
+                _ => None,
+            });
+
+        for listener in listeners {
+            let _runtime = RuntimeGuard::new(dom.runtime.clone());
+            listener.call(Event::new(Rc::new(()) as Rc<dyn Any>, false));
+        }
+    }
+
     fn reclaim_roots(
         &self,
         mount: MountId,
@@ -397,6 +441,8 @@ impl VNode {
 
     fn remove_attribute(&self, attribute: &Attribute, id: ElementId, to: &mut impl WriteMutations) {
         match &attribute.value {
+            // The virtual dom calls unmounted listeners itself, so renderers never see them
+            AttributeValue::Listener(_) if attribute.name == UNMOUNTED_LISTENER => {}
             AttributeValue::Listener(_) => {
                 to.remove_event_listener(&attribute.name[2..], id);
             }
@@ -427,7 +473,9 @@ impl VNode {
                     mount,
                 };
                 dom.elements[id.0] = Some(element_ref);
-                to.create_event_listener(&attribute.name[2..], id);
+                if attribute.name != UNMOUNTED_LISTENER {
+                    to.create_event_listener(&attribute.name[2..], id);
+                }
             }
             _ => {
                 to.set_attribute(attribute.name, attribute.namespace, &attribute.value, id);
@@ -666,7 +714,7 @@ impl VNode {
                 to.create_placeholder(id);
                 1
             }
-            Text(VText { value }) => {
+            Text(VText { value, .. }) => {
                 let id = mount.mount_node(idx, dom);
                 to.create_text_node(value, id);
                 1
@@ -974,3 +1022,36 @@ fn matching_components<'a>(
         })
         .collect()
 }
+
+/// Find the range of the old text that changed. Returns the byte offset the texts start to differ at, the text that
+/// was removed from the old text and the text that was inserted in its place.
+fn changed_text_range<'a>(old: &'a str, new: &'a str) -> (usize, &'a str, &'a str) {
+    let prefix = old
+        .char_indices()
+        .zip(new.chars())
+        .find(|((_, old), new)| old != new)
+        .map(|((idx, _), _)| idx)
+        .unwrap_or_else(|| old.len().min(new.len()));
+
+    // The suffix can't overlap the prefix in either text
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(old, new)| old == new)
+        .map(|(c, _)| c.len_utf8())
+        .scan(0, |len, char_len| {
+            *len += char_len;
+            Some(*len)
+        })
+        .take_while(|len| *len <= max_suffix)
+        .last()
+        .unwrap_or(0);
+
+    (
+        prefix,
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    )
+}
@@ -0,0 +1,73 @@
+use crate::{
+    innerlude::{ElementRef, PortalContext, WriteMutations},
+    nodes::RenderReturn,
+    scopes::ScopeId,
+    virtual_dom::VirtualDom,
+};
+
+impl VirtualDom {
+    /// Get the portal a scope renders if the scope is a [`Portal`](crate::Portal)
+    pub(crate) fn portal(&self, scope: ScopeId) -> Option<PortalContext> {
+        self.runtime.get_state(scope)?.has_context()
+    }
+
+    /// Create the children of a portal in its target element, and a placeholder where the portal is
+    pub(crate) fn create_portal(
+        &mut self,
+        to: &mut impl WriteMutations,
+        scope: ScopeId,
+        portal: PortalContext,
+        children: RenderReturn,
+        parent: Option<ElementRef>,
+    ) -> usize {
+        self.runtime.scope_stack.borrow_mut().push(scope);
+
+        let m = children.create(self, to, parent);
+        portal.mount_target();
+        to.append_to_portal(&portal.target(), m);
+        portal.set_children(children);
+
+        let placeholder = RenderReturn::default();
+        let m = placeholder.create(self, to, parent);
+        self.scopes[scope.0].last_rendered_node = Some(placeholder);
+
+        self.runtime.scope_stack.borrow_mut().pop();
+        m
+    }
+
+    /// Diff the new children of a portal and move them if the target of the portal changed
+    pub(crate) fn diff_portal(
+        &mut self,
+        to: &mut impl WriteMutations,
+        scope: ScopeId,
+        portal: PortalContext,
+        new_nodes: RenderReturn,
+    ) {
+        self.runtime.scope_stack.borrow_mut().push(scope);
+
+        let old = portal
+            .take_children()
+            .expect("the children of a portal to be mounted");
+        old.diff_node(&new_nodes, self, to);
+
+        if portal.mount_target() {
+            let mut roots = Vec::new();
+            new_nodes.collect_root_ids(self, &mut roots, false);
+            for id in &roots {
+                to.push_root(*id);
+            }
+            to.append_to_portal(&portal.target(), roots.len());
+        }
+        portal.set_children(new_nodes);
+
+        self.runtime.scope_stack.borrow_mut().pop();
+    }
+
+    /// Remove the children of a portal that is being removed. They aren't under the element of the portal, so they
+    /// are always removed from the dom on their own.
+    pub(crate) fn remove_portal_nodes(&mut self, to: &mut impl WriteMutations, scope: ScopeId) {
+        if let Some(nodes) = self.portal(scope).and_then(|portal| portal.take_children()) {
+            nodes.remove_node(self, to, None, true);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    arena::ElementId,
+    innerlude::{ElementRef, NoOpMutations, SuspenseContext, WriteMutations},
+    nodes::RenderReturn,
+    scopes::ScopeId,
+    virtual_dom::VirtualDom,
+    AttributeValue, DynamicNode, Template, TemplateNode, VNode,
+};
+
+/// Mutations for nodes that are mounted under a suspended boundary
+///
+/// Those nodes aren't in the real dom, so every mutation is thrown out except for the templates they register. The
+/// templates are passed on to the real renderer so it can load them once the boundary resolves.
+#[derive(Default)]
+pub(crate) struct OffscreenMutations {
+    templates: Vec<Template>,
+}
+
+impl OffscreenMutations {
+    fn flush(self, to: &mut impl WriteMutations) {
+        for template in self.templates {
+            to.register_template(template);
+        }
+    }
+}
+
+impl WriteMutations for OffscreenMutations {
+    fn register_template(&mut self, template: Template) {
+        self.templates.push(template);
+    }
+
+    fn append_children(&mut self, _: ElementId, _: usize) {}
+
+    fn append_to_portal(&mut self, _: &str, _: usize) {}
+
+    fn assign_node_id(&mut self, _: &'static [u8], _: ElementId) {}
+
+    fn create_placeholder(&mut self, _: ElementId) {}
+
+    fn create_text_node(&mut self, _: &str, _: ElementId) {}
+
+    fn hydrate_text_node(&mut self, _: &'static [u8], _: &str, _: ElementId) {}
+
+    fn load_template(&mut self, _: &'static str, _: usize, _: ElementId) {}
+
+    fn replace_node_with(&mut self, _: ElementId, _: usize) {}
+
+    fn replace_placeholder_with_nodes(&mut self, _: &'static [u8], _: usize) {}
+
+    fn insert_nodes_after(&mut self, _: ElementId, _: usize) {}
+
+    fn insert_nodes_before(&mut self, _: ElementId, _: usize) {}
+
+    fn set_attribute(
+        &mut self,
+        _: &'static str,
+        _: Option<&'static str>,
+        _: &AttributeValue,
+        _: ElementId,
+    ) {
+    }
+
+    fn set_node_text(&mut self, _: &str, _: ElementId) {}
+
+    fn create_event_listener(&mut self, _: &'static str, _: ElementId) {}
+
+    fn remove_event_listener(&mut self, _: &'static str, _: ElementId) {}
+
+    fn remove_node(&mut self, _: ElementId) {}
+
+    fn push_root(&mut self, _: ElementId) {}
+}
+
+impl VirtualDom {
+    /// Get the suspense boundary a scope renders if the scope is a [`SuspenseBoundary`](crate::SuspenseBoundary)
+    pub(crate) fn suspense_boundary(&self, scope: ScopeId) -> Option<SuspenseContext> {
+        self.runtime.get_state(scope)?.has_context()
+    }
+
+    /// Check if a scope is mounted under a boundary that shows its fallback
+    fn is_offscreen(&self, scope: ScopeId) -> bool {
+        let mut parent = self.runtime.get_state(scope).and_then(|s| s.parent_id);
+        while let Some(id) = parent {
+            let Some(state) = self.runtime.get_state(id) else {
+                break;
+            };
+            if state
+                .has_context::<SuspenseContext>()
+                .is_some_and(|boundary| boundary.suspended())
+            {
+                return true;
+            }
+            parent = state.parent_id;
+        }
+        false
+    }
+
+    /// Diff a scope that was rerun outside of the render of its parent
+    pub(crate) fn diff_dirty_scope(
+        &mut self,
+        to: &mut impl WriteMutations,
+        scope: ScopeId,
+        new_nodes: RenderReturn,
+    ) {
+        if self.is_offscreen(scope) {
+            let mut offscreen = OffscreenMutations::default();
+            self.diff_scope(&mut offscreen, scope, new_nodes);
+            offscreen.flush(to);
+        } else {
+            self.diff_scope(to, scope, new_nodes);
+        }
+    }
+
+    /// Run some diffing work for the fallback of a boundary
+    ///
+    /// Components in the fallback are created as children of the scope above the boundary so they don't suspend the
+    /// boundary they are the fallback of, and so they aren't hidden when the boundary is suspended.
+    fn in_fallback<O>(&mut self, boundary: ScopeId, f: impl FnOnce(&mut Self) -> O) -> O {
+        let Some(parent) = self.runtime.get_state(boundary).and_then(|s| s.parent_id) else {
+            return f(self);
+        };
+        self.runtime.scope_stack.borrow_mut().push(parent);
+        let out = f(self);
+        self.runtime.scope_stack.borrow_mut().pop();
+        out
+    }
+
+    /// Create the children of a boundary. The children are mounted first without touching the real dom. If any of them
+    /// suspends, the fallback is created instead and the children stay hidden.
+    pub(crate) fn create_suspense_boundary(
+        &mut self,
+        to: &mut impl WriteMutations,
+        scope: ScopeId,
+        boundary: SuspenseContext,
+        children: RenderReturn,
+        parent: Option<ElementRef>,
+    ) -> usize {
+        self.runtime.scope_stack.borrow_mut().push(scope);
+
+        let mut offscreen = OffscreenMutations::default();
+        children.create(self, &mut offscreen, parent);
+        offscreen.flush(to);
+
+        let (m, rendered) = if boundary.has_suspended_tasks() {
+            let fallback = boundary.fallback();
+            let m = self.in_fallback(scope, |dom| fallback.create(dom, to, parent));
+            boundary.set_suspended_nodes(children);
+            (m, fallback)
+        } else {
+            (children.remount(self, to), children)
+        };
+        self.scopes[scope.0].last_rendered_node = Some(rendered);
+
+        self.runtime.scope_stack.borrow_mut().pop();
+        m
+    }
+
+    /// Diff the new children of a boundary and switch between the children and the fallback if the boundary started or
+    /// stopped waiting on its children
+    pub(crate) fn diff_suspense_boundary(
+        &mut self,
+        to: &mut impl WriteMutations,
+        scope: ScopeId,
+        boundary: SuspenseContext,
+        new_nodes: RenderReturn,
+    ) {
+        self.runtime.scope_stack.borrow_mut().push(scope);
+        let old = self.scopes[scope.0].last_rendered_node.take().unwrap();
+
+        let rendered = match boundary.take_suspended_nodes() {
+            // The children are in the real dom
+            None => {
+                old.diff_node(&new_nodes, self, to);
+                if boundary.has_suspended_tasks() {
+                    // Hide the children and show the fallback in their place
+                    let fallback = boundary.fallback();
+                    let parent = self.mounts[new_nodes.mount.get().0].parent;
+                    let m = self.in_fallback(scope, |dom| fallback.create(dom, to, parent));
+                    let mut roots = Vec::new();
+                    new_nodes.collect_root_ids(self, &mut roots, true);
+                    let (last, rest) = roots.split_last().unwrap();
+                    for id in rest {
+                        to.remove_node(*id);
+                    }
+                    to.replace_node_with(*last, m);
+                    boundary.set_suspended_nodes(new_nodes);
+                    fallback
+                } else {
+                    new_nodes
+                }
+            }
+            // The fallback is in the real dom
+            Some(suspended) => {
+                let mut offscreen = OffscreenMutations::default();
+                suspended.diff_node(&new_nodes, self, &mut offscreen);
+                offscreen.flush(to);
+                if boundary.has_suspended_tasks() {
+                    let fallback = boundary.fallback();
+                    self.in_fallback(scope, |dom| old.diff_node(&fallback, dom, to));
+                    boundary.set_suspended_nodes(new_nodes);
+                    fallback
+                } else {
+                    // Every child is ready, so move the children into the real dom
+                    let m = new_nodes.remount(self, to);
+                    old.remove_node(self, to, Some(m), true);
+                    new_nodes
+                }
+            }
+        };
+        self.scopes[scope.0].last_rendered_node = Some(rendered);
+
+        self.runtime.scope_stack.borrow_mut().pop();
+    }
+
+    /// Remove the hidden children of a boundary that is being removed
+    pub(crate) fn remove_suspended_nodes(&mut self, scope: ScopeId) {
+        if let Some(nodes) = self
+            .suspense_boundary(scope)
+            .and_then(|boundary| boundary.take_suspended_nodes())
+        {
+            nodes.remove_node(self, &mut NoOpMutations, None, false);
+        }
+    }
+}
+
+impl VNode {
+    /// Write the mutations to create a node that is already mounted, like the children of a boundary that resolved. The
+    /// node keeps its element ids and its components aren't rerun.
+    pub(crate) fn remount(&self, dom: &VirtualDom, to: &mut impl WriteMutations) -> usize {
+        let template = self.template.get();
+        let mount = &dom.mounts[self.mount.get().0];
+
+        template
+            .roots
+            .iter()
+            .enumerate()
+            .map(|(root_idx, root)| match root {
+                TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                    let id = *id;
+                    let element = ElementId(mount.mounted_dynamic_nodes[id]);
+                    match &self.dynamic_nodes[id] {
+                        DynamicNode::Text(text) => {
+                            to.create_text_node(&text.value, element);
+                            1
+                        }
+                        DynamicNode::Placeholder(_) => {
+                            to.create_placeholder(element);
+                            1
+                        }
+                        _ => self.remount_dynamic_node(id, dom, to),
+                    }
+                }
+                TemplateNode::Text { .. } => {
+                    to.load_template(template.name, root_idx, mount.root_ids[root_idx]);
+                    1
+                }
+                TemplateNode::Element { .. } => {
+                    let root = mount.root_ids[root_idx];
+                    to.load_template(template.name, root_idx, root);
+
+                    // Write the dynamic attributes under this root
+                    let mut last_path = None;
+                    for (idx, path) in template.attr_paths.iter().enumerate() {
+                        if path[0] as usize != root_idx {
+                            continue;
+                        }
+                        let element = mount.mounted_attributes[idx];
+                        if path.len() > 1 && last_path != Some(path) {
+                            to.assign_node_id(&path[1..], element);
+                        }
+                        last_path = Some(path);
+                        for attribute in &*self.dynamic_attrs[idx] {
+                            match &attribute.value {
+                                AttributeValue::Listener(_)
+                                    if attribute.name == super::node::UNMOUNTED_LISTENER => {}
+                                AttributeValue::Listener(_) => {
+                                    to.create_event_listener(&attribute.name[2..], element)
+                                }
+                                value => to.set_attribute(
+                                    attribute.name,
+                                    attribute.namespace,
+                                    value,
+                                    element,
+                                ),
+                            }
+                        }
+                    }
+
+                    // Fill in the dynamic nodes under this root. Later nodes are written first so replacing a
+                    // placeholder doesn't move the nodes that come after it.
+                    let mut nodes: Vec<_> = template
+                        .node_paths
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, path)| path.len() > 1 && path[0] as usize == root_idx)
+                        .collect();
+                    nodes.sort_by_key(|(_, path)| *path);
+                    for (idx, path) in nodes.into_iter().rev() {
+                        let element = ElementId(mount.mounted_dynamic_nodes[idx]);
+                        match &self.dynamic_nodes[idx] {
+                            DynamicNode::Text(text) => {
+                                to.hydrate_text_node(&path[1..], &text.value, element)
+                            }
+                            DynamicNode::Placeholder(_) => to.assign_node_id(&path[1..], element),
+                            _ => {
+                                let m = self.remount_dynamic_node(idx, dom, to);
+                                if m > 0 {
+                                    to.replace_placeholder_with_nodes(&path[1..], m);
+                                }
+                            }
+                        }
+                    }
+
+                    1
+                }
+            })
+            .sum()
+    }
+
+    /// Remount the components and fragments in a dynamic node
+    fn remount_dynamic_node(
+        &self,
+        idx: usize,
+        dom: &VirtualDom,
+        to: &mut impl WriteMutations,
+    ) -> usize {
+        match &self.dynamic_nodes[idx] {
+            DynamicNode::Component(_) => {
+                let scope = ScopeId(dom.mounts[self.mount.get().0].mounted_dynamic_nodes[idx]);
+                if let Some(portal) = dom.portal(scope) {
+                    portal.with_children(|children| {
+                        let m = children.remount(dom, to);
+                        to.append_to_portal(&portal.target(), m);
+                    });
+                }
+                dom.scopes[scope.0].root_node().remount(dom, to)
+            }
+            DynamicNode::Fragment(nodes) => nodes.iter().map(|node| node.remount(dom, to)).sum(),
+            DynamicNode::Text(_) | DynamicNode::Placeholder(_) => 0,
+        }
+    }
+
+    /// Collect the ids of the top level elements of this node in the real dom. The children of portals under this node
+    /// are only collected with `portals`.
+    pub(super) fn collect_root_ids(
+        &self,
+        dom: &VirtualDom,
+        ids: &mut Vec<ElementId>,
+        portals: bool,
+    ) {
+        let mount = &dom.mounts[self.mount.get().0];
+        for (idx, root) in self.template.get().roots.iter().enumerate() {
+            match root {
+                TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                    match &self.dynamic_nodes[*id] {
+                        DynamicNode::Component(_) => {
+                            let scope = ScopeId(mount.mounted_dynamic_nodes[*id]);
+                            if let Some(portal) = dom.portal(scope).filter(|_| portals) {
+                                portal.with_children(|children| {
+                                    children.collect_root_ids(dom, ids, portals)
+                                });
+                            }
+                            dom.scopes[scope.0]
+                                .root_node()
+                                .collect_root_ids(dom, ids, portals);
+                        }
+                        DynamicNode::Fragment(nodes) => {
+                            for node in nodes {
+                                node.collect_root_ids(dom, ids, portals);
+                            }
+                        }
+                        DynamicNode::Text(_) | DynamicNode::Placeholder(_) => {
+                            ids.push(ElementId(mount.mounted_dynamic_nodes[*id]))
+                        }
+                    }
+                }
+                TemplateNode::Element { .. } | TemplateNode::Text { .. } => {
+                    ids.push(mount.root_ids[idx])
+                }
+            }
+        }
+    }
+}
@@ -22,9 +22,21 @@ pub struct CapturedPanic {
     pub error: Box<dyn Any + 'static>,
 }
 
+impl CapturedPanic {
+    /// The message the component panicked with, if it panicked with a string
+    pub fn message(&self) -> Option<&str> {
+        match self.error.downcast_ref::<&'static str>() {
+            Some(message) => Some(message),
+            None => self.error.downcast_ref::<String>().map(String::as_str),
+        }
+    }
+}
+
 impl Debug for CapturedPanic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("CapturedPanic").finish()
+        f.debug_struct("CapturedPanic")
+            .field("message", &self.message())
+            .finish()
     }
 }
 
@@ -56,12 +68,18 @@ impl Debug for ErrorBoundaryInner {
 /// A trait for any type that can be downcast to a concrete type and implements Debug. This is automatically implemented for all types that implement Any + Debug.
 pub trait AnyDebug: Any + Debug {
     fn as_any(&self) -> &dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
 impl<T: Any + Debug> AnyDebug for T {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -92,11 +110,35 @@ impl CapturedError {
     /// Downcast the error type into a concrete error type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         if TypeId::of::<T>() == (*self.error).type_id() {
-            self.error.as_any().downcast_ref::<T>()
+            (*self.error).as_any().downcast_ref::<T>()
         } else {
             None
         }
     }
+
+    /// Take the error out if it is a concrete error type, or get the captured error back if it isn't
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// #[derive(Debug)]
+    /// struct NotFound(String);
+    ///
+    /// fn handle_error(error: CapturedError) -> Element {
+    ///     match error.consume_error::<NotFound>() {
+    ///         Ok(NotFound(path)) => rsx! { "Nothing lives at {path}" },
+    ///         Err(error) => rsx! { "Something went wrong: {error}" },
+    ///     }
+    /// }
+    /// ```
+    pub fn consume_error<T: 'static>(self) -> Result<T, Self> {
+        if !(*self.error).as_any().is::<T>() {
+            return Err(self);
+        }
+        match self.error.into_any().downcast::<T>() {
+            Ok(error) => Ok(*error),
+            Err(_) => unreachable!("the type of the error was checked above"),
+        }
+    }
 }
 
 impl Default for ErrorBoundaryInner {
@@ -141,6 +183,15 @@ impl ErrorBoundary {
     pub fn take_error(&self) -> Option<CapturedError> {
         self.inner.error.take()
     }
+
+    /// Forget the last error and render the children of the boundary again
+    ///
+    /// The children that failed are created from scratch, so they run their hooks again. Call this from the error
+    /// handler to let the user retry after the problem was fixed.
+    pub fn reset(&self) {
+        self.inner.error.take();
+        self.inner._id.needs_update();
+    }
 }
 
 /// A trait to allow results to be thrown upwards to the nearest Error Boundary
@@ -225,16 +276,54 @@ pub trait Throw<S = ()>: Sized {
 pub(crate) fn throw_error<T>(e: impl Debug + 'static) -> Option<T> {
     if let Some(cx) = try_consume_context::<ErrorBoundary>() {
         match current_scope_id() {
-            Some(id) => cx.insert_error(id, Box::new(e), Backtrace::capture()),
+            Some(id) => cx.insert_error(id, e, Backtrace::capture()),
             None => {
                 tracing::error!("Cannot throw error outside of a component's scope.")
             }
         }
+    } else if let (Some(hook), Some(scope)) =
+        (ScopeId::ROOT.has_context::<ErrorHook>(), current_scope_id())
+    {
+        hook.call(&CapturedError {
+            error: Box::new(e),
+            backtrace: Backtrace::capture(),
+            scope,
+        });
     }
 
     None
 }
 
+/// A callback for errors that are thrown outside of any error boundary.
+///
+/// Provide it as a context in the root scope to log or report errors that no error boundary handles:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// let dom = VirtualDom::new(|| None)
+///     .with_root_context(ErrorHook::new(|error| eprintln!("{error}")));
+/// ```
+#[derive(Clone)]
+pub struct ErrorHook(Rc<dyn Fn(&CapturedError)>);
+
+impl ErrorHook {
+    /// Create a new error hook
+    pub fn new(hook: impl Fn(&CapturedError) + 'static) -> Self {
+        Self(Rc::new(hook))
+    }
+
+    /// Call the hook with an error
+    pub fn call(&self, error: &CapturedError) {
+        (self.0)(error)
+    }
+}
+
+impl Debug for ErrorHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorHook").finish()
+    }
+}
+
 /// We call clone on any errors that can be owned out of a reference
 impl<'a, T, O: Debug + 'static, E: ToOwned<Owned = O>> Throw for &'a Result<T, E> {
     type Out = &'a T;
@@ -375,9 +464,9 @@ impl<T> ErrorBoundaryPropsBuilder_Optional<T> for (T,) {
 impl<__handle_error> ErrorBoundaryPropsBuilder<((), __handle_error)> {
     pub fn children(
         self,
-        children: Element,
+        children: impl ::core::convert::Into<Element>,
     ) -> ErrorBoundaryPropsBuilder<((Element,), __handle_error)> {
-        let children = (children,);
+        let children = (children.into(),);
         let (_, handle_error) = self.fields;
         ErrorBoundaryPropsBuilder {
             fields: (children, handle_error),
@@ -450,6 +539,7 @@ impl<
 /// ## Details
 ///
 /// Error boundaries handle errors within a specific part of your application. Any errors passed in a child with [`Throw`] will be caught by the nearest error boundary.
+/// Children that panic while rendering or in a task they spawned are caught as a [`CapturedPanic`] on platforms that support unwinding.
 ///
 /// ## Example
 ///
@@ -469,6 +559,26 @@ impl<
 /// Error boundaries are an easy way to handle errors in your application.
 /// They are similar to `try/catch` in JavaScript, but they only catch errors in the tree below them.
 /// Error boundaries are quick to implement, but it can be useful to individually handle errors in your components to provide a better user experience when you know that an error is likely to occur.
+///
+/// The error handler runs in the scope of the boundary, so it can get the boundary from the context and
+/// [`reset`](crate::prelude::ErrorBoundary::reset) it to render the children again:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # fn ThrowsError() -> Element { todo!() }
+/// rsx! {
+///     ErrorBoundary {
+///         handle_error: |error| {
+///             let boundary = consume_context::<ErrorBoundary>();
+///             rsx! {
+///                 "{error}"
+///                 button { onclick: move |_| boundary.reset(), "Try again" }
+///             }
+///         },
+///         ThrowsError {}
+///     }
+/// };
+/// ```
 #[allow(non_upper_case_globals, non_snake_case)]
 pub fn ErrorBoundary(props: ErrorBoundaryProps) -> Element {
     let error_boundary = use_error_boundary();
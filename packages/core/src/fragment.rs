@@ -37,8 +37,8 @@ pub struct FragmentProps(Element);
 
 pub struct FragmentBuilder<const BUILT: bool>(Element);
 impl FragmentBuilder<false> {
-    pub fn children(self, children: Element) -> FragmentBuilder<true> {
-        FragmentBuilder(children)
+    pub fn children(self, children: impl Into<Element>) -> FragmentBuilder<true> {
+        FragmentBuilder(children.into())
     }
 }
 impl<const A: bool> FragmentBuilder<A> {
@@ -89,6 +89,9 @@ pub fn spawn_isomorphic(fut: impl Future<Output = ()> + 'static) -> Task {
 
 /// Spawns the future but does not return the [`Task`]. This task will automatically be canceled when the component is dropped.
 ///
+/// If the task panics, the panic is caught as a [`crate::CapturedPanic`] and thrown into the nearest error boundary of
+/// the component, just like a panic while rendering. Use a [`crate::TaskScope`] to read the output of the task.
+///
 /// # Example
 /// ```rust
 /// use dioxus::prelude::*;
@@ -250,6 +253,49 @@ pub fn needs_update_any(id: ScopeId) {
     Runtime::with_current_scope(|cx| cx.needs_update_any(id));
 }
 
+/// Run a closure and mark the updates it causes as a transition
+///
+/// Components that rerender because of a signal written in the closure render after all other updates, one component
+/// at a time, so the renderer can handle new events in between. Use this for expensive updates that can lag behind the
+/// input that caused them, like filtering a large list while the user types in a search box. Tasks the closure wakes,
+/// like the tasks that update memos, schedule their updates as part of the transition too.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut query = use_signal(String::new);
+///     let mut filter = use_signal(String::new);
+///
+///     rsx! {
+///         input {
+///             value: "{query}",
+///             oninput: move |event| {
+///                 // The input updates right away
+///                 query.set(event.value());
+///                 // The list catches up when the renderer isn't busy with new input
+///                 start_transition(move || filter.set(event.value()));
+///             }
+///         }
+///         Results { filter }
+///     }
+/// }
+///
+/// #[component]
+/// fn Results(filter: ReadOnlySignal<String>) -> Element {
+///     rsx! {
+///         for item in (0..10_000).map(|i| i.to_string()).filter(|item| item.contains(&*filter.read())) {
+///             p { "{item}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn start_transition<O>(f: impl FnOnce() -> O) -> O {
+    match Runtime::current() {
+        Some(runtime) => runtime.in_transition(f),
+        None => f(),
+    }
+}
+
 /// Schedule an update for the current component
 ///
 /// Note: Unlike [`needs_update`], the function returned by this method will work outside of the dioxus runtime.
@@ -0,0 +1,136 @@
+//! Literals in rsx that can be changed while hot reloading
+//!
+//! The rsx! macro gives every literal prop passed to a component (`Link { to: "/home" }`) and every literal in the
+//! iterator of a `for` loop or the condition of an `if` (`for i in 0..10`) an id in its rsx call, and reads the literal
+//! through [`hot_literal`]. When the hot reloader sees only those literals change, it sends the new values with
+//! [`VirtualDom::update_hot_literals`](crate::VirtualDom::update_hot_literals) instead of forcing a rebuild.
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+/// The value of a hot reloadable literal
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum HotLiteral {
+    /// A string literal
+    Str(
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "crate::nodes::deserialize_string_leaky")
+        )]
+        &'static str,
+    ),
+    /// An integer literal of any integer type
+    Int(i128),
+    /// A float literal of any float type
+    Float(f64),
+    /// A bool literal
+    Bool(bool),
+}
+
+/// New values for the hot reloadable literals in one rsx! call
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(bound(deserialize = "'de: 'static")))]
+pub struct HotLiterals {
+    /// The `file:line:column` of the rsx! call. This is the name of its templates without the trailing id
+    pub location: String,
+
+    /// The new values, paired with the id the rsx! macro gave each literal when the app was compiled
+    pub values: Vec<(usize, HotLiteral)>,
+}
+
+/// A type a hot reloadable literal can have
+pub trait HotLiteralValue: Sized {
+    /// Convert a literal to this type, or return `None` if the literal has a different type or doesn't fit
+    fn from_hot_literal(literal: HotLiteral) -> Option<Self>;
+}
+
+impl HotLiteralValue for &'static str {
+    fn from_hot_literal(literal: HotLiteral) -> Option<Self> {
+        match literal {
+            HotLiteral::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl HotLiteralValue for bool {
+    fn from_hot_literal(literal: HotLiteral) -> Option<Self> {
+        match literal {
+            HotLiteral::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_hot_literal_int {
+    ($($ty:ty),*) => {
+        $(
+            impl HotLiteralValue for $ty {
+                fn from_hot_literal(literal: HotLiteral) -> Option<Self> {
+                    match literal {
+                        HotLiteral::Int(value) => value.try_into().ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_hot_literal_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl HotLiteralValue for f64 {
+    fn from_hot_literal(literal: HotLiteral) -> Option<Self> {
+        match literal {
+            HotLiteral::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl HotLiteralValue for f32 {
+    fn from_hot_literal(literal: HotLiteral) -> Option<Self> {
+        f64::from_hot_literal(literal).map(|value| value as f32)
+    }
+}
+
+/// The hot reloaded literals of every rsx! call, keyed by location. Liveview renders on many threads, so this is shared
+fn hot_literals() -> &'static RwLock<HashMap<String, HashMap<usize, HotLiteral>>> {
+    static HOT_LITERALS: OnceLock<RwLock<HashMap<String, HashMap<usize, HotLiteral>>>> =
+        OnceLock::new();
+    HOT_LITERALS.get_or_init(Default::default)
+}
+
+/// Store new values for the literals of an rsx! call
+pub(crate) fn set_hot_literals(literals: HotLiterals) {
+    let mut all = hot_literals().write().unwrap();
+    all.entry(literals.location)
+        .or_default()
+        .extend(literals.values);
+}
+
+/// Read a hot reloadable literal, returning the hot reloaded value if there is one.
+///
+/// This is called by the rsx! macro. `template` is the name of the template the literal is in and `id` is the id of the
+/// literal in its rsx! call. In release builds this always returns `default`.
+#[doc(hidden)]
+#[inline]
+pub fn hot_literal<T: HotLiteralValue>(template: &str, id: usize, default: T) -> T {
+    if !cfg!(debug_assertions) {
+        return default;
+    }
+
+    let location = template
+        .rsplit_once(':')
+        .map_or(template, |(location, _)| location);
+    let value = hot_literals()
+        .read()
+        .ok()
+        .and_then(|all| all.get(location)?.get(&id).copied());
+
+    value.and_then(T::from_hot_literal).unwrap_or(default)
+}
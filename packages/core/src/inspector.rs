@@ -0,0 +1,267 @@
+//! A protocol for development tools that inspect the component tree of a running app
+//!
+//! An inspector connects with [`connect_inspector`] or [`VirtualDom::connect_inspector`]. After every render that
+//! changed something, the virtual dom sends it the component tree: the props, hooks and render count of every
+//! component, and why each component rendered last. The inspector sends [`InspectorCommand`]s back, like
+//! highlighting the elements of a component in the renderer.
+//!
+//! The messages and commands are plain data. With the `serialize` feature they can be sent to a browser extension or
+//! a standalone inspector over any socket, for example as json over a websocket.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::Stream;
+
+use crate::{
+    innerlude::{DynamicNode, ElementId, SchedulerMsg, VNode, WriteMutations},
+    nodes::TemplateNode,
+    Runtime, ScopeId, VirtualDom,
+};
+
+/// Why a component rendered
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderReason {
+    /// The component rendered for the first time
+    Mounted,
+    /// The parent of the component rendered and passed it new props
+    PropsChanged,
+    /// State the component reads changed, or the component was marked dirty
+    StateChanged,
+}
+
+/// A hook of a component
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookInfo {
+    /// The position of the hook in the component
+    pub index: usize,
+    /// The type of the value the hook stores, like `dioxus_signals::Signal<i32>`
+    pub type_name: String,
+}
+
+/// A component mounted in the virtual dom
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInfo {
+    /// The scope the component renders in
+    pub id: ScopeId,
+    /// The name of the component
+    pub name: String,
+    /// The scope the component is mounted in
+    pub parent: Option<ScopeId>,
+    /// The props formatted like `Debug` does. Fields that don't implement `Debug` are shown as `_`. `None` for props
+    /// that weren't derived with `#[derive(Props)]` or `#[component]`.
+    pub props: Option<String>,
+    /// The hooks of the component, in the order they are called
+    pub hooks: Vec<HookInfo>,
+    /// The number of times the component rendered, including the first render
+    pub renders: usize,
+    /// Why the component rendered last
+    pub render_reason: RenderReason,
+}
+
+/// A message from the virtual dom to an inspector
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InspectorMessage {
+    /// Every component that is mounted, ordered by scope id
+    Tree(Vec<ComponentInfo>),
+}
+
+/// A command from an inspector to the virtual dom
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InspectorCommand {
+    /// Send the component tree, even if nothing rendered
+    RequestTree,
+    /// Highlight the elements the component renders. The renderer is asked to highlight them with
+    /// [`WriteMutations::highlight_elements`] during the next render.
+    HighlightScope(ScopeId),
+    /// Remove the highlight
+    ClearHighlight,
+}
+
+/// A connection between an inspector and a virtual dom
+///
+/// The connection is a [`Stream`] of the messages of the virtual dom. It can be moved to another thread to forward
+/// the messages to a socket.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::{InspectorCommand, InspectorMessage, NoOpMutations};
+/// # fn app() -> Element { rsx! { div {} } }
+/// let mut dom = VirtualDom::new(app);
+/// let mut inspector = dom.connect_inspector();
+/// dom.rebuild(&mut NoOpMutations);
+/// inspector.send(InspectorCommand::HighlightScope(ScopeId::ROOT));
+/// dom.render_immediate(&mut NoOpMutations);
+///
+/// let Some(InspectorMessage::Tree(components)) = inspector.try_next() else {
+///     panic!("the tree is sent once the app rendered");
+/// };
+/// assert_eq!(components[0].name, "app");
+/// ```
+pub struct InspectorConnection {
+    messages: UnboundedReceiver<InspectorMessage>,
+    commands: UnboundedSender<SchedulerMsg>,
+}
+
+impl InspectorConnection {
+    /// Send a command to the virtual dom. It is handled during the next render.
+    pub fn send(&self, command: InspectorCommand) {
+        _ = self
+            .commands
+            .unbounded_send(SchedulerMsg::Inspector(command));
+    }
+
+    /// Take the next message if one is ready without waiting
+    pub fn try_next(&mut self) -> Option<InspectorMessage> {
+        self.messages.try_next().ok().flatten()
+    }
+}
+
+impl Stream for InspectorConnection {
+    type Item = InspectorMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<InspectorMessage>> {
+        Pin::new(&mut self.messages).poll_next(cx)
+    }
+}
+
+/// Connect an inspector to the virtual dom of the current runtime. The component tree is sent after the next render.
+pub fn connect_inspector() -> InspectorConnection {
+    let connection = Runtime::with(|rt| rt.connect_inspector()).expect("to be in a dioxus runtime");
+    // Wake up the virtual dom so it sends the tree even if nothing else happens
+    connection.send(InspectorCommand::RequestTree);
+    connection
+}
+
+impl Runtime {
+    fn connect_inspector(&self) -> InspectorConnection {
+        let (sender, messages) = futures_channel::mpsc::unbounded();
+        self.inspectors.borrow_mut().push(sender);
+        InspectorConnection {
+            messages,
+            commands: self.sender.clone(),
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Connect an inspector to this virtual dom. The component tree is sent after the next render.
+    pub fn connect_inspector(&mut self) -> InspectorConnection {
+        self.inspector_commands.push(InspectorCommand::RequestTree);
+        self.runtime.connect_inspector()
+    }
+
+    /// Get the component tree the inspector shows
+    pub(crate) fn inspect_tree(&self) -> Vec<ComponentInfo> {
+        let scopes = self.runtime.scope_states.borrow();
+        scopes
+            .iter()
+            .flatten()
+            .map(|scope| ComponentInfo {
+                id: scope.id,
+                name: scope.name.to_string(),
+                parent: scope.parent_id,
+                props: self
+                    .scopes
+                    .get(scope.id.0)
+                    .and_then(|state| state.props.debug_props()),
+                hooks: scope
+                    .hook_types
+                    .borrow()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, type_name)| HookInfo {
+                        index,
+                        type_name: type_name.to_string(),
+                    })
+                    .collect(),
+                renders: scope.render_count.get(),
+                render_reason: scope.render_reason.get(),
+            })
+            .collect()
+    }
+
+    /// Remember why a scope is about to render
+    pub(crate) fn set_render_reason(&self, scope: ScopeId, reason: RenderReason) {
+        if let Some(state) = self.runtime.get_state(scope) {
+            state.render_reason.set(reason);
+        }
+    }
+
+    /// Handle the commands of the inspectors and send them the tree if it changed since the last render
+    pub(crate) fn update_inspectors(&mut self, to: &mut impl WriteMutations, rendered: bool) {
+        let commands = std::mem::take(&mut self.inspector_commands);
+        let inspectors = self.runtime.inspectors.borrow().len();
+        if inspectors == 0 {
+            return;
+        }
+
+        let mut send_tree = rendered;
+        for command in commands {
+            match command {
+                InspectorCommand::RequestTree => send_tree = true,
+                InspectorCommand::HighlightScope(scope) => {
+                    to.highlight_elements(&self.scope_elements(scope))
+                }
+                InspectorCommand::ClearHighlight => to.highlight_elements(&[]),
+            }
+        }
+
+        if send_tree {
+            let tree = self.inspect_tree();
+            // Forget the inspectors that disconnected
+            self.runtime.inspectors.borrow_mut().retain(|inspector| {
+                inspector
+                    .unbounded_send(InspectorMessage::Tree(tree.clone()))
+                    .is_ok()
+            });
+        }
+    }
+
+    /// Get the elements at the root of a scope
+    fn scope_elements(&self, scope: ScopeId) -> Vec<ElementId> {
+        let mut elements = Vec::new();
+        if let Some(node) = self
+            .get_scope(scope)
+            .and_then(|scope| scope.try_root_node())
+        {
+            self.collect_root_elements(node, &mut elements);
+        }
+        elements
+    }
+
+    fn collect_root_elements(&self, node: &VNode, elements: &mut Vec<ElementId>) {
+        let Some(mount) = self.mounts.get(node.mount.get().0) else {
+            return;
+        };
+        for (root_idx, root) in node.template.get().roots.iter().enumerate() {
+            match root {
+                TemplateNode::Dynamic { id } => match &node.dynamic_nodes[*id] {
+                    DynamicNode::Fragment(children) => {
+                        for child in children {
+                            self.collect_root_elements(child, elements);
+                        }
+                    }
+                    DynamicNode::Component(_) => {
+                        let scope = ScopeId(mount.mounted_dynamic_nodes[*id]);
+                        elements.extend(self.scope_elements(scope));
+                    }
+                    DynamicNode::Placeholder(_) | DynamicNode::Text(_) => {
+                        elements.push(mount.root_ids[root_idx])
+                    }
+                },
+                _ => elements.push(mount.root_ids[root_idx]),
+            }
+        }
+    }
+}
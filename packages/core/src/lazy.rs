@@ -0,0 +1,180 @@
+use std::{
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use crate::innerlude::SuperFrom;
+
+/// A prop value that is only computed the first time it is read.
+///
+/// Fields marked with `#[props(lazy)]` accept either a value or a closure that returns the value. The closure is
+/// only called if the component reads the prop, which makes it a good fit for expensive content that may never be
+/// shown, like fallbacks.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Props, Clone, PartialEq)]
+/// struct LoaderProps {
+///     loaded: bool,
+///     #[props(lazy)]
+///     fallback: Lazy<Element>,
+/// }
+///
+/// fn Loader(props: LoaderProps) -> Element {
+///     if props.loaded {
+///         return rsx! { "Done!" };
+///     }
+///     props.fallback.get().clone()
+/// }
+///
+/// fn app() -> Element {
+///     rsx! {
+///         Loader {
+///             loaded: true,
+///             // This closure is never called because the content is already loaded
+///             fallback: || rsx! { "Loading..." },
+///         }
+///     }
+/// }
+/// ```
+pub struct Lazy<T: 'static> {
+    state: Rc<RefCell<LazyState<T>>>,
+}
+
+enum LazyState<T> {
+    Pending(Box<dyn FnOnce() -> T>),
+    Evaluating,
+    Ready(T),
+}
+
+impl<T: 'static> Lazy<T> {
+    /// Create a lazy value that will be computed with the closure the first time it is read
+    pub fn new(init: impl FnOnce() -> T + 'static) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(LazyState::Pending(Box::new(init)))),
+        }
+    }
+
+    /// Create a lazy value that has already been computed
+    pub fn ready(value: T) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(LazyState::Ready(value))),
+        }
+    }
+
+    /// Check if the value has been computed yet
+    pub fn is_ready(&self) -> bool {
+        matches!(&*self.state.borrow(), LazyState::Ready(_))
+    }
+
+    /// Get the value, computing it if this is the first time it is read
+    ///
+    /// # Panics
+    ///
+    /// Panics if the closure that computes the value tries to read the value itself
+    pub fn get(&self) -> Ref<'_, T> {
+        if !self.is_ready() {
+            let state = std::mem::replace(&mut *self.state.borrow_mut(), LazyState::Evaluating);
+            let LazyState::Pending(init) = state else {
+                panic!("Lazy value was read while it was being computed")
+            };
+            let value = init();
+            *self.state.borrow_mut() = LazyState::Ready(value);
+        }
+
+        Ref::map(self.state.borrow(), |state| match state {
+            LazyState::Ready(value) => value,
+            _ => unreachable!(),
+        })
+    }
+}
+
+impl<T: 'static> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Lazy values are equal if they point to the same value or if both have been computed and the values are equal.
+/// Values that haven't been computed yet are never compared because that would force them to be computed.
+///
+/// Memoized components also skip a lazy prop the component hasn't read yet, because the last render can't depend on it.
+impl<T: PartialEq + 'static> PartialEq for Lazy<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if Rc::ptr_eq(&self.state, &other.state) {
+            return true;
+        }
+
+        match (&*self.state.borrow(), &*other.state.borrow()) {
+            (LazyState::Ready(value), LazyState::Ready(other)) => value == other,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Default + 'static> Default for Lazy<T> {
+    fn default() -> Self {
+        Self::new(T::default)
+    }
+}
+
+impl<T: Debug + 'static> Debug for Lazy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.state.borrow() {
+            LazyState::Ready(value) => f.debug_tuple("Lazy").field(value).finish(),
+            _ => f.write_str("Lazy(<pending>)"),
+        }
+    }
+}
+
+impl<T: 'static> From<T> for Lazy<T> {
+    fn from(value: T) -> Self {
+        Self::ready(value)
+    }
+}
+
+#[doc(hidden)]
+pub struct LazyClosureMarker;
+
+impl<T: 'static, F: FnOnce() -> T + 'static> SuperFrom<F, LazyClosureMarker> for Lazy<T> {
+    fn super_from(input: F) -> Self {
+        Self::new(input)
+    }
+}
+
+#[test]
+fn lazy_values_are_computed_once() {
+    use std::cell::Cell;
+
+    let calls = Rc::new(Cell::new(0));
+    let lazy = Lazy::new({
+        let calls = calls.clone();
+        move || {
+            calls.set(calls.get() + 1);
+            "computed"
+        }
+    });
+    let copy = lazy.clone();
+
+    assert!(!lazy.is_ready());
+    assert_eq!(calls.get(), 0);
+    assert_eq!(*copy.get(), "computed");
+    assert_eq!(*lazy.get(), "computed");
+    assert!(lazy.is_ready());
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn computed_lazy_values_compare_their_values() {
+    let pending = Lazy::new(|| 1);
+    assert!(pending == pending.clone());
+    assert!(pending != Lazy::new(|| 1));
+    assert!(pending != Lazy::ready(1));
+
+    pending.get();
+    assert!(pending == Lazy::ready(1));
+    assert!(pending != Lazy::ready(2));
+}
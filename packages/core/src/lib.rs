@@ -5,6 +5,8 @@
 
 mod any_props;
 mod arena;
+mod children;
+mod dev_tools;
 mod diff;
 mod effect;
 mod error_boundary;
@@ -12,8 +14,14 @@ mod events;
 mod fragment;
 mod generational_box;
 mod global_context;
+mod hot_literals;
+mod inspector;
+mod lazy;
+mod memo_boundary;
 mod mutations;
+mod namespace;
 mod nodes;
+mod portal;
 mod properties;
 mod render_signal;
 mod runtime;
@@ -21,30 +29,57 @@ mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+#[cfg(feature = "serialize")]
+mod snapshot;
+mod suspense;
+mod task_scope;
 mod tasks;
 mod virtual_dom;
 
 /// Items exported from this module are used in macros and should not be used directly.
 #[doc(hidden)]
 pub mod internal {
+    pub use crate::children::RsxChildren;
+    pub use crate::hot_literals::hot_literal;
+    pub use crate::namespace::__is_valid_attribute_value;
+    pub use crate::nodes::{SpreadAttributePairs, SpreadAttributes};
     pub use crate::properties::verify_component_called_as_component;
+    pub use crate::properties::{
+        debug_props, DebugProps, DebugPropsWithDebug, DebugPropsWithoutDebug,
+    };
 }
 
 pub(crate) mod innerlude {
     pub(crate) use crate::any_props::*;
     pub use crate::arena::*;
+    pub use crate::children::ChildList;
+    pub use crate::dev_tools::{
+        hot_reload_count, hot_reload_error, scope_renders, subscribe_hot_reload, HotReloadError,
+        HotReloadErrorKind, HotReloadEvent, ScopeRenderInfo,
+    };
+    pub(crate) use crate::dev_tools::{HotReloadErrorState, PendingHotReload};
     pub(crate) use crate::effect::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
     pub use crate::generational_box::*;
     pub use crate::global_context::*;
+    pub use crate::hot_literals::*;
+    pub use crate::inspector::*;
+    pub use crate::lazy::*;
+    pub use crate::memo_boundary::*;
     pub use crate::mutations::*;
+    pub use crate::namespace::*;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
     pub use crate::properties::*;
     pub use crate::runtime::{Runtime, RuntimeGuard};
     pub use crate::scheduler::*;
     pub use crate::scopes::*;
+    #[cfg(feature = "serialize")]
+    pub use crate::snapshot::*;
+    pub use crate::suspense::*;
+    pub use crate::task_scope::*;
     pub use crate::tasks::*;
     pub use crate::virtual_dom::*;
 
@@ -58,12 +93,24 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, generation, schedule_update, schedule_update_any, use_hook, vdom_is_rendering,
-    AnyValue, Attribute, AttributeValue, CapturedError, Component, ComponentFunction, DynamicNode,
-    Element, ElementId, Event, Fragment, HasAttributes, IntoDynNode, MarkerWrapper, Mutation,
-    Mutations, NoOpMutations, Properties, RenderReturn, Runtime, ScopeId, ScopeState, SpawnIfAsync,
-    Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner, VPlaceholder,
-    VText, VirtualDom, WriteMutations,
+    connect_inspector, fc_to_builder, generation, hot_reload_count, hot_reload_error,
+    schedule_update, schedule_update_any, scope_renders, subscribe_hot_reload, use_hook,
+    use_task_scope, vdom_is_rendering, AnyValue, Attribute, AttributeValue, CapturedError,
+    CapturedPanic, Component, ComponentFunction, ComponentInfo, DynamicNode, Element, ElementId,
+    ElementNamespace, Event, Fragment, HasAttributes, HookInfo, HotLiteral, HotLiteralValue,
+    HotLiterals, HotReloadError, HotReloadErrorKind, HotReloadEvent, InspectorCommand,
+    InspectorConnection, InspectorMessage, IntoDynNode, JoinHandle, MarkerWrapper, MemoBoundary,
+    MemoBoundaryProps, Mutation, Mutations, NoOpMutations, Portal, PortalContext, PortalProps,
+    Properties, RenderReason, RenderReturn, Runtime, ScopeId, ScopeRenderInfo, ScopeState,
+    SpawnIfAsync, SuspenseBoundary, SuspenseBoundaryProps, SuspenseContext, Task, TaskScope,
+    Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner, VPlaceholder, VText,
+    VirtualDom, WriteMutations, TEMPLATE_FORMAT_VERSION,
+};
+
+#[cfg(feature = "serialize")]
+pub use crate::innerlude::{
+    AttributeSnapshot, AttributeValueSnapshot, DynamicNodeSnapshot, ScopeSnapshot, VNodeSnapshot,
+    VirtualDomSnapshot,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -74,12 +121,18 @@ pub mod prelude {
         consume_context, consume_context_from_scope, current_owner, current_scope_id,
         fc_to_builder, generation, has_context, needs_update, needs_update_any, parent_scope,
         provide_context, provide_root_context, queue_effect, remove_future, schedule_update,
-        schedule_update_any, spawn, spawn_forever, spawn_isomorphic, suspend, try_consume_context,
-        use_after_render, use_before_render, use_drop, use_error_boundary, use_hook,
-        use_hook_with_cleanup, wait_for_next_render, with_owner, AnyValue, Attribute, Callback,
-        Component, ComponentFunction, Element, ErrorBoundary, Event, EventHandler, Fragment,
-        HasAttributes, IntoAttributeValue, IntoDynNode, OptionStringFromMarker, Properties,
-        Runtime, RuntimeGuard, ScopeId, ScopeState, SuperFrom, SuperInto, Task, Template,
-        TemplateAttribute, TemplateNode, Throw, VNode, VNodeInner, VirtualDom,
+        schedule_update_any, spawn, spawn_forever, spawn_isomorphic, start_transition, suspend,
+        try_consume_context, use_after_render, use_before_render, use_drop, use_error_boundary,
+        use_hook, use_hook_with_cleanup, use_task_scope, wait_for_next_render, with_owner,
+        AnyValue, Attribute, Callback, CapturedError, CapturedPanic, ChildList, Component,
+        ComponentFunction, Element, ErrorBoundary, ErrorHook, Event, EventHandler, Fragment,
+        HasAttributes, IntoAttributeValue, IntoDynNode, Lazy, MemoBoundary, MemoBoundaryProps,
+        OptionStringFromMarker, Portal, PortalContext, PortalProps, Properties, Runtime,
+        RuntimeGuard, ScopeId, ScopeState, SuperFrom, SuperInto, SuspenseBoundary,
+        SuspenseBoundaryProps, SuspenseContext, Task, TaskScope, Template, TemplateAttribute,
+        TemplateNode, Throw, VNode, VNodeInner, VirtualDom,
     };
+
+    #[cfg(feature = "serialize")]
+    pub use crate::innerlude::{add_snapshot_value, restored_value, VirtualDomSnapshot};
 }
@@ -0,0 +1,117 @@
+use std::marker::PhantomData;
+
+use crate::innerlude::*;
+
+/// The props of a [`MemoBoundary`]
+#[derive(Clone)]
+pub struct MemoBoundaryProps<D> {
+    deps: D,
+    children: Element,
+}
+
+impl<D: PartialEq + Clone + 'static> MemoBoundaryProps<D> {
+    /// Create a builder for building `MemoBoundaryProps`.
+    /// On the builder, call `.deps(...)`, `.children(...)`(optional) to set the values of the fields.
+    /// Finally, call `.build()` to create the instance of `MemoBoundaryProps`.
+    pub fn builder() -> MemoBoundaryPropsBuilder<D, ()> {
+        MemoBoundaryPropsBuilder {
+            deps: (),
+            children: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<D: PartialEq + Clone + 'static> Properties for MemoBoundaryProps<D> {
+    type Builder = MemoBoundaryPropsBuilder<D, ()>;
+    fn builder() -> Self::Builder {
+        MemoBoundaryProps::builder()
+    }
+    fn memoize(&mut self, other: &Self) -> bool {
+        // Only the dependencies decide if the boundary renders again. The new children are thrown out if they are equal
+        if self.deps == other.deps {
+            return true;
+        }
+        *self = other.clone();
+        false
+    }
+}
+
+#[must_use]
+#[doc(hidden)]
+pub struct MemoBoundaryPropsBuilder<D, Deps> {
+    deps: Deps,
+    children: Element,
+    _marker: PhantomData<D>,
+}
+
+#[allow(missing_docs)]
+impl<D> MemoBoundaryPropsBuilder<D, ()> {
+    pub fn deps(self, deps: D) -> MemoBoundaryPropsBuilder<D, (D,)> {
+        MemoBoundaryPropsBuilder {
+            deps: (deps,),
+            children: self.children,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[allow(missing_docs)]
+impl<D, Deps> MemoBoundaryPropsBuilder<D, Deps> {
+    pub fn children(mut self, children: impl Into<Element>) -> Self {
+        self.children = children.into();
+        self
+    }
+}
+
+#[allow(missing_docs)]
+impl<D> MemoBoundaryPropsBuilder<D, (D,)> {
+    pub fn build(self) -> MemoBoundaryProps<D> {
+        MemoBoundaryProps {
+            deps: self.deps.0,
+            children: self.children,
+        }
+    }
+}
+
+/// Skip rendering the children again unless the dependencies change
+///
+/// ## Details
+///
+/// Components are already skipped when their props are equal, but components that take children or other props
+/// that never compare equal render every time their parent does. Wrapping an expensive subtree in a memo boundary
+/// makes it render only when `deps` changes, no matter what else the parent renders.
+///
+/// While the dependencies are equal, the boundary keeps the children from the last time they changed, including their
+/// event handlers. Everything the children read from the parent must be part of the dependencies, otherwise the
+/// children show stale values. Components in the children still render when their own state changes.
+///
+/// ## Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Dashboard(rows: ReadOnlySignal<Vec<String>>, filter: String) -> Element {
+///     rsx! {
+///         MemoBoundary { deps: filter.clone(),
+///             Table { title: "Rows matching {filter}",
+///                 for row in rows.read().iter().filter(|row| row.contains(&filter)) {
+///                     p { "{row}" }
+///                 }
+///             }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Table(title: String, children: Element) -> Element {
+///     rsx! {
+///         h1 { "{title}" }
+///         {children}
+///     }
+/// }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn MemoBoundary<D: PartialEq + Clone + 'static>(props: MemoBoundaryProps<D>) -> Element {
+    props.children
+}
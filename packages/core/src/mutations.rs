@@ -23,6 +23,17 @@ pub trait WriteMutations {
     /// M: The number of nodes on the stack to append to the target element
     fn append_children(&mut self, id: ElementId, m: usize);
 
+    /// Add these m children to the element with the given id attribute, like the target of a [`Portal`](crate::Portal)
+    ///
+    /// Target: The id attribute of the element outside of the app
+    /// M: The number of nodes on the stack to append to the target element
+    ///
+    /// Renderers that don't support portals don't need to implement this. The default panics when a portal is mounted.
+    fn append_to_portal(&mut self, target: &str, m: usize) {
+        _ = m;
+        panic!("This renderer doesn't support portals, so it can't render into the element with the id `{target}`");
+    }
+
     /// Assign the element at the given path the target ElementId.
     ///
     /// The path is in the form of a list of indices based on children. Templates cannot have more than 255 children per
@@ -108,6 +119,29 @@ pub trait WriteMutations {
     /// Id: The ID of the node to set the textcontent of.
     fn set_node_text(&mut self, value: &str, id: ElementId);
 
+    /// Replace a range of the text content of a node.
+    ///
+    /// The offsets count UTF-16 code units like the ranges of text in the browser.
+    ///
+    /// Start: The offset of the first code unit to replace.
+    /// Delete: The number of code units to remove at the start offset.
+    /// Value: The text to insert at the start offset.
+    /// Text: The whole text of the node after the replacement.
+    /// Id: The ID of the node to replace the text of.
+    ///
+    /// Renderers that can't replace a range of text don't need to implement this. The default sets the whole text.
+    fn replace_node_text(
+        &mut self,
+        start: usize,
+        delete: usize,
+        value: &str,
+        text: &str,
+        id: ElementId,
+    ) {
+        _ = (start, delete, value);
+        self.set_node_text(text, id);
+    }
+
     /// Create a new Event Listener.
     ///
     /// Name: The name of the event to listen for.
@@ -135,6 +169,10 @@ pub trait WriteMutations {
 
     /// Mark a scope as dirty
     fn mark_scope_dirty(&mut self, _scope_id: ScopeId) {}
+
+    /// Highlight the elements an [inspector](crate::InspectorConnection) asked for, replacing the last highlight. An
+    /// empty list removes the highlight.
+    fn highlight_elements(&mut self, _ids: &[ElementId]) {}
 }
 
 /// A `Mutation` represents a single instruction for the renderer to use to modify the UI tree to match the state
@@ -152,6 +190,15 @@ pub enum Mutation {
         m: usize,
     },
 
+    /// Add these m children to the element with the given id attribute, like the target of a [`Portal`](crate::Portal)
+    AppendToPortal {
+        /// The id attribute of the element outside of the app
+        target: String,
+
+        /// The number of nodes on the stack to append to the target element
+        m: usize,
+    },
+
     /// Assign the element at the given path the target ElementId.
     ///
     /// The path is in the form of a list of indices based on children. Templates cannot have more than 255 children per
@@ -294,6 +341,21 @@ pub enum Mutation {
         id: ElementId,
     },
 
+    /// Replace a range of the textcontent of a node. The offsets count UTF-16 code units.
+    ReplaceText {
+        /// The offset of the first code unit to replace
+        start: usize,
+
+        /// The number of code units to remove at the start offset
+        delete: usize,
+
+        /// The text to insert at the start offset
+        value: String,
+
+        /// The ID of the node to replace the text of.
+        id: ElementId,
+    },
+
     /// Create a new Event Listener.
     NewEventListener {
         /// The name of the event to listen for.
@@ -338,6 +400,9 @@ pub struct Mutations {
 
     /// Any mutations required to patch the renderer to match the layout of the VirtualDom
     pub edits: Vec<Mutation>,
+
+    /// The elements an inspector asked to highlight, if the highlight changed. An empty list removes the highlight.
+    pub highlighted: Option<Vec<ElementId>>,
 }
 
 impl Mutations {
@@ -364,6 +429,13 @@ impl WriteMutations for Mutations {
         self.edits.push(Mutation::AppendChildren { id, m })
     }
 
+    fn append_to_portal(&mut self, target: &str, m: usize) {
+        self.edits.push(Mutation::AppendToPortal {
+            target: target.to_string(),
+            m,
+        })
+    }
+
     fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
         self.edits.push(Mutation::AssignId { path, id })
     }
@@ -436,6 +508,22 @@ impl WriteMutations for Mutations {
         })
     }
 
+    fn replace_node_text(
+        &mut self,
+        start: usize,
+        delete: usize,
+        value: &str,
+        _: &str,
+        id: ElementId,
+    ) {
+        self.edits.push(Mutation::ReplaceText {
+            start,
+            delete,
+            value: value.into(),
+            id,
+        })
+    }
+
     fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
         self.edits.push(Mutation::NewEventListener {
             name: name.into(),
@@ -463,6 +551,10 @@ impl WriteMutations for Mutations {
     fn mark_scope_dirty(&mut self, scope_id: ScopeId) {
         self.dirty_scopes.insert(scope_id);
     }
+
+    fn highlight_elements(&mut self, ids: &[ElementId]) {
+        self.highlighted = Some(ids.to_vec());
+    }
 }
 
 /// A struct that ignores all mutations
@@ -473,6 +565,8 @@ impl WriteMutations for NoOpMutations {
 
     fn append_children(&mut self, _: ElementId, _: usize) {}
 
+    fn append_to_portal(&mut self, _: &str, _: usize) {}
+
     fn assign_node_id(&mut self, _: &'static [u8], _: ElementId) {}
 
     fn create_placeholder(&mut self, _: ElementId) {}
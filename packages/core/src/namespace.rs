@@ -0,0 +1,160 @@
+/// A set of elements and attributes the rsx macro can check at compile time.
+///
+/// The rsx macro resolves every element and attribute through a `dioxus_elements` module in scope. Renderers that don't
+/// draw html (a terminal or game UI for example) can define their own module with [`define_elements!`](crate::define_elements)
+/// instead of depending on `dioxus-html`. The type the macro generates implements this trait so hot reloading can
+/// map the rust names in rsx back to the names the renderer expects: `dioxus-rsx` implements its `HotReloadingContext`
+/// for every `ElementNamespace`, so the type can be passed anywhere the html context is used.
+pub trait ElementNamespace {
+    /// Map the rust name of an attribute on an element to its name and namespace in the renderer
+    fn map_attribute(
+        element_name_rust: &str,
+        attribute_name_rust: &str,
+    ) -> Option<(&'static str, Option<&'static str>)>;
+
+    /// Map the rust name of an element to its tag name and namespace in the renderer
+    fn map_element(element_name_rust: &str) -> Option<(&'static str, Option<&'static str>)>;
+}
+
+/// Define the elements and attributes of a custom namespace for the rsx macro.
+///
+/// Invoke this inside the module you import as `dioxus_elements`. Each element becomes a module under `elements` with
+/// its tag name, namespace and attributes, and the struct you name implements [`ElementNamespace`].
+/// Attributes are always accepted as written; use `= "name"` to rename an attribute that is a rust keyword.
+///
+/// Event handlers like `onclick` are resolved through `dioxus_elements::events::onclick`, so renderers that handle
+/// events should add their own `events` module next to the elements.
+///
+/// ```rust
+/// mod dioxus_elements {
+///     dioxus_core::define_elements! {
+///         pub struct TuiNamespace;
+///
+///         /// A block of text
+///         label {
+///             text,
+///             color,
+///             r#type = "type",
+///         };
+///
+///         /// A shape drawn with the canvas namespace
+///         rect in "canvas" {
+///             width,
+///             height,
+///         };
+///     }
+/// }
+///
+/// use dioxus_core::ElementNamespace;
+///
+/// assert_eq!(dioxus_elements::elements::rect::NAME_SPACE, Some("canvas"));
+/// assert_eq!(
+///     dioxus_elements::TuiNamespace::map_attribute("label", "r#type"),
+///     Some(("type", None))
+/// );
+/// ```
+#[macro_export]
+macro_rules! define_elements {
+    (
+        $(#[$ctx_attr:meta])*
+        $vis:vis struct $ctx:ident;
+
+        $(
+            $(#[$attr:meta])*
+            $name:ident $(in $namespace:literal)? {
+                $(
+                    $(#[$attr_method:meta])*
+                    $fil:ident $(= $rename:literal)?,
+                )*
+            };
+        )*
+    ) => {
+        $(#[$ctx_attr])*
+        $vis struct $ctx;
+
+        impl $crate::ElementNamespace for $ctx {
+            fn map_attribute(element: &str, attribute: &str) -> Option<(&'static str, Option<&'static str>)> {
+                $(
+                    if element == stringify!($name) {
+                        $(
+                            if attribute == stringify!($fil) {
+                                let (name, namespace, _) = elements::$name::$fil;
+                                return Some((name, namespace));
+                            }
+                        )*
+                        return None;
+                    }
+                )*
+                None
+            }
+
+            fn map_element(element: &str) -> Option<(&'static str, Option<&'static str>)> {
+                $(
+                    if element == stringify!($name) {
+                        return Some((elements::$name::TAG_NAME, elements::$name::NAME_SPACE));
+                    }
+                )*
+                None
+            }
+        }
+
+        pub mod elements {
+            $(
+                $(#[$attr])*
+                #[allow(non_camel_case_types, non_upper_case_globals)]
+                pub mod $name {
+                    pub const TAG_NAME: &'static str = stringify!($name);
+                    pub const NAME_SPACE: Option<&'static str> = $crate::__element_namespace!($($namespace)?);
+
+                    $(
+                        $(#[$attr_method])*
+                        pub const $fil: (&'static str, Option<&'static str>, bool) =
+                            ($crate::__attribute_name!($fil $($rename)?), None, false);
+                    )*
+                }
+            )*
+
+            /// This module contains helpers for rust analyzer autocompletion
+            #[doc(hidden)]
+            pub mod completions {
+                /// This helper tells rust analyzer that it should autocomplete the element name with braces.
+                #[allow(non_camel_case_types)]
+                pub enum CompleteWithBraces {
+                    $(
+                        $(#[$attr])*
+                        $name {}
+                    ),*
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __element_namespace {
+    () => {
+        None
+    };
+    ($namespace:literal) => {
+        Some($namespace)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attribute_name {
+    ($fil:ident) => {
+        stringify!($fil)
+    };
+    ($fil:ident $rename:literal) => {
+        $rename
+    };
+}
+
+/// The check the rsx macro uses for static attribute values when the element namespace doesn't list the values its
+/// attributes accept. Element modules can define their own `__is_valid_attribute_value` to shadow this one.
+#[doc(hidden)]
+pub const fn __is_valid_attribute_value(_attribute: &str, _value: &str) -> bool {
+    true
+}
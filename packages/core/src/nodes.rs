@@ -271,6 +271,27 @@ impl VNode {
         }
     }
 
+    /// Give the node a key. A node that is shared with other nodes is put in a new keyed node instead.
+    pub(crate) fn with_key(mut self, key: String) -> Self {
+        if let Some(inner) = Rc::get_mut(&mut self.vnode) {
+            inner.key = Some(key);
+            return self;
+        }
+
+        static KEYED: Template = Template {
+            name: "packages/core/nodes.rs:281:0:0",
+            roots: &[TemplateNode::Dynamic { id: 0 }],
+            node_paths: &[&[0]],
+            attr_paths: &[],
+        };
+        Self::new(
+            Some(key),
+            KEYED,
+            Box::new([DynamicNode::Fragment(vec![self])]),
+            Box::new([]),
+        )
+    }
+
     /// Load a dynamic root at the given index
     ///
     /// Returns [`None`] if the root is actually a static node (Element/Text)
@@ -325,6 +346,10 @@ impl VNode {
     }
 }
 
+/// The version of the serialized form of [`Template`]. Hot reloading servers and apps compare it before they exchange
+/// templates, so it must be bumped whenever a change to the template types changes how they serialize.
+pub const TEMPLATE_FORMAT_VERSION: u32 = 3;
+
 /// A static layout of a UI tree that describes a set of dynamic and static nodes.
 ///
 /// This is the core innovation in Dioxus. Most UIs are made of static nodes, yet participate in diffing like any
@@ -373,7 +398,7 @@ pub struct Template {
 }
 
 #[cfg(feature = "serialize")]
-fn deserialize_string_leaky<'a, 'de, D>(deserializer: D) -> Result<&'a str, D::Error>
+pub(crate) fn deserialize_string_leaky<'a, 'de, D>(deserializer: D) -> Result<&'a str, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -421,7 +446,108 @@ where
     Ok(deserialized.map(|deserialized| &*Box::leak(deserialized.into_boxed_str())))
 }
 
+// Templates are hashed with FNV-1a since it is simple enough to run in a const fn. Every string and list is prefixed
+// with its length so the boundaries between them are part of the hash.
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+const fn hash_byte(hash: u64, byte: u8) -> u64 {
+    (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+}
+
+const fn hash_usize(mut hash: u64, value: usize) -> u64 {
+    let bytes = (value as u64).to_le_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = hash_byte(hash, bytes[i]);
+        i += 1;
+    }
+    hash
+}
+
+const fn hash_bytes(mut hash: u64, bytes: &[u8]) -> u64 {
+    hash = hash_usize(hash, bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = hash_byte(hash, bytes[i]);
+        i += 1;
+    }
+    hash
+}
+
+const fn hash_namespace(hash: u64, namespace: Option<&str>) -> u64 {
+    match namespace {
+        Some(namespace) => hash_bytes(hash_byte(hash, 1), namespace.as_bytes()),
+        None => hash_byte(hash, 0),
+    }
+}
+
+const fn hash_paths(mut hash: u64, paths: &[&[u8]]) -> u64 {
+    hash = hash_usize(hash, paths.len());
+    let mut i = 0;
+    while i < paths.len() {
+        hash = hash_bytes(hash, paths[i]);
+        i += 1;
+    }
+    hash
+}
+
+const fn hash_nodes(mut hash: u64, nodes: &[TemplateNode]) -> u64 {
+    hash = hash_usize(hash, nodes.len());
+    let mut i = 0;
+    while i < nodes.len() {
+        hash = match &nodes[i] {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let mut hash = hash_bytes(hash_byte(hash, 0), tag.as_bytes());
+                hash = hash_namespace(hash, *namespace);
+                hash = hash_attributes(hash, attrs);
+                hash_nodes(hash, children)
+            }
+            TemplateNode::Text { text } => hash_bytes(hash_byte(hash, 1), text.as_bytes()),
+            TemplateNode::Dynamic { id } => hash_usize(hash_byte(hash, 2), *id),
+            TemplateNode::DynamicText { id } => hash_usize(hash_byte(hash, 3), *id),
+        };
+        i += 1;
+    }
+    hash
+}
+
+const fn hash_attributes(mut hash: u64, attributes: &[TemplateAttribute]) -> u64 {
+    hash = hash_usize(hash, attributes.len());
+    let mut i = 0;
+    while i < attributes.len() {
+        hash = match &attributes[i] {
+            TemplateAttribute::Static {
+                name,
+                value,
+                namespace,
+            } => {
+                let hash = hash_bytes(hash_byte(hash, 0), name.as_bytes());
+                let hash = hash_bytes(hash, value.as_bytes());
+                hash_namespace(hash, *namespace)
+            }
+            TemplateAttribute::Dynamic { id } => hash_usize(hash_byte(hash, 1), *id),
+        };
+        i += 1;
+    }
+    hash
+}
+
 impl Template {
+    /// A hash of the roots and paths of the template, so hot reloading can skip templates that didn't change
+    ///
+    /// This is a const fn, so the hash of a template in a static can be computed at compile time.
+    pub const fn content_hash(&self) -> u64 {
+        let mut hash = hash_nodes(FNV_OFFSET, self.roots);
+        hash = hash_paths(hash, self.node_paths);
+        hash_paths(hash, self.attr_paths)
+    }
+
     /// Is this template worth caching at all, since it's completely runtime?
     ///
     /// There's no point in saving templates that are completely dynamic, since they'll be recreated every time anyway.
@@ -592,6 +718,7 @@ impl VComponent {
         let props = Box::new(VProps::new(
             component,
             <P as Properties>::memoize,
+            <P as Properties>::debug_props,
             props,
             fn_name,
         ));
@@ -635,12 +762,37 @@ impl std::fmt::Debug for VComponent {
 pub struct VText {
     /// The actual text itself
     pub value: String,
+
+    /// Patch only the range of the text that changed instead of setting all of it. Set with [`VText::ranged`]
+    pub(crate) ranged: bool,
 }
 
 impl VText {
     /// Create a new VText
     pub fn new(value: String) -> Self {
-        Self { value }
+        Self {
+            value,
+            ranged: false,
+        }
+    }
+
+    /// Create a new VText that patches only the range of the text that changed when it is diffed. This is useful for
+    /// large blobs of text like logs that change a little at a time.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::VText;
+    /// fn Log(lines: Vec<String>) -> Element {
+    ///     rsx! {
+    ///         pre { {VText::ranged(lines.join("\n"))} }
+    ///     }
+    /// }
+    /// ```
+    pub fn ranged(value: String) -> Self {
+        Self {
+            value,
+            ranged: true,
+        }
     }
 }
 
@@ -726,6 +878,57 @@ impl Attribute {
     }
 }
 
+/// Create an attribute from a `(name, value)` pair. Spreading pairs into an element converts every pair, so pairs that
+/// are built at runtime can be spread directly:
+///
+/// ```rust, ignore
+/// let data = [("data-id", "1"), ("data-kind", "primary")];
+/// rsx! { div { ..data } }
+/// ```
+///
+/// The name has to be a `&'static str` like the [`name`](Attribute::name) of every attribute, so only the values of
+/// the pairs can be created at runtime.
+impl<T: IntoAttributeValue> From<(&'static str, T)> for Attribute {
+    fn from((name, value): (&'static str, T)) -> Self {
+        Attribute::new(name, value, None, false)
+    }
+}
+
+/// The value of a `..spread` in rsx.
+///
+/// Iterators of attributes use the inherent [`SpreadAttributes::into_attributes`], which method resolution picks
+/// first, so the item type of a spread like `..vec![]` is still inferred as [`Attribute`]. Iterators of `(name, value)`
+/// pairs fall back to [`SpreadAttributePairs::into_attributes`].
+#[doc(hidden)]
+pub struct SpreadAttributes<I>(pub I);
+
+impl<I: IntoIterator<Item = Attribute>> SpreadAttributes<I> {
+    pub fn into_attributes(self) -> I::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Spread `(name, value)` pairs into an element. See [`SpreadAttributes`]
+#[doc(hidden)]
+pub trait SpreadAttributePairs {
+    type Attributes: Iterator<Item = Attribute>;
+
+    fn into_attributes(self) -> Self::Attributes;
+}
+
+impl<I, T> SpreadAttributePairs for SpreadAttributes<I>
+where
+    I: IntoIterator<Item = (&'static str, T)>,
+    T: IntoAttributeValue,
+{
+    #[allow(clippy::type_complexity)]
+    type Attributes = std::iter::Map<I::IntoIter, fn((&'static str, T)) -> Attribute>;
+
+    fn into_attributes(self) -> Self::Attributes {
+        self.0.into_iter().map(Attribute::from as fn(_) -> _)
+    }
+}
+
 /// Any of the built-in values that the Dioxus VirtualDom supports as dynamic attributes on elements
 ///
 /// These are built-in to be faster during the diffing process. To use a custom value, use the [`AttributeValue::Any`]
@@ -813,7 +1016,9 @@ impl Clone for AttributeValue {
             Self::Float(arg0) => Self::Float(*arg0),
             Self::Int(arg0) => Self::Int(*arg0),
             Self::Bool(arg0) => Self::Bool(*arg0),
-            Self::Listener(_) | Self::Any(_) => panic!("Cannot clone listener or any value"),
+            // Listeners are copyable handles, so clones share the same callback
+            Self::Listener(listener) => Self::Listener(*listener),
+            Self::Any(_) => panic!("Cannot clone any value"),
             Self::None => Self::None,
         }
     }
@@ -887,23 +1092,25 @@ impl IntoDynNode for &Element {
 
 impl IntoDynNode for &str {
     fn into_dyn_node(self) -> DynamicNode {
-        DynamicNode::Text(VText {
-            value: self.to_string(),
-        })
+        DynamicNode::Text(VText::new(self.to_string()))
     }
 }
 
 impl IntoDynNode for String {
     fn into_dyn_node(self) -> DynamicNode {
-        DynamicNode::Text(VText { value: self })
+        DynamicNode::Text(VText::new(self))
     }
 }
 
 impl IntoDynNode for Arguments<'_> {
     fn into_dyn_node(self) -> DynamicNode {
-        DynamicNode::Text(VText {
-            value: self.to_string(),
-        })
+        DynamicNode::Text(VText::new(self.to_string()))
+    }
+}
+
+impl IntoDynNode for VText {
+    fn into_dyn_node(self) -> DynamicNode {
+        DynamicNode::Text(self)
     }
 }
 
@@ -0,0 +1,242 @@
+use crate::{
+    global_context::current_scope_id, innerlude::provide_context, nodes::RenderReturn, use_hook,
+    Element, Properties, ScopeId,
+};
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+/// The state of a [`Portal`]
+///
+/// The portal renders a placeholder where it is in the tree. Its children are mounted into the target element instead.
+#[derive(Clone)]
+pub struct PortalContext {
+    inner: Rc<PortalContextInner>,
+}
+
+struct PortalContextInner {
+    id: ScopeId,
+    /// The id of the element the children should be in
+    target: RefCell<String>,
+    /// The id of the element the children were mounted into
+    mounted_target: RefCell<String>,
+    /// The children of the portal. They are in the target element instead of where the portal is.
+    children: RefCell<Option<RenderReturn>>,
+}
+
+impl Debug for PortalContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PortalContext")
+            .field("id", &self.inner.id)
+            .field("target", &self.inner.target.borrow())
+            .finish()
+    }
+}
+
+impl PortalContext {
+    pub(crate) fn new(id: ScopeId) -> Self {
+        Self {
+            inner: Rc::new(PortalContextInner {
+                id,
+                target: Default::default(),
+                mounted_target: Default::default(),
+                children: Default::default(),
+            }),
+        }
+    }
+
+    /// The scope of the portal
+    pub fn id(&self) -> ScopeId {
+        self.inner.id
+    }
+
+    /// The id of the element the children of the portal are rendered into
+    pub fn target(&self) -> String {
+        self.inner.target.borrow().clone()
+    }
+
+    fn set_target(&self, target: String) {
+        *self.inner.target.borrow_mut() = target;
+    }
+
+    /// Remember the element the children were mounted into. Returns true if they were in a different element before.
+    pub(crate) fn mount_target(&self) -> bool {
+        let target = self.inner.target.borrow();
+        let mut mounted = self.inner.mounted_target.borrow_mut();
+        if *mounted == *target {
+            return false;
+        }
+        *mounted = target.clone();
+        true
+    }
+
+    pub(crate) fn take_children(&self) -> Option<RenderReturn> {
+        self.inner.children.borrow_mut().take()
+    }
+
+    pub(crate) fn set_children(&self, children: RenderReturn) {
+        *self.inner.children.borrow_mut() = Some(children);
+    }
+
+    /// Run a closure with the children of the portal, if they are mounted
+    pub(crate) fn with_children<O>(&self, f: impl FnOnce(&RenderReturn) -> O) -> Option<O> {
+        self.inner.children.borrow().as_ref().map(f)
+    }
+}
+
+/// The props of a [`Portal`]
+#[derive(Clone)]
+pub struct PortalProps {
+    target: String,
+    children: Element,
+}
+impl PortalProps {
+    /**
+    Create a builder for building `PortalProps`.
+    On the builder, call `.target(...)`, `.children(...)`(optional) to set the values of the fields.
+    Finally, call `.build()` to create the instance of `PortalProps`.
+                        */
+    #[allow(dead_code)]
+    pub fn builder() -> PortalPropsBuilder<((), ())> {
+        PortalPropsBuilder { fields: ((), ()) }
+    }
+}
+#[must_use]
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub struct PortalPropsBuilder<TypedBuilderFields> {
+    fields: TypedBuilderFields,
+}
+impl<TypedBuilderFields> Clone for PortalPropsBuilder<TypedBuilderFields>
+where
+    TypedBuilderFields: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+        }
+    }
+}
+impl Properties for PortalProps {
+    type Builder = PortalPropsBuilder<((), ())>;
+    fn builder() -> Self::Builder {
+        PortalProps::builder()
+    }
+    fn memoize(&mut self, other: &Self) -> bool {
+        *self = other.clone();
+        false
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub trait PortalPropsBuilder_Optional<T> {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
+}
+impl<T> PortalPropsBuilder_Optional<T> for () {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
+        default()
+    }
+}
+impl<T> PortalPropsBuilder_Optional<T> for (T,) {
+    fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
+        self.0
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> PortalPropsBuilder<((), __children)> {
+    pub fn target(
+        self,
+        target: impl ::core::convert::Into<String>,
+    ) -> PortalPropsBuilder<((String,), __children)> {
+        let target = (target.into(),);
+        let (_, children) = self.fields;
+        PortalPropsBuilder {
+            fields: (target, children),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum PortalPropsBuilder_Error_Repeated_field_target {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> PortalPropsBuilder<((String,), __children)> {
+    #[deprecated(note = "Repeated field target")]
+    pub fn target(
+        self,
+        _: PortalPropsBuilder_Error_Repeated_field_target,
+    ) -> PortalPropsBuilder<((String,), __children)> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__target> PortalPropsBuilder<(__target, ())> {
+    pub fn children(
+        self,
+        children: impl ::core::convert::Into<Element>,
+    ) -> PortalPropsBuilder<(__target, (Element,))> {
+        let children = (children.into(),);
+        let (target, _) = self.fields;
+        PortalPropsBuilder {
+            fields: (target, children),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum PortalPropsBuilder_Error_Repeated_field_children {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__target> PortalPropsBuilder<(__target, (Element,))> {
+    #[deprecated(note = "Repeated field children")]
+    pub fn children(
+        self,
+        _: PortalPropsBuilder_Error_Repeated_field_children,
+    ) -> PortalPropsBuilder<(__target, (Element,))> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children: PortalPropsBuilder_Optional<Element>> PortalPropsBuilder<((String,), __children)> {
+    pub fn build(self) -> PortalProps {
+        let ((target,), children) = self.fields;
+        let children = PortalPropsBuilder_Optional::into_value(children, || {
+            ::core::default::Default::default()
+        });
+        PortalProps { target, children }
+    }
+}
+/// Render the children into another element of the page
+///
+/// ## Details
+///
+/// Portals render their children into the element with the id of the `target` prop instead of where the portal is in
+/// the tree. This is useful for modals, tooltips and dropdowns that need to escape the overflow or stacking context of
+/// their parent. The children stay in the component tree, so they can use the context of their parents and their
+/// events bubble up to the parents of the portal.
+///
+/// ## Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn Dialog() -> Element {
+///     rsx! {
+///         Portal { target: "modal-root",
+///             div { class: "modal", "Are you sure?" }
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Usage
+///
+/// The target element must exist before the portal is mounted, and it should be outside of the elements that dioxus
+/// renders. Renderers that don't have a document, like ssr, render nothing for portals.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn Portal(props: PortalProps) -> Element {
+    let portal = use_hook(|| provide_context(PortalContext::new(current_scope_id().unwrap())));
+    portal.set_target(props.target);
+    props.children
+}
@@ -56,6 +56,12 @@ pub trait Properties: Clone + Sized + 'static {
     /// Make the old props equal to the new props. Return if the props were equal and should be memoized.
     fn memoize(&mut self, other: &Self) -> bool;
 
+    /// Format the props for development tools like the [inspector](crate::InspectorConnection). Derived props format
+    /// every field that implements `Debug`, and show `_` for the rest.
+    fn debug_props(&self) -> Option<String> {
+        None
+    }
+
     /// Create a component from the props.
     fn into_vcomponent<M: 'static>(
         self,
@@ -117,6 +123,47 @@ where
     P::builder()
 }
 
+/// Formats the fields of props with their `Debug` implementation if they have one. The props macro calls
+/// `(&&DebugProps(&props.field)).debug_props()` for every field, which picks [`DebugPropsWithDebug`] when the field
+/// implements `Debug` and falls back to [`DebugPropsWithoutDebug`] otherwise.
+#[doc(hidden)]
+pub struct DebugProps<'a, P>(pub &'a P);
+
+/// Format props like `Debug` does, with `_` for the fields that can't be formatted
+#[doc(hidden)]
+pub fn debug_props(name: &str, fields: &[(&str, Option<String>)]) -> String {
+    if fields.is_empty() {
+        return name.to_string();
+    }
+    let fields = fields
+        .iter()
+        .map(|(field, value)| format!("{field}: {}", value.as_deref().unwrap_or("_")))
+        .collect::<Vec<_>>();
+    format!("{name} {{ {} }}", fields.join(", "))
+}
+
+#[doc(hidden)]
+pub trait DebugPropsWithDebug {
+    fn debug_props(&self) -> Option<String>;
+}
+
+impl<P: std::fmt::Debug> DebugPropsWithDebug for &DebugProps<'_, P> {
+    fn debug_props(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait DebugPropsWithoutDebug {
+    fn debug_props(&self) -> Option<String>;
+}
+
+impl<P> DebugPropsWithoutDebug for DebugProps<'_, P> {
+    fn debug_props(&self) -> Option<String> {
+        None
+    }
+}
+
 /// Make sure that this component is currently running as a component, not a function call
 #[doc(hidden)]
 #[allow(unused)]
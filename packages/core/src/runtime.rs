@@ -1,6 +1,6 @@
 use slotmap::DefaultKey;
 
-use crate::innerlude::Effect;
+use crate::innerlude::{Effect, HotReloadErrorState, InspectorMessage};
 use crate::{
     innerlude::{LocalTask, SchedulerMsg},
     render_signal::RenderSignal,
@@ -8,6 +8,8 @@ use crate::{
     scopes::ScopeId,
     Task,
 };
+use futures_channel::mpsc::UnboundedSender;
+use rustc_hash::FxHashSet;
 use std::collections::BTreeSet;
 use std::{
     cell::{Cell, Ref, RefCell},
@@ -36,17 +38,29 @@ pub struct Runtime {
 
     pub(crate) rendering: Cell<bool>,
 
-    pub(crate) sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>,
+    pub(crate) sender: UnboundedSender<SchedulerMsg>,
 
     // Synchronous tasks need to be run after the next render. The virtual dom stores a list of those tasks to send a signal to them when the next render is done.
     pub(crate) render_signal: RenderSignal,
 
     // The effects that need to be run after the next render
     pub(crate) pending_effects: RefCell<BTreeSet<Effect>>,
+
+    // The change the hot reloader couldn't apply, shown by development tools
+    pub(crate) hot_reload_error: RefCell<HotReloadErrorState>,
+
+    // Scopes marked dirty while this is set rerender as part of a transition
+    pub(crate) transition: Cell<bool>,
+
+    // Tasks that were woken by a transition. They are polled as part of the transition
+    pub(crate) transition_tasks: RefCell<FxHashSet<Task>>,
+
+    // The inspectors that are connected to the virtual dom
+    pub(crate) inspectors: RefCell<Vec<UnboundedSender<InspectorMessage>>>,
 }
 
 impl Runtime {
-    pub(crate) fn new(sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>) -> Rc<Self> {
+    pub(crate) fn new(sender: UnboundedSender<SchedulerMsg>) -> Rc<Self> {
         Rc::new(Self {
             sender,
             render_signal: RenderSignal::default(),
@@ -57,6 +71,10 @@ impl Runtime {
             tasks: Default::default(),
             suspended_tasks: Default::default(),
             pending_effects: Default::default(),
+            hot_reload_error: Default::default(),
+            transition: Default::default(),
+            transition_tasks: Default::default(),
+            inspectors: Default::default(),
         })
     }
 
@@ -101,6 +119,45 @@ impl Runtime {
         self.scope_states.borrow_mut()[id.0].take();
     }
 
+    /// Run a closure with the updates it schedules marked as a transition
+    pub(crate) fn in_transition<O>(&self, f: impl FnOnce() -> O) -> O {
+        // Restore the flag even if the closure panics. Task panics are caught, so a flag that stays set would turn
+        // every later update into a transition
+        struct RestoreTransition<'a> {
+            transition: &'a Cell<bool>,
+            outer: bool,
+        }
+
+        impl Drop for RestoreTransition<'_> {
+            fn drop(&mut self) {
+                self.transition.set(self.outer);
+            }
+        }
+
+        let _restore = RestoreTransition {
+            outer: self.transition.replace(true),
+            transition: &self.transition,
+        };
+        f()
+    }
+
+    /// The message that marks a scope dirty with the priority of the update that is running on this thread
+    pub(crate) fn mark_dirty_msg(id: ScopeId) -> SchedulerMsg {
+        match Runtime::with(|rt| rt.transition.get()) {
+            Some(true) => SchedulerMsg::Transition(id),
+            _ => SchedulerMsg::Immediate(id),
+        }
+    }
+
+    /// Remember that a task was woken by a transition so the task runs as part of it
+    pub(crate) fn task_woken(task: Task) {
+        Runtime::with(|rt| {
+            if rt.transition.get() {
+                rt.transition_tasks.borrow_mut().insert(task);
+            }
+        });
+    }
+
     /// Get the current scope id
     pub(crate) fn current_scope_id(&self) -> Option<ScopeId> {
         self.scope_stack.borrow().last().copied()
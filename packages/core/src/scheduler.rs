@@ -63,7 +63,7 @@
 //!
 //! ## Implementation
 //!
-//! There are three different types of queued work that can be run by the virtualdom:
+//! There are four different types of queued work that can be run by the virtualdom:
 //! 1. Dirty Scopes:
 //!    Description: When a scope is marked dirty, a rerun of the scope will be scheduled. This will cause the scope to rerun and update the DOM if any changes are detected during the diffing phase.
 //!    Priority: These are the highest priority tasks. Dirty scopes will be rerun in order from the scope closest to the root to the scope furthest from the root. We follow this order to ensure that if a higher component reruns and drops a lower component, the lower component will not be run after it should be dropped.
@@ -72,7 +72,11 @@
 //!    Description: Futures spawned in the dioxus runtime each have an unique task id. When the waker for that future is called, the task is rerun.
 //!    Priority: These are the second highest priority tasks. They are run after all other dirty scopes have been resolved because those dirty scopes may cause children (and the tasks those children own) to drop which should cancel the futures.
 //!
-//! 3. Effects:
+//! 3. Transitions:
+//!    Description: Scopes marked dirty inside of [`start_transition`](crate::prelude::start_transition) rerun as a transition. Transitions are for expensive updates that can lag behind the input that caused them, like filtering a large list while the user types.
+//!    Priority: Transitions are rendered after all other dirty scopes and tasks. [`VirtualDom::render_immediate`] renders one transition scope at a time, so the renderer can apply the edits and handle new events in between. If a scope is marked dirty immediately while a transition for it is queued, it is rerendered immediately instead.
+//!
+//! 4. Effects:
 //!    Description: Effects should always run after all changes to the DOM have been applied.
 //!    Priority: These are the lowest priority tasks in the scheduler. They are run after all other dirty scopes and futures have been resolved. Other tasks may cause components to rerun, which would update the DOM. These effects should only run after the DOM has been updated.
 
@@ -136,11 +140,34 @@ impl VirtualDom {
 
     /// Queue a scope to be rerendered
     pub(crate) fn queue_scope(&mut self, order: ScopeOrder) {
+        self.dirty_transitions.remove(&order);
         self.dirty_scopes.insert(order);
     }
 
+    /// Queue a scope to be rerendered as part of a transition
+    pub(crate) fn queue_transition(&mut self, order: ScopeOrder) {
+        if !self.dirty_scopes.contains(&order) {
+            self.dirty_transitions.insert(order);
+        }
+    }
+
+    /// Take the transition of the highest scope
+    pub(crate) fn pop_transition(&mut self) -> Option<ScopeOrder> {
+        let mut order = self.dirty_transitions.pop_first()?;
+
+        // If the scope doesn't exist for whatever reason, then we should skip it
+        while !self.scopes.contains(order.id.0) {
+            order = self.dirty_transitions.pop_first()?;
+        }
+
+        Some(order)
+    }
+
     /// Check if there are any dirty scopes
-    pub(crate) fn has_dirty_scopes(&self) -> bool {
+    ///
+    /// Scopes stay dirty after a render that used up its [mutation budget](VirtualDom::with_mutation_budget).
+    /// Renderers should apply the mutations they have and render again on the next frame while this is true.
+    pub fn has_dirty_scopes(&self) -> bool {
         !self.dirty_scopes.is_empty()
     }
 
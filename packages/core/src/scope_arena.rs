@@ -1,4 +1,4 @@
-use crate::innerlude::ScopeOrder;
+use crate::innerlude::{ScopeOrder, SuspenseContext};
 use crate::{
     any_props::{AnyProps, BoxedAnyProps},
     innerlude::ScopeState,
@@ -69,15 +69,32 @@ impl VirtualDom {
         self.dirty_scopes
             .remove(&ScopeOrder::new(context.height, scope_id));
 
+        let mut suspended_boundary = None;
         if let Some(task) = context.last_suspendable_task.take() {
             if matches!(new_nodes, RenderReturn::Aborted(_)) {
                 tracing::trace!("Suspending {:?} on {:?}", scope_id, task);
-                self.runtime.tasks.borrow().get(task.0).unwrap().suspend();
+                // Let the nearest suspense boundary show its fallback until the task is done
+                let boundary = context.consume_context::<SuspenseContext>();
+                if let Some(boundary) = &boundary {
+                    boundary.add_task(task);
+                    suspended_boundary = Some(boundary.id());
+                }
+                self.runtime
+                    .tasks
+                    .borrow()
+                    .get(task.0)
+                    .unwrap()
+                    .suspend(boundary);
                 self.runtime
                     .suspended_tasks
                     .set(self.runtime.suspended_tasks.get() + 1);
             }
         }
+        drop(context);
+
+        if let Some(boundary) = suspended_boundary {
+            self.mark_dirty(boundary);
+        }
 
         self.runtime.scope_stack.borrow_mut().pop();
 
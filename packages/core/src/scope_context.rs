@@ -1,4 +1,7 @@
-use crate::{innerlude::SchedulerMsg, Element, Runtime, ScopeId, Task};
+use crate::{
+    innerlude::{RenderReason, SchedulerMsg},
+    Element, Runtime, ScopeId, Task,
+};
 use rustc_hash::FxHashSet;
 use std::{
     any::Any,
@@ -16,16 +19,26 @@ pub(crate) struct Scope {
     pub(crate) parent_id: Option<ScopeId>,
     pub(crate) height: u32,
     pub(crate) render_count: Cell<usize>,
+    // Why the scope rendered last, shown by the inspector
+    pub(crate) render_reason: Cell<RenderReason>,
 
     // Note: the order of the hook and context fields is important. The hooks field must be dropped before the contexts field in case a hook drop implementation tries to access a context.
     pub(crate) hooks: RefCell<Vec<Box<dyn Any>>>,
     pub(crate) hook_index: Cell<usize>,
+    // The type names of the hooks, shown by the inspector
+    pub(crate) hook_types: RefCell<Vec<&'static str>>,
     pub(crate) shared_contexts: RefCell<Vec<Box<dyn Any>>>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<Task>>,
     /// The task that was last spawned that may suspend. We use this task to check what task to suspend in the event of an early None return from a component
     pub(crate) last_suspendable_task: Cell<Option<Task>>,
     pub(crate) before_render: RefCell<Vec<Box<dyn FnMut()>>>,
     pub(crate) after_render: RefCell<Vec<Box<dyn FnMut()>>>,
+
+    /// The values registered with `add_snapshot_value` and the values they had in the restored snapshot
+    #[cfg(feature = "serialize")]
+    pub(crate) snapshot_values: RefCell<Vec<Box<dyn Fn() -> serde_json::Value>>>,
+    #[cfg(feature = "serialize")]
+    pub(crate) restored_values: RefCell<Vec<serde_json::Value>>,
 }
 
 impl Scope {
@@ -41,13 +54,19 @@ impl Scope {
             parent_id,
             height,
             render_count: Cell::new(0),
+            render_reason: Cell::new(RenderReason::Mounted),
             shared_contexts: RefCell::new(vec![]),
             spawned_tasks: RefCell::new(FxHashSet::default()),
             last_suspendable_task: Cell::new(None),
             hooks: RefCell::new(vec![]),
             hook_index: Cell::new(0),
+            hook_types: RefCell::new(vec![]),
             before_render: RefCell::new(vec![]),
             after_render: RefCell::new(vec![]),
+            #[cfg(feature = "serialize")]
+            snapshot_values: RefCell::new(vec![]),
+            #[cfg(feature = "serialize")]
+            restored_values: RefCell::new(vec![]),
         }
     }
 
@@ -67,7 +86,7 @@ impl Scope {
     /// Mark this scope as dirty, and schedule a render for it.
     pub fn needs_update_any(&self, id: ScopeId) {
         self.sender()
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(Runtime::mark_dirty_msg(id))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -76,7 +95,7 @@ impl Scope {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.sender(), self.id);
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || drop(chan.unbounded_send(Runtime::mark_dirty_msg(id))))
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -87,7 +106,7 @@ impl Scope {
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.sender();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            chan.unbounded_send(Runtime::mark_dirty_msg(id)).unwrap();
         })
     }
 
@@ -329,6 +348,9 @@ impl Scope {
 
         if cur_hook >= hooks.len() {
             hooks.push(Box::new(initializer()));
+            self.hook_types
+                .borrow_mut()
+                .push(std::any::type_name::<State>());
         }
 
         hooks
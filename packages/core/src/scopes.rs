@@ -1,5 +1,6 @@
 use crate::{
-    any_props::BoxedAnyProps, nodes::RenderReturn, runtime::Runtime, scope_context::Scope,
+    any_props::BoxedAnyProps, innerlude::SuspenseContext, nodes::RenderReturn, runtime::Runtime,
+    scope_context::Scope,
 };
 use std::{cell::Ref, rc::Rc};
 
@@ -81,6 +82,13 @@ impl ScopeState {
         self.last_rendered_node.as_ref()
     }
 
+    /// Get the state of the suspense boundary this scope renders if it is a [`SuspenseBoundary`](crate::SuspenseBoundary)
+    ///
+    /// Renderers like SSR use this to find the boundaries that show their fallback.
+    pub fn suspense_boundary(&self) -> Option<SuspenseContext> {
+        self.state().has_context()
+    }
+
     pub(crate) fn state(&self) -> Ref<'_, Scope> {
         self.runtime.get_state(self.context_id).unwrap()
     }
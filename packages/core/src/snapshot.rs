@@ -0,0 +1,434 @@
+//! Serializable snapshots of a [`VirtualDom`]
+//!
+//! A snapshot records the tree that is currently mounted along with any state components registered with
+//! [`add_snapshot_value`]. Restoring the snapshot into a new [`VirtualDom`] lets the components pick their state back
+//! up when they are first created, so the new dom renders the same tree the old one had.
+
+use crate::{
+    innerlude::{MountId, VComponent},
+    runtime::{Runtime, RuntimeGuard},
+    AttributeValue, DynamicNode, ScopeId, VNode, VirtualDom,
+};
+use rustc_hash::FxHashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// A serializable copy of the tree and state of a [`VirtualDom`]
+///
+/// Create one with [`VirtualDom::snapshot`] and resume it with [`VirtualDom::restore`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct VirtualDomSnapshot {
+    /// The root component. This is `None` if the virtual dom was never rebuilt.
+    pub root: Option<ScopeSnapshot>,
+}
+
+/// The state of a single mounted component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopeSnapshot {
+    /// The name of the component
+    pub name: String,
+
+    /// The values the component registered with [`add_snapshot_value`] in the order they were registered
+    pub values: Vec<serde_json::Value>,
+
+    /// The node the component rendered last
+    pub node: VNodeSnapshot,
+}
+
+/// A rendered template with the values of its dynamic parts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VNodeSnapshot {
+    /// The key of the node
+    pub key: Option<String>,
+
+    /// The name of the template the node was rendered from
+    ///
+    /// Templates are compiled into the app, so the name is enough to find the static parts of the node again.
+    pub template: String,
+
+    /// The dynamic nodes in the template
+    pub dynamic_nodes: Vec<DynamicNodeSnapshot>,
+
+    /// The dynamic attributes in the template
+    pub dynamic_attrs: Vec<Vec<AttributeSnapshot>>,
+}
+
+/// A dynamic node in a [`VNodeSnapshot`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DynamicNodeSnapshot {
+    /// A mounted component
+    Component(ScopeSnapshot),
+
+    /// A text node
+    Text(String),
+
+    /// A placeholder
+    Placeholder,
+
+    /// A list of nodes
+    Fragment(Vec<VNodeSnapshot>),
+}
+
+/// A dynamic attribute in a [`VNodeSnapshot`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeSnapshot {
+    /// The name of the attribute
+    pub name: String,
+
+    /// The namespace of the attribute
+    pub namespace: Option<String>,
+
+    /// The value of the attribute
+    pub value: AttributeValueSnapshot,
+}
+
+/// The value of an [`AttributeSnapshot`]
+///
+/// Listeners and [`AttributeValue::Any`] values can't be serialized. They are recreated when the component that owns
+/// them renders again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AttributeValueSnapshot {
+    /// Text attribute
+    Text(String),
+
+    /// A float
+    Float(f64),
+
+    /// Signed integer
+    Int(i64),
+
+    /// Boolean
+    Bool(bool),
+
+    /// An event listener
+    Listener,
+
+    /// A value that can't be serialized
+    Any,
+
+    /// A "none" value, resulting in the removal of an attribute from the dom
+    None,
+}
+
+impl From<&AttributeValue> for AttributeValueSnapshot {
+    fn from(value: &AttributeValue) -> Self {
+        match value {
+            AttributeValue::Text(value) => Self::Text(value.clone()),
+            AttributeValue::Float(value) => Self::Float(*value),
+            AttributeValue::Int(value) => Self::Int(*value),
+            AttributeValue::Bool(value) => Self::Bool(*value),
+            AttributeValue::Listener(_) => Self::Listener,
+            AttributeValue::Any(_) => Self::Any,
+            AttributeValue::None => Self::None,
+        }
+    }
+}
+
+/// The state of a component in a snapshot that is waiting for the component to be created
+#[derive(Debug)]
+pub(crate) struct RestoredScope {
+    name: String,
+    values: Vec<serde_json::Value>,
+}
+
+/// The position of a component in the tree
+///
+/// Each component is identified by the index of the dynamic node it is mounted in, and the index in the fragment
+/// if the node is part of a list, starting from the root component. The position stays the same when the tree is
+/// rendered again from the same state, so it can be used to find the state of a component in a snapshot.
+pub(crate) type ScopePath = Vec<usize>;
+
+impl VirtualDom {
+    /// Take a snapshot of the mounted tree and the state that components registered with [`add_snapshot_value`]
+    ///
+    /// The snapshot can be serialized and later restored into a new [`VirtualDom`] with [`VirtualDom::restore`] to
+    /// resume the app, for example after moving a liveview session to another server or after a crash.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// fn app() -> Element {
+    ///     rsx! { "hello world" }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// let snapshot = dom.snapshot();
+    ///
+    /// let mut restored = VirtualDom::new(app);
+    /// restored.restore(snapshot);
+    /// restored.rebuild_in_place();
+    /// ```
+    pub fn snapshot(&self) -> VirtualDomSnapshot {
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        VirtualDomSnapshot {
+            root: self.snapshot_scope(ScopeId::ROOT),
+        }
+    }
+
+    /// Restore the state of a snapshot taken with [`VirtualDom::snapshot`]
+    ///
+    /// This must be called before the virtual dom is rebuilt. As components are created during the rebuild, they get
+    /// the values they registered with [`add_snapshot_value`] back from [`restored_value`]. Components that are not in
+    /// the same place in the tree or have a different name start with fresh state.
+    pub fn restore(&mut self, snapshot: VirtualDomSnapshot) {
+        debug_assert!(
+            self.base_scope().try_root_node().is_none(),
+            "Snapshots must be restored before the virtual dom is rebuilt"
+        );
+
+        self.restored_scopes.clear();
+        if let Some(root) = snapshot.root {
+            root.collect_restored(Vec::new(), &mut self.restored_scopes);
+        }
+
+        // The root component already exists, so it gets its state right away
+        self.restore_scope(Vec::new(), ScopeId::ROOT);
+    }
+
+    /// Take a [snapshot](VirtualDom::snapshot) and serialize it to JSON
+    ///
+    /// Save the state to resume the app later with [`VirtualDom::resume_from`], for example to restore the UI of a
+    /// desktop app after it restarts, or compare it in tests to check the state of the app.
+    pub fn serialize_state(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.snapshot())
+    }
+
+    /// Restore the state serialized with [`VirtualDom::serialize_state`]
+    ///
+    /// Like [`VirtualDom::restore`], this must be called before the virtual dom is rebuilt. If the state fails to
+    /// deserialize, nothing is restored and the app starts fresh.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// fn app() -> Element {
+    ///     rsx! { "hello world" }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// let state = dom.serialize_state().unwrap();
+    ///
+    /// let mut resumed = VirtualDom::new(app);
+    /// resumed.resume_from(&state).unwrap();
+    /// resumed.rebuild_in_place();
+    /// ```
+    pub fn resume_from(&mut self, state: &str) -> Result<(), serde_json::Error> {
+        let snapshot = serde_json::from_str(state)?;
+        self.restore(snapshot);
+        Ok(())
+    }
+
+    /// Give the state from the restored snapshot to a component that was just created
+    pub(crate) fn restore_scope(&mut self, path: ScopePath, scope: ScopeId) {
+        let Some(restored) = self.restored_scopes.remove(&path) else {
+            return;
+        };
+
+        let Some(state) = self.runtime.get_state(scope) else {
+            return;
+        };
+        if restored.name != state.name {
+            tracing::trace!(
+                "Not restoring the state of {} because the snapshot has {} in its place",
+                state.name,
+                restored.name
+            );
+            return;
+        }
+        *state.restored_values.borrow_mut() = restored.values;
+    }
+
+    /// Get the position of the component mounted in a dynamic node
+    pub(crate) fn scope_path(&self, mount: MountId, idx: usize) -> ScopePath {
+        let mut path = vec![idx];
+        let mut mount = mount;
+
+        // Walk up through the parents of the node until we reach the root component
+        while let Some(parent) = self.mounts[mount.0].parent {
+            let parent_mount = &self.mounts[parent.mount.0];
+            let node = &parent_mount.node;
+            let Some(parent_idx) = node
+                .template
+                .get()
+                .node_paths
+                .iter()
+                .position(|path| *path == parent.path.path)
+            else {
+                break;
+            };
+
+            if let DynamicNode::Fragment(children) = &node.dynamic_nodes[parent_idx] {
+                if let Some(position) = children.iter().position(|child| child.mount.get() == mount)
+                {
+                    path.push(position);
+                }
+            }
+            path.push(parent_idx);
+            mount = parent.mount;
+        }
+
+        path.reverse();
+        path
+    }
+
+    fn snapshot_scope(&self, scope: ScopeId) -> Option<ScopeSnapshot> {
+        let node = self.get_scope(scope)?.try_root_node()?;
+        let state = self.runtime.get_state(scope)?;
+        let values = self.runtime.on_scope(scope, || {
+            state
+                .snapshot_values
+                .borrow()
+                .iter()
+                .map(|value| value())
+                .collect()
+        });
+
+        Some(ScopeSnapshot {
+            name: state.name.to_string(),
+            values,
+            node: self.snapshot_node(node),
+        })
+    }
+
+    fn snapshot_node(&self, node: &VNode) -> VNodeSnapshot {
+        let dynamic_nodes = node
+            .dynamic_nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, dynamic)| match dynamic {
+                DynamicNode::Component(component) => self
+                    .snapshot_component(component, idx, node)
+                    .map(DynamicNodeSnapshot::Component)
+                    .unwrap_or(DynamicNodeSnapshot::Placeholder),
+                DynamicNode::Text(text) => DynamicNodeSnapshot::Text(text.value.clone()),
+                DynamicNode::Placeholder(_) => DynamicNodeSnapshot::Placeholder,
+                DynamicNode::Fragment(children) => DynamicNodeSnapshot::Fragment(
+                    children
+                        .iter()
+                        .map(|child| self.snapshot_node(child))
+                        .collect(),
+                ),
+            })
+            .collect();
+
+        let dynamic_attrs = node
+            .dynamic_attrs
+            .iter()
+            .map(|attributes| {
+                attributes
+                    .iter()
+                    .map(|attribute| AttributeSnapshot {
+                        name: attribute.name.to_string(),
+                        namespace: attribute.namespace.map(ToString::to_string),
+                        value: (&attribute.value).into(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        VNodeSnapshot {
+            key: node.key.clone(),
+            template: node.template.get().name.to_string(),
+            dynamic_nodes,
+            dynamic_attrs,
+        }
+    }
+
+    fn snapshot_component(
+        &self,
+        component: &VComponent,
+        idx: usize,
+        node: &VNode,
+    ) -> Option<ScopeSnapshot> {
+        let scope = component.mounted_scope(idx, node, self)?;
+        self.snapshot_scope(scope.context_id)
+    }
+}
+
+impl ScopeSnapshot {
+    fn collect_restored(self, path: ScopePath, scopes: &mut FxHashMap<ScopePath, RestoredScope>) {
+        self.node.collect_restored(&path, scopes);
+        scopes.insert(
+            path,
+            RestoredScope {
+                name: self.name,
+                values: self.values,
+            },
+        );
+    }
+}
+
+impl VNodeSnapshot {
+    fn collect_restored(self, path: &[usize], scopes: &mut FxHashMap<ScopePath, RestoredScope>) {
+        for (idx, node) in self.dynamic_nodes.into_iter().enumerate() {
+            match node {
+                DynamicNodeSnapshot::Component(scope) => {
+                    scope.collect_restored([path, &[idx]].concat(), scopes);
+                }
+                DynamicNodeSnapshot::Fragment(children) => {
+                    for (position, child) in children.into_iter().enumerate() {
+                        child.collect_restored(&[path, &[idx, position]].concat(), scopes);
+                    }
+                }
+                DynamicNodeSnapshot::Text(_) | DynamicNodeSnapshot::Placeholder => {}
+            }
+        }
+    }
+}
+
+/// Get the value that was registered at this position with [`add_snapshot_value`] in the snapshot the virtual dom
+/// was restored from
+///
+/// Values are matched up by the order they are registered in, so call this right before the matching
+/// [`add_snapshot_value`] inside of [`use_hook`](crate::prelude::use_hook):
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::prelude::{add_snapshot_value, restored_value};
+/// fn use_restorable_count() -> Signal<i32> {
+///     use_hook(|| {
+///         let count = Signal::new(restored_value().unwrap_or_default());
+///         add_snapshot_value(move || *count.peek());
+///         count
+///     })
+/// }
+/// ```
+///
+/// Returns `None` if the virtual dom wasn't restored, the component wasn't in the snapshot, or the value can't be
+/// deserialized as `T`.
+pub fn restored_value<T: DeserializeOwned>() -> Option<T> {
+    Runtime::with_current_scope(|cx| {
+        let index = cx.snapshot_values.borrow().len();
+        let value = cx.restored_values.borrow().get(index)?.clone();
+        match serde_json::from_value(value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                tracing::warn!("Failed to restore the snapshot value of {}: {err}", cx.name);
+                None
+            }
+        }
+    })
+    .flatten()
+}
+
+/// Register a value of the current component that is saved every time the virtual dom takes a
+/// [snapshot](VirtualDom::snapshot)
+///
+/// The closure runs when the snapshot is taken, so it should read the latest value of the state without subscribing
+/// to it. Use [`restored_value`] to get the value back after the snapshot is restored.
+pub fn add_snapshot_value<T: Serialize>(value: impl Fn() -> T + 'static) {
+    Runtime::with_current_scope(|cx| {
+        let name = cx.name;
+        cx.snapshot_values.borrow_mut().push(Box::new(move || {
+            match serde_json::to_value(value()) {
+                Ok(value) => value,
+                Err(err) => {
+                    tracing::warn!("Failed to snapshot a value of {name}: {err}");
+                    serde_json::Value::Null
+                }
+            }
+        }));
+    })
+    .expect("to be in a dioxus runtime");
+}
@@ -0,0 +1,264 @@
+use crate::{
+    global_context::current_scope_id, innerlude::provide_context, nodes::RenderReturn, use_hook,
+    Element, Properties, ScopeId, Task,
+};
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+/// The state of a [`SuspenseBoundary`] that is shared with the components under it
+///
+/// Components that suspend with [`suspend`](crate::prelude::suspend) register their task with the nearest boundary. The
+/// boundary shows its fallback until all of those tasks finish.
+#[derive(Clone)]
+pub struct SuspenseContext {
+    inner: Rc<SuspenseContextInner>,
+}
+
+struct SuspenseContextInner {
+    id: ScopeId,
+    /// The tasks that the components under the boundary are waiting on
+    tasks: RefCell<Vec<Task>>,
+    /// The fallback from the last render of the boundary
+    fallback: RefCell<Element>,
+    /// The children of the boundary while the fallback is shown. They stay mounted, but they aren't in the real dom.
+    suspended_nodes: RefCell<Option<RenderReturn>>,
+}
+
+impl Debug for SuspenseContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SuspenseContext")
+            .field("id", &self.inner.id)
+            .field("tasks", &self.inner.tasks.borrow())
+            .field("suspended", &self.suspended())
+            .finish()
+    }
+}
+
+impl SuspenseContext {
+    pub(crate) fn new(id: ScopeId) -> Self {
+        Self {
+            inner: Rc::new(SuspenseContextInner {
+                id,
+                tasks: Default::default(),
+                fallback: Default::default(),
+                suspended_nodes: Default::default(),
+            }),
+        }
+    }
+
+    /// The scope of the boundary
+    pub fn id(&self) -> ScopeId {
+        self.inner.id
+    }
+
+    /// Whether the boundary shows its fallback instead of its children
+    pub fn suspended(&self) -> bool {
+        self.inner.suspended_nodes.borrow().is_some()
+    }
+
+    /// Whether any component under the boundary is waiting on a task
+    pub(crate) fn has_suspended_tasks(&self) -> bool {
+        !self.inner.tasks.borrow().is_empty()
+    }
+
+    pub(crate) fn add_task(&self, task: Task) {
+        let mut tasks = self.inner.tasks.borrow_mut();
+        if !tasks.contains(&task) {
+            tasks.push(task);
+        }
+    }
+
+    /// Forget a task that finished. Returns true if that was the last task the boundary was waiting on.
+    pub(crate) fn remove_task(&self, task: Task) -> bool {
+        let mut tasks = self.inner.tasks.borrow_mut();
+        let len = tasks.len();
+        tasks.retain(|t| *t != task);
+        len != tasks.len() && tasks.is_empty()
+    }
+
+    fn set_fallback(&self, fallback: Element) {
+        *self.inner.fallback.borrow_mut() = fallback;
+    }
+
+    /// Get the fallback to render while the boundary is suspended
+    pub(crate) fn fallback(&self) -> RenderReturn {
+        match self.inner.fallback.borrow().clone() {
+            Some(node) => RenderReturn::Ready(node),
+            None => RenderReturn::default(),
+        }
+    }
+
+    pub(crate) fn take_suspended_nodes(&self) -> Option<RenderReturn> {
+        self.inner.suspended_nodes.borrow_mut().take()
+    }
+
+    pub(crate) fn set_suspended_nodes(&self, nodes: RenderReturn) {
+        *self.inner.suspended_nodes.borrow_mut() = Some(nodes);
+    }
+}
+
+/// The props of a [`SuspenseBoundary`]
+#[derive(Clone)]
+pub struct SuspenseBoundaryProps {
+    children: Element,
+    fallback: Element,
+}
+impl SuspenseBoundaryProps {
+    /**
+    Create a builder for building `SuspenseBoundaryProps`.
+    On the builder, call `.children(...)`(optional), `.fallback(...)`(optional) to set the values of the fields.
+    Finally, call `.build()` to create the instance of `SuspenseBoundaryProps`.
+                        */
+    #[allow(dead_code)]
+    pub fn builder() -> SuspenseBoundaryPropsBuilder<((), ())> {
+        SuspenseBoundaryPropsBuilder { fields: ((), ()) }
+    }
+}
+#[must_use]
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub struct SuspenseBoundaryPropsBuilder<TypedBuilderFields> {
+    fields: TypedBuilderFields,
+}
+impl<TypedBuilderFields> Clone for SuspenseBoundaryPropsBuilder<TypedBuilderFields>
+where
+    TypedBuilderFields: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+        }
+    }
+}
+impl Properties for SuspenseBoundaryProps {
+    type Builder = SuspenseBoundaryPropsBuilder<((), ())>;
+    fn builder() -> Self::Builder {
+        SuspenseBoundaryProps::builder()
+    }
+    fn memoize(&mut self, other: &Self) -> bool {
+        *self = other.clone();
+        false
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub trait SuspenseBoundaryPropsBuilder_Optional<T> {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
+}
+impl<T> SuspenseBoundaryPropsBuilder_Optional<T> for () {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
+        default()
+    }
+}
+impl<T> SuspenseBoundaryPropsBuilder_Optional<T> for (T,) {
+    fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
+        self.0
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__fallback> SuspenseBoundaryPropsBuilder<((), __fallback)> {
+    pub fn children(
+        self,
+        children: impl ::core::convert::Into<Element>,
+    ) -> SuspenseBoundaryPropsBuilder<((Element,), __fallback)> {
+        let children = (children.into(),);
+        let (_, fallback) = self.fields;
+        SuspenseBoundaryPropsBuilder {
+            fields: (children, fallback),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum SuspenseBoundaryPropsBuilder_Error_Repeated_field_children {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__fallback> SuspenseBoundaryPropsBuilder<((Element,), __fallback)> {
+    #[deprecated(note = "Repeated field children")]
+    pub fn children(
+        self,
+        _: SuspenseBoundaryPropsBuilder_Error_Repeated_field_children,
+    ) -> SuspenseBoundaryPropsBuilder<((Element,), __fallback)> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> SuspenseBoundaryPropsBuilder<(__children, ())> {
+    pub fn fallback(
+        self,
+        fallback: impl ::core::convert::Into<Element>,
+    ) -> SuspenseBoundaryPropsBuilder<(__children, (Element,))> {
+        let fallback = (fallback.into(),);
+        let (children, _) = self.fields;
+        SuspenseBoundaryPropsBuilder {
+            fields: (children, fallback),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum SuspenseBoundaryPropsBuilder_Error_Repeated_field_fallback {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> SuspenseBoundaryPropsBuilder<(__children, (Element,))> {
+    #[deprecated(note = "Repeated field fallback")]
+    pub fn fallback(
+        self,
+        _: SuspenseBoundaryPropsBuilder_Error_Repeated_field_fallback,
+    ) -> SuspenseBoundaryPropsBuilder<(__children, (Element,))> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<
+        __fallback: SuspenseBoundaryPropsBuilder_Optional<Element>,
+        __children: SuspenseBoundaryPropsBuilder_Optional<Element>,
+    > SuspenseBoundaryPropsBuilder<(__children, __fallback)>
+{
+    pub fn build(self) -> SuspenseBoundaryProps {
+        let (children, fallback) = self.fields;
+        let children = SuspenseBoundaryPropsBuilder_Optional::into_value(children, || {
+            ::core::default::Default::default()
+        });
+        let fallback = SuspenseBoundaryPropsBuilder_Optional::into_value(fallback, || {
+            ::core::default::Default::default()
+        });
+        SuspenseBoundaryProps { children, fallback }
+    }
+}
+/// Create a new suspense boundary component.
+///
+/// ## Details
+///
+/// Suspense boundaries show a fallback while the components under them wait on async work. A component waits by
+/// returning [`suspend`](crate::prelude::suspend) with the task it needs, like the task of a resource. Once every task
+/// the boundary is waiting on finishes, the boundary swaps the fallback for its children.
+///
+/// The children stay mounted while the fallback is shown, so they keep their state and their tasks keep running.
+///
+/// ## Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # fn Profile() -> Element { todo!() }
+/// rsx! {
+///     SuspenseBoundary {
+///         fallback: rsx! { "Loading your profile..." },
+///         Profile {}
+///     }
+/// };
+/// ```
+///
+/// ## Usage
+///
+/// Suspended components that aren't under a boundary render nothing until they are ready. During server side rendering,
+/// the renderer can write the fallback of a boundary first and stream its children once they resolve.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn SuspenseBoundary(props: SuspenseBoundaryProps) -> Element {
+    let boundary = use_hook(|| provide_context(SuspenseContext::new(current_scope_id().unwrap())));
+    boundary.set_fallback(props.fallback);
+    props.children
+}
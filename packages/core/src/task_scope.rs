@@ -0,0 +1,219 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use futures_channel::oneshot;
+
+use crate::innerlude::*;
+
+/// A group of tasks that can be cancelled or awaited together
+///
+/// [`spawn`] ties a task to the component that spawned it, but once it is spawned the only way to stop it is to hold
+/// on to the [`Task`]. A task scope keeps track of the tasks spawned through it so they can be cancelled with
+/// [`TaskScope::cancel_all`] or awaited with [`TaskScope::join_all`]. Every task also returns a [`JoinHandle`] to
+/// read its output.
+///
+/// The tasks run in the scope the task scope was created in, so they are cancelled when that component is unmounted.
+/// Use [`TaskScope::app`] for tasks that should keep running until the app is closed.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # async fn search(query: &str) -> Vec<String> { vec![query.to_string()] }
+/// fn app() -> Element {
+///     let tasks = use_task_scope();
+///     let mut results = use_signal(Vec::new);
+///     rsx! {
+///         input {
+///             oninput: move |event| {
+///                 // Only the search for the latest query keeps running
+///                 tasks.cancel_all();
+///                 tasks.spawn(async move {
+///                     results.set(search(&event.value()).await);
+///                 });
+///             },
+///         }
+///         for result in results() {
+///             p { "{result}" }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TaskScope {
+    inner: Rc<TaskScopeInner>,
+}
+
+struct TaskScopeInner {
+    scope: ScopeId,
+    tasks: RefCell<Vec<Task>>,
+    // The join_all futures waiting for the running tasks to finish
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl TaskScopeInner {
+    fn forget_task(&self, task: Task) {
+        let mut tasks = self.tasks.borrow_mut();
+        tasks.retain(|t| *t != task);
+        if tasks.is_empty() {
+            drop(tasks);
+            for waker in self.waiters.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl TaskScope {
+    /// Create a task scope whose tasks run in the current component
+    pub fn new() -> Self {
+        Self::new_in(current_scope_id().expect("to be in a dioxus runtime"))
+    }
+
+    /// Create a task scope whose tasks are tied to the app instead of a component. They keep running until they
+    /// finish, they are cancelled or the app is closed.
+    pub fn app() -> Self {
+        Self::new_in(ScopeId::ROOT)
+    }
+
+    /// Create a task scope whose tasks run in the given scope
+    pub fn new_in(scope: ScopeId) -> Self {
+        Self {
+            inner: Rc::new(TaskScopeInner {
+                scope,
+                tasks: Default::default(),
+                waiters: Default::default(),
+            }),
+        }
+    }
+
+    /// The scope the tasks run in
+    pub fn scope(&self) -> ScopeId {
+        self.inner.scope
+    }
+
+    /// Spawn a task in this task scope
+    pub fn spawn<T: 'static>(&self, fut: impl Future<Output = T> + 'static) -> JoinHandle<T> {
+        let (sender, receiver) = oneshot::channel();
+        let id = Rc::new(Cell::new(None));
+        // Removes the task from the scope once it finishes or is cancelled
+        let guard = ForgetOnDrop {
+            inner: self.inner.clone(),
+            task: id.clone(),
+        };
+        let task = Runtime::with_scope(self.inner.scope, |cx| {
+            cx.spawn(async move {
+                let _guard = guard;
+                let _ = sender.send(fut.await);
+            })
+        })
+        .expect("the scope of a task scope to be alive");
+        id.set(Some(task));
+        // The task may have finished or been cancelled while it was spawned
+        if Runtime::with(|rt| rt.tasks.borrow().contains_key(task.0)).unwrap_or_default() {
+            self.inner.tasks.borrow_mut().push(task);
+        }
+
+        JoinHandle { task, receiver }
+    }
+
+    /// Cancel every task that is still running
+    pub fn cancel_all(&self) {
+        let tasks = self.inner.tasks.take();
+        for task in tasks {
+            task.cancel();
+        }
+        for waker in self.inner.waiters.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wait until every task that is running finishes or is cancelled
+    pub fn join_all(&self) -> impl Future<Output = ()> {
+        JoinAll {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// The number of tasks that are still running
+    pub fn len(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+
+    /// Check if all of the tasks finished
+    pub fn is_empty(&self) -> bool {
+        self.inner.tasks.borrow().is_empty()
+    }
+}
+
+impl Default for TaskScope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct ForgetOnDrop {
+    inner: Rc<TaskScopeInner>,
+    task: Rc<Cell<Option<Task>>>,
+}
+
+impl Drop for ForgetOnDrop {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.get() {
+            self.inner.forget_task(task);
+        }
+    }
+}
+
+struct JoinAll {
+    inner: Rc<TaskScopeInner>,
+}
+
+impl Future for JoinAll {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.inner.tasks.borrow().is_empty() {
+            return Poll::Ready(());
+        }
+        self.inner.waiters.borrow_mut().push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// A handle to a task spawned in a [`TaskScope`]
+///
+/// Awaiting the handle returns the output of the task, or `None` if the task was cancelled. Dropping the handle does
+/// not cancel the task.
+pub struct JoinHandle<T> {
+    task: Task,
+    receiver: oneshot::Receiver<T>,
+}
+
+impl<T> JoinHandle<T> {
+    /// The task the handle belongs to
+    pub fn task(&self) -> Task {
+        self.task
+    }
+
+    /// Cancel the task
+    pub fn cancel(&self) {
+        self.task.cancel();
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Pin::new(&mut self.receiver).poll(cx).map(Result::ok)
+    }
+}
+
+/// Create a [`TaskScope`] for the current component. Its tasks are cancelled when the component is unmounted.
+pub fn use_task_scope() -> TaskScope {
+    use_hook(TaskScope::new)
+}
@@ -1,6 +1,8 @@
 use crate::innerlude::Effect;
+use crate::innerlude::InspectorCommand;
 use crate::innerlude::ScopeOrder;
-use crate::innerlude::{remove_future, spawn, Runtime};
+use crate::innerlude::SuspenseContext;
+use crate::innerlude::{remove_future, spawn, throw_error, CapturedPanic, Runtime};
 use crate::ScopeId;
 use futures_util::task::ArcWake;
 use slotmap::DefaultKey;
@@ -8,7 +10,7 @@ use std::sync::Arc;
 use std::task::Waker;
 use std::{cell::Cell, future::Future};
 use std::{cell::RefCell, rc::Rc};
-use std::{pin::Pin, task::Poll};
+use std::{panic::AssertUnwindSafe, pin::Pin, task::Poll};
 
 /// A task's unique identifier.
 ///
@@ -62,6 +64,7 @@ impl Task {
 
     /// Wake the task.
     pub fn wake(&self) {
+        Runtime::task_woken(*self);
         Runtime::with(|rt| _ = rt.sender.unbounded_send(SchedulerMsg::TaskNotified(*self)));
     }
 
@@ -155,6 +158,7 @@ impl Runtime {
                         tx: self.sender.clone(),
                     })),
                     ty: Cell::new(ty),
+                    boundary: RefCell::new(None),
                 });
 
                 local_task = Some(new_task.clone());
@@ -225,7 +229,23 @@ impl Runtime {
         self.rendering.set(false);
         self.current_task.set(Some(id));
 
-        let poll_result = task.task.borrow_mut().as_mut().poll(&mut cx);
+        // Tasks that were woken by a transition schedule their updates as part of the transition
+        let mut poll = || task.task.borrow_mut().as_mut().poll(&mut cx);
+        let poll_result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            if self.transition_tasks.borrow_mut().remove(&id) {
+                self.in_transition(poll)
+            } else {
+                poll()
+            }
+        }));
+
+        // A task that panicked is finished. The panic is thrown into the nearest error boundary of the task's scope
+        // instead of unwinding through the runtime
+        let poll_result = poll_result.unwrap_or_else(|err| {
+            tracing::error!("Task {id:?} panicked: {err:?}");
+            throw_error::<()>(CapturedPanic { error: err });
+            Poll::Ready(())
+        });
 
         if poll_result.is_ready() {
             // Remove it from the scope so we dont try to double drop it when the scope dropes
@@ -254,6 +274,14 @@ impl Runtime {
         if let Some(task) = &task {
             if task.suspended() {
                 self.suspended_tasks.set(self.suspended_tasks.get() - 1);
+                // Rerender the boundary that was waiting on the task once it has nothing left to wait on
+                if let Some(boundary) = task.boundary.take() {
+                    if boundary.remove_task(id) {
+                        _ = self
+                            .sender
+                            .unbounded_send(SchedulerMsg::Immediate(boundary.id()));
+                    }
+                }
             }
         }
         task
@@ -275,11 +303,14 @@ pub(crate) struct LocalTask {
     waker: Waker,
     ty: Cell<TaskType>,
     active: Cell<bool>,
+    /// The suspense boundary that waits on this task if it is suspended
+    boundary: RefCell<Option<SuspenseContext>>,
 }
 
 impl LocalTask {
-    pub(crate) fn suspend(&self) {
+    pub(crate) fn suspend(&self, boundary: Option<SuspenseContext>) {
         self.ty.set(TaskType::Suspended);
+        *self.boundary.borrow_mut() = boundary;
     }
 
     pub(crate) fn suspended(&self) -> bool {
@@ -307,11 +338,18 @@ pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
     Immediate(ScopeId),
 
+    /// Updates from Components that were marked dirty in a [transition](crate::prelude::start_transition). They are
+    /// rendered after all immediate updates
+    Transition(ScopeId),
+
     /// A task has woken and needs to be progressed
     TaskNotified(Task),
 
     /// An effect has been queued to run after the next render
     EffectQueued,
+
+    /// An inspector sent a command
+    Inspector(InspectorCommand),
 }
 
 struct LocalTaskHandle {
@@ -321,6 +359,7 @@ struct LocalTaskHandle {
 
 impl ArcWake for LocalTaskHandle {
     fn wake_by_ref(arc_self: &Arc<Self>) {
+        Runtime::task_woken(arc_self.id);
         _ = arc_self
             .tx
             .unbounded_send(SchedulerMsg::TaskNotified(arc_self.id));
@@ -6,9 +6,11 @@ use crate::Task;
 use crate::{
     any_props::AnyProps,
     arena::ElementId,
+    diff::BudgetedMutations,
     innerlude::{
-        DirtyTasks, ElementRef, ErrorBoundary, NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState,
-        VNodeMount, VProps, WriteMutations,
+        DirtyTasks, ElementRef, ErrorBoundary, HotLiterals, HotReloadError, HotReloadEvent,
+        InspectorCommand, NoOpMutations, PendingHotReload, RenderReason, SchedulerMsg, ScopeOrder,
+        ScopeState, VNodeMount, VProps, WriteMutations,
     },
     nodes::RenderReturn,
     nodes::{Template, TemplateId},
@@ -19,8 +21,9 @@ use crate::{
 use futures_util::StreamExt;
 use rustc_hash::FxHashMap;
 use slab::Slab;
-use std::collections::BTreeSet;
-use std::{any::Any, rc::Rc};
+use std::collections::{BTreeSet, VecDeque};
+use std::time::{Duration, Instant};
+use std::{any::Any, cell::Cell, rc::Rc};
 use tracing::instrument;
 
 /// A virtual node system that progresses user events and diffs UI trees.
@@ -207,6 +210,7 @@ pub struct VirtualDom {
 
     pub(crate) dirty_scopes: BTreeSet<ScopeOrder>,
     pub(crate) dirty_tasks: BTreeSet<DirtyTasks>,
+    pub(crate) dirty_transitions: BTreeSet<ScopeOrder>,
 
     // Maps a template path to a map of byte indexes to templates
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template>>,
@@ -214,6 +218,12 @@ pub struct VirtualDom {
     // Templates changes that are queued for the next render
     pub(crate) queued_templates: Vec<Template>,
 
+    // Templates that are registered with the renderer when it has time, before any component renders them
+    pub(crate) preregistered_templates: VecDeque<Template>,
+
+    // Hot reloads that are reported to hot reload listeners once the next render finishes
+    pub(crate) pending_hot_reloads: Vec<PendingHotReload>,
+
     // The element ids that are used in the renderer
     pub(crate) elements: Slab<Option<ElementRef>>,
 
@@ -222,6 +232,23 @@ pub struct VirtualDom {
 
     pub(crate) runtime: Rc<Runtime>,
 
+    // Text at least this many bytes long is patched by the range that changed instead of replacing all of it
+    pub(crate) text_diff_threshold: Option<usize>,
+
+    // The number of mutations a render can write before the rest of the dirty components are left for the next render
+    pub(crate) mutation_budget: Option<usize>,
+
+    // The number of mutations the current render wrote
+    pub(crate) mutations_written: Rc<Cell<usize>>,
+
+    // The commands inspectors sent since the last render
+    pub(crate) inspector_commands: Vec<InspectorCommand>,
+
+    // The state of the components in a restored snapshot that haven't been created yet
+    #[cfg(feature = "serialize")]
+    pub(crate) restored_scopes:
+        FxHashMap<crate::snapshot::ScopePath, crate::snapshot::RestoredScope>,
+
     rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
 }
 
@@ -297,7 +324,7 @@ impl VirtualDom {
         root: impl ComponentFunction<P, M>,
         root_props: P,
     ) -> Self {
-        Self::new_with_component(VProps::new(root, |_, _| true, root_props, "root"))
+        Self::new_with_component(VProps::new(root, |_, _| true, |_| None, root_props, "root"))
     }
 
     /// Create a new virtualdom and build it immediately
@@ -318,10 +345,19 @@ impl VirtualDom {
             scopes: Default::default(),
             dirty_scopes: Default::default(),
             dirty_tasks: Default::default(),
+            dirty_transitions: Default::default(),
             templates: Default::default(),
             queued_templates: Default::default(),
+            preregistered_templates: Default::default(),
+            pending_hot_reloads: Default::default(),
             elements: Default::default(),
             mounts: Default::default(),
+            text_diff_threshold: None,
+            mutation_budget: None,
+            mutations_written: Default::default(),
+            inspector_commands: Default::default(),
+            #[cfg(feature = "serialize")]
+            restored_scopes: Default::default(),
         };
 
         let root = dom.new_scope(Box::new(root), "app");
@@ -365,6 +401,39 @@ impl VirtualDom {
         self
     }
 
+    /// Set how long text must be before it is patched by the range that changed instead of replacing all of it
+    ///
+    /// Replacing a range of a large blob of text like a log sends less data to the renderer and touches less of the
+    /// DOM than replacing the whole text. Text is patched by range when it is at least `threshold` bytes long, or
+    /// always for [`VText::ranged`](crate::VText::ranged) nodes. Pass `None` to only patch ranged nodes by range, which
+    /// is the default.
+    pub fn with_text_diff_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.text_diff_threshold = threshold;
+        self
+    }
+
+    /// Spread large updates over multiple renders by limiting how many mutations one render can write
+    ///
+    /// Normally a state change that dirties thousands of nodes blocks the thread until all of them are diffed. With a
+    /// budget, [`Self::render_immediate`] and [`Self::flush_sync`] stop once they wrote `budget` mutations and leave
+    /// the rest of the dirty components for the next render, so the renderer can apply the mutations it has and keep
+    /// the page responsive. The budget is checked before each component is rerun, so a single component always
+    /// renders completely and a render can write more than `budget` mutations. Until a component is rendered, the
+    /// renderer keeps showing its old nodes.
+    ///
+    /// Use [`Self::has_dirty_scopes`] to check if an update is still in progress. Pass `None`, the default, to render
+    /// every dirty component at once.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { rsx! { div {} } }
+    /// let dom = VirtualDom::new(app).with_mutation_budget(Some(1000));
+    /// ```
+    pub fn with_mutation_budget(mut self, budget: Option<usize>) -> Self {
+        self.mutation_budget = budget;
+        self
+    }
+
     /// Provide a context to the root scope
     pub fn provide_root_context<T: Clone + 'static>(&self, context: T) {
         self.base_scope().state().provide_context(context);
@@ -391,6 +460,31 @@ impl VirtualDom {
         self.queue_scope(order);
     }
 
+    /// Mark a scope as requiring a re-render as part of a transition. Transitions are rendered after all other work.
+    fn mark_transition_dirty(&mut self, id: ScopeId) {
+        let Some(scope) = self.runtime.get_state(id) else {
+            return;
+        };
+
+        tracing::event!(
+            tracing::Level::TRACE,
+            "Marking scope {:?} as dirty in a transition",
+            id
+        );
+        let order = ScopeOrder::new(scope.height(), id);
+        drop(scope);
+        self.queue_transition(order);
+    }
+
+    /// Check if any scopes are waiting to rerender as part of a [transition](crate::prelude::start_transition)
+    ///
+    /// [`Self::render_immediate`] renders one transition scope at a time. Renderers that can yield to the event loop
+    /// should do so between renders while transitions are pending, so new input is handled before the rest of the
+    /// transitions.
+    pub fn has_pending_transitions(&self) -> bool {
+        !self.dirty_transitions.is_empty()
+    }
+
     /// Mark a task as dirty
     fn mark_task_dirty(&mut self, task: Task) {
         let Some(scope) = self.runtime.task_scope(task) else {
@@ -465,7 +559,10 @@ impl VirtualDom {
             self.process_events();
 
             // Now that we have collected all queued work, we should check if we have any dirty scopes. If there are not, then we can poll any queued futures
-            if self.has_dirty_scopes() {
+            if self.has_dirty_scopes()
+                || self.has_pending_transitions()
+                || !self.inspector_commands.is_empty()
+            {
                 return;
             }
 
@@ -482,12 +579,14 @@ impl VirtualDom {
     async fn wait_for_event(&mut self) {
         match self.rx.next().await.expect("channel should never close") {
             SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+            SchedulerMsg::Transition(id) => self.mark_transition_dirty(id),
             SchedulerMsg::TaskNotified(id) => {
                 // Instead of running the task immediately, we insert it into the runtime's task queue.
                 // The task may be marked dirty at the same time as the scope that owns the task is dropped.
                 self.mark_task_dirty(id);
             }
             SchedulerMsg::EffectQueued => {}
+            SchedulerMsg::Inspector(command) => self.inspector_commands.push(command),
         };
     }
 
@@ -497,8 +596,10 @@ impl VirtualDom {
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
                 SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Transition(id) => self.mark_transition_dirty(id),
                 SchedulerMsg::TaskNotified(task) => self.mark_task_dirty(task),
                 SchedulerMsg::EffectQueued => {}
+                SchedulerMsg::Inspector(command) => self.inspector_commands.push(command),
             }
         }
     }
@@ -562,26 +663,91 @@ impl VirtualDom {
     ///
     /// The caller must ensure that the template references the same dynamic attributes and nodes as the original template.
     ///
-    /// This will only replace the parent template, not any nested templates.
+    /// This will only replace the parent template, not any nested templates. Templates with the same
+    /// [`Template::content_hash`] as the one they replace are skipped.
     #[instrument(skip(self), level = "trace", name = "VirtualDom::replace_template")]
     pub fn replace_template(&mut self, template: Template) {
+        let (path, _) = template.name.rsplit_once(':').unwrap();
+        let unchanged = self
+            .templates
+            .get(path)
+            .and_then(|templates| templates.iter().min_by_key(|(byte_index, _)| **byte_index))
+            .is_some_and(|(_, old)| old.content_hash() == template.content_hash());
+        if unchanged {
+            if crate::dev_tools::has_hot_reload_listeners() {
+                crate::dev_tools::emit_hot_reload_event(HotReloadEvent::TemplateUnchanged {
+                    name: template.name,
+                });
+            }
+            return;
+        }
+
+        if crate::dev_tools::has_hot_reload_listeners() {
+            self.pending_hot_reloads
+                .push(PendingHotReload::template(template.name));
+        }
         self.register_template_first_byte_index(template);
+        crate::dev_tools::record_hot_reload();
+        self.mark_rsx_call_dirty(template.name.rsplit_once(':').unwrap().0);
+    }
+
+    /// Replace the values of hot reloadable literals at runtime, like literal component props and loop bounds. This will
+    /// re-render all components that use the rsx call the literals are in.
+    ///
+    /// This is the primitive that enables hot-reloading props like the `to` of `Link { to: "/home" }`. Values are
+    /// shared by every [`VirtualDom`] in the process since they are tied to the source code, not to one app.
+    #[instrument(skip(self), level = "trace", name = "VirtualDom::update_hot_literals")]
+    pub fn update_hot_literals(&mut self, literals: HotLiterals) {
+        let location = literals.location.clone();
+        if crate::dev_tools::has_hot_reload_listeners() {
+            self.pending_hot_reloads
+                .push(PendingHotReload::literals(location.clone()));
+        }
+        crate::hot_literals::set_hot_literals(literals);
+        crate::dev_tools::record_hot_reload();
+        self.mark_rsx_call_dirty(&location);
+    }
 
+    /// Set the change the hot reloader couldn't apply, or clear it with `None` once a later change was applied. This
+    /// re-renders every component that read the error with [`hot_reload_error`](crate::hot_reload_error).
+    ///
+    /// Development tools like the dev overlay show the error so failed changes don't go unnoticed.
+    #[instrument(skip(self), level = "trace", name = "VirtualDom::set_hot_reload_error")]
+    pub fn set_hot_reload_error(&mut self, error: Option<HotReloadError>) {
+        let readers = {
+            let mut state = self.runtime.hot_reload_error.borrow_mut();
+            if state.error == error {
+                return;
+            }
+            if let Some(error) = &error {
+                crate::dev_tools::emit_hot_reload_event(HotReloadEvent::Failed(error.clone()));
+            }
+            state.error = error;
+            std::mem::take(&mut state.readers)
+        };
+
+        for scope in readers {
+            self.mark_dirty(scope);
+        }
+    }
+
+    /// Mark every scope that renders a template from the rsx call at `location` (`file:line:column`) as dirty
+    fn mark_rsx_call_dirty(&mut self, location: &str) {
         // iterating a slab is very inefficient, but this is a rare operation that will only happen during development so it's fine
         let mut dirty = Vec::new();
         for (id, scope) in self.scopes.iter() {
             // Recurse into the dynamic nodes of the existing mounted node to see if the template is alive in the tree
-            fn check_node_for_templates(node: &VNode, template: Template) -> bool {
+            fn check_node_for_templates(node: &VNode, location: &str) -> bool {
                 let this_template_name = node.template.get().name.rsplit_once(':').unwrap().0;
 
-                if this_template_name == template.name.rsplit_once(':').unwrap().0 {
+                if this_template_name == location {
                     return true;
                 }
 
                 for dynamic in node.dynamic_nodes.iter() {
                     if let crate::DynamicNode::Fragment(nodes) = dynamic {
                         for node in nodes {
-                            if check_node_for_templates(node, template) {
+                            if check_node_for_templates(node, location) {
                                 return true;
                             }
                         }
@@ -592,7 +758,7 @@ impl VirtualDom {
             }
 
             if let Some(RenderReturn::Ready(sync)) = scope.try_root_node() {
-                if check_node_for_templates(sync, template) {
+                if check_node_for_templates(sync, location) {
                     dirty.push(ScopeId(id));
                 }
             }
@@ -651,10 +817,26 @@ impl VirtualDom {
         let m = self.create_scope(to, ScopeId::ROOT, new_nodes, None);
 
         to.append_children(ElementId(0), m);
+        self.report_hot_reloads();
+        self.update_inspectors(to, true);
+
+        // Components that weren't created during the rebuild are no longer in the tree the snapshot described
+        #[cfg(feature = "serialize")]
+        self.restored_scopes.clear();
+    }
+
+    /// Report the hot reloads that were applied before the last render to hot reload listeners
+    fn report_hot_reloads(&mut self) {
+        for pending in std::mem::take(&mut self.pending_hot_reloads) {
+            crate::dev_tools::emit_hot_reload_event(pending.finish());
+        }
     }
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
+    ///
+    /// If there is no other work, this renders the highest scope that is waiting for a
+    /// [transition](crate::prelude::start_transition) instead. The rest of the transitions are rendered in later calls.
     #[instrument(skip(self, to), level = "trace", name = "VirtualDom::render_immediate")]
     pub fn render_immediate(&mut self, to: &mut impl WriteMutations) {
         self.flush_templates(to);
@@ -664,9 +846,109 @@ impl VirtualDom {
         // This also processes futures which might progress into immediately rerunning a scope
         self.process_events();
 
-        // Next, diff any dirty scopes
-        // We choose not to poll the deadline since we complete pretty quickly anyways
-        while let Some(work) = self.pop_work() {
+        self.reset_mutation_budget();
+        let rendered = self.render_dirty_scopes_or_transition(to);
+        self.reset_mutation_budget();
+
+        self.runtime.finish_render();
+        self.report_hot_reloads();
+        self.update_inspectors(to, rendered);
+    }
+
+    /// Run all of the work that is ready, until nothing is left or the deadline passes. Returns true if work remains.
+    ///
+    /// This is for renderers that drive the VirtualDom from their own loop, like a game loop that renders once per
+    /// frame. Unlike [`Self::render_immediate`], this keeps going until the VirtualDom settles: queued tasks that are
+    /// ready are polled, effects run, and every dirty scope and [transition](crate::prelude::start_transition) is
+    /// rendered. Tasks waiting on something outside of the VirtualDom stay pending.
+    ///
+    /// Effects run before the renderer applies the mutations. The deadline is checked between renders, so a slow
+    /// component can run past it. Platforms without a clock, like wasm in the browser, ignore the deadline.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::NoOpMutations;
+    /// # use std::time::Duration;
+    /// # fn app() -> Element { rsx! { div {} } }
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild(&mut NoOpMutations);
+    ///
+    /// // Once per frame
+    /// let work_remains = dom.flush_sync(&mut NoOpMutations, Some(Duration::from_millis(4)));
+    /// ```
+    #[instrument(skip(self, to), level = "trace", name = "VirtualDom::flush_sync")]
+    pub fn flush_sync(&mut self, to: &mut impl WriteMutations, deadline: Option<Duration>) -> bool {
+        let deadline = deadline.and_then(|deadline| crate::dev_tools::now()?.checked_add(deadline));
+
+        self.flush_templates(to);
+
+        self.reset_mutation_budget();
+        let mut rendered = false;
+        loop {
+            self.process_events();
+
+            let worked = self.render_dirty_scopes_or_transition(to);
+            rendered |= worked;
+            if !worked && !self.has_sync_work() {
+                break;
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                || self.is_over_mutation_budget()
+            {
+                break;
+            }
+        }
+        self.reset_mutation_budget();
+
+        self.runtime.finish_render();
+        self.report_hot_reloads();
+        self.update_inspectors(to, rendered);
+
+        self.queue_events();
+        self.has_sync_work()
+    }
+
+    /// Check if there are tasks, effects, scopes or transitions ready to run without waiting for anything
+    fn has_sync_work(&self) -> bool {
+        self.has_dirty_scopes()
+            || self.has_pending_transitions()
+            || !self.dirty_tasks.is_empty()
+            || !self.runtime.pending_effects.borrow().is_empty()
+    }
+
+    /// Render all dirty scopes, or the highest transition if nothing else is dirty. Returns true if anything rendered
+    fn render_dirty_scopes_or_transition(&mut self, to: &mut impl WriteMutations) -> bool {
+        let to = &mut BudgetedMutations::new(self, to);
+        if self.render_dirty_scopes(to) {
+            return true;
+        }
+
+        // Keep the transitions for the next render if the dirty scopes used up the budget
+        if self.is_over_mutation_budget() {
+            return false;
+        }
+
+        // Transitions only render once everything else is up to date. Render one of them and leave the rest for the
+        // next render, so the renderer can handle new events in between
+        let Some(order) = self.pop_transition() else {
+            return false;
+        };
+        self.queue_scope(order);
+        let runtime = self.runtime.clone();
+        runtime.in_transition(|| self.render_dirty_scopes(to))
+    }
+
+    /// Rerun and diff all dirty scopes. Returns true if there was any work to do
+    fn render_dirty_scopes(&mut self, to: &mut impl WriteMutations) -> bool {
+        let mut worked = false;
+        // We choose not to poll the deadline since we complete pretty quickly anyways. The mutation budget is checked
+        // instead, so very large updates can be spread over multiple renders
+        while !self.is_over_mutation_budget() {
+            let Some(work) = self.pop_work() else {
+                break;
+            };
+            worked = true;
             {
                 let _runtime = RuntimeGuard::new(self.runtime.clone());
 
@@ -679,14 +961,14 @@ impl VirtualDom {
 
                 // If the scope is dirty, run the scope and get the mutations
                 if work.rerun_scope {
+                    self.set_render_reason(work.scope.id, RenderReason::StateChanged);
                     let new_nodes = self.run_scope(work.scope.id);
 
-                    self.diff_scope(to, work.scope.id, new_nodes);
+                    self.diff_dirty_scope(to, work.scope.id, new_nodes);
                 }
             }
         }
-
-        self.runtime.finish_render();
+        worked
     }
 
     /// [`Self::render_immediate`] to a vector of mutations for testing purposes
@@ -715,6 +997,10 @@ impl VirtualDom {
                 // Sometimes when wakers fire we get a slew of updates at once, so its important that we drain this completely
                 self.queue_events();
 
+                // Transitions can't wait while rendering suspense, so they rerender like any other dirty scope
+                let mut transitions = std::mem::take(&mut self.dirty_transitions);
+                self.dirty_scopes.append(&mut transitions);
+
                 // Now that we have collected all queued work, we should check if we have any dirty scopes. If there are not, then we can poll any queued futures
                 if self.has_dirty_scopes() {
                     break;
@@ -763,6 +1049,7 @@ impl VirtualDom {
 
                 // If the scope is dirty, run the scope and get the mutations
                 if work.rerun_scope {
+                    self.set_render_reason(work.scope.id, RenderReason::StateChanged);
                     let new_nodes = self.run_scope(work.scope.id);
 
                     self.diff_scope(&mut NoOpMutations, work.scope.id, new_nodes);
@@ -776,6 +1063,56 @@ impl VirtualDom {
         self.runtime.clone()
     }
 
+    /// Queue templates to register with the renderer before any component renders them
+    ///
+    /// Renderers create the dom nodes of a template the first time it is rendered. Preregistering the templates of
+    /// heavy pages moves that work out of the first interaction that opens them. The templates are registered with
+    /// [`VirtualDom::register_preregistered_template`] whenever the renderer has time. Templates that are rendered
+    /// before that are registered as usual and skipped later.
+    ///
+    /// The template of an element is the `template` of its [`VNode`]:
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// fn app() -> Element {
+    ///     rsx! { "hello world" }
+    /// }
+    ///
+    /// fn settings_page() -> Element {
+    ///     rsx! { div { class: "settings", h1 { "Settings" } } }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// let template = dom.in_runtime(|| settings_page().unwrap().template.get());
+    /// dom.preregister_templates(&[template]);
+    /// ```
+    pub fn preregister_templates(&mut self, templates: &[Template]) {
+        self.preregistered_templates.extend(
+            templates
+                .iter()
+                .filter(|template| !template.is_completely_dynamic())
+                .copied(),
+        );
+    }
+
+    /// Check if there are preregistered templates that were not registered with the renderer yet
+    pub fn has_preregistered_templates(&self) -> bool {
+        !self.preregistered_templates.is_empty()
+    }
+
+    /// Register the next [preregistered template](VirtualDom::preregister_templates) with the renderer. Returns false
+    /// if there were no templates left.
+    ///
+    /// Renderers call this while they are idle, one template at a time so they can stop when they have other work.
+    pub fn register_preregistered_template(&mut self, to: &mut impl WriteMutations) -> bool {
+        let Some(template) = self.preregistered_templates.pop_front() else {
+            return false;
+        };
+        self.register_template(to, template);
+        true
+    }
+
     /// Flush any queued template changes
     #[instrument(skip(self, to), level = "trace", name = "VirtualDom::flush_templates")]
     fn flush_templates(&mut self, to: &mut impl WriteMutations) {
@@ -100,3 +100,57 @@ fn dynamic_node_as_root() {
         ]
     )
 }
+
+/// Components that take a `ChildList` get one node per root, and one node per item of loops and iterators
+#[test]
+fn child_lists_split_children() {
+    fn app() -> Element {
+        let items = ["b", "c"];
+        let rows = vec![rsx! { "e" }, None, rsx! { "f" }];
+        rsx! {
+            Stack {
+                "a"
+                for item in items {
+                    span { "{item}" }
+                }
+                div { "d" }
+            }
+            Stack { children: rows }
+            Stack { children: (0..2).map(|i| rsx! { "{i}" }) }
+            PassThru {
+                "one"
+                "two"
+            }
+        }
+    }
+
+    #[component]
+    fn Stack(children: ChildList) -> Element {
+        let count = children.len();
+        rsx! {
+            ul { "data-count": "{count}",
+                for child in children {
+                    li { {child} }
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn PassThru(children: Element) -> Element {
+        rsx!({ children })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        concat!(
+            r#"<ul data-count="4"><li>a</li><li><span>b</span></li><li><span>c</span></li><li><div>d</div></li></ul>"#,
+            r#"<ul data-count="2"><li>e</li><li>f</li></ul>"#,
+            r#"<ul data-count="2"><li>0</li><li>1</li></ul>"#,
+            "onetwo"
+        )
+    );
+}
@@ -0,0 +1,77 @@
+//! Renderers can supply their own elements to rsx without dioxus-html
+
+use dioxus::dioxus_core::{ElementId, ElementNamespace, Mutation::*};
+use dioxus::prelude::*;
+
+mod dioxus_elements {
+    dioxus_core::define_elements! {
+        pub struct TuiNamespace;
+
+        label {
+            text,
+            color,
+            r#type = "type",
+        };
+
+        rect in "canvas" {
+            width,
+        };
+    }
+}
+
+#[test]
+fn custom_elements_render() {
+    let mut dom = VirtualDom::new(|| {
+        let color = "red";
+        rsx! {
+            label { text: "hello", color: "{color}", r#type: "bold",
+                rect { width: "10" }
+            }
+        }
+    });
+
+    let mutations = dom.rebuild_to_vec().santize();
+    assert_eq!(
+        mutations.edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute { name: "color", value: "red".into_value(), id: ElementId(1), ns: None },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    let TemplateNode::Element { tag, namespace, attrs, children } = mutations.templates[0].roots[0]
+    else {
+        panic!("expected an element root");
+    };
+    assert_eq!((tag, namespace), ("label", None));
+    assert_eq!(
+        attrs,
+        [
+            TemplateAttribute::Static { name: "text", value: "hello", namespace: None },
+            TemplateAttribute::Dynamic { id: 0 },
+            TemplateAttribute::Static { name: "type", value: "bold", namespace: None },
+        ]
+    );
+    assert!(matches!(
+        children,
+        [TemplateNode::Element { tag: "rect", namespace: Some("canvas"), .. }]
+    ));
+}
+
+#[test]
+fn custom_namespace_maps_rust_names() {
+    assert_eq!(
+        dioxus_elements::TuiNamespace::map_element("rect"),
+        Some(("rect", Some("canvas")))
+    );
+    assert_eq!(
+        dioxus_elements::TuiNamespace::map_attribute("label", "r#type"),
+        Some(("type", None))
+    );
+    assert_eq!(
+        dioxus_elements::TuiNamespace::map_attribute("rect", "text"),
+        None
+    );
+    assert_eq!(dioxus_elements::TuiNamespace::map_element("div"), None);
+}
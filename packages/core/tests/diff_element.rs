@@ -1,5 +1,5 @@
 use dioxus::dioxus_core::Mutation::*;
-use dioxus::dioxus_core::{AttributeValue, ElementId, NoOpMutations};
+use dioxus::dioxus_core::{AttributeValue, ElementId, NoOpMutations, VText};
 use dioxus::prelude::*;
 
 #[test]
@@ -31,6 +31,58 @@ fn text_diff() {
     );
 }
 
+#[test]
+fn ranged_text_diff() {
+    fn app() -> Element {
+        let lines = (0..generation() + 4).map(|line| format!("line {line}\n")).collect::<String>();
+        rsx!( pre { {VText::ranged(lines)} } )
+    }
+
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild(&mut NoOpMutations);
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate_to_vec().edits,
+        [ReplaceText { start: 28, delete: 0, value: "line 4\n".to_string(), id: ElementId(2) }]
+    );
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate_to_vec().edits,
+        [ReplaceText { start: 35, delete: 0, value: "line 5\n".to_string(), id: ElementId(2) }]
+    );
+}
+
+#[test]
+fn long_text_diff_replaces_the_changed_range() {
+    fn app() -> Element {
+        let status = if generation() == 0 { "😀 ok" } else { "😀 failed" };
+        let log = "x".repeat(32);
+        rsx!( pre { "{status} {log}" } )
+    }
+
+    let mut vdom = VirtualDom::new(app).with_text_diff_threshold(Some(32));
+    vdom.rebuild(&mut NoOpMutations);
+
+    // The offsets count UTF-16 code units, so the emoji is two long
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate_to_vec().edits,
+        [ReplaceText { start: 3, delete: 2, value: "failed".to_string(), id: ElementId(2) }]
+    );
+
+    // Short text is always set
+    let mut vdom = VirtualDom::new(app).with_text_diff_threshold(None);
+    vdom.rebuild(&mut NoOpMutations);
+
+    vdom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        vdom.render_immediate_to_vec().edits,
+        [SetText { value: format!("😀 failed {}", "x".repeat(32)), id: ElementId(2) }]
+    );
+}
+
 #[test]
 fn element_swap() {
     fn app() -> Element {
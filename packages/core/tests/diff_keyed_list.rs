@@ -356,3 +356,117 @@ fn no_common_keys() {
         ]
     );
 }
+
+/// A keyed fragment moves all of its roots together
+#[test]
+fn keyed_fragments_move_their_roots_together() {
+    let mut dom = VirtualDom::new(|| {
+        let order = match generation() % 2 {
+            0 => &[0, 1],
+            1 => &[1, 0],
+            _ => unreachable!(),
+        };
+
+        rsx! {
+            for i in order {
+                fragment { key: "{i}",
+                    dt {}
+                    dd {}
+                }
+            }
+        }
+    });
+
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate_to_vec().edits,
+        [
+            PushRoot { id: ElementId(3,) },
+            PushRoot { id: ElementId(4,) },
+            InsertBefore { id: ElementId(1,), m: 2 },
+        ]
+    );
+}
+
+/// A key on a loop keys every root of its body like a keyed fragment
+#[test]
+fn loop_keys_move_every_root_together() {
+    let mut dom = VirtualDom::new(|| {
+        let order = match generation() % 2 {
+            0 => &[0, 1],
+            1 => &[1, 0],
+            _ => unreachable!(),
+        };
+
+        rsx! {
+            for i in order { key: "{i}",
+                dt {}
+                dd {}
+            }
+        }
+    });
+
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate_to_vec().edits,
+        [
+            PushRoot { id: ElementId(3,) },
+            PushRoot { id: ElementId(4,) },
+            InsertBefore { id: ElementId(1,), m: 2 },
+        ]
+    );
+}
+
+/// Children wrapped by a layout component keep their keys, so reordering them moves the wrappers
+#[test]
+fn wrapped_children_keep_their_keys() {
+    let mut dom = VirtualDom::new(|| {
+        let order = match generation() % 2 {
+            0 => &[0, 1],
+            1 => &[1, 0],
+            _ => unreachable!(),
+        };
+
+        rsx! {
+            Grid {
+                for i in order {
+                    span { key: "{i}" }
+                }
+            }
+        }
+    });
+
+    #[component]
+    fn Grid(children: ChildList) -> Element {
+        let keys: Vec<_> = children.keys().map(|key| key.map(str::to_string)).collect();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(Option::is_some));
+
+        let cells = children.wrap(|child| {
+            rsx! {
+                div { {child} }
+            }
+        });
+        assert!(cells.keys().eq(keys.iter().map(Option::as_deref)));
+        rsx! {
+            {cells}
+        }
+    }
+
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    // The second cell moves in front of the first without being rebuilt
+    dom.mark_dirty(ScopeId::ROOT);
+    let edits = dom.render_immediate_to_vec().edits;
+    assert!(!edits
+        .iter()
+        .any(|edit| matches!(edit, LoadTemplate { .. } | Remove { .. })));
+    assert!(edits.ends_with(&[
+        PushRoot { id: ElementId(3,) },
+        InsertBefore { id: ElementId(1,), m: 1 },
+    ]));
+}
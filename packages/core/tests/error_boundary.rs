@@ -30,3 +30,193 @@ fn ThrowChild() -> Element {
 
     rsx! { div {} }
 }
+
+#[test]
+fn error_hook_receives_unhandled_errors() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new(app).with_root_context(ErrorHook::new({
+        let errors = errors.clone();
+        move |error| errors.borrow_mut().push(format!("{:?}", error.error))
+    }));
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(errors.borrow().len(), 1);
+    assert!(errors.borrow()[0].contains("AddrInUse"));
+}
+
+#[test]
+fn captured_errors_downcast_to_the_thrown_type() {
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Debug, PartialEq)]
+    struct NotFound(&'static str);
+
+    fn app() -> Element {
+        let errors = use_context::<Rc<RefCell<Vec<Option<String>>>>>();
+        rsx! {
+            ErrorBoundary {
+                handle_error: move |error: CapturedError| {
+                    errors
+                        .borrow_mut()
+                        .push(error.downcast::<NotFound>().map(|error| error.0.to_string()));
+                    None
+                },
+                Throws {}
+            }
+        }
+    }
+
+    fn Throws() -> Element {
+        Err(NotFound("/missing")).throw()?;
+        None
+    }
+
+    let errors: Rc<RefCell<Vec<Option<String>>>> = Default::default();
+    let mut dom = VirtualDom::new(app).with_root_context(errors.clone());
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(*errors.borrow(), vec![Some("/missing".to_string())]);
+}
+
+#[test]
+fn consume_error_takes_out_the_thrown_type() {
+    #[derive(Debug, PartialEq)]
+    struct NotFound(&'static str);
+
+    fn throw(error: impl std::fmt::Debug + 'static) -> CapturedError {
+        CapturedError {
+            error: Box::new(error),
+            backtrace: std::backtrace::Backtrace::disabled(),
+            scope: ScopeId::ROOT,
+        }
+    }
+
+    assert_eq!(
+        throw(NotFound("/missing"))
+            .consume_error::<NotFound>()
+            .unwrap(),
+        NotFound("/missing")
+    );
+    let error = throw("not a NotFound")
+        .consume_error::<NotFound>()
+        .unwrap_err();
+    assert_eq!(error.downcast::<&str>(), Some(&"not a NotFound"));
+}
+
+#[test]
+fn panics_are_captured_by_the_nearest_boundary() {
+    use std::{cell::RefCell, rc::Rc};
+
+    fn app() -> Element {
+        let messages = use_context::<Rc<RefCell<Vec<String>>>>();
+        rsx! {
+            ErrorBoundary {
+                handle_error: move |error: CapturedError| {
+                    let panic = error.downcast::<CapturedPanic>().unwrap();
+                    messages.borrow_mut().push(panic.message().unwrap().to_string());
+                    None
+                },
+                Panics {}
+            }
+        }
+    }
+
+    fn Panics() -> Element {
+        panic!("render failed")
+    }
+
+    let messages: Rc<RefCell<Vec<String>>> = Default::default();
+    let mut dom = VirtualDom::new(app).with_root_context(messages.clone());
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(*messages.borrow(), vec!["render failed".to_string()]);
+}
+
+#[tokio::test]
+async fn task_panics_are_captured_by_the_nearest_boundary() {
+    use std::{cell::RefCell, rc::Rc};
+
+    fn app() -> Element {
+        let messages = use_context::<Rc<RefCell<Vec<String>>>>();
+        rsx! {
+            ErrorBoundary {
+                handle_error: move |error: CapturedError| {
+                    let panic = error.downcast::<CapturedPanic>().unwrap();
+                    messages.borrow_mut().push(panic.message().unwrap().to_string());
+                    rsx! { "failed" }
+                },
+                PanicsInTask {}
+            }
+        }
+    }
+
+    fn PanicsInTask() -> Element {
+        use_hook(|| {
+            spawn(async {
+                panic!("task failed");
+            })
+        });
+        rsx! { "running" }
+    }
+
+    let messages: Rc<RefCell<Vec<String>>> = Default::default();
+    let mut dom = VirtualDom::new(app).with_root_context(messages.clone());
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "running");
+
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {}
+    };
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(*messages.borrow(), vec!["task failed".to_string()]);
+    assert_eq!(dioxus_ssr::render(&dom), "failed");
+}
+
+#[test]
+fn reset_renders_the_failed_children_again() {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    static FAIL: AtomicBool = AtomicBool::new(true);
+
+    fn app() -> Element {
+        let boundary = use_context::<Rc<RefCell<Option<ErrorBoundary>>>>();
+        rsx! {
+            ErrorBoundary {
+                handle_error: move |_| {
+                    *boundary.borrow_mut() = Some(consume_context::<ErrorBoundary>());
+                    rsx! { "failed" }
+                },
+                Flaky {}
+            }
+        }
+    }
+
+    fn Flaky() -> Element {
+        if FAIL.load(Ordering::SeqCst) {
+            Err("not ready").throw()?;
+        }
+        rsx! { "ready" }
+    }
+
+    let boundary: Rc<RefCell<Option<ErrorBoundary>>> = Default::default();
+    let mut dom = VirtualDom::new(app).with_root_context(boundary.clone());
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "failed");
+
+    FAIL.store(false, Ordering::SeqCst);
+    let boundary = boundary.borrow_mut().take().unwrap();
+    dom.in_runtime(|| boundary.reset());
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "ready");
+}
@@ -0,0 +1,73 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+#[derive(Clone, Default)]
+struct Renders(Rc<RefCell<Vec<String>>>);
+
+impl Renders {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+/// An app that settles over several rounds of work: a task, then an effect, then a transition
+fn app() -> Element {
+    let mut loaded = use_signal(|| false);
+    let mut effect_ran = use_signal(|| false);
+    let mut transitioned = use_signal(|| false);
+
+    use_hook(|| {
+        spawn(async move {
+            loaded.set(true);
+        })
+    });
+
+    use_effect(move || {
+        if loaded() && !effect_ran() {
+            effect_ran.set(true);
+            start_transition(move || transitioned.set(true));
+        }
+    });
+
+    consume_context::<Renders>()
+        .0
+        .borrow_mut()
+        .push(format!("{} {} {}", loaded(), effect_ran(), transitioned()));
+
+    rsx! { "{loaded}" }
+}
+
+#[test]
+fn flush_sync_runs_all_ready_work() {
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["false false false"]);
+
+    let work_remains = dom.flush_sync(&mut NoOpMutations, None);
+    assert!(!work_remains);
+    assert_eq!(renders.take().last().unwrap(), "true true true");
+
+    // Nothing is left to do
+    assert!(!dom.flush_sync(&mut NoOpMutations, None));
+    assert!(renders.take().is_empty());
+}
+
+#[test]
+fn flush_sync_stops_at_the_deadline() {
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild(&mut NoOpMutations);
+    renders.take();
+
+    // The deadline passes after the first round of work
+    assert!(dom.flush_sync(&mut NoOpMutations, Some(Duration::ZERO)));
+    assert_ne!(renders.take().last().unwrap(), "true true true");
+
+    // The rest of the work finishes in later calls
+    while dom.flush_sync(&mut NoOpMutations, Some(Duration::ZERO)) {}
+    assert_eq!(renders.take().last().unwrap(), "true true true");
+}
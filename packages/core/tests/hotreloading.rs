@@ -1 +1,288 @@
 //! It should be possible to swap out templates at runtime, enabling hotreloading
+
+use dioxus::dioxus_core::{
+    hot_reload_error, subscribe_hot_reload, HotLiteral, HotLiterals, HotReloadError,
+    HotReloadErrorKind, HotReloadEvent, NoOpMutations, RenderReturn, Template, TemplateNode,
+};
+use dioxus::prelude::*;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+#[test]
+fn hot_literals_update_component_props() {
+    fn app() -> Element {
+        rsx! {
+            Label { text: "before", count: 1 }
+        }
+    }
+
+    #[component]
+    fn Label(text: &'static str, count: i32) -> Element {
+        rsx! { "{text} {count}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "before 1");
+
+    // Every template of an rsx call is named after its location
+    let RenderReturn::Ready(node) = dom.base_scope().root_node() else {
+        panic!("app should render");
+    };
+    let location = node
+        .template
+        .get()
+        .name
+        .rsplit_once(':')
+        .unwrap()
+        .0
+        .to_string();
+
+    dom.update_hot_literals(HotLiterals {
+        location,
+        values: vec![(0, HotLiteral::Str("after")), (1, HotLiteral::Int(2))],
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "after 2");
+}
+
+#[test]
+fn hot_literals_update_loops_and_conditions() {
+    fn app() -> Element {
+        rsx! {
+            for i in 0..2 {
+                span { "{i}" }
+            }
+            if true {
+                "shown"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<span>0</span><span>1</span>shown"
+    );
+
+    let RenderReturn::Ready(node) = dom.base_scope().root_node() else {
+        panic!("app should render");
+    };
+    let location = node
+        .template
+        .get()
+        .name
+        .rsplit_once(':')
+        .unwrap()
+        .0
+        .to_string();
+
+    // The bounds of the loop are literals 0 and 1 and the condition is literal 2
+    dom.update_hot_literals(HotLiterals {
+        location,
+        values: vec![(1, HotLiteral::Int(3)), (2, HotLiteral::Bool(false))],
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<span>0</span><span>1</span><span>2</span>"
+    );
+}
+
+#[test]
+fn templates_with_new_static_nodes_keep_dynamic_nodes() {
+    fn app() -> Element {
+        let name = "world";
+        rsx! {
+            div { "hello {name}" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "<div>hello world</div>");
+
+    let RenderReturn::Ready(node) = dom.base_scope().root_node() else {
+        panic!("app should render");
+    };
+    let name = node.template.get().name;
+
+    // The hot reloader added a static heading and moved the text after it
+    dom.replace_template(Template {
+        name,
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[],
+            children: &[
+                TemplateNode::Element {
+                    tag: "h1",
+                    namespace: None,
+                    attrs: &[],
+                    children: &[TemplateNode::Text { text: "added" }],
+                },
+                TemplateNode::DynamicText { id: 0 },
+            ],
+        }],
+        node_paths: &[&[0, 1]],
+        attr_paths: &[],
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div><h1>added</h1>hello world</div>"
+    );
+}
+
+#[test]
+fn unchanged_templates_are_skipped() {
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    fn app() -> Element {
+        RENDERS.fetch_add(1, Ordering::SeqCst);
+        rsx! {
+            div { "hello" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let RenderReturn::Ready(node) = dom.base_scope().root_node() else {
+        panic!("app should render");
+    };
+    let template = node.template.get();
+
+    // A template rebuilt from the same source hashes the same way, so the app doesn't rerender
+    let rebuilt = Template {
+        name: template.name,
+        roots: template.roots,
+        node_paths: template.node_paths,
+        attr_paths: template.attr_paths,
+    };
+    assert_eq!(rebuilt.content_hash(), template.content_hash());
+    dom.replace_template(rebuilt);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    dom.replace_template(Template {
+        name: template.name,
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Text { text: "changed" }],
+        }],
+        node_paths: &[],
+        attr_paths: &[],
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+    assert_eq!(dioxus_ssr::render(&dom), "<div>changed</div>");
+}
+
+#[test]
+fn hot_reload_errors_rerender_readers() {
+    fn app() -> Element {
+        rsx! {
+            Banner {}
+        }
+    }
+
+    #[component]
+    fn Banner() -> Element {
+        match hot_reload_error() {
+            Some(error) => rsx! { "{error}" },
+            None => rsx! { "ok" },
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "ok");
+
+    dom.set_hot_reload_error(Some(HotReloadError {
+        kind: HotReloadErrorKind::RebuildRequired,
+        file: "src/main.rs".to_string(),
+        line: Some(3),
+        column: Some(4),
+        message: "code outside of rsx changed".to_string(),
+    }));
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "src/main.rs:3:4: code outside of rsx changed"
+    );
+
+    dom.set_hot_reload_error(None);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "ok");
+}
+
+#[test]
+fn hot_reload_events_are_reported() {
+    static EVENTS: Mutex<Vec<HotReloadEvent>> = Mutex::new(Vec::new());
+
+    fn app() -> Element {
+        rsx! {
+            p { "events" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let RenderReturn::Ready(node) = dom.base_scope().root_node() else {
+        panic!("app should render");
+    };
+    let template = node.template.get();
+
+    // Other tests hot reload at the same time, so only keep the events about this app
+    subscribe_hot_reload(move |event| {
+        let ours = match event {
+            HotReloadEvent::TemplatePatched { name, .. }
+            | HotReloadEvent::TemplateUnchanged { name } => *name == template.name,
+            HotReloadEvent::Failed(error) => error.file == "src/events.rs",
+            _ => false,
+        };
+        if ours {
+            EVENTS.lock().unwrap().push(event.clone());
+        }
+    });
+
+    dom.replace_template(template);
+    dom.replace_template(Template {
+        name: template.name,
+        roots: &[TemplateNode::Element {
+            tag: "p",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Text { text: "patched" }],
+        }],
+        node_paths: &[],
+        attr_paths: &[],
+    });
+    // Patches are reported once the components that use them rerender
+    assert_eq!(EVENTS.lock().unwrap().len(), 1);
+    dom.render_immediate(&mut NoOpMutations);
+
+    let error = HotReloadError {
+        kind: HotReloadErrorKind::RebuildRequired,
+        file: "src/events.rs".to_string(),
+        line: Some(1),
+        column: None,
+        message: "code outside of rsx changed".to_string(),
+    };
+    dom.set_hot_reload_error(Some(error.clone()));
+
+    let events = EVENTS.lock().unwrap().clone();
+    let [HotReloadEvent::TemplateUnchanged { .. }, HotReloadEvent::TemplatePatched { duration, .. }, HotReloadEvent::Failed(failed)] =
+        events.as_slice()
+    else {
+        panic!("unexpected hot reload events: {events:?}");
+    };
+    assert!(duration.is_some());
+    assert_eq!(failed, &error);
+}
@@ -0,0 +1,118 @@
+#![allow(non_snake_case)]
+
+//! Inspectors receive the component tree and can highlight the elements of a component
+use dioxus::prelude::*;
+use dioxus_core::{
+    ComponentInfo, InspectorCommand, InspectorConnection, InspectorMessage, RenderReason,
+};
+
+fn app() -> Element {
+    let count = use_signal(|| 0);
+    use_hook(|| provide_context(count));
+
+    rsx! {
+        Counter { count: count() }
+        NotDebug { value: Value }
+        Generic { value: 1 }
+    }
+}
+
+#[component]
+fn Counter(count: i32) -> Element {
+    rsx! {
+        div { "{count}" }
+        div {}
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct Value;
+
+#[component]
+fn NotDebug(value: Value) -> Element {
+    rsx! {}
+}
+
+#[component]
+fn Generic<T: Clone + PartialEq + 'static>(value: T) -> Element {
+    rsx! {}
+}
+
+fn next_tree(inspector: &mut InspectorConnection) -> Vec<ComponentInfo> {
+    match inspector.try_next() {
+        Some(InspectorMessage::Tree(tree)) => tree,
+        other => panic!("expected a tree, got {other:?}"),
+    }
+}
+
+fn find<'a>(tree: &'a [ComponentInfo], name: &str) -> &'a ComponentInfo {
+    tree.iter()
+        .find(|component| component.name == name)
+        .unwrap()
+}
+
+#[test]
+fn inspectors_receive_the_component_tree_after_renders() {
+    let mut dom = VirtualDom::new(app);
+    let mut inspector = dom.connect_inspector();
+    dom.rebuild_in_place();
+
+    let tree = next_tree(&mut inspector);
+    let root = find(&tree, "app");
+    assert_eq!(root.render_reason, RenderReason::Mounted);
+    assert_eq!(root.hooks.len(), 2);
+    assert!(root.hooks[0].type_name.contains("Signal<i32"));
+
+    let counter = find(&tree, "Counter");
+    assert_eq!(counter.parent, Some(root.id));
+    assert_eq!(counter.props.as_deref(), Some("CounterProps { count: 0 }"));
+    assert_eq!(
+        find(&tree, "NotDebug").props.as_deref(),
+        Some("NotDebugProps { value: _ }")
+    );
+    // Fields with a generic type can't be formatted
+    assert_eq!(
+        find(&tree, "Generic").props.as_deref(),
+        Some("GenericProps { value: _ }")
+    );
+
+    // Nothing rendered, so nothing is sent
+    dom.render_immediate_to_vec();
+    assert_eq!(inspector.try_next(), None);
+
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| *consume_context::<Signal<i32>>().write() += 1));
+    dom.render_immediate_to_vec();
+    let tree = next_tree(&mut inspector);
+    assert_eq!(find(&tree, "app").render_reason, RenderReason::StateChanged);
+    assert_eq!(find(&tree, "app").renders, 2);
+    let counter = find(&tree, "Counter");
+    assert_eq!(counter.render_reason, RenderReason::PropsChanged);
+    assert_eq!(counter.props.as_deref(), Some("CounterProps { count: 1 }"));
+    // The props of NotDebug are equal, so it is memoized
+    assert_eq!(find(&tree, "NotDebug").renders, 1);
+}
+
+#[test]
+fn inspectors_can_highlight_a_component() {
+    let mut dom = VirtualDom::new(app);
+    let mut inspector = dom.connect_inspector();
+    dom.rebuild_in_place();
+    let counter = find(&next_tree(&mut inspector), "Counter").id;
+
+    inspector.send(InspectorCommand::HighlightScope(counter));
+    let mutations = dom.render_immediate_to_vec();
+    // Both of the divs the component renders
+    assert_eq!(mutations.highlighted.map(|ids| ids.len()), Some(2));
+    assert!(mutations.edits.is_empty());
+
+    inspector.send(InspectorCommand::ClearHighlight);
+    let mutations = dom.render_immediate_to_vec();
+    assert_eq!(mutations.highlighted, Some(vec![]));
+
+    inspector.send(InspectorCommand::RequestTree);
+    dom.render_immediate_to_vec();
+    assert!(matches!(
+        inspector.try_next(),
+        Some(InspectorMessage::Tree(_))
+    ));
+}
@@ -0,0 +1,82 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Clone, Default)]
+struct Renders(Rc<RefCell<Vec<&'static str>>>);
+
+impl Renders {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    dep: Signal<i32>,
+    other: Signal<i32>,
+}
+
+fn app() -> Element {
+    let state = use_hook(|| {
+        provide_context(State {
+            dep: Signal::new(0),
+            other: Signal::new(0),
+        })
+    });
+    let dep = (state.dep)();
+    let other = (state.other)();
+    rsx! {
+        "{other}"
+        MemoBoundary { deps: dep,
+            Card { "{dep}" }
+        }
+        Card { "{dep}" }
+    }
+}
+
+#[component]
+fn Card(children: Element) -> Element {
+    consume_context::<Renders>().0.borrow_mut().push("card");
+    rsx! { div { {children} } }
+}
+
+fn set(dom: &VirtualDom, f: impl FnOnce(State)) {
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| f(consume_context::<State>())));
+}
+
+#[test]
+fn memo_boundary_skips_children_while_deps_are_equal() {
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["card", "card"]);
+
+    // Only the card outside of the boundary renders again
+    set(&dom, |mut state| state.other += 1);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["card"]);
+
+    // Both cards render once the dependencies change
+    set(&dom, |mut state| state.dep += 1);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["card", "card"]);
+}
+
+#[test]
+fn memo_boundary_renders_the_latest_children() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        use_hook(|| spawn(async move { count += 1 }));
+        rsx! {
+            MemoBoundary { deps: count(), "{count}" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    dom.flush_sync(&mut NoOpMutations, None);
+    assert_eq!(dioxus_ssr::render(&dom), "1");
+}
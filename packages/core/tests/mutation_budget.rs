@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+//! A mutation budget spreads large updates over multiple renders
+use dioxus::prelude::*;
+use dioxus_core::{Mutation, NoOpMutations};
+
+fn app() -> Element {
+    let count = use_signal(|| 0);
+    use_hook(|| provide_context(count));
+
+    rsx! {
+        for i in 0..100 {
+            Row { key: "{i}", index: i, count: count() }
+        }
+    }
+}
+
+#[component]
+fn Row(index: usize, count: usize) -> Element {
+    rsx! {
+        div { "{index}: {count}" }
+    }
+}
+
+fn increment(dom: &VirtualDom) {
+    dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| *consume_context::<Signal<usize>>().write() += 1)
+    });
+}
+
+#[test]
+fn renders_stop_once_the_budget_is_used_up() {
+    let mut dom = VirtualDom::new(app).with_mutation_budget(Some(10));
+    dom.rebuild(&mut NoOpMutations);
+    let expected = dioxus_ssr::render(&dom).replace(": 0", ": 1");
+
+    increment(&dom);
+    let mut renders = 0;
+    loop {
+        let edits = dom.render_immediate_to_vec().edits;
+        renders += 1;
+        assert!(
+            edits.len() <= 10 + 1,
+            "one render wrote {} mutations",
+            edits.len()
+        );
+        if !dom.has_dirty_scopes() {
+            break;
+        }
+        // The rows that were not rendered yet still show the old count
+        assert!(dioxus_ssr::render(&dom).contains("99: 0"));
+    }
+
+    assert!(renders > 1);
+    assert_eq!(dioxus_ssr::render(&dom), expected);
+}
+
+#[test]
+fn flush_sync_stops_once_the_budget_is_used_up() {
+    let mut dom = VirtualDom::new(app).with_mutation_budget(Some(10));
+    dom.rebuild(&mut NoOpMutations);
+
+    increment(&dom);
+    let mut renders = 1;
+    while dom.flush_sync(&mut NoOpMutations, None) {
+        renders += 1;
+    }
+
+    assert!(renders > 1);
+    assert!(dioxus_ssr::render(&dom).contains("99: 1"));
+}
+
+#[test]
+fn without_a_budget_everything_renders_at_once() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+
+    increment(&dom);
+    let edits = dom.render_immediate_to_vec().edits;
+
+    let texts = edits
+        .iter()
+        .filter(|edit| matches!(edit, Mutation::SetText { .. }))
+        .count();
+    assert_eq!(texts, 100);
+    assert!(!dom.has_dirty_scopes());
+}
@@ -0,0 +1,106 @@
+#![allow(non_snake_case)]
+
+use dioxus::dioxus_core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn portal_children_are_appended_to_the_target() {
+    fn app() -> Element {
+        rsx! {
+            div {
+                Portal { target: "modal-root",
+                    p { "modal" }
+                }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild_to_vec().santize();
+
+    assert_eq!(
+        edits.edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            AppendToPortal { target: "modal-root".to_string(), m: 1 },
+            CreatePlaceholder { id: ElementId(3) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn portal_children_are_removed_with_the_portal() {
+    fn app() -> Element {
+        let mut open = use_hook(|| provide_context(Signal::new(true)));
+        rsx! {
+            div {
+                if open() {
+                    Portal { target: "modal-root",
+                        button { onclick: move |_| open.set(false), "close" }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| consume_context::<Signal<bool>>().set(false)));
+    let edits = dom.render_immediate_to_vec().santize();
+
+    assert_eq!(
+        edits.edits,
+        [
+            CreatePlaceholder { id: ElementId(4) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+            Remove { id: ElementId(2) },
+        ]
+    );
+}
+
+#[test]
+fn portal_children_read_context_and_bubble_events_to_the_parents_of_the_portal() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    fn app() -> Element {
+        let clicks = use_context::<Rc<Cell<usize>>>();
+        use_context_provider(|| "dark");
+        rsx! {
+            div { onclick: move |_| clicks.set(clicks.get() + 1),
+                Portal { target: "modal-root", Themed {} }
+            }
+        }
+    }
+
+    fn Themed() -> Element {
+        let theme = use_context::<&'static str>();
+        rsx! {
+            button { class: "{theme}", onclick: |_| {}, "open" }
+        }
+    }
+
+    let clicks = Rc::new(Cell::new(0usize));
+    let mut dom = VirtualDom::new(app).with_root_context(clicks.clone());
+    let edits = dom.rebuild_to_vec();
+
+    assert!(edits.edits.contains(&SetAttribute {
+        name: "class",
+        ns: None,
+        value: "dark".into_value(),
+        id: ElementId(2),
+    }));
+
+    dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(2),
+        true,
+    );
+    assert_eq!(clicks.get(), 1);
+}
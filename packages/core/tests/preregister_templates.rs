@@ -0,0 +1,48 @@
+//! Templates can be registered with the renderer before any component renders them
+
+use dioxus::dioxus_core::{Mutations, Template};
+use dioxus::prelude::*;
+
+fn settings_page() -> Element {
+    rsx! {
+        div { class: "settings",
+            h1 { "Settings" }
+        }
+    }
+}
+
+fn settings_template(dom: &VirtualDom) -> Template {
+    dom.in_runtime(|| settings_page().unwrap().template.get())
+}
+
+#[test]
+fn preregistered_templates_are_registered_when_requested() {
+    let mut dom = VirtualDom::new(|| rsx! { "hello world" });
+    dom.rebuild_in_place();
+
+    let template = settings_template(&dom);
+    dom.preregister_templates(&[template]);
+    assert!(dom.has_preregistered_templates());
+
+    // Rendering doesn't register them
+    assert!(dom.render_immediate_to_vec().templates.is_empty());
+
+    let mut mutations = Mutations::default();
+    assert!(dom.register_preregistered_template(&mut mutations));
+    assert_eq!(mutations.templates, [template]);
+    assert!(!dom.has_preregistered_templates());
+    assert!(!dom.register_preregistered_template(&mut mutations));
+}
+
+#[test]
+fn rendered_templates_are_not_registered_again() {
+    let mut dom = VirtualDom::new(settings_page);
+    let template = settings_template(&dom);
+    dom.preregister_templates(&[template]);
+
+    assert_eq!(dom.rebuild_to_vec().templates, [template]);
+
+    let mut mutations = Mutations::default();
+    assert!(dom.register_preregistered_template(&mut mutations));
+    assert!(mutations.templates.is_empty());
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "serialize")]
+//! Snapshots carry the state of components over to a new virtual dom
+
+use dioxus::prelude::*;
+use dioxus_core::prelude::{add_snapshot_value, restored_value};
+use dioxus_core::{DynamicNodeSnapshot, VirtualDomSnapshot};
+
+fn use_restorable_count(initial: i32) -> Signal<i32> {
+    use_hook(|| {
+        let count = Signal::new(restored_value().unwrap_or(initial));
+        add_snapshot_value(move || *count.peek());
+        count
+    })
+}
+
+fn app() -> Element {
+    let count = use_restorable_count(0);
+
+    rsx! {
+        h1 { "{count}" }
+        for i in 0..count() {
+            Item { key: "{i}", index: i }
+        }
+    }
+}
+
+#[component]
+fn Item(index: i32) -> Element {
+    let count = use_restorable_count(index * 10);
+
+    rsx! { p { "{index}: {count}" } }
+}
+
+fn app_with_handles() -> Element {
+    let count = use_restorable_count(0);
+    use_context_provider(|| count);
+    use_context_provider(|| Signal::new(Vec::<Signal<i32>>::new()));
+
+    rsx! {
+        h1 { "{count}" }
+        for i in 0..count() {
+            ItemWithHandle { key: "{i}", index: i }
+        }
+    }
+}
+
+#[component]
+fn ItemWithHandle(index: i32) -> Element {
+    let count = use_restorable_count(index * 10);
+    use_hook(|| {
+        consume_context::<Signal<Vec<Signal<i32>>>>()
+            .write()
+            .push(count)
+    });
+
+    rsx! { p { "{index}: {count}" } }
+}
+
+#[test]
+fn snapshots_restore_component_state() {
+    let mut dom = VirtualDom::new(app_with_handles);
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "<h1>0</h1>");
+
+    let root_count = dom.in_runtime(|| ScopeId::ROOT.in_runtime(consume_context::<Signal<i32>>));
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| *root_count.write_unchecked() = 3));
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    let items = dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| consume_context::<Signal<Vec<Signal<i32>>>>().cloned())
+    });
+    for item in items {
+        dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| *item.write_unchecked() += 1));
+    }
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    let html = dioxus_ssr::render(&dom);
+    assert_eq!(html, "<h1>3</h1><p>0: 1</p><p>1: 11</p><p>2: 21</p>");
+
+    // The snapshot survives a round trip through a serializer
+    let snapshot = dom.snapshot();
+    let serialized = serde_json::to_string(&snapshot).unwrap();
+    let snapshot: VirtualDomSnapshot = serde_json::from_str(&serialized).unwrap();
+
+    let root = snapshot.root.as_ref().unwrap();
+    assert_eq!(root.values, vec![serde_json::json!(3)]);
+    let DynamicNodeSnapshot::Fragment(items) = &root.node.dynamic_nodes[1] else {
+        panic!("Expected the items to be a fragment");
+    };
+    assert_eq!(items.len(), 3);
+
+    // A new virtual dom picks up where the old one left off
+    let mut restored = VirtualDom::new(app_with_handles);
+    restored.restore(snapshot);
+    restored.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&restored), html);
+}
+
+#[test]
+fn mismatched_snapshots_start_fresh() {
+    let mut dom = VirtualDom::new(app_with_handles);
+    dom.rebuild_in_place();
+    let root_count = dom.in_runtime(|| ScopeId::ROOT.in_runtime(consume_context::<Signal<i32>>));
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| *root_count.write_unchecked() = 2));
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    let snapshot = dom.snapshot();
+
+    // The root is restored, but the items are a different component in the new app so they start over
+    let mut restored = VirtualDom::new(app);
+    restored.restore(snapshot);
+    restored.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&restored),
+        "<h1>2</h1><p>0: 0</p><p>1: 10</p>"
+    );
+}
+
+#[test]
+fn serialized_state_resumes_in_a_new_dom() {
+    let mut dom = VirtualDom::new(app_with_handles);
+    dom.rebuild_in_place();
+    let root_count = dom.in_runtime(|| ScopeId::ROOT.in_runtime(consume_context::<Signal<i32>>));
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| *root_count.write_unchecked() = 2));
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    let html = dioxus_ssr::render(&dom);
+
+    let state = dom.serialize_state().unwrap();
+    let mut resumed = VirtualDom::new(app_with_handles);
+    resumed.resume_from(&state).unwrap();
+    resumed.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&resumed), html);
+
+    // The resumed dom serializes to the same state
+    assert_eq!(resumed.serialize_state().unwrap(), state);
+}
+
+#[test]
+fn invalid_state_starts_fresh() {
+    let mut dom = VirtualDom::new(app);
+    assert!(dom.resume_from("not a snapshot").is_err());
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "<h1>0</h1>");
+}
@@ -1,5 +1,7 @@
 use dioxus::prelude::*;
+use dioxus_core::Mutation;
 use std::future::poll_fn;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::task::Poll;
 
 #[test]
@@ -65,3 +67,167 @@ fn suspended_child() -> Element {
 
     rsx!("child")
 }
+
+static SLOW_CHILDREN_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn boundary_shows_fallback_until_children_resolve() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(boundary_app);
+            let edits = dom.rebuild_to_vec();
+            assert_eq!(dioxus_ssr::render(&dom), "<div>loading</div>");
+
+            // The children are mounted, but only the fallback is in the dom
+            let fallback = edits
+                .edits
+                .iter()
+                .rev()
+                .find_map(|edit| match edit {
+                    Mutation::LoadTemplate { id, .. } => Some(*id),
+                    _ => None,
+                })
+                .unwrap();
+            assert!(!edits
+                .edits
+                .iter()
+                .any(|edit| matches!(edit, Mutation::LoadTemplate { index: 1, .. })));
+
+            let mut edits = Vec::new();
+            while dioxus_ssr::render(&dom) == "<div>loading</div>" {
+                dom.wait_for_work().await;
+                edits.extend(dom.render_immediate_to_vec().edits);
+            }
+
+            assert_eq!(
+                dioxus_ssr::render(&dom),
+                "<div><p>resolved 1</p><span>sibling</span></div>"
+            );
+            assert!(edits
+                .iter()
+                .any(|edit| matches!(edit, Mutation::ReplaceWith { id, .. } if *id == fallback)));
+            // The child kept its state when it moved into the dom
+            assert_eq!(SLOW_CHILDREN_CREATED.load(Ordering::SeqCst), 1);
+        });
+}
+
+#[test]
+fn wait_for_suspense_resolves_boundaries() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(boundary_app);
+            dom.rebuild(&mut dioxus_core::NoOpMutations);
+            dom.wait_for_suspense().await;
+
+            assert_eq!(
+                dioxus_ssr::render(&dom),
+                "<div><p>resolved 1</p><span>sibling</span></div>"
+            );
+        });
+}
+
+fn boundary_app() -> Element {
+    rsx! {
+        div {
+            SuspenseBoundary {
+                fallback: rsx! { "loading" },
+                SlowChild {}
+                span { "sibling" }
+            }
+        }
+    }
+}
+
+#[component]
+fn SlowChild() -> Element {
+    let mut loaded = use_signal(|| 0);
+    use_hook(|| SLOW_CHILDREN_CREATED.fetch_add(1, Ordering::SeqCst));
+
+    if loaded() == 0 {
+        let task = use_hook(|| {
+            spawn(async move {
+                let mut count = 0;
+                poll_fn(|cx| {
+                    if count < 3 {
+                        count += 1;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    } else {
+                        Poll::Ready(())
+                    }
+                })
+                .await;
+                loaded += 1;
+            })
+        });
+        suspend(task)?;
+    }
+
+    rsx! { p { "resolved {loaded}" } }
+}
+
+#[test]
+fn boundary_shows_fallback_when_children_suspend_again() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(|| {
+                let page = use_context_provider(|| Signal::new(1));
+                rsx! {
+                    SuspenseBoundary {
+                        fallback: rsx! { "loading" },
+                        Page { page: page() }
+                    }
+                }
+            });
+            dom.rebuild(&mut dioxus_core::NoOpMutations);
+            while dioxus_ssr::render(&dom) == "loading" {
+                dom.wait_for_work().await;
+                dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            }
+            assert_eq!(dioxus_ssr::render(&dom), "<p>page 1</p>");
+
+            dom.in_runtime(|| {
+                ScopeId::ROOT.in_runtime(|| consume_context::<Signal<usize>>().set(2))
+            });
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            assert_eq!(dioxus_ssr::render(&dom), "loading");
+
+            while dioxus_ssr::render(&dom) == "loading" {
+                dom.wait_for_work().await;
+                dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            }
+            assert_eq!(dioxus_ssr::render(&dom), "<p>page 2</p>");
+        });
+}
+
+#[component]
+fn Page(page: usize) -> Element {
+    let mut loaded = use_signal(|| 0);
+
+    if loaded() != page {
+        let task = spawn(async move {
+            let mut polled = false;
+            poll_fn(|cx| {
+                if polled {
+                    Poll::Ready(())
+                } else {
+                    polled = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+            .await;
+            loaded.set(page);
+        });
+        suspend(task)?;
+    }
+
+    rsx! { p { "page {loaded}" } }
+}
@@ -0,0 +1,127 @@
+//! Tasks spawned in a task scope can be cancelled and awaited together
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+
+#[derive(Clone, Default)]
+struct Log(Rc<RefCell<Vec<&'static str>>>);
+
+impl Log {
+    fn push(&self, entry: &'static str) {
+        self.0.borrow_mut().push(entry);
+    }
+
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+async fn run(dom: &mut VirtualDom, time: Duration) {
+    tokio::select! {
+        _ = async {
+            loop {
+                dom.wait_for_work().await;
+                dom.render_immediate(&mut NoOpMutations);
+            }
+        } => {}
+        _ = tokio::time::sleep(time) => {}
+    };
+}
+
+#[tokio::test]
+async fn join_handles_return_the_output_of_their_task() {
+    fn app() -> Element {
+        let log = consume_context::<Log>();
+        let tasks = use_task_scope();
+        use_hook(move || {
+            let first = tasks.spawn(async { 1 });
+            let second = tasks.spawn(async { 2 });
+            spawn(async move {
+                tasks.join_all().await;
+                assert!(tasks.is_empty());
+                assert_eq!(first.await.unwrap() + second.await.unwrap(), 3);
+                log.push("joined");
+            });
+        });
+        rsx! {}
+    }
+
+    let log = Log::default();
+    let mut dom = VirtualDom::new(app).with_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+    run(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(log.take(), ["joined"]);
+}
+
+#[tokio::test]
+async fn cancel_all_drops_running_tasks() {
+    fn app() -> Element {
+        let log = consume_context::<Log>();
+        let tasks = use_task_scope();
+        use_hook(move || {
+            let slow_log = log.clone();
+            let slow = tasks.spawn(async move {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                slow_log.push("finished");
+            });
+            assert_eq!(tasks.len(), 1);
+            tasks.cancel_all();
+            assert!(tasks.is_empty());
+            spawn(async move {
+                assert_eq!(slow.await, None);
+                log.push("cancelled");
+            });
+        });
+        rsx! {}
+    }
+
+    let log = Log::default();
+    let mut dom = VirtualDom::new(app).with_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+    run(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(log.take(), ["cancelled"]);
+}
+
+#[tokio::test]
+async fn unmounting_cancels_the_tasks_of_the_component() {
+    fn app() -> Element {
+        let mut show = use_signal(|| true);
+        use_hook(move || {
+            spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                show.set(false);
+            })
+        });
+        rsx! {
+            if show() {
+                Child {}
+            }
+        }
+    }
+
+    #[component]
+    fn Child() -> Element {
+        let log = consume_context::<Log>();
+        let tasks = use_task_scope();
+        use_hook(move || {
+            let component_log = log.clone();
+            tasks.spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                component_log.push("component");
+            });
+            TaskScope::app().spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                log.push("app");
+            });
+        });
+        rsx! {}
+    }
+
+    let log = Log::default();
+    let mut dom = VirtualDom::new(app).with_root_context(log.clone());
+    dom.rebuild(&mut NoOpMutations);
+    run(&mut dom, Duration::from_millis(200)).await;
+    assert_eq!(log.take(), ["app"]);
+}
@@ -0,0 +1,168 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Clone, Default)]
+struct Renders(Rc<RefCell<Vec<&'static str>>>);
+
+impl Renders {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    query: Signal<String>,
+    filter: Signal<String>,
+}
+
+fn app() -> Element {
+    let state = use_hook(|| {
+        provide_context(State {
+            query: Signal::new(String::new()),
+            filter: Signal::new(String::new()),
+        })
+    });
+    rsx! {
+        Input { query: state.query }
+        List { filter: state.filter }
+    }
+}
+
+#[component]
+fn Input(query: Signal<String>) -> Element {
+    consume_context::<Renders>().0.borrow_mut().push("input");
+    rsx! { "{query}" }
+}
+
+#[component]
+fn List(filter: Signal<String>) -> Element {
+    let filter = use_memo(move || filter.read().to_uppercase());
+    consume_context::<Renders>().0.borrow_mut().push("list");
+    rsx! { "{filter}" }
+}
+
+fn type_in_search(dom: &VirtualDom, text: &'static str) {
+    dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            let mut state = consume_context::<State>();
+            state.query.set(text.to_string());
+            start_transition(move || state.filter.set(text.to_string()));
+        })
+    });
+}
+
+#[test]
+fn transitions_render_after_urgent_updates() {
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild(&mut NoOpMutations);
+    // Start the task that updates the memo
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["input", "list"]);
+
+    type_in_search(&dom, "a");
+
+    // The input renders first. The list waits for the next render
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["input"]);
+    assert!(dom.has_pending_transitions());
+
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["list"]);
+    assert!(!dom.has_pending_transitions());
+}
+
+#[test]
+fn urgent_updates_take_over_queued_transitions() {
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild(&mut NoOpMutations);
+    dom.render_immediate(&mut NoOpMutations);
+    renders.take();
+
+    type_in_search(&dom, "a");
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["input"]);
+
+    // Typing again before the transition renders only renders the input again
+    type_in_search(&dom, "ab");
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["input"]);
+
+    // An urgent update of the list renders it right away
+    dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| consume_context::<State>().filter.write().push('c'))
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["list"]);
+    assert!(!dom.has_pending_transitions());
+}
+
+#[tokio::test]
+async fn panicking_transition_tasks_end_the_transition() {
+    use std::{cell::Cell, task::Poll};
+
+    fn app() -> Element {
+        let state = use_hook(|| {
+            provide_context(State {
+                query: Signal::new(String::new()),
+                filter: Signal::new(String::new()),
+            })
+        });
+        rsx! {
+            Input { query: state.query }
+            List { filter: state.filter }
+            ErrorBoundary { handle_error: |_| None, PanicsWhenWoken {} }
+        }
+    }
+
+    fn PanicsWhenWoken() -> Element {
+        let task = use_hook(|| {
+            let mut polled = false;
+            spawn(std::future::poll_fn(move |_| {
+                if polled {
+                    panic!("woken by a transition");
+                }
+                polled = true;
+                Poll::Pending
+            }))
+        });
+        consume_context::<Rc<Cell<Option<Task>>>>().set(Some(task));
+        None
+    }
+
+    let renders = Renders::default();
+    let task: Rc<Cell<Option<Task>>> = Default::default();
+    let mut dom = VirtualDom::new(app)
+        .with_root_context(renders.clone())
+        .with_root_context(task.clone());
+    dom.rebuild(&mut NoOpMutations);
+    dom.render_immediate(&mut NoOpMutations);
+    renders.take();
+
+    // Wake the task in a transition. It panics while it is polled as part of the transition
+    let task = task.get().unwrap();
+    dom.in_runtime(|| start_transition(|| task.wake()));
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {}
+    };
+    dom.render_immediate(&mut NoOpMutations);
+    renders.take();
+
+    // Later urgent updates still render right away instead of waiting like transitions
+    dom.in_runtime(|| {
+        ScopeId::ROOT.in_runtime(|| {
+            let mut state = consume_context::<State>();
+            state.query.set("a".to_string());
+            state.filter.set("a".to_string());
+        })
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.take(), ["input", "list"]);
+    assert!(!dom.has_pending_transitions());
+}
@@ -0,0 +1,44 @@
+#![allow(non_snake_case)]
+
+//! The virtual dom calls onunmounted listeners itself when an element is removed
+use dioxus::dioxus_core::Mutation::*;
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Clone, Default)]
+struct Unmounted(Rc<RefCell<Vec<&'static str>>>);
+
+fn app() -> Element {
+    let show = use_signal(|| true);
+    use_hook(|| provide_context(show));
+    let unmounted = consume_context::<Unmounted>();
+
+    rsx! {
+        if show() {
+            div {
+                onunmounted: move |_| unmounted.0.borrow_mut().push("div"),
+                "hello"
+            }
+        }
+    }
+}
+
+#[test]
+fn unmounted_listeners_run_when_the_element_is_removed() {
+    let unmounted = Unmounted::default();
+    let mut dom = VirtualDom::new(app).with_root_context(unmounted.clone());
+
+    // Renderers never see the listener
+    let edits = dom.rebuild_to_vec().santize().edits;
+    assert!(!edits
+        .iter()
+        .any(|edit| matches!(edit, NewEventListener { .. })));
+    assert!(unmounted.0.borrow().is_empty());
+
+    dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| consume_context::<Signal<bool>>().set(false)));
+    let edits = dom.render_immediate_to_vec().santize().edits;
+    assert!(!edits
+        .iter()
+        .any(|edit| matches!(edit, RemoveEventListener { .. })));
+    assert_eq!(*unmounted.0.borrow(), ["div"]);
+}
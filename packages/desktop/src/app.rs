@@ -1,5 +1,7 @@
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use crate::menu_actions::MenuActionRegistry;
 use crate::{
-    config::{Config, WindowCloseBehaviour},
+    config::{Config, PanicBehavior, WindowCloseBehaviour},
     element::DesktopElement,
     event_handlers::WindowEventHandlers,
     file_upload::{DesktopFileDragEvent, DesktopFileUploadForm, FileDialogRequest},
@@ -14,8 +16,10 @@ use dioxus_html::{native_bind::NativeFileEngine, HasFileData, HtmlEvent, Platfor
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
+    panic::AssertUnwindSafe,
     rc::Rc,
     sync::Arc,
+    time::{Duration, Instant},
 };
 use tao::{
     event::Event,
@@ -23,6 +27,9 @@ use tao::{
     window::WindowId,
 };
 
+/// An app that panics again this soon after it restarted is caught in a loop
+const RESTART_LOOP_WINDOW: Duration = Duration::from_secs(5);
+
 /// The single top-level object that manages all the running windows, assets, shortcuts, etc
 pub(crate) struct App {
     // move the props into a cell so we can pop it out later to create the first window
@@ -38,6 +45,12 @@ pub(crate) struct App {
     pub(crate) float_all: bool,
     pub(crate) show_devtools: bool,
 
+    // What to do when the app panics, and how to create it again if it restarts
+    pub(crate) panic_behavior: PanicBehavior,
+    pub(crate) make_dom: Option<Box<dyn Fn() -> VirtualDom>>,
+    pub(crate) last_restart: Option<Instant>,
+    pub(crate) quick_restarts: usize,
+
     /// This single blob of state is shared between all the windows so they have access to the runtime state
     ///
     /// This includes stuff like the event handlers, shortcuts, etc as well as ways to modify *other* windows
@@ -49,12 +62,24 @@ pub(crate) struct SharedContext {
     pub(crate) event_handlers: WindowEventHandlers,
     pub(crate) pending_webviews: RefCell<Vec<WebviewInstance>>,
     pub(crate) shortcut_manager: ShortcutRegistry,
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub(crate) menu_actions: MenuActionRegistry,
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
 }
 
 impl App {
-    pub fn new(cfg: Config, virtual_dom: VirtualDom) -> (EventLoop<UserWindowEvent>, Self) {
+    pub fn new(mut cfg: Config, virtual_dom: VirtualDom) -> (EventLoop<UserWindowEvent>, Self) {
+        // Pick up the state the previous build saved before `dx serve` rebuilt the app
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        let virtual_dom = {
+            let mut virtual_dom = virtual_dom;
+            if let Some(snapshot) = take_saved_dom_state() {
+                virtual_dom.restore(snapshot);
+            }
+            virtual_dom
+        };
+
         let event_loop = EventLoopBuilder::<UserWindowEvent>::with_user_event().build();
 
         let app = Self {
@@ -65,11 +90,17 @@ impl App {
             unmounted_dom: Cell::new(Some(virtual_dom)),
             float_all: !cfg!(debug_assertions),
             show_devtools: false,
+            panic_behavior: cfg.panic_behavior,
+            make_dom: cfg.make_dom.take(),
+            last_restart: None,
+            quick_restarts: 0,
             cfg: Cell::new(Some(cfg)),
             shared: Rc::new(SharedContext {
                 event_handlers: WindowEventHandlers::default(),
                 pending_webviews: Default::default(),
                 shortcut_manager: ShortcutRegistry::new(),
+                #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+                menu_actions: MenuActionRegistry::default(),
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
             }),
@@ -87,12 +118,7 @@ impl App {
         app.set_menubar_receiver();
 
         // Allow hotreloading to work - but only in debug mode
-        #[cfg(all(
-            feature = "hot-reload",
-            debug_assertions,
-            not(target_os = "android"),
-            not(target_os = "ios")
-        ))]
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
         app.connect_hotreload();
 
         #[cfg(debug_assertions)]
@@ -137,27 +163,36 @@ impl App {
                     }
                 }
             }
-            _ => (),
+            _ => {
+                self.shared.menu_actions.call_handler(event.id());
+            }
         }
     }
 
-    #[cfg(all(
-        feature = "hot-reload",
-        debug_assertions,
-        not(target_os = "android"),
-        not(target_os = "ios")
-    ))]
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
     pub fn connect_hotreload(&self) {
-        let Ok(cfg) = dioxus_cli_config::CURRENT_CONFIG.as_ref() else {
-            return;
-        };
-
-        dioxus_hot_reload::connect_at(cfg.target_dir.join("dioxusin"), {
+        let callback = {
             let proxy = self.shared.proxy.clone();
             move |template| {
                 let _ = proxy.send_event(UserWindowEvent::HotReloadEvent(template));
             }
-        });
+        };
+
+        // Phones can't reach the socket on the machine running the CLI, so they connect to `dx serve --device-port`
+        // over the network
+        if cfg!(any(target_os = "android", target_os = "ios")) {
+            dioxus_hot_reload::connect_remote(callback);
+            return;
+        }
+
+        let Ok(cfg) = dioxus_cli_config::CURRENT_CONFIG.as_ref() else {
+            return;
+        };
+
+        let namespace = dioxus_hot_reload::namespace();
+        let socket_name = dioxus_hot_reload::socket_name(namespace.as_deref());
+
+        dioxus_hot_reload::connect_at(cfg.target_dir.join(socket_name), callback);
     }
 
     pub fn handle_new_window(&mut self) {
@@ -235,13 +270,16 @@ impl App {
     ///
     /// Let's rebuild it and then start polling it
     pub fn handle_initialize_msg(&mut self, id: WindowId) {
-        let view = self.webviews.get_mut(&id).unwrap();
-
-        view.dom
-            .rebuild(&mut *view.desktop_context.mutation_state.borrow_mut());
+        self.run_guarded(id, |view| {
+            view.dom
+                .rebuild(&mut *view.desktop_context.mutation_state.borrow_mut());
 
-        view.desktop_context.send_edits();
+            view.desktop_context.send_edits();
+        });
 
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
         view.desktop_context
             .window
             .set_visible(self.is_visible_before_start);
@@ -306,6 +344,7 @@ impl App {
                     Rc::new(PlatformEventData::new(Box::new(DesktopFileDragEvent {
                         mouse: drag.mouse.clone(),
                         files: Arc::new(NativeFileEngine::new(paths)),
+                        data_transfer: dioxus_html::HasDragData::data_transfer(drag),
                     })))
                 } else {
                     data.into_any()
@@ -314,18 +353,15 @@ impl App {
             _ => data.into_any(),
         };
 
-        view.dom.handle_event(&name, as_any, element, bubbles);
-        view.dom
-            .render_immediate(&mut *view.desktop_context.mutation_state.borrow_mut());
-        view.desktop_context.send_edits();
+        self.run_guarded(id, |view| {
+            view.dom.handle_event(&name, as_any, element, bubbles);
+            view.dom
+                .render_immediate(&mut *view.desktop_context.mutation_state.borrow_mut());
+            view.desktop_context.send_edits();
+        });
     }
 
-    #[cfg(all(
-        feature = "hot-reload",
-        debug_assertions,
-        not(target_os = "android"),
-        not(target_os = "ios")
-    ))]
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
     pub fn handle_hot_reload_msg(&mut self, msg: dioxus_hot_reload::HotReloadMsg) {
         match msg {
             dioxus_hot_reload::HotReloadMsg::UpdateTemplate(template) => {
@@ -334,18 +370,45 @@ impl App {
                     webview.poll_vdom();
                 }
             }
+            dioxus_hot_reload::HotReloadMsg::UpdateLiterals(literals) => {
+                for webview in self.webviews.values_mut() {
+                    webview.dom.update_hot_literals(literals.clone());
+                    webview.poll_vdom();
+                }
+            }
+            dioxus_hot_reload::HotReloadMsg::ReportError(error) => {
+                for webview in self.webviews.values_mut() {
+                    webview.dom.set_hot_reload_error(error.clone());
+                    webview.poll_vdom();
+                }
+            }
+            dioxus_hot_reload::HotReloadMsg::SaveState => {
+                self.save_dom_state();
+                self.control_flow = ControlFlow::Exit;
+            }
             dioxus_hot_reload::HotReloadMsg::Shutdown => {
                 self.control_flow = ControlFlow::Exit;
             }
 
-            dioxus_hot_reload::HotReloadMsg::UpdateAsset(_) => {
+            dioxus_hot_reload::HotReloadMsg::UpdateAsset { path, contents } => {
                 for webview in self.webviews.values_mut() {
-                    webview.kick_stylsheets();
+                    webview.update_asset(&path, contents.as_deref());
                 }
             }
         }
     }
 
+    /// Save the state of the main window's virtual dom so the next build can restore it
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn save_dom_state(&self) {
+        let (Some(webview), Some(path)) = (self.webviews.values().next(), dom_state_path()) else {
+            return;
+        };
+        if let Ok(state) = webview.dom.serialize_state() {
+            _ = std::fs::write(path, state);
+        }
+    }
+
     pub fn handle_file_dialog_msg(&mut self, msg: IpcMessage, window: WindowId) {
         let Ok(file_dialog) = serde_json::from_value::<FileDialogRequest>(msg.params()) else {
             return;
@@ -362,19 +425,19 @@ impl App {
 
         let data = Rc::new(PlatformEventData::new(as_any));
 
-        let view = self.webviews.get_mut(&window).unwrap();
+        self.run_guarded(window, |view| {
+            if event_name == "change&input" {
+                view.dom
+                    .handle_event("input", data.clone(), id, event_bubbles);
+                view.dom.handle_event("change", data, id, event_bubbles);
+            } else {
+                view.dom.handle_event(event_name, data, id, event_bubbles);
+            }
 
-        if event_name == "change&input" {
             view.dom
-                .handle_event("input", data.clone(), id, event_bubbles);
-            view.dom.handle_event("change", data, id, event_bubbles);
-        } else {
-            view.dom.handle_event(event_name, data, id, event_bubbles);
-        }
-
-        view.dom
-            .render_immediate(&mut *view.desktop_context.mutation_state.borrow_mut());
-        view.desktop_context.send_edits();
+                .render_immediate(&mut *view.desktop_context.mutation_state.borrow_mut());
+            view.desktop_context.send_edits();
+        });
     }
 
     /// Poll the virtualdom until it's pending
@@ -383,11 +446,36 @@ impl App {
     ///
     /// All IO is done on the tokio runtime we started earlier
     pub fn poll_vdom(&mut self, id: WindowId) {
+        self.run_guarded(id, |view| view.poll_vdom());
+    }
+
+    /// Run the virtual dom of a window. If the app panics, it is restarted if the panic behavior allows it, otherwise
+    /// the panic continues
+    fn run_guarded(&mut self, id: WindowId, f: impl FnOnce(&mut WebviewInstance)) {
         let Some(view) = self.webviews.get_mut(&id) else {
             return;
         };
+        let Err(panic) = std::panic::catch_unwind(AssertUnwindSafe(|| f(view))) else {
+            return;
+        };
+        let make_dom = match (&self.make_dom, self.panic_behavior) {
+            (Some(make_dom), PanicBehavior::RestartWebview) => make_dom,
+            _ => std::panic::resume_unwind(panic),
+        };
 
-        view.poll_vdom();
+        // Restore the state of the app unless it keeps panicking right after restarts. Give up if it panics right
+        // after starting fresh too
+        match self.last_restart {
+            Some(last) if last.elapsed() < RESTART_LOOP_WINDOW => self.quick_restarts += 1,
+            _ => self.quick_restarts = 0,
+        }
+        if self.quick_restarts > 1 {
+            tracing::error!("The app panicked right after it restarted. Exiting");
+            std::panic::resume_unwind(panic);
+        }
+        tracing::error!("The app panicked. Restarting it");
+        self.last_restart = Some(Instant::now());
+        view.restart(make_dom(), self.quick_restarts == 0);
     }
 
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
@@ -513,6 +601,27 @@ impl App {
     }
 }
 
+/// Where the state of the virtual dom is saved while the app is rebuilt. Apps in the same workspace share the target
+/// directory, so the file is named after the hot reloading namespace of the app
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+fn dom_state_path() -> Option<std::path::PathBuf> {
+    let cfg = dioxus_cli_config::CURRENT_CONFIG.as_ref().ok()?;
+    let name = match dioxus_hot_reload::namespace() {
+        Some(namespace) => format!("dom_state-{namespace}.json"),
+        None => "dom_state.json".to_string(),
+    };
+    Some(cfg.target_dir.join(name))
+}
+
+/// Take the state the previous build saved with [`App::save_dom_state`]. The state is only restored once
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+fn take_saved_dom_state() -> Option<dioxus_core::VirtualDomSnapshot> {
+    let path = dom_state_path()?;
+    let state = std::fs::read_to_string(&path).ok()?;
+    _ = std::fs::remove_file(path);
+    serde_json::from_str(&state).ok()
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct PreservedWindowState {
     x: i32,
@@ -13,18 +13,24 @@ pub struct AssetHandler {
 
 #[derive(Clone)]
 pub struct AssetHandlerRegistry {
-    dom_rt: Rc<Runtime>,
+    dom_rt: Rc<RefCell<Rc<Runtime>>>,
     handlers: Rc<RefCell<FxHashMap<String, AssetHandler>>>,
 }
 
 impl AssetHandlerRegistry {
     pub fn new(dom_rt: Rc<Runtime>) -> Self {
         AssetHandlerRegistry {
-            dom_rt,
+            dom_rt: Rc::new(RefCell::new(dom_rt)),
             handlers: Default::default(),
         }
     }
 
+    /// Run the handlers of a new virtual dom after the app restarted. The handlers of the old one are removed.
+    pub(crate) fn reset(&self, dom_rt: Rc<Runtime>) {
+        *self.dom_rt.borrow_mut() = dom_rt;
+        self.handlers.borrow_mut().clear();
+    }
+
     pub fn has_handler(&self, name: &str) -> bool {
         self.handlers.borrow().contains_key(name)
     }
@@ -36,12 +42,13 @@ impl AssetHandlerRegistry {
         responder: RequestAsyncResponder,
     ) {
         if let Some(handler) = self.handlers.borrow().get(name) {
+            let dom_rt = self.dom_rt.borrow().clone();
+
             // Push the runtime onto the stack
-            let _guard = RuntimeGuard::new(self.dom_rt.clone());
+            let _guard = RuntimeGuard::new(dom_rt.clone());
 
             // And run the handler in the scope of the component that created it
-            self.dom_rt
-                .on_scope(handler.scope, || (handler.f)(request, responder));
+            dom_rt.on_scope(handler.scope, || (handler.f)(request, responder));
         }
     }
 
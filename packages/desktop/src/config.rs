@@ -1,3 +1,4 @@
+use dioxus_core::VirtualDom;
 use std::borrow::Cow;
 use std::path::PathBuf;
 use tao::window::{Icon, WindowBuilder};
@@ -17,6 +18,22 @@ pub enum WindowCloseBehaviour {
     CloseWindow,
 }
 
+/// What the application does after the app panics
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PanicBehavior {
+    /// Default behaviour, the panic exits the app
+    #[default]
+    Exit,
+    /// Start the app in the window over. The new app gets the state the old one registered for snapshots, like
+    /// signals. If it panics again right away, it starts fresh instead, and if that panics right away too, the app
+    /// exits.
+    ///
+    /// Only apps started with [`launch`](crate::launch::launch) can be started over. Apps launched from a virtual dom
+    /// exit instead.
+    RestartWebview,
+}
+
 /// The configuration for the desktop application.
 pub struct Config {
     pub(crate) window: WindowBuilder,
@@ -31,6 +48,9 @@ pub struct Config {
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behavior: WindowCloseBehaviour,
+    pub(crate) panic_behavior: PanicBehavior,
+    /// Creates the app again when it is restarted after a panic
+    pub(crate) make_dom: Option<Box<dyn Fn() -> VirtualDom>>,
 }
 
 pub(crate) type WryProtocol = (
@@ -65,6 +85,8 @@ impl Config {
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behavior: WindowCloseBehaviour::LastWindowExitsApp,
+            panic_behavior: PanicBehavior::Exit,
+            make_dom: None,
         }
     }
 
@@ -108,6 +130,12 @@ impl Config {
         self
     }
 
+    /// Sets what the application does after the app panics.
+    pub fn with_panic_behavior(mut self, behavior: PanicBehavior) -> Self {
+        self.panic_behavior = behavior;
+        self
+    }
+
     /// Set a custom protocol
     pub fn with_custom_protocol<F>(mut self, name: String, handler: F) -> Self
     where
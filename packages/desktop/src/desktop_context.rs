@@ -1,3 +1,5 @@
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use crate::MenuActionHandle;
 use crate::{
     app::SharedContext,
     assets::AssetHandlerRegistry,
@@ -241,6 +243,29 @@ impl DesktopService {
         self.shared.shortcut_manager.remove_all()
     }
 
+    /// Handle clicks on the menu items with the given id.
+    ///
+    /// Menu items with the id are enabled while at least one handler is registered. If several handlers are
+    /// registered for the same id, the most recent one handles the click.
+    ///
+    /// See [`use_menu_action`](crate::use_menu_action) for a convenient hook.
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn create_menu_action(
+        &self,
+        id: impl Into<muda::MenuId>,
+        callback: impl Fn() + 'static,
+    ) -> MenuActionHandle {
+        self.shared
+            .menu_actions
+            .add_action(id.into(), Rc::new(callback))
+    }
+
+    /// Remove a menu action created with [`DesktopContext::create_menu_action`]
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn remove_menu_action(&self, handle: MenuActionHandle) {
+        self.shared.menu_actions.remove_action(handle)
+    }
+
     /// Provide a callback to handle asset loading yourself.
     /// If the ScopeId isn't provided, defaults to a global handler.
     /// Note that the handler is namespaced by name, not ScopeId.
@@ -37,6 +37,13 @@ impl EditQueue {
         }
     }
 
+    /// Throw away the pending edits when the page reloads. The new page requests the edits of the new app.
+    pub fn reset(&self) {
+        self.queue.borrow_mut().clear();
+        self.responder.borrow_mut().take();
+        self.edits_finished();
+    }
+
     fn start_edits(&self) {
         self.edits_in_progress.set(true);
     }
@@ -8,7 +8,7 @@ use dioxus_html::{
         InteractionElementOffset, InteractionLocation, ModifiersInteraction, PointerInteraction,
     },
     prelude::{SerializedMouseData, SerializedPointInteraction},
-    FileEngine, HasDragData, HasFileData, HasFormData, HasMouseData,
+    DataTransfer, FileEngine, HasDragData, HasFileData, HasFormData, HasMouseData,
 };
 
 use serde::Deserialize;
@@ -178,6 +178,7 @@ impl NativeFileHover {
 pub(crate) struct DesktopFileDragEvent {
     pub mouse: SerializedPointInteraction,
     pub files: Arc<NativeFileEngine>,
+    pub data_transfer: DataTransfer,
 }
 
 impl HasFileData for DesktopFileDragEvent {
@@ -187,6 +188,10 @@ impl HasFileData for DesktopFileDragEvent {
 }
 
 impl HasDragData for DesktopFileDragEvent {
+    fn data_transfer(&self) -> DataTransfer {
+        self.data_transfer.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
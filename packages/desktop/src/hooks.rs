@@ -1,5 +1,7 @@
 use std::rc::Rc;
 
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+use crate::MenuActionHandle;
 use crate::{
     assets::*, ipc::UserWindowEvent, shortcut::IntoAccelerator, window, DesktopContext,
     ShortcutHandle, ShortcutRegistryError, WryEventHandler,
@@ -44,6 +46,41 @@ pub fn use_muda_event_handler(
     })
 }
 
+/// Handle clicks on the native menu items with the given id while the component is mounted.
+///
+/// Menu items with the id are enabled while a handler for them is mounted and disabled when the last one is dropped,
+/// so give them the enabled state they should have before any component mounts when building the menu. If several
+/// mounted components handle the same id, the one that was mounted last handles the click.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_desktop::use_menu_action;
+///
+/// fn Editor() -> Element {
+///     let mut saved = use_signal(|| false);
+///     use_menu_action("file.save", move || saved.set(true));
+///
+///     rsx! { "Saved: {saved}" }
+/// }
+/// ```
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(target_os = "windows", target_os = "linux", target_os = "macos")))
+)]
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub fn use_menu_action(
+    id: impl Into<muda::MenuId>,
+    handler: impl FnMut() + 'static,
+) -> MenuActionHandle {
+    // wrap the user's handler in something that will carry the scope/runtime with it
+    let cb = use_callback(handler);
+
+    use_hook_with_cleanup(
+        move || window().create_menu_action(id, move || cb.call()),
+        |handle| handle.remove(),
+    )
+}
+
 /// Provide a callback to handle asset loading yourself.
 ///
 /// The callback takes a path as requested by the web view, and it should return `Some(response)`
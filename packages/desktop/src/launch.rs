@@ -41,12 +41,7 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, desktop_config: Conf
                 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
                 UserWindowEvent::MudaMenuEvent(evnt) => app.handle_menu_event(evnt),
 
-                #[cfg(all(
-                    feature = "hot-reload",
-                    debug_assertions,
-                    not(target_os = "android"),
-                    not(target_os = "ios")
-                ))]
+                #[cfg(all(feature = "hot-reload", debug_assertions))]
                 UserWindowEvent::HotReloadEvent(msg) => app.handle_hot_reload_msg(msg),
 
                 UserWindowEvent::Ipc { id, msg } => match msg.method() {
@@ -84,13 +79,21 @@ pub fn launch_virtual_dom(virtual_dom: VirtualDom, desktop_config: Config) {
 pub fn launch(
     root: fn() -> Element,
     contexts: Vec<Box<dyn Fn() -> Box<dyn Any>>>,
-    platform_config: Config,
+    mut platform_config: Config,
 ) {
-    let mut virtual_dom = VirtualDom::new(root);
+    let make_dom = move || {
+        let mut virtual_dom = VirtualDom::new(root);
 
-    for context in contexts {
-        virtual_dom.insert_any_root_context(context());
-    }
+        for context in &contexts {
+            virtual_dom.insert_any_root_context(context());
+        }
+
+        virtual_dom
+    };
+    let virtual_dom = make_dom();
+
+    // The app can only be restarted after a panic if we can create it again
+    platform_config.make_dom = Some(Box::new(make_dom));
 
     launch_virtual_dom(virtual_dom, platform_config)
 }
@@ -15,6 +15,8 @@ mod events;
 mod file_upload;
 mod hooks;
 mod ipc;
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+mod menu_actions;
 mod menubar;
 mod protocol;
 mod query;
@@ -41,9 +43,11 @@ pub use muda;
 
 // Public exports
 pub use assets::AssetRequest;
-pub use config::{Config, WindowCloseBehaviour};
+pub use config::{Config, PanicBehavior, WindowCloseBehaviour};
 pub use desktop_context::{window, DesktopContext, DesktopService};
 pub use event_handlers::WryEventHandler;
 pub use hooks::*;
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub use menu_actions::MenuActionHandle;
 pub use shortcut::{ShortcutHandle, ShortcutRegistryError};
 pub use wry::RequestAsyncResponder;
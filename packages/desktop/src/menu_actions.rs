@@ -0,0 +1,115 @@
+use crate::window;
+use muda::{Menu, MenuId, MenuItemKind};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+use tao::window::WindowId;
+
+/// A handle to a menu action registered with [`use_menu_action`](crate::use_menu_action).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MenuActionHandle {
+    action: MenuId,
+    number: usize,
+}
+
+impl MenuActionHandle {
+    /// Get the id of the menu item this action handles
+    pub fn action(&self) -> &str {
+        self.action.as_ref()
+    }
+
+    /// Remove the menu action.
+    pub fn remove(&self) {
+        window().remove_menu_action(self.clone());
+    }
+}
+
+/// Routes menu events to the handlers that components registered for the id of the menu item.
+///
+/// Menu items are enabled while at least one handler for their id is mounted and disabled once the last one is
+/// removed. Items whose id never had a handler are left alone so menus that are handled with
+/// [`use_muda_event_handler`](crate::use_muda_event_handler) keep working.
+#[derive(Default)]
+pub(crate) struct MenuActionRegistry {
+    // Handlers in the order they were registered. An id with no handlers is kept so new menus are disabled too.
+    actions: RefCell<HashMap<MenuId, Vec<(usize, Rc<dyn Fn()>)>>>,
+    menus: RefCell<HashMap<WindowId, Menu>>,
+    next_number: Cell<usize>,
+}
+
+impl MenuActionRegistry {
+    /// Run the most recently mounted handler for the menu item. Returns false if no handler is mounted.
+    pub(crate) fn call_handler(&self, id: &MenuId) -> bool {
+        // Release the borrow before running the handler since it may mount or unmount other actions
+        let handler = self
+            .actions
+            .borrow()
+            .get(id)
+            .and_then(|handlers| handlers.last())
+            .map(|(_, handler)| handler.clone());
+
+        match handler {
+            Some(handler) => {
+                handler();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn add_action(&self, action: MenuId, handler: Rc<dyn Fn()>) -> MenuActionHandle {
+        let number = self.next_number.get();
+        self.next_number.set(number + 1);
+
+        let mut actions = self.actions.borrow_mut();
+        let handlers = actions.entry(action.clone()).or_default();
+        handlers.push((number, handler));
+        if handlers.len() == 1 {
+            self.set_enabled(&action, true);
+        }
+
+        MenuActionHandle { action, number }
+    }
+
+    pub(crate) fn remove_action(&self, handle: MenuActionHandle) {
+        let mut actions = self.actions.borrow_mut();
+        if let Some(handlers) = actions.get_mut(&handle.action) {
+            handlers.retain(|(number, _)| *number != handle.number);
+            if handlers.is_empty() {
+                self.set_enabled(&handle.action, false);
+            }
+        }
+    }
+
+    /// Track the menu of a window so its items follow the mounted actions
+    pub(crate) fn add_menu(&self, window: WindowId, menu: Menu) {
+        for (action, handlers) in self.actions.borrow().iter() {
+            set_items_enabled(menu.items(), action, !handlers.is_empty());
+        }
+        self.menus.borrow_mut().insert(window, menu);
+    }
+
+    pub(crate) fn remove_menu(&self, window: WindowId) {
+        self.menus.borrow_mut().remove(&window);
+    }
+
+    fn set_enabled(&self, action: &MenuId, enabled: bool) {
+        for menu in self.menus.borrow().values() {
+            set_items_enabled(menu.items(), action, enabled);
+        }
+    }
+}
+
+fn set_items_enabled(items: Vec<MenuItemKind>, action: &MenuId, enabled: bool) {
+    for item in items {
+        match item {
+            MenuItemKind::MenuItem(item) if item.id() == action => item.set_enabled(enabled),
+            MenuItemKind::Check(item) if item.id() == action => item.set_enabled(enabled),
+            MenuItemKind::Icon(item) if item.id() == action => item.set_enabled(enabled),
+            MenuItemKind::Submenu(submenu) => set_items_enabled(submenu.items(), action, enabled),
+            _ => {}
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
 use dioxus_core::{ScopeId, VirtualDom};
 use dioxus_html::prelude::EvalProvider;
 use futures_util::{pin_mut, FutureExt};
-use std::{rc::Rc, task::Waker};
+use std::{panic::AssertUnwindSafe, rc::Rc, task::Waker};
 use wry::{RequestAsyncResponder, WebContext, WebViewBuilder};
 
 pub(crate) struct WebviewInstance {
@@ -194,6 +194,10 @@ impl WebviewInstance {
         let menu = if cfg!(not(any(target_os = "android", target_os = "ios"))) {
             if let Some(menu) = &cfg.menu {
                 crate::menubar::init_menu_bar(menu, &window);
+
+                // Let menu actions mounted anywhere in the app enable and disable the items of this menu
+                #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+                shared.menu_actions.add_menu(window.id(), menu.clone());
             }
             cfg.menu
         } else {
@@ -209,13 +213,7 @@ impl WebviewInstance {
             file_hover,
         ));
 
-        let provider: Rc<dyn EvalProvider> =
-            Rc::new(DesktopEvalProvider::new(desktop_context.clone()));
-
-        dom.in_runtime(|| {
-            ScopeId::ROOT.provide_context(desktop_context.clone());
-            ScopeId::ROOT.provide_context(provider);
-        });
+        provide_desktop_context(&dom, &desktop_context);
 
         WebviewInstance {
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
@@ -226,6 +224,34 @@ impl WebviewInstance {
         }
     }
 
+    /// Replace the app after it panicked and reload the page to render the new one
+    ///
+    /// The new app gets the state of the old one if `restore_state` is true.
+    pub(crate) fn restart(&mut self, dom: VirtualDom, restore_state: bool) {
+        // The old app can be in any state after the panic, so taking the snapshot and dropping it can panic too
+        let snapshot = restore_state
+            .then(|| std::panic::catch_unwind(AssertUnwindSafe(|| self.dom.snapshot())).ok())
+            .flatten();
+        let old = std::mem::replace(&mut self.dom, dom);
+        _ = std::panic::catch_unwind(AssertUnwindSafe(move || drop(old)));
+        if let Some(snapshot) = snapshot {
+            self.dom.restore(snapshot);
+        }
+
+        provide_desktop_context(&self.dom, &self.desktop_context);
+        self.desktop_context
+            .asset_handlers
+            .reset(self.dom.runtime());
+
+        // The page starts over with the new app, which is rebuilt once the page initializes again
+        *self.desktop_context.mutation_state.borrow_mut() = Default::default();
+        self.desktop_context.edit_queue.reset();
+        _ = self
+            .desktop_context
+            .webview
+            .evaluate_script("window.location.reload()");
+    }
+
     pub fn poll_vdom(&mut self) {
         let mut cx = std::task::Context::from_waker(&self.waker);
 
@@ -256,12 +282,32 @@ impl WebviewInstance {
     }
 
     #[cfg(all(feature = "hot-reload", debug_assertions))]
-    pub fn kick_stylsheets(&self) {
-        // run eval in the webview to kick the stylesheets by appending a query string
-        // we should do something less clunky than this
+    pub fn update_asset(&self, path: &std::path::Path, contents: Option<&str>) {
+        // swap the stylesheet, image or script in the page without reloading it
         _ = self
             .desktop_context
             .webview
-            .evaluate_script("window.interpreter.kickAllStylesheetsOnPage()");
+            .evaluate_script(&dioxus_hot_reload::update_asset_script(path, contents));
+    }
+}
+
+/// Give the app access to the window it renders in
+fn provide_desktop_context(dom: &VirtualDom, desktop_context: &DesktopContext) {
+    let provider: Rc<dyn EvalProvider> = Rc::new(DesktopEvalProvider::new(desktop_context.clone()));
+
+    dom.in_runtime(|| {
+        ScopeId::ROOT.provide_context(desktop_context.clone());
+        ScopeId::ROOT.provide_context(provider);
+    });
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+impl Drop for WebviewInstance {
+    fn drop(&mut self) {
+        // The menu goes away with the window so menu actions shouldn't touch it anymore
+        self.desktop_context
+            .shared
+            .menu_actions
+            .remove_menu(self.desktop_context.window.id());
     }
 }
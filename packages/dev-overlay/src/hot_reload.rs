@@ -0,0 +1,34 @@
+use dioxus_lib::prelude::dioxus_core::{hot_reload_error, HotReloadErrorKind};
+use dioxus_lib::prelude::*;
+
+const STYLE: &str = include_str!("./hot_reload.css");
+
+/// A banner with the last change the hot reloader couldn't apply: the rsx parse error, or the change that requires a
+/// rebuild, with the file and line it is in. The banner disappears once a later change is hot reloaded.
+///
+/// [`DevOverlay`](crate::DevOverlay) renders the banner whether it is open or not.
+#[component]
+pub fn HotReloadBanner() -> Element {
+    let error = hot_reload_error()?;
+
+    let title = match error.kind {
+        HotReloadErrorKind::Parse => "Failed to hot reload",
+        HotReloadErrorKind::RebuildRequired => "Rebuild required",
+    };
+    let location = match (error.line, error.column) {
+        (Some(line), Some(column)) => format!("{}:{line}:{column}", error.file),
+        (Some(line), None) => format!("{}:{line}", error.file),
+        _ => error.file.clone(),
+    };
+
+    rsx! {
+        div {
+            class: "dioxus-dev-overlay-error",
+            "data-dioxus-no-highlight": true,
+            style { {STYLE} }
+            strong { "{title}" }
+            span { class: "dioxus-dev-overlay-muted", " {location}" }
+            pre { "{error.message}" }
+        }
+    }
+}
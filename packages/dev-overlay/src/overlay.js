@@ -0,0 +1,45 @@
+// Measures the frame rate while the overlay is open, turns the renderer's update highlighting on and off, and toggles
+// the overlay with ctrl+shift+d. Rust sends `{ open, highlight }` when either changes. This script sends `{ fps }`
+// twice a second while the overlay is open and `{ toggle: true }` when the shortcut is pressed.
+let open = false;
+let measuring = false;
+let frames = 0;
+let sampleStart = 0;
+
+function measureFrame(now) {
+  if (!open) {
+    measuring = false;
+    return;
+  }
+  frames++;
+  if (now - sampleStart >= 500) {
+    dioxus.send({ fps: Math.round((frames * 1000) / (now - sampleStart)) });
+    frames = 0;
+    sampleStart = now;
+  }
+  requestAnimationFrame(measureFrame);
+}
+
+window.addEventListener("keydown", (event) => {
+  if (event.ctrlKey && event.shiftKey && event.key.toLowerCase() === "d") {
+    event.preventDefault();
+    dioxus.send({ toggle: true });
+  }
+});
+
+while (true) {
+  const message = await dioxus.recv();
+  open = message.open;
+  // The interpreter flashes nodes that receive mutations while this is on
+  window.dispatchEvent(
+    new CustomEvent("dioxus-highlight-updates", {
+      detail: open && message.highlight,
+    })
+  );
+  if (open && !measuring) {
+    measuring = true;
+    frames = 0;
+    sampleStart = performance.now();
+    requestAnimationFrame(measureFrame);
+  }
+}
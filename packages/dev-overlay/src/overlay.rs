@@ -0,0 +1,183 @@
+use std::{collections::HashMap, rc::Rc};
+
+use dioxus_lib::prelude::dioxus_core::{hot_reload_count, scope_renders};
+use dioxus_lib::prelude::*;
+use serde_json::json;
+
+use crate::HotReloadBanner;
+
+/// The number of components with the most renders the overlay lists
+const SCOPE_ROWS: usize = 12;
+
+/// The number of recent signal writes the overlay lists
+const WRITE_ROWS: usize = 8;
+
+const SCRIPT: &str = include_str!("./overlay.js");
+const STYLE: &str = include_str!("./overlay.css");
+
+/// The state of the overlay. This is stored in a [`CopyValue`] instead of a signal so the overlay doesn't fill the
+/// signal write log with its own writes.
+#[derive(Default)]
+struct OverlayState {
+    open: bool,
+    /// Whether the renderer flashes nodes that receive mutations while the overlay is open
+    highlight: bool,
+    fps: Option<u64>,
+    /// The script that measures the frame rate and listens for the shortcut
+    script: Option<UseEval>,
+    /// The render count of every scope the last time the overlay rendered
+    renders: HashMap<ScopeId, usize>,
+    /// The hot reload count the last time the overlay rendered
+    hot_reloads: usize,
+}
+
+/// An overlay with the frame rate of the page, the render count of every component, the latest signal writes and the
+/// number of hot reloads the app has applied.
+///
+/// Press `ctrl+shift+d` to open or close the overlay. While it is open, it updates twice a second and highlights
+/// components that rendered since the last update. The renderer also flashes every node that receives a mutation,
+/// colored by the kind of mutation, unless highlighting is turned off in the overlay. The frame rate, flashes and
+/// shortcut need a renderer that can run javascript.
+///
+/// Changes the hot reloader couldn't apply are shown in a [`HotReloadBanner`] even while the overlay is closed.
+#[component]
+pub fn DevOverlay(
+    /// Whether the overlay starts open
+    #[props(default)]
+    open: bool,
+) -> Element {
+    let mut state = use_hook(|| {
+        CopyValue::new(OverlayState {
+            open,
+            highlight: true,
+            ..Default::default()
+        })
+    });
+
+    use_hook(|| {
+        // Servers rendering a fullstack app can't run javascript
+        if try_consume_context::<Rc<dyn EvalProvider>>().is_none() {
+            return;
+        }
+        let update = schedule_update();
+        let mut script = eval(SCRIPT);
+        state.write().script = Some(script);
+        sync_script(state);
+        spawn(async move {
+            while let Ok(message) = script.recv().await {
+                if message["toggle"] == true {
+                    let open = !state.read().open;
+                    state.write().open = open;
+                    sync_script(state);
+                }
+                if let Some(fps) = message["fps"].as_u64() {
+                    state.write().fps = Some(fps);
+                }
+                update();
+            }
+        });
+    });
+
+    let mut current = state.write();
+    if !current.open {
+        return rsx! { HotReloadBanner {} };
+    }
+
+    // Rendering the overlay would count as a render on every update, so leave it and its banner out
+    let overlay = current_scope_id();
+    let mut scopes: Vec<_> = scope_renders()
+        .into_iter()
+        .filter(|scope| Some(scope.id) != overlay && scope.parent != overlay)
+        .map(|scope| {
+            let rendered = current.renders.get(&scope.id) != Some(&scope.renders);
+            (scope, rendered)
+        })
+        .collect();
+    current.renders = scopes
+        .iter()
+        .map(|(scope, _)| (scope.id, scope.renders))
+        .collect();
+    scopes.sort_by_key(|(scope, _)| std::cmp::Reverse(scope.renders));
+    scopes.truncate(SCOPE_ROWS);
+
+    let hot_reloads = hot_reload_count();
+    let hot_reloaded = hot_reloads != current.hot_reloads;
+    current.hot_reloads = hot_reloads;
+
+    let writes: Vec<_> = recent_signal_writes()
+        .into_iter()
+        .rev()
+        .take(WRITE_ROWS)
+        .collect();
+
+    let fps = match current.fps {
+        Some(fps) => format!("{fps} fps"),
+        None => "measuring...".to_string(),
+    };
+
+    let highlight = current.highlight;
+    drop(current);
+
+    rsx! {
+        HotReloadBanner {}
+        div {
+            id: "dioxus-dev-overlay",
+            class: "dioxus-dev-overlay",
+            "data-dioxus-no-highlight": true,
+            style { {STYLE} }
+            header {
+                "Dioxus dev overlay"
+                span { class: "dioxus-dev-overlay-muted", "ctrl+shift+d" }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: highlight,
+                    onchange: move |event| {
+                        state.write().highlight = event.checked();
+                        sync_script(state);
+                        needs_update();
+                    }
+                }
+                "Highlight updates"
+            }
+            h2 { "Frame rate" }
+            "{fps}"
+            h2 { "Renders" }
+            table {
+                for (scope, rendered) in scopes {
+                    tr { key: "{scope.id:?}", class: changed_class(rendered),
+                        td { "{scope.name}" }
+                        td { "{scope.renders}" }
+                    }
+                }
+            }
+            h2 { "Signal writes" }
+            ul {
+                for write in writes {
+                    li { key: "{write.index}",
+                        "{write.origin} "
+                        span { class: "dioxus-dev-overlay-muted", "{write.value_type}" }
+                    }
+                }
+            }
+            h2 { "Hot reload" }
+            span { class: changed_class(hot_reloaded), "{hot_reloads} updates applied" }
+        }
+    }
+}
+
+/// Tell the script whether the overlay is open and whether the renderer should highlight updates
+fn sync_script(state: CopyValue<OverlayState>) {
+    let state = state.read();
+    if let Some(script) = state.script {
+        let _ = script.send(json!({ "open": state.open, "highlight": state.highlight }));
+    }
+}
+
+fn changed_class(changed: bool) -> &'static str {
+    match changed {
+        true => "dioxus-dev-overlay-changed",
+        false => "",
+    }
+}
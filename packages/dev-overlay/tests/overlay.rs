@@ -0,0 +1,77 @@
+#![allow(non_snake_case)]
+
+use dioxus::dioxus_core::{HotReloadError, HotReloadErrorKind};
+use dioxus::prelude::*;
+use dioxus_dev_overlay::prelude::*;
+
+#[test]
+fn closed_overlay_renders_nothing() {
+    fn app() -> Element {
+        rsx! {
+            "hello"
+            DevOverlay {}
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    assert_eq!(dioxus_ssr::render(&dom), "hello");
+}
+
+#[test]
+fn lists_render_counts_without_the_overlay() {
+    fn app() -> Element {
+        rsx! {
+            Counter {}
+            DevOverlay { open: true }
+        }
+    }
+
+    #[component]
+    fn Counter() -> Element {
+        let mut count = use_signal(|| 0);
+        use_hook(move || count += 1);
+        rsx! { "{count}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains("<td>Counter</td><td>"), "{html}");
+    assert!(!html.contains("<td>DevOverlay</td>"));
+    assert!(html.contains("0 updates applied"));
+    assert!(html.contains("i32"), "the write to the counter is listed");
+}
+
+#[test]
+fn banner_shows_hot_reload_errors() {
+    fn app() -> Element {
+        rsx! {
+            "hello"
+            DevOverlay {}
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    dom.set_hot_reload_error(Some(HotReloadError {
+        kind: HotReloadErrorKind::Parse,
+        file: "src/main.rs".to_string(),
+        line: Some(12),
+        column: Some(8),
+        message: "expected `}`".to_string(),
+    }));
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains("Failed to hot reload"), "{html}");
+    assert!(html.contains("src/main.rs:12:8"), "{html}");
+
+    // The next change that hot reloads clears the banner
+    dom.set_hot_reload_error(None);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "hello");
+}
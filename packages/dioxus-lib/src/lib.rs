@@ -33,7 +33,9 @@ pub mod prelude {
 
     #[cfg(feature = "macro")]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, format_args_f, inline_props, render, rsx, Props};
+    pub use dioxus_core_macro::{
+        component, format_args_f, inline_props, render, rsx, rsx_include, Props,
+    };
 
     #[cfg(feature = "macro")]
     pub use dioxus_config_macro::*;
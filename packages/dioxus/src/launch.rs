@@ -131,6 +131,14 @@ impl<Cfg> LaunchBuilder<Cfg, UnsendContext> {
             .push(Box::new(move || Box::new(state.clone())));
         self
     }
+
+    /// Call a hook with every error that is thrown outside of an error boundary.
+    pub fn with_error_hook(
+        self,
+        hook: impl Fn(&dioxus_core::CapturedError) + Clone + 'static,
+    ) -> Self {
+        self.with_context_provider(move || Box::new(ErrorHook::new(hook.clone())))
+    }
 }
 
 impl<Cfg> LaunchBuilder<Cfg, SendContext> {
@@ -149,6 +157,14 @@ impl<Cfg> LaunchBuilder<Cfg, SendContext> {
             .push(Box::new(move || Box::new(state.clone())));
         self
     }
+
+    /// Call a hook with every error that is thrown outside of an error boundary.
+    pub fn with_error_hook(
+        self,
+        hook: impl Fn(&dioxus_core::CapturedError) + Clone + Send + Sync + 'static,
+    ) -> Self {
+        self.with_context_provider(move || Box::new(ErrorHook::new(hook.clone())))
+    }
 }
 
 /// A trait for converting a type into a platform-specific config:
@@ -181,6 +197,25 @@ impl TryIntoConfig<current_platform::Config> for () {
     }
 }
 
+/// A platform config that contains the config for another platform. This is what lets one builder configure every
+/// platform with [`LaunchBuilder::with_web`] and [`LaunchBuilder::with_desktop`]:
+/// - Every config contains itself
+/// - The fullstack config contains the configs of the platforms it renders on
+/// - The current platform config ignores the configs of other enabled platforms
+///
+/// Configuring a platform that isn't enabled, or that the builder can never launch on, is a compile error.
+pub trait ConfigSection<Section> {
+    /// Update the section of the config
+    fn map_section(self, f: impl FnOnce(Section) -> Section) -> Self;
+}
+
+// A config is always a section of itself
+impl<Cfg> ConfigSection<Cfg> for Cfg {
+    fn map_section(self, f: impl FnOnce(Cfg) -> Cfg) -> Self {
+        f(self)
+    }
+}
+
 impl<Cfg: Default + 'static, ContextFn: ?Sized> LaunchBuilder<Cfg, ContextFn> {
     /// Provide a platform-specific config to the builder.
     pub fn with_cfg(mut self, config: impl TryIntoConfig<Cfg>) -> Self {
@@ -188,14 +223,102 @@ impl<Cfg: Default + 'static, ContextFn: ?Sized> LaunchBuilder<Cfg, ContextFn> {
         self
     }
 
+    /// Configure the app when it runs on the web. The config is ignored on other platforms.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { None }
+    /// LaunchBuilder::new()
+    ///     .with_web(|cfg| cfg.rootname("app"))
+    ///     .with_context(1234u32)
+    ///     .launch(app);
+    /// ```
+    #[cfg(feature = "web")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "web")))]
+    pub fn with_web(self, f: impl FnOnce(dioxus_web::Config) -> dioxus_web::Config) -> Self
+    where
+        Cfg: ConfigSection<dioxus_web::Config>,
+    {
+        self.map_config(f)
+    }
+
+    /// Configure the app when it runs on desktop. The config is ignored on other platforms.
+    #[cfg(feature = "desktop")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "desktop")))]
+    pub fn with_desktop(
+        self,
+        f: impl FnOnce(dioxus_desktop::Config) -> dioxus_desktop::Config,
+    ) -> Self
+    where
+        Cfg: ConfigSection<dioxus_desktop::Config>,
+    {
+        self.map_config(f)
+    }
+
+    /// Configure the server and the platforms of a fullstack app.
+    #[cfg(feature = "fullstack")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
+    pub fn with_fullstack(
+        self,
+        f: impl FnOnce(dioxus_fullstack::Config) -> dioxus_fullstack::Config,
+    ) -> Self
+    where
+        Cfg: ConfigSection<dioxus_fullstack::Config>,
+    {
+        self.map_config(f)
+    }
+
+    fn map_config<Section>(mut self, f: impl FnOnce(Section) -> Section) -> Self
+    where
+        Cfg: ConfigSection<Section>,
+    {
+        let config = self.platform_config.take().unwrap_or_default();
+        self.platform_config = Some(config.map_section(f));
+        self
+    }
+
     /// Launch your application.
     pub fn launch(self, app: fn() -> Element) {
         let cfg = self.platform_config.unwrap_or_default();
 
+        #[cfg(all(feature = "dev-overlay", debug_assertions))]
+        let app = with_dev_overlay(app);
+
         (self.launch_fn)(app, self.contexts, cfg)
     }
 }
 
+/// Render the [dev overlay](dioxus_dev_overlay::DevOverlay) next to the app.
+///
+/// The root of the app is a function pointer that can't capture anything, so the app is stored in a static. If an app
+/// was already launched in this process, the new app is launched without the overlay.
+#[cfg(all(feature = "dev-overlay", debug_assertions))]
+fn with_dev_overlay(app: fn() -> Element) -> fn() -> Element {
+    use dioxus_core::{DynamicNode, VComponent};
+    use dioxus_core_macro::rsx;
+
+    static APP: std::sync::Mutex<Option<fn() -> Element>> = std::sync::Mutex::new(None);
+
+    fn root() -> Element {
+        let app = APP
+            .lock()
+            .unwrap()
+            .expect("the app is set before it is launched");
+        let app = DynamicNode::Component(VComponent::new(app, (), "app"));
+        rsx! {
+            {app}
+            dioxus_dev_overlay::DevOverlay {}
+        }
+    }
+
+    let mut stored = APP.lock().unwrap();
+    if stored.is_some() {
+        return app;
+    }
+    *stored = Some(app);
+    root
+}
+
 /// Re-export the platform we expect the user wants
 ///
 /// If multiple platforms are enabled, we use this priority (from highest to lowest):
@@ -218,11 +341,28 @@ mod current_platform {
             )*
         };
     }
-    use crate::prelude::TryIntoConfig;
+    use crate::prelude::{ConfigSection, TryIntoConfig};
 
     #[cfg(feature = "fullstack")]
     pub use dioxus_fullstack::launch::*;
 
+    #[cfg(all(feature = "fullstack", feature = "web"))]
+    impl ConfigSection<::dioxus_web::Config> for ::dioxus_fullstack::Config {
+        fn map_section(self, f: impl FnOnce(::dioxus_web::Config) -> ::dioxus_web::Config) -> Self {
+            self.map_web_cfg(f)
+        }
+    }
+
+    #[cfg(all(feature = "fullstack", feature = "desktop"))]
+    impl ConfigSection<::dioxus_desktop::Config> for ::dioxus_fullstack::Config {
+        fn map_section(
+            self,
+            f: impl FnOnce(::dioxus_desktop::Config) -> ::dioxus_desktop::Config,
+        ) -> Self {
+            self.map_desktop_cfg(f)
+        }
+    }
+
     #[cfg(any(feature = "desktop", feature = "mobile"))]
     if_else_cfg! {
         if not(feature = "fullstack") {
@@ -262,6 +402,14 @@ mod current_platform {
                     None
                 }
             }
+
+            // The fullstack config forwards the web config instead
+            #[cfg(not(feature = "fullstack"))]
+            impl ConfigSection<::dioxus_web::Config> for crate::launch::current_platform::Config {
+                fn map_section(self, _: impl FnOnce(::dioxus_web::Config) -> ::dioxus_web::Config) -> Self {
+                    self
+                }
+            }
         }
     }
 
@@ -6,9 +6,10 @@
 //!
 //! - `signals`: (default) re-exports `dioxus-signals`
 //! - `macro`: (default) re-exports `dioxus-macro`
-//! - `html`: (default) exports `dioxus-html` as the default elements to use in rsx
+//! - `html`: (default) exports `dioxus-html` as the default elements to use in rsx. Renderers that draw something other than html can disable it and define their own elements with [`define_elements!`](dioxus_core::define_elements)
 //! - `hooks`: (default) re-exports `dioxus-hooks`
 //! - `hot-reload`: (default) enables hot rsx reloading in all renderers that support it
+//! - `dev-overlay`: renders an overlay with the frame rate, render counts, signal writes and hot reloads next to the app in debug builds. Toggle it with `ctrl+shift+d`. `dx serve` enables this automatically
 //! - `router`: exports the [router](https://dioxuslabs.com/learn/0.5/router) and enables any router features for the current platform
 //! - `third-party-renderer`: Just disables warnings about no active platform when no renderers are enabled
 //!
@@ -76,7 +77,9 @@ pub mod prelude {
     #[cfg(feature = "macro")]
     #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, format_args_f, inline_props, render, rsx, Props};
+    pub use dioxus_core_macro::{
+        component, format_args_f, inline_props, render, rsx, rsx_include, styled, Props,
+    };
 
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
@@ -61,6 +61,7 @@ use axum::{
     response::IntoResponse,
 };
 use dioxus_lib::prelude::VirtualDom;
+use dioxus_ssr::incremental::{IncrementalRendererError, RenderFreshness};
 use futures_util::Future;
 use http::header::*;
 
@@ -130,6 +131,7 @@ pub trait DioxusRouterExt<S> {
 
     /// Serves the Dioxus application. This will serve a complete server side rendered application.
     /// This will serve static assets, server render the application, register server functions, and integrate with hot reloading.
+    /// If the config has a [`ComponentRegistry`], the components in it are served as HTML fragments at `/render/:component`.
     ///
     /// # Example
     /// ```rust
@@ -171,6 +173,7 @@ where
     fn register_server_fns(mut self) -> Self {
         use http::method::Method;
 
+        let mut versioned_prefixes = std::collections::HashSet::new();
         for (path, method) in server_fn::axum::server_fn_paths() {
             tracing::trace!("Registering server function: {} {}", method, path);
             let handler = move |req| handle_server_fns_inner(path, || {}, req);
@@ -180,6 +183,24 @@ where
                 Method::PUT => self.route(path, put(handler)),
                 _ => todo!(),
             };
+            if let Some((prefix, _)) = crate::version::split_versioned_path(path) {
+                versioned_prefixes.insert(prefix);
+            }
+        }
+
+        // Calls from server functions that use `BatchClient`
+        self = self.route(
+            crate::batch::SERVER_FN_BATCH_PATH,
+            post(handle_server_fn_batch),
+        );
+
+        // Clients built against another version of a server function call a path we don't serve anymore.
+        // Tell them instead of letting the request fall through to the html fallback
+        for prefix in versioned_prefixes {
+            self = self.route(
+                &format!("{prefix}/:version/*path"),
+                any(handle_server_fn_version_mismatch),
+            );
         }
 
         self
@@ -252,6 +273,16 @@ where
                 server = server.forward_cli_hot_reloading();
             }
 
+            // Render the components other pages embed on their own
+            if !cfg.components.is_empty() {
+                server = server.route(
+                    COMPONENT_RENDER_PATH,
+                    get(render_component_handler)
+                        .post(render_component_handler)
+                        .with_state((cfg.clone(), ssr_state.clone())),
+                );
+            }
+
             server.fallback(get(render_handler).with_state((
                 cfg,
                 Arc::new(build_virtual_dom),
@@ -336,6 +367,12 @@ pub async fn render_handler_with_context<F: FnMut(&mut DioxusServerContext)>(
     let mut server_context = DioxusServerContext::new(parts.clone());
     inject_context(&mut server_context);
 
+    // Cached pages are always rendered in full
+    if cfg.stream_suspense && cfg.incremental.is_none() {
+        return render_streaming_response(&cfg, &ssr_state, virtual_dom_factory, server_context)
+            .await;
+    }
+
     match ssr_state
         .render(url, &cfg, move || virtual_dom_factory(), &server_context)
         .await
@@ -344,17 +381,60 @@ pub async fn render_handler_with_context<F: FnMut(&mut DioxusServerContext)>(
             let crate::render::RenderResponse { html, freshness } = rendered;
             let mut response = axum::response::Html::from(html).into_response();
             freshness.write(response.headers_mut());
-            let headers = server_context.response_parts().unwrap().headers.clone();
-            apply_request_parts_to_response(headers, &mut response);
+            let parts = server_context.response_parts().unwrap();
+            // Error pages set the status of the response while rendering
+            *response.status_mut() = parts.status;
+            apply_request_parts_to_response(parts.headers.clone(), &mut response);
+            drop(parts);
             response
         }
-        Err(e) => {
-            tracing::error!("Failed to render page: {}", e);
-            report_err(e).into_response()
+        Err(e) => render_error_response(e),
+    }
+}
+
+/// Render a page with [`SSRState::render_streaming`], sending its status and headers with the first chunk
+async fn render_streaming_response(
+    cfg: &ServeConfig,
+    ssr_state: &SSRState,
+    virtual_dom_factory: Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+    server_context: DioxusServerContext,
+) -> Response<Body> {
+    match ssr_state
+        .render_streaming(cfg, move || virtual_dom_factory(), &server_context)
+        .await
+    {
+        Ok(rendered) => {
+            let mut response = Response::builder()
+                .header(http::header::CONTENT_TYPE, "text/html; charset=utf-8")
+                .body(Body::from_stream(rendered.into_stream()))
+                .unwrap();
+            RenderFreshness::now(None).write(response.headers_mut());
+            let parts = server_context.response_parts().unwrap();
+            // Error pages set the status of the response while rendering
+            *response.status_mut() = parts.status;
+            apply_request_parts_to_response(parts.headers.clone(), &mut response);
+            drop(parts);
+            response
         }
+        Err(e) => render_error_response(e),
     }
 }
 
+/// Respond to a page that failed to render
+fn render_error_response(e: IncrementalRendererError) -> Response<Body> {
+    if crate::render::is_render_pool_saturated(&e) {
+        tracing::warn!("Rejected a request because the server is too busy to render it");
+        return Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .header(RETRY_AFTER, "1")
+            .body(body::Body::new(format!("Error: {}", e)))
+            .unwrap();
+    }
+
+    tracing::error!("Failed to render page: {}", e);
+    report_err(e)
+}
+
 type RenderHandlerExtractor = (
     ServeConfig,
     Arc<dyn Fn() -> VirtualDom + Send + Sync>,
@@ -373,6 +453,77 @@ pub async fn render_handler(
     .await
 }
 
+/// The path components from the [`ComponentRegistry`] are rendered at
+pub const COMPONENT_RENDER_PATH: &str = "/render/:component";
+
+/// Render a component from the [`ComponentRegistry`] of the config to an HTML fragment
+///
+/// The props are the JSON body of a `POST` request or the `props` query parameter of a `GET` request.
+pub async fn render_component_handler(
+    State((cfg, ssr_state)): State<(ServeConfig, SSRState)>,
+    axum::extract::Path(component): axum::extract::Path<String>,
+    request: Request<Body>,
+) -> Response<Body> {
+    let (parts, body) = request.into_parts();
+
+    let props = match parts.method {
+        http::Method::POST => axum::body::to_bytes(body, usize::MAX)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| match bytes.is_empty() {
+                true => Ok(serde_json::Value::Null),
+                false => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+            }),
+        _ => axum::extract::Query::<std::collections::HashMap<String, String>>::try_from_uri(
+            &parts.uri,
+        )
+        .map_err(|e| e.to_string())
+        .and_then(|query| match query.get("props") {
+            Some(props) => serde_json::from_str(props).map_err(|e| e.to_string()),
+            None => Ok(serde_json::Value::Null),
+        }),
+    };
+    let props = match props {
+        Ok(props) => props,
+        Err(error) => return (StatusCode::BAD_REQUEST, error).into_response(),
+    };
+
+    let parts = Arc::new(tokio::sync::RwLock::new(parts));
+    let server_context = DioxusServerContext::new(parts);
+
+    match ssr_state
+        .render_component(&cfg, &component, props, &server_context)
+        .await
+    {
+        Ok(rendered) => {
+            let mut response = axum::response::Html::from(rendered.html).into_response();
+            let parts = server_context.response_parts().unwrap();
+            *response.status_mut() = parts.status;
+            apply_request_parts_to_response(parts.headers.clone(), &mut response);
+            drop(parts);
+            response
+        }
+        Err(e @ ComponentRenderError::NotFound(_)) => {
+            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+        }
+        Err(e @ ComponentRenderError::InvalidProps(_)) => {
+            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+        }
+        Err(ComponentRenderError::Render(e)) if crate::render::is_render_pool_saturated(&e) => {
+            tracing::warn!("Rejected a request because the server is too busy to render it");
+            Response::builder()
+                .status(StatusCode::SERVICE_UNAVAILABLE)
+                .header(RETRY_AFTER, "1")
+                .body(body::Body::new(format!("Error: {}", e)))
+                .unwrap()
+        }
+        Err(e) => {
+            tracing::error!("Failed to render component {component}: {}", e);
+            report_err(e)
+        }
+    }
+}
+
 fn report_err<E: std::fmt::Display>(e: E) -> Response<axum::body::Body> {
     Response::builder()
         .status(StatusCode::INTERNAL_SERVER_ERROR)
@@ -380,6 +531,92 @@ fn report_err<E: std::fmt::Display>(e: E) -> Response<axum::body::Body> {
         .unwrap()
 }
 
+/// Respond to a call to a server function path that isn't registered on this server
+async fn handle_server_fn_version_mismatch(req: Request<Body>) -> Response<Body> {
+    use server_fn::error::{NoCustomError, ServerFnErrorSerde};
+
+    let path = req.uri().path();
+    if crate::version::split_versioned_path(path).is_none() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    tracing::warn!("A client called the server function {path} which this server doesn't serve. The client is likely out of date");
+    let error: ServerFnError<NoCustomError> =
+        crate::version::ServerFnVersionMismatch::new(path).into_error();
+    (StatusCode::CONFLICT, error.ser().unwrap_or_default()).into_response()
+}
+
+/// Run a batch of server function calls sent by `BatchClient` concurrently and answer with all of their responses
+async fn handle_server_fn_batch(req: Request<Body>) -> Response<Body> {
+    use crate::batch::{BatchResponse, BatchedCall};
+
+    let (parts, body) = req.into_parts();
+    let calls: Vec<BatchedCall> = match axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| ciborium::from_reader(bytes.as_ref()).map_err(|e| e.to_string()))
+    {
+        Ok(calls) => calls,
+        Err(error) => return (StatusCode::BAD_REQUEST, error).into_response(),
+    };
+
+    let responses = calls.into_iter().map(|call| {
+        // Every call sees the headers of the batch, like the cookies the server function authenticates with
+        let mut req = Request::new(Body::from(call.body));
+        *req.headers_mut() = parts.headers.clone();
+        req.headers_mut().remove(CONTENT_LENGTH);
+        if let Ok(accepts) = HeaderValue::from_str(&call.accepts) {
+            req.headers_mut().insert(ACCEPT, accepts);
+        }
+        if let Ok(content_type) = HeaderValue::from_str(&call.content_type) {
+            req.headers_mut().insert(CONTENT_TYPE, content_type);
+        }
+        let uri = match call.get {
+            true => format!("{}?{}", call.path, call.query),
+            false => call.path.clone(),
+        };
+        *req.method_mut() = match call.get {
+            true => http::Method::GET,
+            false => http::Method::POST,
+        };
+        *req.version_mut() = parts.version;
+        *req.uri_mut() = uri.parse().unwrap_or_default();
+
+        async move {
+            let res = handle_server_fns_inner(&call.path, || {}, req)
+                .await
+                .into_response();
+            let status = res.status();
+            let location = res
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .map_or_else(|| call.path.clone(), str::to_string);
+            let redirect = res
+                .headers()
+                .contains_key(server_fn::redirect::REDIRECT_HEADER);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+                .await
+                .unwrap_or_default();
+
+            BatchResponse {
+                status: status.as_u16(),
+                status_text: status.canonical_reason().unwrap_or_default().to_string(),
+                location,
+                redirect,
+                body,
+            }
+        }
+    });
+    let responses = futures_util::future::join_all(responses).await;
+
+    let mut body = Vec::new();
+    if let Err(error) = ciborium::into_writer(&responses, &mut body) {
+        return report_err(error);
+    }
+    ([(CONTENT_TYPE, "application/cbor")], body).into_response()
+}
+
 /// A handler for Dioxus server functions. This will run the server function and return the result.
 async fn handle_server_fns_inner(
     path: &str,
@@ -409,8 +646,12 @@ async fn handle_server_fns_inner(
                 .unwrap_or(false);
             let referrer = req.headers().get(REFERER).cloned();
 
+            // If the client goes away, the handler is dropped before the server fn finishes and the token is cancelled
+            let cancel_on_drop = server_context.cancellation_token().drop_guard();
+
             // actually run the server fn (which may use the server context)
             let mut res = ProvideServerContext::new(service.run(req), server_context.clone()).await;
+            cancel_on_drop.disarm();
 
             // it it accepts text/html (i.e., is a plain form post) and doesn't already have a
             // Location set, then redirect to Referer
@@ -472,3 +713,110 @@ async fn handle_server_fns_inner(
         future().await
     }
 }
+
+#[tokio::test]
+async fn saturated_servers_ask_clients_to_retry() {
+    use std::time::Duration;
+
+    static RELEASE: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+    fn waits() -> dioxus_lib::prelude::Element {
+        use dioxus_lib::prelude::*;
+        use_server_future(|| RELEASE.notified())?;
+        rsx! { "waited" }
+    }
+
+    // Only one page can render at a time
+    let cfg = ServeConfigBuilder::new()
+        .index_html(r#"<html><body><div id="main"></div></body></html>"#.to_string())
+        .render_threads(1)
+        .render_queue_size(0)
+        .build();
+    let state = (
+        |_: &mut DioxusServerContext| {},
+        cfg.clone(),
+        SSRState::new(&cfg),
+        Arc::new(|| VirtualDom::new(waits)) as Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+    );
+    let request = || Request::builder().uri("/").body(Body::empty()).unwrap();
+
+    let first = render_handler_with_context(State(state.clone()), request());
+    tokio::pin!(first);
+    tokio::select! {
+        _ = &mut first => panic!("the page waits for the server future"),
+        _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+    }
+
+    let busy = render_handler_with_context(State(state), request())
+        .await
+        .into_response();
+    assert_eq!(busy.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(busy.headers()[RETRY_AFTER], "1");
+
+    RELEASE.notify_one();
+    assert_eq!(first.await.into_response().status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn streams_suspense_boundaries_as_they_resolve() {
+    use dioxus_lib::prelude::*;
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    static RELEASE: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+    fn app() -> Element {
+        rsx! {
+            h1 { "title" }
+            SuspenseBoundary { fallback: rsx! { "loading" }, Waits {} }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn Waits() -> Element {
+        use_server_future(|| RELEASE.notified())?;
+        rsx! { p { "waited" } }
+    }
+
+    let cfg = ServeConfigBuilder::new()
+        .index_html(r#"<html><body><div id="main"></div></body></html>"#.to_string())
+        .render_threads(1)
+        .stream_suspense(true)
+        .build();
+    let state = (
+        |_: &mut DioxusServerContext| {},
+        cfg.clone(),
+        SSRState::new(&cfg),
+        Arc::new(|| VirtualDom::new(app)) as Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+    );
+    let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+
+    // The response starts before the server future resolves
+    let response = tokio::time::timeout(
+        Duration::from_secs(5),
+        render_handler_with_context(State(state), request),
+    )
+    .await
+    .expect("the shell is sent while the boundary waits")
+    .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut body = response.into_body().into_data_stream();
+    let shell = String::from_utf8(body.next().await.unwrap().unwrap().to_vec()).unwrap();
+    assert!(shell.starts_with(r#"<html><body><div id="main">"#));
+    assert!(shell.contains("title"));
+    assert!(shell.contains(r#"<div id="ds-"#));
+    assert!(shell.contains("loading"));
+    assert!(!shell.contains("waited"));
+
+    RELEASE.notify_one();
+    let mut rest = String::new();
+    while let Some(chunk) = body.next().await {
+        rest.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+    }
+    let resolved = rest.find("<p").unwrap();
+    assert!(rest[..resolved].contains("-resolved\">"));
+    assert!(rest[resolved..].contains("waited"));
+    assert!(rest.contains("__dioxus_swap_suspense("));
+    assert!(rest.ends_with("</div></body></html>"));
+}
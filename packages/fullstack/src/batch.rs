@@ -0,0 +1,333 @@
+//! Send the server function calls a page makes in the same tick as one request
+//!
+//! Server functions that opt into [`BatchClient`] don't send their request right away. They queue it and give the
+//! other tasks that are ready a chance to run first. The first call to resume sends every queued call to
+//! [`SERVER_FN_BATCH_PATH`] in one request. The server runs the calls concurrently and answers with all of the
+//! responses, which are handed back to the calls that made them.
+
+use bytes::Bytes;
+use futures_channel::oneshot;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    client::Client,
+    error::{NoCustomError, ServerFnError},
+    request::ClientReq,
+    response::ClientRes,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+/// The path batched server function calls are sent to
+pub const SERVER_FN_BATCH_PATH: &str = "/api/_batch";
+
+/// The content type of batched calls and responses
+const BATCH_CONTENT_TYPE: &str = "application/cbor";
+
+/// The client server functions use when they don't batch their calls
+#[cfg(feature = "desktop")]
+pub(crate) type DirectClient = server_fn::client::reqwest::ReqwestClient;
+#[cfg(not(feature = "desktop"))]
+pub(crate) type DirectClient = server_fn::client::browser::BrowserClient;
+
+/// A [`Client`] that batches the server function calls issued in the same tick into a single request.
+///
+/// Pages that call many small server functions when they load send one request instead of one per call. Opt in with
+/// the `client` argument of the server macro. The server side of the batch is registered with the rest of the
+/// server functions:
+/// ```rust,ignore
+/// #[server(client = BatchClient)]
+/// pub async fn get_user(id: u32) -> Result<User, ServerFnError> {
+///     load_user(id).await
+/// }
+/// ```
+///
+/// Calls with form data, multipart or streaming bodies can't be batched. Calls that are alone in their tick are sent
+/// without batching.
+pub struct BatchClient;
+
+impl<CustErr> Client<CustErr> for BatchClient {
+    type Request = BatchRequest;
+    type Response = BatchResponse;
+
+    async fn send(req: Self::Request) -> Result<Self::Response, ServerFnError<CustErr>> {
+        let (sender, receiver) = oneshot::channel();
+        QUEUE.lock().unwrap().push((req.0.clone(), sender));
+
+        // Let the other tasks that are ready queue their calls before the batch is sent
+        YieldNow(false).await;
+        flush_queue().await;
+
+        let response = match receiver.await {
+            Ok(response) => response,
+            // The task that sent the batch was dropped before it got the responses
+            Err(_) => send_direct(req.0).await,
+        };
+        response.map_err(ServerFnError::Request)
+    }
+}
+
+/// The calls waiting to be sent, with the channels their responses are sent back on
+#[allow(clippy::type_complexity)]
+static QUEUE: Mutex<Vec<(BatchedCall, oneshot::Sender<Result<BatchResponse, String>>)>> =
+    Mutex::new(Vec::new());
+
+/// Send every queued call. If another call already sent the queue, this does nothing
+async fn flush_queue() {
+    let queued = std::mem::take(&mut *QUEUE.lock().unwrap());
+    let (calls, senders): (Vec<_>, Vec<_>) = queued.into_iter().unzip();
+
+    let responses = match calls.len() {
+        0 => return,
+        1 => vec![send_direct(calls.into_iter().next().unwrap()).await],
+        _ => match send_batch(&calls).await {
+            Ok(responses) if responses.len() == calls.len() => {
+                responses.into_iter().map(Ok).collect()
+            }
+            Ok(_) => {
+                let error = "the server answered a batch with the wrong number of responses";
+                vec![Err(error.to_string()); calls.len()]
+            }
+            Err(error) => vec![Err(error); calls.len()],
+        },
+    };
+
+    for (sender, response) in senders.into_iter().zip(responses) {
+        let _ = sender.send(response);
+    }
+}
+
+/// Send one call as a normal server function request
+async fn send_direct(call: BatchedCall) -> Result<BatchResponse, String> {
+    type Request = <DirectClient as Client<NoCustomError>>::Request;
+
+    let BatchedCall {
+        path,
+        get,
+        accepts,
+        content_type,
+        query,
+        body,
+    } = call;
+    let request: Result<Request, ServerFnError> = match get {
+        true => Request::try_new_get(&path, &accepts, &content_type, &query),
+        false => Request::try_new_post_bytes(&path, &accepts, &content_type, body),
+    };
+    let response =
+        <DirectClient as Client<NoCustomError>>::send(request.map_err(|e| e.to_string())?)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    read_response(response).await
+}
+
+/// Send several calls to the batch endpoint
+async fn send_batch(calls: &[BatchedCall]) -> Result<Vec<BatchResponse>, String> {
+    type Request = <DirectClient as Client<NoCustomError>>::Request;
+
+    let mut body = Vec::new();
+    ciborium::into_writer(calls, &mut body).map_err(|e| e.to_string())?;
+    let request: Result<Request, ServerFnError> = Request::try_new_post_bytes(
+        SERVER_FN_BATCH_PATH,
+        BATCH_CONTENT_TYPE,
+        BATCH_CONTENT_TYPE,
+        body.into(),
+    );
+    let response =
+        <DirectClient as Client<NoCustomError>>::send(request.map_err(|e| e.to_string())?)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let response = read_response(response).await?;
+    if response.status != 200 {
+        return Err(format!(
+            "the server rejected a batch of server function calls: {} {}",
+            response.status, response.status_text
+        ));
+    }
+    ciborium::from_reader(response.body.as_ref()).map_err(|e| e.to_string())
+}
+
+/// Read a response of the underlying client into the form batched responses are returned in
+async fn read_response(response: impl ClientRes<NoCustomError>) -> Result<BatchResponse, String> {
+    let status = response.status();
+    let status_text = response.status_text();
+    let location = response.location();
+    let redirect = response.has_redirect();
+    let body = response.try_into_bytes().await.map_err(|e| e.to_string())?;
+
+    Ok(BatchResponse {
+        status,
+        status_text,
+        location,
+        redirect,
+        body,
+    })
+}
+
+/// A future that is pending once, so the tasks the executor already has ready run before it resumes
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// One server function call in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BatchedCall {
+    /// The path of the server function
+    pub(crate) path: String,
+    /// Whether the call is a `GET` request with its arguments in the query. Other calls are `POST` requests
+    pub(crate) get: bool,
+    pub(crate) accepts: String,
+    pub(crate) content_type: String,
+    pub(crate) query: String,
+    pub(crate) body: Bytes,
+}
+
+/// A server function call that is sent with the other calls in its tick by [`BatchClient`]
+#[derive(Debug)]
+pub struct BatchRequest(BatchedCall);
+
+impl BatchRequest {
+    fn new(path: &str, get: bool, accepts: &str, content_type: &str) -> Self {
+        Self(BatchedCall {
+            path: path.to_string(),
+            get,
+            accepts: accepts.to_string(),
+            content_type: content_type.to_string(),
+            query: String::new(),
+            body: Bytes::new(),
+        })
+    }
+}
+
+fn unbatchable<CustErr>(kind: &str) -> ServerFnError<CustErr> {
+    ServerFnError::Request(format!(
+        "server functions with {kind} bodies can't be batched"
+    ))
+}
+
+impl<CustErr> ClientReq<CustErr> for BatchRequest {
+    type FormData = ();
+
+    fn try_new_get(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let mut request = Self::new(path, true, accepts, content_type);
+        request.0.query = query.to_string();
+        Ok(request)
+    }
+
+    fn try_new_post(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let mut request = Self::new(path, false, accepts, content_type);
+        request.0.body = body.into();
+        Ok(request)
+    }
+
+    fn try_new_post_bytes(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let mut request = Self::new(path, false, accepts, content_type);
+        request.0.body = body;
+        Ok(request)
+    }
+
+    fn try_new_post_form_data(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Err(unbatchable("form data"))
+    }
+
+    fn try_new_multipart(
+        _path: &str,
+        _accepts: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Err(unbatchable("multipart"))
+    }
+
+    fn try_new_streaming(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: impl Stream<Item = Bytes> + Send + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Err(unbatchable("streaming"))
+    }
+}
+
+/// The response to one server function call in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub(crate) status: u16,
+    pub(crate) status_text: String,
+    /// The `Location` header of the response, or the path of the call if there is none
+    pub(crate) location: String,
+    /// Whether the server function redirected
+    pub(crate) redirect: bool,
+    pub(crate) body: Bytes,
+}
+
+impl<CustErr> ClientRes<CustErr> for BatchResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        String::from_utf8(self.body.to_vec())
+            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        Ok(self.body)
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(futures_util::stream::once(async move { Ok(self.body) }))
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn status_text(&self) -> String {
+        self.status_text.clone()
+    }
+
+    fn location(&self) -> String {
+        self.location.clone()
+    }
+
+    fn has_redirect(&self) -> bool {
+        self.redirect
+    }
+}
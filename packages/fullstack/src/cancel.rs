@@ -0,0 +1,165 @@
+//! Server function calls that stop when they are dropped or take too long
+//!
+//! Dropping the future of a server function call stops waiting for the response, but the browser keeps the `fetch`
+//! going and the server keeps running the server function. Server functions that opt into [`AbortableClient`] abort
+//! the `fetch` when their call is dropped. The server notices the request went away and cancels the
+//! `cancellation_token` of the server context, which long running server functions can poll to stop early.
+//!
+//! [`ServerFnCallExt::with_timeout`] drops a call that takes too long, which is useful for search-as-you-type against
+//! a slow endpoint.
+
+use crate::batch::DirectClient;
+use server_fn::{client::Client, error::ServerFnError};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// A [`Client`] that aborts the request when the call is dropped.
+///
+/// In the browser, the request is sent with an `AbortController` that is aborted if the future of the call is dropped
+/// before the response arrives. Native clients already close the connection when the call is dropped. Opt in with the
+/// `client` argument of the server macro:
+/// ```rust,ignore
+/// #[server(client = AbortableClient)]
+/// pub async fn search(query: String) -> Result<Vec<String>, ServerFnError> {
+///     let cancelled = server_context().cancellation_token();
+///     tokio::select! {
+///         results = run_search(&query) => Ok(results),
+///         _ = cancelled.cancelled() => Ok(Vec::new()),
+///     }
+/// }
+/// ```
+pub struct AbortableClient;
+
+impl<CustErr> Client<CustErr> for AbortableClient {
+    type Request = <DirectClient as Client<CustErr>>::Request;
+    type Response = <DirectClient as Client<CustErr>>::Response;
+
+    #[cfg(all(feature = "web", not(feature = "desktop")))]
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send {
+        send_wrapper::SendWrapper::new(async move {
+            let controller = web_sys::AbortController::new()
+                .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
+            let request: web_sys::Request = req.into();
+            let mut init = web_sys::RequestInit::new();
+            init.signal(Some(&controller.signal()));
+            let request = web_sys::Request::new_with_request_and_init(&request, &init)
+                .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
+
+            // Abort the request if this future is dropped before it finishes
+            let abort = AbortOnDrop(Some(controller));
+            let request = server_fn::request::browser::Request::from(request);
+            let response = <DirectClient as Client<CustErr>>::send(request.into()).await;
+            abort.disarm();
+            response
+        })
+    }
+
+    #[cfg(not(all(feature = "web", not(feature = "desktop"))))]
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send {
+        <DirectClient as Client<CustErr>>::send(req)
+    }
+}
+
+/// Aborts the controller when it is dropped, unless the request finished
+#[cfg(all(feature = "web", not(feature = "desktop")))]
+struct AbortOnDrop(Option<web_sys::AbortController>);
+
+#[cfg(all(feature = "web", not(feature = "desktop")))]
+impl AbortOnDrop {
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+#[cfg(all(feature = "web", not(feature = "desktop")))]
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let Some(controller) = self.0.take() {
+            controller.abort();
+        }
+    }
+}
+
+/// Extension methods for the futures returned by server function calls
+pub trait ServerFnCallExt<T, CustErr>:
+    Future<Output = Result<T, ServerFnError<CustErr>>> + Sized
+{
+    /// Fail the call with [`ServerFnError::Request`] if it doesn't finish within `duration`.
+    ///
+    /// The call is dropped when it times out, so calls that use [`AbortableClient`] abort their request.
+    /// ```rust,ignore
+    /// let results = search(query).with_timeout(Duration::from_millis(500)).await;
+    /// ```
+    fn with_timeout(self, duration: Duration) -> WithTimeout<Self> {
+        WithTimeout {
+            call: Some(Box::pin(self)),
+            timer: Box::pin(sleep(duration)),
+            duration,
+        }
+    }
+}
+
+impl<T, CustErr, F> ServerFnCallExt<T, CustErr> for F where
+    F: Future<Output = Result<T, ServerFnError<CustErr>>>
+{
+}
+
+/// A server function call that fails if it takes too long. Created by [`ServerFnCallExt::with_timeout`]
+pub struct WithTimeout<F> {
+    call: Option<Pin<Box<F>>>,
+    timer: Pin<Box<dyn Future<Output = ()>>>,
+    duration: Duration,
+}
+
+impl<T, CustErr, F> Future for WithTimeout<F>
+where
+    F: Future<Output = Result<T, ServerFnError<CustErr>>>,
+{
+    type Output = Result<T, ServerFnError<CustErr>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(call) = self.call.as_mut() else {
+            panic!("a server function call was polled after it finished");
+        };
+        if let Poll::Ready(result) = call.as_mut().poll(cx) {
+            self.call = None;
+            return Poll::Ready(result);
+        }
+        if self.timer.as_mut().poll(cx).is_ready() {
+            // Drop the call right away so the request is aborted
+            self.call = None;
+            return Poll::Ready(Err(ServerFnError::Request(format!(
+                "the server function call timed out after {:?}",
+                self.duration
+            ))));
+        }
+        Poll::Pending
+    }
+}
+
+/// Wait for the duration using the timers of the browser
+#[cfg(target_arch = "wasm32")]
+fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    gloo_timers::future::sleep(duration)
+}
+
+/// Wait for the duration on a background thread, so the timer works with any executor
+#[cfg(not(target_arch = "wasm32"))]
+fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    let (sender, receiver) = futures_channel::oneshot::channel();
+    std::thread::spawn(move || {
+        std::thread::sleep(duration);
+        let _ = sender.send(());
+    });
+    async move {
+        let _ = receiver.await;
+    }
+}
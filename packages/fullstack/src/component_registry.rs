@@ -0,0 +1,184 @@
+//! A registry of components the server can render on their own so other pages can embed them
+
+use std::{collections::HashMap, sync::Arc};
+
+use dioxus_lib::prelude::*;
+use dioxus_ssr::incremental::{IncrementalRendererError, WrapBody};
+use serde::de::DeserializeOwned;
+
+use crate::prelude::*;
+
+type VirtualDomFactory = Box<dyn FnOnce() -> VirtualDom + Send + Sync>;
+
+type ComponentFactory =
+    Arc<dyn Fn(serde_json::Value) -> Result<VirtualDomFactory, serde_json::Error> + Send + Sync>;
+
+/// The components the server renders to HTML fragments at `/render/:component`
+///
+/// Other services and pages, even ones that don't use dioxus, can embed the fragments. Register the components with
+/// [`ServeConfigBuilder::components`] and request them with the props as JSON, either in the body of a `POST` request
+/// or in the `props` query parameter of a `GET` request. Components without props can be requested without any.
+///
+/// The fragment contains the rendered component in an element with the id `dioxus-component-{name}`, the data of the
+/// server functions that ran while rendering, and the props in a JSON script with the id
+/// `dioxus-component-{name}-props`. The client reads the data of the server functions from the page, so only embed
+/// one fragment per page if you hydrate it.
+///
+/// # Example
+/// ```rust
+/// # use dioxus_lib::prelude::*;
+/// # use dioxus_fullstack::prelude::*;
+/// #[derive(Props, Clone, PartialEq, serde::Deserialize)]
+/// struct CounterProps {
+///     start: i32,
+/// }
+///
+/// #[allow(non_snake_case)]
+/// fn Counter(props: CounterProps) -> Element {
+///     let mut count = use_signal(|| props.start);
+///     rsx! { button { onclick: move |_| count += 1, "{count}" } }
+/// }
+///
+/// let cfg = ServeConfig::builder()
+///     .index_html(r#"<div id="main"></div>"#.to_string())
+///     .components(ComponentRegistry::new().register("counter", Counter))
+///     .build();
+/// ```
+///
+/// To hydrate the fragment, the client launches the same component with the props from the fragment into its root
+/// element:
+/// ```rust, ignore
+/// let props = web_sys::window()
+///     .unwrap()
+///     .document()
+///     .unwrap()
+///     .get_element_by_id("dioxus-component-counter-props")
+///     .and_then(|props| props.text_content())
+///     .unwrap();
+/// let props: CounterProps = serde_json::from_str(&props).unwrap();
+/// dioxus_web::launch::launch_virtual_dom(
+///     VirtualDom::new_with_props(Counter, props),
+///     dioxus_web::Config::new().rootname("dioxus-component-counter").hydrate(true),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct ComponentRegistry {
+    components: HashMap<String, ComponentFactory>,
+}
+
+impl ComponentRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a component under a name. The props of the component are deserialized from the JSON of the request.
+    pub fn register<P: Clone + DeserializeOwned + Send + Sync + 'static, M: 'static>(
+        mut self,
+        name: impl Into<String>,
+        component: impl ComponentFunction<P, M> + Send + Sync,
+    ) -> Self {
+        self.components.insert(
+            name.into(),
+            Arc::new(move |props| {
+                let props: P = serde_json::from_value(props)?;
+                let component = component.clone();
+                Ok(Box::new(move || {
+                    VirtualDom::new_with_props(component, props)
+                }))
+            }),
+        );
+        self
+    }
+
+    /// Check if a component is registered under a name
+    pub fn contains(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// Check if there are no components in the registry
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Create the virtual dom that renders a component with the props
+    pub(crate) fn virtual_dom_factory(
+        &self,
+        name: &str,
+        props: serde_json::Value,
+    ) -> Result<VirtualDomFactory, ComponentRenderError> {
+        let factory = self
+            .components
+            .get(name)
+            .ok_or_else(|| ComponentRenderError::NotFound(name.to_string()))?;
+        factory(props).map_err(ComponentRenderError::InvalidProps)
+    }
+}
+
+/// An error that occurred while rendering a component from the [`ComponentRegistry`]
+#[derive(Debug)]
+pub enum ComponentRenderError {
+    /// No component is registered under the name
+    NotFound(String),
+
+    /// The props don't match the props of the component
+    InvalidProps(serde_json::Error),
+
+    /// The component failed to render
+    Render(IncrementalRendererError),
+}
+
+impl std::fmt::Display for ComponentRenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentRenderError::NotFound(name) => {
+                write!(f, "No component is registered under the name {name}")
+            }
+            ComponentRenderError::InvalidProps(err) => {
+                write!(f, "Failed to deserialize the props of the component: {err}")
+            }
+            ComponentRenderError::Render(err) => write!(f, "Failed to render the component: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ComponentRenderError {}
+
+/// The template that wraps a component rendered on its own. The root element is followed by the data needed to hydrate it.
+pub(crate) struct ComponentFragmentTemplate {
+    pub(crate) name: String,
+    pub(crate) props: serde_json::Value,
+    pub(crate) server_context: DioxusServerContext,
+}
+
+impl WrapBody for ComponentFragmentTemplate {
+    fn render_before_body<R: std::io::Write>(
+        &self,
+        to: &mut R,
+    ) -> Result<(), IncrementalRendererError> {
+        let name = &self.name;
+        write!(
+            to,
+            r#"<div id="dioxus-component-{name}" data-dioxus-component="{name}">"#
+        )?;
+        Ok(())
+    }
+
+    fn render_after_body<R: std::io::Write>(
+        &self,
+        to: &mut R,
+    ) -> Result<(), IncrementalRendererError> {
+        to.write_all(b"</div>")?;
+
+        crate::render::write_server_data(&self.server_context, to)?;
+
+        // The props end up in a script tag, so they can't close it
+        let props = self.props.to_string().replace("</", r"<\/");
+        let name = &self.name;
+        write!(
+            to,
+            r#"<script type="application/json" id="dioxus-component-{name}-props">{props}</script>"#
+        )?;
+        Ok(())
+    }
+}
@@ -110,6 +110,28 @@ impl Config {
         Self { mobile_cfg, ..self }
     }
 
+    /// Update the web config.
+    #[cfg(feature = "web")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "web")))]
+    pub fn map_web_cfg(self, f: impl FnOnce(dioxus_web::Config) -> dioxus_web::Config) -> Self {
+        let web_cfg = f(self.web_cfg);
+        Self { web_cfg, ..self }
+    }
+
+    /// Update the desktop config.
+    #[cfg(feature = "desktop")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "desktop")))]
+    pub fn map_desktop_cfg(
+        self,
+        f: impl FnOnce(dioxus_desktop::Config) -> dioxus_desktop::Config,
+    ) -> Self {
+        let desktop_cfg = f(self.desktop_cfg);
+        Self {
+            desktop_cfg,
+            ..self
+        }
+    }
+
     #[cfg(feature = "server")]
     #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
     /// Launch a server application
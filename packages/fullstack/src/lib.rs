@@ -12,12 +12,18 @@ mod html_storage;
 #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
 mod axum_adapter;
 
+mod batch;
+mod cancel;
 mod config;
 mod hooks;
 pub mod launch;
+mod version;
 
 pub use config::*;
 
+#[cfg(feature = "server")]
+mod component_registry;
+
 #[cfg(feature = "server")]
 mod render;
 
@@ -38,7 +44,11 @@ pub mod prelude {
 
     #[cfg(feature = "server")]
     #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
-    pub use crate::render::{FullstackHTMLTemplate, SSRState};
+    pub use crate::component_registry::{ComponentRegistry, ComponentRenderError};
+
+    #[cfg(feature = "server")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+    pub use crate::render::{FullstackHTMLTemplate, SSRState, StreamingRenderResponse};
 
     #[cfg(feature = "router")]
     #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
@@ -63,6 +73,9 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
     pub use dioxus_ssr::incremental::{IncrementalRenderer, IncrementalRendererConfig};
 
+    pub use crate::batch::{BatchClient, BatchRequest, BatchResponse, SERVER_FN_BATCH_PATH};
+    pub use crate::cancel::{AbortableClient, ServerFnCallExt, WithTimeout};
+    pub use crate::version::ServerFnVersionMismatch;
     pub use dioxus_server_macro::*;
     pub use server_fn::{self, ServerFn as _, ServerFnError};
 }
@@ -1,193 +1,383 @@
 //! A shared pool of renderers for efficient server side rendering.
 use crate::render::dioxus_core::NoOpMutations;
 use dioxus_ssr::{
-    incremental::{RenderFreshness, WrapBody},
+    incremental::{IncrementalRendererError, RenderFreshness, WrapBody},
     Renderer,
 };
-use std::future::Future;
+use futures_util::future::LocalBoxFuture;
 use std::sync::Arc;
-use std::sync::RwLock;
-use tokio::task::block_in_place;
-use tokio::task::JoinHandle;
+use tokio::sync::Semaphore;
 
 use crate::prelude::*;
 use dioxus_lib::prelude::*;
 
-fn spawn_platform<Fut>(f: impl FnOnce() -> Fut + Send + 'static) -> JoinHandle<Fut::Output>
-where
-    Fut: Future + 'static,
-    Fut::Output: Send + 'static,
-{
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        tokio::task::spawn_blocking(move || {
-            tokio::runtime::Runtime::new()
-                .expect("couldn't spawn runtime")
-                .block_on(f())
-        })
+/// The renderer a render thread keeps between requests. Reusing it keeps the templates it has already seen cached.
+enum PoolRenderer {
+    Renderer(Renderer),
+    Incremental(dioxus_ssr::incremental::IncrementalRenderer),
+}
+
+impl PoolRenderer {
+    fn new(cfg: &ServeConfig) -> Self {
+        match &cfg.incremental {
+            Some(incremental) => Self::Incremental(incremental_pre_renderer(incremental)),
+            None => Self::Renderer(pre_renderer()),
+        }
     }
-    #[cfg(target_arch = "wasm32")]
-    {
-        tokio::task::spawn_local(f())
+}
+
+/// Rendering a page on a render thread. The job borrows the renderer of the thread and hands it back when it is done.
+type RenderJob = Box<dyn FnOnce(PoolRenderer) -> LocalBoxFuture<'static, PoolRenderer> + Send>;
+
+/// The error a render returns when the pool is already rendering as many requests as it allows
+#[derive(Debug)]
+struct RenderPoolSaturated;
+
+impl std::fmt::Display for RenderPoolSaturated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("The server is already rendering as many pages as it allows")
     }
 }
 
-enum SsrRendererPool {
-    Renderer(RwLock<Vec<Renderer>>),
-    Incremental(RwLock<Vec<dioxus_ssr::incremental::IncrementalRenderer>>),
+impl std::error::Error for RenderPoolSaturated {}
+
+/// Check if a render failed because the server was too busy to render the page
+pub(crate) fn is_render_pool_saturated(err: &IncrementalRendererError) -> bool {
+    matches!(err, IncrementalRendererError::Other(err) if err.is::<RenderPoolSaturated>())
+}
+
+/// A pool of threads that render pages.
+///
+/// Every thread runs its own single threaded runtime and renderers, so independent requests render in parallel without
+/// setting up a runtime per request. Each render is a local task on its thread, so a render that waits for a server
+/// future lets the thread render other pages in the meantime.
+struct SsrRendererPool {
+    #[cfg(not(target_arch = "wasm32"))]
+    jobs: tokio::sync::mpsc::UnboundedSender<RenderJob>,
+    #[cfg(target_arch = "wasm32")]
+    cfg: ServeConfig,
+    // One permit for every request that is rendering or waiting for a render thread
+    permits: Arc<Semaphore>,
 }
 
 impl SsrRendererPool {
-    async fn render_to(
-        &self,
-        cfg: &ServeConfig,
-        route: String,
-        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
-        server_context: &DioxusServerContext,
-    ) -> Result<(RenderFreshness, String), dioxus_ssr::incremental::IncrementalRendererError> {
-        let wrapper = FullstackHTMLTemplate {
-            cfg: cfg.clone(),
-            server_context: server_context.clone(),
-        };
-        match self {
-            Self::Renderer(pool) => {
-                let server_context = server_context.clone();
-                let mut renderer = pool.write().unwrap().pop().unwrap_or_else(pre_renderer);
+    fn new(cfg: &ServeConfig) -> Self {
+        let permits = Arc::new(Semaphore::new(cfg.render_threads + cfg.render_queue_size));
 
-                let (tx, rx) = tokio::sync::oneshot::channel();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (jobs, receiver) = tokio::sync::mpsc::unbounded_channel();
+            let receiver = Arc::new(tokio::sync::Mutex::new(receiver));
+
+            for index in 0..cfg.render_threads {
+                let receiver = receiver.clone();
+                let cfg = cfg.clone();
+                std::thread::Builder::new()
+                    .name(format!("dioxus-ssr-{index}"))
+                    .spawn(move || render_thread(cfg, receiver))
+                    .expect("couldn't spawn render thread");
+            }
 
-                spawn_platform(move || async move {
-                    let mut vdom = virtual_dom_factory();
-                    let mut to = WriteBuffer { buffer: Vec::new() };
-                    // poll the future, which may call server_context()
-                    tracing::info!("Rebuilding vdom");
-                    with_server_context(server_context.clone(), || {
-                        block_in_place(|| vdom.rebuild(&mut NoOpMutations));
-                    });
-                    ProvideServerContext::new(vdom.wait_for_suspense(), server_context).await;
-                    tracing::info!("Suspense resolved");
+            Self { jobs, permits }
+        }
 
-                    if let Err(err) = wrapper.render_before_body(&mut *to) {
-                        let _ = tx.send(Err(err));
-                        return;
-                    }
-                    if let Err(err) = renderer.render_to(&mut to, &vdom) {
-                        let _ = tx.send(Err(
-                            dioxus_ssr::incremental::IncrementalRendererError::RenderError(err),
-                        ));
-                        return;
-                    }
-                    if let Err(err) = wrapper.render_after_body(&mut *to) {
-                        let _ = tx.send(Err(err));
-                        return;
-                    }
-                    match String::from_utf8(to.buffer) {
-                        Ok(html) => {
-                            let _ = tx.send(Ok((renderer, RenderFreshness::now(None), html)));
-                        }
-                        Err(err) => {
-                            _ = tx.send(Err(
-                                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(
-                                    err,
-                                )),
-                            ));
-                        }
-                    }
-                });
-                let (renderer, freshness, html) = rx.await.unwrap()?;
-                pool.write().unwrap().push(renderer);
-                Ok((freshness, html))
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                cfg: cfg.clone(),
+                permits,
             }
-            Self::Incremental(pool) => {
-                let mut renderer =
-                    pool.write().unwrap().pop().unwrap_or_else(|| {
-                        incremental_pre_renderer(cfg.incremental.as_ref().unwrap())
-                    });
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn submit(&self, job: RenderJob) -> Result<(), IncrementalRendererError> {
+        self.jobs
+            .send(job)
+            .map_err(|_| IncrementalRendererError::Other("The render threads have stopped".into()))
+    }
 
-                let (tx, rx) = tokio::sync::oneshot::channel();
+    // There are no threads to render on, so every render runs as a local task with a fresh renderer
+    #[cfg(target_arch = "wasm32")]
+    fn submit(&self, job: RenderJob) -> Result<(), IncrementalRendererError> {
+        tokio::task::spawn_local(job(PoolRenderer::new(&self.cfg)));
+        Ok(())
+    }
 
-                let server_context = server_context.clone();
-                spawn_platform(move || async move {
-                    let mut to = WriteBuffer { buffer: Vec::new() };
-                    match renderer
-                        .render(
+    /// Render a page on a render thread. Routes are cached by the incremental renderer, renders without a route are not.
+    async fn render_to(
+        &self,
+        route: Option<String>,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        wrapper: impl WrapBody + Send + Sync + 'static,
+    ) -> Result<(RenderFreshness, String), IncrementalRendererError> {
+        let permit = self.acquire_permit()?;
+
+        let server_context = server_context.clone();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        self.submit(Box::new(move |mut renderer| {
+            Box::pin(async move {
+                let result = match (&mut renderer, route) {
+                    (PoolRenderer::Renderer(renderer), _) => {
+                        render_page(renderer, virtual_dom_factory, server_context, &wrapper).await
+                    }
+                    (PoolRenderer::Incremental(renderer), None) => {
+                        render_page(
+                            renderer.renderer_mut(),
+                            virtual_dom_factory,
+                            server_context,
+                            &wrapper,
+                        )
+                        .await
+                    }
+                    (PoolRenderer::Incremental(renderer), Some(route)) => {
+                        render_incremental_page(
+                            renderer,
                             route,
                             virtual_dom_factory,
-                            &mut *to,
-                            |vdom| {
-                                Box::pin(async move {
-                                    // poll the future, which may call server_context()
-                                    tracing::info!("Rebuilding vdom");
-                                    with_server_context(server_context.clone(), || {
-                                        block_in_place(|| vdom.rebuild(&mut NoOpMutations));
-                                    });
-                                    ProvideServerContext::new(
-                                        vdom.wait_for_suspense(),
-                                        server_context,
-                                    )
-                                    .await;
-                                    tracing::info!("Suspense resolved");
-                                })
-                            },
+                            server_context,
                             &wrapper,
                         )
                         .await
-                    {
-                        Ok(freshness) => {
-                            match String::from_utf8(to.buffer).map_err(|err| {
-                                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(
-                                    err,
-                                ))
-                            }) {
-                                Ok(html) => {
-                                    let _ = tx.send(Ok((freshness, html)));
-                                }
-                                Err(err) => {
-                                    let _ = tx.send(Err(err));
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            let _ = tx.send(Err(err));
-                        }
                     }
-                });
-                let (freshness, html) = rx.await.unwrap()?;
+                };
+                let _ = tx.send(result);
+                drop(permit);
+                renderer
+            })
+        }))?;
+
+        rx.await.map_err(|_| {
+            IncrementalRendererError::Other("The render thread panicked while rendering".into())
+        })?
+    }
 
-                Ok((freshness, html))
-            }
+    /// Render a page on a render thread, returning the page with the fallbacks of its suspense boundaries as soon as it
+    /// renders. The children of the boundaries are sent over the returned channel as they resolve.
+    async fn stream_to(
+        &self,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        wrapper: impl WrapBody + Send + Sync + 'static,
+    ) -> Result<(String, RenderChunks), IncrementalRendererError> {
+        let permit = self.acquire_permit()?;
+
+        let server_context = server_context.clone();
+        let (shell_tx, shell_rx) = tokio::sync::oneshot::channel();
+        let (chunks_tx, chunks_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        self.submit(Box::new(move |mut renderer| {
+            Box::pin(async move {
+                let pool_renderer = match &mut renderer {
+                    PoolRenderer::Renderer(renderer) => renderer,
+                    PoolRenderer::Incremental(renderer) => renderer.renderer_mut(),
+                };
+                stream_page(
+                    pool_renderer,
+                    virtual_dom_factory,
+                    server_context,
+                    &wrapper,
+                    shell_tx,
+                    chunks_tx,
+                )
+                .await;
+                drop(permit);
+                renderer
+            })
+        }))?;
+
+        let shell = shell_rx.await.map_err(|_| {
+            IncrementalRendererError::Other("The render thread panicked while rendering".into())
+        })??;
+
+        Ok((shell, chunks_rx))
+    }
+
+    /// Turn the request away if the server is saturated instead of letting the renders pile up without bound
+    fn acquire_permit(
+        &self,
+    ) -> Result<tokio::sync::OwnedSemaphorePermit, IncrementalRendererError> {
+        self.permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| IncrementalRendererError::Other(Box::new(RenderPoolSaturated)))
+    }
+}
+
+/// The chunks of a streamed page that are rendered after the first one
+type RenderChunks = tokio::sync::mpsc::UnboundedReceiver<Result<String, IncrementalRendererError>>;
+
+/// Run render jobs until the pool is dropped
+#[cfg(not(target_arch = "wasm32"))]
+fn render_thread(
+    cfg: ServeConfig,
+    jobs: Arc<tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<RenderJob>>>,
+) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("couldn't spawn runtime");
+    let local = tokio::task::LocalSet::new();
+    // The renderers that are not rendering right now. There is one for every page the thread rendered at once
+    let renderers = std::rc::Rc::new(std::cell::RefCell::new(vec![PoolRenderer::new(&cfg)]));
+
+    local.block_on(&runtime, async move {
+        loop {
+            // The other threads wait on the lock while this one waits for a job
+            let Some(job) = jobs.lock().await.recv().await else {
+                return;
+            };
+
+            let renderer = renderers
+                .borrow_mut()
+                .pop()
+                .unwrap_or_else(|| PoolRenderer::new(&cfg));
+            let renderers = renderers.clone();
+            // A panic while rendering only fails that request and drops the renderer it used
+            tokio::task::spawn_local(async move {
+                let renderer = job(renderer).await;
+                renderers.borrow_mut().push(renderer);
+            });
         }
+    });
+}
+
+async fn render_page(
+    renderer: &mut Renderer,
+    virtual_dom_factory: impl FnOnce() -> VirtualDom,
+    server_context: DioxusServerContext,
+    wrapper: &impl WrapBody,
+) -> Result<(RenderFreshness, String), IncrementalRendererError> {
+    let mut vdom = virtual_dom_factory();
+    // poll the future, which may call server_context()
+    tracing::info!("Rebuilding vdom");
+    with_server_context(server_context.clone(), || vdom.rebuild(&mut NoOpMutations));
+    ProvideServerContext::new(vdom.wait_for_suspense(), server_context.clone()).await;
+    tracing::info!("Suspense resolved");
+    // Error boundaries that caught an error while rendering need to render their error page
+    with_server_context(server_context, || vdom.render_immediate(&mut NoOpMutations));
+
+    let mut to = WriteBuffer { buffer: Vec::new() };
+    wrapper.render_before_body(&mut *to)?;
+    renderer.render_to(&mut to, &vdom)?;
+    wrapper.render_after_body(&mut *to)?;
+
+    Ok((RenderFreshness::now(None), to.into_html()?))
+}
+
+/// Render a page with the fallbacks of its suspense boundaries, then render each boundary once it resolves
+///
+/// The data of the server functions is written after the last boundary resolves, so the client only hydrates the page
+/// once it is complete.
+async fn stream_page(
+    renderer: &mut Renderer,
+    virtual_dom_factory: impl FnOnce() -> VirtualDom,
+    server_context: DioxusServerContext,
+    wrapper: &impl WrapBody,
+    shell: tokio::sync::oneshot::Sender<Result<String, IncrementalRendererError>>,
+    chunks: tokio::sync::mpsc::UnboundedSender<Result<String, IncrementalRendererError>>,
+) {
+    let mut vdom = virtual_dom_factory();
+    tracing::info!("Rebuilding vdom");
+    with_server_context(server_context.clone(), || vdom.rebuild(&mut NoOpMutations));
+    // Error boundaries that caught an error while rendering need to render their error page
+    with_server_context(server_context.clone(), || {
+        vdom.render_immediate(&mut NoOpMutations)
+    });
+
+    renderer.stream_suspense = true;
+    let mut to = WriteBuffer { buffer: Vec::new() };
+    let rendered = wrapper
+        .render_before_body(&mut *to)
+        .and_then(|_| Ok(renderer.render_to(&mut to, &vdom)?));
+    let streaming = rendered.is_ok() && shell.send(rendered.and_then(|_| to.into_html())).is_ok();
+
+    // Stop rendering if the client went away
+    while streaming && renderer.has_pending_boundaries() && !chunks.is_closed() {
+        ProvideServerContext::new(vdom.wait_for_work(), server_context.clone()).await;
+        with_server_context(server_context.clone(), || {
+            vdom.render_immediate(&mut NoOpMutations)
+        });
+
+        let mut chunk = String::new();
+        match renderer.render_resolved_boundaries(&mut chunk, &vdom) {
+            Ok(()) if chunk.is_empty() => {}
+            Ok(()) => _ = chunks.send(Ok(chunk)),
+            Err(err) => _ = chunks.send(Err(err.into())),
+        }
+    }
+    renderer.stream_suspense = false;
+
+    if streaming && !renderer.has_pending_boundaries() {
+        tracing::info!("Suspense resolved");
+        let mut to = WriteBuffer { buffer: Vec::new() };
+        let rendered = wrapper.render_after_body(&mut *to);
+        _ = chunks.send(rendered.and_then(|_| to.into_html()));
     }
 }
 
-/// State used in server side rendering. This utilizes a pool of [`dioxus_ssr::Renderer`]s to cache static templates between renders.
+async fn render_incremental_page(
+    renderer: &mut dioxus_ssr::incremental::IncrementalRenderer,
+    route: String,
+    virtual_dom_factory: impl FnOnce() -> VirtualDom,
+    server_context: DioxusServerContext,
+    wrapper: &(impl WrapBody + Send + Sync),
+) -> Result<(RenderFreshness, String), IncrementalRendererError> {
+    let mut to = WriteBuffer { buffer: Vec::new() };
+    let response_context = server_context.clone();
+    let freshness = renderer
+        .render(
+            route.clone(),
+            virtual_dom_factory,
+            &mut *to,
+            |vdom| {
+                Box::pin(async move {
+                    // poll the future, which may call server_context()
+                    tracing::info!("Rebuilding vdom");
+                    with_server_context(server_context.clone(), || {
+                        vdom.rebuild(&mut NoOpMutations)
+                    });
+                    ProvideServerContext::new(vdom.wait_for_suspense(), server_context.clone())
+                        .await;
+                    tracing::info!("Suspense resolved");
+                    // Error boundaries that caught an error while rendering need to render their error page
+                    with_server_context(server_context, || {
+                        vdom.render_immediate(&mut NoOpMutations)
+                    });
+                })
+            },
+            wrapper,
+        )
+        .await?;
+
+    // Error pages are rendered again for every request instead of being cached
+    let is_success = response_context
+        .response_parts()
+        .map(|response| response.status.is_success())
+        .unwrap_or(true);
+    if !is_success {
+        renderer.invalidate(&route);
+    }
+
+    Ok((freshness, to.into_html()?))
+}
+
+/// State used in server side rendering. This utilizes a pool of render threads, each with its own [`dioxus_ssr::Renderer`] to cache static templates between renders.
+///
+/// The number of threads and how many requests can render at once are set with [`ServeConfigBuilder::render_threads`] and [`ServeConfigBuilder::render_queue_size`].
 #[derive(Clone)]
 pub struct SSRState {
-    // We keep a pool of renderers to avoid re-creating them on every request. They are boxed to make them very cheap to move
+    // The render threads stop once every clone of the state is dropped
     renderers: Arc<SsrRendererPool>,
 }
 
 impl SSRState {
     /// Create a new [`SSRState`].
     pub fn new(cfg: &ServeConfig) -> Self {
-        if cfg.incremental.is_some() {
-            return Self {
-                renderers: Arc::new(SsrRendererPool::Incremental(RwLock::new(vec![
-                    incremental_pre_renderer(cfg.incremental.as_ref().unwrap()),
-                    incremental_pre_renderer(cfg.incremental.as_ref().unwrap()),
-                    incremental_pre_renderer(cfg.incremental.as_ref().unwrap()),
-                    incremental_pre_renderer(cfg.incremental.as_ref().unwrap()),
-                ]))),
-            };
-        }
-
         Self {
-            renderers: Arc::new(SsrRendererPool::Renderer(RwLock::new(vec![
-                pre_renderer(),
-                pre_renderer(),
-                pre_renderer(),
-                pre_renderer(),
-            ]))),
+            renderers: Arc::new(SsrRendererPool::new(cfg)),
         }
     }
 
@@ -199,15 +389,63 @@ impl SSRState {
         virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
         server_context: &'a DioxusServerContext,
     ) -> Result<RenderResponse, dioxus_ssr::incremental::IncrementalRendererError> {
-        let ServeConfig { .. } = cfg;
+        let wrapper = FullstackHTMLTemplate::new(cfg, server_context);
 
         let (freshness, html) = self
             .renderers
-            .render_to(cfg, route, virtual_dom_factory, server_context)
+            .render_to(Some(route), virtual_dom_factory, server_context, wrapper)
             .await?;
 
         Ok(RenderResponse { html, freshness })
     }
+
+    /// Render the application to HTML, streaming its suspense boundaries in as they resolve.
+    ///
+    /// This returns as soon as the page renders with the fallbacks of the boundaries that are still waiting. See
+    /// [`ServeConfigBuilder::stream_suspense`].
+    pub async fn render_streaming(
+        &self,
+        cfg: &ServeConfig,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+    ) -> Result<StreamingRenderResponse, IncrementalRendererError> {
+        let wrapper = FullstackHTMLTemplate::new(cfg, server_context);
+
+        let (shell, chunks) = self
+            .renderers
+            .stream_to(virtual_dom_factory, server_context, wrapper)
+            .await?;
+
+        Ok(StreamingRenderResponse { shell, chunks })
+    }
+
+    /// Render a component from the [`ComponentRegistry`] of the config to an HTML fragment that other pages can embed.
+    ///
+    /// The fragment is the rendered component in a root element, followed by the data the client needs to hydrate it.
+    /// See [`ComponentRegistry`] for how to hydrate it.
+    pub async fn render_component(
+        &self,
+        cfg: &ServeConfig,
+        name: &str,
+        props: serde_json::Value,
+        server_context: &DioxusServerContext,
+    ) -> Result<RenderResponse, ComponentRenderError> {
+        let virtual_dom_factory = cfg.components.virtual_dom_factory(name, props.clone())?;
+        let wrapper = crate::component_registry::ComponentFragmentTemplate {
+            name: name.to_string(),
+            props,
+            server_context: server_context.clone(),
+        };
+
+        // Fragments depend on their props, so they are never cached by the incremental renderer
+        let (freshness, html) = self
+            .renderers
+            .render_to(None, virtual_dom_factory, server_context, wrapper)
+            .await
+            .map_err(ComponentRenderError::Render)?;
+
+        Ok(RenderResponse { html, freshness })
+    }
 }
 
 /// The template that wraps the body of the HTML for a fullstack page. This template contains the data needed to hydrate server functions that were run on the server.
@@ -244,28 +482,7 @@ impl dioxus_ssr::incremental::WrapBody for FullstackHTMLTemplate {
         to: &mut R,
     ) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
         // serialize the server state
-        crate::html_storage::serialize::encode_in_element(
-            &*self.server_context.html_data().map_err(|_| {
-                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new({
-                    #[derive(Debug)]
-                    struct HTMLDataReadError;
-
-                    impl std::fmt::Display for HTMLDataReadError {
-                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                            f.write_str(
-                                "Failed to read the server data to serialize it into the HTML",
-                            )
-                        }
-                    }
-
-                    impl std::error::Error for HTMLDataReadError {}
-
-                    HTMLDataReadError
-                }))
-            })?,
-            to,
-        )
-        .map_err(|err| dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err)))?;
+        write_server_data(&self.server_context, to)?;
 
         #[cfg(all(debug_assertions, feature = "hot-reload"))]
         {
@@ -285,6 +502,35 @@ impl dioxus_ssr::incremental::WrapBody for FullstackHTMLTemplate {
     }
 }
 
+/// Serialize the data of the server functions that ran while rendering into the HTML so the client can hydrate them
+pub(crate) fn write_server_data<R: std::io::Write>(
+    server_context: &DioxusServerContext,
+    to: &mut R,
+) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
+    crate::html_storage::serialize::encode_in_element(
+        &*server_context.html_data().map_err(|_| {
+            dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new({
+                #[derive(Debug)]
+                struct HTMLDataReadError;
+
+                impl std::fmt::Display for HTMLDataReadError {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str("Failed to read the server data to serialize it into the HTML")
+                    }
+                }
+
+                impl std::error::Error for HTMLDataReadError {}
+
+                HTMLDataReadError
+            }))
+        })?,
+        to,
+    )
+    .map_err(|err| dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err)))?;
+
+    Ok(())
+}
+
 /// A rendered response from the server.
 #[derive(Debug)]
 pub struct RenderResponse {
@@ -304,6 +550,31 @@ impl RenderResponse {
     }
 }
 
+/// A response that is streamed to the client as the suspense boundaries of the page resolve.
+#[derive(Debug)]
+pub struct StreamingRenderResponse {
+    shell: String,
+    chunks: RenderChunks,
+}
+
+impl StreamingRenderResponse {
+    /// Get the HTML of the page with the fallbacks of the boundaries that were still waiting when it rendered.
+    pub fn shell(&self) -> &str {
+        &self.shell
+    }
+
+    /// Stream the whole page, starting with the shell and followed by every boundary as it resolves.
+    pub fn into_stream(
+        self,
+    ) -> impl futures_util::Stream<Item = Result<String, IncrementalRendererError>> + Send {
+        use futures_util::StreamExt;
+
+        futures_util::stream::once(std::future::ready(Ok(self.shell))).chain(
+            tokio_stream::wrappers::UnboundedReceiverStream::new(self.chunks),
+        )
+    }
+}
+
 fn pre_renderer() -> Renderer {
     let mut renderer = Renderer::default();
     renderer.pre_render = true;
@@ -322,6 +593,12 @@ struct WriteBuffer {
     buffer: Vec<u8>,
 }
 
+impl WriteBuffer {
+    fn into_html(self) -> Result<String, IncrementalRendererError> {
+        String::from_utf8(self.buffer).map_err(|err| IncrementalRendererError::Other(Box::new(err)))
+    }
+}
+
 impl std::fmt::Write for WriteBuffer {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
         self.buffer.extend_from_slice(s.as_bytes());
@@ -342,3 +619,57 @@ impl std::ops::DerefMut for WriteBuffer {
         &mut self.buffer
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::test]
+async fn render_threads_render_other_pages_while_one_waits() {
+    use std::time::Duration;
+
+    static RELEASE: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+    fn waits() -> Element {
+        use_server_future(|| RELEASE.notified())?;
+        rsx! { "waited" }
+    }
+
+    fn ready() -> Element {
+        rsx! { "ready" }
+    }
+
+    let cfg = ServeConfigBuilder::new()
+        .index_html(r#"<html><body><div id="main"></div></body></html>"#.to_string())
+        .render_threads(1)
+        .build();
+    let state = SSRState::new(&cfg);
+    let server_context = DioxusServerContext::default();
+
+    // Start a render that waits for a server future on the only render thread
+    let waiting = state.render(
+        "/waits".to_string(),
+        &cfg,
+        || VirtualDom::new(waits),
+        &server_context,
+    );
+    tokio::pin!(waiting);
+    tokio::select! {
+        _ = &mut waiting => panic!("the page waits for the server future"),
+        _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+    }
+
+    let rendered = tokio::time::timeout(
+        Duration::from_secs(5),
+        state.render(
+            "/ready".to_string(),
+            &cfg,
+            || VirtualDom::new(ready),
+            &server_context,
+        ),
+    )
+    .await
+    .expect("the thread renders other pages while a page waits")
+    .unwrap();
+    assert!(rendered.html().contains("ready"));
+
+    RELEASE.notify_one();
+    assert!(waiting.await.unwrap().html().contains("waited"));
+}
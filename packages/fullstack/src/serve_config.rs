@@ -14,6 +14,10 @@ pub struct ServeConfigBuilder {
     pub(crate) assets_path: Option<PathBuf>,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) render_threads: Option<usize>,
+    pub(crate) render_queue_size: Option<usize>,
+    pub(crate) stream_suspense: bool,
+    pub(crate) components: crate::component_registry::ComponentRegistry,
 }
 
 impl ServeConfigBuilder {
@@ -25,6 +29,10 @@ impl ServeConfigBuilder {
             index_path: None,
             assets_path: None,
             incremental: None,
+            render_threads: None,
+            render_queue_size: None,
+            stream_suspense: false,
+            components: Default::default(),
         }
     }
 
@@ -34,6 +42,44 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Set the number of threads that render pages. (defaults to the number of cores)
+    ///
+    /// Each thread keeps its own renderers, so the templates it has seen stay cached between requests. A thread renders
+    /// other pages while a page waits for a server future.
+    pub fn render_threads(mut self, render_threads: usize) -> Self {
+        self.render_threads = Some(render_threads.max(1));
+        self
+    }
+
+    /// Set the number of requests that can render at the same time on top of one for every render thread, including
+    /// renders that wait for a server future. (defaults to 16 per render thread)
+    ///
+    /// Requests that arrive while that many pages are rendering are rejected with a 503 Service Unavailable response
+    /// instead of piling up.
+    pub fn render_queue_size(mut self, render_queue_size: usize) -> Self {
+        self.render_queue_size = Some(render_queue_size);
+        self
+    }
+
+    /// Stream pages to the client as their suspense boundaries resolve. (defaults to false)
+    ///
+    /// The page is sent with the fallback of every boundary that is still waiting as soon as it renders, and the
+    /// children of each boundary are streamed in once they resolve. The status and headers of the response are sent with
+    /// the first chunk, so they can't be changed by components that render after a boundary resolves. Pages that are
+    /// cached by the [`incremental`](Self::incremental) renderer are always rendered in full.
+    pub fn stream_suspense(mut self, stream_suspense: bool) -> Self {
+        self.stream_suspense = stream_suspense;
+        self
+    }
+
+    /// Set the components the server renders on their own at `/render/:component` so other pages can embed them
+    ///
+    /// See [`ComponentRegistry`](crate::prelude::ComponentRegistry) for how the fragments are requested and hydrated.
+    pub fn components(mut self, components: crate::component_registry::ComponentRegistry) -> Self {
+        self.components = components;
+        self
+    }
+
     /// Set the contents of the index.html file to be served. (precedence over index_path)
     pub fn index_html(mut self, index_html: String) -> Self {
         self.index_html = Some(index_html);
@@ -79,10 +125,22 @@ impl ServeConfigBuilder {
             .unwrap_or_else(|| load_index_path(index_path));
 
         let index = load_index_html(index_html, root_id);
+
+        let render_threads = self.render_threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1)
+        });
+        let render_queue_size = self.render_queue_size.unwrap_or(render_threads * 16);
+
         ServeConfig {
             index,
             assets_path,
             incremental: self.incremental,
+            render_threads,
+            render_queue_size,
+            stream_suspense: self.stream_suspense,
+            components: self.components,
         }
     }
 }
@@ -129,6 +187,10 @@ pub struct ServeConfig {
     pub(crate) assets_path: PathBuf,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) render_threads: usize,
+    pub(crate) render_queue_size: usize,
+    pub(crate) stream_suspense: bool,
+    pub(crate) components: crate::component_registry::ComponentRegistry,
 }
 
 impl Default for ServeConfig {
@@ -14,6 +14,7 @@ pub struct DioxusServerContext {
     response_parts: std::sync::Arc<std::sync::RwLock<http::response::Parts>>,
     pub(crate) parts: Arc<tokio::sync::RwLock<http::request::Parts>>,
     html_data: Arc<RwLock<HTMLData>>,
+    cancellation_token: tokio_util::sync::CancellationToken,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -28,6 +29,7 @@ impl Default for DioxusServerContext {
                 http::request::Request::new(()).into_parts().0,
             )),
             html_data: Arc::new(RwLock::new(HTMLData::default())),
+            cancellation_token: Default::default(),
         }
     }
 }
@@ -50,9 +52,17 @@ mod server_fn_impl {
                     http::response::Response::new(()).into_parts().0,
                 )),
                 html_data: Arc::new(RwLock::new(HTMLData::default())),
+                cancellation_token: Default::default(),
             }
         }
 
+        /// A token that is cancelled when the client stops waiting for the response, like when a call made with
+        /// `AbortableClient` is dropped. The server function itself stops at its next `.await` once that happens,
+        /// so this is mostly useful to stop work it spawned onto other tasks.
+        pub fn cancellation_token(&self) -> tokio_util::sync::CancellationToken {
+            self.cancellation_token.clone()
+        }
+
         /// Clone a value from the shared server context
         pub fn get<T: Any + Send + Sync + Clone + 'static>(&self) -> Option<T> {
             self.shared_context.read().ok()?.get::<T>().cloned()
@@ -0,0 +1,91 @@
+//! Detect clients calling a server function version the server no longer serves
+
+use server_fn::ServerFnError;
+
+const VERSION_MISMATCH: &str = "server function version mismatch: ";
+
+/// The error a server function returns when the client was built against a different version of the function than
+/// the server serves.
+///
+/// Server functions are mounted under a `v{version}-{hash}` segment that changes whenever the text of their signature
+/// or their `version` changes. Changes inside the types in the signature need a new `version` to be detected.
+/// A client that was cached before a deploy calls the old path, and the server answers with this error instead of a
+/// response the client can't deserialize. The usual fix is to reload the page to pick up the new client.
+///
+/// ```rust
+/// use dioxus_fullstack::prelude::*;
+///
+/// let error: ServerFnError = ServerFnVersionMismatch::new("/api/v1-00000000/get_posts").into_error();
+/// assert_eq!(
+///     ServerFnVersionMismatch::from_error(&error).unwrap().path(),
+///     "/api/v1-00000000/get_posts"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerFnVersionMismatch {
+    path: String,
+}
+
+impl ServerFnVersionMismatch {
+    /// Create a version mismatch error for the path the client called
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The path the outdated client called
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Check if an error returned from a server function is a version mismatch
+    pub fn from_error<E>(error: &ServerFnError<E>) -> Option<Self> {
+        match error {
+            ServerFnError::Request(message) => {
+                message.strip_prefix(VERSION_MISMATCH).map(Self::new)
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert the mismatch into the error the client receives
+    pub fn into_error<E>(self) -> ServerFnError<E> {
+        ServerFnError::Request(self.to_string())
+    }
+}
+
+impl std::fmt::Display for ServerFnVersionMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{VERSION_MISMATCH}{}", self.path)
+    }
+}
+
+impl std::error::Error for ServerFnVersionMismatch {}
+
+/// Check if a path segment is the `v{version}-{hash}` segment the server macro mounts server functions under
+#[cfg(feature = "server")]
+pub(crate) fn is_version_segment(segment: &str) -> bool {
+    let Some((version, hash)) = segment
+        .strip_prefix('v')
+        .and_then(|rest| rest.split_once('-'))
+    else {
+        return false;
+    };
+
+    !version.is_empty()
+        && version.bytes().all(|b| b.is_ascii_digit())
+        && hash.len() == 8
+        && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Split a server function path into the prefix before its version segment and the version segment
+#[cfg(feature = "server")]
+pub(crate) fn split_versioned_path(path: &str) -> Option<(&str, &str)> {
+    let mut start = 0;
+    for segment in path.split('/') {
+        if is_version_segment(segment) {
+            return Some((path[..start].trim_end_matches('/'), segment));
+        }
+        start += segment.len() + 1;
+    }
+    None
+}
@@ -0,0 +1,37 @@
+use dioxus_core::prelude::*;
+use dioxus_core::JoinHandle;
+use dioxus_signals::*;
+
+/// Deliver the output of a task spawned in a [`TaskScope`] to a signal
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # async fn fetch_user(id: u32) -> String { format!("user {id}") }
+/// fn app() -> Element {
+///     let tasks = use_task_scope();
+///     let user = use_hook(|| tasks.spawn(fetch_user(1)).into_signal());
+///     rsx! {
+///         match user() {
+///             Some(user) => rsx! { "{user}" },
+///             None => rsx! { "Loading..." },
+///         }
+///     }
+/// }
+/// ```
+pub trait JoinHandleExt<T: 'static> {
+    /// Create a signal in the current scope that is set to the output of the task once it finishes. The signal stays
+    /// `None` if the task is cancelled or panics.
+    fn into_signal(self) -> ReadOnlySignal<Option<T>>;
+}
+
+impl<T: 'static> JoinHandleExt<T> for JoinHandle<T> {
+    fn into_signal(self) -> ReadOnlySignal<Option<T>> {
+        let mut output = Signal::new(None);
+        spawn(async move {
+            if let Some(value) = self.await {
+                output.set(Some(value));
+            }
+        });
+        output.into()
+    }
+}
@@ -93,5 +93,11 @@ pub use use_hook_did_run::*;
 mod use_signal;
 pub use use_signal::*;
 
+mod join_handle;
+pub use join_handle::*;
+
 mod use_set_compare;
 pub use use_set_compare::*;
+
+mod use_reduced_motion;
+pub use use_reduced_motion::*;
@@ -1,7 +1,9 @@
+use crate::use_memo;
 use dioxus_core::{
     prelude::{consume_context, provide_context, try_consume_context},
     use_hook,
 };
+use dioxus_signals::{Memo, Readable, Signal};
 
 /// Consume some context in the tree, providing a sharable handle to the value
 ///
@@ -38,6 +40,42 @@ pub fn use_context<T: 'static + Clone>() -> T {
     use_hook(|| consume_context::<T>())
 }
 
+/// Select part of a context that is stored in a [`Signal`], and only rerun the component when that part changes
+///
+/// Reading a signal from context directly reruns the component whenever any part of the value changes. This hook
+/// memoizes the selected part instead, so components that read a small slice of a large app state only rerun when
+/// their slice changes. The selected value is compared with [`PartialEq`].
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Default)]
+/// struct AppState {
+///     user: String,
+///     todos: Vec<String>,
+/// }
+///
+/// fn app() -> Element {
+///     use_context_provider(|| Signal::new(AppState::default()));
+///     rsx! { UserName {} }
+/// }
+///
+/// #[component]
+/// fn UserName() -> Element {
+///     // Changing the todos doesn't rerun this component
+///     let user = use_context_selector(|state: &AppState| state.user.clone());
+///     rsx! { "Signed in as {user}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_context_selector<T: 'static, O: PartialEq + 'static>(
+    mut select: impl FnMut(&T) -> O + 'static,
+) -> Memo<O> {
+    let state = use_context::<Signal<T>>();
+    use_memo(move || select(&state.read()))
+}
+
 /// Provide some context via the tree and return a reference to it
 ///
 /// Once the context has been provided, it is immutable. Mutations should be done via interior mutability.
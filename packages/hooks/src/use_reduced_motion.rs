@@ -0,0 +1,70 @@
+use crate::use_memo;
+use dioxus_core::prelude::*;
+use dioxus_signals::{Memo, Readable, Signal, Writable};
+
+/// Whether the user asked for reduced motion, shared by every component in the app.
+///
+/// Renderers report the system setting with [`MotionPreference::set_system`] (the web renderer follows the
+/// `prefers-reduced-motion` media query). Apps can force a preference either way with [`MotionPreference::set_override`],
+/// for example from a settings page.
+#[derive(Clone, Copy, PartialEq)]
+pub struct MotionPreference {
+    system: Signal<bool>,
+    app_override: Signal<Option<bool>>,
+}
+
+impl MotionPreference {
+    /// Get the motion preference of the current app, creating it at the root if it doesn't exist yet
+    pub fn current() -> Self {
+        // Renderers call this outside of any component, so look the context up from the root scope directly
+        ScopeId::ROOT.consume_context::<Self>().unwrap_or_else(|| {
+            ScopeId::ROOT.provide_context(Self {
+                system: Signal::new_in_scope(false, ScopeId::ROOT),
+                app_override: Signal::new_in_scope(None, ScopeId::ROOT),
+            })
+        })
+    }
+
+    /// Set whether the operating system or browser asks for reduced motion
+    pub fn set_system(&mut self, reduced: bool) {
+        let mut system = self.system;
+        if *system.peek() != reduced {
+            ScopeId::ROOT.in_runtime(|| system.set(reduced));
+        }
+    }
+
+    /// Force reduced motion on or off for the whole app, or pass `None` to follow the system setting again
+    pub fn set_override(&mut self, reduced: Option<bool>) {
+        let mut app_override = self.app_override;
+        ScopeId::ROOT.in_runtime(|| app_override.set(reduced));
+    }
+
+    /// The app level override, if one is set
+    pub fn app_override(&self) -> Option<bool> {
+        (self.app_override)()
+    }
+
+    /// Whether motion should be reduced. The app level override wins over the system setting.
+    pub fn reduced(&self) -> bool {
+        self.app_override().unwrap_or_else(|| (self.system)())
+    }
+}
+
+/// Check if the user prefers reduced motion.
+///
+/// Animations should skip straight to their final value when this is true. The memo updates when the system setting or
+/// the app level override in [`MotionPreference`] changes.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn Spinner() -> Element {
+///     let reduced_motion = use_prefers_reduced_motion();
+///     let class = if reduced_motion() { "spinner" } else { "spinner spinning" };
+///     rsx! { div { class: "{class}" } }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_prefers_reduced_motion() -> Memo<bool> {
+    let preference = use_hook(MotionPreference::current);
+    use_memo(move || preference.reduced())
+}
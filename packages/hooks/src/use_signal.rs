@@ -93,3 +93,54 @@ fn use_maybe_signal_sync<T: 'static, U: Storage<SignalData<T>>>(
 
     use_hook(|| Signal::new_with_caller(f(), caller))
 }
+
+/// Creates a new Signal that is saved in [snapshots](dioxus_core::VirtualDom::snapshot) of the virtual dom.
+///
+/// When the virtual dom is [restored](dioxus_core::VirtualDom::restore) from a snapshot, the signal starts with the
+/// value it had when the snapshot was taken instead of calling `f`.
+///
+/// In debug builds, `dx serve` also saves these signals before it rebuilds a desktop or web app and restores them in the
+/// new build, so the app keeps its state when hot reloading falls back to a full rebuild.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_hooks::use_restorable_signal;
+///
+/// fn App() -> Element {
+///     let mut count = use_restorable_signal(|| 0);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| count += 1,
+///             "{count}"
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "serialize")]
+#[must_use]
+#[track_caller]
+pub fn use_restorable_signal<T>(f: impl FnOnce() -> T) -> Signal<T, UnsyncStorage>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + 'static,
+{
+    let caller = std::panic::Location::caller();
+
+    use_hook(|| {
+        let signal = Signal::new_with_caller(restored_value().unwrap_or_else(f), caller);
+        add_snapshot_value(move || SnapshotSignal(signal));
+        signal
+    })
+}
+
+/// Serializes the current value of a signal without subscribing to it
+#[cfg(feature = "serialize")]
+struct SnapshotSignal<T: 'static>(Signal<T, UnsyncStorage>);
+
+#[cfg(feature = "serialize")]
+impl<T: serde::Serialize + 'static> serde::Serialize for SnapshotSignal<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use dioxus_signals::Readable;
+        self.0.peek().serialize(serializer)
+    }
+}
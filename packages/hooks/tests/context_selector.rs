@@ -0,0 +1,55 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[tokio::test]
+async fn context_selector_only_reruns_when_the_selection_changes() {
+    #[derive(Default)]
+    struct AppState {
+        user: String,
+        todos: Vec<String>,
+    }
+
+    #[derive(Clone, Default)]
+    struct Renders(Rc<RefCell<Vec<String>>>);
+
+    fn app() -> Element {
+        use_context_provider(|| Signal::new(AppState::default()));
+        rsx! { UserName {} }
+    }
+
+    fn UserName() -> Element {
+        let renders = use_context::<Renders>();
+        let user = use_context_selector(|state: &AppState| state.user.clone());
+        renders.0.borrow_mut().push(user());
+        None
+    }
+
+    let renders = Renders::default();
+    let mut dom = VirtualDom::new(app).with_root_context(renders.clone());
+    dom.rebuild_in_place();
+
+    let mut state =
+        dom.in_runtime(|| ScopeId::ROOT.in_runtime(consume_context::<Signal<AppState>>));
+    let mut update = |dom: &VirtualDom, f: fn(&mut AppState)| {
+        dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| f(&mut state.write())))
+    };
+
+    // Other parts of the state don't rerun the component
+    update(&dom, |state| state.todos.push("write tests".to_string()));
+    rerender(&mut dom).await;
+
+    update(&dom, |state| state.user = "Ferris".to_string());
+    rerender(&mut dom).await;
+
+    assert_eq!(*renders.0.borrow(), ["", "Ferris"]);
+}
+
+async fn rerender(dom: &mut VirtualDom) {
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {}
+    }
+    dom.render_immediate(&mut dioxus::dioxus_core::NoOpMutations);
+}
@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[tokio::test]
+async fn reduced_motion_follows_system_and_override() {
+    #[derive(Clone, Default)]
+    struct Seen(Rc<RefCell<Vec<bool>>>);
+
+    fn app() -> Element {
+        let seen = use_context::<Seen>();
+        let reduced_motion = use_prefers_reduced_motion();
+        seen.0.borrow_mut().push(reduced_motion());
+        None
+    }
+
+    let seen = Seen::default();
+    let mut dom = VirtualDom::new(app).with_root_context(seen.clone());
+    dom.rebuild_in_place();
+
+    let mut preference = dom.in_runtime(MotionPreference::current);
+
+    // The renderer reports the system setting
+    dom.in_runtime(|| preference.set_system(true));
+    rerender(&mut dom).await;
+
+    // The app can turn motion back on
+    dom.in_runtime(|| preference.set_override(Some(false)));
+    rerender(&mut dom).await;
+
+    // And go back to following the system
+    dom.in_runtime(|| preference.set_override(None));
+    rerender(&mut dom).await;
+
+    assert_eq!(*seen.0.borrow(), [false, true, false, true]);
+}
+
+async fn rerender(dom: &mut VirtualDom) {
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {}
+    }
+    dom.render_immediate(&mut dioxus::dioxus_core::NoOpMutations);
+}
@@ -0,0 +1,49 @@
+//! Updating assets like stylesheets and images without rebuilding the app or reloading the page
+
+use crate::HotReloadMsg;
+use std::path::{Path, PathBuf};
+
+/// The extensions of assets the renderers can swap without reloading the page
+pub const HOT_RELOADABLE_ASSETS: &[&str] = &[
+    "css", "js", "png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "ico",
+];
+
+/// A javascript function that applies an asset update to the page. It takes the path and contents of the asset from
+/// [`HotReloadMsg::UpdateAsset`]
+pub const UPDATE_ASSET_SCRIPT: &str = include_str!("assets/update_asset.js");
+
+/// Check if a changed file is an asset the renderers can update without a rebuild
+pub fn is_hot_reloadable_asset(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| HOT_RELOADABLE_ASSETS.contains(&ext.to_lowercase().as_str()))
+}
+
+impl HotReloadMsg {
+    /// Read a changed asset into a [`HotReloadMsg::UpdateAsset`]
+    ///
+    /// `file` is the changed file and `path` is the path the app loads it from. Stylesheets are sent with their
+    /// contents so they can be swapped in place, other assets are fetched again by the renderer.
+    pub fn update_asset(file: &Path, path: PathBuf) -> Option<Self> {
+        if !is_hot_reloadable_asset(file) {
+            return None;
+        }
+
+        let is_stylesheet = file
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("css"));
+        let contents = match is_stylesheet {
+            true => Some(std::fs::read_to_string(file).ok()?),
+            false => None,
+        };
+
+        Some(HotReloadMsg::UpdateAsset { path, contents })
+    }
+}
+
+/// A script that applies an asset update to the page when it is evaluated in the renderer
+pub fn update_asset_script(path: &Path, contents: Option<&str>) -> String {
+    let path = serde_json::Value::from(path.to_string_lossy());
+    let contents = serde_json::Value::from(contents);
+    format!("({UPDATE_ASSET_SCRIPT})({path}, {contents});")
+}
@@ -0,0 +1,73 @@
+// Apply a hot reloaded asset to the page without reloading it. Elements are matched to the asset by file name
+(path, contents) => {
+  const name = path.split(/[\\/]/).pop();
+  const matches = (url) => {
+    try {
+      return !!url && new URL(url, location.href).pathname.split("/").pop() === name;
+    } catch {
+      return false;
+    }
+  };
+  const bust = (url) => {
+    const busted = new URL(url, location.href);
+    busted.searchParams.set("dx-hot-reload", Date.now());
+    return busted.href;
+  };
+
+  if (name.endsWith(".css")) {
+    const links = [...document.querySelectorAll("link[rel=stylesheet]")].filter(
+      (link) => matches(link.getAttribute("href"))
+    );
+
+    // Stylesheets the page didn't link directly (like @import) can only be found by reloading all of them
+    if (!links.length) {
+      for (const link of document.querySelectorAll("link[rel=stylesheet]")) {
+        link.href = bust(link.href);
+      }
+      return;
+    }
+
+    for (const link of links) {
+      // Swap in the new rules right away. The base url keeps relative urls in the stylesheet pointing at the same files
+      if (contents !== null && "adoptedStyleSheets" in document) {
+        const sheet = new CSSStyleSheet({ baseURL: link.href });
+        sheet.replaceSync(contents);
+        document.adoptedStyleSheets = [
+          ...document.adoptedStyleSheets.filter((old) => old.dioxusAsset !== link.href),
+          Object.assign(sheet, { dioxusAsset: link.href }),
+        ];
+        link.disabled = true;
+      } else {
+        link.href = bust(link.href);
+      }
+    }
+    return;
+  }
+
+  if (name.endsWith(".js")) {
+    // Scripts only run when they are added to the page, so replace them with a copy that loads the new file
+    for (const script of document.querySelectorAll("script[src]")) {
+      if (matches(script.getAttribute("src"))) {
+        const copy = document.createElement("script");
+        for (const attr of script.attributes) {
+          copy.setAttribute(attr.name, attr.value);
+        }
+        copy.src = bust(script.src);
+        script.replaceWith(copy);
+      }
+    }
+    return;
+  }
+
+  // Images and other media are loaded again with a new url that skips the cache
+  for (const element of document.querySelectorAll("[src]")) {
+    if (matches(element.getAttribute("src"))) {
+      element.src = bust(element.src);
+    }
+  }
+  for (const link of document.querySelectorAll("link[rel~=icon]")) {
+    if (matches(link.getAttribute("href"))) {
+      link.href = bust(link.href);
+    }
+  }
+}
@@ -0,0 +1,42 @@
+//! Telling the app about changes that couldn't be hot reloaded, so development tools can show them
+
+use crate::HotReloadMsg;
+use dioxus_core::{HotReloadError, HotReloadErrorKind};
+use dioxus_rsx::hot_reload::{HotreloadError, UpdateResult};
+use std::path::Path;
+
+impl HotReloadMsg {
+    /// The [`HotReloadMsg::ReportError`] to send after trying to hot reload a change to a file
+    ///
+    /// `file` is the path of the changed file relative to the crate root. Changes that were hot reloaded clear the
+    /// error. Returns `None` if the file couldn't be read, since that says nothing about the code in it.
+    pub fn report_error(
+        file: &Path,
+        result: &Result<UpdateResult, HotreloadError>,
+    ) -> Option<Self> {
+        let file = file.display().to_string();
+        let error = match result {
+            Ok(UpdateResult::UpdatedRsx { .. }) => None,
+            Ok(UpdateResult::NeedsRebuild { reason, location }) => Some(HotReloadError {
+                kind: HotReloadErrorKind::RebuildRequired,
+                file,
+                line: location.map(|location| location.line),
+                column: location.map(|location| location.column),
+                message: format!("{reason}, rebuilding the app"),
+            }),
+            Err(HotreloadError::Parse(err)) => {
+                let start = err.span().start();
+                Some(HotReloadError {
+                    kind: HotReloadErrorKind::Parse,
+                    file,
+                    line: Some(start.line),
+                    column: Some(start.column),
+                    message: err.to_string(),
+                })
+            }
+            Err(_) => return None,
+        };
+
+        Some(HotReloadMsg::ReportError(error))
+    }
+}
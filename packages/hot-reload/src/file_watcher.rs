@@ -5,7 +5,10 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use crate::HotReloadMsg;
+use crate::{
+    encode_message, is_hot_reloadable_asset, write_frame, Handshake, HotReloadMsg,
+    ScopedHotReloadMsg,
+};
 use dioxus_rsx::{
     hot_reload::{FileMap, FileMapBuildResult, UpdateResult},
     HotReloadingContext,
@@ -160,8 +163,11 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
 
     let file_map = Arc::new(Mutex::new(file_map));
 
+    let namespace = crate::namespace();
+    let socket_name = crate::socket_name(namespace.as_deref());
+
     let target_dir = crate_dir.join("target");
-    let hot_reload_socket_path = target_dir.join("dioxusin");
+    let hot_reload_socket_path = target_dir.join(&socket_name);
 
     #[cfg(unix)]
     {
@@ -174,7 +180,7 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
     }
 
     let listener = if cfg!(windows) {
-        LocalSocketListener::bind("@dioxusin")
+        LocalSocketListener::bind(format!("@{socket_name}"))
     } else {
         LocalSocketListener::bind(hot_reload_socket_path)
     };
@@ -194,23 +200,34 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
         let file_map = file_map.clone();
         let channels = channels.clone();
         let aborted = aborted.clone();
+        let namespace = namespace.clone();
         let _ = local_socket_stream.set_nonblocking(true);
         move || {
             loop {
                 if let Ok(mut connection) = local_socket_stream.accept() {
-                    // send any templates than have changed before the socket connected
-                    let templates: Vec<_> = {
-                        file_map
-                            .lock()
-                            .unwrap()
+                    // The app is built with this server, so its answer to the handshake always matches
+                    if write_frame(&mut connection, &Handshake::CURRENT.encode()).is_err() {
+                        continue;
+                    }
+
+                    // send any templates and literals that have changed before the socket connected
+                    let messages: Vec<_> = {
+                        let file_map = file_map.lock().unwrap();
+                        let templates = file_map
                             .map
                             .values()
                             .flat_map(|v| v.templates.values().copied())
-                            .collect()
+                            .map(HotReloadMsg::UpdateTemplate);
+                        let literals = file_map
+                            .map
+                            .values()
+                            .flat_map(|v| v.literals.values().cloned())
+                            .map(HotReloadMsg::UpdateLiterals);
+                        templates.chain(literals).collect()
                     };
 
-                    for template in templates {
-                        if !send_msg(HotReloadMsg::UpdateTemplate(template), &mut connection) {
+                    for msg in messages {
+                        if !send_msg(&namespace, msg, &mut connection) {
                             continue;
                         }
                     }
@@ -280,6 +297,7 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
         let mut rebuild = {
             let aborted = aborted.clone();
             let channels = channels.clone();
+            let namespace = namespace.clone();
             move || {
                 if let Some(rebuild_callback) = &mut rebuild_with {
                     if log {
@@ -292,7 +310,7 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
                     }
 
                     for channel in &mut *channels.lock().unwrap() {
-                        send_msg(HotReloadMsg::Shutdown, channel);
+                        send_msg(&namespace, HotReloadMsg::Shutdown, channel);
                     }
 
                     return shutdown;
@@ -317,10 +335,10 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
                 .iter()
                 .filter(|path| {
                     // skip non rust files
-                    matches!(
+                    (matches!(
                         path.extension().and_then(|p| p.to_str()),
-                        Some("rs" | "toml" | "css" | "html" | "js")
-                    ) &&
+                        Some("rs" | "rsx" | "toml" | "css" | "html" | "js")
+                    ) || is_hot_reloadable_asset(path)) &&
                     // skip excluded paths
                     !excluded_paths.iter().any(|p| path.starts_with(p)) &&
                     // respect .gitignore
@@ -337,8 +355,22 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
 
             let mut channels = channels.lock().unwrap();
             for path in real_paths {
+                // assets linked from rsx can be swapped in place. Anything else might be pulled in with include_str!
+                if is_hot_reloadable_asset(path) && file_map.lock().unwrap().tracks_asset_file(path)
+                {
+                    let relative = path.strip_prefix(&crate_dir).unwrap_or(path);
+                    if let Some(msg) = HotReloadMsg::update_asset(path, relative.to_path_buf()) {
+                        send_to_all(&namespace, &mut channels, msg);
+                        continue;
+                    }
+                }
+
                 // if this file type cannot be hot reloaded, rebuild the application
-                if path.extension().and_then(|p| p.to_str()) != Some("rs") && rebuild() {
+                if !matches!(
+                    path.extension().and_then(|p| p.to_str()),
+                    Some("rs" | "rsx")
+                ) && rebuild()
+                {
                     return;
                 }
                 // find changes to the rsx in the file
@@ -347,22 +379,26 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
                     .unwrap()
                     .update_rsx(path, crate_dir.as_path());
 
+                let relative = path.strip_prefix(&crate_dir).unwrap_or(path);
+                if let Some(msg) = HotReloadMsg::report_error(relative, &changes) {
+                    send_to_all(&namespace, &mut channels, msg);
+                }
+
                 match changes {
-                    Ok(UpdateResult::UpdatedRsx(msgs)) => {
+                    Ok(UpdateResult::UpdatedRsx {
+                        templates,
+                        literals,
+                    }) => {
+                        let msgs = templates
+                            .into_iter()
+                            .map(HotReloadMsg::UpdateTemplate)
+                            .chain(literals.into_iter().map(HotReloadMsg::UpdateLiterals));
                         for msg in msgs {
-                            let mut i = 0;
-                            while i < channels.len() {
-                                let channel = &mut channels[i];
-                                if send_msg(HotReloadMsg::UpdateTemplate(msg), channel) {
-                                    i += 1;
-                                } else {
-                                    channels.remove(i);
-                                }
-                            }
+                            send_to_all(&namespace, &mut channels, msg);
                         }
                     }
 
-                    Ok(UpdateResult::NeedsRebuild) => {
+                    Ok(UpdateResult::NeedsRebuild { .. }) => {
                         drop(channels);
                         if rebuild() {
                             return;
@@ -382,16 +418,23 @@ pub fn init<Ctx: HotReloadingContext + Send + 'static>(cfg: Config<Ctx>) {
     });
 }
 
-fn send_msg(msg: HotReloadMsg, channel: &mut impl Write) -> bool {
-    if let Ok(msg) = serde_json::to_string(&msg) {
-        if channel.write_all(msg.as_bytes()).is_err() {
-            return false;
-        }
-        if channel.write_all(&[b'\n']).is_err() {
-            return false;
+/// Send a message to every connected app, dropping the connections that closed
+fn send_to_all(namespace: &Option<String>, channels: &mut Vec<impl Write>, msg: HotReloadMsg) {
+    let mut i = 0;
+    while i < channels.len() {
+        let channel = &mut channels[i];
+        if send_msg(namespace, msg.clone(), channel) {
+            i += 1;
+        } else {
+            channels.remove(i);
         }
-        true
-    } else {
-        false
+    }
+}
+
+fn send_msg(namespace: &Option<String>, msg: HotReloadMsg, channel: &mut impl Write) -> bool {
+    let msg = ScopedHotReloadMsg::new(namespace.clone(), msg);
+    match encode_message(&msg) {
+        Some(frame) => write_frame(channel, &frame).is_ok(),
+        None => false,
     }
 }
@@ -1,14 +1,26 @@
 use std::{
-    io::{BufRead, BufReader},
+    io::{Read, Write},
     path::PathBuf,
 };
 
-use dioxus_core::Template;
+pub use dioxus_core::HotReloadEvent;
+use dioxus_core::{HotLiterals, HotReloadError, Template};
 #[cfg(feature = "file_watcher")]
 pub use dioxus_html::HtmlCtx;
 use interprocess::local_socket::LocalSocketStream;
 use serde::{Deserialize, Serialize};
 
+mod asset;
+pub use asset::*;
+
+mod error;
+
+mod protocol;
+pub use protocol::*;
+
+mod remote;
+pub use remote::*;
+
 #[cfg(feature = "custom_file_watcher")]
 mod file_watcher;
 #[cfg(feature = "custom_file_watcher")]
@@ -28,19 +40,94 @@ pub const RECONNECT_SCRIPT: &str = include_str!("assets/autoreload.js");
 #[serde(bound(deserialize = "'de: 'static"))]
 pub enum HotReloadMsg {
     /// A template has been updated
-    UpdateTemplate(Template),
+    UpdateTemplate(#[serde(with = "protocol::native_template")] Template),
+
+    /// Literal component props in an rsx call have new values
+    UpdateLiterals(HotLiterals),
+
+    /// An asset linked from rsx! like a stylesheet or image has been updated
+    UpdateAsset {
+        /// The path the app loads the asset from, relative to the asset directory
+        path: PathBuf,
 
-    /// An asset discovered by rsx! has been updated
-    UpdateAsset(PathBuf),
+        /// The new contents of stylesheets, so they can be swapped without fetching them again. Other assets are
+        /// fetched again by the renderer
+        contents: Option<String>,
+    },
+
+    /// The last change couldn't be hot reloaded, or `None` once a later change was. Development tools like the dev
+    /// overlay show the error with [`dioxus_core::hot_reload_error`]
+    ReportError(Option<HotReloadError>),
+
+    /// The program is about to be rebuilt. The client should save the state components registered with
+    /// `add_snapshot_value` (like `use_restorable_signal`) so the new build can restore it.
+    ///
+    /// Desktop apps save the state to a file and shut down, the web saves it in the session storage of the page.
+    SaveState,
 
     /// The program needs to be recompiled, and the client should shut down
     Shutdown,
 }
 
+/// The environment variable the CLI sets on the apps it launches with the hot reloading namespace they belong to
+pub const NAMESPACE_ENV: &str = "DIOXUS_HOT_RELOAD_NAMESPACE";
+
+/// The hot reloading namespace this process belongs to
+///
+/// The CLI gives every app it serves its own namespace (the name of its package) so apps in the same workspace only
+/// receive their own templates. Apps started outside of the CLI have no namespace and accept every message on the
+/// socket they connect to.
+pub fn namespace() -> Option<String> {
+    std::env::var(NAMESPACE_ENV).ok()
+}
+
+/// The name of the local socket the hot reloading server for a namespace listens on
+pub fn socket_name(namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!("dioxusin-{namespace}"),
+        None => "dioxusin".to_string(),
+    }
+}
+
+/// A [`HotReloadMsg`] tagged with the namespace it was sent to. This is what is written to the hot reloading socket.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(bound(deserialize = "'de: 'static"))]
+pub struct ScopedHotReloadMsg {
+    /// The namespace of the app the message is for
+    pub namespace: Option<String>,
+
+    /// The message itself
+    pub msg: HotReloadMsg,
+}
+
+impl ScopedHotReloadMsg {
+    /// Tag a message with a namespace
+    pub fn new(namespace: Option<String>, msg: HotReloadMsg) -> Self {
+        Self { namespace, msg }
+    }
+
+    /// Check if a client subscribed to a namespace should receive this message
+    pub fn is_for(&self, namespace: Option<&str>) -> bool {
+        namespace.is_none() || self.namespace.as_deref() == namespace
+    }
+}
+
+/// Listen to what hot reloading does to the app: which templates were patched, how long patching them took, and which
+/// changes couldn't be hot reloaded and rebuild the app instead.
+///
+/// This is meant for development tools like editor status bars. See [`HotReloadEvent`] for the events.
+pub fn subscribe(listener: impl Fn(&HotReloadEvent) + Send + Sync + 'static) {
+    dioxus_core::subscribe_hot_reload(listener);
+}
+
 /// Connect to the hot reloading listener. The callback provided will be called every time a template change is detected
+///
+/// Only messages sent to the namespace of this process are passed to the callback.
 pub fn connect(callback: impl FnMut(HotReloadMsg) + Send + 'static) {
+    let socket_name = socket_name(namespace().as_deref());
+
     if cfg!(windows) {
-        connect_at(PathBuf::from("@dioxusin"), callback);
+        connect_at(PathBuf::from(format!("@{socket_name}")), callback);
     } else {
         // FIXME: this is falling back onto the current directory when not running under cargo, which is how the CLI runs this.
         // This needs to be fixed.
@@ -54,7 +141,7 @@ pub fn connect(callback: impl FnMut(HotReloadMsg) + Send + 'static) {
 
         // walk the path until we a find a socket named `dioxusin` inside that folder's target directory
         loop {
-            let maybe = path.join("target").join("dioxusin");
+            let maybe = path.join("target").join(&socket_name);
 
             if maybe.exists() {
                 path = maybe;
@@ -73,11 +160,15 @@ pub fn connect(callback: impl FnMut(HotReloadMsg) + Send + 'static) {
     }
 }
 
-pub fn connect_at(socket: PathBuf, mut callback: impl FnMut(HotReloadMsg) + Send + 'static) {
+pub fn connect_at(socket: PathBuf, callback: impl FnMut(HotReloadMsg) + Send + 'static) {
+    let namespace = namespace();
+
     std::thread::spawn(move || {
         // There might be a socket since the we're not running under the hot reloading server
         let stream = if cfg!(windows) {
-            LocalSocketStream::connect("@dioxusin")
+            // Windows sockets are named instead of living in the target directory
+            let name = socket.file_name().unwrap_or_default().to_string_lossy();
+            LocalSocketStream::connect(format!("@{}", name.trim_start_matches('@')))
         } else {
             LocalSocketStream::connect(socket.clone())
         };
@@ -89,24 +180,47 @@ pub fn connect_at(socket: PathBuf, mut callback: impl FnMut(HotReloadMsg) + Send
             return;
         };
 
-        let mut buf_reader = BufReader::new(socket);
+        read_messages(socket, namespace, callback);
+    });
+}
 
-        loop {
-            let mut buf = String::new();
+/// Read messages from a hot reloading server until the connection closes
+fn read_messages(
+    mut stream: impl Read + Write,
+    namespace: Option<String>,
+    mut callback: impl FnMut(HotReloadMsg),
+) {
+    match exchange_handshake(&mut stream) {
+        Ok(server) if server.is_compatible() => {}
+        Ok(server) => return incompatible_server(server),
+        Err(err) => {
+            println!("the hot reloading server didn't answer the handshake: {err}");
+            return;
+        }
+    }
 
-            if let Err(err) = buf_reader.read_line(&mut buf) {
-                if err.kind() != std::io::ErrorKind::WouldBlock {
-                    break;
-                }
-            }
+    // The server closed the connection
+    while let Ok(frame) = read_frame(&mut stream) {
+        if let Some(msg) = parse_message(frame, namespace.as_deref()) {
+            callback(msg);
+        }
+    }
+}
 
-            let Ok(template) = serde_json::from_str(Box::leak(buf.into_boxed_str())) else {
-                continue;
-            };
+/// Parse a message from the hot reloading server, or return `None` if it is invalid or meant for another namespace
+fn parse_message(frame: Vec<u8>, namespace: Option<&str>) -> Option<HotReloadMsg> {
+    let scoped = decode_message(frame)?;
 
-            callback(template);
-        }
-    });
+    // Another app in the workspace might share the socket, ignore anything that isn't meant for us
+    scoped.is_for(namespace).then_some(scoped.msg)
+}
+
+/// Tell the user why hot reloading doesn't work with a server built against another version of dioxus
+fn incompatible_server(server: Handshake) {
+    println!(
+        "hot reloading is disabled because the server speaks {server} and this app speaks {}. Changes rebuild the app instead until the dioxus-cli matches the version of dioxus the app uses",
+        Handshake::CURRENT
+    );
 }
 
 /// Start the hot reloading server with the current directory as the root
@@ -0,0 +1,382 @@
+//! The binary protocol apps and the hot reloading server speak over the local socket and device WebSockets
+//!
+//! The server sends a [`Handshake`] with the protocol and template format versions it was built with as soon as an app
+//! connects, and the app answers with its own. Every message after that is a [`ScopedHotReloadMsg`] encoded with
+//! bincode. Frames on the local socket are prefixed with their length as a little endian `u32`, WebSocket frames are
+//! binary messages.
+//!
+//! Templates are internally tagged with a `type` field in JSON, which the web client reads. bincode can't read
+//! internally tagged enums back, so templates are written with the plain enums in [`native_template`] instead.
+//!
+//! When the versions don't match, the app stops listening and the server rebuilds the app on every change instead of
+//! sending it templates it can't read.
+
+use crate::ScopedHotReloadMsg;
+use dioxus_core::TEMPLATE_FORMAT_VERSION;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::io::{Error, ErrorKind, Read, Write};
+
+/// The version of the hot reloading protocol. Bump it whenever a change to [`crate::HotReloadMsg`] changes how it
+/// serializes.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The largest frame an app accepts. Anything bigger is a corrupted stream, not a template
+const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// The versions one side of a hot reloading connection was built with
+///
+/// This is the first frame in both directions, and the one thing in the protocol that must never change how it
+/// serializes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    /// The version of the hot reloading protocol
+    pub protocol: u32,
+
+    /// The version of the serialized form of templates
+    pub template_format: u32,
+}
+
+impl Handshake {
+    /// The versions this build speaks
+    pub const CURRENT: Self = Self {
+        protocol: PROTOCOL_VERSION,
+        template_format: TEMPLATE_FORMAT_VERSION,
+    };
+
+    /// Check if the other side of a connection can read the messages this build sends
+    pub fn is_compatible(&self) -> bool {
+        *self == Self::CURRENT
+    }
+
+    /// Encode the handshake into a frame
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("handshakes always serialize")
+    }
+
+    /// Decode a handshake frame. Peers that don't speak this protocol send something that isn't one
+    pub fn decode(frame: &[u8]) -> std::io::Result<Self> {
+        bincode::deserialize(frame).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+}
+
+impl std::fmt::Display for Handshake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "protocol v{} with template format v{}",
+            self.protocol, self.template_format
+        )
+    }
+}
+
+/// Encode a message into a frame
+pub fn encode_message(msg: &ScopedHotReloadMsg) -> Option<Vec<u8>> {
+    bincode::serialize(msg).ok()
+}
+
+/// Decode a message frame
+///
+/// Messages may borrow from the frame for the rest of the program, so the frame is leaked.
+pub fn decode_message(frame: Vec<u8>) -> Option<ScopedHotReloadMsg> {
+    bincode::deserialize(Box::leak(frame.into_boxed_slice())).ok()
+}
+
+/// The representation of [`Template`](dioxus_core::Template)s in the binary protocol
+///
+/// Used with `#[serde(with)]` on [`crate::HotReloadMsg::UpdateTemplate`]. Human readable formats like JSON keep the
+/// internally tagged form of the template. Everything else mirrors the template types with externally tagged enums.
+/// The variants and fields must stay in the same order as the ones in `dioxus_core`.
+pub(crate) mod native_template {
+    use super::*;
+    use dioxus_core::{Template, TemplateAttribute, TemplateNode};
+
+    #[derive(Serialize, Deserialize)]
+    struct WireTemplate<'a> {
+        name: Cow<'a, str>,
+        roots: Vec<WireNode<'a>>,
+        node_paths: Vec<Cow<'a, [u8]>>,
+        attr_paths: Vec<Cow<'a, [u8]>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum WireNode<'a> {
+        Element {
+            tag: Cow<'a, str>,
+            namespace: Option<Cow<'a, str>>,
+            attrs: Vec<WireAttribute<'a>>,
+            children: Vec<WireNode<'a>>,
+        },
+        Text {
+            text: Cow<'a, str>,
+        },
+        Dynamic {
+            id: usize,
+        },
+        DynamicText {
+            id: usize,
+        },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum WireAttribute<'a> {
+        Static {
+            name: Cow<'a, str>,
+            value: Cow<'a, str>,
+            namespace: Option<Cow<'a, str>>,
+        },
+        Dynamic {
+            id: usize,
+        },
+    }
+
+    impl<'a> From<&'a Template> for WireTemplate<'a> {
+        fn from(template: &'a Template) -> Self {
+            Self {
+                name: template.name.into(),
+                roots: template.roots.iter().map(Into::into).collect(),
+                node_paths: template
+                    .node_paths
+                    .iter()
+                    .map(|path| (*path).into())
+                    .collect(),
+                attr_paths: template
+                    .attr_paths
+                    .iter()
+                    .map(|path| (*path).into())
+                    .collect(),
+            }
+        }
+    }
+
+    impl<'a> From<&'a TemplateNode> for WireNode<'a> {
+        fn from(node: &'a TemplateNode) -> Self {
+            match *node {
+                TemplateNode::Element {
+                    tag,
+                    namespace,
+                    attrs,
+                    children,
+                } => Self::Element {
+                    tag: tag.into(),
+                    namespace: namespace.map(Into::into),
+                    attrs: attrs.iter().map(Into::into).collect(),
+                    children: children.iter().map(Into::into).collect(),
+                },
+                TemplateNode::Text { text } => Self::Text { text: text.into() },
+                TemplateNode::Dynamic { id } => Self::Dynamic { id },
+                TemplateNode::DynamicText { id } => Self::DynamicText { id },
+            }
+        }
+    }
+
+    impl<'a> From<&'a TemplateAttribute> for WireAttribute<'a> {
+        fn from(attr: &'a TemplateAttribute) -> Self {
+            match *attr {
+                TemplateAttribute::Static {
+                    name,
+                    value,
+                    namespace,
+                } => Self::Static {
+                    name: name.into(),
+                    value: value.into(),
+                    namespace: namespace.map(Into::into),
+                },
+                TemplateAttribute::Dynamic { id } => Self::Dynamic { id },
+            }
+        }
+    }
+
+    // Templates live for the rest of the program, so everything they point to is leaked
+    fn leak_str(value: Cow<str>) -> &'static str {
+        Box::leak(value.into_owned().into_boxed_str())
+    }
+
+    fn leak_slice<T>(values: Vec<T>) -> &'static [T] {
+        Box::leak(values.into_boxed_slice())
+    }
+
+    impl WireTemplate<'_> {
+        fn leak(self) -> Template {
+            Template {
+                name: leak_str(self.name),
+                roots: leak_slice(self.roots.into_iter().map(WireNode::leak).collect()),
+                node_paths: leak_slice(
+                    self.node_paths
+                        .into_iter()
+                        .map(|path| leak_slice(path.into_owned()))
+                        .collect(),
+                ),
+                attr_paths: leak_slice(
+                    self.attr_paths
+                        .into_iter()
+                        .map(|path| leak_slice(path.into_owned()))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    impl WireNode<'_> {
+        fn leak(self) -> TemplateNode {
+            match self {
+                Self::Element {
+                    tag,
+                    namespace,
+                    attrs,
+                    children,
+                } => TemplateNode::Element {
+                    tag: leak_str(tag),
+                    namespace: namespace.map(leak_str),
+                    attrs: leak_slice(attrs.into_iter().map(WireAttribute::leak).collect()),
+                    children: leak_slice(children.into_iter().map(WireNode::leak).collect()),
+                },
+                Self::Text { text } => TemplateNode::Text {
+                    text: leak_str(text),
+                },
+                Self::Dynamic { id } => TemplateNode::Dynamic { id },
+                Self::DynamicText { id } => TemplateNode::DynamicText { id },
+            }
+        }
+    }
+
+    impl WireAttribute<'_> {
+        fn leak(self) -> TemplateAttribute {
+            match self {
+                Self::Static {
+                    name,
+                    value,
+                    namespace,
+                } => TemplateAttribute::Static {
+                    name: leak_str(name),
+                    value: leak_str(value),
+                    namespace: namespace.map(leak_str),
+                },
+                Self::Dynamic { id } => TemplateAttribute::Dynamic { id },
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(template: &Template, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            template.serialize(serializer)
+        } else {
+            WireTemplate::from(template).serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Template, D::Error>
+    where
+        D: Deserializer<'de>,
+        'de: 'static,
+    {
+        if deserializer.is_human_readable() {
+            Template::deserialize(deserializer)
+        } else {
+            WireTemplate::deserialize(deserializer).map(WireTemplate::leak)
+        }
+    }
+}
+
+/// Write a length prefixed frame to a stream
+pub fn write_frame(stream: &mut impl Write, frame: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(frame.len()).map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+    stream.write_all(&len.to_le_bytes())?;
+    stream.write_all(frame)?;
+    stream.flush()
+}
+
+/// Read a length prefixed frame from a stream
+pub fn read_frame(stream: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "hot reloading frame is too large",
+        ));
+    }
+
+    let mut frame = vec![0; len];
+    stream.read_exact(&mut frame)?;
+    Ok(frame)
+}
+
+/// Send the handshake of this build over a local socket and read the one of the other side
+pub fn exchange_handshake(stream: &mut (impl Read + Write)) -> std::io::Result<Handshake> {
+    write_frame(stream, &Handshake::CURRENT.encode())?;
+    Handshake::decode(&read_frame(stream)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HotReloadMsg;
+    use dioxus_core::{Template, TemplateAttribute, TemplateNode};
+
+    #[test]
+    fn templates_round_trip() {
+        let template = Template {
+            name: "src/main.rs:4:5:0",
+            roots: &[TemplateNode::Element {
+                tag: "div",
+                namespace: None,
+                attrs: &[TemplateAttribute::Static {
+                    name: "class",
+                    value: "card",
+                    namespace: None,
+                }],
+                children: &[TemplateNode::Text { text: "hello" }],
+            }],
+            node_paths: &[],
+            attr_paths: &[],
+        };
+        let msg = ScopedHotReloadMsg::new(
+            Some("app".to_string()),
+            HotReloadMsg::UpdateTemplate(template),
+        );
+
+        let mut stream = Vec::new();
+        write_frame(&mut stream, &encode_message(&msg).unwrap()).unwrap();
+        let frame = read_frame(&mut stream.as_slice()).unwrap();
+
+        let decoded = decode_message(frame).unwrap();
+        assert_eq!(decoded.namespace.as_deref(), Some("app"));
+        assert!(
+            matches!(decoded.msg, HotReloadMsg::UpdateTemplate(decoded) if decoded == template)
+        );
+    }
+
+    #[test]
+    fn json_templates_stay_internally_tagged() {
+        let template = Template {
+            name: "src/main.rs:4:5:0",
+            roots: &[TemplateNode::Dynamic { id: 0 }],
+            node_paths: &[&[0]],
+            attr_paths: &[],
+        };
+
+        // The web client reads the `type` of every node
+        let json = serde_json::to_string(&template).unwrap();
+        assert!(json.contains(r#"{"type":"Dynamic","id":0}"#));
+
+        let msg = serde_json::to_string(&HotReloadMsg::UpdateTemplate(template)).unwrap();
+        assert!(msg.contains(r#"{"type":"Dynamic","id":0}"#));
+    }
+
+    #[test]
+    fn mismatched_versions_are_incompatible() {
+        let frame = Handshake::CURRENT.encode();
+        assert!(Handshake::decode(&frame).unwrap().is_compatible());
+
+        let newer = Handshake {
+            protocol: PROTOCOL_VERSION,
+            template_format: TEMPLATE_FORMAT_VERSION + 1,
+        };
+        assert!(!Handshake::decode(&newer.encode()).unwrap().is_compatible());
+
+        // A server from before the handshake sends newline separated JSON
+        assert!(Handshake::decode(b"{").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Hot reloading over the network for apps running on phones and other devices
+//!
+//! `dx serve --device-port` serves hot reloading over a WebSocket next to the local socket. Apps connect to the address
+//! embedded in them when they were built, or find the server by broadcasting a discovery request on the local network.
+//! The server only accepts connections with the token embedded in the app, so other machines on the network can't
+//! read the source of the app.
+
+use crate::{incompatible_server, parse_message, Handshake, HotReloadMsg};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+use tungstenite::{stream::MaybeTlsStream, Message};
+
+/// The environment variable with the network address of the hot reloading server, for apps running on phones and other
+/// devices that can't reach the local socket.
+///
+/// `dx serve --device-port` sets it while building the app so the address is embedded in the build. Set it yourself to
+/// use another address, like `127.0.0.1:<port>` after forwarding the port over USB with `adb reverse`.
+pub const ADDRESS_ENV: &str = "DIOXUS_HOT_RELOAD_ADDRESS";
+
+/// The environment variable with the token the hot reloading server accepts network connections with.
+///
+/// `dx serve --device-port` sets it while building the app so the token is embedded in the build. The token is kept in
+/// the target directory so it stays the same between sessions and doesn't force a rebuild.
+pub const TOKEN_ENV: &str = "DIOXUS_HOT_RELOAD_TOKEN";
+
+/// The UDP port the hot reloading server answers discovery requests on
+pub const DISCOVERY_PORT: u16 = 8748;
+
+/// The message apps broadcast to find hot reloading servers on the local network
+pub const DISCOVERY_REQUEST: &[u8] = b"dioxus-hot-reload-discover";
+
+/// How long to wait for hot reloading servers to answer a discovery request
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The answer of a hot reloading server to a discovery request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DiscoveryResponse {
+    /// The namespace of the app the server hot reloads
+    pub namespace: Option<String>,
+
+    /// The port the server accepts WebSocket connections on. The address is the one the response came from
+    pub port: u16,
+}
+
+/// The network address of the hot reloading server that was embedded in this app when it was built
+pub fn embedded_address() -> Option<&'static str> {
+    option_env!("DIOXUS_HOT_RELOAD_ADDRESS")
+}
+
+/// The token of the hot reloading server that was embedded in this app when it was built
+pub fn embedded_token() -> Option<&'static str> {
+    option_env!("DIOXUS_HOT_RELOAD_TOKEN")
+}
+
+/// The url an app connects to the hot reloading server at
+pub fn websocket_url(address: SocketAddr, token: Option<&str>) -> String {
+    match token {
+        Some(token) => format!("ws://{address}/?token={token}"),
+        None => format!("ws://{address}/"),
+    }
+}
+
+/// Check if the url a client connected with carries the token of the server
+pub fn has_token(query: Option<&str>, token: &str) -> bool {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("token="))
+        .any(|candidate| constant_time_eq(candidate.as_bytes(), token.as_bytes()))
+}
+
+/// Compare every byte even after a mismatch, so the time the comparison takes doesn't leak how much of a guessed
+/// token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Broadcast a discovery request on the local network and collect the addresses of the hot reloading servers that
+/// answer within `timeout`
+pub fn discover(timeout: Duration) -> std::io::Result<Vec<SocketAddr>> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_broadcast(true)?;
+    socket.send_to(DISCOVERY_REQUEST, (Ipv4Addr::BROADCAST, DISCOVERY_PORT))?;
+
+    let mut servers = Vec::new();
+    let deadline = Instant::now() + timeout;
+    let mut buf = [0; 1024];
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        socket.set_read_timeout(Some(remaining.max(Duration::from_millis(1))))?;
+        let Ok((len, from)) = socket.recv_from(&mut buf) else {
+            break;
+        };
+        if let Ok(response) = serde_json::from_slice::<DiscoveryResponse>(&buf[..len]) {
+            let server = SocketAddr::new(from.ip(), response.port);
+            if !servers.contains(&server) {
+                servers.push(server);
+            }
+        }
+    }
+
+    Ok(servers)
+}
+
+/// Connect to the hot reloading server `dx serve --device-port` starts for apps running on other devices. The callback
+/// provided will be called every time a template change is detected
+///
+/// The app connects to the address embedded in it when it was built. If there is none, or it can't be reached, the app
+/// looks for servers on the local network and connects to the first one that accepts its token. Apps that weren't built
+/// by `dx serve --device-port` have no token and don't connect.
+pub fn connect_remote(callback: impl FnMut(HotReloadMsg) + Send + 'static) {
+    let Some(token) = embedded_token() else {
+        return;
+    };
+
+    std::thread::spawn(move || {
+        let embedded = embedded_address()
+            .and_then(|address| address.to_socket_addrs().ok())
+            .into_iter()
+            .flatten();
+        if let Some(socket) = connect_first(embedded, Some(token)) {
+            return read_websocket(socket, callback);
+        }
+
+        // The embedded address is out of date when the phone or the workstation moved to another network
+        let discovered = discover(DISCOVERY_TIMEOUT).unwrap_or_default();
+        match connect_first(discovered, Some(token)) {
+            Some(socket) => read_websocket(socket, callback),
+            None => println!(
+                "could not find a hot reloading server on the network, make sure `dx serve --device-port` is running"
+            ),
+        }
+    });
+}
+
+/// Connect to the first server that accepts the token
+fn connect_first(
+    addresses: impl IntoIterator<Item = SocketAddr>,
+    token: Option<&str>,
+) -> Option<tungstenite::WebSocket<MaybeTlsStream<TcpStream>>> {
+    addresses.into_iter().find_map(|address| {
+        tungstenite::connect(websocket_url(address, token))
+            .ok()
+            .map(|(socket, _)| socket)
+    })
+}
+
+/// Connect to a hot reloading server over a WebSocket. The callback provided will be called every time a template
+/// change is detected
+pub fn connect_websocket(
+    address: SocketAddr,
+    token: Option<&'static str>,
+    callback: impl FnMut(HotReloadMsg) + Send + 'static,
+) {
+    std::thread::spawn(
+        move || match tungstenite::connect(websocket_url(address, token)) {
+            Ok((socket, _)) => read_websocket(socket, callback),
+            Err(error) => {
+                println!("could not connect to the hot reloading server at {address}: {error}")
+            }
+        },
+    );
+}
+
+/// Read messages from a hot reloading server until the connection closes
+fn read_websocket<S: std::io::Read + std::io::Write>(
+    mut socket: tungstenite::WebSocket<S>,
+    mut callback: impl FnMut(HotReloadMsg),
+) {
+    let namespace = crate::namespace();
+
+    // The server sends its handshake first
+    let server = match socket.read() {
+        Ok(Message::Binary(frame)) => Handshake::decode(&frame),
+        _ => return println!("the hot reloading server didn't send a handshake"),
+    };
+    if socket
+        .send(Message::Binary(Handshake::CURRENT.encode()))
+        .is_err()
+    {
+        return;
+    }
+    match server {
+        Ok(server) if server.is_compatible() => {}
+        Ok(server) => return incompatible_server(server),
+        Err(err) => return println!("the hot reloading server sent an invalid handshake: {err}"),
+    }
+
+    loop {
+        match socket.read() {
+            Ok(Message::Binary(frame)) => {
+                if let Some(msg) = parse_message(frame, namespace.as_deref()) {
+                    callback(msg);
+                }
+            }
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_exact_token_is_accepted() {
+        let token = "0123456789abcdef";
+        assert!(has_token(Some("token=0123456789abcdef"), token));
+        assert!(has_token(Some("build=1&token=0123456789abcdef"), token));
+        assert!(!has_token(Some("token=0123456789abcdee"), token));
+        assert!(!has_token(Some("token=0123456789abcde"), token));
+        assert!(!has_token(Some("token=0123456789abcdef0"), token));
+        assert!(!has_token(Some("token="), token));
+        assert!(!has_token(None, token));
+    }
+}
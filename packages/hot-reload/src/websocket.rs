@@ -12,7 +12,7 @@ use axum::{
     routing::get,
     Extension, Router,
 };
-use dioxus_core::Template;
+use dioxus_core::{HotLiterals, Template};
 use futures_util::{pin_mut, FutureExt};
 use tokio::sync::broadcast;
 
@@ -135,6 +135,9 @@ pub struct HotReloadReceiver {
 
     /// Any template updates that have happened since the last full render
     template_updates: SharedTemplateUpdates,
+
+    /// The latest literal component props for every rsx call they changed in since the last full render
+    literal_updates: Arc<Mutex<HashMap<String, HotLiterals>>>,
 }
 
 impl HotReloadReceiver {
@@ -150,6 +153,7 @@ impl Default for HotReloadReceiver {
             messages: broadcast::channel(100).0,
             reload: broadcast::channel(100).0,
             template_updates: Default::default(),
+            literal_updates: Default::default(),
         }
     }
 }
@@ -167,12 +171,29 @@ impl HotReloadReceiver {
             .collect()
     }
 
+    /// Find the latest literal component props of every rsx call they changed in since the last full render
+    pub fn all_modified_literals(&self) -> Vec<HotLiterals> {
+        self.literal_updates
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect()
+    }
+
     /// Send a hot reloading message to the client
     pub fn send_message(&self, msg: HotReloadMsg) {
         // Before we send the message, update the list of changed templates
-        if let HotReloadMsg::UpdateTemplate(template) = msg {
-            let mut template_updates = self.template_updates.lock().unwrap();
-            template_updates.insert(template.name, template);
+        match &msg {
+            HotReloadMsg::UpdateTemplate(template) => {
+                let mut template_updates = self.template_updates.lock().unwrap();
+                template_updates.insert(template.name, *template);
+            }
+            HotReloadMsg::UpdateLiterals(literals) => {
+                let mut literal_updates = self.literal_updates.lock().unwrap();
+                literal_updates.insert(literals.location.clone(), literals.clone());
+            }
+            _ => {}
         }
         if let Err(err) = self.messages.send(msg) {
             tracing::error!("Failed to send hot reload message: {}", err);
@@ -219,6 +240,9 @@ async fn hotreload_loop(
             .send(Message::Text(serde_json::to_string(&template).unwrap()))
             .await?;
     }
+    for literals in state.all_modified_literals() {
+        socket.send(literals_message(&literals)).await?;
+    }
 
     loop {
         let msg = {
@@ -248,9 +272,15 @@ async fn hotreload_loop(
                 HotReloadMsg::UpdateTemplate(template) => {
                     Message::Text(serde_json::to_string(&template).unwrap())
                 }
-                HotReloadMsg::UpdateAsset(asset) => {
-                    Message::Text(format!("reload-asset: {}", asset.display()))
+                HotReloadMsg::UpdateLiterals(literals) => literals_message(&literals),
+                HotReloadMsg::UpdateAsset { path, .. } => {
+                    Message::Text(format!("reload-asset: {}", path.display()))
                 }
+                HotReloadMsg::ReportError(error) => Message::Text(format!(
+                    "hot-reload-error: {}",
+                    serde_json::to_string(&error).unwrap()
+                )),
+                HotReloadMsg::SaveState => Message::Text("save-state".to_string()),
                 HotReloadMsg::Shutdown => {
                     tracing::info!("🔥 Hot Reload WebSocket shutting down");
                     break;
@@ -264,6 +294,14 @@ async fn hotreload_loop(
     Ok(())
 }
 
+/// Literal updates are tagged so the client can tell them apart from templates
+fn literals_message(literals: &HotLiterals) -> Message {
+    Message::Text(format!(
+        "hot-literals: {}",
+        serde_json::to_string(literals).unwrap()
+    ))
+}
+
 pub(crate) fn forward_cli_hot_reload() -> HotReloadReceiver {
     let hot_reload_state = HotReloadReceiver::default();
 
@@ -0,0 +1,135 @@
+//! Screen reader announcements through live regions managed by dioxus.
+//!
+//! [`use_announcer`] returns an [`Announcer`] that writes messages into a visually hidden `aria-live` region. The
+//! regions are created the first time they are needed. Server rendered pages can include [`LIVE_REGIONS_HTML`] so the
+//! regions already exist when the first message is announced, which some screen readers require.
+
+use crate::eval::EvalProvider;
+use dioxus_core::prelude::*;
+use std::rc::Rc;
+
+/// How urgently a screen reader should read an announcement
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Politeness {
+    /// Read the message once the user is idle, like a toast or "10 items loaded"
+    #[default]
+    Polite,
+    /// Interrupt the user to read the message, for errors and other time sensitive updates
+    Assertive,
+}
+
+impl Politeness {
+    /// The value of the `aria-live` attribute for this politeness
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+
+    fn role(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "status",
+            Politeness::Assertive => "alert",
+        }
+    }
+}
+
+/// Hides the live regions visually while keeping them in the accessibility tree
+const VISUALLY_HIDDEN: &str = "position:absolute;width:1px;height:1px;margin:-1px;padding:0;overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0;";
+
+/// The live regions [`Announcer`] writes to. Include this in the body of server rendered pages.
+pub const LIVE_REGIONS_HTML: &str = concat!(
+    r#"<div id="dioxus-announcer-polite" role="status" aria-live="polite" aria-atomic="true" style="position:absolute;width:1px;height:1px;margin:-1px;padding:0;overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0;"></div>"#,
+    r#"<div id="dioxus-announcer-assertive" role="alert" aria-live="assertive" aria-atomic="true" style="position:absolute;width:1px;height:1px;margin:-1px;padding:0;overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0;"></div>"#
+);
+
+/// Posts announcements to screen readers. Get one with [`use_announcer`].
+#[derive(Clone)]
+pub struct Announcer {
+    provider: Option<Rc<dyn EvalProvider>>,
+}
+
+impl Announcer {
+    /// Announce a message once the user is idle
+    pub fn polite(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Polite)
+    }
+
+    /// Announce a message immediately, interrupting whatever the screen reader is reading
+    pub fn assertive(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Assertive)
+    }
+
+    /// Announce a message with the given politeness
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) {
+        // Renderers without javascript (like ssr) have nothing to announce to
+        let Some(provider) = &self.provider else {
+            return;
+        };
+        let Ok(message) = serde_json::to_string(&message.into()) else {
+            return;
+        };
+        _ = provider.new_evaluator(announce_script(&message, politeness));
+    }
+}
+
+fn announce_script(message: &str, politeness: Politeness) -> String {
+    let politeness_str = politeness.as_str();
+    let role = politeness.role();
+    format!(
+        r#"{{
+            const id = "dioxus-announcer-{politeness_str}";
+            let region = document.getElementById(id);
+            if (!region) {{
+                region = document.createElement("div");
+                region.id = id;
+                region.setAttribute("role", "{role}");
+                region.setAttribute("aria-live", "{politeness_str}");
+                region.setAttribute("aria-atomic", "true");
+                region.setAttribute("style", "{VISUALLY_HIDDEN}");
+                document.body.appendChild(region);
+            }}
+            // Clear the region first so the same message is announced again if it repeats
+            region.textContent = "";
+            setTimeout(() => {{ region.textContent = {message}; }}, 50);
+        }}"#
+    )
+}
+
+/// Get an [`Announcer`] that reads messages to screen reader users.
+///
+/// Use it for updates that are only visible on screen, like a toast appearing or a list finishing loading.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Inbox() -> Element {
+///     let announcer = use_announcer();
+///     rsx! {
+///         button {
+///             onclick: move |_| announcer.polite("3 new messages loaded"),
+///             "Refresh"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_announcer() -> Announcer {
+    use_hook(|| Announcer {
+        provider: try_consume_context::<Rc<dyn EvalProvider>>(),
+    })
+}
+
+#[test]
+fn live_regions_match_the_script() {
+    for politeness in [Politeness::Polite, Politeness::Assertive] {
+        let region = format!(
+            r#"<div id="dioxus-announcer-{politeness}" role="{role}" aria-live="{politeness}" aria-atomic="true" style="{VISUALLY_HIDDEN}"></div>"#,
+            politeness = politeness.as_str(),
+            role = politeness.role(),
+        );
+        assert!(LIVE_REGIONS_HTML.contains(&region));
+    }
+
+    let script = announce_script(r#""say \"hi\"""#, Politeness::Assertive);
+    assert!(script.contains(r#"region.textContent = "say \"hi\"";"#));
+}
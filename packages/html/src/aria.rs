@@ -0,0 +1,170 @@
+//! Typed values for enumerated ARIA attributes
+//!
+//! Every ARIA attribute still accepts a string, but enumerated attributes can also be set with one of these enums to
+//! make sure the value is a state the attribute understands:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! rsx! {
+//!     div { aria_live: AriaLive::Polite, "3 new messages" }
+//!     button { aria_haspopup: AriaHasPopup::Menu, aria_pressed: "mixed", "Options" }
+//! };
+//! ```
+//!
+//! Static strings are checked against the same values at compile time, so `aria_live: "politely"` is a warning.
+//! Attributes that only accept `true` or `false` take a `bool`.
+
+use std::fmt::{Display, Formatter};
+
+use dioxus_core::{prelude::IntoAttributeValue, AttributeValue};
+
+use crate::attribute_values::str_eq;
+
+macro_rules! aria_values {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident for $($attribute:literal)|+ {
+                $(
+                    $(#[$variant_attr:meta])*
+                    $variant:ident = $value:literal,
+                )*
+            }
+        )*
+    ) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $(
+                    $(#[$variant_attr])*
+                    $variant,
+                )*
+            }
+
+            impl $name {
+                /// Get the value of the attribute as it is written in html
+                pub const fn as_str(&self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $value,)*
+                    }
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl IntoAttributeValue for $name {
+                fn into_value(self) -> AttributeValue {
+                    AttributeValue::Text(self.as_str().to_string())
+                }
+            }
+        )*
+
+        /// Returns the values an enumerated ARIA attribute accepts, or `None` if it accepts any value
+        pub(crate) const fn allowed_aria_values(attribute: &str) -> Option<&'static [&'static str]> {
+            $(
+                if $(str_eq(attribute, $attribute))||+ {
+                    return Some(&[$($value),*]);
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+aria_values! {
+    /// Values for [`aria-autocomplete`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-autocomplete)
+    AriaAutocomplete for "aria-autocomplete" {
+        Inline = "inline",
+        List = "list",
+        Both = "both",
+        None = "none",
+    }
+
+    /// Values for [`aria-checked`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-checked)
+    /// and [`aria-pressed`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-pressed)
+    AriaTristate for "aria-checked" | "aria-pressed" {
+        True = "true",
+        False = "false",
+        /// The element is partially checked or pressed
+        Mixed = "mixed",
+        Undefined = "undefined",
+    }
+
+    /// Values for [`aria-current`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-current)
+    AriaCurrent for "aria-current" {
+        Page = "page",
+        Step = "step",
+        Location = "location",
+        Date = "date",
+        Time = "time",
+        True = "true",
+        False = "false",
+    }
+
+    /// Values for [`aria-haspopup`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-haspopup)
+    AriaHasPopup for "aria-haspopup" {
+        False = "false",
+        True = "true",
+        Menu = "menu",
+        Listbox = "listbox",
+        Tree = "tree",
+        Grid = "grid",
+        Dialog = "dialog",
+    }
+
+    /// Values for [`aria-invalid`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-invalid)
+    AriaInvalid for "aria-invalid" {
+        Grammar = "grammar",
+        False = "false",
+        Spelling = "spelling",
+        True = "true",
+    }
+
+    /// Values for [`aria-live`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-live)
+    AriaLive for "aria-live" {
+        Assertive = "assertive",
+        Off = "off",
+        Polite = "polite",
+    }
+
+    /// Values for [`aria-orientation`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-orientation)
+    AriaOrientation for "aria-orientation" {
+        Horizontal = "horizontal",
+        Vertical = "vertical",
+        Undefined = "undefined",
+    }
+
+    /// Values for [`aria-sort`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-sort)
+    AriaSort for "aria-sort" {
+        Ascending = "ascending",
+        Descending = "descending",
+        None = "none",
+        Other = "other",
+    }
+
+    /// Values for ARIA states that are either true, false or not applicable, like
+    /// [`aria-expanded`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-expanded)
+    AriaBooleanOrUndefined for "aria-expanded" | "aria-grabbed" | "aria-hidden" | "aria-selected" {
+        True = "true",
+        False = "false",
+        Undefined = "undefined",
+    }
+}
+
+/// ARIA attributes that only accept `"true"` or `"false"`
+pub(crate) const fn is_true_false_aria_attribute(attribute: &str) -> bool {
+    str_eq(attribute, "aria-atomic")
+        || str_eq(attribute, "aria-busy")
+        || str_eq(attribute, "aria-disabled")
+        || str_eq(attribute, "aria-modal")
+        || str_eq(attribute, "aria-multiline")
+        || str_eq(attribute, "aria-multiselectable")
+        || str_eq(attribute, "aria-readonly")
+        || str_eq(attribute, "aria-required")
+}
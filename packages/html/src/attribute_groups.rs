@@ -127,6 +127,17 @@ macro_rules! mod_methods {
                 )?
                 pub const $name: AttributeDiscription = mod_methods! { $name $(: $js_name)? $(in $ns)?; };
             )*
+
+            /// Look up one of these attributes by the name it is written with in rsx. Used by custom elements.
+            #[doc(hidden)]
+            pub const fn __attribute(__name: &str) -> Option<AttributeDiscription> {
+                $(
+                    if crate::attribute_values::str_eq(__name, stringify!($name)) {
+                        return Some($name);
+                    }
+                )*
+                None
+            }
         }
 
         #[cfg(feature = "hot-reload-context")]
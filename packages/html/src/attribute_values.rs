@@ -0,0 +1,134 @@
+//! Allowed values for enumerated attributes
+//!
+//! Attributes in the element definitions are tagged with the kind of value they accept. Some of those kinds only accept
+//! a fixed set of keywords. The rsx macro checks static string literals against these lists at compile time through
+//! the `__is_valid_attribute_value` function generated for every element, and warns about values that aren't listed.
+//!
+//! Keywords are matched ASCII case-insensitively, like the browser does.
+
+/// Returns the keywords an attribute of the given kind accepts, or `None` if it accepts any value
+pub(crate) const fn allowed_values(kind: &str) -> Option<&'static [&'static str]> {
+    if str_eq(kind, "InputType") {
+        Some(&[
+            "button",
+            "checkbox",
+            "color",
+            "date",
+            "datetime-local",
+            "email",
+            "file",
+            "hidden",
+            "image",
+            "month",
+            "number",
+            "password",
+            "radio",
+            "range",
+            "reset",
+            "search",
+            "submit",
+            "tel",
+            "text",
+            "time",
+            "url",
+            "week",
+        ])
+    } else if str_eq(kind, "ButtonType") {
+        Some(&["submit", "reset", "button"])
+    } else if str_eq(kind, "OnOff") {
+        Some(&["on", "off"])
+    } else if str_eq(kind, "Direction") {
+        Some(&["ltr", "rtl", "auto"])
+    } else if str_eq(kind, "TrueFalse") {
+        Some(&["true", "false"])
+    } else {
+        None
+    }
+}
+
+/// Check a value against the keywords an attribute of the given kind accepts
+pub const fn is_valid_value(kind: &str, value: &str) -> bool {
+    match allowed_values(kind) {
+        Some(allowed) => is_allowed(allowed, value),
+        None => true,
+    }
+}
+
+const fn is_allowed(allowed: &[&str], value: &str) -> bool {
+    let mut i = 0;
+    while i < allowed.len() {
+        if str_eq_ignore_ascii_case(allowed[i], value) {
+            return true;
+        }
+        i += 1;
+    }
+
+    false
+}
+
+/// Check a value for one of the global attributes shared by every html element
+pub const fn is_valid_global_attribute_value(attribute: &str, value: &str) -> bool {
+    if str_eq(attribute, "dir") {
+        return is_valid_value("Direction", value);
+    }
+
+    if crate::aria::is_true_false_aria_attribute(attribute) {
+        return is_valid_value("TrueFalse", value);
+    }
+
+    match crate::aria::allowed_aria_values(attribute) {
+        Some(allowed) => is_allowed(allowed, value),
+        None => true,
+    }
+}
+
+pub const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+const fn str_eq_ignore_ascii_case(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if !a[i].eq_ignore_ascii_case(&b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+#[test]
+fn enumerated_values() {
+    assert!(is_valid_value("InputType", "checkbox"));
+    assert!(is_valid_value("InputType", "DateTime-Local"));
+    assert!(!is_valid_value("InputType", "chekbox"));
+    assert!(is_valid_value("ButtonType", "submit"));
+    assert!(!is_valid_value("ButtonType", "text"));
+    assert!(is_valid_value("String", "anything"));
+    assert!(is_valid_global_attribute_value("dir", "rtl"));
+    assert!(!is_valid_global_attribute_value("dir", "left"));
+    assert!(is_valid_global_attribute_value("aria-live", "polite"));
+    assert!(!is_valid_global_attribute_value("aria-live", "politely"));
+    assert!(is_valid_global_attribute_value("aria-pressed", "mixed"));
+    assert!(!is_valid_global_attribute_value("aria-busy", "yes"));
+    assert!(is_valid_global_attribute_value("aria-label", "anything"));
+}
@@ -0,0 +1,332 @@
+//! Values for the `class` and `style` attributes that compose without string concatenation
+//!
+//! Component libraries often take a `class` or `style` prop and add their own on top of it. Joining the strings by
+//! hand is easy to get wrong: a missing space merges two classes, a missing semicolon breaks a declaration and the same
+//! class ends up in the attribute twice. [`Class`] and [`Style`] keep the tokens and declarations apart and join them
+//! when they are rendered.
+
+use dioxus_core::prelude::IntoAttributeValue;
+use dioxus_core::AttributeValue;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign};
+
+/// A set of classes for the `class` attribute
+///
+/// Classes are split on whitespace and every class is only kept once, in the order they were first added.
+///
+/// ```rust
+/// # use dioxus_html::Class;
+/// let class = Class::from("btn  btn-primary") + "btn-large btn";
+/// assert_eq!(class.to_string(), "btn btn-primary btn-large");
+/// ```
+///
+/// Take a `Class` prop to let users add their own classes:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn Button(#[props(into, default)] class: Class, children: Element) -> Element {
+///     rsx! {
+///         button { class: Class::from("btn") + class, {children} }
+///     }
+/// }
+///
+/// fn app() -> Element {
+///     rsx! {
+///         Button { class: "btn-primary", "Save" }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Class {
+    classes: Vec<String>,
+}
+
+impl Class {
+    /// Create an empty set of classes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the classes that aren't in the set yet
+    pub fn extend(&mut self, classes: impl Into<Class>) {
+        for class in classes.into().classes {
+            if !self.contains(&class) {
+                self.classes.push(class);
+            }
+        }
+    }
+
+    /// Remove a class from the set
+    pub fn remove(&mut self, class: &str) {
+        self.classes.retain(|c| c != class);
+    }
+
+    /// Check if the set contains a class
+    pub fn contains(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+
+    /// Check if there are no classes in the set
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Iterate over the classes in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.classes.iter().map(String::as_str)
+    }
+}
+
+impl From<&str> for Class {
+    fn from(classes: &str) -> Self {
+        let mut class = Self::new();
+        for token in classes.split_ascii_whitespace() {
+            if !class.contains(token) {
+                class.classes.push(token.to_string());
+            }
+        }
+        class
+    }
+}
+
+impl From<String> for Class {
+    fn from(classes: String) -> Self {
+        Self::from(classes.as_str())
+    }
+}
+
+impl From<&String> for Class {
+    fn from(classes: &String) -> Self {
+        Self::from(classes.as_str())
+    }
+}
+
+impl<T: Into<Class>> From<Option<T>> for Class {
+    fn from(classes: Option<T>) -> Self {
+        classes.map(Into::into).unwrap_or_default()
+    }
+}
+
+impl<T: Into<Class>> FromIterator<T> for Class {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut class = Self::new();
+        for classes in iter {
+            class.extend(classes);
+        }
+        class
+    }
+}
+
+impl<T: Into<Class>> Add<T> for Class {
+    type Output = Class;
+
+    fn add(mut self, classes: T) -> Self::Output {
+        self.extend(classes);
+        self
+    }
+}
+
+impl<T: Into<Class>> AddAssign<T> for Class {
+    fn add_assign(&mut self, classes: T) {
+        self.extend(classes);
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.classes.join(" "))
+    }
+}
+
+impl From<Class> for String {
+    fn from(class: Class) -> Self {
+        class.to_string()
+    }
+}
+
+impl IntoAttributeValue for Class {
+    fn into_value(self) -> AttributeValue {
+        AttributeValue::Text(self.to_string())
+    }
+}
+
+/// A list of css declarations for the `style` attribute
+///
+/// Setting a property that is already in the list replaces the old value, so the last value of a property wins like
+/// it does in css. Property names are case-insensitive, except for custom properties.
+///
+/// ```rust
+/// # use dioxus_html::Style;
+/// let style = Style::from("color: red; padding: 4px") + "color: blue;margin: 0";
+/// assert_eq!(style.to_string(), "padding: 4px; color: blue; margin: 0;");
+/// assert_eq!(style.get("color"), Some("blue"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    declarations: Vec<(String, String)>,
+}
+
+impl Style {
+    /// Create an empty list of declarations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a property, replacing its old value
+    pub fn set(&mut self, property: impl AsRef<str>, value: impl AsRef<str>) {
+        let property = normalize_property(property.as_ref());
+        let value = value.as_ref().trim();
+        if property.is_empty() || value.is_empty() {
+            return;
+        }
+        self.remove(&property);
+        self.declarations.push((property, value.to_string()));
+    }
+
+    /// Get the value of a property
+    pub fn get(&self, property: &str) -> Option<&str> {
+        let property = normalize_property(property);
+        self.declarations
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Remove a property
+    pub fn remove(&mut self, property: &str) {
+        let property = normalize_property(property);
+        self.declarations.retain(|(p, _)| *p != property);
+    }
+
+    /// Add declarations, replacing the values of properties that are already set
+    pub fn extend(&mut self, style: impl Into<Style>) {
+        for (property, value) in style.into().declarations {
+            self.set(property, value);
+        }
+    }
+
+    /// Check if there are no declarations
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    /// Iterate over the properties and their values in the order they were set
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations
+            .iter()
+            .map(|(property, value)| (property.as_str(), value.as_str()))
+    }
+}
+
+/// Custom properties are case-sensitive, the rest are not
+fn normalize_property(property: &str) -> String {
+    let property = property.trim();
+    match property.starts_with("--") {
+        true => property.to_string(),
+        false => property.to_ascii_lowercase(),
+    }
+}
+
+/// Split declarations on the semicolons that aren't in quotes or parentheses, like `url("a;b")`
+fn split_declarations(style: &str) -> Vec<&str> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    let mut declarations = Vec::new();
+    for (i, c) in style.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push(&style[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push(&style[start..]);
+    declarations
+}
+
+impl From<&str> for Style {
+    fn from(style: &str) -> Self {
+        let mut parsed = Self::new();
+        for declaration in split_declarations(style) {
+            if let Some((property, value)) = declaration.split_once(':') {
+                parsed.set(property, value);
+            }
+        }
+        parsed
+    }
+}
+
+impl From<String> for Style {
+    fn from(style: String) -> Self {
+        Self::from(style.as_str())
+    }
+}
+
+impl From<&String> for Style {
+    fn from(style: &String) -> Self {
+        Self::from(style.as_str())
+    }
+}
+
+impl<T: Into<Style>> From<Option<T>> for Style {
+    fn from(style: Option<T>) -> Self {
+        style.map(Into::into).unwrap_or_default()
+    }
+}
+
+impl<P: AsRef<str>, V: AsRef<str>> FromIterator<(P, V)> for Style {
+    fn from_iter<I: IntoIterator<Item = (P, V)>>(iter: I) -> Self {
+        let mut style = Self::new();
+        for (property, value) in iter {
+            style.set(property, value);
+        }
+        style
+    }
+}
+
+impl<T: Into<Style>> Add<T> for Style {
+    type Output = Style;
+
+    fn add(mut self, style: T) -> Self::Output {
+        self.extend(style);
+        self
+    }
+}
+
+impl<T: Into<Style>> AddAssign<T> for Style {
+    fn add_assign(&mut self, style: T) {
+        self.extend(style);
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (property, value)) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{property}: {value};")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Style> for String {
+    fn from(style: Style) -> Self {
+        style.to_string()
+    }
+}
+
+impl IntoAttributeValue for Style {
+    fn into_value(self) -> AttributeValue {
+        AttributeValue::Text(self.to_string())
+    }
+}
@@ -0,0 +1,188 @@
+//! Typed attributes and events for custom elements
+//!
+//! Custom elements (web components) are written in rsx with a hyphenated tag name like `my-widget {}`. By default they
+//! accept any attribute. If you declare them with the [`custom_elements!`](crate::custom_elements!) macro, rsx checks
+//! their attributes, static attribute values and events at compile time just like it does for built-in elements.
+//!
+//! rsx looks up declared elements in a module named `custom_elements` in the scope it is called from. This module is
+//! the fallback that the prelude exports when no custom elements are declared.
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::attribute_values::{is_valid_global_attribute_value, is_valid_value, str_eq};
+    pub use dioxus_core;
+}
+
+/// The module rsx uses for custom elements that were never declared. It accepts any attribute.
+#[doc(hidden)]
+pub mod __untyped {
+    use crate::AttributeDiscription;
+
+    pub const fn __attribute(name: &'static str) -> Option<AttributeDiscription> {
+        Some((name, None, false))
+    }
+
+    pub const fn __is_valid_attribute_value(_attribute: &str, _value: &str) -> bool {
+        true
+    }
+
+    pub mod events {
+        pub use crate::events::*;
+    }
+}
+
+/// Declare custom elements with typed attributes and events.
+///
+/// Each element has a rust name, the tag name it is written with in rsx and html, a list of attributes and an
+/// optional list of events. Attributes are declared like the attributes of built-in elements: a name, the kind of value
+/// they accept and optionally the name of the attribute in html. Events are declared with their name and the type of
+/// data the handler receives.
+///
+/// Declared elements accept their own attributes and the global attributes every html element accepts. Using any other
+/// attribute is a compile error, and a static value an enumerated attribute doesn't accept is a warning.
+///
+/// rsx looks up declared elements in a module named `custom_elements`, so the macro needs to be called inside of a
+/// module with that name:
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// mod custom_elements {
+///     dioxus::html::custom_elements! {
+///         /// A color picker web component
+///         color_picker "color-picker" {
+///             value: String,
+///             mode: OnOff,
+///             swatch_count: String "swatch-count",
+///         } events {
+///             oncolorchange: FormData,
+///         };
+///     }
+/// }
+///
+/// fn app() -> Element {
+///     rsx! {
+///         color-picker {
+///             class: "picker",
+///             value: "#ff0000",
+///             mode: "on",
+///             swatch_count: "8",
+///             oncolorchange: move |event| println!("{}", event.value()),
+///         }
+///     }
+/// }
+/// ```
+///
+/// To use the custom elements in other modules, import the module with `use crate::path::to::custom_elements;`.
+#[macro_export]
+macro_rules! custom_elements {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident $tag:literal {
+                $(
+                    $(#[$attr_method:meta])*
+                    $fil:ident: $vil:ident $($js_name:literal)?,
+                )*
+            } $(events {
+                $(
+                    $(#[$event_attr:meta])*
+                    $event:ident: $data:ty,
+                )*
+            })?;
+        )*
+    ) => {
+            $(
+                $(#[$attr])*
+                #[allow(non_upper_case_globals, non_snake_case, dead_code)]
+                pub mod $name {
+                    pub use $crate::global_attributes::*;
+
+                    pub const TAG_NAME: &'static str = $tag;
+                    pub const NAME_SPACE: Option<&'static str> = None;
+
+                    $(
+                        $(#[$attr_method])*
+                        pub const $fil: $crate::AttributeDiscription =
+                            ($crate::custom_elements!(@name $fil $($js_name)?), None, false);
+                    )*
+
+                    #[doc(hidden)]
+                    pub const fn __attribute(__name: &str) -> Option<$crate::AttributeDiscription> {
+                        $(
+                            if $crate::custom_elements::__private::str_eq(__name, stringify!($fil)) {
+                                return Some($fil);
+                            }
+                        )*
+
+                        $crate::global_attributes::__attribute(__name)
+                    }
+
+                    #[doc(hidden)]
+                    pub const fn __is_valid_attribute_value(__attribute: &str, __value: &str) -> bool {
+                        $(
+                            if $crate::custom_elements::__private::str_eq(__attribute, $fil.0) {
+                                return $crate::custom_elements::__private::is_valid_value(stringify!($vil), __value);
+                            }
+                        )*
+
+                        $crate::custom_elements::__private::is_valid_global_attribute_value(__attribute, __value)
+                    }
+
+                    /// The events this element emits along with the events every html element emits
+                    pub mod events {
+                        pub use $crate::events::*;
+
+                        $($(
+                            $(#[$event_attr])*
+                            #[inline]
+                            pub fn $event<__Marker>(
+                                _f: impl $crate::custom_elements::__private::dioxus_core::prelude::SuperInto<
+                                    $crate::custom_elements::__private::dioxus_core::prelude::EventHandler<
+                                        $crate::custom_elements::__private::dioxus_core::Event<$data>,
+                                    >,
+                                    __Marker,
+                                >,
+                            ) -> $crate::custom_elements::__private::dioxus_core::Attribute {
+                                use $crate::custom_elements::__private::dioxus_core;
+                                let event_handler: dioxus_core::prelude::EventHandler<dioxus_core::Event<$data>> =
+                                    _f.super_into();
+                                dioxus_core::Attribute::new(
+                                    stringify!($event),
+                                    dioxus_core::AttributeValue::listener(
+                                        move |e: dioxus_core::Event<$crate::PlatformEventData>| {
+                                            event_handler.call(e.map(|e| e.into()));
+                                        },
+                                    ),
+                                    None,
+                                    false,
+                                )
+                            }
+
+                            #[doc(hidden)]
+                            pub mod $event {
+                                use super::*;
+
+                                // rsx calls this version of the function for inline closures to give better type inference
+                                pub fn call_with_explicit_closure<
+                                    __Marker,
+                                    Return: $crate::custom_elements::__private::dioxus_core::SpawnIfAsync<__Marker> + 'static,
+                                >(
+                                    event_handler: impl FnMut($crate::custom_elements::__private::dioxus_core::Event<$data>) -> Return + 'static,
+                                ) -> $crate::custom_elements::__private::dioxus_core::Attribute {
+                                    super::$event(event_handler)
+                                }
+                            }
+                        )*)?
+                    }
+                }
+            )*
+    };
+
+    (@name $name:ident $js_name:literal) => {
+        $js_name
+    };
+    (@name $name:ident) => {
+        stringify!($name)
+    };
+}
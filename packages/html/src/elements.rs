@@ -11,6 +11,25 @@ use crate::{map_global_attributes, map_svg_attributes};
 
 pub type AttributeDiscription = (&'static str, Option<&'static str>, bool);
 
+/// A static value for the rsx example of an attribute. Enumerated attributes only accept some values at compile time.
+macro_rules! example_attribute_value {
+    (InputType) => {
+        "text"
+    };
+    (ButtonType) => {
+        "submit"
+    };
+    (OnOff) => {
+        "on"
+    };
+    (TrueFalse) => {
+        "true"
+    };
+    ($vil:ident) => {
+        "value"
+    };
+}
+
 macro_rules! impl_attribute {
     (
         $element:ident {
@@ -30,7 +49,7 @@ macro_rules! impl_attribute {
         ///     // Attributes need to be under the element they modify
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Attributes are followed by a colon and then the value of the attribute
-        #[doc = concat!("        ", stringify!($fil), ": \"value\"")]
+        #[doc = concat!("        ", stringify!($fil), ": \"", example_attribute_value!($vil), "\"")]
         ///     }
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Or you can use the shorthand syntax if you have a variable in scope that has the same name as the attribute
@@ -59,7 +78,7 @@ macro_rules! impl_attribute {
         ///     // Attributes need to be under the element they modify
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Attributes are followed by a colon and then the value of the attribute
-        #[doc = concat!("        ", stringify!($fil), ": \"value\"")]
+        #[doc = concat!("        ", stringify!($fil), ": \"", example_attribute_value!($vil), "\"")]
         ///     }
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Or you can use the shorthand syntax if you have a variable in scope that has the same name as the attribute
@@ -88,7 +107,7 @@ macro_rules! impl_attribute {
         ///     // Attributes need to be under the element they modify
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Attributes are followed by a colon and then the value of the attribute
-        #[doc = concat!("        ", stringify!($fil), ": \"value\"")]
+        #[doc = concat!("        ", stringify!($fil), ": \"", example_attribute_value!($vil), "\"")]
         ///     }
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Or you can use the shorthand syntax if you have a variable in scope that has the same name as the attribute
@@ -117,7 +136,7 @@ macro_rules! impl_attribute {
         ///     // Attributes need to be under the element they modify
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Attributes are followed by a colon and then the value of the attribute
-        #[doc = concat!("        ", stringify!($fil), ": \"value\"")]
+        #[doc = concat!("        ", stringify!($fil), ": \"", example_attribute_value!($vil), "\"")]
         ///     }
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Or you can use the shorthand syntax if you have a variable in scope that has the same name as the attribute
@@ -146,7 +165,7 @@ macro_rules! impl_attribute {
         ///     // Attributes need to be under the element they modify
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Attributes are followed by a colon and then the value of the attribute
-        #[doc = concat!("        ", stringify!($fil), ": \"value\"")]
+        #[doc = concat!("        ", stringify!($fil), ": \"", example_attribute_value!($vil), "\"")]
         ///     }
         #[doc = concat!("    ", stringify!($element), " {")]
         ///         // Or you can use the shorthand syntax if you have a variable in scope that has the same name as the attribute
@@ -212,6 +231,50 @@ macro_rules! impl_html_to_rsx_attribute_match {
     };
 }
 
+macro_rules! impl_boolean_attribute_match {
+    (
+        $attr:ident $fil:ident: Bool $name:literal
+    ) => {
+        if $attr == $name {
+            return true;
+        }
+    };
+
+    (
+        $attr:ident $fil:ident: Bool $_:ident
+    ) => {
+        if $attr == stringify!($fil) {
+            return true;
+        }
+    };
+
+    (
+        $attr:ident $fil:ident: $vil:ident $_:tt
+    ) => {};
+}
+
+macro_rules! impl_enumerated_attribute_match {
+    (
+        $attr:ident $fil:ident: TrueFalse $_:tt
+    ) => {
+        if $attr == stringify!($fil) {
+            return true;
+        }
+    };
+
+    (
+        $attr:ident $fil:ident: BoolOrDefault $_:tt
+    ) => {
+        if $attr == stringify!($fil) {
+            return true;
+        }
+    };
+
+    (
+        $attr:ident $fil:ident: $vil:ident $_:tt
+    ) => {};
+}
+
 macro_rules! impl_element {
     (
         $(#[$attr:meta])*
@@ -256,6 +319,22 @@ macro_rules! impl_element {
             pub const TAG_NAME: &'static str = stringify!($name);
             pub const NAME_SPACE: Option<&'static str> = None;
 
+            /// The attribute group this element accepts. Used by `#[props(extends = ...)]` to forward the shared attributes.
+            pub use crate::attribute_groups::GlobalAttributesExtension as AttributeGroupExtension;
+
+            /// Returns false if a static value is not one of the keywords an enumerated attribute on this element accepts.
+            /// The rsx macro calls this at compile time for every static attribute.
+            #[doc(hidden)]
+            pub const fn __is_valid_attribute_value(__attribute: &str, __value: &str) -> bool {
+                $(
+                    if crate::attribute_values::str_eq(__attribute, $fil.0) {
+                        return crate::attribute_values::is_valid_value(stringify!($vil), __value);
+                    }
+                )*
+
+                crate::attribute_values::is_valid_global_attribute_value(__attribute, __value)
+            }
+
             $(
                 impl_attribute!(
                     $name {
@@ -309,6 +388,23 @@ macro_rules! impl_element {
             pub const TAG_NAME: &'static str = stringify!($name);
             pub const NAME_SPACE: Option<&'static str> = Some($namespace);
 
+            /// The attribute group this element accepts. Used by `#[props(extends = ...)]` to forward the shared attributes.
+            pub use crate::attribute_groups::SvgAttributesExtension as AttributeGroupExtension;
+
+            /// Returns false if a static value is not one of the keywords an enumerated attribute on this element accepts.
+            /// The rsx macro calls this at compile time for every static attribute.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub const fn __is_valid_attribute_value(__attribute: &str, __value: &str) -> bool {
+                $(
+                    if crate::attribute_values::str_eq(__attribute, $fil.0) {
+                        return crate::attribute_values::is_valid_value(stringify!($vil), __value);
+                    }
+                )*
+
+                true
+            }
+
             $(
                 impl_attribute!(
                     $name {
@@ -363,6 +459,23 @@ macro_rules! impl_element {
             pub const TAG_NAME: &'static str = $name;
             pub const NAME_SPACE: Option<&'static str> = Some($namespace);
 
+            /// The attribute group this element accepts. Used by `#[props(extends = ...)]` to forward the shared attributes.
+            pub use crate::attribute_groups::SvgAttributesExtension as AttributeGroupExtension;
+
+            /// Returns false if a static value is not one of the keywords an enumerated attribute on this element accepts.
+            /// The rsx macro calls this at compile time for every static attribute.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub const fn __is_valid_attribute_value(__attribute: &str, __value: &str) -> bool {
+                $(
+                    if crate::attribute_values::str_eq(__attribute, $fil.0) {
+                        return crate::attribute_values::is_valid_value(stringify!($vil), __value);
+                    }
+                )*
+
+                true
+            }
+
             $(
                 impl_attribute!(
                     $element {
@@ -558,6 +671,40 @@ macro_rules! builder_constructors {
             None
         }
 
+        /// Returns true if the attribute is an [HTML boolean attribute](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#boolean-attributes)
+        /// whose presence alone turns it on. Attributes are marked with the `Bool` type in the element definitions.
+        ///
+        /// Renderers drop these attributes entirely when they are set to a falsy value like `"false"`.
+        pub fn is_boolean_attribute(attribute: &str) -> bool {
+            $(
+                $(
+                    impl_boolean_attribute_match!(
+                        attribute $fil: $vil $extra
+                    );
+                )*
+            )*
+
+            matches!(attribute, "hidden" | "inert" | "itemscope" | "truespeed")
+        }
+
+        /// Returns true if the attribute is an enumerated attribute that expects the literal strings `"true"` or `"false"`.
+        /// Attributes are marked with the `TrueFalse` or `BoolOrDefault` type in the element definitions.
+        ///
+        /// Boolean values on every other attribute, including custom attributes, toggle the presence of the attribute
+        /// instead of rendering `"false"`.
+        pub fn is_enumerated_attribute(attribute: &str) -> bool {
+            $(
+                $(
+                    impl_enumerated_attribute_match!(
+                        attribute $fil: $vil $extra
+                    );
+                )*
+            )*
+
+            attribute.starts_with("aria-")
+                || matches!(attribute, "contenteditable" | "draggable" | "spellcheck")
+        }
+
         $(
             impl_element!(
                 $(#[$attr])*
@@ -1372,7 +1519,7 @@ builder_constructors! {
         popovertarget: String DEFAULT,
         popovertargetaction: String DEFAULT,
         value: String DEFAULT,
-        r#type: String "type",
+        r#type: ButtonType "type",
     };
 
     /// Build a
@@ -1433,7 +1580,7 @@ builder_constructors! {
         readonly: Bool DEFAULT,
         required: Bool DEFAULT,
         size: usize DEFAULT,
-        spellcheck: Bool DEFAULT,
+        spellcheck: BoolOrDefault DEFAULT,
         src: Uri DEFAULT,
         step: String DEFAULT,
         tabindex: usize DEFAULT,
@@ -1558,7 +1705,7 @@ builder_constructors! {
     /// [`<textarea>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/textarea)
     /// element.
     textarea None {
-        autocomplete: OnOff DEFAULT,
+        autocomplete: String DEFAULT,
         autofocus: Bool DEFAULT,
         cols: usize DEFAULT,
         disabled: Bool DEFAULT,
@@ -2008,23 +2155,23 @@ builder_constructors! {
     /// [`<mo>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mo)
     /// element.
     mo "http://www.w3.org/1998/Math/MathML" {
-        fence: Bool DEFAULT,
-        largeop: Bool DEFAULT,
+        fence: TrueFalse DEFAULT,
+        largeop: TrueFalse DEFAULT,
         lspace: usize DEFAULT,
         maxsize: usize DEFAULT,
         minsize: usize DEFAULT,
-        movablelimits: Bool DEFAULT,
+        movablelimits: TrueFalse DEFAULT,
         rspace: usize DEFAULT,
-        separator: Bool DEFAULT,
-        stretchy: Bool DEFAULT,
-        symmetric: Bool DEFAULT,
+        separator: TrueFalse DEFAULT,
+        stretchy: TrueFalse DEFAULT,
+        symmetric: TrueFalse DEFAULT,
     };
 
     /// Build a
     /// [`<mover>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mover)
     /// element.
     mover "http://www.w3.org/1998/Math/MathML" {
-        accent: Bool DEFAULT,
+        accent: TrueFalse DEFAULT,
     };
 
     /// Build a
@@ -2129,15 +2276,15 @@ builder_constructors! {
     /// [`<munder>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/munder)
     /// element.
     munder "http://www.w3.org/1998/Math/MathML" {
-        accentunder: Bool DEFAULT,
+        accentunder: TrueFalse DEFAULT,
     };
 
     /// Build a
     /// [`<munderover>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/munderover)
     /// element.
     munderover "http://www.w3.org/1998/Math/MathML" {
-        accent: Bool DEFAULT,
-        accentunder: Bool DEFAULT,
+        accent: TrueFalse DEFAULT,
+        accentunder: TrueFalse DEFAULT,
     };
 
     /// Build a
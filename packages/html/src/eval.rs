@@ -151,6 +151,9 @@ pub enum EvalError {
     /// The provided JavaScript is not valid and can't be ran.
     InvalidJs(String),
 
+    /// The page's Content Security Policy doesn't allow JavaScript to be created from a string.
+    Blocked,
+
     /// Represents an error communicating between JavaScript and Rust.
     Communication(String),
 }
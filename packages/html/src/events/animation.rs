@@ -131,7 +131,7 @@ pub trait HasAnimationData: std::any::Any {
 }
 
 impl_event! [
-    AnimationData;
+    AnimationData as AnimationEventsExtension;
 
     /// onanimationstart
     onanimationstart
@@ -80,7 +80,7 @@ pub trait HasClipboardData: std::any::Any {
 }
 
 impl_event![
-    ClipboardData;
+    ClipboardData as ClipboardEventsExtension;
 
     /// oncopy
     oncopy
@@ -96,7 +96,7 @@ pub trait HasCompositionData: std::any::Any {
 }
 
 impl_event! [
-    CompositionData;
+    CompositionData as CompositionEventsExtension;
 
     /// oncompositionstart
     oncompositionstart
@@ -52,12 +52,178 @@ impl DragData {
         }
     }
 
+    /// The data being dragged. Browsers only expose the string data of the items in `ondragstart` and `ondrop`, the
+    /// other drag events only see the kinds and types of the items. The contents of dragged files are available from
+    /// [`HasFileData::files`].
+    pub fn data_transfer(&self) -> DataTransfer {
+        self.inner.data_transfer()
+    }
+
     /// Downcast this event data to a specific type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         HasDragData::as_any(&*self.inner).downcast_ref::<T>()
     }
 }
 
+/// The data being dragged in a drag and drop interaction
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut dropped = use_signal(String::new);
+///     rsx! {
+///         div {
+///             prevent_default: "ondragover ondrop",
+///             ondragover: move |_| {},
+///             ondrop: move |event| {
+///                 if let Some(text) = event.data_transfer().get_data("text/plain") {
+///                     dropped.set(text.to_string());
+///                 }
+///             },
+///             "Dropped: {dropped}"
+///         }
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataTransfer {
+    #[cfg_attr(feature = "serialize", serde(default))]
+    drop_effect: String,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    effect_allowed: String,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    items: Vec<DataTransferItem>,
+}
+
+impl DataTransfer {
+    /// Create a new DataTransfer
+    pub fn new(
+        drop_effect: impl Into<String>,
+        effect_allowed: impl Into<String>,
+        items: Vec<DataTransferItem>,
+    ) -> Self {
+        Self {
+            drop_effect: drop_effect.into(),
+            effect_allowed: effect_allowed.into(),
+            items,
+        }
+    }
+
+    /// The drop effect of the drag operation: `none`, `copy`, `link` or `move`
+    pub fn drop_effect(&self) -> &str {
+        &self.drop_effect
+    }
+
+    /// The effects the source of the drag allows, like `copyMove` or `all`
+    pub fn effect_allowed(&self) -> &str {
+        &self.effect_allowed
+    }
+
+    /// The items being dragged
+    pub fn items(&self) -> &[DataTransferItem] {
+        &self.items
+    }
+
+    /// The types of the items being dragged in order, without duplicates. Files are listed as `Files` like in the
+    /// browser.
+    pub fn types(&self) -> Vec<&str> {
+        let mut types = Vec::new();
+        for item in &self.items {
+            let ty = match item.kind {
+                DataTransferItemKind::String => item.ty.as_str(),
+                DataTransferItemKind::File => "Files",
+            };
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        types
+    }
+
+    /// Get the string data of the given type, if it is available
+    pub fn get_data(&self, ty: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .filter(|item| item.kind == DataTransferItemKind::String)
+            .find(|item| item.ty.eq_ignore_ascii_case(ty))
+            .and_then(|item| item.data.as_deref())
+    }
+
+    /// The items that are files
+    pub fn files(&self) -> impl Iterator<Item = &DataTransferItem> {
+        self.items
+            .iter()
+            .filter(|item| item.kind == DataTransferItemKind::File)
+    }
+}
+
+/// An item being dragged
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTransferItem {
+    kind: DataTransferItemKind,
+    #[cfg_attr(feature = "serialize", serde(rename = "type", default))]
+    ty: String,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    data: Option<String>,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    name: Option<String>,
+}
+
+impl DataTransferItem {
+    /// Create a new string item with the given mime type and data, if the data is readable
+    pub fn string(ty: impl Into<String>, data: Option<String>) -> Self {
+        Self {
+            kind: DataTransferItemKind::String,
+            ty: ty.into(),
+            data,
+            name: None,
+        }
+    }
+
+    /// Create a new file item with the given mime type and file name, if the name is readable
+    pub fn file(ty: impl Into<String>, name: Option<String>) -> Self {
+        Self {
+            kind: DataTransferItemKind::File,
+            ty: ty.into(),
+            data: None,
+            name,
+        }
+    }
+
+    /// Whether the item is a string or a file
+    pub fn kind(&self) -> DataTransferItemKind {
+        self.kind
+    }
+
+    /// The mime type of the item, like `text/plain`. This may be empty for files with an unknown type
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// The data of a string item, if it is readable in this event
+    pub fn data(&self) -> Option<&str> {
+        self.data.as_deref()
+    }
+
+    /// The name of a file item, if it is readable in this event
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// The kind of a [`DataTransferItem`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataTransferItemKind {
+    /// A string, like text or a url
+    String,
+    /// A file
+    File,
+}
+
 impl HasFileData for DragData {
     fn files(&self) -> Option<std::sync::Arc<dyn FileEngine>> {
         self.inner.files()
@@ -113,6 +279,9 @@ pub struct SerializedDragData {
 
     #[serde(default)]
     files: Option<crate::file_data::SerializedFileEngine>,
+
+    #[serde(default)]
+    data_transfer: DataTransfer,
 }
 
 #[cfg(feature = "serialize")]
@@ -121,12 +290,17 @@ impl SerializedDragData {
         Self {
             mouse: crate::point_interaction::SerializedPointInteraction::from(drag),
             files,
+            data_transfer: drag.data_transfer(),
         }
     }
 }
 
 #[cfg(feature = "serialize")]
 impl HasDragData for SerializedDragData {
+    fn data_transfer(&self) -> DataTransfer {
+        self.data_transfer.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -211,12 +385,17 @@ impl<'de> serde::Deserialize<'de> for DragData {
 
 /// A trait for any object that has the data for a drag event
 pub trait HasDragData: HasMouseData + HasFileData {
+    /// The data being dragged
+    fn data_transfer(&self) -> DataTransfer {
+        DataTransfer::default()
+    }
+
     /// return self as Any
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
 impl_event! {
-    DragData;
+    DragData as DragEventsExtension;
 
     /// ondrag
     ondrag
@@ -14,13 +14,15 @@ impl<E: HasFocusData> From<E> for FocusData {
 
 impl std::fmt::Debug for FocusData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("FocusData").finish()
+        f.debug_struct("FocusData")
+            .field("related_target", &self.related_target())
+            .finish()
     }
 }
 
 impl PartialEq for FocusData {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+    fn eq(&self, other: &Self) -> bool {
+        self.related_target() == other.related_target()
     }
 }
 
@@ -32,26 +34,101 @@ impl FocusData {
         }
     }
 
+    /// The element on the other side of the focus change: the element losing focus for `onfocus` and `onfocusin`,
+    /// or the element gaining focus for `onblur` and `onfocusout`. This is `None` if focus moved to or from the page
+    /// itself or the platform doesn't report it.
+    pub fn related_target(&self) -> Option<RelatedTarget> {
+        self.inner.related_target()
+    }
+
     /// Downcast this event data to a specific type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         self.inner.as_any().downcast_ref::<T>()
     }
 }
 
+/// A description of the element on the other side of a focus change
+///
+/// Compare the [`id`](RelatedTarget::id) with the ids of your own elements to find out if focus stayed inside a
+/// component:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut open = use_signal(|| false);
+///     rsx! {
+///         div {
+///             onfocusin: move |_| open.set(true),
+///             onfocusout: move |event| {
+///                 let focused = event.related_target();
+///                 let inside = focused.as_ref().and_then(|target| target.id()) == Some("menu-item");
+///                 open.set(inside);
+///             },
+///             button { "Menu" }
+///             if open() {
+///                 button { id: "menu-item", "Item" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RelatedTarget {
+    tag_name: String,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    id: Option<String>,
+}
+
+impl RelatedTarget {
+    /// Create a new RelatedTarget
+    pub fn new(tag_name: impl Into<String>, id: Option<String>) -> Self {
+        Self {
+            tag_name: tag_name.into().to_ascii_lowercase(),
+            id: id.filter(|id| !id.is_empty()),
+        }
+    }
+
+    /// The lowercase tag name of the element, like `input`
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    /// The `id` attribute of the element, if it has one
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
 #[cfg(feature = "serialize")]
 /// A serialized version of FocusData
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone, Default)]
-pub struct SerializedFocusData {}
+pub struct SerializedFocusData {
+    #[serde(default)]
+    related_target: Option<RelatedTarget>,
+}
+
+#[cfg(feature = "serialize")]
+impl SerializedFocusData {
+    /// Create a new SerializedFocusData
+    pub fn new(related_target: Option<RelatedTarget>) -> Self {
+        Self { related_target }
+    }
+}
 
 #[cfg(feature = "serialize")]
 impl From<&FocusData> for SerializedFocusData {
-    fn from(_: &FocusData) -> Self {
-        Self {}
+    fn from(data: &FocusData) -> Self {
+        Self::new(data.related_target())
     }
 }
 
 #[cfg(feature = "serialize")]
 impl HasFocusData for SerializedFocusData {
+    fn related_target(&self) -> Option<RelatedTarget> {
+        self.related_target.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -75,12 +152,17 @@ impl<'de> serde::Deserialize<'de> for FocusData {
 }
 
 pub trait HasFocusData: std::any::Any {
+    /// The element on the other side of the focus change
+    fn related_target(&self) -> Option<RelatedTarget> {
+        None
+    }
+
     /// return self as Any
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
 impl_event! [
-    FocusData;
+    FocusData as FocusEventsExtension;
 
     /// onfocus
     onfocus
@@ -90,6 +90,35 @@ impl FormData {
         self.value().parse()
     }
 
+    /// The `type` attribute of the input that triggered the event, like `number` or `checkbox`. This is `None` for
+    /// elements that aren't inputs, like `textarea`, `select` or `form`.
+    pub fn input_type(&self) -> Option<String> {
+        self.inner.input_type()
+    }
+
+    /// Get the value of the form event, parsed according to the `type` attribute of the input
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// fn app() -> Element {
+    ///     let mut volume = use_signal(|| 50.0);
+    ///     rsx! {
+    ///         input {
+    ///             r#type: "range",
+    ///             value: "{volume}",
+    ///             oninput: move |event| {
+    ///                 if let InputValue::Number(value) = event.typed_value() {
+    ///                     volume.set(value);
+    ///                 }
+    ///             },
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn typed_value(&self) -> InputValue {
+        InputValue::parse(self.input_type().as_deref(), self.value())
+    }
+
     /// Try to parse the value as a boolean
     ///
     /// Returns false if the value is not a boolean, or if it is false!
@@ -121,12 +150,46 @@ impl FormData {
     }
 }
 
+/// The value of an input, parsed according to its `type` attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputValue {
+    /// The value of a text-like input, or of an input whose value could not be parsed. Dates and times are kept as
+    /// text in the formats the browser uses, like `2024-01-31` or `13:45`.
+    Text(String),
+    /// The value of a `number` or `range` input
+    Number(f64),
+    /// Whether a `checkbox` is checked
+    Checked(bool),
+    /// The value of a `number` or `range` input that is empty
+    Empty,
+}
+
+impl InputValue {
+    /// Parse the value of an input with the given `type` attribute
+    pub fn parse(input_type: Option<&str>, value: String) -> Self {
+        match input_type.map(str::to_ascii_lowercase).as_deref() {
+            Some("number" | "range") if value.trim().is_empty() => Self::Empty,
+            Some("number" | "range") => match value.trim().parse() {
+                Ok(number) => Self::Number(number),
+                Err(_) => Self::Text(value),
+            },
+            Some("checkbox") => Self::Checked(value == "true"),
+            _ => Self::Text(value),
+        }
+    }
+}
+
 /// An object that has all the data for a form event
 pub trait HasFormData: HasFileData + std::any::Any {
     fn value(&self) -> String {
         Default::default()
     }
 
+    /// The `type` attribute of the input that triggered the event
+    fn input_type(&self) -> Option<String> {
+        None
+    }
+
     fn valid(&self) -> bool {
         true
     }
@@ -178,6 +241,9 @@ pub struct SerializedFormData {
 
     #[serde(default)]
     files: Option<crate::file_data::SerializedFileEngine>,
+
+    #[serde(default)]
+    input_type: Option<String>,
 }
 
 #[cfg(feature = "serialize")]
@@ -193,15 +259,23 @@ impl SerializedFormData {
             values,
             files,
             valid: true,
+            input_type: None,
         }
     }
 
+    /// Set the `type` attribute of the input that triggered the event
+    pub fn with_input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.input_type = Some(input_type.into());
+        self
+    }
+
     /// Create a new serialized form data object from a traditional form data object
     pub async fn async_from(data: &FormData) -> Self {
         Self {
             value: data.value(),
             values: data.values(),
             valid: data.valid(),
+            input_type: data.input_type(),
             files: match data.files() {
                 Some(files) => {
                     let mut resolved_files = HashMap::new();
@@ -225,6 +299,7 @@ impl SerializedFormData {
             value: data.value(),
             values: data.values(),
             valid: data.valid(),
+            input_type: data.input_type(),
             files: None,
         }
     }
@@ -244,6 +319,10 @@ impl HasFormData for SerializedFormData {
         self.valid
     }
 
+    fn input_type(&self) -> Option<String> {
+        self.input_type.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -282,7 +361,7 @@ impl<'de> serde::Deserialize<'de> for FormData {
 }
 
 impl_event! {
-    FormData;
+    FormData as FormEventsExtension;
 
     /// onchange
     onchange
@@ -95,7 +95,7 @@ pub trait HasImageData: std::any::Any {
 }
 
 impl_event! [
-    ImageData;
+    ImageData as ImageEventsExtension;
 
     /// onerror
     onerror
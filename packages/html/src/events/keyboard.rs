@@ -226,7 +226,7 @@ impl<'de> serde::Deserialize<'de> for KeyboardData {
 }
 
 impl_event! {
-    KeyboardData;
+    KeyboardData as KeyboardEventsExtension;
 
     /// onkeydown
     onkeydown
@@ -79,7 +79,7 @@ pub trait HasMediaData: std::any::Any {
 }
 
 impl_event! [
-    MediaData;
+    MediaData as MediaEventsExtension;
 
     ///abort
     onabort
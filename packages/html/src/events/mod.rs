@@ -5,12 +5,29 @@ use std::sync::RwLock;
 
 macro_rules! impl_event {
     (
-        $data:ty;
+        $data:ty as $extension:ident;
         $(
             $( #[$attr:meta] )*
             $name:ident $(: $js_name:literal)?
         )*
     ) => {
+        /// Event handler props for components that forward these events to an element.
+        ///
+        /// This trait is implemented for the props builder of every component with a `#[props(extends = ...)]` element
+        /// field. The handlers are pushed into the extended attribute list, ready to be spread onto the inner element.
+        pub trait $extension: crate::events::EventHandlersExtension {
+            $(
+                $( #[$attr] )*
+                #[allow(deprecated)]
+                fn $name<__Marker>(self, _f: impl ::dioxus_core::prelude::SuperInto<::dioxus_core::prelude::EventHandler<::dioxus_core::Event<$data>>, __Marker>) -> Self {
+                    let attribute = $name(_f);
+                    self.push_attribute(attribute.name, attribute.namespace, attribute.value, attribute.volatile)
+                }
+            )*
+        }
+
+        impl<T: crate::events::EventHandlersExtension> $extension for T {}
+
         $(
             $( #[$attr] )*
             /// <details open>
@@ -65,6 +82,11 @@ macro_rules! impl_event {
     };
 }
 
+/// A marker for props builders that accept every event handler as a forwarded attribute.
+///
+/// Implementing this trait enables the event extension traits like [`MouseEventsExtension`] for the type.
+pub trait EventHandlersExtension: ::dioxus_core::HasAttributes + Sized {}
+
 static EVENT_CONVERTER: RwLock<Option<Box<dyn HtmlEventConverter>>> = RwLock::new(None);
 
 #[inline]
@@ -134,6 +156,8 @@ pub trait HtmlEventConverter: Send + Sync {
     fn convert_mouse_data(&self, event: &PlatformEventData) -> MouseData;
     /// Convert a general event to a pointer data event
     fn convert_pointer_data(&self, event: &PlatformEventData) -> PointerData;
+    /// Convert a general event to a resized data event
+    fn convert_resized_data(&self, event: &PlatformEventData) -> ResizedData;
     /// Convert a general event to a scroll data event
     fn convert_scroll_data(&self, event: &PlatformEventData) -> ScrollData;
     /// Convert a general event to a selection data event
@@ -220,6 +244,12 @@ impl From<&PlatformEventData> for PointerData {
     }
 }
 
+impl From<&PlatformEventData> for ResizedData {
+    fn from(val: &PlatformEventData) -> Self {
+        with_event_converter(|c| c.convert_resized_data(val))
+    }
+}
+
 impl From<&PlatformEventData> for ScrollData {
     fn from(val: &PlatformEventData) -> Self {
         with_event_converter(|c| c.convert_scroll_data(val))
@@ -268,11 +298,13 @@ mod media;
 mod mounted;
 mod mouse;
 mod pointer;
+mod resized;
 mod scroll;
 mod selection;
 mod toggle;
 mod touch;
 mod transition;
+mod unmounted;
 mod wheel;
 
 pub use animation::*;
@@ -287,11 +319,13 @@ pub use media::*;
 pub use mounted::*;
 pub use mouse::*;
 pub use pointer::*;
+pub use resized::*;
 pub use scroll::*;
 pub use selection::*;
 pub use toggle::*;
 pub use touch::*;
 pub use transition::*;
+pub use unmounted::*;
 pub use wheel::*;
 
 pub fn event_bubbles(evt: &str) -> bool {
@@ -380,6 +414,8 @@ pub fn event_bubbles(evt: &str) -> bool {
         "transitionend" => true,
         "toggle" => true,
         "mounted" => false,
+        "unmounted" => false,
+        "resized" => false,
         _ => {
             tracing::warn!("Unknown event name: {evt}");
             true
@@ -43,6 +43,15 @@ pub trait RenderedElementBacking: std::any::Any {
     fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         Box::pin(async { Err(MountedError::NotSupported) })
     }
+
+    /// Find the descendants of the element that match a query
+    #[allow(clippy::type_complexity)]
+    fn query_all(
+        &self,
+        _query: ElementQuery,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<Vec<MountedData>>>>> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
 }
 
 impl RenderedElementBacking for () {
@@ -63,6 +72,66 @@ pub enum ScrollBehavior {
     Smooth,
 }
 
+/// A query for the descendants of a mounted element. Create one with [`MountedData::query_all`] or the shorthand
+/// methods like [`MountedData::query_by_test_id`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementQuery {
+    /// The CSS selector the elements must match
+    pub selector: String,
+    /// If this is set, elements must also contain exactly this text, ignoring whitespace at the start and end.
+    /// Only the innermost element with the text matches.
+    pub text: Option<String>,
+}
+
+impl ElementQuery {
+    /// Find the elements that match a CSS selector
+    pub fn selector(selector: impl Into<String>) -> Self {
+        Self {
+            selector: selector.into(),
+            text: None,
+        }
+    }
+
+    /// Find the elements with a `data-testid` attribute
+    pub fn test_id(id: &str) -> Self {
+        Self::selector(format!("[data-testid=\"{}\"]", escape_attribute_value(id)))
+    }
+
+    /// Find the elements with an explicit `role` attribute. Roles that are implied by the tag of the element are not
+    /// matched.
+    pub fn role(role: &str) -> Self {
+        Self::selector(format!("[role=\"{}\"]", escape_attribute_value(role)))
+    }
+
+    /// Find the elements that contain some text
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::selector("*").with_text(text)
+    }
+
+    /// Only match elements that also contain some text
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+}
+
+impl From<&str> for ElementQuery {
+    fn from(selector: &str) -> Self {
+        Self::selector(selector)
+    }
+}
+
+impl From<String> for ElementQuery {
+    fn from(selector: String) -> Self {
+        Self::selector(selector)
+    }
+}
+
+fn escape_attribute_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 /// An Element that has been rendered and allows reading and modifying information about it.
 ///
 /// Different platforms will have different implementations and different levels of support for this trait. Renderers that do not support specific features will return `None` for those queries.
@@ -120,6 +189,47 @@ impl MountedData {
         self.inner.set_focus(focus)
     }
 
+    /// Find the descendants of the element that match a query. The elements can be measured, scrolled and focused
+    /// like any other mounted element.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # async fn rows(table: MountedData) -> Result<(), MountedError> {
+    /// let rows = table.query_all(ElementQuery::test_id("row").with_text("Total")).await?;
+    /// for row in rows {
+    ///     println!("{:?}", row.get_client_rect().await?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_all(
+        &self,
+        query: impl Into<ElementQuery>,
+    ) -> MountedResult<Vec<MountedData>> {
+        self.inner.query_all(query.into()).await
+    }
+
+    /// Find the descendants of the element that match a CSS selector
+    #[doc(alias = "querySelectorAll")]
+    pub async fn query_selector_all(&self, selector: &str) -> MountedResult<Vec<MountedData>> {
+        self.query_all(ElementQuery::selector(selector)).await
+    }
+
+    /// Find the descendants of the element with a `data-testid` attribute
+    pub async fn query_by_test_id(&self, id: &str) -> MountedResult<Vec<MountedData>> {
+        self.query_all(ElementQuery::test_id(id)).await
+    }
+
+    /// Find the descendants of the element with an explicit `role` attribute
+    pub async fn query_by_role(&self, role: &str) -> MountedResult<Vec<MountedData>> {
+        self.query_all(ElementQuery::role(role)).await
+    }
+
+    /// Find the innermost descendants of the element that contain some text
+    pub async fn query_by_text(&self, text: &str) -> MountedResult<Vec<MountedData>> {
+        self.query_all(ElementQuery::text(text)).await
+    }
+
     /// Downcast this event to a concrete event type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         self.inner.as_any().downcast_ref::<T>()
@@ -133,7 +243,7 @@ use crate::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
 pub type MountedEvent = Event<MountedData>;
 
 impl_event! [
-    MountedData;
+    MountedData as MountedEventsExtension;
 
     #[doc(alias = "ref")]
     #[doc(alias = "createRef")]
@@ -175,6 +285,20 @@ impl_event! [
     /// }
     /// ```
     ///
+    /// If you only need to store the element, you can use the `ref` attribute instead. It is shorthand for an onmounted handler that sets the signal:
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// fn App() -> Element {
+    ///     let header_element = use_signal(|| None);
+    ///
+    ///     rsx! {
+    ///         // Same as `onmounted: move |element| header_element.set(Some(element.data()))`
+    ///         h1 { ref: header_element, "Scroll to top example" }
+    ///     }
+    /// }
+    /// ```
+    ///
     /// The `MountedData` struct contains cross platform APIs that work on the desktop, mobile, liveview and web platforms. For the web platform, you can also downcast the `MountedData` event to the `web-sys::Element` type for more web specific APIs:
     ///
     /// ```rust, no_run
@@ -45,7 +45,7 @@ pub trait HasMouseData: PointerInteraction {
 }
 
 impl_event! {
-    MouseData;
+    MouseData as MouseEventsExtension;
 
     /// Execute a callback when a button is clicked.
     ///
@@ -105,7 +105,7 @@ pub trait HasPointerData: PointerInteraction {
 }
 
 impl_event![
-    PointerData;
+    PointerData as PointerEventsExtension;
     /// pointerdown
     onpointerdown
 
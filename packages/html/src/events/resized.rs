@@ -0,0 +1,139 @@
+use dioxus_core::Event;
+
+use crate::geometry::PixelsSize;
+
+pub type ResizedEvent = Event<ResizedData>;
+
+/// The new size of an element that changed size
+pub struct ResizedData {
+    inner: Box<dyn HasResizedData>,
+}
+
+impl<E: HasResizedData> From<E> for ResizedData {
+    fn from(e: E) -> Self {
+        Self { inner: Box::new(e) }
+    }
+}
+
+impl std::fmt::Debug for ResizedData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResizedData")
+            .field("content_box_size", &self.get_content_box_size())
+            .field("border_box_size", &self.get_border_box_size())
+            .finish()
+    }
+}
+
+impl PartialEq for ResizedData {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_content_box_size() == other.get_content_box_size()
+            && self.get_border_box_size() == other.get_border_box_size()
+    }
+}
+
+impl ResizedData {
+    /// Create a new ResizedData
+    pub fn new(inner: impl HasResizedData + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+        }
+    }
+
+    /// The size of the content of the element, without its padding and border
+    pub fn get_content_box_size(&self) -> PixelsSize {
+        self.inner.get_content_box_size()
+    }
+
+    /// The size of the element, including its padding and border
+    pub fn get_border_box_size(&self) -> PixelsSize {
+        self.inner.get_border_box_size()
+    }
+
+    /// Downcast this event to a concrete event type
+    pub fn downcast<T: 'static>(&self) -> Option<&T> {
+        self.inner.as_any().downcast_ref::<T>()
+    }
+}
+
+#[cfg(feature = "serialize")]
+/// A serialized version of ResizedData
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct SerializedResizedData {
+    content_box_width: f64,
+    content_box_height: f64,
+    border_box_width: f64,
+    border_box_height: f64,
+}
+
+#[cfg(feature = "serialize")]
+impl SerializedResizedData {
+    /// Create a new SerializedResizedData
+    pub fn new(content_box_size: PixelsSize, border_box_size: PixelsSize) -> Self {
+        Self {
+            content_box_width: content_box_size.width,
+            content_box_height: content_box_size.height,
+            border_box_width: border_box_size.width,
+            border_box_height: border_box_size.height,
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl From<&ResizedData> for SerializedResizedData {
+    fn from(data: &ResizedData) -> Self {
+        Self::new(data.get_content_box_size(), data.get_border_box_size())
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl HasResizedData for SerializedResizedData {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_content_box_size(&self) -> PixelsSize {
+        PixelsSize::new(self.content_box_width, self.content_box_height)
+    }
+
+    fn get_border_box_size(&self) -> PixelsSize {
+        PixelsSize::new(self.border_box_width, self.border_box_height)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl serde::Serialize for ResizedData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedResizedData::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> serde::Deserialize<'de> for ResizedData {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SerializedResizedData::deserialize(deserializer)?;
+        Ok(Self {
+            inner: Box::new(data),
+        })
+    }
+}
+
+pub trait HasResizedData: std::any::Any {
+    /// return self as Any
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    /// The size of the content of the element, without its padding and border
+    fn get_content_box_size(&self) -> PixelsSize;
+
+    /// The size of the element, including its padding and border
+    fn get_border_box_size(&self) -> PixelsSize;
+}
+
+impl_event! {
+    ResizedData as ResizedEventsExtension;
+
+    /// onresized
+    ///
+    /// Called with the size of the element once it is mounted and every time its size changes after that. Renderers
+    /// without a layout never call it.
+    onresized
+}
@@ -80,7 +80,7 @@ pub trait HasScrollData: std::any::Any {
 }
 
 impl_event! {
-    ScrollData;
+    ScrollData as ScrollEventsExtension;
 
     /// onscroll
     onscroll
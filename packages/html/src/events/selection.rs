@@ -80,7 +80,7 @@ pub trait HasSelectionData: std::any::Any {
 }
 
 impl_event! [
-    SelectionData;
+    SelectionData as SelectionEventsExtension;
 
     /// select
     onselect
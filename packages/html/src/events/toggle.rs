@@ -80,7 +80,7 @@ pub trait HasToggleData: std::any::Any {
 }
 
 impl_event! {
-    ToggleData;
+    ToggleData as ToggleEventsExtension;
 
     /// ontoggle
     ontoggle
@@ -351,7 +351,7 @@ impl InteractionLocation for SerializedTouchPoint {
 }
 
 impl_event! {
-    TouchData;
+    TouchData as TouchEventsExtension;
     /// touchstart
     ontouchstart
 
@@ -109,7 +109,7 @@ pub trait HasTransitionData: std::any::Any {
 }
 
 impl_event! {
-    TransitionData;
+    TransitionData as TransitionEventsExtension;
 
     /// transitionend
     ontransitionend
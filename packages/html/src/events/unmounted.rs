@@ -0,0 +1,76 @@
+use dioxus_core::prelude::{EventHandler, SuperInto};
+use dioxus_core::{Attribute, AttributeValue, Event};
+
+pub type UnmountedEvent = Event<UnmountedData>;
+
+/// The data of an unmounted event. The element is already gone, so there is nothing to read from it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnmountedData {}
+
+/// Event handler props for components that forward the unmounted event to an element.
+///
+/// This trait is implemented for the props builder of every component with a `#[props(extends = ...)]` element
+/// field. The handler is pushed into the extended attribute list, ready to be spread onto the inner element.
+pub trait UnmountedEventsExtension: crate::events::EventHandlersExtension {
+    /// onunmounted
+    fn onunmounted<__Marker>(
+        self,
+        _f: impl SuperInto<EventHandler<UnmountedEvent>, __Marker>,
+    ) -> Self {
+        let attribute = onunmounted(_f);
+        self.push_attribute(
+            attribute.name,
+            attribute.namespace,
+            attribute.value,
+            attribute.volatile,
+        )
+    }
+}
+
+impl<T: crate::events::EventHandlersExtension> UnmountedEventsExtension for T {}
+
+/// onunmounted
+///
+/// Called when the element is removed from the page, before the component that rendered it is dropped. The virtual
+/// dom calls it directly instead of the renderer, so it works the same way in every renderer. It is not called when
+/// the whole virtual dom is dropped.
+///
+/// <details open>
+/// <summary>General Event Handler Information</summary>
+///
+#[doc = include_str!("../../docs/event_handlers.md")]
+///
+/// </details>
+///
+#[doc = include_str!("../../docs/common_event_handler_errors.md")]
+#[inline]
+pub fn onunmounted<__Marker>(
+    _f: impl SuperInto<EventHandler<UnmountedEvent>, __Marker>,
+) -> Attribute {
+    let event_handler = _f.super_into();
+    Attribute::new(
+        "onunmounted",
+        // The virtual dom sends the event without any data
+        AttributeValue::listener(move |e: Event<()>| {
+            event_handler.call(e.map(|_| UnmountedData {}));
+        }),
+        None,
+        false,
+    )
+}
+
+#[doc(hidden)]
+pub mod onunmounted {
+    use super::*;
+
+    // When expanding the macro, we use this version of the function if we see an inline closure to give better type inference
+    pub fn call_with_explicit_closure<
+        __Marker,
+        Return: ::dioxus_core::SpawnIfAsync<__Marker> + 'static,
+    >(
+        event_handler: impl FnMut(UnmountedEvent) -> Return + 'static,
+    ) -> Attribute {
+        super::onunmounted(event_handler)
+    }
+}
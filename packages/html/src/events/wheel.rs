@@ -114,7 +114,7 @@ impl<'de> serde::Deserialize<'de> for WheelData {
 }
 
 impl_event![
-    WheelData;
+    WheelData as WheelEventsExtension;
 
     /// Called when the mouse wheel is rotated over an element.
     onwheel
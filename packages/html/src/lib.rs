@@ -24,13 +24,19 @@ pub use elements::{map_html_attribute_to_rsx, map_html_element_to_rsx};
 pub mod events;
 pub(crate) mod file_data;
 pub use file_data::*;
+pub mod aria;
 mod attribute_groups;
+mod attribute_values;
+mod class_style;
+pub mod custom_elements;
 pub mod geometry;
 pub mod input_data;
 #[cfg(feature = "native-bind")]
 pub mod native_bind;
 pub mod point_interaction;
 mod render_template;
+pub mod spatial_navigation;
+pub mod styled;
 #[cfg(feature = "wasm-bind")]
 mod web_sys_bind;
 
@@ -41,6 +47,7 @@ mod transit;
 pub use transit::*;
 
 pub use attribute_groups::*;
+pub use class_style::{Class, Style};
 pub use elements::*;
 pub use events::*;
 pub use render_template::*;
@@ -48,17 +55,30 @@ pub use render_template::*;
 #[cfg(feature = "eval")]
 pub mod eval;
 
+#[cfg(feature = "eval")]
+pub mod announcer;
+
 pub mod extensions {
     pub use crate::attribute_groups::{GlobalAttributesExtension, SvgAttributesExtension};
     pub use crate::elements::extensions::*;
 }
 
 pub mod prelude {
+    #[cfg(feature = "eval")]
+    pub use crate::announcer::{use_announcer, Announcer, Politeness};
+    pub use crate::aria::*;
     pub use crate::attribute_groups::{GlobalAttributesExtension, SvgAttributesExtension};
+    pub use crate::class_style::{Class, Style};
+    pub use crate::custom_elements;
     pub use crate::elements::extensions::*;
     #[cfg(feature = "eval")]
     pub use crate::eval::*;
     pub use crate::events::*;
     pub use crate::point_interaction::*;
+    pub use crate::spatial_navigation::{
+        use_focusable, use_navigation_container, use_spatial_navigation, Direction,
+        FocusableHandle, GamepadButton, GamepadStick, NavigationContainer, NavigationInput,
+        NavigationInputSource, NavigationStrategy, SpatialNavigation,
+    };
     pub use keyboard_types::{self, Code, Key, Location, Modifiers};
 }
@@ -0,0 +1,678 @@
+//! Spatial navigation for TV, kiosk and gamepad driven apps.
+//!
+//! Spatial navigation moves focus with arrow keys or a d-pad instead of a pointer. [`use_spatial_navigation`] returns
+//! the [`SpatialNavigation`] of the app. Elements become focus targets with [`use_focusable`], and are measured with
+//! their [`MountedData`] when focus moves, so the next element is picked from the layout the renderer produced.
+//!
+//! Containers created with [`use_navigation_container`] pick the next element with their own
+//! [`NavigationStrategy`], like a row of cards that wraps around. Inputs come from any [`NavigationInputSource`]:
+//! keyboard events, [`Key`]s and [`GamepadButton`]s are supported out of the box.
+
+use crate::geometry::PixelsRect;
+use crate::{KeyboardData, MountedData};
+use dioxus_core::prelude::*;
+use keyboard_types::Key;
+use std::{cell::RefCell, rc::Rc};
+
+/// A direction focus can move in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Towards the top of the screen
+    Up,
+    /// Towards the bottom of the screen
+    Down,
+    /// Towards the left of the screen
+    Left,
+    /// Towards the right of the screen
+    Right,
+}
+
+/// An input spatial navigation understands
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationInput {
+    /// Move focus in a direction
+    Move(Direction),
+    /// Activate the focused element, like Enter or the A button
+    Activate,
+    /// Go back, like Escape or the B button
+    Back,
+}
+
+/// Something that can be turned into a [`NavigationInput`], like a key press or a gamepad button
+pub trait NavigationInputSource {
+    /// Get the navigation input, or `None` if the input has no meaning for navigation
+    fn navigation_input(&self) -> Option<NavigationInput>;
+}
+
+impl NavigationInputSource for NavigationInput {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        Some(*self)
+    }
+}
+
+impl NavigationInputSource for Direction {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        Some(NavigationInput::Move(*self))
+    }
+}
+
+impl<T: NavigationInputSource + ?Sized> NavigationInputSource for Rc<T> {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        self.as_ref().navigation_input()
+    }
+}
+
+impl NavigationInputSource for Key {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        match self {
+            Key::ArrowUp => Some(NavigationInput::Move(Direction::Up)),
+            Key::ArrowDown => Some(NavigationInput::Move(Direction::Down)),
+            Key::ArrowLeft => Some(NavigationInput::Move(Direction::Left)),
+            Key::ArrowRight => Some(NavigationInput::Move(Direction::Right)),
+            Key::Enter | Key::Select => Some(NavigationInput::Activate),
+            Key::Escape | Key::GoBack | Key::BrowserBack => Some(NavigationInput::Back),
+            _ => None,
+        }
+    }
+}
+
+impl NavigationInputSource for KeyboardData {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        self.key().navigation_input()
+    }
+}
+
+/// A button of a gamepad, named after the [standard gamepad layout](https://w3c.github.io/gamepad/#remapping)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// The bottom face button, A on Xbox controllers
+    South,
+    /// The right face button, B on Xbox controllers
+    East,
+    /// The left face button, X on Xbox controllers
+    West,
+    /// The top face button, Y on Xbox controllers
+    North,
+    /// Up on the d-pad
+    DPadUp,
+    /// Down on the d-pad
+    DPadDown,
+    /// Left on the d-pad
+    DPadLeft,
+    /// Right on the d-pad
+    DPadRight,
+    /// Any other button
+    Other(usize),
+}
+
+impl GamepadButton {
+    /// Get the button at an index of the standard gamepad layout, like `Gamepad.buttons` in the browser
+    pub fn from_standard_index(index: usize) -> Self {
+        match index {
+            0 => GamepadButton::South,
+            1 => GamepadButton::East,
+            2 => GamepadButton::West,
+            3 => GamepadButton::North,
+            12 => GamepadButton::DPadUp,
+            13 => GamepadButton::DPadDown,
+            14 => GamepadButton::DPadLeft,
+            15 => GamepadButton::DPadRight,
+            other => GamepadButton::Other(other),
+        }
+    }
+}
+
+impl NavigationInputSource for GamepadButton {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        match self {
+            GamepadButton::DPadUp => Some(NavigationInput::Move(Direction::Up)),
+            GamepadButton::DPadDown => Some(NavigationInput::Move(Direction::Down)),
+            GamepadButton::DPadLeft => Some(NavigationInput::Move(Direction::Left)),
+            GamepadButton::DPadRight => Some(NavigationInput::Move(Direction::Right)),
+            GamepadButton::South => Some(NavigationInput::Activate),
+            GamepadButton::East => Some(NavigationInput::Back),
+            _ => None,
+        }
+    }
+}
+
+/// The position of an analog stick. Each axis goes from -1.0 to 1.0, with positive y pointing down like the
+/// standard gamepad layout.
+///
+/// Sticks report their position continuously, so apps should only send a stick to [`SpatialNavigation::handle`] when
+/// it leaves the dead zone, not on every poll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GamepadStick {
+    /// The horizontal position of the stick
+    pub x: f64,
+    /// The vertical position of the stick
+    pub y: f64,
+}
+
+impl GamepadStick {
+    /// How far the stick has to be pushed before it moves focus
+    pub const DEAD_ZONE: f64 = 0.5;
+}
+
+impl NavigationInputSource for GamepadStick {
+    fn navigation_input(&self) -> Option<NavigationInput> {
+        if self.x.abs().max(self.y.abs()) < Self::DEAD_ZONE {
+            return None;
+        }
+        let direction = if self.x.abs() > self.y.abs() {
+            if self.x > 0.0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if self.y > 0.0 {
+            Direction::Down
+        } else {
+            Direction::Up
+        };
+        Some(NavigationInput::Move(direction))
+    }
+}
+
+/// How a container picks the next element inside of it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum NavigationStrategy {
+    /// Move to the closest element in the direction of the input
+    #[default]
+    Geometric,
+    /// A horizontal list. Left and right move through the list ordered by position, up and down leave the container.
+    Row {
+        /// Move from the last element to the first and back instead of leaving the container
+        wrap: bool,
+    },
+    /// A vertical list. Up and down move through the list ordered by position, left and right leave the container.
+    Column {
+        /// Move from the last element to the first and back instead of leaving the container
+        wrap: bool,
+    },
+    /// A grid with a fixed number of columns, filled in reading order. Moving past an edge leaves the container.
+    Grid {
+        /// The number of elements in each row
+        columns: usize,
+    },
+}
+
+/// A group of focusable elements that share a [`NavigationStrategy`]. Get one with [`use_navigation_container`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NavigationContainer(usize);
+
+struct Focusable {
+    id: usize,
+    container: Option<NavigationContainer>,
+    mounted: Option<Rc<MountedData>>,
+}
+
+#[derive(Default)]
+struct NavigationState {
+    next_id: usize,
+    focusables: Vec<Focusable>,
+    strategies: Vec<(NavigationContainer, NavigationStrategy)>,
+    focused: Option<usize>,
+}
+
+impl NavigationState {
+    fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn strategy(&self, container: NavigationContainer) -> NavigationStrategy {
+        self.strategies
+            .iter()
+            .find(|(id, _)| *id == container)
+            .map(|(_, strategy)| *strategy)
+            .unwrap_or_default()
+    }
+}
+
+/// Moves focus between the focusable elements of the app. Get it with [`use_spatial_navigation`].
+#[derive(Clone, Default)]
+pub struct SpatialNavigation {
+    state: Rc<RefCell<NavigationState>>,
+}
+
+impl SpatialNavigation {
+    /// Handle an input from any [`NavigationInputSource`].
+    ///
+    /// Returns true if the input moves focus. Focus moves in a task once the elements are measured. [`Activate`] and
+    /// [`Back`] are left to the app, which can read them with [`NavigationInputSource::navigation_input`].
+    ///
+    /// [`Activate`]: NavigationInput::Activate
+    /// [`Back`]: NavigationInput::Back
+    pub fn handle(&self, input: impl NavigationInputSource) -> bool {
+        let Some(NavigationInput::Move(direction)) = input.navigation_input() else {
+            return false;
+        };
+        let navigation = self.clone();
+        spawn(async move {
+            navigation.navigate(direction).await;
+        });
+        true
+    }
+
+    /// Move focus in a direction. Returns true if focus moved.
+    ///
+    /// If nothing is focused yet, the first mounted focusable element is focused.
+    pub async fn navigate(&self, direction: Direction) -> bool {
+        let (focused, candidates) = {
+            let state = self.state.borrow();
+            let focused = state
+                .focused
+                .and_then(|id| state.focusables.iter().find(|f| f.id == id))
+                .and_then(|f| Some((f.id, f.container, f.mounted.clone()?)));
+            let candidates: Vec<_> = state
+                .focusables
+                .iter()
+                .filter_map(|f| Some((f.id, f.container, f.mounted.clone()?)))
+                .collect();
+            (focused, candidates)
+        };
+
+        let Some((focused_id, container, focused_mounted)) = focused else {
+            return match candidates.first() {
+                Some((id, _, mounted)) => self.focus(*id, mounted).await,
+                None => false,
+            };
+        };
+        let Ok(from) = focused_mounted.get_client_rect().await else {
+            return false;
+        };
+
+        let mut rects = Vec::with_capacity(candidates.len());
+        for (id, candidate_container, mounted) in &candidates {
+            if *id == focused_id {
+                continue;
+            }
+            if let Ok(rect) = mounted.get_client_rect().await {
+                rects.push((*id, *candidate_container, rect));
+            }
+        }
+
+        let strategy = container.map(|container| self.state.borrow().strategy(container));
+        let target = match (container, strategy) {
+            (Some(container), Some(strategy)) if strategy != NavigationStrategy::Geometric => {
+                let siblings: Vec<_> = rects
+                    .iter()
+                    .filter(|(_, c, _)| *c == Some(container))
+                    .map(|(id, _, rect)| (*id, *rect))
+                    .collect();
+                // Leave the container to the closest element outside of it at an edge
+                pick_in_container(strategy, (focused_id, from), &siblings, direction).or_else(
+                    || {
+                        let outside: Vec<_> = rects
+                            .iter()
+                            .filter(|(_, c, _)| *c != Some(container))
+                            .map(|(id, _, rect)| (*id, *rect))
+                            .collect();
+                        pick_geometric(from, &outside, direction)
+                    },
+                )
+            }
+            _ => {
+                let all: Vec<_> = rects.iter().map(|(id, _, rect)| (*id, *rect)).collect();
+                pick_geometric(from, &all, direction)
+            }
+        };
+
+        let Some(target) = target else {
+            return false;
+        };
+        let Some((_, _, mounted)) = candidates.iter().find(|(id, _, _)| *id == target) else {
+            return false;
+        };
+        self.focus(target, mounted).await
+    }
+
+    async fn focus(&self, id: usize, mounted: &MountedData) -> bool {
+        if mounted.set_focus(true).await.is_err() {
+            return false;
+        }
+        self.state.borrow_mut().focused = Some(id);
+        true
+    }
+
+    fn register(&self, container: Option<NavigationContainer>) -> usize {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_id();
+        state.focusables.push(Focusable {
+            id,
+            container,
+            mounted: None,
+        });
+        id
+    }
+
+    fn unregister(&self, id: usize) {
+        let mut state = self.state.borrow_mut();
+        state.focusables.retain(|f| f.id != id);
+        if state.focused == Some(id) {
+            state.focused = None;
+        }
+    }
+}
+
+/// A focus target registered with [`use_focusable`]. Pass the mounted event of the element to
+/// [`FocusableHandle::mounted`], and its focus event to [`FocusableHandle::focused`] so focus that moved another way,
+/// like a click, is tracked too.
+#[derive(Clone)]
+pub struct FocusableHandle {
+    id: usize,
+    navigation: SpatialNavigation,
+}
+
+impl FocusableHandle {
+    /// Attach the element this handle belongs to
+    pub fn mounted(&self, event: Event<MountedData>) {
+        let mut state = self.navigation.state.borrow_mut();
+        if let Some(focusable) = state.focusables.iter_mut().find(|f| f.id == self.id) {
+            focusable.mounted = Some(event.data());
+        }
+    }
+
+    /// Remember that the element received focus
+    pub fn focused(&self) {
+        self.navigation.state.borrow_mut().focused = Some(self.id);
+    }
+
+    /// Move focus to the element
+    pub async fn focus(&self) -> bool {
+        let mounted = self
+            .navigation
+            .state
+            .borrow()
+            .focusables
+            .iter()
+            .find(|f| f.id == self.id)
+            .and_then(|f| f.mounted.clone());
+        match mounted {
+            Some(mounted) => self.navigation.focus(self.id, &mounted).await,
+            None => false,
+        }
+    }
+}
+
+/// Get the [`SpatialNavigation`] of the app. Every component shares the same one.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_html::spatial_navigation::*;
+/// fn App() -> Element {
+///     let navigation = use_spatial_navigation();
+///     rsx! {
+///         div {
+///             onkeydown: move |event| {
+///                 navigation.handle(event.data());
+///             },
+///             Tile { label: "Movies" }
+///             Tile { label: "Shows" }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Tile(label: String) -> Element {
+///     let tile = use_focusable();
+///     let focus = tile.clone();
+///     rsx! {
+///         button {
+///             onmounted: move |event| tile.mounted(event),
+///             onfocus: move |_| focus.focused(),
+///             "{label}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_spatial_navigation() -> SpatialNavigation {
+    use_hook(|| {
+        try_consume_context::<SpatialNavigation>()
+            .unwrap_or_else(|| provide_root_context(SpatialNavigation::default()))
+    })
+}
+
+/// Register an element as a focus target of spatial navigation. The element is removed when the component is dropped.
+pub fn use_focusable() -> FocusableHandle {
+    let container = use_hook(try_consume_context::<NavigationContainer>);
+    let navigation = use_spatial_navigation();
+    let handle = use_hook(|| FocusableHandle {
+        id: navigation.register(container),
+        navigation,
+    });
+    let drop_handle = handle.clone();
+    use_drop(move || drop_handle.navigation.unregister(drop_handle.id));
+    handle
+}
+
+/// Create a container with its own [`NavigationStrategy`]. Every [`use_focusable`] in the children of the component
+/// belongs to the container.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_html::spatial_navigation::*;
+/// #[component]
+/// fn Shelf(children: Element) -> Element {
+///     use_navigation_container(NavigationStrategy::Row { wrap: true });
+///     rsx! { div { class: "shelf", {children} } }
+/// }
+/// ```
+pub fn use_navigation_container(strategy: NavigationStrategy) -> NavigationContainer {
+    let navigation = use_spatial_navigation();
+    let container = use_hook(|| {
+        let mut state = navigation.state.borrow_mut();
+        let container = NavigationContainer(state.next_id());
+        state.strategies.push((container, strategy));
+        provide_context(container)
+    });
+    // Keep the strategy in sync with the latest render
+    if let Some(entry) = navigation
+        .state
+        .borrow_mut()
+        .strategies
+        .iter_mut()
+        .find(|(id, _)| *id == container)
+    {
+        entry.1 = strategy;
+    }
+    use_drop(move || {
+        navigation
+            .state
+            .borrow_mut()
+            .strategies
+            .retain(|(id, _)| *id != container)
+    });
+    container
+}
+
+fn center(rect: &PixelsRect) -> (f64, f64) {
+    let center = rect.center();
+    (center.x, center.y)
+}
+
+/// Pick the closest rect in a direction. The distance along the direction counts once, the offset across it twice, so
+/// elements in the same row or column win over closer diagonal ones.
+fn pick_geometric(
+    from: PixelsRect,
+    candidates: &[(usize, PixelsRect)],
+    direction: Direction,
+) -> Option<usize> {
+    let (from_x, from_y) = center(&from);
+    candidates
+        .iter()
+        .filter_map(|(id, rect)| {
+            let (x, y) = center(rect);
+            let (along, across, overlap) = match direction {
+                Direction::Up => (
+                    from.min_y() - rect.max_y(),
+                    x - from_x,
+                    rect.min_x() < from.max_x() && rect.max_x() > from.min_x(),
+                ),
+                Direction::Down => (
+                    rect.min_y() - from.max_y(),
+                    x - from_x,
+                    rect.min_x() < from.max_x() && rect.max_x() > from.min_x(),
+                ),
+                Direction::Left => (
+                    from.min_x() - rect.max_x(),
+                    y - from_y,
+                    rect.min_y() < from.max_y() && rect.max_y() > from.min_y(),
+                ),
+                Direction::Right => (
+                    rect.min_x() - from.max_x(),
+                    y - from_y,
+                    rect.min_y() < from.max_y() && rect.max_y() > from.min_y(),
+                ),
+            };
+            let ahead = match direction {
+                Direction::Up => y < from_y,
+                Direction::Down => y > from_y,
+                Direction::Left => x < from_x,
+                Direction::Right => x > from_x,
+            };
+            if !ahead {
+                return None;
+            }
+            let across = if overlap { 0.0 } else { across.abs() };
+            Some((*id, along.max(0.0) + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(id, _)| id)
+}
+
+/// Pick the next element of a container with an ordered strategy. Returns `None` when focus should leave the
+/// container.
+fn pick_in_container(
+    strategy: NavigationStrategy,
+    from: (usize, PixelsRect),
+    siblings: &[(usize, PixelsRect)],
+    direction: Direction,
+) -> Option<usize> {
+    let mut ordered: Vec<_> = siblings.iter().copied().chain([from]).collect();
+    let position = |rect: &PixelsRect| (rect.min_y(), rect.min_x());
+    match strategy {
+        NavigationStrategy::Row { .. } => {
+            ordered.sort_by(|(_, a), (_, b)| a.min_x().total_cmp(&b.min_x()))
+        }
+        NavigationStrategy::Column { .. } => {
+            ordered.sort_by(|(_, a), (_, b)| a.min_y().total_cmp(&b.min_y()))
+        }
+        _ => ordered.sort_by(|(_, a), (_, b)| {
+            let (ay, ax) = position(a);
+            let (by, bx) = position(b);
+            ay.total_cmp(&by).then(ax.total_cmp(&bx))
+        }),
+    }
+    let index = ordered.iter().position(|(id, _)| *id == from.0)?;
+    let len = ordered.len();
+
+    let step = |forward: bool, wrap: bool| -> Option<usize> {
+        let next = if forward {
+            match index + 1 {
+                next if next < len => next,
+                _ if wrap => 0,
+                _ => return None,
+            }
+        } else {
+            match index.checked_sub(1) {
+                Some(previous) => previous,
+                None if wrap => len - 1,
+                None => return None,
+            }
+        };
+        Some(next)
+    };
+
+    let next = match (strategy, direction) {
+        (NavigationStrategy::Row { wrap }, Direction::Left) => step(false, wrap),
+        (NavigationStrategy::Row { wrap }, Direction::Right) => step(true, wrap),
+        (NavigationStrategy::Column { wrap }, Direction::Up) => step(false, wrap),
+        (NavigationStrategy::Column { wrap }, Direction::Down) => step(true, wrap),
+        (NavigationStrategy::Grid { columns }, direction) => {
+            let columns = columns.max(1);
+            match direction {
+                Direction::Left if index % columns > 0 => Some(index - 1),
+                Direction::Right if index % columns + 1 < columns && index + 1 < len => {
+                    Some(index + 1)
+                }
+                Direction::Up => index.checked_sub(columns),
+                Direction::Down if index + columns < len => Some(index + columns),
+                _ => None,
+            }
+        }
+        _ => None,
+    }?;
+    (next != index).then(|| ordered[next].0)
+}
+
+#[test]
+fn geometric_prefers_aligned_elements() {
+    use crate::geometry::{euclid::Point2D, euclid::Size2D, PixelsRect};
+
+    let rect = |x: f64, y: f64| PixelsRect::new(Point2D::new(x, y), Size2D::new(100.0, 50.0));
+    let from = rect(0.0, 0.0);
+    let candidates = [
+        (1, rect(150.0, 0.0)),
+        (2, rect(120.0, 100.0)),
+        (3, rect(0.0, 100.0)),
+        (4, rect(-150.0, 0.0)),
+    ];
+
+    assert_eq!(pick_geometric(from, &candidates, Direction::Right), Some(1));
+    assert_eq!(pick_geometric(from, &candidates, Direction::Down), Some(3));
+    assert_eq!(pick_geometric(from, &candidates, Direction::Left), Some(4));
+    assert_eq!(pick_geometric(from, &candidates, Direction::Up), None);
+}
+
+#[test]
+fn containers_move_in_order_and_leave_at_edges() {
+    use crate::geometry::{euclid::Point2D, euclid::Size2D, PixelsRect};
+
+    let rect = |x: f64, y: f64| PixelsRect::new(Point2D::new(x, y), Size2D::new(100.0, 50.0));
+    let row = [(2, rect(100.0, 0.0)), (3, rect(200.0, 0.0))];
+    let wrapping = NavigationStrategy::Row { wrap: true };
+    let clamped = NavigationStrategy::Row { wrap: false };
+
+    assert_eq!(
+        pick_in_container(wrapping, (1, rect(0.0, 0.0)), &row, Direction::Left),
+        Some(3)
+    );
+    assert_eq!(
+        pick_in_container(clamped, (1, rect(0.0, 0.0)), &row, Direction::Left),
+        None
+    );
+    assert_eq!(
+        pick_in_container(clamped, (1, rect(0.0, 0.0)), &row, Direction::Down),
+        None
+    );
+
+    // 1 2
+    // 3 4
+    let grid = NavigationStrategy::Grid { columns: 2 };
+    let cells = [
+        (2, rect(100.0, 0.0)),
+        (3, rect(0.0, 50.0)),
+        (4, rect(100.0, 50.0)),
+    ];
+    let first = (1, rect(0.0, 0.0));
+    assert_eq!(
+        pick_in_container(grid, first, &cells, Direction::Right),
+        Some(2)
+    );
+    assert_eq!(
+        pick_in_container(grid, first, &cells, Direction::Down),
+        Some(3)
+    );
+    assert_eq!(pick_in_container(grid, first, &cells, Direction::Up), None);
+
+    assert_eq!(
+        GamepadStick { x: 0.1, y: -0.9 }.navigation_input(),
+        Some(NavigationInput::Move(Direction::Up))
+    );
+    assert_eq!(
+        GamepadButton::from_standard_index(15),
+        GamepadButton::DPadRight
+    );
+}
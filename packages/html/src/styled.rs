@@ -0,0 +1,189 @@
+//! Runtime support for the `styled!` macro.
+//!
+//! `styled!` turns a block of css declarations into a [`StyleRules`] set. [`styled`] hashes the rules into a class
+//! name, registers the stylesheet once per [`VirtualDom`](dioxus_core::VirtualDom), and returns the class so it can be
+//! passed to the `class` attribute of an element.
+
+use dioxus_core::prelude::*;
+use std::{cell::RefCell, collections::HashSet, fmt::Display, rc::Rc};
+
+/// The theme `styled!` blocks read from when they don't name a theme type.
+///
+/// Provide a custom theme with `use_context_provider(|| Theme { .. })`. Without one, [`Theme::default`] is used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub colors: ThemeColors,
+    pub fonts: ThemeFonts,
+    /// The default border radius
+    pub radius: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            colors: ThemeColors::default(),
+            fonts: ThemeFonts::default(),
+            radius: "4px".to_string(),
+        }
+    }
+}
+
+/// The color palette of a [`Theme`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeColors {
+    pub primary: String,
+    pub secondary: String,
+    pub background: String,
+    pub surface: String,
+    pub text: String,
+    pub muted: String,
+    pub border: String,
+    pub error: String,
+}
+
+impl Default for ThemeColors {
+    fn default() -> Self {
+        Self {
+            primary: "#2563eb".to_string(),
+            secondary: "#7c3aed".to_string(),
+            background: "#ffffff".to_string(),
+            surface: "#f3f4f6".to_string(),
+            text: "#111827".to_string(),
+            muted: "#6b7280".to_string(),
+            border: "#d1d5db".to_string(),
+            error: "#dc2626".to_string(),
+        }
+    }
+}
+
+/// The font stacks of a [`Theme`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeFonts {
+    pub body: String,
+    pub mono: String,
+}
+
+impl Default for ThemeFonts {
+    fn default() -> Self {
+        Self {
+            body: "system-ui, sans-serif".to_string(),
+            mono: "ui-monospace, monospace".to_string(),
+        }
+    }
+}
+
+/// The css rules built by a `styled!` block.
+///
+/// Selectors use `&` for the generated class, so `"&:hover"` styles the element when it is hovered.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleRules {
+    rules: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl StyleRules {
+    /// Add a declaration to the rule for `selector`, creating the rule if it doesn't exist yet
+    pub fn declare(&mut self, selector: &str, property: &str, value: impl Display) {
+        let declaration = (property.to_string(), value.to_string());
+        match self.rules.iter_mut().find(|(s, _)| s == selector) {
+            Some((_, declarations)) => declarations.push(declaration),
+            None => self.rules.push((selector.to_string(), vec![declaration])),
+        }
+    }
+
+    /// Render the rules as css, replacing `&` in each selector with `.{class}`
+    pub fn to_css(&self, class: &str) -> String {
+        let class = format!(".{class}");
+        let mut css = String::new();
+        for (selector, declarations) in &self.rules {
+            if declarations.is_empty() {
+                continue;
+            }
+            css.push_str(&selector.replace('&', &class));
+            css.push_str(" {");
+            for (property, value) in declarations {
+                css.push_str(&format!(" {property}: {value};"));
+            }
+            css.push_str(" }\n");
+        }
+        css
+    }
+
+    /// The class name for these rules. Identical rule sets get the same class.
+    pub fn class_name(&self) -> String {
+        // FNV-1a keeps class names stable across builds and platforms
+        let mut hash: u32 = 0x811c9dc5;
+        for byte in self.to_css("&").bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+        format!("dx-{hash:08x}")
+    }
+}
+
+/// The stylesheets registered in a virtual dom
+#[derive(Clone, Default)]
+struct StyleRegistry {
+    inner: Rc<RefCell<RegisteredStyles>>,
+}
+
+#[derive(Default)]
+struct RegisteredStyles {
+    classes: HashSet<String>,
+    css: String,
+}
+
+/// Build the rules with the current theme, register them, and return the generated class name.
+///
+/// This is what `styled!` expands to. Each distinct rule set is only injected once per virtual dom: on platforms
+/// with an eval provider it is appended to the document head, and it is always available from [`stylesheet`] for
+/// server rendering.
+pub fn styled<T: Clone + Default + 'static>(build: impl FnOnce(&T) -> StyleRules) -> String {
+    let theme = try_consume_context::<T>().unwrap_or_default();
+    let rules = build(&theme);
+    let class = rules.class_name();
+
+    let registry = try_consume_context::<StyleRegistry>()
+        .unwrap_or_else(|| provide_root_context(StyleRegistry::default()));
+    let mut registered = registry.inner.borrow_mut();
+    if registered.classes.insert(class.clone()) {
+        let css = rules.to_css(&class);
+        registered.css.push_str(&css);
+        inject_stylesheet(&class, &css);
+    }
+
+    class
+}
+
+#[cfg(feature = "eval")]
+fn inject_stylesheet(class: &str, css: &str) {
+    // Renderers without an eval provider (like ssr) read the css from `stylesheet` instead
+    if try_consume_context::<Rc<dyn crate::eval::EvalProvider>>().is_none() {
+        return;
+    }
+    let (Ok(id), Ok(css)) = (serde_json::to_string(class), serde_json::to_string(css)) else {
+        return;
+    };
+    _ = crate::eval::eval(&format!(
+        r#"if (!document.getElementById({id})) {{
+            const style = document.createElement("style");
+            style.id = {id};
+            style.textContent = {css};
+            document.head.appendChild(style);
+        }}"#
+    ));
+}
+
+#[cfg(not(feature = "eval"))]
+fn inject_stylesheet(_class: &str, _css: &str) {}
+
+/// All css registered by `styled!` in this virtual dom, in the order it was first used.
+///
+/// Server renderers can include this in a `<style>` tag after rendering the dom.
+pub fn stylesheet(dom: &VirtualDom) -> String {
+    dom.in_runtime(|| {
+        ScopeId::ROOT
+            .consume_context::<StyleRegistry>()
+            .map(|registry| registry.inner.borrow().css.clone())
+            .unwrap_or_default()
+    })
+}
@@ -132,6 +132,9 @@ fn deserialize_raw(
         // Mounted
         "mounted" => Mounted,
 
+        // Resized
+        "resized" => Resized(de(data)?),
+
         // OtherData => "abort" | "afterprint" | "beforeprint" | "beforeunload" | "hashchange" | "languagechange" | "message" | "offline" | "online" | "pagehide" | "pageshow" | "popstate" | "rejectionhandled" | "storage" | "unhandledrejection" | "unload" | "userproximity" | "vrdisplayactivate" | "vrdisplayblur" | "vrdisplayconnect" | "vrdisplaydeactivate" | "vrdisplaydisconnect" | "vrdisplayfocus" | "vrdisplaypointerrestricted" | "vrdisplaypointerunrestricted" | "vrdisplaypresentchange";
         other => {
             return Err(serde_value::DeserializerError::UnknownVariant(
@@ -172,6 +175,7 @@ pub enum EventData {
     Transition(SerializedTransitionData),
     Toggle(SerializedToggleData),
     Image(SerializedImageData),
+    Resized(SerializedResizedData),
     Mounted,
 }
 
@@ -225,6 +229,9 @@ impl EventData {
             EventData::Image(data) => {
                 Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>
             }
+            EventData::Resized(data) => {
+                Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>
+            }
             EventData::Mounted => {
                 Rc::new(PlatformEventData::new(Box::new(MountedData::new(())))) as Rc<dyn Any>
             }
@@ -368,6 +375,14 @@ impl HtmlEventConverter for SerializedHtmlEventConverter {
             .into()
     }
 
+    fn convert_resized_data(&self, event: &PlatformEventData) -> ResizedData {
+        event
+            .downcast::<SerializedResizedData>()
+            .cloned()
+            .unwrap()
+            .into()
+    }
+
     fn convert_scroll_data(&self, event: &PlatformEventData) -> ScrollData {
         event
             .downcast::<SerializedScrollData>()
@@ -170,6 +170,13 @@ impl HasMouseData for MouseEvent {
 impl HasFileData for MouseEvent {}
 
 impl HasDragData for MouseEvent {
+    fn data_transfer(&self) -> DataTransfer {
+        self.dyn_ref::<web_sys::DragEvent>()
+            .and_then(|event| event.data_transfer())
+            .map(|data_transfer| DataTransfer::from(&data_transfer))
+            .unwrap_or_default()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -490,8 +497,59 @@ impl crate::RenderedElementBacking for web_sys::Element {
             });
         Box::pin(async { result })
     }
+
+    fn query_all(
+        &self,
+        query: crate::ElementQuery,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = crate::MountedResult<Vec<crate::MountedData>>>>,
+    > {
+        let result = self
+            .query_selector_all(&query.selector)
+            .map(|nodes| {
+                let elements: Vec<web_sys::Element> = (0..nodes.length())
+                    .filter_map(|i| nodes.get(i)?.dyn_into::<web_sys::Element>().ok())
+                    .collect();
+                elements
+                    .iter()
+                    .filter(|element| match &query.text {
+                        // Only keep the innermost element that has the text
+                        Some(text) => {
+                            has_text(element, text)
+                                && !elements.iter().any(|other| {
+                                    *other != **element
+                                        && element.contains(Some(other))
+                                        && has_text(other, text)
+                                })
+                        }
+                        None => true,
+                    })
+                    .map(|element| crate::MountedData::new(element.clone()))
+                    .collect()
+            })
+            .map_err(|err| crate::MountedError::OperationFailed(Box::new(QueryError(err))));
+        Box::pin(async { result })
+    }
+}
+
+#[cfg(feature = "mounted")]
+fn has_text(element: &web_sys::Element, text: &str) -> bool {
+    element
+        .text_content()
+        .is_some_and(|content| content.trim() == text)
+}
+
+#[derive(Debug)]
+struct QueryError(JsValue);
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to query elements {:?}", self.0)
+    }
 }
 
+impl std::error::Error for QueryError {}
+
 #[derive(Debug)]
 struct FocusError(JsValue);
 
@@ -522,17 +580,75 @@ impl From<&Event> for ClipboardData {
 }
 
 impl HasFocusData for web_sys::FocusEvent {
+    fn related_target(&self) -> Option<RelatedTarget> {
+        let element = web_sys::FocusEvent::related_target(self)?
+            .dyn_into::<web_sys::Element>()
+            .ok()?;
+        Some(RelatedTarget::new(element.tag_name(), Some(element.id())))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 }
 
+impl From<&web_sys::DataTransfer> for DataTransfer {
+    fn from(data_transfer: &web_sys::DataTransfer) -> Self {
+        let list = data_transfer.items();
+        let items = (0..list.length())
+            .filter_map(|i| list.get(i))
+            .map(|item| match item.kind().as_str() {
+                "file" => {
+                    let name = item.get_as_file().ok().flatten().map(|file| file.name());
+                    DataTransferItem::file(item.type_(), name)
+                }
+                _ => {
+                    // The data is only readable in dragstart and drop, every other event gets an empty string
+                    let data = data_transfer
+                        .get_data(&item.type_())
+                        .ok()
+                        .filter(|data| !data.is_empty());
+                    DataTransferItem::string(item.type_(), data)
+                }
+            })
+            .collect();
+        DataTransfer::new(
+            data_transfer.drop_effect(),
+            data_transfer.effect_allowed(),
+            items,
+        )
+    }
+}
+
 impl HasToggleData for web_sys::Event {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 }
 
+impl HasResizedData for web_sys::ResizeObserverEntry {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_content_box_size(&self) -> PixelsSize {
+        let rect = self.content_rect();
+        PixelsSize::new(rect.width(), rect.height())
+    }
+
+    fn get_border_box_size(&self) -> PixelsSize {
+        // Older browsers only report the content box
+        match self
+            .border_box_size()
+            .get(0)
+            .dyn_into::<web_sys::ResizeObserverSize>()
+        {
+            Ok(size) => PixelsSize::new(size.inline_size(), size.block_size()),
+            Err(_) => self.get_content_box_size(),
+        }
+    }
+}
+
 impl HasSelectionData for web_sys::Event {
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -5,6 +5,14 @@ import { setAttributeInner } from "./set_attribute";
 
 export type NodeId = number;
 
+// The kinds of mutations update highlighting flashes, and the color of each
+export type HighlightKind = "attribute" | "text" | "replace";
+const HIGHLIGHT_COLORS: { [key in HighlightKind]: string } = {
+  attribute: "#3b82f6",
+  text: "#22c55e",
+  replace: "#f97316",
+};
+
 export class BaseInterpreter {
   // non bubbling events listen at the element the listener was created at
   global: {
@@ -18,6 +26,8 @@ export class BaseInterpreter {
   };
 
   root: HTMLElement;
+  // elements outside of the root that portals render into. Bubbling events listen at them too
+  portalRoots: HTMLElement[];
   handler: EventListener;
   nodes: Node[];
   stack: Node[];
@@ -28,12 +38,21 @@ export class BaseInterpreter {
   // sledgehammer is generating this...
   m: any;
 
-  constructor() {}
+  // Flash nodes when they receive mutations. Toggled with the `dioxus-highlight-updates` event on the window
+  highlightUpdates: boolean;
+
+  constructor() {
+    this.highlightUpdates = false;
+    window.addEventListener("dioxus-highlight-updates", (event: Event) => {
+      this.highlightUpdates = (event as CustomEvent<boolean>).detail;
+    });
+  }
 
   initialize(root: HTMLElement, handler: EventListener | null = null) {
     this.global = {};
     this.local = {};
     this.root = root;
+    this.portalRoots = [];
 
     this.nodes = [root];
     this.stack = [root];
@@ -49,6 +68,9 @@ export class BaseInterpreter {
       if (this.global[event_name] === undefined) {
         this.global[event_name] = { active: 1, callback: this.handler };
         this.root.addEventListener(event_name, this.handler);
+        for (const portalRoot of this.portalRoots) {
+          portalRoot.addEventListener(event_name, this.handler);
+        }
       } else {
         this.global[event_name].active++;
       }
@@ -76,6 +98,12 @@ export class BaseInterpreter {
         event_name,
         this.global[event_name].callback
       );
+      for (const portalRoot of this.portalRoots) {
+        portalRoot.removeEventListener(
+          event_name,
+          this.global[event_name].callback
+        );
+      }
       delete this.global[event_name];
     }
   }
@@ -106,6 +134,26 @@ export class BaseInterpreter {
     }
   }
 
+  // Move nodes into an element outside of the root, like the target of a portal. Events from the nodes don't bubble
+  // up to the root, so the bubbling listeners are added to the target too
+  appendToPortal(target: string, many: number) {
+    const els = this.stack.splice(this.stack.length - many);
+    const root = document.getElementById(target);
+    if (!root) {
+      console.warn(`Portal target "${target}" was not found`);
+      return;
+    }
+    if (!this.root.contains(root) && !this.portalRoots.includes(root)) {
+      this.portalRoots.push(root);
+      for (const event_name in this.global) {
+        root.addEventListener(event_name, this.global[event_name].callback);
+      }
+    }
+    for (const el of els) {
+      root.appendChild(el);
+    }
+  }
+
   loadChild(ptr: number, len: number): Node {
     // iterate through each number and get that child
     let node = this.stack[this.stack.length - 1] as Node;
@@ -177,6 +225,33 @@ export class BaseInterpreter {
     }
   }
 
+  // Flash the area of a node that received a mutation. Nodes inside an element with the `data-dioxus-no-highlight`
+  // attribute are never flashed
+  highlight(node: Node, kind: HighlightKind) {
+    const element =
+      node.nodeType === Node.ELEMENT_NODE
+        ? (node as HTMLElement)
+        : node.parentElement;
+    if (
+      !element ||
+      !element.isConnected ||
+      element.closest("[data-dioxus-no-highlight]")
+    ) {
+      return;
+    }
+    const rect = element.getBoundingClientRect();
+    if (rect.width === 0 && rect.height === 0) {
+      return;
+    }
+    const color = HIGHLIGHT_COLORS[kind];
+    const flash = document.createElement("div");
+    flash.setAttribute("data-dioxus-no-highlight", "");
+    flash.style.cssText = `position:fixed;left:${rect.left}px;top:${rect.top}px;width:${rect.width}px;height:${rect.height}px;outline:2px solid ${color};background:${color}22;pointer-events:none;z-index:2147483646;transition:opacity 0.5s;`;
+    document.body.appendChild(flash);
+    setTimeout(() => (flash.style.opacity = "0"), 100);
+    setTimeout(() => flash.remove(), 600);
+  }
+
   setAttributeInner(
     node: HTMLElement,
     field: string,
@@ -26,7 +26,7 @@ export function serializeEvent(event: Event, target: EventTarget): SerializedEve
   if (event instanceof TransitionEvent) { extend({ property_name: event.propertyName, elapsed_time: event.elapsedTime, pseudo_element: event.pseudoElement, }) }
   if (event instanceof CompositionEvent) { extend({ data: event.data, }) }
   if (event instanceof DragEvent) { extend(serializeDragEvent(event)) }
-  if (event instanceof FocusEvent) { extend({}) }
+  if (event instanceof FocusEvent) { extend(serializeFocusEvent(event)) }
   if (event instanceof ClipboardEvent) { extend({}) }
 
   // safari is quirky and doesn't have TouchEvent
@@ -80,6 +80,7 @@ function serializeInputEvent(event: InputEvent, target: EventTarget): Serialized
     }
 
     contents.value = value;
+    contents.input_type = target.type;
   }
 
   if (event.target instanceof HTMLTextAreaElement) {
@@ -206,6 +207,7 @@ function serializeDragEvent(event: DragEvent): SerializedEvent {
     };
   }
   return {
+    data_transfer: serializeDataTransfer(event.dataTransfer),
     mouse: {
       alt_key: event.altKey,
       ctrl_key: event.ctrlKey,
@@ -216,3 +218,34 @@ function serializeDragEvent(event: DragEvent): SerializedEvent {
     files,
   };
 }
+
+function serializeFocusEvent(event: FocusEvent): SerializedEvent {
+  let related_target = null;
+  if (event.relatedTarget instanceof Element) {
+    related_target = {
+      tag_name: event.relatedTarget.tagName.toLowerCase(),
+      id: event.relatedTarget.id || null,
+    };
+  }
+  return { related_target };
+}
+
+function serializeDataTransfer(dataTransfer: DataTransfer | null): SerializedEvent {
+  if (!dataTransfer) {
+    return {};
+  }
+  let items = Array.from(dataTransfer.items).map((item) => {
+    if (item.kind === "file") {
+      let file = item.getAsFile();
+      return { kind: "file", type: item.type, name: file ? file.name : null };
+    }
+    // The data is only readable in dragstart and drop, every other event gets an empty string
+    let data = dataTransfer.getData(item.type);
+    return { kind: "string", type: item.type, data: data === "" ? null : data };
+  });
+  return {
+    drop_effect: dataTransfer.dropEffect,
+    effect_allowed: dataTransfer.effectAllowed,
+    items,
+  };
+}
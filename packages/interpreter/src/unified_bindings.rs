@@ -32,6 +32,21 @@ impl Interpreter {
     }
 }
 
+/// The longest attribute value in bytes that is sent through the string table of the channel. Longer values are
+/// rarely repeated, so they are sent inline instead of evicting the short ones.
+pub const MAX_CACHED_ATTRIBUTE_VALUE_LEN: usize = 64;
+
+impl Interpreter {
+    /// Set an attribute, sending short values through the string table of the channel
+    pub fn set_attribute_value(&mut self, id: u32, field: &str, value: &str, ns: &str) {
+        if value.len() <= MAX_CACHED_ATTRIBUTE_VALUE_LEN {
+            self.set_cached_attribute(id, field, value, ns)
+        } else {
+            self.set_attribute(id, field, value, ns)
+        }
+    }
+}
+
 #[sledgehammer_bindgen::bindgen(module)]
 mod js {
     // Extend the web base class
@@ -50,11 +65,14 @@ mod js {
     fn append_children(id: u32, many: u16) {
         "{this.appendChildren($id$, $many$);}"
     }
+    fn append_to_portal(target: &str, many: u16) {
+        "{this.appendToPortal($target$, $many$);}"
+    }
     fn pop_root() {
         "{this.stack.pop();}"
     }
     fn replace_with(id: u32, n: u16) {
-        "{const root = this.nodes[$id$]; let els = this.stack.splice(this.stack.length-$n$); if (root.listening) { this.removeAllNonBubblingListeners(root); } root.replaceWith(...els);}"
+        "{const root = this.nodes[$id$]; let els = this.stack.splice(this.stack.length-$n$); if (root.listening) { this.removeAllNonBubblingListeners(root); } root.replaceWith(...els); if (this.highlightUpdates) { for (const el of els) { this.highlight(el, 'replace'); } }}"
     }
     fn insert_after(id: u32, n: u16) {
         "{this.nodes[$id$].after(...this.stack.splice(this.stack.length-$n$));}"
@@ -83,13 +101,26 @@ mod js {
         "#
     }
     fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{let node = this.nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); this.removeListener(node, $event_name$, $bubbles$);}"
+        "{let node = this.nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); if ($event_name$ === 'resized' && node.resizeObserver) { node.resizeObserver.disconnect(); node.resizeObserver = null; } this.removeListener(node, $event_name$, $bubbles$);}"
     }
     fn set_text(id: u32, text: &str) {
-        "{this.nodes[$id$].textContent = $text$;}"
+        "{let node = this.nodes[$id$]; node.textContent = $text$; if (this.highlightUpdates) { this.highlight(node, 'text'); }}"
+    }
+    fn replace_text(id: u32, start: u32, delete: u32, text: &str) {
+        "{let node = this.nodes[$id$]; node.replaceData($start$, $delete$, $text$); if (this.highlightUpdates) { this.highlight(node, 'text'); }}"
     }
     fn set_attribute(id: u32, field: &str<u8, attr>, value: &str, ns: &str<u8, ns_cache>) {
-        "{let node = this.nodes[$id$]; this.setAttributeInner(node, $field$, $value$, $ns$);}"
+        "{let node = this.nodes[$id$]; this.setAttributeInner(node, $field$, $value$, $ns$); if (this.highlightUpdates) { this.highlight(node, 'attribute'); }}"
+    }
+    // Short values like classes repeat across many elements, so they are kept in a string table on both sides of
+    // the channel and sent as an index after the first time. Use `set_attribute_value` to pick the right binding.
+    fn set_cached_attribute(
+        id: u32,
+        field: &str<u8, attr>,
+        value: &str<u16, attr_value>,
+        ns: &str<u8, ns_cache>,
+    ) {
+        "{let node = this.nodes[$id$]; this.setAttributeInner(node, $field$, $value$, $ns$); if (this.highlightUpdates) { this.highlight(node, 'attribute'); }}"
     }
     fn remove_attribute(id: u32, field: &str<u8, attr>, ns: &str<u8, ns_cache>) {
         r#"{
@@ -106,6 +137,12 @@ mod js {
                     case "selected":
                         node.selected = false;
                         break;
+                    case "initial_checked":
+                        node.defaultChecked = false;
+                        break;
+                    case "initial_selected":
+                        node.defaultSelected = false;
+                        break;
                     case "dangerous_inner_html":
                         node.innerHTML = "";
                         break;
@@ -118,6 +155,9 @@ mod js {
             } else {
                 node.removeAttributeNS(ns, field);
             }
+            if (this.highlightUpdates) {
+                this.highlight(node, "attribute");
+            }
         }"#
     }
     fn assign_id(ptr: u32, len: u8, id: u32) {
@@ -202,6 +242,34 @@ mod js {
                 bubbles,
             })
         );
+    } else if (event_name === "resized") {
+        // resized events come from a resize observer instead of a dom listener
+        if (this_node.resizeObserver) {
+            this_node.resizeObserver.disconnect();
+        }
+        this_node.resizeObserver = new ResizeObserver((entries, observer) => {
+            for (const entry of entries) {
+                if (!entry.target.isConnected) {
+                    observer.disconnect();
+                    return;
+                }
+                const border_box = entry.borderBoxSize && entry.borderBoxSize[0];
+                window.ipc.postMessage(
+                    this.serializeIpcMessage("user_event", {
+                        name: event_name,
+                        element: id,
+                        data: {
+                            content_box_width: entry.contentRect.width,
+                            content_box_height: entry.contentRect.height,
+                            border_box_width: border_box ? border_box.inlineSize : entry.contentRect.width,
+                            border_box_height: border_box ? border_box.blockSize : entry.contentRect.height,
+                        },
+                        bubbles,
+                    })
+                );
+            }
+        });
+        this_node.resizeObserver.observe(this_node);
     } else {
         this.createListener(event_name, this_node, bubbles, (event) => {
             this.handler(event, event_name, bubbles);
@@ -99,6 +99,10 @@ impl WriteMutations for MutationState {
         self.channel.append_children(id.0 as u32, m as u16);
     }
 
+    fn append_to_portal(&mut self, target: &str, m: usize) {
+        self.channel.append_to_portal(target, m as u16);
+    }
+
     fn assign_node_id(&mut self, path: &'static [u8], id: dioxus_core::ElementId) {
         self.channel.assign_id_ref(path, id.0 as u32);
     }
@@ -148,21 +152,28 @@ impl WriteMutations for MutationState {
         match value {
             dioxus_core::AttributeValue::Text(txt) => {
                 self.channel
-                    .set_attribute(id.0 as u32, name, txt, ns.unwrap_or_default())
+                    .set_attribute_value(id.0 as u32, name, txt, ns.unwrap_or_default())
             }
-            dioxus_core::AttributeValue::Float(f) => self.channel.set_attribute(
+            dioxus_core::AttributeValue::Float(f) => self.channel.set_attribute_value(
                 id.0 as u32,
                 name,
                 &f.to_string(),
                 ns.unwrap_or_default(),
             ),
-            dioxus_core::AttributeValue::Int(n) => self.channel.set_attribute(
+            dioxus_core::AttributeValue::Int(n) => self.channel.set_attribute_value(
                 id.0 as u32,
                 name,
                 &n.to_string(),
                 ns.unwrap_or_default(),
             ),
-            dioxus_core::AttributeValue::Bool(b) => self.channel.set_attribute(
+            // Boolean values toggle the presence of the attribute unless it expects "true" or "false"
+            dioxus_core::AttributeValue::Bool(false)
+                if !dioxus_html::is_enumerated_attribute(name) =>
+            {
+                self.channel
+                    .remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
+            }
+            dioxus_core::AttributeValue::Bool(b) => self.channel.set_attribute_value(
                 id.0 as u32,
                 name,
                 if *b { "true" } else { "false" },
@@ -180,6 +191,18 @@ impl WriteMutations for MutationState {
         self.channel.set_text(id.0 as u32, value);
     }
 
+    fn replace_node_text(
+        &mut self,
+        start: usize,
+        delete: usize,
+        value: &str,
+        _: &str,
+        id: dioxus_core::ElementId,
+    ) {
+        self.channel
+            .replace_text(id.0 as u32, start as u32, delete as u32, value);
+    }
+
     fn create_event_listener(&mut self, name: &'static str, id: dioxus_core::ElementId) {
         // note that we use the foreign event listener here instead of the native one
         // the native method assumes we have direct access to the dom, which we don't.
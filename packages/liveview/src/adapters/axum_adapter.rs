@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{interpreter_glue, LiveViewError, LiveViewSocket, LiveviewRouter};
+use crate::{interpreter_glue, LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -43,8 +43,15 @@ impl LiveviewRouter for Router {
         route: &str,
         app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
     ) -> Self {
-        let view = crate::LiveViewPool::new();
+        self.with_virtual_dom_in_pool(route, app, LiveViewPool::new())
+    }
 
+    fn with_virtual_dom_in_pool(
+        self,
+        route: &str,
+        app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+        view: LiveViewPool,
+    ) -> Self {
         let ws_path = format!("{}/ws", route);
         let title = crate::app_title();
 
@@ -69,7 +76,7 @@ impl LiveviewRouter for Router {
                 let app = app.clone();
                 ws.on_upgrade(move |socket| async move {
                     _ = view
-                        .launch_virtualdom(axum_socket(socket), move || app())
+                        .launch_restartable(axum_socket(socket), move || app())
                         .await;
                 })
             }),
@@ -2,6 +2,8 @@ use std::future::Future;
 
 use dioxus_core::{Element, VirtualDom};
 
+use crate::LiveViewPool;
+
 #[cfg(feature = "axum")]
 pub mod axum_adapter;
 #[cfg(feature = "axum")]
@@ -27,6 +29,22 @@ pub trait LiveviewRouter {
         app: impl Fn() -> VirtualDom + Send + Sync + 'static,
     ) -> Self;
 
+    /// Add a liveview route to the server from a virtual dom, with the sessions running in a pool.
+    ///
+    /// Routers that don't support custom pools ignore the pool and use the default one.
+    fn with_virtual_dom_in_pool(
+        self,
+        route: &str,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+        pool: LiveViewPool,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        _ = pool;
+        self.with_virtual_dom(route, app)
+    }
+
     /// Start the server on an address.
     fn start(self, address: impl Into<std::net::SocketAddr>) -> impl Future<Output = ()>;
 }
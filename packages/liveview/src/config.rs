@@ -1,7 +1,7 @@
 use dioxus_cli_config::CURRENT_CONFIG;
 use dioxus_core::VirtualDom;
 
-use crate::LiveviewRouter;
+use crate::{LiveViewPool, LiveviewRouter, PanicBehavior};
 
 pub(crate) fn app_title() -> String {
     CURRENT_CONFIG
@@ -15,6 +15,7 @@ pub struct Config<R: LiveviewRouter> {
     router: R,
     address: std::net::SocketAddr,
     route: String,
+    panic_behavior: PanicBehavior,
 }
 
 impl<R: LiveviewRouter> Default for Config<R> {
@@ -23,6 +24,7 @@ impl<R: LiveviewRouter> Default for Config<R> {
             router: R::create_default_liveview_router(),
             address: ([127, 0, 0, 1], 8080).into(),
             route: "/".to_string(),
+            panic_behavior: PanicBehavior::default(),
         }
     }
 }
@@ -34,18 +36,26 @@ impl<R: LiveviewRouter> Config<R> {
         self
     }
 
-    /// Create a new configuration for the LiveView server.
-    pub fn with_app(mut self, app: fn() -> dioxus_core::prelude::Element) -> Self {
-        self.router = self.router.with_app(&self.route, app);
+    /// Set what a session does after the app panics. This applies to the apps added after it.
+    pub fn with_panic_behavior(mut self, behavior: PanicBehavior) -> Self {
+        self.panic_behavior = behavior;
         self
     }
 
+    /// Create a new configuration for the LiveView server.
+    pub fn with_app(self, app: fn() -> dioxus_core::prelude::Element) -> Self {
+        self.with_virtual_dom(move || VirtualDom::new(app))
+    }
+
     /// Create a new configuration for the LiveView server.
     pub fn with_virtual_dom(
         mut self,
         virtual_dom: impl Fn() -> VirtualDom + Send + Sync + 'static,
     ) -> Self {
-        self.router = self.router.with_virtual_dom(&self.route, virtual_dom);
+        let pool = LiveViewPool::new().with_panic_behavior(self.panic_behavior);
+        self.router = self
+            .router
+            .with_virtual_dom_in_pool(&self.route, virtual_dom, pool);
         self
     }
 
@@ -1,7 +1,7 @@
 use dioxus_core::ElementId;
 use dioxus_html::{
     geometry::{PixelsRect, PixelsSize, PixelsVector2D},
-    MountedResult, RenderedElementBacking,
+    ElementQuery, MountedData, MountedResult, RenderedElementBacking,
 };
 
 use crate::query::QueryEngine;
@@ -10,15 +10,44 @@ use crate::query::QueryEngine;
 #[derive(Clone)]
 pub struct LiveviewElement {
     id: ElementId,
+    /// The queries and indexes that lead from the mounted element to the descendant this handle points to
+    path: Vec<(ElementQuery, usize)>,
     query: QueryEngine,
 }
 
 impl LiveviewElement {
     pub(crate) fn new(id: ElementId, query: QueryEngine) -> Self {
-        Self { id, query }
+        Self {
+            id,
+            path: Vec::new(),
+            query,
+        }
+    }
+
+    /// A javascript expression that evaluates to the node this handle points to
+    fn node_script(&self) -> String {
+        self.path.iter().fold(
+            format!("window.interpreter.nodes[{}]", self.id.0),
+            |node, (query, index)| format!("{}[{index}]", query_script(&node, query)),
+        )
     }
 }
 
+/// A javascript expression that evaluates to the elements under `root` that match the query
+fn query_script(root: &str, query: &ElementQuery) -> String {
+    format!(
+        r#"((root, selector, text) => {{
+            if (!(root instanceof Element)) return [];
+            const all = Array.from(root.querySelectorAll(selector));
+            if (text === null) return all;
+            const matches = all.filter((el) => el.textContent.trim() === text);
+            return matches.filter((el) => !matches.some((other) => other !== el && el.contains(other)));
+        }})({root}, {}, {})"#,
+        serde_json::to_string(&query.selector).expect("Failed to serialize the selector"),
+        serde_json::to_string(&query.text).expect("Failed to serialize the text")
+    )
+}
+
 macro_rules! scripted_getter {
     ($meth_name:ident, $script:literal, $output_type:path) => {
         fn $meth_name(
@@ -26,7 +55,7 @@ macro_rules! scripted_getter {
         ) -> std::pin::Pin<
             Box<dyn futures_util::Future<Output = dioxus_html::MountedResult<$output_type>>>,
         > {
-            let script = format!($script, id = self.id.0);
+            let script = format!($script, node = self.node_script());
 
             let fut = self
                 .query
@@ -54,19 +83,19 @@ impl RenderedElementBacking for LiveviewElement {
 
     scripted_getter!(
         get_scroll_offset,
-        "return [window.interpreter.getScrollLeft({id}), window.interpreter.getScrollTop({id})]",
+        "const node = {node}; if (node instanceof HTMLElement) return [node.scrollLeft, node.scrollTop]; return null;",
         PixelsVector2D
     );
 
     scripted_getter!(
         get_scroll_size,
-        "return [window.interpreter.getScrollWidth({id}), window.interpreter.getScrollHeight({id})]",
+        "const node = {node}; if (node instanceof HTMLElement) return [node.scrollWidth, node.scrollHeight]; return null;",
         PixelsSize
     );
 
     scripted_getter!(
         get_client_rect,
-        "return window.interpreter.getClientRect({id});",
+        "const node = {node}; if (!(node instanceof HTMLElement)) return null; const rect = node.getBoundingClientRect(); return {{ type: 'GetClientRect', origin: [rect.x, rect.y], size: [rect.width, rect.height] }};",
         PixelsRect
     );
 
@@ -75,8 +104,8 @@ impl RenderedElementBacking for LiveviewElement {
         behavior: dioxus_html::ScrollBehavior,
     ) -> std::pin::Pin<Box<dyn futures_util::Future<Output = dioxus_html::MountedResult<()>>>> {
         let script = format!(
-            "return window.interpreter.scrollTo({}, {});",
-            self.id.0,
+            "const node = {}; if (!(node instanceof HTMLElement)) return false; node.scrollIntoView({{ behavior: {} }}); return true;",
+            self.node_script(),
             serde_json::to_string(&behavior).expect("Failed to serialize ScrollBehavior")
         );
 
@@ -99,8 +128,9 @@ impl RenderedElementBacking for LiveviewElement {
         focus: bool,
     ) -> std::pin::Pin<Box<dyn futures_util::Future<Output = dioxus_html::MountedResult<()>>>> {
         let script = format!(
-            "return window.interpreter.setFocus({}, {});",
-            self.id.0, focus
+            "const node = {}; if (!(node instanceof HTMLElement)) return false; {}; return true;",
+            self.node_script(),
+            if focus { "node.focus()" } else { "node.blur()" }
         );
 
         let fut = self.query.new_query::<bool>(&script).resolve();
@@ -117,6 +147,42 @@ impl RenderedElementBacking for LiveviewElement {
             }
         })
     }
+
+    fn query_all(
+        &self,
+        query: ElementQuery,
+    ) -> std::pin::Pin<
+        Box<dyn futures_util::Future<Output = dioxus_html::MountedResult<Vec<MountedData>>>>,
+    > {
+        let script = format!(
+            "const node = {}; if (!(node instanceof Element)) return null; return {}.length;",
+            self.node_script(),
+            query_script("node", &query)
+        );
+
+        let fut = self.query.new_query::<Option<usize>>(&script).resolve();
+        let element = self.clone();
+        Box::pin(async move {
+            match fut.await {
+                Ok(Some(count)) => Ok((0..count)
+                    .map(|index| {
+                        let mut path = element.path.clone();
+                        path.push((query.clone(), index));
+                        MountedData::new(LiveviewElement {
+                            path,
+                            ..element.clone()
+                        })
+                    })
+                    .collect()),
+                Ok(None) => MountedResult::Err(dioxus_html::MountedError::OperationFailed(
+                    Box::new(DesktopQueryError::FailedToQuery),
+                )),
+                Err(err) => {
+                    MountedResult::Err(dioxus_html::MountedError::OperationFailed(Box::new(err)))
+                }
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,107 @@
+//! Keep the url bar of the page in sync with the router running on the server
+//!
+//! The browser sends the location of the page when the session starts and whenever the user goes back or forward.
+//! Routers send the navigations they make back to the browser with [`HistoryChannel::navigate`].
+
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+/// The location of the page in the browser
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageLocation {
+    /// The path, query and fragment of the page
+    pub route: String,
+
+    /// The state the router stored in the history entry, if the entry was created by the router
+    #[serde(default)]
+    pub state: Option<serde_json::Value>,
+
+    /// The state the router stored for the tab, which survives reloads and reconnects
+    #[serde(default)]
+    pub session: Option<serde_json::Value>,
+
+    /// The number of entries in the history of the tab
+    #[serde(default)]
+    pub depth: usize,
+}
+
+/// A navigation the server asks the browser to make
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum HistoryAction {
+    /// Add a history entry. This doesn't trigger a popstate event
+    Push {
+        /// The path, query and fragment of the new entry
+        route: String,
+        /// The state to store in the new entry
+        state: serde_json::Value,
+        /// The new state of the tab
+        session: serde_json::Value,
+    },
+    /// Replace the current history entry. This doesn't trigger a popstate event
+    Replace {
+        /// The path, query and fragment of the entry
+        route: String,
+        /// The state to store in the entry
+        state: serde_json::Value,
+        /// The new state of the tab
+        session: serde_json::Value,
+    },
+    /// Go back one entry. The browser answers with the location it went to
+    Back,
+    /// Go forward one entry. The browser answers with the location it went to
+    Forward,
+    /// Leave the app for another url
+    External {
+        /// The url to navigate to
+        url: String,
+    },
+}
+
+/// The history of the page a liveview session is connected to. It is provided as a context at the root of every
+/// liveview app.
+#[derive(Clone)]
+pub struct HistoryChannel {
+    initial: Option<PageLocation>,
+    actions: UnboundedSender<HistoryAction>,
+    listeners: Rc<RefCell<Vec<UnboundedSender<PageLocation>>>>,
+}
+
+impl HistoryChannel {
+    pub(crate) fn new(
+        initial: Option<PageLocation>,
+        actions: UnboundedSender<HistoryAction>,
+    ) -> Self {
+        Self {
+            initial,
+            actions,
+            listeners: Default::default(),
+        }
+    }
+
+    /// The location of the page when the session started. This is the deep link the user opened, or the page they
+    /// reloaded
+    pub fn initial_location(&self) -> Option<&PageLocation> {
+        self.initial.as_ref()
+    }
+
+    /// Ask the browser to make a navigation
+    pub fn navigate(&self, action: HistoryAction) {
+        let _ = self.actions.send(action);
+    }
+
+    /// Receive the location of the page every time the user goes back or forward
+    pub fn listen(&self) -> UnboundedReceiver<PageLocation> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        self.listeners.borrow_mut().push(tx);
+        rx
+    }
+
+    /// Tell the listeners the browser moved to another history entry
+    pub(crate) fn pop(&self, location: PageLocation) {
+        self.listeners
+            .borrow_mut()
+            .retain(|listener| listener.send(location.clone()).is_ok());
+    }
+}
@@ -0,0 +1,67 @@
+//! The hot reloading connection of a liveview server
+//!
+//! Every session of the server runs the same code, so the server connects to the hot reloading server once and
+//! forwards the changes to every session. Sessions patch their templates in place, which keeps their websocket and
+//! the state on the server alive.
+
+use dioxus_core::{HotReloadError, Template, VirtualDom};
+use dioxus_hot_reload::HotReloadMsg;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+use tokio::sync::broadcast;
+
+/// How many changes a session can fall behind before it misses some
+const CHANGE_CAPACITY: usize = 256;
+
+/// The changes made since the server started, shared by every session
+struct HotReloadState {
+    /// The latest version of every template that changed. Sessions that start later render them from the first frame
+    templates: HashMap<&'static str, Template>,
+    /// The last change that couldn't be hot reloaded
+    error: Option<HotReloadError>,
+    sender: broadcast::Sender<HotReloadMsg>,
+}
+
+fn state() -> &'static Mutex<HotReloadState> {
+    static STATE: OnceLock<Mutex<HotReloadState>> = OnceLock::new();
+    STATE.get_or_init(|| {
+        let (sender, _) = broadcast::channel(CHANGE_CAPACITY);
+        dioxus_hot_reload::connect(record_change);
+        Mutex::new(HotReloadState {
+            templates: HashMap::new(),
+            error: None,
+            sender,
+        })
+    })
+}
+
+/// Remember a change from the hot reloading server and forward it to the running sessions
+fn record_change(msg: HotReloadMsg) {
+    let mut state = state().lock().unwrap();
+    match &msg {
+        HotReloadMsg::UpdateTemplate(template) => {
+            state.templates.insert(template.name, *template);
+        }
+        HotReloadMsg::ReportError(error) => state.error = error.clone(),
+        // Literals are shared by every virtual dom in the process already
+        HotReloadMsg::UpdateLiterals(_) | HotReloadMsg::UpdateAsset { .. } => {}
+        // The state of liveview apps lives on the server, which is restarted with the new build
+        HotReloadMsg::SaveState => return,
+        HotReloadMsg::Shutdown => std::process::exit(0),
+    }
+    let _ = state.sender.send(msg);
+}
+
+/// Bring a new session up to date with the changes made before it started and listen for the next ones
+pub(crate) fn subscribe(vdom: &mut VirtualDom) -> broadcast::Receiver<HotReloadMsg> {
+    let state = state().lock().unwrap();
+    for template in state.templates.values() {
+        vdom.replace_template(*template);
+    }
+    if state.error.is_some() {
+        vdom.set_hot_reload_error(state.error.clone());
+    }
+    state.sender.subscribe()
+}
@@ -7,11 +7,16 @@ mod adapters;
 pub use adapters::*;
 
 mod element;
+mod history;
+pub use history::*;
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+mod hot_reload;
 pub mod pool;
 mod query;
 use dioxus_interpreter_js::NATIVE_JS;
 use futures_util::{SinkExt, StreamExt};
 pub use pool::*;
+use std::time::Duration;
 mod config;
 mod eval;
 mod events;
@@ -116,6 +121,22 @@ fn handle_edits_code() -> String {
 /// interpreter_glue("ws://localhost:8080/api/liveview");
 /// ```
 pub fn interpreter_glue(url_or_path: &str) -> String {
+    interpreter_glue_with_input_debounce(url_or_path, Duration::ZERO)
+}
+
+/// Create the [`interpreter_glue`] with a debounce for text input events
+///
+/// Every input event is a round trip to the server. On high latency connections you can wait until the user stops
+/// typing for `debounce` before sending the latest value. Events other than input still go out immediately, after
+/// any input they follow. Text an IME is still composing is never sent regardless of the debounce.
+///
+/// ```rust
+/// use dioxus_liveview::interpreter_glue_with_input_debounce;
+/// use std::time::Duration;
+///
+/// interpreter_glue_with_input_debounce("/api/liveview", Duration::from_millis(150));
+/// ```
+pub fn interpreter_glue_with_input_debounce(url_or_path: &str, debounce: Duration) -> String {
     // If the url starts with a `/`, generate glue which reuses current host
     let get_ws_url = if url_or_path.starts_with('/') {
         r#"
@@ -134,6 +155,7 @@ pub fn interpreter_glue(url_or_path: &str) -> String {
     };
 
     let handle_edits = handle_edits_code();
+    let debounce_ms = debounce.as_millis();
 
     format!(
         r#"
@@ -143,6 +165,7 @@ pub fn interpreter_glue(url_or_path: &str) -> String {
     }}
 
     var WS_ADDR = __dioxusGetWsUrl("{url_or_path}");
+    var INPUT_DEBOUNCE_MS = {debounce_ms};
     {handle_edits}
 </script>
     "#
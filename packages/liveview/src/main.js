@@ -1,5 +1,8 @@
 const intercept_link_redirects = false;
 
+// The key the router keeps the history of the tab under, so it survives reloads and reconnects
+const HISTORY_SESSION_KEY = "dioxus-liveview-history";
+
 function main() {
   let root = window.document.getElementById("main");
   if (root != null) {
@@ -9,9 +12,20 @@ function main() {
 
 class IPC {
   constructor(root) {
+    this.root = root;
     window.interpreter = new NativeInterpreter();
     window.interpreter.initialize(root);
     window.interpreter.ipc = this;
+
+    // Input events that haven't been sent to the server yet, keyed by the id of their element
+    this.pendingInputs = new Map();
+    // The values of input events sent to the server that it hasn't rendered back yet
+    this.sentValues = new Map();
+    this.composing = null;
+    this.flushTimeout = null;
+    this.trackComposition(root);
+    this.reconcileValues(window.interpreter);
+
     const ws = new WebSocket(WS_ADDR);
     ws.binaryType = "arraybuffer";
 
@@ -22,9 +36,14 @@ class IPC {
     ws.onopen = () => {
       // we ping every 30 seconds to keep the websocket alive
       setInterval(ping, 30000);
-      ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      ws.send(window.interpreter.serializeIpcMessage("initialize", { location: this.pageLocation() }));
     };
 
+    // Tell the router on the server when the user goes back or forward
+    window.addEventListener("popstate", () => {
+      this.postMessage(window.interpreter.serializeIpcMessage("history", this.pageLocation()));
+    });
+
     ws.onerror = (err) => {
       // todo: retry the connection
     };
@@ -52,6 +71,12 @@ class IPC {
             case "query":
               Function("Eval", `"use strict";${event.data};`)();
               break;
+            case "history":
+              this.applyHistory(event.data);
+              break;
+            case "restart":
+              this.restart();
+              break;
           }
         }
       }
@@ -60,9 +85,137 @@ class IPC {
     this.ws = ws;
   }
 
+  pageLocation() {
+    return {
+      route: location.pathname + location.search + location.hash,
+      state: history.state,
+      session: JSON.parse(sessionStorage.getItem(HISTORY_SESSION_KEY)),
+      depth: history.length,
+    };
+  }
+
+  // The server started the app over after it panicked. It renders the new app from scratch
+  restart() {
+    clearTimeout(this.flushTimeout);
+    this.pendingInputs.clear();
+    this.sentValues.clear();
+    this.root.replaceChildren();
+    window.interpreter.nodes = [this.root];
+  }
+
+  // Make a navigation the router on the server asked for
+  applyHistory(action) {
+    switch (action.action) {
+      case "push":
+        history.pushState(action.state, "", action.route);
+        break;
+      case "replace":
+        history.replaceState(action.state, "", action.route);
+        break;
+      case "back":
+        history.back();
+        break;
+      case "forward":
+        history.forward();
+        break;
+      case "external":
+        location.href = action.url;
+        break;
+    }
+    if (action.session !== undefined) {
+      sessionStorage.setItem(HISTORY_SESSION_KEY, JSON.stringify(action.session));
+    }
+  }
+
   postMessage(msg) {
+    const parsed = JSON.parse(msg);
+    const params = parsed.params;
+
+    if (parsed.method === "user_event" && params.name === "input" && params.data.value !== undefined) {
+      this.pendingInputs.set(String(params.element), msg);
+      this.scheduleFlush();
+      return;
+    }
+
+    // Everything else has to see the latest value of the inputs, so send them first
+    this.flushInputs();
     this.ws.send(msg);
   }
+
+  // Hold input events while an IME is composing text so only committed text reaches the server
+  trackComposition(root) {
+    root.addEventListener("compositionstart", (event) => {
+      this.composing = event.target;
+    }, true);
+    root.addEventListener("compositionend", () => {
+      this.composing = null;
+      this.scheduleFlush();
+    }, true);
+  }
+
+  scheduleFlush() {
+    if (this.composing !== null) {
+      return;
+    }
+    if (INPUT_DEBOUNCE_MS <= 0) {
+      this.flushInputs();
+      return;
+    }
+    clearTimeout(this.flushTimeout);
+    this.flushTimeout = setTimeout(() => this.flushInputs(), INPUT_DEBOUNCE_MS);
+  }
+
+  flushInputs() {
+    clearTimeout(this.flushTimeout);
+    if (this.composing !== null) {
+      return;
+    }
+    for (const [id, msg] of this.pendingInputs) {
+      const sent = this.sentValues.get(id) || [];
+      sent.push(JSON.parse(msg).params.data.value);
+      this.sentValues.set(id, sent);
+      this.ws.send(msg);
+    }
+    this.pendingInputs.clear();
+  }
+
+  // The server renders the value of an input a round trip after the user typed it. Skip values that would overwrite
+  // text the server hasn't seen yet or an in-progress composition
+  reconcileValues(interpreter) {
+    const setAttributeInner = interpreter.setAttributeInner.bind(interpreter);
+    interpreter.setAttributeInner = (node, field, value, ns) => {
+      if (field === "value" && !ns && this.isStaleValue(node, value)) {
+        return;
+      }
+      setAttributeInner(node, field, value, ns);
+    };
+  }
+
+  isStaleValue(node, value) {
+    const id = node.getAttribute && node.getAttribute("data-dioxus-id");
+    if (id === null || id === undefined) {
+      return false;
+    }
+    if (this.composing === node || this.pendingInputs.has(id)) {
+      return true;
+    }
+
+    const sent = this.sentValues.get(id);
+    if (sent === undefined) {
+      return false;
+    }
+
+    // The server caught up to an older value, keep waiting for the newer ones
+    const index = sent.lastIndexOf(value);
+    if (index !== -1 && index < sent.length - 1) {
+      sent.splice(0, index + 1);
+      return true;
+    }
+
+    // Either the server rendered the latest value or it changed the value itself
+    this.sentValues.delete(id);
+    return false;
+  }
 }
 
 main();
@@ -2,20 +2,49 @@ use crate::{
     element::LiveviewElement,
     eval::init_eval,
     events::SerializedHtmlEventConverter,
+    history::{HistoryAction, HistoryChannel, PageLocation},
     query::{QueryEngine, QueryResult},
     LiveViewError,
 };
 use dioxus_core::prelude::*;
 use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
 use dioxus_interpreter_js::MutationState;
-use futures_util::{pin_mut, SinkExt, StreamExt};
-use serde::Serialize;
-use std::{rc::Rc, time::Duration};
+use futures_util::{pin_mut, FutureExt, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio_util::task::LocalPoolHandle;
 
+/// How long to wait for the browser to send the location of the page before the first render
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A session that panics again this soon after it restarted is caught in a loop
+const RESTART_LOOP_WINDOW: Duration = Duration::from_secs(5);
+
+/// What a session does after the app panics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PanicBehavior {
+    /// Close the session. The page stops responding until it is reloaded
+    #[default]
+    CloseSession,
+
+    /// Start the app over on the server and render it into the same page, over the same websocket
+    ///
+    /// The new app gets the state the old one registered for [snapshots](dioxus_core::VirtualDomSnapshot). If it
+    /// panics again right away, it starts fresh instead, and if that panics right away too, the session is closed.
+    RestartSession,
+}
+
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    panic_behavior: PanicBehavior,
 }
 
 impl Default for LiveViewPool {
@@ -31,9 +60,16 @@ impl LiveViewPool {
 
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            panic_behavior: PanicBehavior::default(),
         }
     }
 
+    /// Set what sessions launched with [`LiveViewPool::launch_restartable`] do after the app panics
+    pub fn with_panic_behavior(mut self, behavior: PanicBehavior) -> Self {
+        self.panic_behavior = behavior;
+        self
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -63,6 +99,26 @@ impl LiveViewPool {
             Err(_) => Err(LiveViewError::SendingFailed),
         }
     }
+
+    /// Launch a session that can start the app over with `make_app` if the pool
+    /// [restarts sessions](PanicBehavior::RestartSession) that panic
+    pub async fn launch_restartable(
+        &self,
+        ws: impl LiveViewSocket,
+        make_app: impl Fn() -> VirtualDom + Send + 'static,
+    ) -> Result<(), LiveViewError> {
+        let restart = self.panic_behavior == PanicBehavior::RestartSession;
+        let session = self.pool.spawn_pinned(move || async move {
+            let vdom = make_app();
+            let make_app: &dyn Fn() -> VirtualDom = &make_app;
+            run_with_restart(vdom, restart.then_some(make_app), ws).await
+        });
+        match session.await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(LiveViewError::SendingFailed),
+        }
+    }
 }
 
 /// A LiveViewSocket is a Sink and Stream of Strings that Dioxus uses to communicate with the client
@@ -115,29 +171,124 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Bytes, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "hot-reload", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        dioxus_hot_reload::connect(move |template| {
-            let _ = tx.send(template);
-        });
-        rx
-    };
-
-    let mut mutations = MutationState::default();
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    run_with_restart(vdom, None, ws).await
+}
 
+/// Run a session, and start the app over with `restart` if it panics
+async fn run_with_restart(
+    mut vdom: VirtualDom,
+    restart: Option<&dyn Fn() -> VirtualDom>,
+    ws: impl LiveViewSocket,
+) -> Result<(), LiveViewError> {
     // Create the a proxy for query engine
     let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
     let query_engine = QueryEngine::new(query_tx);
+
+    // pin the futures so we can use select!
+    pin_mut!(ws);
+
+    // The browser sends the location of the page as soon as it connects. Wait for it so deep links render the right
+    // route from the first frame
+    let mut first_frame = None;
+    let mut initial_location = None;
+    if let Ok(frame) = tokio::time::timeout(INITIALIZE_TIMEOUT, ws.next()).await {
+        match frame {
+            Some(Ok(frame)) => match serde_json::from_slice::<IpcMessage>(&frame) {
+                Ok(IpcMessage::Initialize(params)) => initial_location = params.location,
+                _ => first_frame = Some(frame),
+            },
+            Some(Err(_)) => {}
+            None => return Ok(()),
+        }
+    }
+
+    let (history_tx, mut history_rx) = tokio::sync::mpsc::unbounded_channel();
+    let history = HistoryChannel::new(initial_location, history_tx);
+
+    let mut last_restart: Option<Instant> = None;
+    let mut quick_restarts = 0;
+    loop {
+        let session = run_session(
+            &mut vdom,
+            ws.as_mut(),
+            &query_engine,
+            &mut query_rx,
+            &history,
+            &mut history_rx,
+            first_frame.take(),
+        );
+        let panic = match AssertUnwindSafe(session).catch_unwind().await {
+            Ok(result) => return result,
+            Err(panic) => panic,
+        };
+        let Some(restart) = restart else {
+            std::panic::resume_unwind(panic);
+        };
+
+        // Restore the state of the app unless it keeps panicking right after restarts. Give up if it panics right
+        // after starting fresh too
+        match last_restart {
+            Some(last) if last.elapsed() < RESTART_LOOP_WINDOW => quick_restarts += 1,
+            _ => quick_restarts = 0,
+        }
+        if quick_restarts > 1 {
+            tracing::error!("The app panicked right after it restarted. Closing the session");
+            std::panic::resume_unwind(panic);
+        }
+        tracing::error!("The app panicked. Restarting the session");
+        last_restart = Some(Instant::now());
+        vdom = restart_app(vdom, restart, quick_restarts == 0);
+
+        // The page throws away what the old app rendered
+        ws.send(text_frame(
+            &serde_json::to_string(&ClientUpdate::Restart).unwrap(),
+        ))
+        .await?;
+    }
+}
+
+/// Replace an app that panicked with a new one, optionally with the state of the old one
+fn restart_app(
+    old: VirtualDom,
+    restart: &dyn Fn() -> VirtualDom,
+    restore_state: bool,
+) -> VirtualDom {
+    // The old app can be in any state after the panic, so taking the snapshot and dropping it can panic too
+    let snapshot = restore_state
+        .then(|| std::panic::catch_unwind(AssertUnwindSafe(|| old.snapshot())).ok())
+        .flatten();
+    _ = std::panic::catch_unwind(AssertUnwindSafe(move || drop(old)));
+
+    let mut vdom = restart();
+    if let Some(snapshot) = snapshot {
+        vdom.restore(snapshot);
+    }
+    vdom
+}
+
+/// Render the app into the page and handle its events until the socket closes
+async fn run_session(
+    vdom: &mut VirtualDom,
+    mut ws: Pin<&mut impl LiveViewSocket>,
+    query_engine: &QueryEngine,
+    query_rx: &mut UnboundedReceiver<String>,
+    history: &HistoryChannel,
+    history_rx: &mut UnboundedReceiver<HistoryAction>,
+    first_frame: Option<Vec<u8>>,
+) -> Result<(), LiveViewError> {
+    // Changes are patched into the session in place so the websocket and the state of the app survive them
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    let mut hot_reload_rx = crate::hot_reload::subscribe(vdom);
+
+    let mut mutations = MutationState::default();
+
     vdom.in_runtime(|| {
         ScopeId::ROOT.provide_context(query_engine.clone());
+        ScopeId::ROOT.provide_context(history.clone());
         init_eval();
     });
 
-    // pin the futures so we can use select!
-    pin_mut!(ws);
-
     if let Some(edits) = {
         vdom.rebuild(&mut mutations);
         take_edits(&mut mutations)
@@ -146,20 +297,25 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
         ws.send(edits).await?;
     }
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(HtmlEvent),
-        #[serde(rename = "query")]
-        Query(QueryResult),
+    if let Some(frame) = first_frame {
+        if let Some(reply) = handle_frame(vdom, query_engine, history, &frame) {
+            ws.send(reply).await?;
+        }
     }
 
     loop {
         #[cfg(all(feature = "hot-reload", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
+        let hot_reload_wait = async {
+            use tokio::sync::broadcast::error::RecvError;
+            loop {
+                match hot_reload_rx.recv().await {
+                    Ok(msg) => break Some(msg),
+                    // A session that fell behind skips the changes it missed instead of disconnecting
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break None,
+                }
+            }
+        };
         #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
         let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
 
@@ -168,37 +324,10 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
             _ = vdom.wait_for_work() => {}
 
             evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(text_frame("__pong__")).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            Rc::new(PlatformEventData::new(Box::new(element))),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
-                                    } else {
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            evt.data.into_any(),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
-                                    }
-                                }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
-                            }
+                match evt {
+                    Some(Ok(frame)) => {
+                        if let Some(reply) = handle_frame(vdom, query_engine, history, &frame) {
+                            ws.send(reply).await?;
                         }
                     }
                     // log this I guess? when would we get an error here?
@@ -212,17 +341,29 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                 ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
             }
 
+            // send navigations the router made to the browser
+            Some(action) = history_rx.recv() => {
+                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::History(action)).unwrap())).await?;
+            }
+
             Some(msg) = hot_reload_wait => {
                 #[cfg(all(feature = "hot-reload", debug_assertions))]
                 match msg{
                     dioxus_hot_reload::HotReloadMsg::UpdateTemplate(new_template) => {
                         vdom.replace_template(new_template);
                     }
-                    // todo: enable hotreloading in liveview
-                    dioxus_hot_reload::HotReloadMsg::UpdateAsset(_) => {}
-                    dioxus_hot_reload::HotReloadMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
+                    dioxus_hot_reload::HotReloadMsg::UpdateLiterals(literals) => {
+                        vdom.update_hot_literals(literals);
+                    }
+                    dioxus_hot_reload::HotReloadMsg::ReportError(error) => {
+                        vdom.set_hot_reload_error(error);
+                    }
+                    dioxus_hot_reload::HotReloadMsg::UpdateAsset { path, contents } => {
+                        let script = dioxus_hot_reload::update_asset_script(&path, contents.as_deref());
+                        ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(script)).unwrap())).await?;
+                    }
+                    // The server handles saving state and shutting down once for every session
+                    dioxus_hot_reload::HotReloadMsg::SaveState | dioxus_hot_reload::HotReloadMsg::Shutdown => {}
                 }
                 #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
                 let () = msg;
@@ -244,6 +385,64 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
     }
 }
 
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum IpcMessage {
+    #[serde(rename = "initialize")]
+    Initialize(InitializeParams),
+    #[serde(rename = "user_event")]
+    Event(HtmlEvent),
+    #[serde(rename = "query")]
+    Query(QueryResult),
+    /// The user went back or forward in the browser
+    #[serde(rename = "history")]
+    History(PageLocation),
+}
+
+#[derive(Deserialize, Debug)]
+struct InitializeParams {
+    #[serde(default)]
+    location: Option<PageLocation>,
+}
+
+/// Handle a frame from the client, returning the reply to send back if there is one
+fn handle_frame(
+    vdom: &mut VirtualDom,
+    query_engine: &QueryEngine,
+    history: &HistoryChannel,
+    frame: &[u8],
+) -> Option<Vec<u8>> {
+    // respond with a pong every ping to keep the websocket alive
+    if frame == b"__ping__" {
+        return Some(text_frame("__pong__"));
+    }
+
+    match serde_json::from_slice::<IpcMessage>(frame).ok()? {
+        IpcMessage::Event(evt) => {
+            // Intercept the mounted event and insert a custom element type
+            if let EventData::Mounted = &evt.data {
+                let element = LiveviewElement::new(evt.element, query_engine.clone());
+                vdom.handle_event(
+                    &evt.name,
+                    Rc::new(PlatformEventData::new(Box::new(element))),
+                    evt.element,
+                    evt.bubbles,
+                );
+            } else {
+                vdom.handle_event(&evt.name, evt.data.into_any(), evt.element, evt.bubbles);
+            }
+        }
+        IpcMessage::Query(result) => query_engine.send(result),
+        IpcMessage::History(location) => history.pop(location),
+        // The session already started
+        IpcMessage::Initialize(_) => {}
+    }
+
+    None
+}
+
 fn text_frame(text: &str) -> Vec<u8> {
     let mut bytes = vec![0];
     bytes.extend(text.as_bytes());
@@ -262,4 +461,9 @@ fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
 enum ClientUpdate {
     #[serde(rename = "query")]
     Query(String),
+    #[serde(rename = "history")]
+    History(HistoryAction),
+    /// The app was started over after it panicked
+    #[serde(rename = "restart")]
+    Restart,
 }
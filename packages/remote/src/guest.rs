@@ -0,0 +1,129 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_lib::prelude::*;
+use futures_channel::{mpsc, oneshot};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+/// The name of the function a remote bundle exports for the host to mount it
+pub const MOUNT_EXPORT: &str = "dioxus_remote_mount";
+
+type UpdateProps = Box<dyn Fn(&str) -> Result<(), serde_json::Error>>;
+
+/// A remote component that is mounted into an element of a host app
+///
+/// The host gets the handle from the `dioxus_remote_mount` export of the bundle and uses it to send new props and to
+/// unmount the component.
+#[wasm_bindgen]
+pub struct RemoteHandle {
+    update: UpdateProps,
+    stop: Option<oneshot::Sender<()>>,
+    root: web_sys::Element,
+}
+
+#[wasm_bindgen]
+impl RemoteHandle {
+    /// Render the component with new props, serialized as JSON
+    pub fn update(&self, props: &str) -> Result<(), JsError> {
+        (self.update)(props).map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    /// Stop the app of the component and remove what it rendered
+    pub fn unmount(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            _ = stop.send(());
+        }
+        self.root.set_inner_html("");
+        _ = self.root.remove_attribute(dioxus_web::APP_ROOT_ATTRIBUTE);
+    }
+}
+
+/// Mount a component into an element of the host with props serialized as JSON
+///
+/// Remote bundles call this from the function they export for the host as `dioxus_remote_mount`. The component runs
+/// in its own app inside of the element, so it doesn't share context or signals with the host. Everything it gets
+/// from the host goes through its props.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// #[wasm_bindgen]
+/// pub fn dioxus_remote_mount(root: web_sys::Element, props: &str) -> Result<RemoteHandle, JsError> {
+///     mount_remote(Counter, root, props)
+/// }
+/// ```
+pub fn mount_remote<P: DeserializeOwned + Clone + 'static, M: 'static>(
+    component: impl ComponentFunction<P, M>,
+    root: web_sys::Element,
+    props: &str,
+) -> Result<RemoteHandle, JsError> {
+    let props: P = serde_json::from_str(props)?;
+    let (updates, receiver) = mpsc::unbounded();
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    // The host ignores events from inside of the root. This app handles them
+    root.set_attribute(dioxus_web::APP_ROOT_ATTRIBUTE, "")
+        .map_err(|_| JsError::new("Failed to mark the root of the remote component"))?;
+
+    let vdom = VirtualDom::new_with_props(
+        RemoteRoot,
+        RemoteRootProps {
+            component,
+            props,
+            updates: Rc::new(RefCell::new(Some(receiver))),
+            _marker: std::marker::PhantomData,
+        },
+    );
+    let config = dioxus_web::Config::new().rootelement(root.clone());
+    wasm_bindgen_futures::spawn_local(async move {
+        let app = std::pin::pin!(dioxus_web::run(vdom, config));
+        futures_util::future::select(app, stopped).await;
+    });
+
+    Ok(RemoteHandle {
+        update: Box::new(move |props| {
+            let props: P = serde_json::from_str(props)?;
+            _ = updates.unbounded_send(props);
+            Ok(())
+        }),
+        stop: Some(stop),
+        root,
+    })
+}
+
+struct RemoteRootProps<F, P, M> {
+    component: F,
+    props: P,
+    updates: Rc<RefCell<Option<mpsc::UnboundedReceiver<P>>>>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<F: Clone, P: Clone, M> Clone for RemoteRootProps<F, P, M> {
+    fn clone(&self) -> Self {
+        Self {
+            component: self.component.clone(),
+            props: self.props.clone(),
+            updates: self.updates.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Render the remote component with the latest props from the host
+fn RemoteRoot<F: ComponentFunction<P, M>, P: Clone + 'static, M: 'static>(
+    root: RemoteRootProps<F, P, M>,
+) -> Element {
+    let mut props = use_signal(|| root.props.clone());
+    use_hook(|| {
+        if let Some(mut updates) = root.updates.borrow_mut().take() {
+            spawn(async move {
+                while let Some(new_props) = updates.next().await {
+                    props.set(new_props);
+                }
+            });
+        }
+    });
+
+    root.component.rebuild(props())
+}
@@ -0,0 +1,201 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_lib::prelude::*;
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen(inline_js = r#"
+export function load_remote(src) {
+  return (async () => {
+    const manifest_url = new URL(src, document.baseURI).href;
+    const response = await fetch(manifest_url);
+    if (!response.ok) {
+      throw new Error(`Failed to fetch the manifest of the remote component at ${manifest_url}: ${response.status}`);
+    }
+    const manifest = await response.json();
+    const module = await import(new URL(manifest.module, manifest_url).href);
+    // Initializing the module again returns the instance it already has
+    await module.default(manifest.wasm ? new URL(manifest.wasm, manifest_url).href : undefined);
+    if (typeof module.dioxus_remote_mount !== "function") {
+      throw new Error(`The remote component at ${manifest_url} doesn't export dioxus_remote_mount`);
+    }
+    return module;
+  })();
+}
+
+export function mount_remote_module(module, root, props) {
+  return module.dioxus_remote_mount(root, props);
+}
+
+export function update_remote(handle, props) {
+  handle.update(props);
+}
+
+export function unmount_remote(handle) {
+  handle.unmount();
+  handle.free();
+}
+"#)]
+extern "C" {
+    fn load_remote(src: &str) -> js_sys::Promise;
+
+    #[wasm_bindgen(catch)]
+    fn mount_remote_module(
+        module: &JsValue,
+        root: &web_sys::Element,
+        props: &str,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn update_remote(handle: &JsValue, props: &str) -> Result<(), JsValue>;
+
+    fn unmount_remote(handle: &JsValue);
+}
+
+/// The props of a remote component, serialized as JSON
+///
+/// The remote component deserializes them into its own props type, so both sides should share the definition of the
+/// props, for example in a crate that both depend on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoteProps(String);
+
+impl RemoteProps {
+    /// Serialize the props of a remote component
+    ///
+    /// # Panics
+    ///
+    /// Panics if the props fail to serialize to JSON, like maps with keys that aren't strings.
+    pub fn new(props: &impl Serialize) -> Self {
+        Self(serde_json::to_string(props).expect("the props of a remote component to serialize"))
+    }
+
+    /// The props as JSON
+    pub fn json(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for RemoteProps {
+    fn default() -> Self {
+        Self("null".to_string())
+    }
+}
+
+/// An error that occurred while loading a remote component
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoteError {
+    message: String,
+}
+
+impl RemoteError {
+    fn from_js(value: JsValue) -> Self {
+        let message = match value.dyn_ref::<js_sys::Error>() {
+            Some(error) => error.message().into(),
+            None => format!("{value:?}"),
+        };
+        Self { message }
+    }
+}
+
+impl std::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to load the remote component: {}", self.message)
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+/// Load a separately compiled component at runtime and mount it here
+///
+/// `src` is the url of the manifest of the bundle, a JSON file with the path of the JS of the bundle, and optionally
+/// the path of its wasm, relative to the manifest:
+///
+/// ```json
+/// { "module": "./counter.js", "wasm": "./counter_bg.wasm" }
+/// ```
+///
+/// The bundle exports `dioxus_remote_mount`, which calls [`mount_remote`](crate::mount_remote) with the component.
+/// The component runs as its own app inside of the element of the remote component, and gets its props as JSON.
+/// When the props change, the remote component renders again with the new props.
+///
+/// If the bundle fails to load, the error is thrown to the closest error boundary.
+///
+/// This is experimental. The bundle and the host don't share anything but the props, so context, signals and
+/// events don't cross between them.
+#[component]
+pub fn RemoteComponent(
+    /// The url of the manifest of the bundle
+    #[props(into)]
+    src: String,
+
+    /// The props sent to the remote component
+    #[props(default)]
+    props: RemoteProps,
+
+    /// Rendered while the bundle is loading
+    #[props(default)]
+    fallback: Element,
+) -> Element {
+    let module = use_resource(use_reactive!(|src| async move {
+        JsFuture::from(load_remote(&src))
+            .await
+            .map_err(RemoteError::from_js)
+    }));
+    let mut root = use_signal(|| None::<web_sys::Element>);
+    let handle = use_hook(|| Rc::new(RefCell::new(None::<JsValue>)));
+    let mut latest_props = use_hook(|| CopyValue::new(props.clone()));
+    latest_props.set(props.clone());
+
+    // Mount the component once the bundle is loaded and the element exists, and again if the bundle changes
+    use_effect({
+        let handle = handle.clone();
+        move || {
+            let module = module.read();
+            let (Some(Ok(module)), Some(root)) = (&*module, &*root.read()) else {
+                return;
+            };
+            if let Some(old) = handle.borrow_mut().take() {
+                unmount_remote(&old);
+            }
+            match mount_remote_module(module, root, latest_props.read().json()) {
+                Ok(mounted) => *handle.borrow_mut() = Some(mounted),
+                Err(err) => tracing::error!("{}", RemoteError::from_js(err)),
+            }
+        }
+    });
+
+    // Send new props to the mounted component
+    use_effect({
+        let handle = handle.clone();
+        use_reactive!(|props| {
+            if let Some(mounted) = &*handle.borrow() {
+                if let Err(err) = update_remote(mounted, props.json()) {
+                    tracing::error!("{}", RemoteError::from_js(err));
+                }
+            }
+        })
+    });
+
+    use_drop(move || {
+        if let Some(mounted) = handle.borrow_mut().take() {
+            unmount_remote(&mounted);
+        }
+    });
+
+    let loaded = match &*module.read() {
+        Some(Ok(_)) => true,
+        Some(Err(err)) => return Err::<VNode, _>(err.clone()).throw(),
+        None => false,
+    };
+
+    rsx! {
+        if !loaded {
+            {fallback}
+        }
+        div {
+            class: "remote-component",
+            onmounted: move |event| root.set(event.downcast::<web_sys::Element>().cloned()),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+// cannot use forbid, because props derive macro generates #[allow(missing_docs)]
+#![deny(missing_docs)]
+#![allow(non_snake_case)]
+
+mod guest;
+pub use guest::*;
+
+mod host;
+pub use host::*;
+
+/// A collection of useful items most applications might need.
+pub mod prelude {
+    pub use crate::guest::*;
+    pub use crate::host::*;
+}
@@ -0,0 +1,22 @@
+use dioxus_remote::RemoteProps;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct CounterProps {
+    start: i32,
+    label: String,
+}
+
+#[test]
+fn props_serialize_to_json() {
+    let props = RemoteProps::new(&CounterProps {
+        start: 5,
+        label: "Count".to_string(),
+    });
+    assert_eq!(props.json(), r#"{"start":5,"label":"Count"}"#);
+}
+
+#[test]
+fn default_props_are_null() {
+    assert_eq!(RemoteProps::default().json(), "null");
+}
@@ -0,0 +1,425 @@
+//! Generate a routes enum from the files in a directory
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse::Parse, FnArg, Ident, Item, LitStr, Pat, Type};
+
+/// The arguments of the `file_routes!` macro
+pub struct FileRoutes {
+    dir: String,
+    span: Span,
+}
+
+impl Parse for FileRoutes {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self {
+                dir: "src/routes".to_string(),
+                span: Span::call_site(),
+            });
+        }
+
+        let dir: LitStr = input.parse()?;
+        Ok(Self {
+            dir: dir.value(),
+            span: dir.span(),
+        })
+    }
+}
+
+/// A directory of routes
+struct RouteDir {
+    /// The name of the module the directory is included as
+    module: Ident,
+    layout: Option<PathBuf>,
+    pages: Vec<RouteFile>,
+    children: Vec<RouteDir>,
+}
+
+/// A single route file
+struct RouteFile {
+    module: Ident,
+    path: PathBuf,
+    /// The segments of the url for this file, including the segments of its parent directories
+    segments: Vec<Segment>,
+    fields: Vec<(Ident, Type)>,
+    /// If the file has a `loader`, the page is rendered once the data it loads is ready
+    has_loader: bool,
+}
+
+#[derive(Clone)]
+enum Segment {
+    Static(String),
+    Dynamic(String),
+    CatchAll(String),
+}
+
+impl Segment {
+    fn parse(name: &str) -> Self {
+        if let Some(name) = name.strip_prefix("[...").and_then(|n| n.strip_suffix(']')) {
+            Segment::CatchAll(name.to_string())
+        } else if let Some(name) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+            Segment::Dynamic(name.to_string())
+        } else {
+            Segment::Static(name.to_string())
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Segment::Static(name) | Segment::Dynamic(name) | Segment::CatchAll(name) => name,
+        }
+    }
+
+    fn route(&self) -> String {
+        match self {
+            Segment::Static(name) => format!("/{name}"),
+            Segment::Dynamic(name) => format!("/:{name}"),
+            Segment::CatchAll(name) => format!("/:..{name}"),
+        }
+    }
+}
+
+impl FileRoutes {
+    pub fn expand(&self) -> syn::Result<TokenStream2> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| syn::Error::new(self.span, "CARGO_MANIFEST_DIR is not set"))?;
+        let root = Path::new(&manifest_dir).join(&self.dir);
+        if !root.is_dir() {
+            return Err(syn::Error::new(
+                self.span,
+                format!("The routes directory `{}` does not exist", root.display()),
+            ));
+        }
+
+        let module_name = root
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(module_ident)
+            .unwrap_or_else(|| format_ident!("routes"));
+        let dir = self.read_dir(&root, module_name, Vec::new())?;
+
+        let modules = dir.modules();
+        let mut variants = Vec::new();
+        let mut loaders = Vec::new();
+        let mut names = Vec::new();
+        // Layout attributes are attached to the next variant. Any that are left at the end don't wrap any routes.
+        let mut attributes = Vec::new();
+        self.variants(
+            &dir,
+            &[],
+            &mut attributes,
+            &mut variants,
+            &mut loaders,
+            &mut names,
+        )?;
+
+        Ok(quote! {
+            #modules
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            mod __file_route_loaders {
+                use super::*;
+
+                #(#loaders)*
+            }
+
+            /// The routes generated from the route files
+            #[derive(Clone, Debug, PartialEq, dioxus_router::prelude::Routable)]
+            #[rustfmt::skip]
+            pub enum Route {
+                #(#variants)*
+            }
+        })
+    }
+
+    fn read_dir(&self, dir: &Path, module: Ident, segments: Vec<Segment>) -> syn::Result<RouteDir> {
+        let mut entries = std::fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .map_err(|err| {
+                syn::Error::new(
+                    self.span,
+                    format!("Failed to read `{}`: {err}", dir.display()),
+                )
+            })?;
+        entries.sort();
+
+        let mut route_dir = RouteDir {
+            module,
+            layout: None,
+            pages: Vec::new(),
+            children: Vec::new(),
+        };
+
+        for entry in entries {
+            let Some(name) = entry.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            if entry.is_dir() {
+                let mut segments = segments.clone();
+                segments.push(Segment::parse(name));
+                let module = module_ident(name);
+                route_dir
+                    .children
+                    .push(self.read_dir(&entry, module, segments)?);
+                continue;
+            }
+
+            let Some(stem) = name.strip_suffix(".rs") else {
+                continue;
+            };
+            if stem == "_layout" {
+                route_dir.layout = Some(entry);
+                continue;
+            }
+
+            let mut segments = segments.clone();
+            if stem != "index" {
+                segments.push(Segment::parse(stem));
+            }
+            route_dir
+                .pages
+                .push(self.read_file(entry.clone(), module_ident(stem), segments)?);
+        }
+
+        // Index routes come first so they read top to bottom like the directory
+        route_dir.pages.sort_by_key(|page| page.module != "index");
+
+        Ok(route_dir)
+    }
+
+    fn read_file(
+        &self,
+        path: PathBuf,
+        module: Ident,
+        segments: Vec<Segment>,
+    ) -> syn::Result<RouteFile> {
+        let error =
+            |message: String| syn::Error::new(self.span, format!("{}: {message}", path.display()));
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| error(format!("Failed to read the route file: {err}")))?;
+        let file = syn::parse_file(&contents)
+            .map_err(|err| error(format!("Failed to parse the route file: {err}")))?;
+
+        let function = |name: &str| {
+            file.items.iter().find_map(|item| match item {
+                Item::Fn(function) if function.sig.ident == name => Some(function),
+                _ => None,
+            })
+        };
+
+        let page = function("Page")
+            .ok_or_else(|| error("Route files must define a `Page` component".to_string()))?;
+        let has_loader = function("loader").is_some();
+
+        let mut fields = Vec::new();
+        let mut has_data = false;
+        for arg in &page.sig.inputs {
+            let FnArg::Typed(arg) = arg else {
+                return Err(error("`Page` can't take `self`".to_string()));
+            };
+            let Pat::Ident(name) = &*arg.pat else {
+                return Err(error(
+                    "The arguments of `Page` must be the parameters of the route".to_string(),
+                ));
+            };
+            let name = name.ident.clone();
+            if name == "data" && has_loader {
+                has_data = true;
+                continue;
+            }
+            if !segments
+                .iter()
+                .any(|segment| !matches!(segment, Segment::Static(_)) && name == segment.name())
+            {
+                return Err(error(format!(
+                    "`Page` takes `{name}`, but the route doesn't have a `[{name}]` segment"
+                )));
+            }
+            fields.push((name, (*arg.ty).clone()));
+        }
+
+        for segment in &segments {
+            if !matches!(segment, Segment::Static(_))
+                && !fields.iter().any(|(name, _)| name == segment.name())
+            {
+                return Err(error(format!(
+                    "The route has a `{}` segment, but `Page` doesn't take it as an argument",
+                    segment.name()
+                )));
+            }
+        }
+
+        if has_loader && !has_data {
+            return Err(error(
+                "Route files with a `loader` must have a `data` argument on `Page`".to_string(),
+            ));
+        }
+
+        Ok(RouteFile {
+            module,
+            path,
+            segments,
+            fields,
+            has_loader,
+        })
+    }
+
+    fn variants(
+        &self,
+        dir: &RouteDir,
+        modules: &[Ident],
+        attributes: &mut Vec<TokenStream2>,
+        variants: &mut Vec<TokenStream2>,
+        loaders: &mut Vec<TokenStream2>,
+        names: &mut Vec<String>,
+    ) -> syn::Result<()> {
+        let modules = [modules, std::slice::from_ref(&dir.module)].concat();
+
+        if dir.layout.is_some() {
+            attributes.push(quote! { #[layout(#(#modules::)* _layout::Layout)] });
+        }
+
+        for page in &dir.pages {
+            let name = variant_name(&page.segments);
+            if names.contains(&name) {
+                return Err(syn::Error::new(
+                    self.span,
+                    format!(
+                        "{}: Another route file is also named `{name}`",
+                        page.path.display()
+                    ),
+                ));
+            }
+            names.push(name.clone());
+
+            let variant = Ident::new(&name, Span::call_site());
+            let module = &page.module;
+            let route = match page.segments.is_empty() {
+                true => "/".to_string(),
+                false => page.segments.iter().map(Segment::route).collect(),
+            };
+            let (field_names, field_types): (Vec<_>, Vec<_>) = page.fields.iter().cloned().unzip();
+
+            // Pages with a loader render through a component that waits for the data first
+            let component = if page.has_loader {
+                loaders.push(quote! {
+                    #[component]
+                    pub fn #variant(#(#field_names: #field_types),*) -> Element {
+                        let data = use_resource(use_reactive(
+                            (#(&#field_names,)*),
+                            |(#(#field_names,)*)| #(#modules::)* #module::loader(#(#field_names),*),
+                        ));
+                        let data = data.cloned()?;
+
+                        rsx! {
+                            #(#modules::)* #module::Page { #(#field_names: #field_names.clone(),)* data }
+                        }
+                    }
+                });
+                quote! { __file_route_loaders::#variant }
+            } else {
+                quote! { #(#modules::)* #module::Page }
+            };
+
+            let attributes = std::mem::take(attributes);
+            variants.push(quote! {
+                #(#attributes)*
+                #[route(#route, #component)]
+                #variant { #(#field_names: #field_types,)* },
+            });
+        }
+
+        for child in &dir.children {
+            self.variants(child, &modules, attributes, variants, loaders, names)?;
+        }
+
+        if dir.layout.is_some() {
+            attributes.push(quote! { #[end_layout] });
+        }
+
+        Ok(())
+    }
+}
+
+impl RouteDir {
+    /// Include the route files as modules
+    ///
+    /// The macro reads the signatures of the route files, so each one is also included as bytes to make cargo expand
+    /// the macro again when it changes.
+    fn modules(&self) -> TokenStream2 {
+        let module = &self.module;
+        let layout = self.layout.as_ref().map(|path| {
+            let path = path.to_string_lossy();
+            quote! {
+                #[path = #path]
+                pub mod _layout;
+                const _: &[u8] = include_bytes!(#path);
+            }
+        });
+        let pages = self.pages.iter().map(|page| {
+            let module = &page.module;
+            let path = page.path.to_string_lossy();
+            quote! {
+                #[path = #path]
+                pub mod #module;
+                const _: &[u8] = include_bytes!(#path);
+            }
+        });
+        let children = self.children.iter().map(RouteDir::modules);
+
+        quote! {
+            pub mod #module {
+                #layout
+                #(#pages)*
+                #(#children)*
+            }
+        }
+    }
+}
+
+/// Turn a file or directory name into the name of a module
+fn module_ident(name: &str) -> Ident {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let name = name.trim_matches('_');
+    let name = if name.is_empty() { "route" } else { name };
+    syn::parse_str(name).unwrap_or_else(|_| Ident::new_raw(name, Span::call_site()))
+}
+
+/// The name of the variant for a route: `blog/[id].rs` becomes `BlogId`
+fn variant_name(segments: &[Segment]) -> String {
+    if segments.is_empty() {
+        return "Index".to_string();
+    }
+
+    let name: String = segments
+        .iter()
+        .flat_map(|segment| segment.name().split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // Identifiers can't start with a number, so `404.rs` becomes `Route404`
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("Route{name}"),
+        false => name,
+    }
+}
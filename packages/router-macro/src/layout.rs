@@ -3,6 +3,7 @@ use quote::quote;
 use syn::Path;
 
 use crate::nest::{Nest, NestId};
+use crate::route::with_error_page;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LayoutId(pub usize);
@@ -11,6 +12,8 @@ pub struct LayoutId(pub usize);
 pub struct Layout {
     pub comp: Path,
     pub active_nests: Vec<NestId>,
+    /// The error page that was active when the layout was declared
+    pub error_page: Option<Path>,
 }
 
 impl Layout {
@@ -21,11 +24,13 @@ impl Layout {
             .iter()
             .flat_map(|id| nests[id.0].dynamic_segments());
 
-        quote! {
+        let render = quote! {
             rsx! {
                 #comp_name { #(#dynamic_segments: #dynamic_segments,)* }
             }
-        }
+        };
+
+        with_error_page(&self.error_page, render)
     }
 }
 
@@ -35,6 +40,10 @@ impl Layout {
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
 
-        Ok(Self { comp, active_nests })
+        Ok(Self {
+            comp,
+            active_nests,
+            error_page: None,
+        })
     }
 }
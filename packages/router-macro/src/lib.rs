@@ -16,6 +16,7 @@ use proc_macro2::TokenStream as TokenStream2;
 
 use crate::{layout::LayoutId, route_tree::RouteTree};
 
+mod file_routes;
 mod hash;
 mod layout;
 mod nest;
@@ -238,10 +239,59 @@ mod segment;
 /// # #[component]
 /// # fn Home() -> Element { None }
 /// ```
+///
+/// # `#[error_page(component)]`
+///
+/// The `#[error_page]` attribute renders a component in place of the routes and layouts after it when they throw an
+/// error. It takes 1 parameter:
+/// - `component`: A function that takes the [`RouteError`](dioxus_router::prelude::RouteError) and renders the error
+///
+/// Error pages declared inside a layout render inside of the layout. Server rendered error pages are sent with the
+/// status code of the error.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[layout(BlogFrame)]
+///         #[error_page(BlogError)]
+///             // If BlogPost throws an error, BlogError is rendered in the Outlet of the BlogFrame component
+///             #[route("/:id")]
+///             BlogPost { id: usize },
+///         #[end_error_page]
+///         // Errors from Drafts are not caught by BlogError
+///         #[route("/drafts")]
+///         Drafts {},
+/// }
+/// fn BlogError(error: RouteError) -> Element {
+///     rsx! { "Failed to load the post: {error}" }
+/// }
+/// # #[component]
+/// # fn BlogPost(id: usize) -> Element { None }
+/// # #[component]
+/// # fn Drafts() -> Element { None }
+/// # #[component]
+/// # fn BlogFrame() -> Element { None }
+/// ```
+///
+/// # `#[end_error_page]`
+///
+/// The `#[end_error_page]` attribute is used to end an error page. It takes no parameters.
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route,
+        nest,
+        end_nest,
+        layout,
+        end_layout,
+        error_page,
+        end_error_page,
+        redirect,
+        child
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -271,6 +321,56 @@ pub fn routable(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Generate a `Route` enum from the route files in a directory
+///
+/// The directory defaults to `src/routes` and is relative to the crate root. Each `.rs` file in it is a route:
+///
+/// - `index.rs` is the route of the directory it is in, so `src/routes/index.rs` is `/`
+/// - `about.rs` is `/about` and `blog/index.rs` is `/blog`
+/// - `blog/[id].rs` is `/blog/:id`, a dynamic segment
+/// - `[...segments].rs` is `/:..segments`, a catch all segment
+/// - `_layout.rs` defines a `Layout` component that wraps every route in its directory and renders them in an `Outlet`
+///
+/// Every route file must define a `Page` component. The arguments of `Page` are the dynamic segments of the route.
+/// A route file can also define a `loader` that takes the same arguments and returns a future. The page is rendered
+/// with the loaded value as its `data` argument once the future finishes.
+///
+/// ```rust, ignore
+/// // src/routes/blog/[id].rs
+/// use dioxus::prelude::*;
+///
+/// pub async fn loader(id: usize) -> String {
+///     format!("Post {id}")
+/// }
+///
+/// #[component]
+/// pub fn Page(id: usize, data: String) -> Element {
+///     rsx! { h1 { "{data}" } }
+/// }
+///
+/// // src/main.rs
+/// use dioxus::prelude::*;
+///
+/// file_routes!();
+///
+/// fn main() {
+///     launch(|| rsx! { Router::<Route> {} });
+/// }
+/// ```
+///
+/// The route files are included as modules of a module named after the directory. Edits to existing route files are
+/// tracked, but the macro can't tell when files are added, removed or renamed. Touch the file that calls it after
+/// changing which route files exist so it runs again.
+#[proc_macro]
+pub fn file_routes(input: TokenStream) -> TokenStream {
+    let routes = parse_macro_input!(input as file_routes::FileRoutes);
+
+    match routes.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 struct RouteEnum {
     name: Ident,
     redirects: Vec<Redirect>,
@@ -297,6 +397,8 @@ impl RouteEnum {
         let mut nests = Vec::new();
         let mut nest_stack = Vec::new();
 
+        let mut error_page_stack: Vec<syn::Path> = Vec::new();
+
         for variant in &data.variants {
             let mut excluded = Vec::new();
             // Apply the any nesting attributes in order
@@ -381,7 +483,8 @@ impl RouteEnum {
                         let exclude = bang.is_some();
                         Ok((exclude, Layout::parse(input, nest_stack.clone())?))
                     };
-                    let (exclude, layout): (bool, Layout) = attr.parse_args_with(parser)?;
+                    let (exclude, mut layout): (bool, Layout) = attr.parse_args_with(parser)?;
+                    layout.error_page = error_page_stack.last().cloned();
 
                     if exclude {
                         let Some(layout_index) = layouts.iter().position(|l| l.comp == layout.comp)
@@ -399,6 +502,10 @@ impl RouteEnum {
                     }
                 } else if attr.path().is_ident("end_layout") {
                     layout_stack.pop();
+                } else if attr.path().is_ident("error_page") {
+                    error_page_stack.push(attr.parse_args()?);
+                } else if attr.path().is_ident("end_error_page") {
+                    error_page_stack.pop();
                 } else if attr.path().is_ident("redirect") {
                     let parser = |input: ParseStream| {
                         Redirect::parse(input, nest_stack.clone(), redirects.len())
@@ -412,7 +519,12 @@ impl RouteEnum {
             let mut active_layouts = layout_stack.clone();
             active_layouts.retain(|&id| !excluded.contains(&id));
 
-            let route = Route::parse(active_nests, active_layouts, variant.clone())?;
+            let route = Route::parse(
+                active_nests,
+                active_layouts,
+                error_page_stack.last().cloned(),
+                variant.clone(),
+            )?;
 
             // add the route to the site map
             let mut segment = SiteMapSegment::new(&route.segments);
@@ -60,6 +60,7 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub error_page: Option<Path>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -67,6 +68,7 @@ impl Route {
     pub fn parse(
         nests: Vec<NestId>,
         layouts: Vec<LayoutId>,
+        error_page: Option<Path>,
         variant: syn::Variant,
     ) -> syn::Result<Self> {
         let route_attr = variant
@@ -163,6 +165,7 @@ impl Route {
             hash,
             nests,
             layouts,
+            error_page,
             fields,
         })
     }
@@ -249,14 +252,20 @@ impl Route {
             RouteType::Leaf { component } => {
                 let dynamic_segments = self.dynamic_segments();
                 let dynamic_segments_from_route = self.dynamic_segments();
-                quote! {
-                    #[allow(unused)]
-                    (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
+                let render = with_error_page(
+                    &self.error_page,
+                    quote! {
                         rsx! {
                             #component {
                                 #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
                             }
                         }
+                    },
+                );
+                quote! {
+                    #[allow(unused)]
+                    (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
+                        #render
                     }
                 }
             }
@@ -361,3 +370,13 @@ pub(crate) enum RouteType {
     Child(Field),
     Leaf { component: Path },
 }
+
+/// Render the error page instead of the route or layout if it throws an error
+pub(crate) fn with_error_page(error_page: &Option<Path>, render: TokenStream2) -> TokenStream2 {
+    match error_page {
+        Some(error_page) => quote! {
+            dioxus_router::prelude::route_error_boundary(#error_page, #render)
+        },
+        None => render,
+    }
+}
@@ -3,8 +3,9 @@ use dioxus_lib::prelude::*;
 use std::{cell::RefCell, rc::Rc, str::FromStr};
 
 use crate::{
-    prelude::{provide_router_context, Outlet},
+    prelude::{provide_router_context, route_error_boundary, Outlet, RouteError},
     routable::Routable,
+    route_error::set_response_status,
     router_cfg::RouterConfig,
 };
 
@@ -81,21 +82,38 @@ where
 {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
-    use_hook(|| {
-        provide_router_context(RouterContext::new(
-            (props
-                .config
-                .config
-                .take()
-                .expect("use_context_provider ran twice"))(),
-            schedule_update_any(),
-        ));
+    let (error_page, not_found) = use_hook(|| {
+        let config = (props
+            .config
+            .config
+            .take()
+            .expect("use_context_provider ran twice"))();
+        let error_page = config.error_page;
+        let not_found = config.request_route_not_found();
+        provide_router_context(RouterContext::new(config, schedule_update_any()));
 
         provide_context(OutletContext::<R> {
             current_level: 0,
             _marker: std::marker::PhantomData,
         });
+
+        (error_page, not_found)
     });
 
-    rsx! { Outlet::<R> {} }
+    let routes = match not_found {
+        true => rsx! { RouteNotFound {} },
+        false => rsx! { Outlet::<R> {} },
+    };
+
+    match error_page {
+        Some(error_page) => route_error_boundary(error_page, routes),
+        None => routes,
+    }
+}
+
+/// Throw [`RouteError::NotFound`] in place of the routes when the url doesn't match any of them
+fn RouteNotFound() -> Element {
+    // Routers without an error page let the error bubble up, but the response is still a 404
+    set_response_status(&RouteError::NotFound);
+    Err(RouteError::NotFound).throw()
 }
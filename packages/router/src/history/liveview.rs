@@ -1,16 +1,20 @@
 use super::HistoryProvider;
 use crate::routable::Routable;
 use dioxus_lib::prelude::*;
+use dioxus_liveview::{HistoryAction, HistoryChannel};
 use serde::{Deserialize, Serialize};
 use std::sync::{Mutex, RwLock};
-use std::{collections::BTreeMap, rc::Rc, str::FromStr, sync::Arc};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
-/// A [`HistoryProvider`] that evaluates history through JS.
+/// A [`HistoryProvider`] that keeps the url bar of the browser in sync with a router running in a liveview session.
+///
+/// Navigations the router makes are sent to the browser over the liveview websocket, the back and forward buttons of
+/// the browser navigate the router, and the session starts at the route of the page the user opened.
 pub struct LiveviewHistory<R: Routable>
 where
     <R as FromStr>::Err: std::fmt::Display,
 {
-    action_tx: tokio::sync::mpsc::UnboundedSender<Action<R>>,
+    channel: HistoryChannel,
     timeline: Arc<Mutex<Timeline<R>>>,
     updater_callback: Arc<RwLock<Arc<dyn Fn() + Send + Sync>>>,
 }
@@ -38,19 +42,6 @@ where
     last_visited: usize,
 }
 
-#[derive(Serialize, Deserialize)]
-struct SessionStorage {
-    liveview: Option<String>,
-}
-
-enum Action<R: Routable> {
-    GoBack,
-    GoForward,
-    Push(R),
-    Replace(R),
-    External(String),
-}
-
 impl<R: Routable> Timeline<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -159,159 +150,65 @@ where
         )
     }
 
-    /// Create a [`LiveviewHistory`] in the given scope, starting at `initial_path`.
+    /// Create a [`LiveviewHistory`] in the given scope, starting at `initial_path` if the page the session started on
+    /// isn't a route of the app.
     /// When using a [`LiveviewHistory`] in combination with use_eval, history must be untampered with.
     ///
     /// # Panics
     ///
     /// Panics if the function is not called in a dioxus runtime with a Liveview context.
     pub fn new_with_initial_path(initial_path: R) -> Self {
-        let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel::<Action<R>>();
+        let channel = consume_context::<HistoryChannel>();
+        let mut timeline = Timeline::new(initial_path.clone());
+
+        // Start at the deep link the user opened and pick up the history of the tab from before a reload
+        let location = channel.initial_location().cloned();
+        let (route, state, session, depth) = match location {
+            Some(location) => {
+                let route = location.route.parse::<R>().unwrap_or_else(|err| {
+                    tracing::warn!(
+                        "Failed to parse the route of the page {}: {}",
+                        location.route,
+                        err
+                    );
+                    initial_path
+                });
+                let state = location
+                    .state
+                    .and_then(|state| serde_json::from_value(state).ok());
+                let session = location
+                    .session
+                    .and_then(|session| serde_json::from_value(session).ok());
+                (route, state, session, location.depth.max(1))
+            }
+            None => (initial_path, None, None, 1),
+        };
+        let state = timeline.init(route.clone(), state, session, depth);
+        channel.navigate(replace_action(&route, &state, &timeline));
 
-        let timeline = Arc::new(Mutex::new(Timeline::new(initial_path)));
+        let timeline = Arc::new(Mutex::new(timeline));
         let updater_callback: Arc<RwLock<Arc<dyn Fn() + Send + Sync>>> =
             Arc::new(RwLock::new(Arc::new(|| {})));
 
-        let eval_provider = consume_context::<Rc<dyn EvalProvider>>();
-
-        let create_eval = Rc::new(move |script: &str| {
-            UseEval::new(eval_provider.new_evaluator(script.to_string()))
-        }) as Rc<dyn Fn(&str) -> UseEval>;
-
-        // Listen to server actions
+        // Listen to the back and forward buttons of the browser
         spawn({
+            let mut popstate = channel.listen();
+            let channel = channel.clone();
             let timeline = timeline.clone();
-            let create_eval = create_eval.clone();
-            async move {
-                loop {
-                    let eval = action_rx.recv().await.expect("sender to exist");
-
-                    let _ = match eval {
-                        Action::GoBack => create_eval(
-                            r#"
-                                // this triggers a PopState event
-                                history.back();
-                            "#,
-                        ),
-                        Action::GoForward => create_eval(
-                            r#"
-                                // this triggers a PopState event
-                                history.forward();
-                            "#,
-                        ),
-                        Action::Push(route) => {
-                            let mut timeline = timeline.lock().expect("unpoisoned mutex");
-                            let state = timeline.push(route.clone());
-                            let state = serde_json::to_string(&state).expect("serializable state");
-                            let session = serde_json::to_string(&timeline.session())
-                                .expect("serializable session");
-                            create_eval(&format!(
-                                r#"
-                                // this does not trigger a PopState event
-                                history.pushState({state}, "", "{route}");
-                                sessionStorage.setItem("liveview", '{session}');
-                            "#
-                            ))
-                        }
-                        Action::Replace(route) => {
-                            let mut timeline = timeline.lock().expect("unpoisoned mutex");
-                            let state = timeline.replace(route.clone());
-                            let state = serde_json::to_string(&state).expect("serializable state");
-                            let session = serde_json::to_string(&timeline.session())
-                                .expect("serializable session");
-                            create_eval(&format!(
-                                r#"
-                                // this does not trigger a PopState event
-                                history.replaceState({state}, "", "{route}");
-                                sessionStorage.setItem("liveview", '{session}');
-                            "#
-                            ))
-                        }
-                        Action::External(url) => create_eval(&format!(
-                            r#"
-                                location.href = "{url}";
-                            "#
-                        )),
-                    };
-                }
-            }
-        });
-
-        // Listen to browser actions
-        spawn({
             let updater = updater_callback.clone();
-            let timeline = timeline.clone();
-            let create_eval = create_eval.clone();
             async move {
-                let mut popstate_eval = {
-                    let init_eval = create_eval(
-                        r#"
-                        return [
-                          document.location.pathname + "?" + document.location.search + "\#" + document.location.hash,
-                          history.state,
-                          JSON.parse(sessionStorage.getItem("liveview")),
-                          history.length,
-                        ];
-                    "#,
-                    ).await.expect("serializable state");
-                    let (route, state, session, depth) = serde_json::from_value::<(
-                        String,
-                        Option<State>,
-                        Option<Session<R>>,
-                        usize,
-                    )>(init_eval)
-                    .expect("serializable state");
-                    let Ok(route) = R::from_str(&route.to_string()) else {
-                        return;
-                    };
-                    let mut timeline = timeline.lock().expect("unpoisoned mutex");
-                    let state = timeline.init(route.clone(), state, session, depth);
-                    let state = serde_json::to_string(&state).expect("serializable state");
-                    let session =
-                        serde_json::to_string(&timeline.session()).expect("serializable session");
-
-                    // Call the updater callback
-                    (updater.read().unwrap())();
-
-                    create_eval(&format!(
-                        r#"
-                        // this does not trigger a PopState event
-                        history.replaceState({state}, "", "{route}");
-                        sessionStorage.setItem("liveview", '{session}');
-
-                        window.addEventListener("popstate", (event) => {{
-                          dioxus.send([
-                            document.location.pathname + "?" + document.location.search + "\#" + document.location.hash,
-                            event.state,
-                          ]);
-                        }});
-                    "#
-                    ))
-                };
-
-                loop {
-                    let event = match popstate_eval.recv().await {
-                        Ok(event) => event,
-                        Err(_) => continue,
-                    };
-                    let (route, state) = serde_json::from_value::<(String, Option<State>)>(event)
-                        .expect("serializable state");
-                    let Ok(route) = R::from_str(&route.to_string()) else {
-                        return;
+                while let Some(location) = popstate.recv().await {
+                    let Ok(route) = location.route.parse::<R>() else {
+                        continue;
                     };
-                    let mut timeline = timeline.lock().expect("unpoisoned mutex");
-                    let state = timeline.update(route.clone(), state);
-                    let state = serde_json::to_string(&state).expect("serializable state");
-                    let session =
-                        serde_json::to_string(&timeline.session()).expect("serializable session");
-
-                    let _ = create_eval(&format!(
-                        r#"
-                        // this does not trigger a PopState event
-                        history.replaceState({state}, "", "{route}");
-                        sessionStorage.setItem("liveview", '{session}');
-                    "#
-                    ));
+                    let state = location
+                        .state
+                        .and_then(|state| serde_json::from_value(state).ok());
+                    {
+                        let mut timeline = timeline.lock().expect("unpoisoned mutex");
+                        let state = timeline.update(route.clone(), state);
+                        channel.navigate(replace_action(&route, &state, &timeline));
+                    }
 
                     // Call the updater callback
                     (updater.read().unwrap())();
@@ -320,35 +217,57 @@ where
         });
 
         Self {
-            action_tx,
+            channel,
             timeline,
             updater_callback,
         }
     }
 }
 
+/// Store the state of the current entry and the history of the tab in the browser
+fn replace_action<R: Routable>(route: &R, state: &State, timeline: &Timeline<R>) -> HistoryAction
+where
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    HistoryAction::Replace {
+        route: route.to_string(),
+        state: serde_json::to_value(state).expect("serializable state"),
+        session: serde_json::to_value(timeline.session()).expect("serializable session"),
+    }
+}
+
 impl<R: Routable> HistoryProvider<R> for LiveviewHistory<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
 {
     fn go_back(&mut self) {
-        let _ = self.action_tx.send(Action::GoBack);
+        // The browser answers with a popstate event, which moves the timeline
+        self.channel.navigate(HistoryAction::Back);
     }
 
     fn go_forward(&mut self) {
-        let _ = self.action_tx.send(Action::GoForward);
+        self.channel.navigate(HistoryAction::Forward);
     }
 
     fn push(&mut self, route: R) {
-        let _ = self.action_tx.send(Action::Push(route));
+        let mut timeline = self.timeline.lock().expect("unpoisoned mutex");
+        let state = timeline.push(route.clone());
+        self.channel.navigate(HistoryAction::Push {
+            route: route.to_string(),
+            state: serde_json::to_value(state).expect("serializable state"),
+            session: serde_json::to_value(timeline.session()).expect("serializable session"),
+        });
     }
 
     fn replace(&mut self, route: R) {
-        let _ = self.action_tx.send(Action::Replace(route));
+        let mut timeline = self.timeline.lock().expect("unpoisoned mutex");
+        let state = timeline.replace(route.clone());
+        self.channel
+            .navigate(replace_action(&route, &state, &timeline));
     }
 
     fn external(&mut self, url: String) -> bool {
-        let _ = self.action_tx.send(Action::External(url));
+        self.channel.navigate(HistoryAction::External { url });
         true
     }
 
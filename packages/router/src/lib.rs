@@ -7,6 +7,7 @@
 
 pub mod navigation;
 pub mod routable;
+pub mod route_error;
 
 #[cfg(feature = "ssr")]
 pub mod incremental;
@@ -64,8 +65,9 @@ pub mod prelude {
     pub use crate::hooks::*;
     pub use crate::navigation::*;
     pub use crate::routable::*;
+    pub use crate::route_error::*;
     pub use crate::router_cfg::RouterConfig;
-    pub use dioxus_router_macro::Routable;
+    pub use dioxus_router_macro::{file_routes, Routable};
 
     #[cfg(feature = "ssr")]
     pub use crate::incremental::*;
@@ -0,0 +1,94 @@
+//! Error pages for routes that fail to render.
+
+use std::{fmt::Display, rc::Rc};
+
+use dioxus_lib::prelude::*;
+
+/// An error that stopped a route from rendering.
+///
+/// Throw [`RouteError::NotFound`] from a component to show the not found page for the route:
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[component]
+/// fn BlogPost(id: usize) -> Element {
+///     let title = ["Hello world"].get(id).ok_or(RouteError::NotFound).throw()?;
+///     rsx! { h1 { "{title}" } }
+/// }
+/// ```
+///
+/// Any other error thrown while rendering the route becomes [`RouteError::Failed`].
+#[derive(Debug, Clone)]
+pub enum RouteError {
+    /// The page the route points to doesn't exist.
+    NotFound,
+    /// A component of the route threw an error.
+    Failed(Rc<CapturedError>),
+}
+
+impl RouteError {
+    /// The HTTP status code a server rendered error page is sent with.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            RouteError::NotFound => 404,
+            RouteError::Failed(_) => 500,
+        }
+    }
+}
+
+impl PartialEq for RouteError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RouteError::NotFound, RouteError::NotFound) => true,
+            (RouteError::Failed(a), RouteError::Failed(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::NotFound => f.write_str("Page not found"),
+            RouteError::Failed(error) => write!(f, "{:?}", error.error),
+        }
+    }
+}
+
+impl From<CapturedError> for RouteError {
+    fn from(error: CapturedError) -> Self {
+        match error.downcast::<RouteError>() {
+            Some(error) => error.clone(),
+            None => RouteError::Failed(Rc::new(error)),
+        }
+    }
+}
+
+/// Render `children` and replace them with `error_page` if they throw an error.
+///
+/// The router wraps routes with an error page set by `#[error_page(Component)]` or
+/// [`RouterConfig::error_page`](crate::prelude::RouterConfig::error_page) in this boundary. When the page is server
+/// rendered, the response is sent with the [status code](RouteError::status_code) of the error.
+pub fn route_error_boundary(error_page: fn(RouteError) -> Element, children: Element) -> Element {
+    rsx! {
+        ErrorBoundary {
+            handle_error: move |error: CapturedError| render_error_page(error_page, error.into()),
+            {children}
+        }
+    }
+}
+
+fn render_error_page(error_page: fn(RouteError) -> Element, error: RouteError) -> Element {
+    set_response_status(&error);
+    error_page(error)
+}
+
+/// Send the server rendered response with the status code of the error
+#[allow(unused)]
+pub(crate) fn set_response_status(error: &RouteError) {
+    #[cfg(all(feature = "fullstack", feature = "ssr"))]
+    if let Ok(mut response) = dioxus_fullstack::prelude::server_context().response_parts_mut() {
+        response.status = http::StatusCode::from_u16(error.status_code())
+            .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
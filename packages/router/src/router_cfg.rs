@@ -21,6 +21,7 @@ use std::sync::Arc;
 /// ```
 pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn() -> Element,
+    pub(crate) error_page: Option<fn(RouteError) -> Element>,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
     pub(crate) initial_route: Option<R>,
@@ -33,6 +34,7 @@ where
     fn default() -> Self {
         Self {
             failure_external_navigation: FailureExternalNavigation,
+            error_page: None,
             history: None,
             on_update: None,
             initial_route: None,
@@ -62,6 +64,16 @@ where
     R: Routable,
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
+    /// Check if the router is rendering a server request for a url that doesn't match any route
+    pub(crate) fn request_route_not_found(&self) -> bool {
+        #[cfg(all(feature = "fullstack", feature = "ssr"))]
+        if self.history.is_none() {
+            return request_route::<R>().is_err();
+        }
+
+        false
+    }
+
     /// A function to be called whenever the routing is updated.
     ///
     /// The callback is invoked after the routing is updated, but before components and hooks are
@@ -112,6 +124,20 @@ where
             ..self
         }
     }
+
+    /// A component to render in place of the routes when one of them throws an error.
+    ///
+    /// Routes with their own `#[error_page(Component)]` render that page instead. When the page is server rendered,
+    /// the response is sent with the [status code](RouteError::status_code) of the error. A server request for a url
+    /// that doesn't match any route renders this page with [`RouteError::NotFound`].
+    ///
+    /// Defaults to [`None`], which lets errors bubble up past the router.
+    pub fn error_page(self, component: fn(RouteError) -> Element) -> Self {
+        Self {
+            error_page: Some(component),
+            ..self
+        }
+    }
 }
 
 /// Get the default history provider for the current platform.
@@ -128,21 +154,15 @@ where
     ));
 
     // If we're using fullstack and server side rendering, use the memory history provider
+    // Urls that don't match a route start at the index route, and the router renders the not found page instead
     #[cfg(all(feature = "fullstack", feature = "ssr"))]
     return Box::new(AnyHistoryProviderImplWrapper::new(
-        MemoryHistory::<R>::with_initial_path(
-            dioxus_fullstack::prelude::server_context()
-                .request_parts_blocking()
-                .uri
-                .to_string()
-                .parse()
-                .unwrap_or_else(|err| {
-                    tracing::error!("Failed to parse uri: {}", err);
-                    "/".parse().unwrap_or_else(|err| {
-                        panic!("Failed to parse uri: {}", err);
-                    })
-                }),
-        ),
+        MemoryHistory::<R>::with_initial_path(request_route::<R>().unwrap_or_else(|err| {
+            tracing::trace!("The request doesn't match a route: {}", err);
+            "/".parse().unwrap_or_else(|err| {
+                panic!("Failed to parse uri: {}", err);
+            })
+        })),
     ));
 
     // If liveview is enabled, use the liveview history provider
@@ -157,3 +177,13 @@ where
         MemoryHistory::with_initial_path(initial_route),
     ))
 }
+
+/// Parse the route of the request the server is rendering
+#[cfg(all(feature = "fullstack", feature = "ssr"))]
+fn request_route<R: Routable>() -> Result<R, <R as std::str::FromStr>::Err> {
+    dioxus_fullstack::prelude::server_context()
+        .request_parts_blocking()
+        .uri
+        .to_string()
+        .parse()
+}
@@ -0,0 +1,62 @@
+use dioxus::prelude::*;
+use std::str::FromStr;
+
+file_routes!("tests/file_routes/routes");
+
+#[test]
+fn file_routes_parse() {
+    assert_eq!(Route::from_str("/").unwrap(), Route::Index {});
+    assert_eq!(Route::from_str("/about").unwrap(), Route::About {});
+    assert_eq!(Route::from_str("/blog").unwrap(), Route::Blog {});
+    assert_eq!(Route::from_str("/blog/3").unwrap(), Route::BlogId { id: 3 });
+    assert_eq!(
+        Route::from_str("/missing/page").unwrap(),
+        Route::Segments {
+            segments: vec!["missing".to_string(), "page".to_string()]
+        }
+    );
+    assert_eq!(Route::BlogId { id: 3 }.to_string(), "/blog/3");
+}
+
+#[component]
+fn App(path: Route) -> Element {
+    rsx! {
+        Router::<Route> {
+            config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(path.clone()))
+        }
+    }
+}
+
+fn render(path: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path });
+    vdom.rebuild_in_place();
+    dioxus_ssr::render(&vdom)
+}
+
+#[test]
+fn file_routes_render() {
+    assert_eq!(render(Route::Index {}), "<h1>Home</h1>");
+    assert_eq!(
+        render(Route::Blog {}),
+        "<div class=\"blog\"><h1>Posts</h1></div>"
+    );
+}
+
+#[tokio::test]
+async fn file_routes_wait_for_loaders() {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: Route::BlogId { id: 3 },
+        },
+    );
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<div class=\"blog\"></div>");
+
+    vdom.wait_for_work().await;
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<div class=\"blog\"><h1>3: Post number 3</h1></div>"
+    );
+}
@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn Page(segments: Vec<String>) -> Element {
+    rsx! { h1 { "Not found: {segments:?}" } }
+}
@@ -0,0 +1,10 @@
+use dioxus::prelude::*;
+
+pub async fn loader(id: usize) -> String {
+    format!("Post number {id}")
+}
+
+#[component]
+pub fn Page(id: usize, data: String) -> Element {
+    rsx! { h1 { "{id}: {data}" } }
+}
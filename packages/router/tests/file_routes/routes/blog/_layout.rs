@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn Layout() -> Element {
+    rsx! {
+        div { class: "blog", Outlet::<crate::Route> {} }
+    }
+}
@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn Page() -> Element {
+    rsx! { h1 { "Posts" } }
+}
@@ -0,0 +1,133 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+
+#[derive(Routable, Clone)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[layout(Blog)]
+        #[error_page(BlogError)]
+            #[route("/blog/:id")]
+            BlogPost { id: usize },
+        #[end_error_page]
+    #[end_layout]
+    #[route("/broken")]
+    Broken {},
+}
+
+fn render(path: &str) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path: path.into() });
+    vdom.rebuild_in_place();
+    // Render the error pages of the boundaries that caught an error
+    vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+    dioxus_ssr::render(&vdom)
+}
+
+#[component]
+fn App(path: String) -> Element {
+    rsx! {
+        Router::<Route> {
+            config: {
+                let path = path.parse().unwrap();
+                move || {
+                    RouterConfig::default()
+                        .history(MemoryHistory::with_initial_path(path))
+                        .error_page(GlobalError)
+                }
+            }
+        }
+    }
+}
+
+fn GlobalError(error: RouteError) -> Element {
+    let status = error.status_code();
+    rsx! { h1 { "Global error {status}" } }
+}
+
+fn BlogError(error: RouteError) -> Element {
+    let status = error.status_code();
+    rsx! { h2 { "Blog error {status}: {error}" } }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { h1 { "Home" } }
+}
+
+#[component]
+fn Blog() -> Element {
+    rsx! {
+        h1 { "Blog" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn BlogPost(id: usize) -> Element {
+    let title = ["First post"].get(id).ok_or(RouteError::NotFound).throw()?;
+    rsx! { h2 { "{title}" } }
+}
+
+#[component]
+fn Broken() -> Element {
+    "not a number".parse::<i32>().throw()?;
+    None
+}
+
+#[test]
+fn routes_render_without_errors() {
+    assert_eq!(render("/"), "<h1>Home</h1>");
+    assert_eq!(render("/blog/0"), "<h1>Blog</h1><h2>First post</h2>");
+}
+
+#[test]
+fn route_error_pages_render_inside_layouts() {
+    assert_eq!(
+        render("/blog/5"),
+        "<h1>Blog</h1><h2>Blog error 404: Page not found</h2>"
+    );
+}
+
+#[test]
+fn global_error_page_renders_errors_without_a_route_error_page() {
+    assert_eq!(render("/broken"), "<h1>Global error 500</h1>");
+}
+
+#[cfg(all(feature = "fullstack", feature = "ssr"))]
+#[test]
+fn unknown_urls_render_the_not_found_page_with_a_404() {
+    use dioxus_fullstack::prelude::{with_server_context, DioxusServerContext};
+    use std::sync::Arc;
+
+    fn render_request(uri: &str) -> (String, http::StatusCode) {
+        fn app() -> Element {
+            rsx! {
+                Router::<Route> { config: || RouterConfig::default().error_page(GlobalError) }
+            }
+        }
+
+        let (parts, _) = http::Request::get(uri).body(()).unwrap().into_parts();
+        let server_context = DioxusServerContext::new(Arc::new(tokio::sync::RwLock::new(parts)));
+        let mut vdom = VirtualDom::new(app);
+        with_server_context(server_context.clone(), || {
+            vdom.rebuild_in_place();
+            vdom.render_immediate(&mut dioxus_core::NoOpMutations);
+        });
+        let status = server_context.response_parts().unwrap().status;
+        (dioxus_ssr::render(&vdom), status)
+    }
+
+    assert_eq!(
+        render_request("/"),
+        ("<h1>Home</h1>".to_string(), http::StatusCode::OK)
+    );
+    assert_eq!(
+        render_request("/not/a/route"),
+        (
+            "<h1>Global error 404</h1>".to_string(),
+            http::StatusCode::NOT_FOUND
+        )
+    );
+}
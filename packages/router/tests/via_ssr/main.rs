@@ -1,3 +1,4 @@
+mod error_page;
 mod link;
 mod outlet;
 mod without_index;
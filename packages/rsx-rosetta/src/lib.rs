@@ -14,9 +14,7 @@ use syn::{punctuated::Punctuated, LitStr};
 
 /// Convert an HTML DOM tree into an RSX CallBody
 pub fn rsx_from_html(dom: &Dom) -> CallBody {
-    CallBody {
-        roots: dom.children.iter().filter_map(rsx_node_from_html).collect(),
-    }
+    CallBody::new(dom.children.iter().filter_map(rsx_node_from_html).collect())
 }
 
 /// Convert an HTML Node into an RSX BodyNode
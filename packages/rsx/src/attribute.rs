@@ -1,10 +1,17 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    fmt::{Display, Formatter},
+};
 
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, quote_spanned};
-use syn::{parse_quote, spanned::Spanned, Expr, ExprClosure, ExprIf, Ident, LitStr};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    ext::IdentExt, parse_quote, spanned::Spanned, token::Brace, Expr, ExprClosure, ExprIf, Ident,
+    LitStr,
+};
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum AttributeType {
@@ -13,6 +20,10 @@ pub enum AttributeType {
 
     /// An attribute that's being spread in via the `..` syntax
     Spread(Expr),
+
+    /// A group of attributes that are only applied if a condition is true
+    /// `if selected { class: "active", aria_selected: "true" }`
+    Conditional(ConditionalAttributes),
 }
 
 impl AttributeType {
@@ -20,6 +31,7 @@ impl AttributeType {
         match self {
             AttributeType::Named(n) => n.attr.start(),
             AttributeType::Spread(e) => e.span(),
+            AttributeType::Conditional(c) => c.if_token.span(),
         }
     }
 
@@ -45,6 +57,11 @@ impl AttributeType {
             match attr {
                 AttributeType::Named(named) => single.push(named),
                 AttributeType::Spread(expr) => spread.push(expr),
+                AttributeType::Conditional(_) => {
+                    unreachable!(
+                        "Conditional attribute groups are flattened when the element is created"
+                    )
+                }
             }
         }
 
@@ -57,14 +74,19 @@ impl AttributeType {
             }
         } else {
             // Otherwise start with the single attributes and append the spread attributes
+            // Spreads can be any iterator of attributes or (name, value) pairs
             quote! {
                 {
+                    #[allow(unused_imports)]
+                    use dioxus_core::internal::SpreadAttributePairs as _;
                     let mut __attributes = vec![
                         #(#single),*
                     ];
                     #(
-                        let mut __spread = #spread;
-                        __attributes.append(&mut __spread);
+                        ::std::iter::Extend::<dioxus_core::Attribute>::extend(
+                            &mut __attributes,
+                            dioxus_core::internal::SpreadAttributes(#spread).into_attributes(),
+                        );
                     )*
                     __attributes.into_boxed_slice()
                 }
@@ -91,6 +113,161 @@ impl AttributeType {
     }
 }
 
+thread_local! {
+    /// The number of conditional attribute groups parsed so far in the current rsx call
+    static CONDITION_BINDINGS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run `f` while parsing an rsx call, numbering the condition bindings of its attribute groups from zero
+///
+/// The numbering only depends on the order of the groups in the call, so the hot reloader gives every group the same
+/// binding the macro did.
+pub(crate) fn with_condition_bindings<O>(f: impl FnOnce() -> O) -> O {
+    let previous = CONDITION_BINDINGS.with(|count| count.replace(0));
+    let out = f();
+    CONDITION_BINDINGS.with(|count| count.set(previous));
+    out
+}
+
+/// A group of attributes guarded by a single condition
+///
+/// ```rust, ignore
+/// div {
+///     if selected { class: "active", aria_selected: "true" },
+/// }
+/// ```
+///
+/// Each attribute in the group is lowered into an optional attribute (`class: if selected { "active" }`) when the
+/// element is created, so the group participates in attribute merging just like any other attribute. The condition is
+/// evaluated once into `binding` before the template is built, and every attribute in the group checks that local.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct ConditionalAttributes {
+    pub if_token: Token![if],
+    pub condition: Expr,
+    pub binding: Ident,
+    pub brace: Brace,
+    pub attributes: Vec<ElementAttrNamed>,
+}
+
+impl ConditionalAttributes {
+    /// Check if the stream starts with an `if` block that contains attributes instead of child nodes
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+
+        if fork.parse::<Token![if]>().is_err()
+            || fork.call(Expr::parse_without_eager_brace).is_err()
+        {
+            return false;
+        }
+
+        let Ok((_, content)) = crate::util::try_parse_braces(&fork) else {
+            return false;
+        };
+
+        ElementAttrName::peek_hyphenated(&content)
+            || ((content.peek(LitStr) || content.peek(Ident::peek_any))
+                && content.peek2(Token![:])
+                && !content.peek3(Token![:]))
+    }
+
+    pub(crate) fn parse_with_element(input: ParseStream, el_name: &ElementName) -> Result<Self> {
+        let if_token = input.parse::<Token![if]>()?;
+        let condition = input.call(Expr::parse_without_eager_brace)?;
+        let (brace, content) = crate::util::try_parse_braces(input)?;
+        let binding = CONDITION_BINDINGS.with(|count| {
+            let id = count.get();
+            count.set(id + 1);
+            format_ident!("__cond_{}", id, span = Span::mixed_site())
+        });
+
+        let mut attributes = Vec::new();
+
+        while !content.is_empty() {
+            let name = if content.peek(LitStr) {
+                ElementAttrName::Custom(content.parse()?)
+            } else if ElementAttrName::peek_hyphenated(&content) {
+                ElementAttrName::parse_hyphenated(&content)?
+            } else {
+                let name = Ident::parse_any(&content)?;
+
+                if name == "key" {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "Keys cannot be set conditionally. Move the `key` out of the `if` block",
+                    ));
+                }
+
+                if name.to_string().starts_with("on") {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "Event listeners cannot be added conditionally. Move the listener out of the `if` block and check the condition inside the handler",
+                    ));
+                }
+
+                ElementAttrName::built_in(&name)
+            };
+
+            let value = if content.parse::<Token![:]>().is_ok() {
+                content.parse::<ElementAttrValue>()?
+            } else {
+                match &name {
+                    ElementAttrName::BuiltIn(name) => ElementAttrValue::shorthand(name),
+                    ElementAttrName::Custom(name) | ElementAttrName::Hyphenated(name) => {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            "Expected a value for the custom attribute",
+                        ))
+                    }
+                }
+            };
+
+            attributes.push(ElementAttrNamed::new(
+                el_name.clone(),
+                ElementAttr { name, value },
+            ));
+
+            if content.is_empty() {
+                break;
+            }
+
+            if content.parse::<Token![,]>().is_err() {
+                return Err(crate::errors::missing_trailing_comma(content.span()));
+            }
+        }
+
+        Ok(Self {
+            if_token,
+            condition,
+            binding,
+            brace,
+            attributes,
+        })
+    }
+
+    /// Lower every attribute in the group into an optional attribute that checks the group's binding
+    pub fn flatten(&self) -> impl Iterator<Item = ElementAttrNamed> + '_ {
+        let binding = &self.binding;
+        self.attributes.iter().map(move |attr| {
+            let (condition, value) = match &attr.attr.value {
+                // Nested conditions are combined so we don't end up with an `Option<Option<T>>`
+                ElementAttrValue::AttrOptionalExpr { condition, value } => {
+                    (parse_quote! { #binding && (#condition) }, value.clone())
+                }
+                value => (parse_quote! { #binding }, Box::new(value.clone())),
+            };
+
+            ElementAttrNamed {
+                el_name: attr.el_name.clone(),
+                attr: ElementAttr {
+                    name: attr.attr.name.clone(),
+                    value: ElementAttrValue::AttrOptionalExpr { condition, value },
+                },
+                followed_by_comma: true,
+            }
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ElementAttrNamed {
     pub el_name: ElementName,
@@ -176,11 +353,11 @@ impl ElementAttrNamed {
                 #[doc(hidden)]
                 mod __completions {
                     // Autocomplete as an attribute
-                    pub use super::dioxus_elements::#el::*;
+                    pub use super::dioxus_elements::elements::#el::*;
                     // Autocomplete as an element
                     pub use super::dioxus_elements::elements::completions::CompleteWithBraces::*;
                     fn ignore() {
-                        #name
+                        let _ = #name;
                     }
                 }
             }
@@ -194,13 +371,13 @@ impl ToTokens for ElementAttrNamed {
 
         let ns = |name: &ElementAttrName| match (el_name, name) {
             (ElementName::Ident(i), ElementAttrName::BuiltIn(_)) => {
-                quote! { dioxus_elements::#i::#name.1 }
+                quote! { dioxus_elements::elements::#i::#name.1 }
             }
             _ => quote! { None },
         };
         let volitile = |name: &ElementAttrName| match (el_name, name) {
             (ElementName::Ident(i), ElementAttrName::BuiltIn(_)) => {
-                quote! { dioxus_elements::#i::#name.2 }
+                quote! { dioxus_elements::elements::#i::#name.2 }
             }
             _ => quote! { false },
         };
@@ -212,59 +389,81 @@ impl ToTokens for ElementAttrNamed {
                     quote!(#as_string)
                 }
             },
-            ElementAttrName::Custom(s) => quote! { #s },
+            ElementAttrName::Custom(s) | ElementAttrName::Hyphenated(s) => quote! { #s },
         };
 
         let attribute = {
             let value = &self.attr.value;
-            let is_shorthand_event = match &attr.value {
-                ElementAttrValue::Shorthand(s) => s.to_string().starts_with("on"),
-                _ => false,
-            };
 
-            match &attr.value {
-                ElementAttrValue::AttrLiteral(_)
-                | ElementAttrValue::AttrExpr(_)
-                | ElementAttrValue::Shorthand(_)
-                | ElementAttrValue::AttrOptionalExpr { .. }
-                    if !is_shorthand_event =>
-                {
+            match attr.event_handler() {
+                None => {
                     let name = &self.attr.name;
                     let ns = ns(name);
                     let volitile = volitile(name);
                     let attribute = attribute(name);
-                    let value = quote! { #value };
-
-                    quote! {
-                        dioxus_core::Attribute::new(
-                            #attribute,
-                            #value,
-                            #ns,
-                            #volitile
-                        )
+
+                    match (el_name, name) {
+                        (ElementName::Custom(_), ElementAttrName::BuiltIn(name)) => {
+                            let lookup = el_name.custom_attribute_lookup(name);
+                            el_name.custom_element_scope(quote! {
+                                #lookup
+                                dioxus_core::Attribute::new(
+                                    __ATTRIBUTE.0,
+                                    #value,
+                                    __ATTRIBUTE.1,
+                                    __ATTRIBUTE.2
+                                )
+                            })
+                        }
+                        _ => quote! {
+                            dioxus_core::Attribute::new(
+                                #attribute,
+                                #value,
+                                #ns,
+                                #volitile
+                            )
+                        },
                     }
                 }
-                ElementAttrValue::EventTokens(tokens) => match &self.attr.name {
+                Some(tokens) => match &self.attr.name {
+                    // `ref: my_element` stores the mounted element in the signal once the element is mounted
+                    name if name.is_ref() => {
+                        quote_spanned! { tokens.span() =>
+                            dioxus_elements::events::onmounted::call_with_explicit_closure(
+                                move |__event: dioxus_elements::events::MountedEvent| {
+                                    let mut __ref = #tokens;
+                                    __ref.set(Some(__event.data()));
+                                }
+                            )
+                        }
+                    }
                     ElementAttrName::BuiltIn(name) => {
                         let event_tokens_is_closure =
                             syn::parse2::<ExprClosure>(tokens.to_token_stream()).is_ok();
-                        let function_name =
-                            quote_spanned! { tokens.span() => dioxus_elements::events::#name };
+                        // Custom elements can declare their own events
+                        let function_name = match el_name {
+                            ElementName::Ident(_) => {
+                                quote_spanned! { tokens.span() => dioxus_elements::events::#name }
+                            }
+                            ElementName::Custom(_) => {
+                                let module = el_name.custom_element_module();
+                                quote_spanned! { tokens.span() => #module::events::#name }
+                            }
+                        };
                         let function = if event_tokens_is_closure {
                             // If we see an explicit closure, we can call the `call_with_explicit_closure` version of the event for better type inference
                             quote_spanned! { tokens.span() => #function_name::call_with_explicit_closure }
                         } else {
                             function_name
                         };
-                        quote_spanned! { tokens.span() =>
+                        el_name.custom_element_scope(quote_spanned! { tokens.span() =>
                             #function(#tokens)
-                        }
+                        })
+                    }
+                    ElementAttrName::Custom(_) | ElementAttrName::Hyphenated(_) => {
+                        unreachable!("Handled elsewhere in the macro")
                     }
-                    ElementAttrName::Custom(_) => unreachable!("Handled elsewhere in the macro"),
                 },
-                _ => {
-                    quote_spanned! { value.span() => dioxus_elements::events::#value(#value) }
-                }
             }
         };
 
@@ -302,6 +501,19 @@ impl ElementAttr {
 
         false
     }
+
+    /// Get the handler of an event listener attribute
+    ///
+    /// A bare `onclick` attribute is shorthand for `onclick: onclick`, so it binds the handler with the same name.
+    pub fn event_handler(&self) -> Option<Cow<'_, Expr>> {
+        match &self.value {
+            ElementAttrValue::EventTokens(tokens) => Some(Cow::Borrowed(tokens)),
+            ElementAttrValue::Shorthand(ident) if self.name.listener_name().is_some() => {
+                Some(Cow::Owned(parse_quote! { #ident }))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -376,6 +588,7 @@ impl ElementAttrValue {
 
     fn to_str_expr(&self) -> Option<TokenStream2> {
         match self {
+            ElementAttrValue::Shorthand(ident) => Some(quote!(#ident.to_string())),
             ElementAttrValue::AttrLiteral(lit) => Some(quote!(#lit.to_string())),
             ElementAttrValue::AttrOptionalExpr { value, .. } => value.to_str_expr(),
             ElementAttrValue::AttrExpr(expr) => Some(quote!(#expr.to_string())),
@@ -383,8 +596,17 @@ impl ElementAttrValue {
         }
     }
 
+    /// Combine two values of the same attribute, keeping the order they were written in so later
+    /// declarations (like `style` properties) take precedence
     fn combine(&self, separator: &str, other: &Self) -> Self {
         match (self, other) {
+            // A shorthand is just an expression with the same name as the attribute
+            (Self::Shorthand(ident), other) => {
+                Self::AttrExpr(parse_quote!(#ident)).combine(separator, other)
+            }
+            (this, Self::Shorthand(ident)) => {
+                this.combine(separator, &Self::AttrExpr(parse_quote!(#ident)))
+            }
             (Self::AttrLiteral(lit1), Self::AttrLiteral(lit2)) => {
                 let fmt = lit1.clone().join(lit2.clone(), separator);
                 Self::AttrLiteral(fmt)
@@ -395,10 +617,11 @@ impl ElementAttrValue {
                 ifmt.push_expr(expr2.clone());
                 Self::AttrLiteral(ifmt)
             }
-            (Self::AttrExpr(expr1), Self::AttrLiteral(expr2)) => {
-                let mut ifmt = expr2.clone();
-                ifmt.push_str(separator);
+            (Self::AttrExpr(expr1), Self::AttrLiteral(lit2)) => {
+                let mut ifmt = IfmtInput::default();
                 ifmt.push_expr(expr1.clone());
+                ifmt.push_str(separator);
+                ifmt.segments.extend(lit2.segments.iter().cloned());
                 Self::AttrLiteral(ifmt)
             }
             (Self::AttrExpr(expr1), Self::AttrExpr(expr2)) => {
@@ -441,11 +664,12 @@ impl ElementAttrValue {
                 let second_as_string = other.to_str_expr();
                 Self::AttrExpr(parse_quote! {
                     {
-                        let mut __combined = #second_as_string;
+                        let mut __combined = String::new();
                         if #condition {
-                            __combined.push_str(&#separator);
                             __combined.push_str(&#first_as_string);
+                            __combined.push_str(&#separator);
                         }
+                        __combined.push_str(&#second_as_string);
                         __combined
                     }
                 })
@@ -481,8 +705,15 @@ fn normalize_raw_ident(ident: &Ident) -> Ident {
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum ElementAttrName {
+    /// class: "value"
     BuiltIn(Ident),
+    /// "custom-attribute": "value"
     Custom(LitStr),
+    /// data-testid: "value"
+    ///
+    /// Hyphen-joined identifiers are custom attributes just like string literals, but we keep track of how they
+    /// were written so they can be formatted back out the same way
+    Hyphenated(LitStr),
 }
 
 impl ElementAttrName {
@@ -490,6 +721,53 @@ impl ElementAttrName {
         Self::BuiltIn(normalize_raw_ident(name))
     }
 
+    /// Check if the input starts with a hyphenated attribute name like `data-testid:`
+    pub(crate) fn peek_hyphenated(input: ParseStream) -> bool {
+        let fork = input.fork();
+        fork.call(Self::parse_hyphenated).is_ok() && fork.peek(Token![:]) && !fork.peek(Token![::])
+    }
+
+    /// Parse a hyphenated attribute name like `data-testid` or `hx-get`
+    pub(crate) fn parse_hyphenated(input: ParseStream) -> Result<Self> {
+        let raw = syn::punctuated::Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(
+            input,
+            Ident::parse_any,
+        )?;
+
+        if raw.len() < 2 {
+            return Err(syn::Error::new(
+                raw.span(),
+                "Expected a hyphenated attribute name",
+            ));
+        }
+
+        let span = raw.span();
+        let name = raw
+            .into_iter()
+            .map(|ident| ident.to_string())
+            .collect::<Vec<_>>()
+            .join("-");
+
+        Ok(Self::Hyphenated(LitStr::new(&name, span)))
+    }
+
+    /// Check if this is the `ref` attribute that stores the mounted element in a signal
+    pub fn is_ref(&self) -> bool {
+        matches!(self, ElementAttrName::BuiltIn(name) if name == "ref")
+    }
+
+    /// Get the name of the event listener this attribute adds to the element, if any
+    pub(crate) fn listener_name(&self) -> Option<String> {
+        match self {
+            _ if self.is_ref() => Some("onmounted".to_string()),
+            ElementAttrName::BuiltIn(name) => {
+                let name = name.to_string();
+                name.starts_with("on").then_some(name)
+            }
+            ElementAttrName::Custom(_) | ElementAttrName::Hyphenated(_) => None,
+        }
+    }
+
     fn multi_attribute_separator(&self) -> Option<&'static str> {
         match self {
             ElementAttrName::BuiltIn(i) => match i.to_string().as_str() {
@@ -497,14 +775,14 @@ impl ElementAttrName {
                 "style" => Some(";"),
                 _ => None,
             },
-            ElementAttrName::Custom(_) => None,
+            ElementAttrName::Custom(_) | ElementAttrName::Hyphenated(_) => None,
         }
     }
 
     pub fn start(&self) -> Span {
         match self {
             ElementAttrName::BuiltIn(i) => i.span(),
-            ElementAttrName::Custom(s) => s.span(),
+            ElementAttrName::Custom(s) | ElementAttrName::Hyphenated(s) => s.span(),
         }
     }
 }
@@ -513,7 +791,9 @@ impl ToTokens for ElementAttrName {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         match self {
             ElementAttrName::BuiltIn(i) => tokens.append_all(quote! { #i }),
-            ElementAttrName::Custom(s) => tokens.append_all(quote! { #s }),
+            ElementAttrName::Custom(s) | ElementAttrName::Hyphenated(s) => {
+                tokens.append_all(quote! { #s })
+            }
         }
     }
 }
@@ -522,7 +802,9 @@ impl Display for ElementAttrName {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ElementAttrName::BuiltIn(i) => write!(f, "{}", i),
-            ElementAttrName::Custom(s) => write!(f, "{}", s.value()),
+            ElementAttrName::Custom(s) | ElementAttrName::Hyphenated(s) => {
+                write!(f, "{}", s.value())
+            }
         }
     }
 }
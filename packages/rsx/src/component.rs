@@ -11,15 +11,15 @@
 //! - [ ] Keys
 //! - [ ] Properties spreading with with `..` syntax
 
-use self::{location::CallerLocation, util::try_parse_braces};
+use self::{hot_literals::is_hot_literal, location::CallerLocation, util::try_parse_braces};
 
 use super::*;
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
     ext::IdentExt, spanned::Spanned, token::Brace, AngleBracketedGenericArguments, Error, Expr,
-    Ident, LitStr, PathArguments, Token,
+    Ident, Lit, LitStr, PathArguments, Token,
 };
 
 #[derive(Clone, Debug)]
@@ -252,10 +252,18 @@ impl Component {
             if field.name == "key" {
                 continue;
             }
-            let ComponentField { name, content } = field;
-            toks.append_all(
-                quote_spanned! { manual_props.span() => __manual_props.#name = #content; },
-            );
+            let ComponentField { name, optional, .. } = field;
+            let content = field.value_tokens();
+            // Span the assignment at the field so a field that doesn't exist on the props is reported there
+            match optional {
+                true => toks.append_all(quote_spanned! { name.span() =>
+                    if let Some(__value) = #content {
+                        __manual_props.#name = __value;
+                    }
+                }),
+                false => toks
+                    .append_all(quote_spanned! { name.span() => __manual_props.#name = #content; }),
+            }
         }
         toks.append_all(quote_spanned! { manual_props.span() => __manual_props });
         quote! {{ #toks }}
@@ -271,9 +279,29 @@ impl Component {
         for field in &self.fields {
             toks.append_all(quote! {#field})
         }
-        if !self.children.is_empty() {
-            let renderer = TemplateRenderer::as_tokens(&self.children, None);
-            toks.append_all(quote_spanned! { name.span() => .children( #renderer ) });
+        match self.children.as_slice() {
+            [] => {}
+            [_] => {
+                let renderer = TemplateRenderer::as_sub_template_tokens(&self.children);
+                toks.append_all(quote_spanned! { name.span() => .children( #renderer ) });
+            }
+            // Children with several roots are rendered as one node or as one node per root, depending on whether the
+            // component takes an `Element` or a `ChildList`
+            roots => {
+                let renderer = TemplateRenderer::as_sub_template_tokens(roots);
+                let each_root = roots.iter().map(|root| {
+                    TemplateRenderer::as_sub_template_tokens(std::slice::from_ref(root))
+                });
+                toks.append_all(quote_spanned! { name.span() =>
+                    .children(dioxus_core::internal::RsxChildren(|__each_root: bool| {
+                        if __each_root {
+                            vec![#( #each_root ),*]
+                        } else {
+                            vec![#renderer]
+                        }
+                    }))
+                });
+            }
         }
         toks.append_all(quote_spanned! { name.span() => .build() });
         toks
@@ -297,10 +325,32 @@ impl Component {
 }
 
 // the struct's fields info
-#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+#[derive(Clone, Debug)]
 pub struct ComponentField {
+    /// The name of the prop. For `value as prop` this is `prop`
     pub name: Ident,
     pub content: ContentField,
+    /// `prop?: value` only sets the prop if the value is `Some`
+    pub optional: bool,
+    /// The id of the prop in its rsx call if the value is a literal that can be hot reloaded
+    pub hot_literal_id: Option<usize>,
+}
+
+// The hot literal id is assigned from the position of the prop in the whole rsx call, so it isn't part of the prop itself
+impl PartialEq for ComponentField {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.content == other.content && self.optional == other.optional
+    }
+}
+
+impl Eq for ComponentField {}
+
+impl Hash for ComponentField {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.content.hash(state);
+        self.optional.hash(state);
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -346,11 +396,27 @@ impl Parse for ComponentField {
     fn parse(input: ParseStream) -> Result<Self> {
         let name = Ident::parse_any(input)?;
 
+        // `value as prop` passes the variable `value` to the `prop` field
+        if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            let prop = Ident::parse_any(input)?;
+            return Ok(Self {
+                name: prop,
+                content: ContentField::Shorthand(name),
+                optional: false,
+                hot_literal_id: None,
+            });
+        }
+
+        let optional = input.parse::<Option<Token![?]>>()?.is_some();
+
         // if the next token is not a colon, then it's a shorthand field
         if input.parse::<Token![:]>().is_err() {
             return Ok(Self {
                 content: ContentField::Shorthand(name.clone()),
                 name,
+                optional,
+                hot_literal_id: None,
             });
         };
 
@@ -360,14 +426,27 @@ impl Parse for ComponentField {
             missing_trailing_comma!(content.span());
         }
 
-        Ok(Self { name, content })
+        Ok(Self {
+            name,
+            content,
+            optional,
+            hot_literal_id: None,
+        })
     }
 }
 
 impl ToTokens for ComponentField {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let ComponentField { name, content, .. } = self;
-        tokens.append_all(quote! { .#name(#content) })
+        let ComponentField { name, optional, .. } = self;
+        let content = self.value_tokens();
+        match optional {
+            // Props with a default have a setter that only sets the prop if the value is `Some`
+            true => {
+                let setter = format_ident!("__maybe_{}", name.unraw());
+                tokens.append_all(quote! { .#setter(#content) })
+            }
+            false => tokens.append_all(quote! { .#name(#content) }),
+        }
     }
 }
 
@@ -399,10 +478,32 @@ fn normalize_path(name: &mut syn::Path) -> Option<AngleBracketedGenericArguments
 }
 
 impl ComponentField {
+    /// The value of the prop if it is a literal like `"/home"`, `1`, `1.5` or `true` that can be hot reloaded
+    pub fn literal(&self) -> Option<&Lit> {
+        match &self.content {
+            ContentField::ManExpr(Expr::Lit(expr)) if !self.optional && expr.attrs.is_empty() => {
+                Some(&expr.lit).filter(|lit| is_hot_literal(lit))
+            }
+            _ => None,
+        }
+    }
+
+    /// The tokens of the value passed to the prop. Hot literals are read through the runtime so the hot reloader can
+    /// change them. The template the prop is rendered in is always in scope as `TEMPLATE`
+    fn value_tokens(&self) -> TokenStream2 {
+        let content = &self.content;
+        match self.hot_literal_id {
+            Some(id) => quote_spanned! { content.span() =>
+                dioxus_core::internal::hot_literal(TEMPLATE.name, #id, #content)
+            },
+            None => quote! { #content },
+        }
+    }
+
     pub fn can_be_shorthand(&self) -> bool {
-        // If it's a shorthand...
-        if matches!(self.content, ContentField::Shorthand(_)) {
-            return true;
+        // If it's a shorthand that isn't renamed...
+        if let ContentField::Shorthand(ident) = &self.content {
+            return *ident == self.name;
         }
 
         // If it's in the form of attr: attr, return true
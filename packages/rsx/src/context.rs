@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::*;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
 
 /// As we create the dynamic nodes, we want to keep track of them in a linear fashion
@@ -14,6 +14,9 @@ pub struct DynamicContext<'a> {
     pub node_paths: Vec<Vec<u8>>,
     pub attr_paths: Vec<Vec<u8>>,
 
+    /// The conditional attribute groups in the template, which bind their condition before the template is built
+    pub conditions: Vec<&'a ConditionalAttributes>,
+
     /// Mapping variables used to map the old template to the new template
     ///
     /// This tracks whether or not we're tracking some nodes or attributes
@@ -29,6 +32,13 @@ pub struct DynamicContext<'a> {
     /// We use the fact that AttributeType is Hash/PartialEq to track the attributes when we run into them
     attribute_to_idx: HashMap<AttributeType, Vec<usize>>,
     last_attribute_idx: usize,
+
+    /// The tracked dynamic nodes in the order of their index, used to find the ids their hot literals were compiled with
+    tracked_nodes: Vec<BodyNode>,
+
+    /// The values of the hot literals in the updated template, paired with the ids they were compiled with
+    #[cfg(feature = "hot_reload")]
+    pub hot_literals: Vec<(usize, dioxus_core::HotLiteral)>,
 }
 
 impl<'a> DynamicContext<'a> {
@@ -36,8 +46,8 @@ impl<'a> DynamicContext<'a> {
         let mut new = Self::default();
 
         if let Some(call) = template {
-            for node in call.roots {
-                new.track_node(node);
+            for node in flatten_root_fragment(&call.roots) {
+                new.track_node(node.clone());
             }
             new.is_tracking = true;
         }
@@ -87,7 +97,9 @@ impl<'a> DynamicContext<'a> {
 
             BodyNode::RawExpr(_)
             | BodyNode::Text(_)
+            | BodyNode::WhileLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Fragment(_)
             | BodyNode::Component(_) => self.render_dynamic_node(root),
         }
     }
@@ -115,6 +127,12 @@ impl<'a> DynamicContext<'a> {
         let ns = el_name.namespace();
         let span = el_name.span();
 
+        self.conditions
+            .extend(el.attributes.iter().filter_map(|attr| match attr {
+                AttributeType::Conditional(group) => Some(group),
+                _ => None,
+            }));
+
         let static_attrs = el
             .merged_attributes
             .iter()
@@ -184,15 +202,74 @@ impl<'a> DynamicContext<'a> {
         name: &ElementAttrName,
         el_name: &ElementName,
     ) -> TokenStream2 {
+        let span = value
+            .source
+            .as_ref()
+            .map(|source| source.span())
+            .unwrap_or_else(Span::call_site);
         let value = value.to_static().unwrap();
 
+        // Attributes of custom elements are looked up in the module `custom_elements!` declares for the element
+        if let (ElementName::Custom(tag), ElementAttrName::BuiltIn(attr)) = (el_name, name) {
+            let module = el_name.custom_element_module();
+            let lookup = el_name.custom_attribute_lookup(attr);
+            let message = format!(
+                "`{value}` is not a valid value for the `{}` attribute on `{}`",
+                attr.to_string().trim_start_matches("r#"),
+                tag.value()
+            );
+            let check = check_attribute_value(
+                quote! { #module::__is_valid_attribute_value(__ATTRIBUTE.0, #value) },
+                &message,
+                span,
+            );
+            let attribute = el_name.custom_element_scope(quote_spanned! { span =>
+                #lookup
+                const _: () = { #check };
+                dioxus_core::TemplateAttribute::Static {
+                    name: __ATTRIBUTE.0,
+                    namespace: __ATTRIBUTE.1,
+                    value: #value,
+                }
+            });
+            return quote! { #attribute, };
+        }
+
         let ns = match name {
             ElementAttrName::BuiltIn(name) => quote! { #el_name::#name.1 },
-            ElementAttrName::Custom(_) => quote!(None),
+            ElementAttrName::Custom(_) | ElementAttrName::Hyphenated(_) => quote!(None),
         };
 
         let name = match (el_name, name) {
-            (ElementName::Ident(_), ElementAttrName::BuiltIn(_)) => quote! { #el_name::#name.0 },
+            (ElementName::Ident(el), ElementAttrName::BuiltIn(attr)) => {
+                // Enumerated attributes only accept a fixed set of keywords. Namespaces that list the allowed values
+                // define `__is_valid_attribute_value` in the element module. The glob import shadows the fallback
+                // from dioxus_core that accepts everything, so namespaces without the metadata still compile
+                let message = format!(
+                    "`{value}` is not a valid value for the `{}` attribute on `{el}`",
+                    attr.to_string().trim_start_matches("r#"),
+                );
+                let check = check_attribute_value(
+                    quote! { __is_valid_attribute_value(__ATTRIBUTE, #value) },
+                    &message,
+                    span,
+                );
+                quote_spanned! { span =>
+                    {
+                        const _: () = {
+                            const __ATTRIBUTE: &str = #el_name::#name.0;
+                            #[allow(unused_imports)]
+                            use dioxus_core::internal::__is_valid_attribute_value;
+                            {
+                                #[allow(unused_imports)]
+                                use #el_name::*;
+                                #check
+                            }
+                        };
+                        #el_name::#name.0
+                    }
+                }
+            }
             _ => {
                 //hmmmm I think we could just totokens this, but the to_string might be inserting quotes
                 let as_string = name.to_string();
@@ -234,8 +311,8 @@ impl<'a> DynamicContext<'a> {
             BodyNode::Element(el) => self.update_element::<Ctx>(el),
 
             BodyNode::Text(text) if text.is_static() => {
-                let text = text.source.as_ref().unwrap();
-                let text = intern(text.value().as_str());
+                let text = text.to_static().unwrap();
+                let text = intern(text.as_str());
                 Some(TemplateNode::Text { text })
             }
 
@@ -244,7 +321,9 @@ impl<'a> DynamicContext<'a> {
             BodyNode::RawExpr(_)
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
+            | BodyNode::WhileLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Fragment(_)
             | BodyNode::Component(_) => self.update_dynamic_node(root),
         }
     }
@@ -262,6 +341,12 @@ impl<'a> DynamicContext<'a> {
             false => self.dynamic_nodes.len(),
         };
 
+        // The node may only match the old one once its hot literals are ignored, so send their new values to the app
+        if let Some(old) = self.tracked_nodes.get(idx) {
+            self.hot_literals
+                .extend(crate::hot_literals::hot_literal_changes(old, root));
+        }
+
         // Put the node in the dynamic nodes list
         self.dynamic_nodes.push(root);
 
@@ -356,6 +441,16 @@ impl<'a> DynamicContext<'a> {
         static_attr
     }
 
+    /// Check if every dynamic node and attribute of the old template has a place in the new one
+    ///
+    /// The app was compiled with the old dynamic nodes and attributes, so the new template needs a path for each of
+    /// them. Static subtrees can be added or removed freely, but removing a dynamic part needs a rebuild.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn placed_all_tracked(&self) -> bool {
+        self.node_to_idx.values().all(Vec::is_empty)
+            && self.attribute_to_idx.values().all(Vec::is_empty)
+    }
+
     /// Check if we're tracking any nodes
     ///
     /// If we're tracking, then we'll attempt to use the old mapping
@@ -379,7 +474,9 @@ impl<'a> DynamicContext<'a> {
             BodyNode::RawExpr(_)
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
+            | BodyNode::WhileLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Fragment(_)
             | BodyNode::Component(_) => {
                 self.track_dynamic_node(node);
             }
@@ -410,7 +507,11 @@ impl<'a> DynamicContext<'a> {
     pub(crate) fn track_dynamic_node(&mut self, node: BodyNode) -> usize {
         let idx = self.last_element_idx;
         self.last_element_idx += 1;
-        self.node_to_idx.entry(node).or_default().push(idx);
+        self.node_to_idx
+            .entry(Self::tracking_key(&node))
+            .or_default()
+            .push(idx);
+        self.tracked_nodes.push(node);
         idx
     }
 
@@ -421,6 +522,39 @@ impl<'a> DynamicContext<'a> {
     }
 
     pub(crate) fn tracked_node_idx(&mut self, node: &BodyNode) -> Option<usize> {
-        self.node_to_idx.get_mut(node).and_then(|idxs| idxs.pop())
+        self.node_to_idx
+            .get_mut(&Self::tracking_key(node))
+            .and_then(|idxs| idxs.pop())
+    }
+
+    /// Nodes that only differ in the values of hot literals are the same dynamic node
+    fn tracking_key(node: &BodyNode) -> BodyNode {
+        #[cfg(feature = "hot_reload")]
+        return crate::hot_literals::without_hot_literals(node);
+
+        #[cfg(not(feature = "hot_reload"))]
+        return node.clone();
+    }
+}
+
+/// Warn when a static value isn't one of the keywords an enumerated attribute accepts.
+///
+/// The browser ignores invalid values and uses the default of the attribute, so this is a warning instead of an error.
+/// `is_valid` picks one of two versions of a function at compile time, and the one for invalid values is deprecated
+/// with the message.
+fn check_attribute_value(is_valid: TokenStream2, message: &str, span: Span) -> TokenStream2 {
+    quote_spanned! { span =>
+        #[allow(dead_code)]
+        struct __CheckAttributeValue<const VALID: bool>;
+        #[allow(dead_code)]
+        impl __CheckAttributeValue<true> {
+            const fn check() {}
+        }
+        #[allow(dead_code)]
+        impl __CheckAttributeValue<false> {
+            #[deprecated(note = #message)]
+            const fn check() {}
+        }
+        __CheckAttributeValue::<{ #is_valid }>::check();
     }
 }
@@ -0,0 +1,346 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
+use std::collections::HashMap;
+use syn::{punctuated::Punctuated, Attribute, Ident, Token};
+
+/// The environment variable that switches the rsx macro to machine readable diagnostics. When it is set to `json`,
+/// every diagnostic is also printed to stderr as one line of JSON while the crate compiles.
+pub const DIAGNOSTICS_FORMAT_ENV: &str = "DIOXUS_RSX_DIAGNOSTICS";
+
+/// A problem in the rsx that compiles but probably doesn't do what the author meant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// A key on a node that isn't the root of a loop body. Keys only matter for nodes in a list.
+    KeyOutsideLoop,
+    /// A `for` or `while` loop whose items don't have keys
+    ForLoopWithoutKey,
+    /// An event listener that is identical to a listener on a parent element. Events bubble, so it runs twice.
+    DuplicateNestedListener,
+    /// Children passed to a component that also spreads its props. The children are ignored.
+    ChildrenAfterSpread,
+    /// A named character reference in text that isn't in the HTML spec, like `&nbps;`. It is rendered as written.
+    UnknownHtmlEntity,
+}
+
+impl Lint {
+    /// Every lint the rsx macro checks for
+    pub const ALL: [Lint; 5] = [
+        Lint::KeyOutsideLoop,
+        Lint::ForLoopWithoutKey,
+        Lint::DuplicateNestedListener,
+        Lint::ChildrenAfterSpread,
+        Lint::UnknownHtmlEntity,
+    ];
+
+    /// The name of the lint. This is the name that shows up in the warning.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::KeyOutsideLoop => "key_outside_loop",
+            Lint::ForLoopWithoutKey => "for_loop_without_key",
+            Lint::DuplicateNestedListener => "duplicate_nested_listener",
+            Lint::ChildrenAfterSpread => "children_after_spread",
+            Lint::UnknownHtmlEntity => "unknown_html_entity",
+        }
+    }
+
+    /// The stable code of the lint, like `RSX0001`. Codes never change meaning, even if the lint is renamed.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lint::KeyOutsideLoop => "RSX0001",
+            Lint::ForLoopWithoutKey => "RSX0002",
+            Lint::DuplicateNestedListener => "RSX0003",
+            Lint::ChildrenAfterSpread => "RSX0004",
+            Lint::UnknownHtmlEntity => "RSX0005",
+        }
+    }
+
+    /// Is the lint reported even without the `lints` feature? These lints are about typos that would otherwise ship
+    /// without any sign that something is wrong.
+    pub fn always_checked(&self) -> bool {
+        matches!(self, Lint::UnknownHtmlEntity)
+    }
+
+    /// The level the lint is reported at unless it is configured
+    pub fn default_level(&self) -> Level {
+        match self {
+            Lint::ForLoopWithoutKey => Level::Help,
+            _ => Level::Warn,
+        }
+    }
+
+    /// Find a lint by its name or its code
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|lint| lint.name() == name || lint.code().eq_ignore_ascii_case(name))
+    }
+}
+
+/// How a lint is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    /// The lint is not reported
+    Allow,
+    /// The lint is reported as a suggestion
+    Help,
+    /// The lint is reported as a warning
+    Warn,
+    /// The lint is reported as an error and the crate doesn't compile
+    Deny,
+}
+
+impl Level {
+    /// The name of the level, as it is written in attributes and `Cargo.toml`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Allow => "allow",
+            Level::Help => "help",
+            Level::Warn => "warn",
+            Level::Deny => "deny",
+        }
+    }
+
+    /// Find a level by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Level::Allow, Level::Help, Level::Warn, Level::Deny]
+            .into_iter()
+            .find(|level| level.name() == name)
+    }
+}
+
+/// The levels lints are reported at
+///
+/// A crate configures them in its `Cargo.toml`, and an rsx call overrides them with inner attributes:
+/// ```toml
+/// [package.metadata.dioxus.lints]
+/// for_loop_without_key = "warn"
+/// RSX0003 = "deny"
+/// ```
+/// ```rust, ignore
+/// rsx! {
+///     #![allow(key_outside_loop)]
+///     div { key: "{id}" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintLevels {
+    levels: HashMap<Lint, Level>,
+}
+
+impl LintLevels {
+    /// The level a lint is reported at
+    pub fn level(&self, lint: Lint) -> Level {
+        self.levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+
+    /// Report a lint at a level
+    pub fn set(&mut self, lint: Lint, level: Level) {
+        self.levels.insert(lint, level);
+    }
+
+    /// Read the levels from the inner attributes of an rsx call, like `#![deny(RSX0001, children_after_spread)]`
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut levels = Self::default();
+        levels.extend_from_attributes(attrs)?;
+        Ok(levels)
+    }
+
+    /// Override the levels with the inner attributes of an rsx call
+    pub fn extend_from_attributes(&mut self, attrs: &[Attribute]) -> syn::Result<()> {
+        for attr in attrs {
+            // Templates are configured with the same inner attributes as the lints
+            if crate::is_hashed_templates_attribute(attr) {
+                continue;
+            }
+
+            let level = attr
+                .path()
+                .get_ident()
+                .and_then(|ident| Level::from_name(&ident.to_string()))
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        attr.path(),
+                        "rsx only accepts `allow`, `help`, `warn`, `deny` and `hashed_templates` attributes",
+                    )
+                })?;
+
+            let lints =
+                attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_separated_nonempty)?;
+            for name in lints {
+                let lint = Lint::from_name(&name.to_string()).ok_or_else(|| {
+                    syn::Error::new(name.span(), format!("unknown rsx lint `{name}`"))
+                })?;
+                self.set(lint, level);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the levels from the `[package.metadata.dioxus.lints]` table of the `Cargo.toml` of the crate that is being
+    /// compiled. The table is read once per crate.
+    #[cfg(feature = "lints")]
+    pub fn from_manifest() -> Result<Self, String> {
+        use std::{path::PathBuf, sync::Mutex};
+
+        static MANIFESTS: Mutex<Option<HashMap<PathBuf, Result<LintLevels, String>>>> =
+            Mutex::new(None);
+
+        let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+            return Ok(Self::default());
+        };
+        let manifest = PathBuf::from(dir).join("Cargo.toml");
+
+        let mut manifests = MANIFESTS.lock().unwrap();
+        manifests
+            .get_or_insert_with(HashMap::new)
+            .entry(manifest.clone())
+            .or_insert_with(|| {
+                let contents = std::fs::read_to_string(&manifest).unwrap_or_default();
+                Self::from_manifest_str(&contents)
+                    .map_err(|err| format!("invalid rsx lints in {}: {err}", manifest.display()))
+            })
+            .clone()
+    }
+
+    /// Read the levels from the `[package.metadata.dioxus.lints]` table of a `Cargo.toml`
+    #[cfg(feature = "lints")]
+    pub fn from_manifest_str(manifest: &str) -> Result<Self, String> {
+        let manifest: toml::Table = manifest.parse().map_err(|err| format!("{err}"))?;
+        let table = ["package", "metadata", "dioxus", "lints"]
+            .into_iter()
+            .try_fold(&manifest, |table, key| table.get(key)?.as_table());
+
+        let mut levels = Self::default();
+        for (name, level) in table.into_iter().flatten() {
+            let lint = Lint::from_name(name).ok_or_else(|| format!("unknown lint `{name}`"))?;
+            let level = level
+                .as_str()
+                .and_then(Level::from_name)
+                .ok_or_else(|| format!("the level of `{name}` must be one of \"allow\", \"help\", \"warn\" or \"deny\""))?;
+            levels.set(lint, level);
+        }
+        Ok(levels)
+    }
+}
+
+/// A single warning about the rsx
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub lint: Lint,
+    pub level: Level,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// The diagnostic as a JSON object, for tools that read the diagnostics of the rsx macro
+    #[cfg(feature = "lints")]
+    pub fn to_json(&self) -> String {
+        // Spans inside of a proc macro only know their location on some compilers. Unknown locations are line 0
+        let start = self.span.start();
+        let known = start.line > 0;
+        serde_json::json!({
+            "code": self.lint.code(),
+            "lint": self.lint.name(),
+            "level": self.level.name(),
+            "message": self.message,
+            "line": known.then_some(start.line),
+            "column": known.then_some(start.column + 1),
+        })
+        .to_string()
+    }
+}
+
+/// Warnings collected while linting an rsx call
+///
+/// Stable rust doesn't let proc macros emit warnings, so each warning is emitted as the use of a deprecated constant
+/// named after the lint. The compiler then reports it at the span of the node that caused it. Denied lints are emitted
+/// as compile errors.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Report a lint at its default level
+    pub fn push(&mut self, lint: Lint, span: Span, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            lint,
+            level: lint.default_level(),
+            span,
+            message: message.into(),
+        });
+    }
+
+    /// Report every lint at its configured level, dropping the allowed ones
+    pub fn with_levels(mut self, levels: &LintLevels) -> Self {
+        self.diagnostics.retain_mut(|diagnostic| {
+            diagnostic.level = levels.level(diagnostic.lint);
+            diagnostic.level != Level::Allow
+        });
+        self
+    }
+
+    /// Keep only the diagnostics that match the predicate
+    pub fn retain(&mut self, f: impl FnMut(&Diagnostic) -> bool) {
+        self.diagnostics.retain(f);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+impl ToTokens for Diagnostics {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        for Diagnostic {
+            lint,
+            level,
+            span,
+            message,
+        } in &self.diagnostics
+        {
+            let code = lint.code();
+            let name = lint.name();
+            match level {
+                Level::Allow => {}
+                Level::Deny => {
+                    let message = format!("{message} ({code} {name})");
+                    tokens
+                        .append_all(quote_spanned! { *span => ::core::compile_error!(#message); });
+                }
+                Level::Help | Level::Warn => {
+                    let message = match level {
+                        Level::Help => format!("help: {message} ({code})"),
+                        _ => format!("{message} ({code})"),
+                    };
+
+                    // The use of the constant needs the span of the user's code or the compiler hides the warning as
+                    // part of the macro expansion
+                    let constant = Ident::new(name, Span::call_site());
+                    let usage = Ident::new(name, *span);
+                    let usage = quote_spanned! { *span => let _ = #usage; };
+                    tokens.append_all(quote! {
+                        {
+                            #[deprecated(note = #message)]
+                            #[allow(non_upper_case_globals)]
+                            const #constant: () = ();
+                            #usage
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
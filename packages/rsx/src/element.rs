@@ -1,13 +1,13 @@
 use std::fmt::{Display, Formatter};
 
-use crate::errors::missing_trailing_comma;
+use crate::errors::{attr_after_element, invalid_key, missing_trailing_comma};
 
-use self::util::try_parse_braces;
+use self::util::{skip_until_comma, try_parse_braces};
 
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{
     ext::IdentExt, punctuated::Punctuated, spanned::Spanned, token::Brace, Expr, Ident, LitStr,
     Token,
@@ -64,7 +64,15 @@ impl Element {
         // Deduplicate any attributes that can be combined
         // For example, if there are two `class` attributes, combine them into one
         let mut merged_attributes: Vec<AttributeType> = Vec::new();
-        for attr in &attributes {
+        let flattened = attributes.iter().flat_map(|attr| match attr {
+            // Conditional groups are lowered into one optional attribute per entry
+            AttributeType::Conditional(group) => {
+                group.flatten().map(AttributeType::Named).collect()
+            }
+            attr => vec![attr.clone()],
+        });
+        for attr in flattened {
+            let attr = &attr;
             let attr_index = merged_attributes
                 .iter()
                 .position(|a| a.matches_attr_name(attr));
@@ -118,6 +126,8 @@ impl Element {
                 && !input.peek(Token![for])
                 && !input.peek(Token![if])
                 && !input.peek(Token![match])
+                && !input.peek(Token![while])
+                && !input.peek(Token![loop])
         }
 
         let el_name = ElementName::parse(stream)?;
@@ -144,6 +154,26 @@ impl Element {
             };
         }
 
+        // In partial completion mode, record the error and skip to the next attribute so the rest of the element
+        // still expands and rust analyzer can keep completing inside of it
+        macro_rules! try_or_recover {
+            ($result:expr) => {
+                match $result {
+                    Ok(value) => value,
+                    Err(error) if partial_completions => {
+                        errors.push(error);
+                        skip_until_comma(&content);
+                        if content.is_empty() {
+                            break;
+                        }
+                        let _ = content.parse::<Token![,]>();
+                        continue;
+                    }
+                    Err(error) => return Err(error),
+                }
+            };
+        }
+
         // parse fields with commas
         // break when we don't get this pattern anymore
         // start parsing bodynodes
@@ -152,7 +182,7 @@ impl Element {
         loop {
             if content.peek(Token![..]) {
                 content.parse::<Token![..]>()?;
-                let expr = content.parse::<Expr>()?;
+                let expr = try_or_recover!(content.parse::<Expr>());
                 let span = expr.span();
                 attributes.push(attribute::AttributeType::Spread(expr));
 
@@ -166,6 +196,25 @@ impl Element {
                 continue;
             }
 
+            // Parse conditional attribute groups
+            // if selected { class: "active", aria_selected: "true" },
+            if content.peek(Token![if]) && ConditionalAttributes::peek(&content) {
+                let group = try_or_recover!(ConditionalAttributes::parse_with_element(
+                    &content, &el_name
+                ));
+                let span = group.if_token.span();
+                attributes.push(attribute::AttributeType::Conditional(group));
+
+                if content.is_empty() {
+                    break;
+                }
+
+                if content.parse::<Token![,]>().is_err() {
+                    accumulate_or_return_error!(missing_trailing_comma(span));
+                }
+                continue;
+            }
+
             // Parse the raw literal fields
             // "def": 456,
             if content.peek(LitStr) && content.peek2(Token![:]) && !content.peek3(Token![:]) {
@@ -174,7 +223,7 @@ impl Element {
 
                 content.parse::<Token![:]>()?;
 
-                let value = content.parse::<ElementAttrValue>()?;
+                let value = try_or_recover!(content.parse::<ElementAttrValue>());
                 let followed_by_comma = content.parse::<Token![,]>().is_ok();
                 attributes.push(attribute::AttributeType::Named(ElementAttrNamed {
                     el_name: el_name.clone(),
@@ -195,6 +244,34 @@ impl Element {
                 continue;
             }
 
+            // Parse hyphenated custom attributes
+            // data-testid: "foo",
+            if content.peek(Ident::peek_any)
+                && content.peek2(Token![-])
+                && ElementAttrName::peek_hyphenated(&content)
+            {
+                let name = ElementAttrName::parse_hyphenated(&content)?;
+                let span = name.start();
+                content.parse::<Token![:]>()?;
+
+                let value = try_or_recover!(content.parse::<ElementAttrValue>());
+                let followed_by_comma = content.parse::<Token![,]>().is_ok();
+                attributes.push(attribute::AttributeType::Named(ElementAttrNamed {
+                    el_name: el_name.clone(),
+                    attr: ElementAttr { name, value },
+                    followed_by_comma,
+                }));
+
+                if content.is_empty() {
+                    break;
+                }
+
+                if !followed_by_comma {
+                    accumulate_or_return_error!(missing_trailing_comma(span));
+                }
+                continue;
+            }
+
             // Parse
             // abc: 123,
             if peek_any_ident(&content) && content.peek2(Token![:]) && !content.peek3(Token![:]) {
@@ -208,44 +285,43 @@ impl Element {
                 let span = content.span();
 
                 if name_str == "key" {
-                    let _key: IfmtInput = content.parse()?;
+                    let _key: IfmtInput = try_or_recover!(content.parse());
 
                     if _key.is_static() {
-                        invalid_key!(_key);
+                        accumulate_or_return_error!(invalid_key(&_key));
                     }
 
                     key = Some(_key);
                 } else {
-                    let value = if name_str.starts_with("on") {
+                    // `ref: my_signal` is sugar for an onmounted listener, so we keep the name as is instead of turning it into a raw identifier
+                    let attr_name = match name_str == "ref" {
+                        true => ElementAttrName::BuiltIn(name.clone()),
+                        false => ElementAttrName::built_in(&name),
+                    };
+
+                    let value = if let Some(listener) = attr_name.listener_name() {
                         // check for any duplicate event listeners
-                        if attributes.iter().any(|f| {
-                            if let AttributeType::Named(ElementAttrNamed {
-                                attr:
-                                    ElementAttr {
-                                        name: ElementAttrName::BuiltIn(n),
-                                        value: ElementAttrValue::EventTokens(_),
-                                    },
-                                ..
-                            }) = f
-                            {
-                                n == &name_str
-                            } else {
-                                false
-                            }
-                        }) {
-                            return Err(syn::Error::new(
-                                name.span(),
-                                format!("Duplicate event listener `{}`", name),
-                            ));
+                        if has_listener(&attributes, &listener) {
+                            try_or_recover!(Err(duplicate_listener(&name, &listener)));
+                        }
+
+                        let value = try_or_recover!(content.parse::<Expr>());
+
+                        if attr_name.is_ref() && matches!(value, Expr::Lit(_)) {
+                            try_or_recover!(Err(syn::Error::new(
+                                value.span(),
+                                "`ref` expects a signal to store the mounted element in, like `ref: my_element`",
+                            )));
                         }
-                        ElementAttrValue::EventTokens(content.parse()?)
+
+                        ElementAttrValue::EventTokens(value)
                     } else {
-                        content.parse::<ElementAttrValue>()?
+                        try_or_recover!(content.parse::<ElementAttrValue>())
                     };
                     attributes.push(attribute::AttributeType::Named(ElementAttrNamed {
                         el_name: el_name.clone(),
                         attr: ElementAttr {
-                            name: ElementAttrName::built_in(&name),
+                            name: attr_name,
                             value,
                         },
                         followed_by_comma: content.peek(Token![,]),
@@ -273,7 +349,7 @@ impl Element {
 
                 // If the shorthand field is children, these are actually children!
                 if name == "children" {
-                    return Err(syn::Error::new(
+                    try_or_recover!(Err(syn::Error::new(
                         name.span(),
                         r#"Shorthand element children are not supported.
 To pass children into elements, wrap them in curly braces.
@@ -281,9 +357,16 @@ Like so:
     div { {children} }
 
 "#,
-                    ));
+                    )));
                 };
 
+                if name == "ref" {
+                    try_or_recover!(Err(syn::Error::new(
+                        name.span(),
+                        "`ref` needs a signal to store the mounted element in, like `ref: my_element`",
+                    )));
+                }
+
                 let followed_by_comma = content.parse::<Token![,]>().is_ok();
 
                 // If the shorthand field starts with a capital letter and it isn't followed by a comma, it's actually the start of typing a component
@@ -299,11 +382,19 @@ Like so:
 
                 // Otherwise, it is really a shorthand field
                 let value = ElementAttrValue::shorthand(&name);
+                let attr_name = ElementAttrName::built_in(&name);
+
+                // `onclick,` binds the handler named `onclick`, so it counts as a listener too
+                if let Some(listener) = attr_name.listener_name() {
+                    if has_listener(&attributes, &listener) {
+                        try_or_recover!(Err(duplicate_listener(&name, &listener)));
+                    }
+                }
 
                 attributes.push(attribute::AttributeType::Named(ElementAttrNamed {
                     el_name: el_name.clone(),
                     attr: ElementAttr {
-                        name: ElementAttrName::built_in(&name),
+                        name: attr_name,
                         value,
                     },
                     followed_by_comma,
@@ -323,13 +414,30 @@ Like so:
         }
 
         while !content.is_empty() {
-            if ((content.peek(Ident) || content.peek(LitStr)) && content.peek2(Token![:]))
-                && !content.peek3(Token![:])
+            if (((content.peek(Ident) || content.peek(LitStr)) && content.peek2(Token![:]))
+                && !content.peek3(Token![:]))
+                || ElementAttrName::peek_hyphenated(&content)
             {
-                attr_after_element!(content.span());
+                if !partial_completions {
+                    attr_after_element!(content.span());
+                }
+                errors.push(attr_after_element(content.span()));
+                skip_until_comma(&content);
+                let _ = content.parse::<Token![,]>();
+                continue;
             }
 
-            children.push(BodyNode::parse_with_options(&content, partial_completions)?);
+            match BodyNode::parse_with_options(&content, partial_completions) {
+                Ok(child) => children.push(child),
+                // Keep the children we've parsed so far and drop the rest of the body
+                // There are no separators between children, so there isn't a good place to resume parsing
+                Err(error) if partial_completions => {
+                    errors.push(error);
+                    let _ = content.parse::<TokenStream2>();
+                    break;
+                }
+                Err(error) => return Err(error),
+            }
             // consume comma if it exists
             // we don't actually care if there *are* commas after elements/text
             if content.peek(Token![,]) {
@@ -387,6 +495,24 @@ impl Parse for Element {
     }
 }
 
+/// Check if any of the attributes already adds the event listener
+fn has_listener(attributes: &[AttributeType], listener: &str) -> bool {
+    attributes.iter().any(|attr| match attr {
+        AttributeType::Named(attr) => {
+            attr.attr.event_handler().is_some()
+                && attr.attr.name.listener_name().as_deref() == Some(listener)
+        }
+        _ => false,
+    })
+}
+
+fn duplicate_listener(name: &Ident, listener: &str) -> syn::Error {
+    syn::Error::new(
+        name.span(),
+        format!("Duplicate event listener `{}`", listener),
+    )
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum ElementName {
     Ident(Ident),
@@ -407,6 +533,57 @@ impl ElementName {
             ElementName::Custom(_) => quote! { None },
         }
     }
+
+    /// Wrap some tokens in a scope where the module for a custom element is in scope under the name of the element
+    /// with hyphens replaced by underscores.
+    ///
+    /// Custom elements declared with `custom_elements!` live in the `custom_elements` module at the call site. The
+    /// glob import in the inner block shadows the untyped fallback module from the outer block, so elements that
+    /// were never declared still accept any attribute.
+    pub(crate) fn custom_element_scope(&self, body: TokenStream2) -> TokenStream2 {
+        let ElementName::Custom(tag) = self else {
+            return body;
+        };
+        let module = self.custom_element_module();
+        quote_spanned! { tag.span() =>
+            {
+                #[allow(unused_imports)]
+                use dioxus_elements::custom_elements::__untyped as #module;
+                {
+                    #[allow(unused_imports)]
+                    use custom_elements::*;
+                    #body
+                }
+            }
+        }
+    }
+
+    /// Look up an attribute of a custom element in its module as the `__ATTRIBUTE` constant. This must be used inside
+    /// of [`Self::custom_element_scope`]
+    pub(crate) fn custom_attribute_lookup(&self, attr: &Ident) -> TokenStream2 {
+        let module = self.custom_element_module();
+        let attr_name = attr.to_string();
+        let message = format!(
+            "`{}` is not an attribute of the `{self}` custom element",
+            attr_name.trim_start_matches("r#"),
+        );
+        quote_spanned! { attr.span() =>
+            const __ATTRIBUTE: dioxus_elements::AttributeDiscription = match #module::__attribute(#attr_name) {
+                Some(attribute) => attribute,
+                None => panic!(#message),
+            };
+        }
+    }
+
+    /// The name of the module `custom_elements!` creates for a custom element
+    pub(crate) fn custom_element_module(&self) -> Ident {
+        let name = self
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        Ident::new(&name, self.span())
+    }
 }
 
 impl ElementName {
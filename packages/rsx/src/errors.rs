@@ -1,4 +1,5 @@
 use proc_macro2::Span;
+use syn::spanned::Spanned;
 
 pub(crate) fn missing_trailing_comma(span: Span) -> syn::Error {
     syn::Error::new(span, "missing trailing comma")
@@ -10,9 +11,16 @@ macro_rules! missing_trailing_comma {
     };
 }
 
+pub(crate) fn attr_after_element(span: Span) -> syn::Error {
+    syn::Error::new(
+        span,
+        "expected element\n  = help move the attribute above all the children and text elements",
+    )
+}
+
 macro_rules! attr_after_element {
     ($span:expr) => {
-        return Err(syn::Error::new($span, "expected element\n  = help move the attribute above all the children and text elements"));
+        return Err(crate::errors::attr_after_element($span));
     };
 }
 
@@ -31,12 +39,16 @@ macro_rules! invalid_component_path {
     };
 }
 
+pub(crate) fn invalid_key(key: &crate::IfmtInput) -> syn::Error {
+    let val = key.to_static().unwrap();
+    syn::Error::new(
+        key.span(),
+        format!("Element keys must be a dynamic value. Considering using `key: {{{val}}}` instead.\nStatic keys will result in every element using the same key which will cause rendering issues or panics."),
+    )
+}
+
 macro_rules! invalid_key {
     ($_key:ident) => {
-        let val = $_key.to_static().unwrap();
-        return Err(syn::Error::new(
-            $_key.span(),
-            format!("Element keys must be a dynamic value. Considering using `key: {{{val}}}` instead.\nStatic keys will result in every element using the same key which will cause rendering issues or panics."),
-        ));
+        return Err(crate::errors::invalid_key(&$_key));
     };
 }
@@ -0,0 +1,263 @@
+//! Literals in rsx calls that can be hot reloaded
+//!
+//! Every literal prop passed to a component (`Link { to: "/home" }`) gets an id in its rsx call, counting in the order
+//! the props appear. The literals in the iterator of a `for` loop (`for i in 0..10`) and the condition of an `if`
+//! (`if count > 3`) are numbered after the props. The generated code reads each literal through
+//! `dioxus_core::internal::hot_literal` with its id, so the hot reloader can send a new value instead of forcing a
+//! rebuild when only the literal changed.
+
+use crate::*;
+use std::hash::Hasher;
+use syn::{parse_quote_spanned, spanned::Spanned, Expr, Lit};
+
+/// The ids of the hot literals in the iterator of a `for` loop or the condition of an `if`, in the order they appear
+///
+/// Like the id of a component prop, the ids come from the position of the expression in the whole rsx call, so they
+/// are ignored when nodes are compared.
+#[derive(Clone, Debug, Default)]
+pub struct HotLiteralIds(pub Vec<usize>);
+
+impl PartialEq for HotLiteralIds {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for HotLiteralIds {}
+
+impl Hash for HotLiteralIds {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Give every literal component prop in an rsx call an id, then every literal in a loop iterator or `if` condition
+pub(crate) fn assign_hot_literal_ids(roots: &mut [BodyNode]) {
+    let mut next = 0;
+    let mut next_id = || {
+        next += 1;
+        next - 1
+    };
+    for_each_field_mut(roots, &mut |field| {
+        field.hot_literal_id = field.literal().is_some().then(&mut next_id);
+    });
+    for_each_expr_mut(roots, &mut |expr, ids| {
+        ids.0 = hot_literal_exprs(expr).iter().map(|_| next_id()).collect();
+    });
+}
+
+/// Check if a literal can be hot reloaded. Integers and floats need to fit in the types the runtime stores them as
+pub(crate) fn is_hot_literal(lit: &Lit) -> bool {
+    match lit {
+        Lit::Str(_) | Lit::Bool(_) => true,
+        Lit::Int(int) => int.base10_parse::<i128>().is_ok(),
+        Lit::Float(float) => float.base10_parse::<f64>().is_ok(),
+        _ => false,
+    }
+}
+
+/// The literals in an expression that can be hot reloaded.
+///
+/// Only literals that are operands of ranges, operators and method calls are included. Literals in other positions
+/// could be in a const context like the length of an array, where the runtime can't be read.
+pub(crate) fn hot_literal_exprs(expr: &mut Expr) -> Vec<&mut Expr> {
+    fn collect<'a>(expr: &'a mut Expr, exprs: &mut Vec<&'a mut Expr>) {
+        if matches!(expr, Expr::Lit(lit) if lit.attrs.is_empty() && is_hot_literal(&lit.lit)) {
+            exprs.push(expr);
+            return;
+        }
+
+        match expr {
+            Expr::Range(range) => {
+                if let Some(start) = &mut range.start {
+                    collect(start, exprs);
+                }
+                if let Some(end) = &mut range.end {
+                    collect(end, exprs);
+                }
+            }
+            Expr::Binary(binary) => {
+                collect(&mut binary.left, exprs);
+                collect(&mut binary.right, exprs);
+            }
+            Expr::Unary(unary) => collect(&mut unary.expr, exprs),
+            Expr::Paren(paren) => collect(&mut paren.expr, exprs),
+            Expr::Group(group) => collect(&mut group.expr, exprs),
+            Expr::MethodCall(call) => {
+                collect(&mut call.receiver, exprs);
+                call.args.iter_mut().for_each(|arg| collect(arg, exprs));
+            }
+            _ => {}
+        }
+    }
+
+    let mut exprs = Vec::new();
+    collect(expr, &mut exprs);
+    exprs
+}
+
+/// A copy of a loop iterator or `if` condition that reads its hot literals through the runtime. The template the
+/// expression is rendered in is always in scope as `TEMPLATE`
+pub(crate) fn read_hot_literals(expr: &Expr, ids: &HotLiteralIds) -> Expr {
+    let mut expr = expr.clone();
+    for (lit, id) in hot_literal_exprs(&mut expr).into_iter().zip(&ids.0) {
+        let value = lit.clone();
+        *lit = parse_quote_spanned! { value.span() =>
+            dioxus_core::internal::hot_literal(TEMPLATE.name, #id, #value)
+        };
+    }
+    expr
+}
+
+fn for_each_field_mut(nodes: &mut [BodyNode], f: &mut impl FnMut(&mut ComponentField)) {
+    for node in nodes {
+        match node {
+            BodyNode::Element(el) => for_each_field_mut(&mut el.children, f),
+            BodyNode::Component(comp) => {
+                comp.fields.iter_mut().for_each(&mut *f);
+                for_each_field_mut(&mut comp.children, f);
+            }
+            BodyNode::ForLoop(for_loop) => for_each_field_mut(&mut for_loop.body, f),
+            BodyNode::WhileLoop(while_loop) => for_each_field_mut(&mut while_loop.body, f),
+            BodyNode::Fragment(fragment) => for_each_field_mut(&mut fragment.children, f),
+            BodyNode::IfChain(chain) => {
+                let mut chain = Some(chain);
+                while let Some(link) = chain {
+                    for_each_field_mut(&mut link.then_branch, f);
+                    if let Some(else_branch) = &mut link.else_branch {
+                        for_each_field_mut(else_branch, f);
+                    }
+                    chain = link.else_if_branch.as_deref_mut();
+                }
+            }
+            BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+        }
+    }
+}
+
+/// Call `f` with every loop iterator and `if` condition in the nodes and the ids of the hot literals in it
+fn for_each_expr_mut(nodes: &mut [BodyNode], f: &mut impl FnMut(&mut Expr, &mut HotLiteralIds)) {
+    for node in nodes {
+        match node {
+            BodyNode::Element(el) => for_each_expr_mut(&mut el.children, f),
+            BodyNode::Component(comp) => for_each_expr_mut(&mut comp.children, f),
+            BodyNode::ForLoop(for_loop) => {
+                f(&mut for_loop.expr, &mut for_loop.hot_literal_ids);
+                for_each_expr_mut(&mut for_loop.body, f);
+            }
+            BodyNode::WhileLoop(while_loop) => for_each_expr_mut(&mut while_loop.body, f),
+            BodyNode::Fragment(fragment) => for_each_expr_mut(&mut fragment.children, f),
+            BodyNode::IfChain(chain) => {
+                let mut chain = Some(chain);
+                while let Some(link) = chain {
+                    f(&mut link.cond, &mut link.hot_literal_ids);
+                    for_each_expr_mut(&mut link.then_branch, f);
+                    if let Some(else_branch) = &mut link.else_branch {
+                        for_each_expr_mut(else_branch, f);
+                    }
+                    chain = link.else_if_branch.as_deref_mut();
+                }
+            }
+            BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+        }
+    }
+}
+
+#[cfg(feature = "hot_reload")]
+pub(crate) use reload::*;
+
+#[cfg(feature = "hot_reload")]
+mod reload {
+    use super::*;
+    use dioxus_core::HotLiteral;
+    use syn::{LitBool, LitFloat, LitInt, LitStr};
+
+    /// The hot literals of an rsx call with their ids, in id order
+    pub(crate) fn hot_literals(roots: &[BodyNode]) -> Vec<(usize, HotLiteral)> {
+        let mut roots = roots.to_vec();
+        let mut literals = Vec::new();
+        for_each_field_mut(&mut roots, &mut |field| {
+            if let (Some(id), Some(value)) = (field.hot_literal_id, field.hot_literal()) {
+                literals.push((id, value));
+            }
+        });
+        for_each_expr_mut(&mut roots, &mut |expr, ids| {
+            for (expr, id) in hot_literal_exprs(expr).into_iter().zip(&ids.0) {
+                if let Some(value) = lit_of(expr).and_then(hot_literal_value) {
+                    literals.push((*id, value));
+                }
+            }
+        });
+        literals
+    }
+
+    /// A copy of the node with every hot literal replaced by a placeholder of the same type.
+    ///
+    /// Nodes that are the same without their hot literals only differ in values the hot reloader can send to the app.
+    pub(crate) fn without_hot_literals(node: &BodyNode) -> BodyNode {
+        let mut node = node.clone();
+        for_each_field_mut(std::slice::from_mut(&mut node), &mut |field| {
+            if field.hot_literal_id.is_none() {
+                return;
+            }
+            if let ContentField::ManExpr(Expr::Lit(expr)) = &mut field.content {
+                expr.lit = placeholder(&expr.lit);
+            }
+        });
+        for_each_expr_mut(std::slice::from_mut(&mut node), &mut |expr, _| {
+            for expr in hot_literal_exprs(expr) {
+                if let Expr::Lit(expr) = expr {
+                    expr.lit = placeholder(&expr.lit);
+                }
+            }
+        });
+        node
+    }
+
+    fn placeholder(lit: &Lit) -> Lit {
+        let span = lit.span();
+        match lit {
+            Lit::Str(_) => Lit::Str(LitStr::new("", span)),
+            Lit::Int(int) => Lit::Int(LitInt::new(&format!("0{}", int.suffix()), span)),
+            Lit::Float(float) => Lit::Float(LitFloat::new(&format!("0.0{}", float.suffix()), span)),
+            Lit::Bool(_) => Lit::Bool(LitBool::new(false, span)),
+            lit => lit.clone(),
+        }
+    }
+
+    fn lit_of(expr: &Expr) -> Option<&Lit> {
+        match expr {
+            Expr::Lit(expr) => Some(&expr.lit),
+            _ => None,
+        }
+    }
+
+    /// The value of a literal the hot reloader can send to the app
+    fn hot_literal_value(lit: &Lit) -> Option<HotLiteral> {
+        Some(match lit {
+            Lit::Str(lit) => HotLiteral::Str(intern(lit.value().as_str())),
+            Lit::Int(lit) => HotLiteral::Int(lit.base10_parse().ok()?),
+            Lit::Float(lit) => HotLiteral::Float(lit.base10_parse().ok()?),
+            Lit::Bool(lit) => HotLiteral::Bool(lit.value),
+            _ => return None,
+        })
+    }
+
+    /// The values of the hot literals in `new`, paired with the ids of the same literals in `old`.
+    ///
+    /// The nodes must be the same [`without_hot_literals`]. The ids in `old` are the ones the app was compiled with,
+    /// which can differ from the ids in `new` if dynamic nodes moved around.
+    pub(crate) fn hot_literal_changes(old: &BodyNode, new: &BodyNode) -> Vec<(usize, HotLiteral)> {
+        let old = hot_literals(std::slice::from_ref(old));
+        let new = hot_literals(std::slice::from_ref(new));
+        old.into_iter()
+            .zip(new)
+            .map(|((id, _), (_, value))| (id, value))
+            .collect()
+    }
+
+    impl ComponentField {
+        /// The value of the prop if it is a literal that can be hot reloaded
+        pub fn hot_literal(&self) -> Option<HotLiteral> {
+            hot_literal_value(self.literal()?)
+        }
+    }
+}
@@ -17,3 +17,17 @@ impl HotReloadingContext for Empty {
         None
     }
 }
+
+/// Element namespaces defined with `dioxus_core::define_elements!` hot reload like the html namespace
+impl<T: dioxus_core::ElementNamespace> HotReloadingContext for T {
+    fn map_attribute(
+        element_name_rust: &str,
+        attribute_name_rust: &str,
+    ) -> Option<(&'static str, Option<&'static str>)> {
+        T::map_attribute(element_name_rust, attribute_name_rust)
+    }
+
+    fn map_element(element_name_rust: &str) -> Option<(&'static str, Option<&'static str>)> {
+        T::map_element(element_name_rust)
+    }
+}
@@ -2,14 +2,15 @@ use super::{
     hot_reload_diff::{diff_rsx, DiffResult},
     ChangedRsx,
 };
-use crate::{CallBody, HotReloadingContext};
+use crate::{included_template_location, CallBody, HotReloadingContext};
 use dioxus_core::{
     prelude::{TemplateAttribute, TemplateNode},
-    Template,
+    HotLiteral, HotLiterals, Template,
 };
 use krates::cm::MetadataCommand;
 use krates::Cmd;
-pub use proc_macro2::TokenStream;
+pub use proc_macro2::{LineColumn, TokenStream};
+use quote::ToTokens;
 pub use std::collections::HashMap;
 pub use std::sync::Mutex;
 pub use std::time::SystemTime;
@@ -19,9 +20,25 @@ pub use std::{fs::File, io::Read};
 use syn::spanned::Spanned;
 
 pub enum UpdateResult {
-    UpdatedRsx(Vec<Template>),
+    UpdatedRsx {
+        templates: Vec<Template>,
+        /// New values for hot reloadable literals. Send them with `VirtualDom::update_hot_literals`
+        literals: Vec<HotLiterals>,
+    },
+
+    /// The change can't be hot reloaded, so the app needs to be rebuilt
+    NeedsRebuild {
+        /// Why the change can't be hot reloaded
+        reason: &'static str,
+        /// Where the change is in the new version of the file, if it is known
+        location: Option<LineColumn>,
+    },
+}
 
-    NeedsRebuild,
+impl UpdateResult {
+    fn needs_rebuild(reason: &'static str, location: Option<LineColumn>) -> Self {
+        Self::NeedsRebuild { reason, location }
+    }
 }
 
 /// The result of building a FileMap
@@ -48,9 +65,23 @@ pub struct CachedSynFile {
     pub raw: String,
     pub path: PathBuf,
     pub templates: HashMap<&'static str, Template>,
+    /// The last hot literals sent for each rsx call in this file, keyed by location
+    pub literals: HashMap<String, HotLiterals>,
     pub tracked_assets: HashSet<PathBuf>,
 }
 
+impl CachedSynFile {
+    /// Check if templates or literals were already sent for the rsx call a template name belongs to
+    fn was_hot_reloaded(&self, template_name: &str) -> bool {
+        fn location(name: &str) -> &str {
+            name.rsplit_once(':').map_or(name, |(location, _)| location)
+        }
+
+        let call = location(template_name);
+        self.literals.contains_key(call) || self.templates.keys().any(|name| location(name) == call)
+    }
+}
+
 impl<Ctx: HotReloadingContext> FileMap<Ctx> {
     /// Create a new FileMap from a crate directory
     ///
@@ -101,8 +132,13 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
         let mut src = String::new();
         file.read_to_string(&mut src)?;
 
+        // External templates pulled in with rsx_include! are a bare rsx body instead of a rust file
+        if file_path.extension() == Some(OsStr::new("rsx")) {
+            return self.update_included_rsx(file_path, crate_dir, src);
+        }
+
         // If we can't parse the contents we want to pass it off to the build system to tell the user that there's a syntax error
-        let syntax = syn::parse_file(&src).map_err(|_err| HotreloadError::Parse)?;
+        let syntax = syn::parse_file(&src).map_err(HotreloadError::Parse)?;
 
         let in_workspace = self.child_in_workspace(crate_dir)?;
 
@@ -119,13 +155,13 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
             // merge the new map into the old map
             self.map.extend(map.map);
 
-            return Ok(UpdateResult::NeedsRebuild);
+            return Ok(UpdateResult::needs_rebuild("a new file was added", None));
         };
 
         // If the cached file is not a valid rsx file, rebuild the project, forcing errors
         // TODO: in theory the error is simply in the RsxCallbody. We could attempt to parse it using partial expansion
         // And collect out its errors instead of giving up to a full rebuild
-        let old = syn::parse_file(&old_cached.raw).map_err(|_e| HotreloadError::Parse)?;
+        let old = syn::parse_file(&old_cached.raw).map_err(HotreloadError::Parse)?;
 
         let instances = match diff_rsx(&syntax, &old) {
             // If the changes were just some rsx, we can just update the template
@@ -142,26 +178,30 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
                     raw: src.clone(),
                     path: file_path.to_path_buf(),
                     templates: HashMap::new(),
+                    literals: HashMap::new(),
                     tracked_assets: HashSet::new(),
                 };
 
                 self.map.insert(file_path.to_path_buf(), cached_file);
-                return Ok(UpdateResult::NeedsRebuild);
+                return Ok(UpdateResult::needs_rebuild(
+                    "code outside of rsx changed",
+                    first_changed_item(&syntax, &old),
+                ));
             }
         };
 
         let mut messages: Vec<Template> = Vec::new();
+        let mut literals = Vec::new();
 
         for calls in instances.into_iter() {
             let ChangedRsx { old, new } = calls;
 
             let old_start = old.span().start();
-
-            let old_parsed = syn::parse2::<CallBody>(old.tokens);
-            let new_parsed = syn::parse2::<CallBody>(new);
-            let (Ok(old_call_body), Ok(new_call_body)) = (old_parsed, new_parsed) else {
-                continue;
-            };
+            let new_start = new
+                .clone()
+                .into_iter()
+                .next()
+                .map(|token| token.span().start());
 
             // if the file!() macro is invoked in a workspace, the path is relative to the workspace root, otherwise it's relative to the crate root
             // we need to check if the file is in a workspace or not and strip the prefix accordingly
@@ -174,21 +214,46 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
                 continue;
             };
 
+            // Every rsx call in the file is diffed, but only the ones that changed need new templates. A call that is
+            // the same as the compiled source is skipped unless an earlier change to it was sent and has to be reverted
+            let location = template_location(old_start, file);
+            if old.tokens.to_string() == new.to_string() && !old_cached.was_hot_reloaded(&location)
+            {
+                continue;
+            }
+
+            let old_parsed = syn::parse2::<CallBody>(old.tokens);
+            let new_parsed = syn::parse2::<CallBody>(new);
+            let (Ok(old_call_body), Ok(new_call_body)) = (old_parsed, new_parsed) else {
+                continue;
+            };
+
             // We leak the template since templates are a compiletime value
             // This is not ideal, but also not a huge deal for hot reloading
             // TODO: we could consider arena allocating the templates and dropping them when the connection is closed
-            let leaked_location = Box::leak(template_location(old_start, file).into_boxed_str());
+            let leaked_location = Box::leak(location.into_boxed_str());
 
             // Retuns Some(template) if the template is hotreloadable
             // dynamic changes are not hot reloadable and force a rebuild
-            let hotreloadable_template =
-                new_call_body.update_template::<Ctx>(Some(old_call_body), leaked_location);
+            let compiled_literals = old_call_body.hot_literals();
+            let hotreloadable_template = new_call_body
+                .update_template_and_literals::<Ctx>(Some(old_call_body), leaked_location);
 
             // if the template is not hotreloadable, we need to do a full rebuild
-            let Some(template) = hotreloadable_template else {
-                return Ok(UpdateResult::NeedsRebuild);
+            let Some((template, values)) = hotreloadable_template else {
+                return Ok(UpdateResult::needs_rebuild(
+                    "the structure of an rsx call changed",
+                    new_start,
+                ));
             };
 
+            literals.extend(Self::changed_literals(
+                old_cached,
+                leaked_location,
+                compiled_literals,
+                values,
+            ));
+
             // dioxus cannot handle empty templates...
             // todo: I think it can? or we just skip them nowa
             if template.roots.is_empty() {
@@ -197,7 +262,7 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
 
             // if the template is the same, don't send it
             if let Some(old_template) = old_cached.templates.get(template.name) {
-                if old_template == &template {
+                if old_template.content_hash() == template.content_hash() {
                     continue;
                 }
             };
@@ -213,7 +278,102 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
             messages.push(template);
         }
 
-        Ok(UpdateResult::UpdatedRsx(messages))
+        Ok(UpdateResult::UpdatedRsx {
+            templates: messages,
+            literals,
+        })
+    }
+
+    /// Try to update the template in an rsx file pulled in with `rsx_include!`
+    fn update_included_rsx(
+        &mut self,
+        file_path: &Path,
+        crate_dir: &Path,
+        src: String,
+    ) -> Result<UpdateResult, HotreloadError> {
+        let Some(old_cached) = self.map.get_mut(file_path) else {
+            return Ok(UpdateResult::needs_rebuild("a new file was added", None));
+        };
+
+        let old_call_body =
+            syn::parse_str::<CallBody>(&old_cached.raw).map_err(HotreloadError::Parse)?;
+        let new_call_body = syn::parse_str::<CallBody>(&src).map_err(HotreloadError::Parse)?;
+
+        // rsx_include! resolves paths from the crate root, so the template names use the same relative path
+        let Ok(file) = file_path.strip_prefix(crate_dir) else {
+            return Ok(UpdateResult::needs_rebuild(
+                "the file is outside of the crate",
+                None,
+            ));
+        };
+        let file = file
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let leaked_location = Box::leak(included_template_location(&file, 0).into_boxed_str());
+
+        let compiled_literals = old_call_body.hot_literals();
+        let Some((template, values)) =
+            new_call_body.update_template_and_literals::<Ctx>(Some(old_call_body), leaked_location)
+        else {
+            return Ok(UpdateResult::needs_rebuild(
+                "the structure of the rsx changed",
+                None,
+            ));
+        };
+
+        let literals =
+            Self::changed_literals(old_cached, leaked_location, compiled_literals, values)
+                .into_iter()
+                .collect();
+
+        if template.roots.is_empty() || old_cached.templates.get(template.name) == Some(&template) {
+            return Ok(UpdateResult::UpdatedRsx {
+                templates: vec![],
+                literals,
+            });
+        }
+
+        old_cached.templates.insert(template.name, template);
+        old_cached
+            .tracked_assets
+            .extend(Self::populate_assets(template));
+
+        Ok(UpdateResult::UpdatedRsx {
+            templates: vec![template],
+            literals,
+        })
+    }
+
+    /// Get the literal component props to send for an rsx call, if they differ from the values the app has now
+    fn changed_literals(
+        cached: &mut CachedSynFile,
+        template_name: &str,
+        compiled: Vec<(usize, HotLiteral)>,
+        values: Vec<(usize, HotLiteral)>,
+    ) -> Option<HotLiterals> {
+        // Every template in an rsx call shares the location before the id
+        let location = template_name
+            .rsplit_once(':')
+            .map_or(template_name, |(location, _)| location);
+
+        let current = cached
+            .literals
+            .get(location)
+            .map_or(&compiled, |literals| &literals.values);
+        if *current == values {
+            return None;
+        }
+
+        let literals = HotLiterals {
+            location: location.to_string(),
+            values,
+        };
+        cached
+            .literals
+            .insert(literals.location.clone(), literals.clone());
+        Some(literals)
     }
 
     fn populate_assets(template: Template) -> HashSet<PathBuf> {
@@ -262,6 +422,7 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
                     path,
                     tracked_assets,
                     templates: HashMap::from([(template.name, template)]),
+                    literals: HashMap::new(),
                 },
             );
         }
@@ -280,6 +441,19 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
             .find(|file| file.tracked_assets.contains(path))
     }
 
+    /// Check if any template links to an asset with the same file name as `path`
+    ///
+    /// Templates link assets by the url they are served from, which rarely matches where the file lives on disk
+    pub fn tracks_asset_file(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name() else {
+            return false;
+        };
+        self.map
+            .values()
+            .flat_map(|file| file.tracked_assets.iter())
+            .any(|asset| asset.file_name() == Some(name))
+    }
+
     fn child_in_workspace(&mut self, crate_dir: &Path) -> io::Result<Option<PathBuf>> {
         if let Some(in_workspace) = self.in_workspace.get(crate_dir) {
             return Ok(in_workspace.clone());
@@ -343,7 +517,10 @@ fn find_rs_files(root: PathBuf, filter: &mut impl FnMut(&Path) -> bool) -> FileM
                 files.extend(map);
             }
         }
-    } else if root.extension().and_then(|s| s.to_str()) == Some("rs") {
+    } else if matches!(
+        root.extension().and_then(|s| s.to_str()),
+        Some("rs" | "rsx")
+    ) {
         if let Ok(mut file) = File::open(root.clone()) {
             let mut src = String::new();
             match file.read_to_string(&mut src) {
@@ -352,6 +529,7 @@ fn find_rs_files(root: PathBuf, filter: &mut impl FnMut(&Path) -> bool) -> FileM
                         raw: src.clone(),
                         path: root.clone(),
                         templates: HashMap::new(),
+                        literals: HashMap::new(),
                         tracked_assets: HashSet::new(),
                     };
 
@@ -368,10 +546,23 @@ fn find_rs_files(root: PathBuf, filter: &mut impl FnMut(&Path) -> bool) -> FileM
     FileMapSearchResult { map: files, errors }
 }
 
+/// Find where the first item that differs between two versions of a file starts in the new version
+fn first_changed_item(new: &syn::File, old: &syn::File) -> Option<LineColumn> {
+    let changed = new
+        .items
+        .iter()
+        .zip(old.items.iter().map(Some).chain(std::iter::repeat(None)))
+        .find(|(new, old)| {
+            old.map(|old| old.to_token_stream().to_string())
+                != Some(new.to_token_stream().to_string())
+        });
+    changed.map(|(item, _)| item.span().start())
+}
+
 #[derive(Debug)]
 pub enum HotreloadError {
     Failure(io::Error),
-    Parse,
+    Parse(syn::Error),
     NoPreviousBuild,
 }
 
@@ -379,7 +570,7 @@ impl std::fmt::Display for HotreloadError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Failure(err) => write!(f, "Failed to parse file: {}", err),
-            Self::Parse => write!(f, "Failed to parse file"),
+            Self::Parse(err) => write!(f, "Failed to parse file: {}", err),
             Self::NoPreviousBuild => write!(f, "No previous build found"),
         }
     }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::html_entities::NAMED_ENTITIES;
+
 use proc_macro2::{Span, TokenStream};
 
 use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
@@ -68,6 +70,51 @@ impl IfmtInput {
             .iter()
             .all(|seg| matches!(seg, Segment::Literal(_)))
     }
+
+    /// Decode the HTML character references (`&nbsp;`, `&#169;`, `&#x1F600;`) in the literal segments of this input
+    ///
+    /// Rust escapes like `\u{a0}` are already handled by the string literal itself. The source literal is left
+    /// untouched so tools like autofmt can still write out exactly what the user typed.
+    ///
+    /// Every named reference in the HTML spec is supported. Unknown named references (eg `&nbps;`) are left as is,
+    /// the same way a browser renders them, and so is an `&` that isn't followed by a `name;` or `#number;` sequence.
+    pub fn decode_html_entities(&mut self) -> Result<()> {
+        let span = match &self.source {
+            Some(source) => source.span(),
+            None => Span::call_site(),
+        };
+
+        for segment in self.segments.iter_mut() {
+            if let Segment::Literal(literal) = segment {
+                if literal.contains('&') {
+                    *literal = decode_html_entities(literal, span, &mut |_| {})?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The named character references in the source literal that aren't in the HTML spec, like `&nbps;`
+    pub fn unknown_html_entities(&self) -> Vec<String> {
+        let mut unknown = Vec::new();
+        let Some(source) = &self.source else {
+            return unknown;
+        };
+        let Ok(written) = IfmtInput::from_str(&source.value()) else {
+            return unknown;
+        };
+
+        for segment in &written.segments {
+            if let Segment::Literal(literal) = segment {
+                let _ = decode_html_entities(literal, source.span(), &mut |reference| {
+                    unknown.push(reference.to_string())
+                });
+            }
+        }
+
+        unknown
+    }
 }
 
 impl IfmtInput {
@@ -92,6 +139,15 @@ impl IfmtInput {
         })
     }
 
+    /// Check if the source literal would still parse into the segments we have
+    fn source_matches_segments(&self) -> bool {
+        match &self.source {
+            Some(source) => IfmtInput::from_str(&source.value())
+                .is_ok_and(|from_source| from_source.segments == self.segments),
+            None => false,
+        }
+    }
+
     /// Try to convert this into a single _.to_string() call if possible
     ///
     /// Using "{single_expression}" is pretty common, but you don't need to go through the whole format! machinery for that, so we optimize it here.
@@ -211,7 +267,8 @@ impl ToTokens for IfmtInput {
         }
 
         // If the segments are not complex exprs, we can just use format! directly to take advantage of RA rename/expansion
-        if self.is_simple_expr() {
+        // This is only valid if the source literal still matches the segments (eg no html entities were decoded)
+        if self.is_simple_expr() && self.source_matches_segments() {
             let raw = &self.source;
             tokens.extend(quote! {
                 ::std::format_args!(#raw)
@@ -265,6 +322,70 @@ impl ToTokens for IfmtInput {
     }
 }
 
+/// Decode every character reference in a literal piece of text. Unknown named references are passed to `on_unknown`
+fn decode_html_entities(
+    literal: &str,
+    span: Span,
+    on_unknown: &mut dyn FnMut(&str),
+) -> Result<String> {
+    let mut decoded = String::with_capacity(literal.len());
+    let mut rest = literal;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // Find the body of the reference between `&` and `;`
+        let end = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+            .map(|idx| idx + 1)
+            .unwrap_or(rest.len());
+        let body = &rest[1..end];
+
+        if body.is_empty() || !rest[end..].starts_with(';') {
+            // Not a character reference, just a plain ampersand
+            decoded.push('&');
+            rest = &rest[1..];
+            continue;
+        }
+
+        let reference = &rest[..=end];
+        if let Some(number) = body.strip_prefix('#') {
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16),
+                None => number.parse::<u32>(),
+            };
+            let c = code.ok().and_then(char::from_u32).ok_or_else(|| {
+                Error::new(
+                    span,
+                    format!("`{reference}` is not a valid numeric character reference"),
+                )
+            })?;
+            decoded.push(c);
+        } else if let Some(entity) = named_html_entity(body) {
+            decoded.push_str(entity);
+        } else {
+            // Browsers render unknown named references as written
+            on_unknown(reference);
+            decoded.push_str(reference);
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    decoded.push_str(rest);
+
+    Ok(decoded)
+}
+
+/// Look up a named character reference from the HTML spec by the name between `&` and `;`
+fn named_html_entity(name: &str) -> Option<&'static str> {
+    NAMED_ENTITIES
+        .binary_search_by_key(&name, |(name, _)| name)
+        .ok()
+        .map(|idx| NAMED_ENTITIES[idx].1)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Segment {
     Literal(String),
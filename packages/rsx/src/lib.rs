@@ -53,8 +53,12 @@
 mod errors;
 mod attribute;
 mod component;
+mod diagnostics;
 mod element;
+mod hot_literals;
+mod html_entities;
 mod ifmt;
+mod lints;
 mod location;
 mod node;
 mod util;
@@ -67,9 +71,12 @@ mod sub_templates;
 pub use attribute::*;
 pub use component::*;
 pub use context::DynamicContext;
+pub use diagnostics::*;
 pub use element::*;
+pub use hot_literals::HotLiteralIds;
 pub use ifmt::*;
 pub use node::*;
+pub use renderer::included_template_location;
 
 #[cfg(feature = "hot_reload")]
 pub mod hot_reload;
@@ -99,10 +106,26 @@ use syn::{
 /// To generate the code used to render the template, use the ToTokens impl on the Callbody, or with the `render_with_location` method.
 #[derive(Default, Debug)]
 pub struct CallBody {
+    /// Inner attributes that configure this call, like `#![allow(key_outside_loop)]` or `#![hashed_templates]`
+    pub attrs: Vec<syn::Attribute>,
     pub roots: Vec<BodyNode>,
 }
 
 impl CallBody {
+    /// Create a body from its nodes
+    pub fn new(roots: Vec<BodyNode>) -> Self {
+        Self {
+            attrs: Vec::new(),
+            roots,
+        }
+    }
+
+    /// Check if the call names its templates after their structure with `#![hashed_templates]`, so identical rsx at
+    /// other call sites shares the same template. Hot reloading can't find templates named this way.
+    pub fn hashed_templates(&self) -> bool {
+        self.attrs.iter().any(is_hashed_templates_attribute)
+    }
+
     /// Render the template with a manually set file location. This should be used when multiple rsx! calls are used in the same macro
     pub fn render_with_location(&self, location: String) -> TokenStream2 {
         // Empty templates just are placeholders for "none"
@@ -110,9 +133,22 @@ impl CallBody {
             return quote! { None };
         }
 
-        let body = TemplateRenderer::as_tokens(&self.roots, Some(location));
+        let body = renderer::with_hashed_templates(self.hashed_templates(), || {
+            TemplateRenderer::as_tokens(&self.roots, Some(location))
+        });
+        let lints = self.lint_tokens();
 
-        quote! { { #body } }
+        quote! { { #lints #body } }
+    }
+
+    /// Render the contents of an external rsx file pulled in with `rsx_include!`
+    ///
+    /// The `path` is relative to the crate root. Every template in the file gets a stable name based on that path so
+    /// the hot reloader can find it again when the file changes.
+    pub fn render_included(&self, path: &str) -> TokenStream2 {
+        renderer::with_included_file(path, || {
+            self.render_with_location(included_template_location(path, 0))
+        })
     }
 
     /// This will try to create a new template from the current body and the previous body. This will return None if the
@@ -147,11 +183,31 @@ impl CallBody {
         old: Option<CallBody>,
         location: &'static str,
     ) -> Option<Template> {
+        self.update_template_and_literals::<Ctx>(old, location)
+            .map(|(template, _)| template)
+    }
+
+    /// Like [`CallBody::update_template`], but also returns the values of the literal component props in this body.
+    ///
+    /// Components that only differ from the old body in literal props like `Link { to: "/home" }` can still be hot
+    /// reloaded. The values are paired with the ids the literals have in the old body, which the app was compiled with,
+    /// and sorted by id. Send them to the app with `VirtualDom::update_hot_literals`.
+    #[cfg(feature = "hot_reload")]
+    pub fn update_template_and_literals<Ctx: HotReloadingContext>(
+        &self,
+        old: Option<CallBody>,
+        location: &'static str,
+    ) -> Option<(Template, Vec<(usize, dioxus_core::HotLiteral)>)> {
         // Create a context that will be used to update the template
         let mut context = DynamicContext::new_with_old(old);
 
         // Force the template node to generate us TemplateNodes, and fill in the location information
-        let roots = context.populate_by_updating::<Ctx>(&self.roots)?;
+        let roots = context.populate_by_updating::<Ctx>(flatten_root_fragment(&self.roots))?;
+
+        // Static nodes may have moved the dynamic parts around, but every one of them still needs a path
+        if !context.placed_all_tracked() {
+            return None;
+        }
 
         // We've received the dioxus-core TemplateNodess, and need to assemble them into a Template
         // We could just use them directly, but we want to intern them to do our best to avoid
@@ -159,7 +215,7 @@ impl CallBody {
         // the `Intern` crate and not just the fact that we call Box::leak.
         //
         // We should also note that order of these nodes could be all scrambeled
-        Some(Template {
+        let template = Template {
             name: location,
             roots: intern(roots.as_slice()),
             node_paths: intern(
@@ -178,7 +234,44 @@ impl CallBody {
                     .collect::<Vec<_>>()
                     .as_slice(),
             ),
-        })
+        };
+
+        let mut literals = context.hot_literals;
+        literals.sort_by_key(|(id, _)| *id);
+
+        Some((template, literals))
+    }
+
+    /// The literal component props in this body that can be hot reloaded, paired with their ids and sorted by id
+    #[cfg(feature = "hot_reload")]
+    pub fn hot_literals(&self) -> Vec<(usize, dioxus_core::HotLiteral)> {
+        hot_literals::hot_literals(&self.roots)
+    }
+
+    /// Warnings for the rsx. Lints are opt-in with the `lints` feature.
+    #[cfg(feature = "lints")]
+    fn lint_tokens(&self) -> TokenStream2 {
+        let levels = match LintLevels::from_manifest() {
+            Ok(levels) => levels,
+            Err(err) => return quote! { ::core::compile_error!(#err); },
+        };
+        let diagnostics = self.lint_with(levels);
+
+        if std::env::var(DIAGNOSTICS_FORMAT_ENV).as_deref() == Ok("json") {
+            for diagnostic in diagnostics.iter() {
+                eprintln!("{}", diagnostic.to_json());
+            }
+        }
+
+        diagnostics.into_token_stream()
+    }
+
+    /// Warnings for the rsx. Lints are opt-in with the `lints` feature, except for the ones that are always checked.
+    #[cfg(not(feature = "lints"))]
+    fn lint_tokens(&self) -> TokenStream2 {
+        let mut diagnostics = self.lint();
+        diagnostics.retain(|diagnostic| diagnostic.lint.always_checked());
+        diagnostics.into_token_stream()
     }
 
     /// Parse a stream into a CallBody. Return all error immediately instead of trying to partially expand the macro
@@ -189,19 +282,34 @@ impl CallBody {
     }
 
     fn parse_with_options(input: ParseStream, partial_completions: bool) -> Result<Self> {
-        let mut roots = Vec::new();
+        let attrs = input.call(syn::Attribute::parse_inner)?;
+        for attr in attrs
+            .iter()
+            .filter(|attr| is_hashed_templates_attribute(attr))
+        {
+            attr.meta.require_path_only()?;
+        }
+        LintLevels::from_attributes(&attrs)?;
+
+        let mut roots = attribute::with_condition_bindings(|| {
+            let mut roots = Vec::new();
+
+            while !input.is_empty() {
+                let node = BodyNode::parse_with_options(input, partial_completions)?;
 
-        while !input.is_empty() {
-            let node = BodyNode::parse_with_options(input, partial_completions)?;
+                if input.peek(Token![,]) {
+                    let _ = input.parse::<Token![,]>();
+                }
 
-            if input.peek(Token![,]) {
-                let _ = input.parse::<Token![,]>();
+                roots.push(node);
             }
 
-            roots.push(node);
-        }
+            Ok::<_, syn::Error>(roots)
+        })?;
+
+        hot_literals::assign_hot_literal_ids(&mut roots);
 
-        Ok(CallBody { roots })
+        Ok(CallBody { attrs, roots })
     }
 }
 
@@ -217,13 +325,21 @@ impl ToTokens for CallBody {
         match self.roots.is_empty() {
             true => out_tokens.append_all(quote! { None }),
             false => {
-                let body = TemplateRenderer::as_tokens(&self.roots, None);
-                out_tokens.append_all(quote! { { #body } })
+                let body = renderer::with_hashed_templates(self.hashed_templates(), || {
+                    TemplateRenderer::as_tokens(&self.roots, None)
+                });
+                let lints = self.lint_tokens();
+                out_tokens.append_all(quote! { { #lints #body } })
             }
         }
     }
 }
 
+/// Check if an inner attribute of an rsx call is `#![hashed_templates]`
+pub(crate) fn is_hashed_templates_attribute(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("hashed_templates")
+}
+
 #[cfg(feature = "hot_reload")]
 // interns a object into a static object, resusing the value if it already exists
 pub(crate) fn intern<T: Eq + Hash + Send + Sync + ?Sized + 'static>(
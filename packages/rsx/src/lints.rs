@@ -0,0 +1,164 @@
+//! Lints for rsx that compiles but silently misbehaves at runtime
+
+use super::*;
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+impl CallBody {
+    /// Check the rsx for mistakes that compile but probably don't do what the author meant
+    ///
+    /// Lints are reported at their default level unless the call configures them with attributes.
+    pub fn lint(&self) -> Diagnostics {
+        self.lint_with(LintLevels::default())
+    }
+
+    /// Check the rsx with the levels configured for the crate. Attributes on the call override them.
+    pub fn lint_with(&self, mut levels: LintLevels) -> Diagnostics {
+        // The attributes were checked when the call was parsed
+        let _ = levels.extend_from_attributes(&self.attrs);
+
+        let mut linter = Linter::default();
+        // The roots of an rsx call may be the items of a list built with an iterator, so they can have keys
+        linter.visit_nodes(&self.roots, true);
+        linter.diagnostics.with_levels(&levels)
+    }
+}
+
+#[derive(Default)]
+struct Linter {
+    diagnostics: Diagnostics,
+    // The event listeners of the elements above the current node
+    listeners: Vec<(String, String)>,
+}
+
+impl Linter {
+    fn visit_nodes(&mut self, nodes: &[BodyNode], in_list: bool) {
+        for node in nodes {
+            self.visit_node(node, in_list);
+        }
+    }
+
+    fn visit_node(&mut self, node: &BodyNode, in_list: bool) {
+        match node {
+            BodyNode::Element(el) => {
+                self.check_key(el.key.as_ref(), in_list);
+
+                let parent_listeners = self.listeners.len();
+                for attr in &el.merged_attributes {
+                    let AttributeType::Named(ElementAttrNamed { attr, .. }) = attr else {
+                        continue;
+                    };
+                    let (ElementAttrName::BuiltIn(name), Some(handler)) =
+                        (&attr.name, attr.event_handler())
+                    else {
+                        continue;
+                    };
+
+                    let listener = (name.to_string(), handler.to_token_stream().to_string());
+                    if self.listeners[..parent_listeners].contains(&listener) {
+                        self.diagnostics.push(
+                            Lint::DuplicateNestedListener,
+                            name.span(),
+                            format!("A parent element has the same `{name}` listener. Events bubble up to parent elements, so this listener will run twice."),
+                        );
+                    }
+                    self.listeners.push(listener);
+                }
+
+                self.visit_nodes(&el.children, false);
+                self.listeners.truncate(parent_listeners);
+            }
+            BodyNode::Component(component) => {
+                self.check_key(component.key(), in_list);
+
+                if component.manual_props.is_some() {
+                    if let Some(child) = component.children.first() {
+                        self.diagnostics.push(
+                            Lint::ChildrenAfterSpread,
+                            child.span(),
+                            "Children are ignored when the props of a component are spread. Set the `children` field of the spread props instead.",
+                        );
+                    }
+                }
+
+                // We don't know where the component renders its children, so listeners above it don't count
+                let listeners = std::mem::take(&mut self.listeners);
+                self.visit_nodes(&component.children, false);
+                self.listeners = listeners;
+            }
+            BodyNode::ForLoop(for_loop) => {
+                // A key on the loop keys the whole body, so the roots of the body aren't list items themselves
+                if for_loop.key.is_none() {
+                    self.check_loop_keys(&for_loop.body, for_loop.for_token.span());
+                }
+                self.visit_nodes(&for_loop.body, for_loop.key.is_none());
+            }
+            BodyNode::WhileLoop(while_loop) => {
+                self.check_loop_keys(&while_loop.body, while_loop.while_token.span());
+                self.visit_nodes(&while_loop.body, true);
+            }
+            BodyNode::IfChain(chain) => {
+                let mut chain = Some(chain);
+                while let Some(IfChain {
+                    then_branch,
+                    else_if_branch,
+                    else_branch,
+                    ..
+                }) = chain
+                {
+                    self.visit_nodes(then_branch, in_list);
+                    if let Some(else_branch) = else_branch {
+                        self.visit_nodes(else_branch, in_list);
+                    }
+                    chain = else_if_branch.as_deref();
+                }
+            }
+            BodyNode::Fragment(fragment) => {
+                self.check_key(fragment.key.as_ref(), in_list);
+                self.visit_nodes(&fragment.children, false);
+            }
+            BodyNode::Text(text) => {
+                let span = text
+                    .source
+                    .as_ref()
+                    .map_or_else(Span::call_site, |source| source.span());
+                for entity in text.unknown_html_entities() {
+                    self.diagnostics.push(
+                        Lint::UnknownHtmlEntity,
+                        span,
+                        format!("`{entity}` is not a named character reference in the HTML spec, so it is rendered as written."),
+                    );
+                }
+            }
+            BodyNode::RawExpr(_) => {}
+        }
+    }
+
+    fn check_loop_keys(&mut self, body: &[BodyNode], span: Span) {
+        let first_key = match body.first() {
+            Some(BodyNode::Element(el)) => Some(el.key.is_some()),
+            Some(BodyNode::Component(component)) => Some(component.key().is_some()),
+            Some(BodyNode::Fragment(fragment)) => Some(fragment.key.is_some()),
+            _ => None,
+        };
+        if first_key == Some(false) {
+            self.diagnostics.push(
+                Lint::ForLoopWithoutKey,
+                span,
+                "The items of this loop don't have keys. Add a `key` to the first node in the loop so items keep their state when the list changes.",
+            );
+        }
+    }
+
+    fn check_key(&mut self, key: Option<&IfmtInput>, in_list: bool) {
+        if let (Some(key), false) = (key, in_list) {
+            self.diagnostics.push(
+                Lint::KeyOutsideLoop,
+                key.span(),
+                "This key has no effect. Keys only matter for the first node in the body of a `for` or `while` loop or in rsx that is returned from an iterator.",
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use self::location::CallerLocation;
+use crate::hot_literals::read_hot_literals;
 
 use super::*;
 
@@ -27,7 +28,9 @@ pub enum BodyNode {
     RawExpr(TokenStream2),
     Component(Component),
     ForLoop(ForLoop),
+    WhileLoop(WhileLoop),
     IfChain(IfChain),
+    Fragment(Fragment),
 }
 
 impl PartialEq for BodyNode {
@@ -38,7 +41,9 @@ impl PartialEq for BodyNode {
             (Self::RawExpr(l), Self::RawExpr(r)) => l.to_string() == r.to_string(),
             (Self::Component(l), Self::Component(r)) => l == r,
             (Self::ForLoop(l), Self::ForLoop(r)) => l == r,
+            (Self::WhileLoop(l), Self::WhileLoop(r)) => l == r,
             (Self::IfChain(l), Self::IfChain(r)) => l == r,
+            (Self::Fragment(l), Self::Fragment(r)) => l == r,
             _ => false,
         }
     }
@@ -54,7 +59,9 @@ impl Hash for BodyNode {
             Self::RawExpr(exp) => exp.to_string().hash(state),
             Self::Component(comp) => comp.hash(state),
             Self::ForLoop(for_loop) => for_loop.hash(state),
+            Self::WhileLoop(while_loop) => while_loop.hash(state),
             Self::IfChain(if_chain) => if_chain.hash(state),
+            Self::Fragment(fragment) => fragment.hash(state),
         }
     }
 }
@@ -71,7 +78,9 @@ impl BodyNode {
             BodyNode::Text(text) => text.source.span(),
             BodyNode::RawExpr(exp) => exp.span(),
             BodyNode::ForLoop(fl) => fl.for_token.span(),
+            BodyNode::WhileLoop(wl) => wl.while_token.span(),
             BodyNode::IfChain(f) => f.if_token.span(),
+            BodyNode::Fragment(f) => f.fragment_token.span(),
         }
     }
 
@@ -85,7 +94,14 @@ impl BodyNode {
         }
 
         if stream.peek(LitStr) {
-            return Ok(BodyNode::Text(stream.parse()?));
+            let mut text: IfmtInput = stream.parse()?;
+            text.decode_html_entities()?;
+            return Ok(BodyNode::Text(text));
+        }
+
+        // `fragment { key: "{id}", ... }` groups sibling nodes under one key
+        if Fragment::peek(stream) {
+            return Ok(BodyNode::Fragment(stream.parse()?));
         }
 
         // if this is a dash-separated path, it's a web component (custom element)
@@ -188,6 +204,19 @@ impl BodyNode {
             return Ok(BodyNode::ForLoop(stream.parse()?));
         }
 
+        // Transform while loops into a list of the nodes of each iteration
+        if stream.peek(Token![while]) {
+            return Ok(BodyNode::WhileLoop(stream.parse()?));
+        }
+
+        // There is no way to break out of a loop with only nodes in its body
+        if stream.peek(Token![loop]) {
+            return Err(syn::Error::new(
+                stream.span(),
+                "`loop` is not supported in rsx because its body can't `break`.\nUse a `while` or `while let` loop instead.",
+            ));
+        }
+
         // Transform unterminated if statements into terminated optional if statements
         if stream.peek(Token![if]) {
             return Ok(BodyNode::IfChain(stream.parse()?));
@@ -282,7 +311,21 @@ impl ToTokens for BodyNode {
 
             BodyNode::ForLoop(exp) => tokens.append_all(quote! { #exp }),
 
+            BodyNode::WhileLoop(exp) => tokens.append_all(quote! { #exp }),
+
             BodyNode::IfChain(chain) => tokens.append_all(quote! { #chain }),
+
+            // A fragment that isn't the only root of its template becomes a template of its own so it can keep its key
+            BodyNode::Fragment(_) => {
+                let renderer = TemplateRenderer::as_sub_template_tokens(std::slice::from_ref(self));
+                tokens.append_all(quote! {
+                    {
+                        #[allow(clippy::let_and_return)]
+                        let ___nodes = (#renderer).into_dyn_node();
+                        ___nodes
+                    }
+                })
+            }
         }
     }
 }
@@ -294,9 +337,13 @@ pub struct ForLoop {
     pub pat: Pat,
     pub in_token: Token![in],
     pub expr: Box<Expr>,
+    /// The key of each iteration, written at the start of the body. Every root of the body is diffed under it.
+    pub key: Option<IfmtInput>,
     pub body: Vec<BodyNode>,
     pub brace_token: token::Brace,
     pub location: CallerLocation,
+    /// The ids of the hot literals in `expr`
+    pub hot_literal_ids: HotLiteralIds,
 }
 
 impl Parse for ForLoop {
@@ -308,27 +355,103 @@ impl Parse for ForLoop {
         let in_token: Token![in] = input.parse()?;
         let expr: Expr = input.call(Expr::parse_without_eager_brace)?;
 
-        let (brace_token, body) = parse_buffer_as_braced_children(input)?;
+        let content;
+        let brace_token = braced!(content in input);
+        let key = parse_leading_key(
+            &content,
+            "the only field the body of a loop accepts is `key`",
+        )?;
+        let mut body = vec![];
+        while !content.is_empty() {
+            body.push(content.parse()?);
+        }
 
         Ok(Self {
             for_token,
             pat,
             in_token,
+            key,
             body,
             brace_token,
             location: CallerLocation::default(),
             expr: Box::new(expr),
+            hot_literal_ids: HotLiteralIds::default(),
         })
     }
 }
 
+impl ForLoop {
+    /// Whether each iteration of the loop is rendered with a key
+    pub fn is_keyed(&self) -> bool {
+        self.key.is_some() || TemplateRenderer::roots_key(&self.body).is_some()
+    }
+
+    /// This loop and the loops nested directly in it whose items are flattened into one list
+    ///
+    /// A loop whose only child is another loop yields one list per iteration. When the items of the inner loop don't
+    /// have keys, they are pushed into a single list instead. Keyed items keep their own list per iteration since keys
+    /// only need to be unique within it.
+    fn flattened_loops(&self) -> Vec<&ForLoop> {
+        let mut loops = vec![self];
+        while let Some(last) = loops.last() {
+            match last.body.as_slice() {
+                [BodyNode::ForLoop(inner)] if last.key.is_none() && !inner.is_keyed() => {
+                    loops.push(inner)
+                }
+                _ => break,
+            }
+        }
+        loops
+    }
+}
+
 impl ToTokens for ForLoop {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let loops = self.flattened_loops();
+        if let [_, .., inner] = loops.as_slice() {
+            let renderer = TemplateRenderer::as_sub_template_tokens(&inner.body);
+
+            // Nest the loops from the inside out, pushing every item of the innermost loop into the same list
+            let mut nested = quote! { ___nodes.push({ #renderer }); };
+            for for_loop in loops.iter().rev() {
+                let ForLoop {
+                    pat,
+                    expr,
+                    hot_literal_ids,
+                    ..
+                } = for_loop;
+                let expr = read_hot_literals(expr, hot_literal_ids);
+                nested = quote! {
+                    for #pat in #expr {
+                        #nested
+                    }
+                };
+            }
+
+            tokens.append_all(quote! {
+                {
+                    let mut ___nodes = Vec::new();
+                    #nested
+                    ___nodes.into_iter().into_dyn_node()
+                }
+            });
+            return;
+        }
+
         let ForLoop {
-            pat, expr, body, ..
+            pat,
+            expr,
+            key,
+            body,
+            hot_literal_ids,
+            ..
         } = self;
 
-        let renderer = TemplateRenderer::as_tokens(body, None);
+        let expr = read_hot_literals(expr, hot_literal_ids);
+        let renderer = match key {
+            Some(key) => TemplateRenderer::as_keyed_sub_template_tokens(body, key),
+            None => TemplateRenderer::as_sub_template_tokens(body),
+        };
 
         // Signals expose an issue with temporary lifetimes
         // We need to directly render out the nodes first to collapse their lifetime to <'a>
@@ -343,15 +466,126 @@ impl ToTokens for ForLoop {
     }
 }
 
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct WhileLoop {
+    pub while_token: Token![while],
+    pub cond: Box<Expr>,
+    pub body: Vec<BodyNode>,
+    pub brace_token: token::Brace,
+    pub location: CallerLocation,
+}
+
+impl Parse for WhileLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let while_token: Token![while] = input.parse()?;
+
+        // stolen from ExprWhile. This also parses `while let` conditions
+        let cond = Box::new(input.call(Expr::parse_without_eager_brace)?);
+
+        let (brace_token, body) = parse_buffer_as_braced_children(input)?;
+
+        Ok(Self {
+            while_token,
+            cond,
+            body,
+            brace_token,
+            location: CallerLocation::default(),
+        })
+    }
+}
+
+impl ToTokens for WhileLoop {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let WhileLoop {
+            while_token,
+            cond,
+            body,
+            ..
+        } = self;
+
+        let renderer = TemplateRenderer::as_sub_template_tokens(body);
+
+        // Each iteration renders the body once, and the nodes are collected into a list like a for loop
+        tokens.append_all(quote! {
+            {
+                let mut ___nodes = Vec::new();
+                #while_token #cond {
+                    ___nodes.push({ #renderer });
+                }
+                ___nodes.into_iter().into_dyn_node()
+            }
+        })
+    }
+}
+
+/// A keyed group of sibling nodes: `fragment { key: "{id}", td {} td {} }`
+///
+/// Loops can only key the first node in their body, so a fragment lets the body of a loop have several roots that
+/// are diffed together by one key.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Fragment {
+    pub fragment_token: Ident,
+    pub brace_token: token::Brace,
+    pub key: Option<IfmtInput>,
+    pub children: Vec<BodyNode>,
+}
+
+/// The nodes that make up a template. A fragment that is the only root is flattened into its children
+pub(crate) fn flatten_root_fragment(mut roots: &[BodyNode]) -> &[BodyNode] {
+    while let [BodyNode::Fragment(fragment)] = roots {
+        roots = &fragment.children;
+    }
+    roots
+}
+
+impl Fragment {
+    fn peek(stream: ParseStream) -> bool {
+        let fork = stream.fork();
+        fork.parse::<Ident>().is_ok_and(|ident| ident == "fragment") && fork.peek(token::Brace)
+    }
+}
+
+impl Parse for Fragment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fragment_token: Ident = input.parse()?;
+
+        let content;
+        let brace_token = braced!(content in input);
+
+        let key = parse_leading_key(
+            &content,
+            "fragments don't have attributes. The only field a fragment accepts is `key`",
+        )?;
+
+        let mut children = vec![];
+        while !content.is_empty() {
+            children.push(content.parse()?);
+        }
+
+        Ok(Self {
+            fragment_token,
+            brace_token,
+            key,
+            children,
+        })
+    }
+}
+
 #[non_exhaustive]
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub struct IfChain {
     pub if_token: Token![if],
     pub cond: Box<Expr>,
     pub then_branch: Vec<BodyNode>,
+    pub then_brace: Brace,
     pub else_if_branch: Option<Box<IfChain>>,
     pub else_branch: Option<Vec<BodyNode>>,
+    pub else_brace: Option<Brace>,
     pub location: CallerLocation,
+    /// The ids of the hot literals in `cond`
+    pub hot_literal_ids: HotLiteralIds,
 }
 
 impl Parse for IfChain {
@@ -361,9 +595,10 @@ impl Parse for IfChain {
         // stolen from ExprIf
         let cond = Box::new(input.call(Expr::parse_without_eager_brace)?);
 
-        let (_, then_branch) = parse_buffer_as_braced_children(input)?;
+        let (then_brace, then_branch) = parse_buffer_as_braced_children(input)?;
 
         let mut else_branch = None;
+        let mut else_brace = None;
         let mut else_if_branch = None;
 
         // if the next token is `else`, set the else branch as the next if chain
@@ -372,8 +607,9 @@ impl Parse for IfChain {
             if input.peek(Token![if]) {
                 else_if_branch = Some(Box::new(input.parse::<IfChain>()?));
             } else {
-                let (_, else_branch_nodes) = parse_buffer_as_braced_children(input)?;
+                let (brace, else_branch_nodes) = parse_buffer_as_braced_children(input)?;
                 else_branch = Some(else_branch_nodes);
+                else_brace = Some(brace);
             }
         }
 
@@ -381,9 +617,12 @@ impl Parse for IfChain {
             cond,
             if_token,
             then_branch,
+            then_brace,
             else_if_branch,
             else_branch,
+            else_brace,
             location: CallerLocation::default(),
+            hot_literal_ids: HotLiteralIds::default(),
         })
     }
 }
@@ -402,10 +641,12 @@ impl ToTokens for IfChain {
                 then_branch,
                 else_if_branch,
                 else_branch,
+                hot_literal_ids,
                 ..
             } = chain;
 
-            let renderer = TemplateRenderer::as_tokens(then_branch, None);
+            let cond = read_hot_literals(cond, hot_literal_ids);
+            let renderer = TemplateRenderer::as_sub_template_tokens(then_branch);
 
             body.append_all(quote! { #if_token #cond { {#renderer} } });
 
@@ -413,7 +654,7 @@ impl ToTokens for IfChain {
                 body.append_all(quote! { else });
                 elif = Some(next);
             } else if let Some(else_branch) = else_branch {
-                let renderer = TemplateRenderer::as_tokens(else_branch, None);
+                let renderer = TemplateRenderer::as_sub_template_tokens(else_branch);
                 body.append_all(quote! { else { {#renderer} } });
                 terminated = true;
                 break;
@@ -438,6 +679,29 @@ impl ToTokens for IfChain {
     }
 }
 
+/// Parse the `key: "{id}",` that the body of a fragment or a loop can start with
+fn parse_leading_key(content: ParseStream, not_key: &str) -> Result<Option<IfmtInput>> {
+    if !(content.peek(Ident) && content.peek2(Token![:]) && !content.peek3(Token![:])) {
+        return Ok(None);
+    }
+
+    let name: Ident = content.parse()?;
+    if name != "key" {
+        return Err(syn::Error::new(name.span(), not_key));
+    }
+    content.parse::<Token![:]>()?;
+
+    let key: IfmtInput = content.parse()?;
+    if key.is_static() {
+        invalid_key!(key);
+    }
+
+    if !content.is_empty() {
+        content.parse::<Token![,]>()?;
+    }
+    Ok(Some(key))
+}
+
 fn parse_buffer_as_braced_children(
     input: &syn::parse::ParseBuffer<'_>,
 ) -> Result<(Brace, Vec<BodyNode>)> {
@@ -1,10 +1,47 @@
 use crate::*;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    /// The external file being expanded by `rsx_include!`
+    static INCLUDED_FILE: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    /// If the rsx call being expanded names its templates after their structure with `#![hashed_templates]`
+    static HASHED_TEMPLATES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Get the name of a template in an rsx file pulled in with `rsx_include!`
+///
+/// External files don't have a call site, so templates follow the path:0:0:id format, where `path` is the file relative
+/// to the crate root. The root template is always id 0 and nested templates use the id of their contents.
+pub fn included_template_location(path: &str, id: usize) -> String {
+    format!("{path}:0:0:{id}")
+}
+
+/// Run `f` while rendering the templates of an rsx call, naming them after their structure if `hashed` is true
+pub(crate) fn with_hashed_templates<O>(hashed: bool, f: impl FnOnce() -> O) -> O {
+    let previous = HASHED_TEMPLATES.with(|cell| cell.replace(hashed));
+    let out = f();
+    HASHED_TEMPLATES.with(|cell| cell.set(previous));
+    out
+}
+
+/// Run `f` while rendering templates from an included file, giving every nested template a stable name in that file
+pub(crate) fn with_included_file<O>(path: &str, f: impl FnOnce() -> O) -> O {
+    let previous = INCLUDED_FILE.with(|file| file.replace(Some(path.to_string())));
+    let out = f();
+    INCLUDED_FILE.with(|file| *file.borrow_mut() = previous);
+    out
+}
 
 pub struct TemplateRenderer<'a> {
     pub roots: &'a [BodyNode],
     location: Option<String>,
+    // The key of a loop body that is written on the loop rather than on one of its roots
+    key: Option<&'a IfmtInput>,
+    // Sub templates are the bodies of loops, conditionals and component children inside another template
+    sub_template: bool,
 }
 
 impl<'a> TemplateRenderer<'a> {
@@ -12,10 +49,52 @@ impl<'a> TemplateRenderer<'a> {
     ///
     /// This will cascade location information down the tree if it already hasn't been set
     pub fn as_tokens(roots: &'a [BodyNode], location: Option<String>) -> TokenStream2 {
-        TemplateRenderer::render(Self { roots, location })
+        TemplateRenderer::render(Self {
+            roots,
+            location,
+            key: None,
+            sub_template: false,
+        })
+    }
+
+    /// Render the body of a loop, conditional or component children that lives inside another template
+    ///
+    /// Sub templates share the file:line:column of the rsx call they are in. They are told apart by the id of their
+    /// contents rather than their position, so adding or removing unrelated nodes in the call doesn't rename them.
+    pub fn as_sub_template_tokens(roots: &'a [BodyNode]) -> TokenStream2 {
+        TemplateRenderer::render(Self {
+            roots,
+            location: None,
+            key: None,
+            sub_template: true,
+        })
+    }
+
+    /// Render the body of a loop that is keyed as a whole. Every root of the body is diffed together under the key.
+    pub fn as_keyed_sub_template_tokens(roots: &'a [BodyNode], key: &'a IfmtInput) -> TokenStream2 {
+        TemplateRenderer::render(Self {
+            roots,
+            location: None,
+            key: Some(key),
+            sub_template: true,
+        })
+    }
+
+    /// The key of a template with these roots, either from a root fragment or from its only element or component
+    pub(crate) fn roots_key(roots: &[BodyNode]) -> Option<IfmtInput> {
+        if let [BodyNode::Fragment(fragment)] = roots {
+            if let Some(key) = &fragment.key {
+                return Some(key.clone());
+            }
+        }
+        Self::implicit_key(flatten_root_fragment(roots))
     }
 
     fn render(mut self) -> TokenStream2 {
+        // A fragment that is the only root is just a key for the rest of the template, so render its children directly
+        let key = self.key.cloned().or_else(|| Self::roots_key(self.roots));
+        self.roots = flatten_root_fragment(self.roots);
+
         // If there are no roots, this is an empty template, so just return None
         if self.roots.is_empty() {
             return quote! { Option::<dioxus_core::VNode>::None };
@@ -26,20 +105,22 @@ impl<'a> TemplateRenderer<'a> {
         let mut context = DynamicContext::default();
 
         // If we have an implicit key, then we need to write its tokens
-        let key_tokens = match self.implicit_key() {
+        let key_tokens = match key {
             Some(tok) => quote! { Some( #tok.to_string() ) },
             None => quote! { None },
         };
 
-        // Get the tokens we'll use as the ID of the template
-        // This follows the file:line:column:id format
-        let name = self.get_template_id_tokens();
-
         // Render the static nodes, generating the mapping of dynamic
         // This will modify the bodynodes in place - sorry about that
         let roots = self.render_body_nodes(&mut context);
 
         let dynamic_nodes = &context.dynamic_nodes;
+        let conditions = context.conditions.iter().map(|group| {
+            let ConditionalAttributes {
+                binding, condition, ..
+            } = group;
+            quote! { let #binding: bool = #condition; }
+        });
         let dyn_attr_printer = context
             .dynamic_attributes
             .iter()
@@ -47,16 +128,24 @@ impl<'a> TemplateRenderer<'a> {
 
         let node_paths = context.node_paths.iter().map(|it| quote!(&[#(#it),*]));
         let attr_paths = context.attr_paths.iter().map(|it| quote!(&[#(#it),*]));
+        let contents = quote! {
+            roots: #roots,
+            node_paths: &[ #(#node_paths),* ],
+            attr_paths: &[ #(#attr_paths),* ],
+        };
+
+        // Get the tokens we'll use as the ID of the template
+        // This follows the file:line:column:id format
+        let name = self.get_template_id_tokens(&contents);
 
         let vnode = quote! {
             static TEMPLATE: dioxus_core::Template = dioxus_core::Template {
                 name: #name,
-                roots: #roots,
-                node_paths: &[ #(#node_paths),* ],
-                attr_paths: &[ #(#attr_paths),* ],
+                #contents
             };
 
             {
+                #(#conditions)*
                 // NOTE: Allocating a temporary is important to make reads within rsx drop before the value is returned
                 #[allow(clippy::let_and_return)]
                 let __vnodes = dioxus_core::VNode::new(
@@ -72,9 +161,40 @@ impl<'a> TemplateRenderer<'a> {
         quote! { Some({ #vnode }) }
     }
 
-    fn get_template_id_tokens(&self) -> TokenStream2 {
-        match self.location {
-            Some(ref loc) => quote! { #loc },
+    fn get_template_id_tokens(&self, contents: &TokenStream2) -> TokenStream2 {
+        // Name templates after their structure so identical rsx from different call sites shares one template.
+        // Included files already have a stable name that doesn't depend on the call site
+        if HASHED_TEMPLATES.with(Cell::get)
+            && self.location.is_none()
+            && INCLUDED_FILE.with(|file| file.borrow().is_none())
+            && !self.looks_like_rust_analyzer()
+        {
+            let name = hashed_template_name(contents);
+            return quote! { #name };
+        }
+
+        if self.sub_template {
+            let id = match self.looks_like_rust_analyzer() {
+                true => 0,
+                false => sub_template_id(contents),
+            };
+
+            // Nested templates in an included file use the path of the file instead of a call site
+            let included = INCLUDED_FILE.with(|file| file.borrow().clone());
+            return match included {
+                Some(path) => {
+                    let loc = included_template_location(&path, id);
+                    quote! { #loc }
+                }
+                None => {
+                    let id = id.to_string();
+                    quote! { concat!(file!(), ":", line!(), ":", column!(), ":", #id) }
+                }
+            };
+        }
+
+        match self.location.clone() {
+            Some(loc) => quote! { #loc },
             None => {
                 // Get the root:column:id tag we'll use as the ID of the template
                 let root_col = self.get_root_col_id();
@@ -95,17 +215,16 @@ impl<'a> TemplateRenderer<'a> {
     }
 
     fn get_root_col_id(&self) -> String {
+        // Rust analyzer will not autocomplete properly if we change the name every time you type a character
+        // If it looks like we are running in rust analyzer, we'll just use a placeholder location
+        if self.looks_like_rust_analyzer() {
+            return "0".to_string();
+        }
+
         let root_col = match self.roots.first() {
             Some(first_root) => {
                 let first_root_span = format!("{:?}", first_root.span());
 
-                // Rust analyzer will not autocomplete properly if we change the name every time you type a character
-                // If it looks like we are running in rust analyzer, we'll just use a placeholder location
-                let looks_like_rust_analyzer = first_root_span.contains("SpanData");
-                if looks_like_rust_analyzer {
-                    return "0".to_string();
-                }
-
                 first_root_span
                     .rsplit_once("..")
                     .and_then(|(_, after)| after.split_once(')').map(|(before, _)| before))
@@ -117,10 +236,16 @@ impl<'a> TemplateRenderer<'a> {
         root_col
     }
 
-    fn implicit_key(&self) -> Option<IfmtInput> {
-        let key = match self.roots.first() {
-            Some(BodyNode::Element(el)) if self.roots.len() == 1 => el.key.clone(),
-            Some(BodyNode::Component(comp)) if self.roots.len() == 1 => comp.key().cloned(),
+    fn looks_like_rust_analyzer(&self) -> bool {
+        self.roots
+            .first()
+            .is_some_and(|root| format!("{:?}", root.span()).contains("SpanData"))
+    }
+
+    fn implicit_key(roots: &[BodyNode]) -> Option<IfmtInput> {
+        let key = match roots.first() {
+            Some(BodyNode::Element(el)) if roots.len() == 1 => el.key.clone(),
+            Some(BodyNode::Component(comp)) if roots.len() == 1 => comp.key().cloned(),
             _ => None,
         };
         key
@@ -140,3 +265,35 @@ impl<'a> TemplateRenderer<'a> {
         }
     }
 }
+
+/// A name for a template that only depends on its structure. The hash goes in the path so the name
+/// still ends in the `:<usize>` id core expects
+fn hashed_template_name(contents: &TokenStream2) -> String {
+    // FNV-1a with 64 bits to make collisions between different templates unlikely
+    let hash = contents
+        .to_string()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+    format!("dioxus-template-{hash:016x}:0:0:0")
+}
+
+/// Get the id of a sub template from its static nodes and the paths to its dynamic parts
+///
+/// Templates with the same contents are interchangeable, so sub templates with the same id in one rsx call are fine.
+/// Root templates are named after the byte offset of their first node and the hot reloader replaces the template with
+/// the lowest id in an rsx call, so sub template ids are always at least 2^31 to stay above any byte offset. They also
+/// stay below `u32::MAX` so they fit in a `usize` on 32 bit targets, where core reserves `usize::MAX`.
+fn sub_template_id(contents: &TokenStream2) -> usize {
+    // FNV-1a. The id needs to be the same every time the macro expands, so we can't use a randomly seeded hasher
+    let hash = contents
+        .to_string()
+        .bytes()
+        .fold(0x811c9dc5u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x01000193)
+        });
+
+    (hash % 0x7fff_ffff) as usize + 0x8000_0000
+}
@@ -5,3 +5,14 @@ pub(crate) fn try_parse_braces<'a>(
     let brace = syn::braced!(content in input);
     Ok((brace, content))
 }
+
+/// Skip tokens until the next top level comma or the end of the stream
+///
+/// This is used to recover from errors in partial completion mode so we can keep parsing the rest of the input
+pub(crate) fn skip_until_comma(input: syn::parse::ParseStream) {
+    while !input.is_empty() && !input.peek(syn::Token![,]) {
+        if input.parse::<proc_macro2::TokenTree>().is_err() {
+            break;
+        }
+    }
+}
@@ -88,3 +88,101 @@ fn testing_for_pattern() {
 
     // dbg!(rsx_calls);
 }
+
+#[test]
+fn hyphenated_attributes_hot_reload() {
+    let old = quote! {
+        div { data_x: "{x}", data-testid: "old" }
+    };
+
+    let new = quote! {
+        div { data_x: "{x}", data-testid: "new", hx-get: "/items" }
+    };
+
+    let old: CallBody = syn::parse2(old).unwrap();
+    let new: CallBody = syn::parse2(new).unwrap();
+
+    let updated = new.update_template::<Mock>(Some(old), "testing").unwrap();
+
+    let dioxus_core::TemplateNode::Element { attrs, .. } = updated.roots[0] else {
+        panic!("Expected an element");
+    };
+
+    assert_eq!(
+        attrs[1],
+        dioxus_core::TemplateAttribute::Static {
+            name: "data-testid",
+            namespace: None,
+            value: "new",
+        }
+    );
+    assert_eq!(
+        attrs[2],
+        dioxus_core::TemplateAttribute::Static {
+            name: "hx-get",
+            namespace: None,
+            value: "/items",
+        }
+    );
+}
+
+#[test]
+fn custom_namespaces_hot_reload() {
+    mod dioxus_elements {
+        dioxus_core::define_elements! {
+            pub struct TuiNamespace;
+
+            label {
+                color,
+                r#type = "type",
+            };
+
+            rect in "canvas" {
+                width,
+            };
+        }
+    }
+
+    let old = quote! {
+        label { color: "{color}", r#type: "bold", rect { width: "10" } }
+    };
+
+    let new = quote! {
+        label { r#type: "italic", color: "{color}", rect { width: "20" } }
+    };
+
+    let old: CallBody = syn::parse2(old).unwrap();
+    let new: CallBody = syn::parse2(new).unwrap();
+
+    let updated = new
+        .update_template::<dioxus_elements::TuiNamespace>(Some(old), "testing")
+        .unwrap();
+
+    let dioxus_core::TemplateNode::Element {
+        tag,
+        attrs,
+        children,
+        ..
+    } = updated.roots[0]
+    else {
+        panic!("Expected an element");
+    };
+    assert_eq!(tag, "label");
+    assert_eq!(
+        attrs[0],
+        dioxus_core::TemplateAttribute::Static {
+            name: "type",
+            namespace: None,
+            value: "italic",
+        }
+    );
+    assert!(matches!(
+        children,
+        [dioxus_core::TemplateNode::Element {
+            tag: "rect",
+            namespace: Some("canvas"),
+            attrs: [dioxus_core::TemplateAttribute::Static { value: "20", .. }],
+            ..
+        }]
+    ));
+}
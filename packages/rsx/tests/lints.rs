@@ -0,0 +1,252 @@
+use dioxus_rsx::{CallBody, Lint};
+
+fn lints(tokens: proc_macro2::TokenStream) -> Vec<Lint> {
+    let body: CallBody = syn::parse2(tokens).unwrap();
+    body.lint()
+        .iter()
+        .map(|diagnostic| diagnostic.lint)
+        .collect()
+}
+
+#[test]
+fn keys_outside_loops() {
+    assert_eq!(
+        lints(quote::quote! {
+            div { key: "{id}",
+                span { key: "{id}" }
+                for item in items {
+                    li { key: "{item}" }
+                }
+            }
+        }),
+        vec![Lint::KeyOutsideLoop]
+    );
+
+    // Keys in branches of an if inside of a loop are still items of the list
+    assert!(lints(quote::quote! {
+        for item in items {
+            if item.visible {
+                li { key: "{item}" }
+            }
+        }
+    })
+    .is_empty());
+}
+
+#[test]
+fn loops_without_keys() {
+    assert_eq!(
+        lints(quote::quote! {
+            ul {
+                for item in items {
+                    li { "{item}" }
+                }
+                for item in items {
+                    Item { item }
+                }
+                for item in items {
+                    "{item}"
+                }
+            }
+        }),
+        vec![Lint::ForLoopWithoutKey, Lint::ForLoopWithoutKey]
+    );
+
+    // While loops render lists too
+    assert_eq!(
+        lints(quote::quote! {
+            while let Some(item) = items.next() {
+                li { "{item}" }
+            }
+            while let Some(item) = items.next() {
+                li { key: "{item}", "{item}" }
+            }
+        }),
+        vec![Lint::ForLoopWithoutKey]
+    );
+
+    // A keyed fragment keys every root of the loop body
+    assert_eq!(
+        lints(quote::quote! {
+            for item in items {
+                fragment { key: "{item}",
+                    dt { "{item}" }
+                    dd { key: "{item}" }
+                }
+            }
+            for item in items {
+                fragment {
+                    dt { "{item}" }
+                }
+            }
+        }),
+        vec![Lint::KeyOutsideLoop, Lint::ForLoopWithoutKey]
+    );
+
+    // So does a key on the loop itself
+    assert_eq!(
+        lints(quote::quote! {
+            for item in items { key: "{item}",
+                dt { "{item}" }
+                dd { key: "{item}" }
+            }
+        }),
+        vec![Lint::KeyOutsideLoop]
+    );
+}
+
+#[test]
+fn duplicate_nested_listeners() {
+    assert_eq!(
+        lints(quote::quote! {
+            div { onclick: move |_| count += 1,
+                button { onclick: move |_| count += 1 }
+                button { onclick: move |_| count -= 1 }
+                Card {
+                    button { onclick: move |_| count += 1 }
+                }
+            }
+        }),
+        vec![Lint::DuplicateNestedListener]
+    );
+
+    // `onclick` is shorthand for `onclick: onclick`
+    assert_eq!(
+        lints(quote::quote! {
+            div { onclick,
+                button { onclick: onclick }
+            }
+        }),
+        vec![Lint::DuplicateNestedListener]
+    );
+}
+
+#[test]
+fn children_after_spread() {
+    assert_eq!(
+        lints(quote::quote! {
+            Card { ..props, "Hello" }
+            Card { ..props }
+            Card { title: "Hello", "Hello" }
+        }),
+        vec![Lint::ChildrenAfterSpread]
+    );
+}
+
+#[test]
+fn lint_levels_from_attributes() {
+    use dioxus_rsx::Level;
+    use quote::ToTokens;
+
+    let levels = |tokens: proc_macro2::TokenStream| {
+        let body: CallBody = syn::parse2(tokens).unwrap();
+        body.lint()
+            .iter()
+            .map(|diagnostic| (diagnostic.lint.code(), diagnostic.level))
+            .collect::<Vec<_>>()
+    };
+
+    // Every lint has a stable code and a default level
+    assert_eq!(
+        levels(quote::quote! {
+            div { span { key: "{id}" } }
+            for item in items { li {} }
+        }),
+        vec![("RSX0001", Level::Warn), ("RSX0002", Level::Help)]
+    );
+
+    // Attributes on the call take names or codes
+    assert_eq!(
+        levels(quote::quote! {
+            #![allow(key_outside_loop)]
+            #![deny(rsx0002)]
+            div { span { key: "{id}" } }
+            for item in items { li {} }
+        }),
+        vec![("RSX0002", Level::Deny)]
+    );
+
+    // Denied lints are compile errors
+    let body: CallBody = syn::parse2(quote::quote! {
+        #![deny(children_after_spread)]
+        Card { ..props, "Hello" }
+    })
+    .unwrap();
+    let tokens = body.lint().into_token_stream().to_string();
+    assert!(tokens.contains("compile_error"), "{tokens}");
+    assert!(tokens.contains("RSX0004"), "{tokens}");
+
+    // Unknown lints and levels are errors
+    assert!(syn::parse2::<CallBody>(quote::quote! { #![allow(not_a_lint)] div {} }).is_err());
+    assert!(syn::parse2::<CallBody>(quote::quote! { #![forbid(RSX0001)] div {} }).is_err());
+}
+
+#[test]
+fn unknown_html_entities() {
+    use quote::ToTokens;
+
+    assert_eq!(
+        lints(quote::quote! {
+            p { "Tom&nbps;&amp;&nbsp;Jerry &zeta; & friends" }
+        }),
+        vec![Lint::UnknownHtmlEntity]
+    );
+
+    // The warning is emitted at the text and names the reference
+    let body: CallBody = syn::parse2(quote::quote! { "a&nbps;b" }).unwrap();
+    let diagnostics = body.lint();
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert!(
+        diagnostic.message.contains("`&nbps;`"),
+        "{}",
+        diagnostic.message
+    );
+    let tokens = diagnostics.into_token_stream().to_string();
+    assert!(tokens.contains("deprecated"), "{tokens}");
+    assert!(tokens.contains("RSX0005"), "{tokens}");
+
+    // The warning fires without the `lints` feature too
+    let tokens = body.into_token_stream().to_string();
+    assert!(tokens.contains("unknown_html_entity"), "{tokens}");
+}
+
+#[cfg(feature = "lints")]
+#[test]
+fn lint_levels_from_manifest() {
+    use dioxus_rsx::{Level, LintLevels};
+
+    let levels = LintLevels::from_manifest_str(
+        r#"
+        [package]
+        name = "app"
+
+        [package.metadata.dioxus.lints]
+        key_outside_loop = "allow"
+        RSX0003 = "deny"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(levels.level(Lint::KeyOutsideLoop), Level::Allow);
+    assert_eq!(levels.level(Lint::DuplicateNestedListener), Level::Deny);
+    assert_eq!(levels.level(Lint::ChildrenAfterSpread), Level::Warn);
+
+    // Attributes on the call override the crate
+    let body: CallBody = syn::parse2(quote::quote! {
+        #![warn(key_outside_loop)]
+        div { span { key: "{id}" } }
+    })
+    .unwrap();
+    let diagnostic = body
+        .lint_with(levels.clone())
+        .iter()
+        .next()
+        .cloned()
+        .unwrap();
+    assert_eq!(diagnostic.level, Level::Warn);
+    assert!(diagnostic.to_json().contains(r#""code":"RSX0001""#));
+
+    assert!(LintLevels::from_manifest_str(
+        "[package.metadata.dioxus.lints]\nkey_outside_loop = \"loud\""
+    )
+    .is_err());
+}
@@ -42,3 +42,209 @@ fn parse_from_str(contents: &str) -> CallBody {
 
     call.mac.parse_body().unwrap()
 }
+
+#[test]
+fn html_entities_in_text() {
+    let body: CallBody =
+        syn::parse_str(r#"div { "a&nbsp;b &amp; &#169; &#x1F600; \u{2014} & c" }"#).unwrap();
+
+    let dioxus_rsx::BodyNode::Element(el) = &body.roots[0] else {
+        panic!("Expected an element");
+    };
+    let dioxus_rsx::BodyNode::Text(text) = &el.children[0] else {
+        panic!("Expected a text node");
+    };
+
+    assert_eq!(
+        text.to_static().unwrap(),
+        "a\u{a0}b & \u{a9} \u{1F600} \u{2014} & c"
+    );
+}
+
+#[test]
+fn every_named_html_entity_in_text() {
+    let body: CallBody =
+        syn::parse_str(r#"div { "&zeta;&lcub;&NotNestedGreaterGreater;&nbps; &amp" }"#).unwrap();
+
+    let dioxus_rsx::BodyNode::Element(el) = &body.roots[0] else {
+        panic!("Expected an element");
+    };
+    let dioxus_rsx::BodyNode::Text(text) = &el.children[0] else {
+        panic!("Expected a text node");
+    };
+
+    // Unknown names are left as written, the same way browsers render them
+    assert_eq!(
+        text.to_static().unwrap(),
+        "\u{3b6}{\u{2aa2}\u{338}&nbps; &amp"
+    );
+}
+
+#[test]
+fn shorthand_listeners_are_checked_for_duplicates() {
+    let err = syn::parse::Parser::parse_str(
+        CallBody::parse_strict,
+        r#"div { onclick: handle_click, onclick }"#,
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Duplicate event listener `onclick`"));
+}
+
+#[test]
+fn partial_elements_still_expand() {
+    // Half typed attribute values are recorded as errors instead of failing the whole macro
+    let body: CallBody = syn::parse_str(r#"div { class: , id: "a", onclick: , cla }"#).unwrap();
+
+    let dioxus_rsx::BodyNode::Element(el) = &body.roots[0] else {
+        panic!("Expected an element");
+    };
+    assert_eq!(el.attributes.len(), 2);
+
+    // The errors are still emitted into the expanded tokens
+    let mut context = DynamicContext::default();
+    let expanded = context.render_static_node(&body.roots[0]).to_string();
+    assert!(expanded.contains("compile_error"));
+
+    // Children after an error are dropped, but the ones before it are kept
+    let body: CallBody = syn::parse_str(r#"div { span {} "hi" 123 }"#).unwrap();
+    let dioxus_rsx::BodyNode::Element(el) = &body.roots[0] else {
+        panic!("Expected an element");
+    };
+    assert_eq!(el.children.len(), 2);
+
+    // Strict mode still returns the first error
+    assert!(
+        syn::parse::Parser::parse_str(CallBody::parse_strict, r#"div { class: , id: "a" }"#)
+            .is_err()
+    );
+}
+
+#[test]
+fn included_templates_have_stable_names() {
+    let body: CallBody = syn::parse_str(
+        r#"div {
+            for item in items { p { "{item}" } }
+            if show { span {} }
+        }"#,
+    )
+    .unwrap();
+
+    let out = body.render_included("templates/list.rsx").to_string();
+
+    let names = template_names(&out);
+    assert_eq!(names.len(), 3, "{out}");
+    assert_eq!(names[0], "templates/list.rsx:0:0:0");
+    assert!(names[1..]
+        .iter()
+        .all(|name| name.starts_with("templates/list.rsx:0:0:") && name != &names[0]));
+    assert!(!out.contains("file !"));
+}
+
+#[test]
+fn sub_template_names_ignore_unrelated_nodes() {
+    let sub_template_names = |rsx: &str| {
+        let body: CallBody = syn::parse_str(rsx).unwrap();
+        let out = body.render_included("templates/list.rsx").to_string();
+        template_names(&out)[1..].to_vec()
+    };
+
+    let before = sub_template_names(
+        r#"div {
+            for item in items { p { "{item}" } }
+            if show { span {} }
+        }"#,
+    );
+    let after = sub_template_names(
+        r#"div {
+            h1 { "Items" }
+            input { value: "{filter}" }
+            for item in items { p { "{item}" } }
+            if show { span {} }
+        }"#,
+    );
+    assert_eq!(before, after);
+
+    // Changing the contents of a sub template gives it a new name
+    let changed = sub_template_names(
+        r#"div {
+            for item in items { p { class: "item", "{item}" } }
+            if show { span {} }
+        }"#,
+    );
+    assert_ne!(before[0], changed[0]);
+    assert_eq!(before[1], changed[1]);
+}
+
+#[test]
+fn nested_loops_are_flattened_unless_keyed() {
+    let template_count = |rsx: &str| {
+        let body: CallBody = syn::parse_str(rsx).unwrap();
+        template_names(&body.render_included("templates/grid.rsx").to_string()).len()
+    };
+
+    // The rows don't get a template of their own, every cell is pushed into one list
+    assert_eq!(
+        template_count(r#"ul { for row in rows { for cell in row { li { "{cell}" } } } }"#),
+        2
+    );
+
+    // Keyed cells keep one list per row so their keys only need to be unique within the row
+    assert_eq!(
+        template_count(
+            r#"ul { for row in rows { for cell in row { li { key: "{cell}", "{cell}" } } } }"#
+        ),
+        3
+    );
+    assert_eq!(
+        template_count(r#"ul { for row in rows { key: "{row.id}", for cell in row { li {} } } }"#),
+        3
+    );
+}
+
+#[test]
+fn hashed_templates_are_shared_between_identical_structures() {
+    let names = |rsx: &str| {
+        let body: CallBody = syn::parse_str(rsx).unwrap();
+        template_names(&quote::ToTokens::to_token_stream(&body).to_string())
+    };
+
+    let first = names(
+        r#"#![hashed_templates]
+        div { class: "card",
+            h1 { "{title}" }
+            for item in items { p { "{item}" } }
+        }"#,
+    );
+    // The dynamic parts are different, but the structure is the same
+    let second = names(
+        r#"#![hashed_templates]
+        div { class: "card",
+            h1 { "{other_title}" }
+            for row in rows { p { "{row.name}" } }
+        }"#,
+    );
+    assert_eq!(first, second);
+    assert!(first.iter().all(|name| name.ends_with(":0:0:0")));
+    assert_ne!(first[0], first[1]);
+
+    let changed = names(r#"#![hashed_templates] div { class: "other", h1 { "{title}" } }"#);
+    assert_ne!(first[0], changed[0]);
+
+    // Calls without the attribute keep their call site names
+    let unhashed = names(r#"div { class: "card", h1 { "{title}" } }"#);
+    assert!(unhashed
+        .iter()
+        .all(|name| !name.starts_with("dioxus-template-")));
+}
+
+/// The names of the templates in expanded rsx in the order they appear
+fn template_names(out: &str) -> Vec<String> {
+    out.match_indices("name : \"")
+        .map(|(start, pattern)| {
+            let rest = &out[start + pattern.len()..];
+            rest[..rest.find('"').unwrap()].to_string()
+        })
+        .collect()
+}
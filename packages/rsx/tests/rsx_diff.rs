@@ -110,3 +110,176 @@ fn changing_forloops_is_okay() {
 
     dbg!(new_template);
 }
+
+#[test]
+fn component_literals_hot_reload() {
+    use dioxus_core::HotLiteral;
+
+    let old: CallBody = syn::parse2(quote! {
+        div {
+            Link { to: "/home", size: 1, "Home" }
+            for i in 0..3 {
+                Counter { step: 1.5, enabled: true, count: i }
+            }
+        }
+    })
+    .unwrap();
+    assert_eq!(
+        old.hot_literals(),
+        [
+            (0, HotLiteral::Str("/home")),
+            (1, HotLiteral::Int(1)),
+            (2, HotLiteral::Float(1.5)),
+            (3, HotLiteral::Bool(true)),
+            (4, HotLiteral::Int(0)),
+            (5, HotLiteral::Int(3)),
+        ]
+    );
+
+    // Changing the literals and moving the components around keeps the ids the app was compiled with
+    let new: CallBody = syn::parse2(quote! {
+        div {
+            for i in 0..3 {
+                Counter { step: 2.0, enabled: false, count: i }
+            }
+            Link { to: "/about", size: 1, "Home" }
+        }
+    })
+    .unwrap();
+    let (_, literals) = new
+        .update_template_and_literals::<Mock>(Some(old), "testing:1:1:0")
+        .unwrap();
+    assert_eq!(
+        literals,
+        [
+            (0, HotLiteral::Str("/about")),
+            (1, HotLiteral::Int(1)),
+            (2, HotLiteral::Float(2.0)),
+            (3, HotLiteral::Bool(false)),
+            (4, HotLiteral::Int(0)),
+            (5, HotLiteral::Int(3)),
+        ]
+    );
+
+    // A literal of a different type needs a rebuild
+    let old: CallBody = syn::parse2(quote! { Link { to: "/home" } }).unwrap();
+    let new: CallBody = syn::parse2(quote! { Link { to: 1 } }).unwrap();
+    assert!(new.update_template::<Mock>(Some(old), "testing").is_none());
+}
+
+#[test]
+fn loop_and_condition_literals_hot_reload() {
+    use dioxus_core::HotLiteral;
+
+    let old: CallBody = syn::parse2(quote! {
+        div {
+            Link { to: "/home" }
+            for i in 0..10 {
+                "{i}"
+            }
+            if count > 3 {
+                "many"
+            } else if true {
+                "few"
+            }
+        }
+    })
+    .unwrap();
+
+    // Loop and condition literals are numbered after the component props
+    assert_eq!(
+        old.hot_literals(),
+        [
+            (0, HotLiteral::Str("/home")),
+            (1, HotLiteral::Int(0)),
+            (2, HotLiteral::Int(10)),
+            (3, HotLiteral::Int(3)),
+            (4, HotLiteral::Bool(true)),
+        ]
+    );
+
+    let new: CallBody = syn::parse2(quote! {
+        div {
+            Link { to: "/home" }
+            for i in 2..5 {
+                "{i}"
+            }
+            if count > 7 {
+                "many"
+            } else if false {
+                "few"
+            }
+        }
+    })
+    .unwrap();
+    let (_, literals) = new
+        .update_template_and_literals::<Mock>(Some(old), "testing:1:1:0")
+        .unwrap();
+    assert_eq!(
+        literals,
+        [
+            (0, HotLiteral::Str("/home")),
+            (1, HotLiteral::Int(2)),
+            (2, HotLiteral::Int(5)),
+            (3, HotLiteral::Int(7)),
+            (4, HotLiteral::Bool(false)),
+        ]
+    );
+
+    // Literals in other positions of the expression are still code
+    let old: CallBody = syn::parse2(quote! { for i in [0; 3] { "{i}" } }).unwrap();
+    let new: CallBody = syn::parse2(quote! { for i in [0; 4] { "{i}" } }).unwrap();
+    assert!(new.update_template::<Mock>(Some(old), "testing").is_none());
+}
+
+#[test]
+fn static_subtrees_hot_reload() {
+    use dioxus_core::TemplateNode;
+
+    let old: CallBody = syn::parse2(quote! {
+        div {
+            p { "remove me" }
+            {x}
+            "{y}"
+        }
+    })
+    .unwrap();
+
+    // Adding and removing static nodes moves the dynamic nodes, but they keep the ids the app was compiled with
+    let new: CallBody = syn::parse2(quote! {
+        div {
+            h1 { class: "title", "added" }
+            "{y}"
+            section {
+                {x}
+            }
+        }
+    })
+    .unwrap();
+    let template = new.update_template::<Mock>(Some(old), "testing").unwrap();
+    assert_eq!(template.node_paths, [&[0, 2, 0][..], &[0, 1]]);
+    let TemplateNode::Element { children, .. } = template.roots[0] else {
+        panic!("root should be an element");
+    };
+    assert_eq!(
+        children[1],
+        TemplateNode::DynamicText { id: 1 },
+        "the text keeps its id"
+    );
+
+    // Removing a dynamic node along with a static subtree needs a rebuild
+    let old: CallBody = syn::parse2(quote! {
+        div {
+            p { {x} }
+            {y}
+        }
+    })
+    .unwrap();
+    let new: CallBody = syn::parse2(quote! { div { {y} } }).unwrap();
+    assert!(new.update_template::<Mock>(Some(old), "testing").is_none());
+
+    // So does removing a dynamic attribute
+    let old: CallBody = syn::parse2(quote! { div { class: "{x}", p {} } }).unwrap();
+    let new: CallBody = syn::parse2(quote! { div { p {} } }).unwrap();
+    assert!(new.update_template::<Mock>(Some(old), "testing").is_none());
+}
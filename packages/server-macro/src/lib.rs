@@ -9,6 +9,8 @@ use proc_macro::TokenStream;
 use server_fn_macro::server_macro_impl;
 use syn::__private::ToTokens;
 
+mod version;
+
 /// Declares that a function is a [server function](https://docs.rs/server_fn/).
 /// This means that its body will only run on the server, i.e., when the `ssr`
 /// feature is enabled on this crate.
@@ -33,6 +35,7 @@ use syn::__private::ToTokens;
 /// - `prefix`: a prefix at which the server function handler will be mounted (defaults to `/api`)
 /// - `endpoint`: specifies the exact path at which the server function handler will be mounted,
 ///   relative to the prefix (defaults to the function name followed by unique hash)
+/// - `version`: the version of the function's wire contract (defaults to `1`). See [Versioning](#versioning)
 /// - `input`: the encoding for the arguments (defaults to `PostUrl`)
 /// - `output`: the encoding for the response (defaults to `Json`)
 /// - `client`: a custom `Client` implementation that will be used for this server fn
@@ -68,11 +71,45 @@ use syn::__private::ToTokens;
 ///   // etc.
 /// }
 /// ```
+///
+/// ## Versioning
+///
+/// Server functions are mounted under a `v{version}-{hash}` segment after the prefix, like
+/// `/api/v1-1a2b3c4d/get_posts123`. The hash covers the argument types, return type and encodings as they are
+/// written in the signature, so a cached client built against an older signature calls a path the server no
+/// longer serves. The server answers those calls with a version mismatch error you can detect with
+/// `ServerFnVersionMismatch::from_error` instead of a response the client can't deserialize.
+///
+/// The hash only sees the text of the signature, not the definitions of the types in it. Adding, removing or
+/// renaming a field of a `Post` argument or return value keeps the same path. `version` is the real wire contract:
+/// bump it whenever the serialized form of an argument or the return value changes.
+///
+/// To keep serving old clients after a breaking change, keep the old function around and give the new one a
+/// higher version. Both versions are served at the same time and each client calls the version it was built with:
+/// ```rust,ignore
+/// #[server(name = GetPostsV1)]
+/// pub async fn get_posts_v1() -> Result<Vec<String>, ServerFnError> {
+///     Ok(get_posts().await?.into_iter().map(|post| post.title).collect())
+/// }
+///
+/// #[server(version = 2)]
+/// pub async fn get_posts() -> Result<Vec<Post>, ServerFnError> {
+///     load_posts().await
+/// }
+/// ```
+///
+/// Server functions with an explicit `endpoint` keep their exact path unless `version` is set.
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let body = s.into();
+    let args = match version::versioned_args(args.into(), &body) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
     match server_macro_impl(
-        args.into(),
-        s.into(),
+        args,
+        body,
         Some(syn::parse_quote!(server_fn)),
         "/api",
         None,
@@ -0,0 +1,187 @@
+//! Versioned server function paths
+//!
+//! Every server function is mounted under a `v{version}-{hash}` segment. The hash covers the argument types,
+//! return type and encodings of the function, so a client built against an older signature calls a path the
+//! server no longer serves and gets a version mismatch error instead of a response it can't deserialize.
+//!
+//! The macro only sees the tokens of the signature, so the hash can't notice changes inside the types it names.
+//! Those changes have to bump the `version` argument.
+
+use proc_macro2::{Ident, Literal, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{LitInt, LitStr};
+
+const DEFAULT_PREFIX: &str = "/api";
+
+/// One comma separated argument of `#[server(...)]`
+enum ServerArg {
+    Keyed { key: Ident, value: Vec<TokenTree> },
+    Positional(Vec<TokenTree>),
+}
+
+impl ServerArg {
+    fn parse_all(args: TokenStream2) -> Vec<Self> {
+        let mut parsed = Vec::new();
+        let mut current = Vec::new();
+        for token in args {
+            match &token {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                    parsed.extend(Self::from_tokens(std::mem::take(&mut current)));
+                }
+                _ => current.push(token),
+            }
+        }
+        parsed.extend(Self::from_tokens(current));
+        parsed
+    }
+
+    fn from_tokens(tokens: Vec<TokenTree>) -> Option<Self> {
+        match tokens.as_slice() {
+            [] => None,
+            [TokenTree::Ident(key), TokenTree::Punct(eq), ..] if eq.as_char() == '=' => {
+                Some(Self::Keyed {
+                    key: key.clone(),
+                    value: tokens[2..].to_vec(),
+                })
+            }
+            _ => Some(Self::Positional(tokens)),
+        }
+    }
+
+    fn is_keyed(&self, name: &str) -> bool {
+        matches!(self, Self::Keyed { key, .. } if key == name)
+    }
+
+    fn value(&self) -> &[TokenTree] {
+        match self {
+            Self::Keyed { value, .. } => value,
+            Self::Positional(value) => value,
+        }
+    }
+
+    fn set_value(&mut self, new_value: Vec<TokenTree>) {
+        match self {
+            Self::Keyed { value, .. } => *value = new_value,
+            Self::Positional(value) => *value = new_value,
+        }
+    }
+}
+
+impl ToTokens for ServerArg {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Keyed { key, value } => tokens.extend(quote! { #key = #(#value)* }),
+            Self::Positional(value) => tokens.extend(value.iter().cloned()),
+        }
+    }
+}
+
+/// Strip the `version` argument and mount the server function under its versioned prefix.
+///
+/// Server functions with a fixed `endpoint` keep their exact path unless they opt in with `version`.
+pub(crate) fn versioned_args(args: TokenStream2, body: &TokenStream2) -> syn::Result<TokenStream2> {
+    let mut args = ServerArg::parse_all(args);
+
+    let mut version = None;
+    if let Some(idx) = args.iter().position(|arg| arg.is_keyed("version")) {
+        let arg = args.remove(idx);
+        let value: TokenStream2 = arg.value().iter().cloned().collect();
+        let lit: LitInt = syn::parse2(value)?;
+        version = Some(lit.base10_parse::<u32>()?);
+    }
+
+    // Legacy positional arguments are `name, prefix, encoding, endpoint`
+    let positional = |idx: usize| match args.get(idx) {
+        Some(ServerArg::Positional(_))
+            if args[..=idx]
+                .iter()
+                .all(|arg| matches!(arg, ServerArg::Positional(_))) =>
+        {
+            Some(idx)
+        }
+        _ => None,
+    };
+    let endpoint = args
+        .iter()
+        .position(|arg| arg.is_keyed("endpoint"))
+        .or_else(|| positional(3));
+    if endpoint.is_some() && version.is_none() {
+        return Ok(quote! { #(#args),* });
+    }
+
+    // Let the server macro report errors in the function itself
+    let Ok(item) = syn::parse2::<syn::ItemFn>(body.clone()) else {
+        return Ok(quote! { #(#args),* });
+    };
+
+    let codecs = args
+        .iter()
+        .filter(|arg| arg.is_keyed("input") || arg.is_keyed("output") || arg.is_keyed("encoding"))
+        .chain(positional(2).map(|idx| &args[idx]))
+        .map(|arg| {
+            arg.value()
+                .iter()
+                .cloned()
+                .collect::<TokenStream2>()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    let inputs = &item.sig.inputs;
+    let output = &item.sig.output;
+    let version = version.unwrap_or(1);
+    // The contract is the signature as written. Changing the fields of a type named in it keeps the same hash
+    let contract = format!(
+        "{version}|{}|{}|{}",
+        codecs.join(","),
+        quote! { #inputs },
+        quote! { #output }
+    );
+    let segment = format!("v{version}-{:08x}", wire_hash(&contract));
+
+    let prefix_idx = args
+        .iter()
+        .position(|arg| arg.is_keyed("prefix"))
+        .or_else(|| positional(1));
+    match prefix_idx {
+        Some(idx) => {
+            let value: TokenStream2 = args[idx].value().iter().cloned().collect();
+            let prefix: LitStr = syn::parse2(value)?;
+            let prefix = versioned_prefix(&prefix.value(), &segment);
+            args[idx].set_value(vec![TokenTree::Literal(Literal::string(&prefix))]);
+        }
+        None => {
+            let prefix =
+                TokenTree::Literal(Literal::string(&versioned_prefix(DEFAULT_PREFIX, &segment)));
+            // Positional arguments can't follow keyed ones, so keep the style the function already uses
+            if !args.is_empty()
+                && args
+                    .iter()
+                    .all(|arg| matches!(arg, ServerArg::Positional(_)))
+            {
+                args.push(ServerArg::Positional(vec![prefix]));
+            } else {
+                args.push(ServerArg::Keyed {
+                    key: Ident::new("prefix", Span::call_site()),
+                    value: vec![prefix],
+                });
+            }
+        }
+    }
+
+    Ok(quote! { #(#args),* })
+}
+
+fn versioned_prefix(prefix: &str, segment: &str) -> String {
+    format!("{}/{segment}", prefix.trim_end_matches('/'))
+}
+
+/// A 32 bit FNV-1a hash. The hash has to be stable across compiler versions because old clients keep calling
+/// the path they were built with.
+fn wire_hash(contract: &str) -> u32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in contract.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
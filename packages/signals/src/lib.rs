@@ -42,3 +42,6 @@ pub use props::*;
 
 mod reactive_context;
 pub use reactive_context::*;
+
+mod write_log;
+pub use write_log::*;
@@ -610,6 +610,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Drop for SignalSubscriberDrop<T, S>
                 "Write on signal at {} finished, updating subscribers",
                 self.origin
             );
+            crate::write_log::record_signal_write::<T>(self.origin);
 
             // Check if the write happened during a render. If it did, we should warn the user that this is generally a bad practice.
             if dioxus_core::vdom_is_rendering() {
@@ -0,0 +1,59 @@
+//! A log of the most recent signal writes for development tools like the dev overlay. Writes are only recorded in
+//! debug builds.
+
+use std::{
+    panic::Location,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// The number of writes [`recent_signal_writes`] remembers
+pub const SIGNAL_WRITE_LOG_SIZE: usize = 32;
+
+static WRITE_COUNT: AtomicUsize = AtomicUsize::new(0);
+// `VecDeque::new` is only const since rust 1.68, newer than the minimum version of this crate. The log is small enough
+// that removing the oldest write from the front of a `Vec` is cheap.
+static WRITES: Mutex<Vec<SignalWrite>> = Mutex::new(Vec::new());
+
+/// A finished write to a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalWrite {
+    /// The number of signal writes in the process before this one
+    pub index: usize,
+    /// Where the write happened
+    pub origin: &'static Location<'static>,
+    /// The type of the value in the signal
+    pub value_type: &'static str,
+}
+
+/// Get up to [`SIGNAL_WRITE_LOG_SIZE`] of the latest signal writes in the process, oldest first.
+///
+/// This is always empty in release builds.
+pub fn recent_signal_writes() -> Vec<SignalWrite> {
+    WRITES
+        .lock()
+        .map(|writes| writes.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Get the number of signal writes in the process. This is always zero in release builds.
+pub fn signal_write_count() -> usize {
+    WRITE_COUNT.load(Ordering::Relaxed)
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn record_signal_write<T>(origin: &'static Location<'static>) {
+    let index = WRITE_COUNT.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut writes) = WRITES.lock() {
+        if writes.len() == SIGNAL_WRITE_LOG_SIZE {
+            writes.remove(0);
+        }
+        writes.push(SignalWrite {
+            index,
+            origin,
+            value_type: std::any::type_name::<T>(),
+        });
+    }
+}
@@ -1,7 +1,8 @@
 use dioxus_core::prelude::*;
+use dioxus_html::is_boolean_attribute;
 use std::fmt::Write;
 
-use crate::renderer::{str_truthy, BOOL_ATTRS};
+use crate::renderer::str_truthy;
 
 #[derive(Debug)]
 pub(crate) struct StringCache {
@@ -92,7 +93,7 @@ impl StringCache {
                                 inner_html = Some(value);
                             } else if let Some("style") = namespace {
                                 styles.push((name, value));
-                            } else if BOOL_ATTRS.contains(name) {
+                            } else if is_boolean_attribute(name) {
                                 if str_truthy(value) {
                                     write!(chain, " {name}=\"{value}\"",)?;
                                 }
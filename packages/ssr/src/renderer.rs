@@ -2,6 +2,7 @@ use super::cache::Segment;
 use crate::cache::StringCache;
 
 use dioxus_core::{prelude::*, AttributeValue, DynamicNode};
+use dioxus_html::{is_boolean_attribute, is_enumerated_attribute};
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
@@ -25,11 +26,25 @@ pub struct Renderer {
     // Don't proceed onto new components. Instead, put the name of the component.
     pub skip_components: bool,
 
+    /// Wrap the fallback of suspense boundaries that are still waiting so their children can be streamed in once they
+    /// resolve with [`Renderer::render_resolved_boundaries`]
+    pub stream_suspense: bool,
+
+    /// Load the script that swaps streamed boundaries in from this url instead of writing it inline, for pages with a
+    /// strict Content Security Policy. The url should serve [`SUSPENSE_SCRIPT`].
+    pub suspense_script_src: Option<String>,
+
     /// A cache of templates that have been rendered
     template_cache: HashMap<&'static str, Arc<StringCache>>,
 
     /// The current dynamic node id for hydration
     dynamic_node_id: usize,
+
+    /// The boundaries that were rendered with their fallback and haven't been streamed yet
+    pending_boundaries: Vec<ScopeId>,
+
+    /// Whether the script that swaps streamed boundaries in for their fallback was written
+    wrote_suspense_script: bool,
 }
 
 impl Renderer {
@@ -44,9 +59,60 @@ impl Renderer {
     }
 
     pub fn render_to(&mut self, buf: &mut impl Write, dom: &VirtualDom) -> std::fmt::Result {
+        self.pending_boundaries.clear();
         self.render_scope(buf, dom, ScopeId::ROOT)
     }
 
+    /// Render the children of the boundaries that resolved since they were rendered with their fallback
+    ///
+    /// Each boundary is written as a `<template>` followed by a script that swaps it in for the fallback, so the chunk
+    /// can be appended to a streamed response. The script is inline unless [`Renderer::suspense_script_src`] is set. To stream a page, enable [`Renderer::stream_suspense`], render the page,
+    /// and then let the [`VirtualDom`] make progress and call this method until [`Renderer::has_pending_boundaries`]
+    /// returns false.
+    pub fn render_resolved_boundaries(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+    ) -> std::fmt::Result {
+        for id in std::mem::take(&mut self.pending_boundaries) {
+            // The boundary was removed while it was waiting
+            let Some(scope) = dom.get_scope(id) else {
+                continue;
+            };
+            if scope
+                .suspense_boundary()
+                .is_some_and(|boundary| boundary.suspended())
+            {
+                self.pending_boundaries.push(id);
+                continue;
+            }
+
+            if !self.wrote_suspense_script && self.suspense_script_src.is_none() {
+                write!(buf, "<script>{SWAP_SUSPENSE_SCRIPT}</script>")?;
+                self.wrote_suspense_script = true;
+            }
+            write!(buf, r#"<template id="ds-{}-resolved">"#, id.0)?;
+            self.render_template(buf, dom, scope.root_node())?;
+            write!(buf, "</template>")?;
+            match &self.suspense_script_src {
+                Some(src) => write!(
+                    buf,
+                    r#"<script src="{}" data-boundary="{}"></script>"#,
+                    askama_escape::escape(src, askama_escape::Html),
+                    id.0
+                )?,
+                None => write!(buf, "<script>__dioxus_swap_suspense({})</script>", id.0)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if any boundary was rendered with its fallback and still needs to be streamed
+    pub fn has_pending_boundaries(&self) -> bool {
+        !self.pending_boundaries.is_empty()
+    }
+
     pub fn render_scope(
         &mut self,
         buf: &mut impl Write,
@@ -92,18 +158,23 @@ impl Renderer {
                             inner_html = Some(attr);
                         } else if attr.namespace == Some("style") {
                             accumulated_dynamic_styles.push(attr);
-                        } else if BOOL_ATTRS.contains(&attr.name) {
+                        } else if is_boolean_attribute(attr.name) {
                             if truthy(&attr.value) {
                                 write_attribute(buf, attr)?;
                             }
+                        } else if let AttributeValue::Bool(false) = attr.value {
+                            // Boolean values toggle the presence of the attribute unless it expects "true" or "false"
+                            if is_enumerated_attribute(attr.name) {
+                                write_attribute(buf, attr)?;
+                            }
                         } else {
                             write_attribute(buf, attr)?;
                         }
 
                         if self.pre_render {
                             if let AttributeValue::Listener(_) = &attr.value {
-                                // The onmounted event doesn't need a DOM listener
-                                if attr.name != "onmounted" {
+                                // Lifecycle events don't need a DOM listener
+                                if !matches!(attr.name, "onmounted" | "onunmounted" | "onresized") {
                                     accumulated_listeners.push(attr.name);
                                 }
                             }
@@ -117,7 +188,23 @@ impl Renderer {
                         } else {
                             let scope = node.mounted_scope(*idx, template, dom).unwrap();
                             let node = scope.root_node();
-                            self.render_template(buf, dom, node)?
+                            match scope
+                                .suspense_boundary()
+                                .filter(|boundary| self.stream_suspense && boundary.suspended())
+                            {
+                                Some(boundary) => {
+                                    let id = boundary.id();
+                                    write!(
+                                        buf,
+                                        r#"<div id="ds-{}" style="display:contents">"#,
+                                        id.0
+                                    )?;
+                                    self.render_template(buf, dom, node)?;
+                                    write!(buf, "</div>")?;
+                                    self.pending_boundaries.push(id);
+                                }
+                                None => self.render_template(buf, dom, node)?,
+                            }
                         }
                     }
                     DynamicNode::Text(text) => {
@@ -212,6 +299,33 @@ impl Renderer {
     }
 }
 
+/// Numbers the hydration markers of the page again in document order
+///
+/// Streamed boundaries are rendered after the rest of the page, so their markers only line up with the order the client
+/// hydrates the page in once they are swapped in for their fallback.
+macro_rules! renumber_hydration_script {
+    () => {
+        r#"var w=document.createTreeWalker(document.body,NodeFilter.SHOW_ELEMENT|NodeFilter.SHOW_COMMENT),n,h,i=0;while(n=w.nextNode())if(n.nodeType===1){h=n.getAttribute("data-node-hydration");if(h!==null)n.setAttribute("data-node-hydration",(i++)+h.slice(h.split(",")[0].length))}else if(n.textContent.indexOf("node-id")===0)n.textContent="node-id"+(i++);"#
+    };
+}
+
+/// Replaces the fallback of a boundary with the template its children were streamed in
+const SWAP_SUSPENSE_SCRIPT: &str = concat!(
+    r#"function __dioxus_swap_suspense(id){var f=document.getElementById("ds-"+id),t=document.getElementById("ds-"+id+"-resolved");if(f&&t)f.replaceWith(t.content);if(t)t.remove();"#,
+    renumber_hydration_script!(),
+    "}"
+);
+
+/// The script to serve at [`Renderer::suspense_script_src`]
+///
+/// Every resolved boundary loads it again with the id of the boundary in its `data-boundary` attribute, so pages don't
+/// need any inline scripts to stream suspense.
+pub const SUSPENSE_SCRIPT: &str = concat!(
+    r#"(function(s){var id=s.dataset.boundary,f=document.getElementById("ds-"+id),t=document.getElementById("ds-"+id+"-resolved");if(f&&t)f.replaceWith(t.content);if(t)t.remove();s.remove();"#,
+    renumber_hydration_script!(),
+    "})(document.currentScript)"
+);
+
 #[test]
 fn to_string_works() {
     use dioxus::prelude::*;
@@ -337,36 +451,6 @@ fn empty_render_works() {
     assert_eq!(out, "");
 }
 
-pub(crate) const BOOL_ATTRS: &[&str] = &[
-    "allowfullscreen",
-    "allowpaymentrequest",
-    "async",
-    "autofocus",
-    "autoplay",
-    "checked",
-    "controls",
-    "default",
-    "defer",
-    "disabled",
-    "formnovalidate",
-    "hidden",
-    "ismap",
-    "itemscope",
-    "loop",
-    "multiple",
-    "muted",
-    "nomodule",
-    "novalidate",
-    "open",
-    "playsinline",
-    "readonly",
-    "required",
-    "reversed",
-    "selected",
-    "truespeed",
-    "webkitdirectory",
-];
-
 pub(crate) fn str_truthy(value: &str) -> bool {
     !value.is_empty() && value != "0" && value.to_lowercase() != "false"
 }
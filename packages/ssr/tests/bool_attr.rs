@@ -35,3 +35,23 @@ fn dynamic_boolean_attributs() {
         r#"<div></div><div hidden=true></div>"#
     );
 }
+
+#[test]
+fn boolean_attribute_shorthand_uses_the_variable() {
+    fn app() -> Element {
+        let disabled = false;
+        let hidden = true;
+        rsx! {
+            input { r#type: "checkbox", disabled, checked: true }
+            div { hidden }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<input type="checkbox" checked=true/><div hidden=true></div>"#
+    );
+}
@@ -0,0 +1,50 @@
+use dioxus::prelude::*;
+
+mod custom_elements {
+    dioxus::html::custom_elements! {
+        /// A color picker web component
+        color_picker "color-picker" {
+            value: String,
+            mode: OnOff,
+            swatch_count: String "swatch-count",
+        } events {
+            oncolorchange: FormData,
+        };
+    }
+}
+
+#[test]
+fn declared_custom_elements() {
+    fn app() -> Element {
+        let count = 8;
+        rsx! {
+            color-picker {
+                class: "picker",
+                mode: "on",
+                swatch_count: "{count}",
+                width: "10px",
+                oncolorchange: move |event| println!("{}", event.value()),
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<color-picker class="picker" mode="on" swatch-count="8" style="width:10px;"></color-picker>"#
+    );
+}
+
+#[test]
+fn undeclared_custom_elements_accept_any_attribute() {
+    let size = "large";
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            my-widget { size: "small", variant: "{size}" }
+        }),
+        r#"<my-widget size="small" variant="large"></my-widget>"#
+    );
+}
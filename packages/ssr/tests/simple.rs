@@ -35,6 +35,77 @@ fn lists() {
     );
 }
 
+#[test]
+fn while_loops() {
+    let mut items = ["a", "b", "c"].into_iter();
+    let mut countdown = 3;
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            ul {
+                while let Some(item) = items.next() {
+                    li { "item {item}" }
+                }
+            }
+            while countdown > 0 {
+                span {
+                    "{countdown}"
+                    {countdown -= 1; None::<VNode>}
+                }
+            }
+        }),
+        "<ul><li>item a</li><li>item b</li><li>item c</li></ul><span>3</span><span>2</span><span>1</span>"
+    );
+}
+
+#[test]
+fn keyed_fragments() {
+    let items = [("a", "first"), ("b", "second")];
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            dl {
+                for (term, definition) in items {
+                    fragment { key: "{term}",
+                        dt { "{term}" }
+                        dd { "{definition}" }
+                    }
+                }
+            }
+            span { "before" }
+            fragment {
+                "hello "
+                b { "world" }
+            }
+        }),
+        "<dl><dt>a</dt><dd>first</dd><dt>b</dt><dd>second</dd></dl><span>before</span>hello <b>world</b>"
+    );
+}
+
+#[test]
+fn keyed_and_nested_loops() {
+    let rows = [[1, 2], [3, 4]];
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            dl {
+                for (term, definition) in [("a", "first"), ("b", "second")] { key: "{term}",
+                    dt { "{term}" }
+                    dd { "{definition}" }
+                }
+            }
+            ul {
+                for row in rows {
+                    for cell in row {
+                        li { "{cell}" }
+                    }
+                }
+            }
+        }),
+        "<dl><dt>a</dt><dd>first</dd><dt>b</dt><dd>second</dd></dl><ul><li>1</li><li>2</li><li>3</li><li>4</li></ul>"
+    );
+}
+
 #[test]
 fn dynamic() {
     let dynamic = 123;
@@ -71,6 +142,45 @@ fn components() {
     );
 }
 
+#[test]
+fn optional_props() {
+    #[component]
+    fn Counter(label: Option<String>, #[props(default = 1)] step: i32) -> Element {
+        let label = label.unwrap_or_else(|| "count".to_string());
+        rsx! { p { "{label} +{step}" } }
+    }
+
+    let label = Some("clicks".to_string());
+    let step = None;
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            Counter { label?: label.clone(), step? }
+            Counter { label?: None::<String>, step?: Some(5) }
+        }),
+        "<p>clicks +1</p><p>count +5</p>"
+    );
+}
+
+#[test]
+fn renamed_shorthand_props() {
+    #[component]
+    fn Greeting(name: String, excited: bool) -> Element {
+        let end = if excited { "!" } else { "." };
+        rsx! { p { "Hello {name}{end}" } }
+    }
+
+    let user = "Alice".to_string();
+    let excited = true;
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            Greeting { user as name, excited }
+        }),
+        "<p>Hello Alice!</p>"
+    );
+}
+
 #[test]
 fn fragments() {
     assert_eq!(
@@ -84,3 +194,113 @@ fn fragments() {
         "<div></div>"
     );
 }
+
+#[test]
+fn html_entities() {
+    let name = "dioxus";
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { "Tom&nbsp;&amp;&nbsp;Jerry" }
+            p { "&copy; {name} &lt;3" }
+        }),
+        "<div>Tom\u{a0}&amp;\u{a0}Jerry</div><p>\u{a9} dioxus &lt;3</p>"
+    );
+}
+
+#[test]
+fn conditional_attribute_groups() {
+    fn render(selected: bool) -> String {
+        dioxus_ssr::render_element(rsx! {
+            div {
+                class: "item",
+                if selected { class: "active", "data-selected": "true" },
+                "hello"
+            }
+        })
+    }
+
+    assert_eq!(
+        render(true),
+        "<div class=\"item active\" data-selected=\"true\">hello</div>"
+    );
+    assert_eq!(render(false), "<div class=\"item\">hello</div>");
+
+    // The condition is only evaluated once for the whole group
+    let checks = std::cell::Cell::new(0);
+    let selected = || {
+        checks.set(checks.get() + 1);
+        true
+    };
+    let highlighted = false;
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div {
+                if selected() { class: "active", title: "selected", "data-highlighted": if highlighted { "true" } }
+            }
+        }),
+        "<div class=\"active\" title=\"selected\"></div>"
+    );
+    assert_eq!(checks.get(), 1);
+}
+
+#[test]
+fn spread_iterators() {
+    let data = std::collections::BTreeMap::from([("data-id", "1"), ("data-kind", "primary")]);
+    let extra = vec![Attribute::new("title", "hello", None, false)];
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { class: "item", ..data, ..extra.into_iter().filter(|_| true) }
+        }),
+        "<div class=\"item\" data-id=\"1\" data-kind=\"primary\" title=\"hello\"></div>"
+    );
+
+    // The item type of an empty spread is inferred from the element
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { class: "item", ..vec![] }
+        }),
+        "<div class=\"item\"></div>"
+    );
+}
+
+#[test]
+fn hyphenated_attributes() {
+    let id = 5;
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            button { data-testid: "submit", hx-post: "/items/{id}", aria-label: "Submit", "go" }
+        }),
+        "<button data-testid=\"submit\" hx-post=\"/items/5\" aria-label=\"Submit\">go</button>"
+    );
+}
+
+#[test]
+fn rsx_include() {
+    fn app() -> Element {
+        let title = "Hello";
+        let items = ["a", "b"];
+        let highlighted = true;
+        rsx_include!("tests/templates/card.rsx")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<div class="card"><h1>Hello</h1><p>a</p><p>b</p><span>highlighted</span></div>"#
+    );
+}
+
+#[test]
+fn typed_aria_attributes() {
+    let expanded = false;
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { aria_live: AriaLive::Polite, aria_expanded: expanded, aria_sort: "ascending" }
+        }),
+        r#"<div aria-live="polite" aria-expanded=false aria-sort="ascending"></div>"#
+    );
+}
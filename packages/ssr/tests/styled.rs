@@ -0,0 +1,80 @@
+use dioxus::prelude::*;
+use dioxus_elements::styled::{stylesheet, Theme};
+
+#[test]
+fn styled_classes() {
+    fn app() -> Element {
+        let card = styled!(|t| {
+            background: t.colors.primary;
+            padding: 8px 16px;
+            font-family: "Inter", sans-serif;
+            width: calc(100% - {2 * 8}px);
+            "&:hover" {
+                opacity: 0.8;
+            }
+        });
+        let same = styled!(|t| {
+            background: t.colors.primary;
+            padding: 8px 16px;
+            font-family: "Inter", sans-serif;
+            width: calc(100% - {2 * 8}px);
+            "&:hover" {
+                opacity: 0.8;
+            }
+        });
+        let plain = styled! { margin: 0 auto; };
+
+        assert_eq!(card, same);
+        assert_ne!(card, plain);
+
+        rsx! {
+            div { class: "{card}" }
+            div { class: "{plain}" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let css = stylesheet(&dom);
+    let classes: Vec<_> = css
+        .lines()
+        .filter_map(|line| line.strip_prefix('.'))
+        .filter_map(|line| line.split_once(' '))
+        .map(|(class, _)| class)
+        .collect();
+    let (card, plain) = (classes[0], classes[2]);
+
+    assert_eq!(
+        css,
+        format!(
+            ".{card} {{ background: #2563eb; padding: 8px 16px; font-family: \"Inter\", sans-serif; width: calc(100% - 16px); }}\n\
+             .{card}:hover {{ opacity: 0.8; }}\n\
+             .{plain} {{ margin: 0 auto; }}\n"
+        )
+    );
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        format!(r#"<div class="{card}"></div><div class="{plain}"></div>"#)
+    );
+}
+
+#[test]
+fn styled_custom_theme() {
+    #[derive(Clone, Default)]
+    struct Brand {
+        accent: &'static str,
+    }
+
+    fn app() -> Element {
+        use_context_provider(|| Brand { accent: "tomato" });
+        let class = styled!(|t: &Brand| { color: t.accent; });
+        rsx! { span { class: "{class}" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert!(stylesheet(&dom).ends_with("{ color: tomato; }\n"));
+    assert_eq!(Theme::default().colors.primary, "#2563eb");
+}
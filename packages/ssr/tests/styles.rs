@@ -38,3 +38,30 @@ fn dynamic_styles() {
         r#"<div style="width:123px;"></div>"#
     );
 }
+
+#[test]
+fn merged_style_attributes() {
+    let color = "red";
+    let bold = true;
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { style: "width: 100px", style: "height: 10px" }
+        }),
+        r#"<div style="width: 100px;height: 10px"></div>"#
+    );
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { style: format!("color: {color}"), style: "color: blue" }
+        }),
+        r#"<div style="color: red;color: blue"></div>"#
+    );
+
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { style: if bold { "font-weight: bold" }, style: "color: {color}" }
+        }),
+        r#"<div style="font-weight: bold;color: red"></div>"#
+    );
+}
@@ -0,0 +1,93 @@
+use dioxus::prelude::*;
+use std::future::poll_fn;
+use std::task::Poll;
+
+#[test]
+fn streams_resolved_boundaries() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.stream_suspense = true;
+    let page = renderer.render(&dom);
+    assert_eq!(
+        page,
+        r#"<main><div id="ds-1" style="display:contents">loading</div></main>"#
+    );
+    assert!(renderer.has_pending_boundaries());
+
+    let mut chunk = String::new();
+    while renderer.has_pending_boundaries() {
+        dom.render_immediate(&mut dioxus_core::NoOpMutations);
+        renderer
+            .render_resolved_boundaries(&mut chunk, &dom)
+            .unwrap();
+    }
+
+    assert!(chunk.starts_with("<script>function __dioxus_swap_suspense(id)"));
+    assert!(chunk.ends_with(
+        r#"<template id="ds-1-resolved"><p>ready</p></template><script>__dioxus_swap_suspense(1)</script>"#
+    ));
+}
+
+#[test]
+fn streams_resolved_boundaries_without_inline_scripts() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.stream_suspense = true;
+    renderer.suspense_script_src = Some("/suspense.js".to_string());
+    renderer.render(&dom);
+
+    let mut chunk = String::new();
+    while renderer.has_pending_boundaries() {
+        dom.render_immediate(&mut dioxus_core::NoOpMutations);
+        renderer
+            .render_resolved_boundaries(&mut chunk, &dom)
+            .unwrap();
+    }
+
+    assert_eq!(
+        chunk,
+        r#"<template id="ds-1-resolved"><p>ready</p></template><script src="/suspense.js" data-boundary="1"></script>"#
+    );
+}
+
+fn app() -> Element {
+    rsx! {
+        main {
+            SuspenseBoundary {
+                fallback: rsx! { "loading" },
+                Slow {}
+            }
+        }
+    }
+}
+
+#[component]
+fn Slow() -> Element {
+    let mut ready = use_signal(|| false);
+
+    if !ready() {
+        let task = use_hook(|| {
+            spawn(async move {
+                let mut polled = false;
+                poll_fn(|cx| {
+                    if polled {
+                        Poll::Ready(())
+                    } else {
+                        polled = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+                ready.set(true);
+            })
+        });
+        suspend(task)?;
+    }
+
+    rsx! { p { "ready" } }
+}
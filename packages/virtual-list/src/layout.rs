@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+/// The positions of the items in a virtual list
+///
+/// Items that were measured use their measured size. The other items are estimated with the average size of the
+/// measured items, or with the initial estimate until any item is measured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualLayout {
+    sizes: Vec<Option<f64>>,
+    estimated_size: f64,
+    measured_total: f64,
+    measured_count: usize,
+    /// The offset of every item, followed by the total size of the list
+    offsets: Vec<f64>,
+}
+
+impl VirtualLayout {
+    /// Create a layout for `len` items that are estimated to be `estimated_size` pixels tall
+    pub fn new(len: usize, estimated_size: f64) -> Self {
+        let mut layout = Self {
+            sizes: vec![None; len],
+            estimated_size,
+            measured_total: 0.0,
+            measured_count: 0,
+            offsets: Vec::new(),
+        };
+        layout.update_offsets();
+        layout
+    }
+
+    /// The number of items in the list
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Check if the list has no items
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    /// Change the number of items. The measurements of the items that are kept are kept too.
+    pub fn resize(&mut self, len: usize) {
+        if len == self.len() {
+            return;
+        }
+        for size in self.sizes.drain(len.min(self.sizes.len())..).flatten() {
+            self.measured_total -= size;
+            self.measured_count -= 1;
+        }
+        self.sizes.resize(len, None);
+        self.update_offsets();
+    }
+
+    /// Record the measured size of an item. Returns true if the layout changed.
+    pub fn measure(&mut self, index: usize, size: f64) -> bool {
+        let Some(slot) = self.sizes.get_mut(index) else {
+            return false;
+        };
+        if *slot == Some(size) {
+            return false;
+        }
+        match slot.replace(size) {
+            Some(old) => self.measured_total -= old,
+            None => self.measured_count += 1,
+        }
+        self.measured_total += size;
+        self.update_offsets();
+        true
+    }
+
+    /// The size of an item, measured or estimated
+    pub fn size(&self, index: usize) -> f64 {
+        self.sizes
+            .get(index)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| self.estimate())
+    }
+
+    /// The distance from the top of the list to an item
+    pub fn offset(&self, index: usize) -> f64 {
+        self.offsets[index.min(self.len())]
+    }
+
+    /// The size of the whole list
+    pub fn total_size(&self) -> f64 {
+        self.offsets[self.len()]
+    }
+
+    /// The index of the item at a distance from the top of the list
+    pub fn index_at(&self, offset: f64) -> usize {
+        // The first item that ends after the offset
+        self.offsets[1..]
+            .partition_point(|end| *end <= offset)
+            .min(self.len().saturating_sub(1))
+    }
+
+    /// The items that are visible in a viewport scrolled to `scroll_offset`, with `overscan` extra items on each side
+    pub fn visible_range(
+        &self,
+        scroll_offset: f64,
+        viewport_size: f64,
+        overscan: usize,
+    ) -> Range<usize> {
+        if self.is_empty() {
+            return 0..0;
+        }
+        let scroll_offset = scroll_offset.max(0.0);
+        let first = self.index_at(scroll_offset);
+        let last = self.index_at(scroll_offset + viewport_size.max(0.0));
+        first.saturating_sub(overscan)..(last + overscan + 1).min(self.len())
+    }
+
+    fn estimate(&self) -> f64 {
+        match self.measured_count {
+            0 => self.estimated_size,
+            count => self.measured_total / count as f64,
+        }
+    }
+
+    fn update_offsets(&mut self) {
+        let estimate = self.estimate();
+        self.offsets.clear();
+        self.offsets.reserve(self.sizes.len() + 1);
+        let mut offset = 0.0;
+        self.offsets.push(offset);
+        for size in &self.sizes {
+            offset += size.unwrap_or(estimate);
+            self.offsets.push(offset);
+        }
+    }
+}
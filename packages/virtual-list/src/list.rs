@@ -0,0 +1,120 @@
+use std::rc::Rc;
+
+use dioxus_lib::prelude::*;
+
+use crate::VirtualLayout;
+
+/// A scrollable list that only renders the items in view
+///
+/// Rendering every row of a long list makes every render diff all of them. The virtual list renders the visible items
+/// and a few on each side, positioned where they would be in the full list, so lists with many thousands of items
+/// stay fast.
+///
+/// Items start with the estimated size and are measured once they are mounted. Items that were never rendered are
+/// estimated with the average size of the measured ones. Give the list a fixed height with css, otherwise it grows to
+/// the size of all of its items and nothing scrolls.
+///
+/// The list is unstyled apart from the positioning. The scroll container has the `virtual-list` class, and every
+/// item is wrapped in an element with the `virtual-list-item` class.
+#[component]
+pub fn VirtualList(
+    /// The number of items in the list
+    len: usize,
+
+    /// Render the item at an index
+    render_item: Callback<usize, Element>,
+
+    /// The estimated size of an item in pixels, used until items are measured
+    #[props(default = 32.0)]
+    estimated_item_size: f64,
+
+    /// The height of the list in pixels until it is measured
+    #[props(default = 400.0)]
+    initial_height: f64,
+
+    /// The number of items rendered above and below the visible ones, so they are ready before they scroll into view
+    #[props(default = 4)]
+    overscan: usize,
+
+    /// The sorted indices of headers. The last header above the visible items sticks to the top of the list and has
+    /// the `virtual-list-sticky` class.
+    #[props(default)]
+    sticky_indices: Vec<usize>,
+) -> Element {
+    let mut layout = use_hook(|| CopyValue::new(VirtualLayout::new(len, estimated_item_size)));
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    let mut scroll_top = use_signal(|| 0.0);
+    let mut height = use_signal(|| initial_height);
+    let scope = current_scope_id().expect("the virtual list to be rendering");
+
+    // The layout isn't reactive, so changing it here doesn't render the list again
+    layout.write().resize(len);
+
+    let current = layout.read();
+    let range = current.visible_range(scroll_top(), height(), overscan);
+    let total_size = current.total_size();
+
+    let first_visible = current.index_at(scroll_top());
+    let sticky = sticky_indices
+        .partition_point(|index| *index <= first_visible)
+        .checked_sub(1)
+        .map(|position| sticky_indices[position])
+        .filter(|index| *index < len);
+
+    let items: Vec<_> = range
+        .filter(|index| Some(*index) != sticky)
+        .map(|index| (index, current.offset(index)))
+        .collect();
+    drop(current);
+
+    let measure = move |index: usize, event: MountedEvent| async move {
+        if let Ok(rect) = event.get_client_rect().await {
+            if layout.write().measure(index, rect.height()) {
+                needs_update_any(scope);
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "virtual-list",
+            style: "overflow-y: auto; position: relative;",
+            onmounted: move |event| async move {
+                container.set(Some(event.data()));
+                if let Ok(rect) = event.get_client_rect().await {
+                    height.set(rect.height());
+                }
+            },
+            onscroll: move |_| async move {
+                let Some(element) = container() else {
+                    return;
+                };
+                if let Ok(offset) = element.get_scroll_offset().await {
+                    scroll_top.set(offset.y);
+                }
+            },
+            div {
+                class: "virtual-list-content",
+                style: "position: relative; height: {total_size}px;",
+                if let Some(index) = sticky {
+                    div {
+                        key: "{index}",
+                        class: "virtual-list-item virtual-list-sticky",
+                        style: "position: sticky; top: 0; z-index: 1;",
+                        onmounted: move |event| measure(index, event),
+                        {render_item.call(index)}
+                    }
+                }
+                for (index, offset) in items {
+                    div {
+                        key: "{index}",
+                        class: "virtual-list-item",
+                        style: "position: absolute; top: {offset}px; left: 0; right: 0;",
+                        onmounted: move |event| measure(index, event),
+                        {render_item.call(index)}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use dioxus_virtual_list::VirtualLayout;
+
+#[test]
+fn estimates_items_until_they_are_measured() {
+    let mut layout = VirtualLayout::new(100, 10.0);
+    assert_eq!(layout.total_size(), 1000.0);
+    assert_eq!(layout.offset(5), 50.0);
+
+    assert!(layout.measure(0, 30.0));
+    assert!(!layout.measure(0, 30.0));
+    // Unmeasured items are estimated with the average of the measured ones
+    assert_eq!(layout.size(1), 30.0);
+    assert_eq!(layout.total_size(), 3000.0);
+
+    assert!(layout.measure(1, 10.0));
+    assert_eq!(layout.offset(2), 40.0);
+    assert_eq!(layout.size(2), 20.0);
+}
+
+#[test]
+fn visible_range_covers_the_viewport_and_overscan() {
+    let layout = VirtualLayout::new(10_000, 20.0);
+    assert_eq!(layout.visible_range(0.0, 100.0, 0), 0..6);
+    assert_eq!(layout.visible_range(1000.0, 100.0, 2), 48..58);
+    assert_eq!(layout.visible_range(1_000_000.0, 100.0, 2), 9997..10_000);
+    assert_eq!(layout.index_at(39.0), 1);
+    assert_eq!(layout.index_at(40.0), 2);
+
+    assert_eq!(
+        VirtualLayout::new(0, 20.0).visible_range(0.0, 100.0, 2),
+        0..0
+    );
+}
+
+#[test]
+fn resizing_keeps_the_measured_items() {
+    let mut layout = VirtualLayout::new(3, 10.0);
+    layout.measure(0, 20.0);
+    layout.measure(2, 40.0);
+
+    layout.resize(2);
+    assert_eq!(layout.len(), 2);
+    assert_eq!(layout.size(1), 20.0);
+
+    layout.resize(4);
+    assert_eq!(layout.total_size(), 80.0);
+}
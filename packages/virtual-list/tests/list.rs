@@ -0,0 +1,53 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_virtual_list::prelude::*;
+
+#[test]
+fn only_renders_the_visible_items() {
+    fn app() -> Element {
+        rsx! {
+            VirtualList {
+                len: 10_000,
+                estimated_item_size: 20.0,
+                initial_height: 100.0,
+                overscan: 1,
+                render_item: |index: usize| rsx! { "Row {index}" },
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains("height: 200000px;"));
+    assert_eq!(html.matches("virtual-list-item").count(), 7);
+    assert!(
+        html.contains(r#"style="position: absolute; top: 120px; left: 0; right: 0;">Row 6</div>"#)
+    );
+    assert!(!html.contains("Row 7"));
+}
+
+#[test]
+fn the_header_above_the_visible_items_sticks() {
+    fn app() -> Element {
+        rsx! {
+            VirtualList {
+                len: 100,
+                initial_height: 64.0,
+                overscan: 0,
+                sticky_indices: vec![0, 50],
+                render_item: |index: usize| rsx! { "Row {index}" },
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains(r#"class="virtual-list-item virtual-list-sticky" style="position: sticky; top: 0; z-index: 1;">Row 0</div>"#));
+    assert_eq!(html.matches("Row 0").count(), 1);
+    assert!(html.contains("Row 2"));
+}
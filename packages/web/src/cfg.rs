@@ -1,3 +1,5 @@
+use crate::PanicBehavior;
+
 ///  Configuration for the WebSys renderer for the Dioxus VirtualDOM.
 ///
 /// This struct helps configure the specifics of hydration and render destination for WebSys.
@@ -11,6 +13,8 @@ pub struct Config {
     pub(crate) hydrate: bool,
     pub(crate) root: ConfigRoot,
     pub(crate) default_panic_hook: bool,
+    pub(crate) panic_behavior: PanicBehavior,
+    pub(crate) mutation_budget: Option<usize>,
 }
 
 pub(crate) enum ConfigRoot {
@@ -63,6 +67,29 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Set what the app does after it panics, like reloading the page or showing an error page. By default, the page
+    /// is left as it is.
+    ///
+    /// ```rust, ignore
+    /// let config = Config::new().with_panic_behavior(PanicBehavior::ErrorPage(
+    ///     "<h1>Something went wrong</h1><a href=\"/\">Start over</a>".to_string(),
+    /// ));
+    /// ```
+    pub fn with_panic_behavior(mut self, behavior: PanicBehavior) -> Self {
+        self.panic_behavior = behavior;
+        self
+    }
+
+    /// Spread very large updates over multiple animation frames. Each frame writes about `budget` mutations to the
+    /// page and continues with the rest of the update on the next frame, so the page stays responsive while thousands
+    /// of nodes change. See [`VirtualDom::with_mutation_budget`](dioxus_core::VirtualDom::with_mutation_budget).
+    ///
+    /// By default, every update is rendered in a single frame.
+    pub fn with_mutation_budget(mut self, budget: Option<usize>) -> Self {
+        self.mutation_budget = budget;
+        self
+    }
 }
 
 impl Default for Config {
@@ -71,6 +98,8 @@ impl Default for Config {
             hydrate: false,
             root: ConfigRoot::RootName("main".to_string()),
             default_panic_hook: true,
+            panic_behavior: PanicBehavior::default(),
+            mutation_budget: None,
         }
     }
 }
@@ -12,7 +12,7 @@ use dioxus_interpreter_js::unified_bindings::Interpreter;
 use futures_channel::mpsc;
 use rustc_hash::FxHashMap;
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::{Document, Element, Event};
+use web_sys::{Document, Element, Event, Node};
 
 use crate::{load_document, virtual_event_from_websys_event, Config, WebEventConverter};
 
@@ -29,6 +29,29 @@ pub struct WebsysDom {
 
     #[cfg(feature = "mounted")]
     pub(crate) queued_mounted_events: Vec<ElementId>,
+
+    #[cfg(feature = "mounted")]
+    pub(crate) queued_resize_observers: Vec<ElementId>,
+
+    #[cfg(feature = "mounted")]
+    pub(crate) resize_observers: FxHashMap<ElementId, ResizeObserverHandle>,
+
+    // The elements an inspector highlighted and the outline they had before
+    pub(crate) highlighted: Vec<(web_sys::HtmlElement, String)>,
+}
+
+/// A resize observer watching a single element. The observer is disconnected when the handle is dropped.
+#[cfg(feature = "mounted")]
+pub(crate) struct ResizeObserverHandle {
+    pub(crate) observer: web_sys::ResizeObserver,
+    pub(crate) _callback: Closure<dyn FnMut(js_sys::Array, web_sys::ResizeObserver)>,
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for ResizeObserverHandle {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
 }
 
 pub struct UiEvent {
@@ -70,7 +93,13 @@ impl WebsysDom {
 
         let handler: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new({
             let event_channel = event_channel.clone();
+            let root = root.clone();
             move |event: &web_sys::Event| {
+                // Another dioxus app mounted inside this one handles its own events
+                if !event_in_app(event, &root) {
+                    return;
+                }
+
                 let name = event.type_();
                 let element = walk_event_for_id(event);
                 let bubbles = event.bubbles();
@@ -131,6 +160,11 @@ impl WebsysDom {
             event_channel,
             #[cfg(feature = "mounted")]
             queued_mounted_events: Default::default(),
+            #[cfg(feature = "mounted")]
+            queued_resize_observers: Default::default(),
+            #[cfg(feature = "mounted")]
+            resize_observers: Default::default(),
+            highlighted: Default::default(),
         }
     }
 
@@ -139,6 +173,33 @@ impl WebsysDom {
     }
 }
 
+/// The attribute that marks the root element of a dioxus app mounted inside another one, like a remote component.
+/// The ids of the elements in it belong to that app, so the apps around it ignore events from inside of it.
+pub const APP_ROOT_ATTRIBUTE: &str = "data-dioxus-app-root";
+
+/// Check if the closest app root around the target of the event is the root of this app
+fn event_in_app(event: &web_sys::Event, root: &Element) -> bool {
+    let Some(target) = event
+        .target()
+        .and_then(|target| target.dyn_into::<Node>().ok())
+    else {
+        return true;
+    };
+    let element = match target.dyn_into::<Element>() {
+        Ok(element) => Some(element),
+        Err(node) => node.parent_element(),
+    };
+    match element.and_then(|element| {
+        element
+            .closest(&format!("[{APP_ROOT_ATTRIBUTE}]"))
+            .ok()
+            .flatten()
+    }) {
+        Some(app_root) => app_root.is_same_node(Some(root)),
+        None => true,
+    }
+}
+
 fn walk_event_for_id(event: &web_sys::Event) -> Option<(ElementId, web_sys::Element)> {
     let target = event
         .target()
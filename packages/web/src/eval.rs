@@ -1,7 +1,6 @@
 use dioxus_html::prelude::{EvalError, EvalProvider, Evaluator};
 use dioxus_interpreter_js::eval::{JSOwner, WeakDioxusChannel, WebDioxusChannel};
 use generational_box::{AnyStorage, GenerationalBox, UnsyncStorage};
-use js_sys::Function;
 use serde_json::Value;
 use std::future::Future;
 use std::pin::Pin;
@@ -23,6 +22,7 @@ impl EvalProvider for WebEvalProvider {
 }
 
 /// Required to avoid blocking the Rust WASM thread.
+#[cfg(not(feature = "strict_csp"))]
 const PROMISE_WRAPPER: &str = r#"
     return new Promise(async (resolve, _reject) => {
         {JS_CODE}
@@ -52,11 +52,7 @@ impl WebEvaluator {
         // The Rust side of the channel is a weak reference to the DioxusChannel
         let weak_channels = channels.weak();
 
-        // Wrap the evaluated JS in a promise so that wasm can continue running (send/receive data from js)
-        let code = PROMISE_WRAPPER.replace("{JS_CODE}", &js);
-
-        let result = match Function::new_with_args("dioxus", &code).call1(&JsValue::NULL, &channels)
-        {
+        let result = match run_js(&js, &channels) {
             Ok(result) => {
                 if let Ok(stringified) = js_sys::JSON::stringify(&result) {
                     if !stringified.is_undefined() && stringified.is_valid_utf16() {
@@ -75,9 +71,7 @@ impl WebEvaluator {
                     ))
                 }
             }
-            Err(err) => Err(EvalError::InvalidJs(
-                err.as_string().unwrap_or("unknown".to_string()),
-            )),
+            Err(err) => Err(err),
         };
 
         generational_box.set(Box::new(Self {
@@ -90,6 +84,41 @@ impl WebEvaluator {
     }
 }
 
+/// Create a function from the JavaScript and call it with the channel
+///
+/// Creating the function is `eval` as far as the Content Security Policy of the page is concerned. When the policy
+/// doesn't allow it, the error is returned instead of throwing through wasm.
+#[cfg(not(feature = "strict_csp"))]
+fn run_js(js: &str, channels: &WebDioxusChannel) -> Result<JsValue, EvalError> {
+    // Wrap the evaluated JS in a promise so that wasm can continue running (send/receive data from js)
+    let code = PROMISE_WRAPPER.replace("{JS_CODE}", js);
+
+    let constructor: js_sys::Function = js_sys::Reflect::get(&js_sys::global(), &"Function".into())
+        .map_err(|_| EvalError::Unsupported)?
+        .unchecked_into();
+    let args = js_sys::Array::of2(&"dioxus".into(), &code.into());
+    let function: js_sys::Function = js_sys::Reflect::construct(&constructor, &args)
+        .map_err(|err| match err.is_instance_of::<js_sys::EvalError>() {
+            true => EvalError::Blocked,
+            false => invalid_js(err),
+        })?
+        .unchecked_into();
+
+    function.call1(&JsValue::NULL, channels).map_err(invalid_js)
+}
+
+/// Apps built for a strict Content Security Policy never create code at runtime
+#[cfg(feature = "strict_csp")]
+fn run_js(_js: &str, _channels: &WebDioxusChannel) -> Result<JsValue, EvalError> {
+    tracing::error!("Eval is disabled because dioxus-web was built with the `strict_csp` feature");
+    Err(EvalError::Blocked)
+}
+
+#[cfg(not(feature = "strict_csp"))]
+fn invalid_js(err: JsValue) -> EvalError {
+    EvalError::InvalidJs(err.as_string().unwrap_or("unknown".to_string()))
+}
+
 impl Evaluator for WebEvaluator {
     /// Runs the evaluated JavaScript.
     fn poll_join(
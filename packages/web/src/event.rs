@@ -112,6 +112,27 @@ impl HtmlEventConverter for WebEventConverter {
         downcast_event(event).raw.clone().into()
     }
 
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn convert_resized_data(
+        &self,
+        event: &dioxus_html::PlatformEventData,
+    ) -> dioxus_html::ResizedData {
+        #[cfg(feature = "mounted")]
+        {
+            dioxus_html::ResizedData::new(
+                event
+                    .downcast::<web_sys::ResizeObserverEntry>()
+                    .expect("event should be a web_sys::ResizeObserverEntry")
+                    .clone(),
+            )
+        }
+        #[cfg(not(feature = "mounted"))]
+        {
+            panic!("resized events are not supported without the mounted feature on the dioxus-web crate enabled")
+        }
+    }
+
     #[inline(always)]
     fn convert_scroll_data(
         &self,
@@ -247,6 +268,14 @@ impl WebEventExt<web_sys::PointerEvent> for dioxus_html::PointerData {
     }
 }
 
+#[cfg(feature = "mounted")]
+impl WebEventExt<web_sys::ResizeObserverEntry> for dioxus_html::ResizedData {
+    fn web_event(&self) -> &web_sys::ResizeObserverEntry {
+        self.downcast::<web_sys::ResizeObserverEntry>()
+            .expect("event should be a web_sys::ResizeObserverEntry")
+    }
+}
+
 impl WebEventExt<web_sys::Event> for ScrollData {
     fn web_event(&self) -> &web_sys::Event {
         self.downcast::<web_sys::Event>()
@@ -384,6 +413,12 @@ impl HasFormData for WebFormData {
         .expect("only an InputElement or TextAreaElement or an element with contenteditable=true can have an oninput event listener")
     }
 
+    fn input_type(&self) -> Option<String> {
+        self.element
+            .dyn_ref::<web_sys::HtmlInputElement>()
+            .map(|input| input.type_())
+    }
+
     fn values(&self) -> HashMap<String, FormValue> {
         let mut values = HashMap::new();
 
@@ -455,6 +490,10 @@ impl WebDragData {
 }
 
 impl HasDragData for WebDragData {
+    fn data_transfer(&self) -> dioxus_html::DataTransfer {
+        HasDragData::data_transfer(&self.raw)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         &self.raw as &dyn std::any::Any
     }
@@ -2,10 +2,56 @@
 
 use futures_channel::mpsc::UnboundedReceiver;
 
-use dioxus_core::Template;
+use dioxus_core::{HotLiterals, HotReloadError, Template, VirtualDom, VirtualDomSnapshot};
 use web_sys::Element;
 
-pub(crate) fn init() -> UnboundedReceiver<Template> {
+/// The session storage key the state of the virtual dom is saved under while the app is rebuilt
+const SAVED_STATE_KEY: &str = "dioxus-hot-reload-state";
+
+/// A change the hot reloading server sent
+pub(crate) enum HotReloadUpdate {
+    Template(Template),
+    Literals(HotLiterals),
+    /// The last change couldn't be hot reloaded, or `None` once a later change was
+    Error(Option<HotReloadError>),
+    /// The app is about to be rebuilt and the page will reload when it is done
+    SaveState,
+}
+
+impl HotReloadUpdate {
+    pub(crate) fn apply(self, dom: &mut VirtualDom) {
+        match self {
+            HotReloadUpdate::Template(template) => dom.replace_template(template),
+            HotReloadUpdate::Literals(literals) => dom.update_hot_literals(literals),
+            HotReloadUpdate::Error(error) => dom.set_hot_reload_error(error),
+            HotReloadUpdate::SaveState => save_state(dom),
+        }
+    }
+}
+
+/// Save the state of the virtual dom in the session storage of the page so the new build can restore it
+fn save_state(dom: &VirtualDom) {
+    let Some(storage) = session_storage() else {
+        return;
+    };
+    if let Ok(state) = dom.serialize_state() {
+        _ = storage.set_item(SAVED_STATE_KEY, &state);
+    }
+}
+
+/// Take the state the previous build saved before the app was rebuilt. The state is only restored once
+pub(crate) fn take_saved_state() -> Option<VirtualDomSnapshot> {
+    let storage = session_storage()?;
+    let state = storage.get_item(SAVED_STATE_KEY).ok()??;
+    _ = storage.remove_item(SAVED_STATE_KEY);
+    serde_json::from_str(&state).ok()
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok()?
+}
+
+pub(crate) fn init() -> UnboundedReceiver<HotReloadUpdate> {
     use wasm_bindgen::closure::Closure;
     use wasm_bindgen::JsCast;
     use web_sys::{MessageEvent, WebSocket};
@@ -33,28 +79,27 @@ pub(crate) fn init() -> UnboundedReceiver<Template> {
         if let Ok(text) = e.data().dyn_into::<js_sys::JsString>() {
             let string: String = text.into();
 
-            if let Ok(val) = serde_json::from_str::<serde_json::Value>(&string) {
+            if let Some(literals) = string.strip_prefix("hot-literals: ") {
+                // leak the value since the literals borrow their strings for the rest of the program
+                let val: &'static serde_json::Value =
+                    Box::leak(Box::new(serde_json::from_str(literals).unwrap()));
+                let literals = HotLiterals::deserialize(val).unwrap();
+                tx.unbounded_send(HotReloadUpdate::Literals(literals))
+                    .unwrap();
+            } else if let Some(error) = string.strip_prefix("hot-reload-error: ") {
+                if let Ok(error) = serde_json::from_str(error) {
+                    tx.unbounded_send(HotReloadUpdate::Error(error)).unwrap();
+                }
+            } else if let Ok(val) = serde_json::from_str::<serde_json::Value>(&string) {
                 // leak the value
                 let val: &'static serde_json::Value = Box::leak(Box::new(val));
                 let template: Template = Template::deserialize(val).unwrap();
-                tx.unbounded_send(template).unwrap();
-            } else {
-                // it might be triggering a reload of assets
-                // invalidate all the stylesheets on the page
-                let links = web_sys::window()
-                    .unwrap()
-                    .document()
-                    .unwrap()
-                    .query_selector_all("link[rel=stylesheet]")
+                tx.unbounded_send(HotReloadUpdate::Template(template))
                     .unwrap();
-
-                let noise = js_sys::Math::random();
-
-                for x in 0..links.length() {
-                    let link: Element = links.get(x).unwrap().unchecked_into();
-                    let href = link.get_attribute("href").unwrap();
-                    _ = link.set_attribute("href", &format!("{}?{}", href, noise));
-                }
+            } else if let Some(path) = string.strip_prefix("reload-asset: ") {
+                update_asset(path);
+            } else if string == "save-state" {
+                tx.unbounded_send(HotReloadUpdate::SaveState).unwrap();
             }
         }
     }) as Box<dyn FnMut(MessageEvent)>);
@@ -64,3 +109,92 @@ pub(crate) fn init() -> UnboundedReceiver<Template> {
 
     rx
 }
+
+/// Load a hot reloaded asset again without reloading the page. Elements are matched to the asset by file name
+fn update_asset(path: &str) {
+    use wasm_bindgen::JsCast;
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let noise = js_sys::Math::random();
+
+    let matches = |url: &str| {
+        url.split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .rsplit('/')
+            .next()
+            == Some(name)
+    };
+    let bust = |url: &str| format!("{}?{noise}", url.split('?').next().unwrap_or_default());
+    let elements = |selector: &str| {
+        let nodes = document.query_selector_all(selector).unwrap();
+        (0..nodes.length())
+            .filter_map(|idx| nodes.get(idx))
+            .map(|node| node.unchecked_into::<Element>())
+            .collect::<Vec<_>>()
+    };
+    let reload = |element: &Element, attribute: &str| {
+        if let Some(url) = element.get_attribute(attribute) {
+            _ = element.set_attribute(attribute, &bust(&url));
+        }
+    };
+
+    if name.ends_with(".css") {
+        let stylesheets = elements("link[rel=stylesheet]");
+        let matching = stylesheets
+            .iter()
+            .filter(|link| {
+                link.get_attribute("href")
+                    .is_some_and(|href| matches(&href))
+            })
+            .collect::<Vec<_>>();
+
+        // Stylesheets the page didn't link directly (like @import) can only be found by reloading all of them
+        match matching.is_empty() {
+            true => stylesheets.iter().for_each(|link| reload(link, "href")),
+            false => matching.into_iter().for_each(|link| reload(link, "href")),
+        }
+        return;
+    }
+
+    if name.ends_with(".js") {
+        // Scripts only run when they are added to the page, so replace them with a copy that loads the new file
+        for script in elements("script[src]") {
+            if !script.get_attribute("src").is_some_and(|src| matches(&src)) {
+                continue;
+            }
+            // A cloned script is marked as already started, so build a new one
+            let Ok(copy) = document.create_element("script") else {
+                continue;
+            };
+            for name in script.get_attribute_names().iter() {
+                let name = name.as_string().unwrap_or_default();
+                if let Some(value) = script.get_attribute(&name) {
+                    _ = copy.set_attribute(&name, &value);
+                }
+            }
+            reload(&copy, "src");
+            _ = script.replace_with_with_node_1(&copy);
+        }
+        return;
+    }
+
+    // Images and other media are loaded again with a new url that skips the cache
+    for element in elements("[src]") {
+        if element
+            .get_attribute("src")
+            .is_some_and(|src| matches(&src))
+        {
+            reload(&element, "src");
+        }
+    }
+    for link in elements("link[rel~=icon]") {
+        if link
+            .get_attribute("href")
+            .is_some_and(|href| matches(&href))
+        {
+            reload(&link, "href");
+        }
+    }
+}
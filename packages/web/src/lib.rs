@@ -23,8 +23,10 @@
 use std::rc::Rc;
 
 pub use crate::cfg::Config;
+pub use crate::dom::APP_ROOT_ATTRIBUTE;
 #[cfg(feature = "file_engine")]
 pub use crate::file_engine::WebFileEngineExt;
+pub use crate::panic::PanicBehavior;
 use dioxus_core::VirtualDom;
 use futures_util::{pin_mut, select, FutureExt, StreamExt};
 
@@ -34,6 +36,8 @@ mod dom;
 mod event;
 pub mod launch;
 mod mutations;
+mod panic;
+mod reduced_motion;
 pub use event::*;
 
 #[cfg(feature = "eval")]
@@ -48,6 +52,9 @@ mod hot_reload;
 #[cfg(feature = "hydrate")]
 mod rehydrate;
 
+#[cfg(feature = "threads")]
+pub mod threads;
+
 /// Runs the app as a future that can be scheduled around the main thread.
 ///
 /// Polls futures internal to the VirtualDOM, hence the async nature of this function.
@@ -61,11 +68,16 @@ mod rehydrate;
 pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
     tracing::info!("Starting up");
 
-    let mut dom = virtual_dom;
+    let mut dom = match web_config.mutation_budget {
+        Some(budget) => virtual_dom.with_mutation_budget(Some(budget)),
+        None => virtual_dom,
+    };
 
     #[cfg(feature = "eval")]
     dom.in_runtime(eval::init_eval);
 
+    dom.in_runtime(reduced_motion::init_reduced_motion);
+
     #[cfg(feature = "panic_hook")]
     if web_config.default_panic_hook {
         console_error_panic_hook::set_once();
@@ -78,7 +90,9 @@ pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
 
     let should_hydrate = web_config.hydrate;
 
+    let panic_behavior = web_config.panic_behavior.clone();
     let mut websys_dom = dom::WebsysDom::new(web_config, tx);
+    panic::set_panic_behavior(panic_behavior, &websys_dom.root);
 
     tracing::info!("rebuilding app");
 
@@ -98,6 +112,13 @@ pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
             }
         }
     } else {
+        // Pick up the state the previous build saved before `dx serve` rebuilt the app. Hydrated apps render what the
+        // server rendered instead
+        #[cfg(all(feature = "hot_reload", debug_assertions))]
+        if let Some(snapshot) = hot_reload::take_saved_state() {
+            dom.restore(snapshot);
+        }
+
         dom.rebuild(&mut websys_dom);
 
         websys_dom.flush_edits();
@@ -109,8 +130,24 @@ pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
     loop {
         // if virtual dom has nothing, wait for it to have something before requesting idle time
         // if there is work then this future resolves immediately.
-        let (mut res, template) = {
-            let work = dom.wait_for_work().fuse();
+        let (mut res, hot_reload_update, idle) = {
+            // If there are preregistered templates, the browser being idle counts as work too
+            let preregistered = dom.has_preregistered_templates();
+            let work = async {
+                let work = dom.wait_for_work().fuse();
+                pin_mut!(work);
+                if !preregistered {
+                    work.await;
+                    return None;
+                }
+                let idle = wait_for_idle().fuse();
+                pin_mut!(idle);
+                select! {
+                    _ = work => None,
+                    idle = idle => Some(idle),
+                }
+            }
+            .fuse();
             pin_mut!(work);
 
             let mut rx_next = rx.select_next_some();
@@ -119,22 +156,36 @@ pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
             {
                 let mut hot_reload_next = hotreload_rx.select_next_some();
                 select! {
-                    _ = work => (None, None),
-                    new_template = hot_reload_next => (None, Some(new_template)),
-                    evt = rx_next => (Some(evt), None),
+                    idle = work => (None, None, idle),
+                    update = hot_reload_next => (None, Some(update), None),
+                    evt = rx_next => (Some(evt), None, None),
                 }
             }
 
             #[cfg(not(all(feature = "hot_reload", debug_assertions)))]
             select! {
-                _ = work => (None, None),
-                evt = rx_next => (Some(evt), None),
+                idle = work => (None, (), idle),
+                evt = rx_next => (Some(evt), (), None),
             }
         };
 
-        if let Some(template) = template {
-            dom.replace_template(template);
+        // Create the dom of the preregistered templates while the browser has nothing else to do, so the first render
+        // that uses them doesn't have to
+        if let Some(idle) = idle {
+            let mut registered = 0;
+            while idle.has_time(registered) && dom.register_preregistered_template(&mut websys_dom)
+            {
+                registered += 1;
+            }
+            continue;
+        }
+
+        #[cfg(all(feature = "hot_reload", debug_assertions))]
+        if let Some(update) = hot_reload_update {
+            update.apply(&mut dom);
         }
+        #[cfg(not(all(feature = "hot_reload", debug_assertions)))]
+        let () = hot_reload_update;
 
         // Dequeue all of the events from the channel in send order
         // todo: we should re-order these if possible
@@ -166,5 +217,72 @@ pub async fn run(virtual_dom: VirtualDom, web_config: Config) {
         // work_loop.wait_for_raf().await;
 
         websys_dom.flush_edits();
+
+        if dom.has_dirty_scopes() {
+            // Let the browser paint the part of the update that fit in the mutation budget before rendering the rest
+            wait_for_animation_frame().await;
+        } else if dom.has_pending_transitions() {
+            // Let the browser handle new input before the next transition renders
+            yield_to_event_loop().await;
+        }
     }
 }
+
+/// A period when the browser has nothing else to do
+struct IdlePeriod(Option<web_sys::IdleDeadline>);
+
+impl IdlePeriod {
+    /// The number of templates registered in one idle period when the browser doesn't say how long it lasts
+    const FALLBACK_BATCH: usize = 8;
+
+    /// Check if there is time left to register another template
+    fn has_time(&self, registered: usize) -> bool {
+        match &self.0 {
+            Some(deadline) => deadline.time_remaining() > 1.0,
+            None => registered < Self::FALLBACK_BATCH,
+        }
+    }
+}
+
+/// Wait until the browser is idle. Browsers without `requestIdleCallback` wait for a new task of the event loop instead.
+async fn wait_for_idle() -> IdlePeriod {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let scheduled = web_sys::window().is_some_and(|window| {
+            window.request_idle_callback(&resolve).is_ok()
+                || window.set_timeout_with_callback(&resolve).is_ok()
+        });
+        if !scheduled {
+            _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        }
+    });
+    let deadline = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .ok()
+        .filter(|deadline| !deadline.is_undefined() && !deadline.is_null())
+        .map(wasm_bindgen::JsCast::unchecked_into);
+    IdlePeriod(deadline)
+}
+
+/// Wait for the browser to start rendering the next frame
+async fn wait_for_animation_frame() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let scheduled = web_sys::window()
+            .is_some_and(|window| window.request_animation_frame(&resolve).is_ok());
+        if !scheduled {
+            _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        }
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Wait for a new task of the event loop. Events the browser queued in the meantime are dispatched first.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let scheduled = web_sys::window()
+            .is_some_and(|window| window.set_timeout_with_callback(&resolve).is_ok());
+        if !scheduled {
+            _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        }
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
@@ -60,6 +60,9 @@ impl WebsysDom {
         // Now that we've flushed the edits and the dom nodes exist, we can send the mounted events.
         #[cfg(feature = "mounted")]
         self.flush_queued_mounted_events();
+
+        #[cfg(feature = "mounted")]
+        self.flush_queued_resize_observers();
     }
 
     #[cfg(feature = "mounted")]
@@ -77,6 +80,56 @@ impl WebsysDom {
         }
     }
 
+    #[cfg(feature = "mounted")]
+    fn flush_queued_resize_observers(&mut self) {
+        use crate::dom::ResizeObserverHandle;
+        use wasm_bindgen::closure::Closure;
+
+        for id in std::mem::take(&mut self.queued_resize_observers) {
+            let node = self.interpreter.base().get_node(id.0 as u32);
+            let Some(element) = node.dyn_ref::<web_sys::Element>() else {
+                continue;
+            };
+            let event_channel = self.event_channel.clone();
+            let callback: Closure<dyn FnMut(js_sys::Array, web_sys::ResizeObserver)> = Closure::new(
+                move |entries: js_sys::Array, observer: web_sys::ResizeObserver| {
+                    for entry in entries.iter() {
+                        let entry: web_sys::ResizeObserverEntry = entry.unchecked_into();
+                        // The element was removed without removing the listener, like when the whole app is dropped
+                        if !entry.target().is_connected() {
+                            observer.disconnect();
+                            return;
+                        }
+                        let _ = event_channel.unbounded_send(UiEvent {
+                            name: "resized".to_string(),
+                            bubbles: false,
+                            element: id,
+                            data: PlatformEventData::new(Box::new(entry)),
+                        });
+                    }
+                },
+            );
+            let Ok(observer) = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref())
+            else {
+                continue;
+            };
+            observer.observe(element);
+            // Replacing the handle disconnects the old observer for the same element
+            self.resize_observers.insert(
+                id,
+                ResizeObserverHandle {
+                    observer,
+                    _callback: callback,
+                },
+            );
+        }
+    }
+
+    #[cfg(feature = "mounted")]
+    pub(crate) fn queue_resize_observer(&mut self, id: ElementId) {
+        self.queued_resize_observers.push(id);
+    }
+
     #[cfg(feature = "mounted")]
     pub(crate) fn send_mount_event(&mut self, id: ElementId) {
         self.queued_mounted_events.push(id);
@@ -101,6 +154,10 @@ impl WriteMutations for WebsysDom {
         self.interpreter.append_children(id.0 as u32, m as u16)
     }
 
+    fn append_to_portal(&mut self, target: &str, m: usize) {
+        self.interpreter.append_to_portal(target, m as u16)
+    }
+
     fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
         self.interpreter
             .assign_id(path.as_ptr() as u32, path.len() as u8, id.0 as u32)
@@ -153,21 +210,25 @@ impl WriteMutations for WebsysDom {
         match value {
             AttributeValue::Text(txt) => {
                 self.interpreter
-                    .set_attribute(id.0 as u32, name, txt, ns.unwrap_or_default())
+                    .set_attribute_value(id.0 as u32, name, txt, ns.unwrap_or_default())
             }
-            AttributeValue::Float(f) => self.interpreter.set_attribute(
+            AttributeValue::Float(f) => self.interpreter.set_attribute_value(
                 id.0 as u32,
                 name,
                 &f.to_string(),
                 ns.unwrap_or_default(),
             ),
-            AttributeValue::Int(n) => self.interpreter.set_attribute(
+            AttributeValue::Int(n) => self.interpreter.set_attribute_value(
                 id.0 as u32,
                 name,
                 &n.to_string(),
                 ns.unwrap_or_default(),
             ),
-            AttributeValue::Bool(b) => self.interpreter.set_attribute(
+            // Boolean values toggle the presence of the attribute unless it expects "true" or "false"
+            AttributeValue::Bool(false) if !dioxus_html::is_enumerated_attribute(name) => self
+                .interpreter
+                .remove_attribute(id.0 as u32, name, ns.unwrap_or_default()),
+            AttributeValue::Bool(b) => self.interpreter.set_attribute_value(
                 id.0 as u32,
                 name,
                 if *b { "true" } else { "false" },
@@ -185,6 +246,18 @@ impl WriteMutations for WebsysDom {
         self.interpreter.set_text(id.0 as u32, value)
     }
 
+    fn replace_node_text(
+        &mut self,
+        start: usize,
+        delete: usize,
+        value: &str,
+        _: &str,
+        id: ElementId,
+    ) {
+        self.interpreter
+            .replace_text(id.0 as u32, start as u32, delete as u32, value)
+    }
+
     fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
         // mounted events are fired immediately after the element is mounted.
         if name == "mounted" {
@@ -193,6 +266,13 @@ impl WriteMutations for WebsysDom {
             return;
         }
 
+        // resized events come from a resize observer that is created once the element is in the dom
+        if name == "resized" {
+            #[cfg(feature = "mounted")]
+            self.queue_resize_observer(id);
+            return;
+        }
+
         self.interpreter
             .new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
     }
@@ -202,6 +282,15 @@ impl WriteMutations for WebsysDom {
             return;
         }
 
+        if name == "resized" {
+            #[cfg(feature = "mounted")]
+            {
+                self.queued_resize_observers.retain(|queued| *queued != id);
+                self.resize_observers.remove(&id);
+            }
+            return;
+        }
+
         self.interpreter
             .remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
     }
@@ -213,4 +302,21 @@ impl WriteMutations for WebsysDom {
     fn push_root(&mut self, id: ElementId) {
         self.interpreter.push_root(id.0 as u32)
     }
+
+    fn highlight_elements(&mut self, ids: &[ElementId]) {
+        // Give the elements that were highlighted before their own outline back
+        for (element, outline) in self.highlighted.drain(..) {
+            _ = element.style().set_property("outline", &outline);
+        }
+        for id in ids {
+            let node = self.interpreter.base().get_node(id.0 as u32);
+            let Ok(element) = node.dyn_into::<web_sys::HtmlElement>() else {
+                continue;
+            };
+            let style = element.style();
+            let outline = style.get_property_value("outline").unwrap_or_default();
+            _ = style.set_property("outline", "2px solid #e11d48");
+            self.highlighted.push((element, outline));
+        }
+    }
 }
@@ -0,0 +1,84 @@
+//! What the app does after it panics
+
+use std::cell::RefCell;
+
+/// The session storage key of the time the page was last reloaded after a panic
+const RELOADED_AT_KEY: &str = "dioxus-panic-reload";
+
+/// How long after a reload another panic leaves the page as it is instead of reloading again, in milliseconds
+const RELOAD_LOOP_WINDOW: f64 = 10_000.0;
+
+/// What a web app does after it panics
+///
+/// Apps can't recover from a panic on the web, so they stop responding by default.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub enum PanicBehavior {
+    /// Leave the page as it is. The panic is logged to the console if the default panic hook is enabled.
+    #[default]
+    Log,
+
+    /// Reload the page to start the app over
+    ///
+    /// If the app panics again right after the reload, the page is left as it is instead of reloading in a loop.
+    Reload,
+
+    /// Replace the contents of the root element with an error page
+    ErrorPage(String),
+}
+
+thread_local! {
+    static ERROR_PAGE: RefCell<Option<(web_sys::Element, String)>> = const { RefCell::new(None) };
+}
+
+/// Run the panic behavior after the panic hook that is already set
+pub(crate) fn set_panic_behavior(behavior: PanicBehavior, root: &web_sys::Element) {
+    let reload = match behavior {
+        PanicBehavior::Log => return,
+        PanicBehavior::Reload => true,
+        PanicBehavior::ErrorPage(html) => {
+            ERROR_PAGE.with(|page| *page.borrow_mut() = Some((root.clone(), html)));
+            false
+        }
+    };
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        if reload {
+            reload_page();
+        } else {
+            show_error_page();
+        }
+    }));
+}
+
+fn reload_page() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let storage = window.session_storage().ok().flatten();
+    let now = js_sys::Date::now();
+    let reloaded_recently = storage
+        .as_ref()
+        .and_then(|storage| storage.get_item(RELOADED_AT_KEY).ok().flatten())
+        .and_then(|reloaded_at| reloaded_at.parse::<f64>().ok())
+        .is_some_and(|reloaded_at| now - reloaded_at < RELOAD_LOOP_WINDOW);
+    if reloaded_recently {
+        tracing::error!("The app panicked again right after reloading. Not reloading it again");
+        return;
+    }
+
+    if let Some(storage) = storage {
+        _ = storage.set_item(RELOADED_AT_KEY, &now.to_string());
+    }
+    _ = window.location().reload();
+}
+
+fn show_error_page() {
+    ERROR_PAGE.with(|page| {
+        if let Some((root, html)) = &*page.borrow() {
+            root.set_inner_html(html);
+        }
+    });
+}
@@ -0,0 +1,31 @@
+use dioxus_core::prelude::{Runtime, RuntimeGuard};
+use dioxus_hooks::MotionPreference;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::MediaQueryListEvent;
+
+/// Keep [`MotionPreference`] in sync with the `prefers-reduced-motion` media query
+pub(crate) fn init_reduced_motion() {
+    let Some(query) = web_sys::window()
+        .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok())
+        .flatten()
+    else {
+        return;
+    };
+
+    let mut preference = MotionPreference::current();
+    preference.set_system(query.matches());
+
+    // The browser calls the listener outside of the virtual dom, so enter the runtime again before updating the signal
+    let Some(runtime) = Runtime::current() else {
+        return;
+    };
+    let on_change = Closure::wrap(Box::new(move |event: MediaQueryListEvent| {
+        let _guard = RuntimeGuard::new(runtime.clone());
+        preference.set_system(event.matches());
+    }) as Box<dyn FnMut(MediaQueryListEvent)>);
+
+    _ = query.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+
+    // The listener lives as long as the page
+    on_change.forget();
+}
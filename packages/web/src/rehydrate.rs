@@ -91,6 +91,10 @@ impl WebsysDom {
                                 if attribute.name == "onmounted" {
                                     to_mount.push(id);
                                 }
+                                #[cfg(feature = "mounted")]
+                                if attribute.name == "onresized" {
+                                    self.queue_resize_observer(id);
+                                }
                             }
                         }
                     }
@@ -180,6 +184,8 @@ impl WriteMutations for OnlyWriteTemplates<'_> {
 
     fn append_children(&mut self, _: ElementId, _: usize) {}
 
+    fn append_to_portal(&mut self, _: &str, _: usize) {}
+
     fn assign_node_id(&mut self, _: &'static [u8], _: ElementId) {}
 
     fn create_placeholder(&mut self, _: ElementId) {}
@@ -0,0 +1,148 @@
+//! Run work on a thread pool of web workers that share the memory of the app
+//!
+//! The pool is only available when the app is built with shared memory (the `threads` option of the `[web.app]` section
+//! of `Dioxus.toml`) and the page is cross origin isolated. Otherwise the work runs on the main thread.
+
+use futures_channel::oneshot;
+use std::{future::Future, sync::OnceLock};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = r#"
+export function spawn_worker(module, memory, work) {
+  // The cli writes the worker script next to the wasm-bindgen output, two folders up from this snippet
+  const worker = new Worker(new URL("../../dioxus_worker.js", import.meta.url), { type: "module" });
+  worker.postMessage([module, memory, work]);
+}
+
+export function can_share_memory() {
+  return globalThis.crossOriginIsolated === true && typeof SharedArrayBuffer !== "undefined";
+}
+"#)]
+extern "C" {
+    fn spawn_worker(module: JsValue, memory: JsValue, work: u32);
+    fn can_share_memory() -> bool;
+}
+
+type Work = Box<dyn FnOnce() + Send>;
+
+/// The entry point of the workers in the pool. The worker script calls this with the work the pool gave it.
+#[doc(hidden)]
+#[wasm_bindgen]
+pub fn __dioxus_run_worker(work: u32) {
+    // Safety: the pointer was created from a `Box<Work>` in `init_thread_pool` and is only sent to one worker
+    let work = unsafe { Box::from_raw(work as *mut Work) };
+    work();
+}
+
+/// An error that occurred while starting the thread pool
+#[derive(Debug)]
+pub enum ThreadPoolError {
+    /// The page isn't cross origin isolated or the app wasn't built with shared memory
+    Unavailable,
+
+    /// The global rayon thread pool failed to start. It may have been started already.
+    Build(rayon::ThreadPoolBuildError),
+}
+
+impl std::fmt::Display for ThreadPoolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThreadPoolError::Unavailable => write!(
+                f,
+                "Threads are unavailable. Build the app with shared memory and serve it with the Cross-Origin-Opener-Policy and Cross-Origin-Embedder-Policy headers"
+            ),
+            ThreadPoolError::Build(err) => write!(f, "Failed to start the thread pool: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ThreadPoolError {}
+
+static THREAD_POOL: OnceLock<bool> = OnceLock::new();
+
+// Writes to the state of the app from the pool must reach the scheduler on the main thread. Signals with sync storage
+// and the reactive contexts they notify are shared between threads, so check that they stay `Send + Sync`
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<dioxus_signals::SyncSignal<()>>();
+    assert_send_sync::<dioxus_signals::CopyValue<(), dioxus_signals::SyncStorage>>();
+    assert_send_sync::<dioxus_signals::ReactiveContext>();
+    assert_send_sync::<std::sync::Arc<dyn Fn(dioxus_core::ScopeId) + Send + Sync>>();
+};
+
+/// Start the global rayon thread pool with one web worker per thread
+///
+/// This is called with the number of cores of the device the first time [`spawn_blocking`] runs, so you only need to
+/// call it to pick the number of threads. Rayon must not block the main thread, so use [`rayon::spawn`] or
+/// [`spawn_blocking`] instead of [`rayon::join`] and the parallel iterators outside of the pool.
+pub fn init_thread_pool(num_threads: usize) -> Result<(), ThreadPoolError> {
+    let mut result = None;
+    let running = *THREAD_POOL.get_or_init(|| {
+        let started = start_thread_pool(num_threads);
+        let running = started.is_ok();
+        result = Some(started);
+        running
+    });
+    match result {
+        Some(result) => result,
+        // The pool was started before
+        None if running => Ok(()),
+        None => Err(ThreadPoolError::Unavailable),
+    }
+}
+
+fn start_thread_pool(num_threads: usize) -> Result<(), ThreadPoolError> {
+    if !cfg!(target_feature = "atomics") || !can_share_memory() {
+        return Err(ThreadPoolError::Unavailable);
+    }
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .spawn_handler(|thread| {
+            let work: Work = Box::new(move || thread.run());
+            let work = Box::into_raw(Box::new(work)) as u32;
+            spawn_worker(wasm_bindgen::module(), wasm_bindgen::memory(), work);
+            Ok(())
+        })
+        .build_global()
+        .map_err(ThreadPoolError::Build)
+}
+
+fn thread_pool_running() -> bool {
+    *THREAD_POOL.get_or_init(|| {
+        let num_threads = web_sys::window()
+            .map(|window| window.navigator().hardware_concurrency() as usize)
+            .unwrap_or(1)
+            .max(1);
+        match start_thread_pool(num_threads) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::debug!("Running work on the main thread. {err}");
+                false
+            }
+        }
+    })
+}
+
+/// Run a closure on the thread pool and wait for the result without blocking the main thread
+///
+/// If the thread pool is unavailable, the closure runs on the main thread instead. The closure and its result cross
+/// threads, so they must be [`Send`]. Use a [`SyncSignal`](dioxus_signals::SyncSignal) to share state with the
+/// rest of the app.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let primes = use_resource(|| dioxus_web::threads::spawn_blocking(|| count_primes(10_000_000)));
+/// ```
+pub fn spawn_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> impl Future<Output = T> {
+    let (tx, rx) = oneshot::channel();
+    if thread_pool_running() {
+        rayon::spawn(move || _ = tx.send(f()));
+    } else {
+        _ = tx.send(f());
+    }
+    async move { rx.await.expect("the thread pool to finish the work") }
+}